@@ -0,0 +1,75 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rui::stress::*;
+use rui::*;
+
+const SIZE: [f32; 2] = [1024.0, 768.0];
+
+fn bench_view(c: &mut Criterion, name: &str, view: impl View) {
+    let (mut cx, mut vger) = headless(SIZE.into());
+    let root = ViewID::default();
+
+    c.bench_function(&format!("{} layout", name), |b| {
+        b.iter(|| view.layout(root, SIZE.into(), &mut cx, &mut vger))
+    });
+
+    c.bench_function(&format!("{} draw", name), |b| {
+        b.iter(|| {
+            begin_frame(&mut vger, SIZE.into());
+            view.draw(root, &mut cx, &mut vger)
+        })
+    });
+
+    let event = Event {
+        kind: EventKind::TouchMove { id: 0 },
+        position: [SIZE[0] / 2.0, SIZE[1] / 2.0].into(),
+    };
+    c.bench_function(&format!("{} process", name), |b| {
+        b.iter(|| view.process(&event, root, &mut cx, &mut vger))
+    });
+}
+
+fn deep(c: &mut Criterion) {
+    bench_view(c, "deep nesting", deep_nesting(100));
+}
+
+fn wide(c: &mut Criterion) {
+    bench_view(c, "wide list", wide_list(1000));
+}
+
+fn text(c: &mut Criterion) {
+    bench_view(c, "text heavy", text_heavy(200));
+}
+
+fn texts(c: &mut Criterion) {
+    bench_view(c, "text list", text_list(1000));
+}
+
+fn grid(c: &mut Criterion) {
+    bench_view(c, "rect grid", rect_grid(100));
+
+    // The same, with solid shapes batched by paint.
+    let view = rect_grid(100);
+    let (mut cx, mut vger) = headless(SIZE.into());
+    let root = ViewID::default();
+    view.layout(root, SIZE.into(), &mut cx, &mut vger);
+
+    begin_frame(&mut vger, SIZE.into());
+    let (shapes, batches) = {
+        let mut batched = BatchingRenderer::new(&mut vger);
+        view.draw(root, &mut cx, &mut batched);
+        batched.flush();
+        batched.stats()
+    };
+    println!("rect grid: {} shapes in {} batches", shapes, batches);
+
+    c.bench_function("rect grid draw batched", |b| {
+        b.iter(|| {
+            begin_frame(&mut vger, SIZE.into());
+            let mut batched = BatchingRenderer::new(&mut vger);
+            view.draw(root, &mut cx, &mut batched)
+        })
+    });
+}
+
+criterion_group!(benches, deep, wide, text, texts, grid);
+criterion_main!(benches);
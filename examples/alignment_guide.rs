@@ -0,0 +1,19 @@
+use rui::*;
+
+/// A row which lines up on the colon after its label.
+fn row(label: &str, value: &str) -> impl View {
+    hstack((
+        text(&format!("{}:", label)).alignment_guide(HorizontalAlignment::Center, |d| d.width),
+        text(value).padding(Auto),
+    ))
+    .size([300.0, 30.0])
+}
+
+fn main() {
+    rui(vstack((
+        row("Name", "Ada Lovelace"),
+        row("Occupation", "Mathematician"),
+        row("Born", "1815"),
+    ))
+    .padding(Auto));
+}
@@ -0,0 +1,33 @@
+use rui::*;
+
+#[derive(Clone, Debug, Default)]
+struct Model {
+    todos: Vec<String>,
+}
+
+fn main() {
+    // Made and owned by the app rather than a view, so it can be handed
+    // to anything which needs it.
+    let model = State::<Model>::default();
+    let _saver = model.on_change(|model| println!("saving {:?}", model));
+
+    let (m1, m2, m3) = (model.clone(), model.clone(), model.clone());
+    rui(vstack((
+        state((), move |_| {
+            text(&format!("{} todos", m1.with(|m| m.todos.len()))).padding(Auto)
+        }),
+        hstack((
+            button(text("add"), move || {
+                m2.update(|m| {
+                    let n = m.todos.len() + 1;
+                    m.todos.push(format!("todo {}", n))
+                })
+            })
+            .padding(Auto),
+            button(text("clear"), move || {
+                m3.take();
+            })
+            .padding(Auto),
+        )),
+    )));
+}
@@ -0,0 +1,37 @@
+use rui::*;
+
+/// A gradient standing in for a photo.
+fn photo(i: usize) -> Frame {
+    let (width, height) = (320, 200);
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.extend_from_slice(&[
+                (x * 255 / width) as u8,
+                (y * 255 / height) as u8,
+                (i * 80 % 256) as u8,
+                255,
+            ]);
+        }
+    }
+    Frame::new(width as u32, height as u32, pixels)
+}
+
+fn main() {
+    let tile = |i| {
+        frame_view(FrameSource::load(move || Some(photo(i))))
+            .aspect_ratio(1.0, ContentMode::Fit)
+            .padding(Auto)
+    };
+    rui(vstack((
+        // Square tiles, however wide the window is.
+        hstack((tile(0), tile(1), tile(2))),
+        hstack((
+            text("fixed").fixed_size(),
+            // Cropped to cover a wide rect.
+            frame_view(FrameSource::load(|| Some(photo(3))))
+                .aspect_ratio(4.0, ContentMode::Fill)
+                .size([200.0, 50.0]),
+        )),
+    )));
+}
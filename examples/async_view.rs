@@ -0,0 +1,32 @@
+use rui::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::{thread, time};
+
+fn main() {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    rui(async_view(
+        move || {
+            let attempts = attempts.clone();
+            async move {
+                // Stands in for a request to a server, which fails the
+                // first time.
+                thread::sleep(time::Duration::from_secs(1));
+                if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err("the server didn't respond".to_string())
+                } else {
+                    Ok(vec!["apples", "oranges", "pears"])
+                }
+            }
+        },
+        |state, retry| match state {
+            AsyncState::Loading => any_view(text("Loading...")),
+            AsyncState::Ready(fruit) => any_view(text(&fruit.join(", "))),
+            AsyncState::Error(err) => any_view(vstack((
+                text(&format!("Couldn't load: {}", err)).color(RED_HIGHLIGHT),
+                button(text("Retry"), move || retry.retry()),
+            ))),
+        },
+    )
+    .padding(Auto));
+}
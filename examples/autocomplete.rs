@@ -0,0 +1,49 @@
+use rui::*;
+use std::{thread, time};
+
+const COUNTRIES: &[&str] = &[
+    "Argentina",
+    "Australia",
+    "Austria",
+    "Brazil",
+    "Canada",
+    "Chile",
+    "China",
+    "Denmark",
+    "Egypt",
+    "France",
+    "Germany",
+    "Iceland",
+    "India",
+    "Ireland",
+    "Japan",
+    "Mexico",
+    "New Zealand",
+    "Norway",
+    "Portugal",
+    "South Africa",
+    "Spain",
+    "Sweden",
+    "United Kingdom",
+    "United States",
+];
+
+fn main() {
+    rui(state(String::new(), |country| {
+        vstack((
+            text("Country"),
+            autocomplete(country.clone(), |query| {
+                // Stands in for a request to a server.
+                thread::sleep(time::Duration::from_millis(100));
+                let query = query.to_lowercase();
+                COUNTRIES
+                    .iter()
+                    .filter(|c| c.to_lowercase().contains(&query))
+                    .map(|c| c.to_string())
+                    .collect()
+            }),
+            text(&format!("Selected: {}", country.get())),
+        ))
+        .padding(Auto)
+    }));
+}
@@ -0,0 +1,30 @@
+use rui::*;
+use std::{thread, time};
+
+/// A gradient standing in for a profile picture from the network.
+fn load() -> Option<Frame> {
+    thread::sleep(time::Duration::from_secs(1));
+    let (width, height) = (160, 120);
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.extend_from_slice(&[
+                (x * 255 / width) as u8,
+                (y * 255 / height) as u8,
+                128,
+                255,
+            ]);
+        }
+    }
+    Some(Frame::new(width as u32, height as u32, pixels))
+}
+
+fn main() {
+    rui(hstack((
+        avatar(FrameSource::load(load), "AL").size(AvatarSize::Small),
+        avatar(FrameSource::load(load), "GH"),
+        avatar(FrameSource::load(load), "KJ").size(AvatarSize::Large),
+        avatar(FrameSource::load(|| None), "TB").size(AvatarSize::Large),
+    ))
+    .padding(Auto));
+}
@@ -0,0 +1,13 @@
+use rui::*;
+
+fn main() {
+    rui(state(0, |count| {
+        let unread = count.get() as usize;
+        vstack((
+            button(text("Inbox"), move || count.with_mut(|c| *c += 1))
+                .badge(unread)
+                .padding(Auto),
+            hstack((capsule("new"), capsule("beta").color(GREEN_HIGHLIGHT))).padding(Auto),
+        ))
+    }));
+}
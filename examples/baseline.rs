@@ -0,0 +1,23 @@
+use rui::*;
+
+fn main() {
+    // The small caption sits on the same baseline as the large title.
+    rui(vstack((
+        hstack_aligned(
+            VAlignment::FirstTextBaseline,
+            (
+                text("Title").font_size(36),
+                text("caption").font_size(12),
+                text("Value").font_size(18),
+            ),
+        ),
+        hstack_aligned(
+            VAlignment::LastTextBaseline,
+            (
+                vstack((text("Two line"), text("label"))),
+                text("ends here").font_size(12),
+            ),
+        ),
+    ))
+    .padding(Auto));
+}
@@ -0,0 +1,27 @@
+use rui::*;
+
+#[derive(Clone, Bindable)]
+struct Mixer {
+    volume: f32,
+    pan: f32,
+    muted: bool,
+}
+
+fn main() {
+    rui(state(
+        Mixer {
+            volume: 0.5,
+            pan: 0.5,
+            muted: false,
+        },
+        |mixer| {
+            vstack((
+                text(&format!("volume: {:.2}", mixer.get().volume)).padding(Auto),
+                hslider(mixer.binding().volume()).padding(Auto),
+                text(&format!("pan: {:.2}", mixer.get().pan)).padding(Auto),
+                hslider(mixer.binding().pan()).padding(Auto),
+                toggle(mixer.binding().muted()).padding(Auto),
+            ))
+        },
+    ));
+}
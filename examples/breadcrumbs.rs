@@ -0,0 +1,14 @@
+use rui::*;
+
+fn main() {
+    rui(state(vec!["Home".to_string()], |path| {
+        let depth = path.get().len();
+        let next = path.clone();
+        vstack((
+            stack_breadcrumbs(path.clone()).padding(Auto),
+            button(text("open folder"), move || {
+                next.with_mut(|p| p.push(format!("Folder {}", depth)))
+            }),
+        ))
+    }));
+}
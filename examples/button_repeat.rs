@@ -0,0 +1,19 @@
+use rui::*;
+use std::time::Duration;
+
+fn main() {
+    rui(state(0, |count| {
+        let (up, down) = (count.clone(), count.clone());
+        hstack((
+            button(text("-"), move || down.with_mut(|n| *n -= 1))
+                .repeats(Duration::from_millis(400), Duration::from_millis(80)),
+            text(&format!("{}", count.get())).padding(Auto),
+            button(text("+"), move || up.with_mut(|n| *n += 1))
+                .repeats(Duration::from_millis(400), Duration::from_millis(80)),
+            button(text("reset"), move || count.set(0))
+                .on_press(|| println!("pressed"))
+                .on_release(|| println!("released"))
+                .key_equivalent(KeyPress::Enter),
+        ))
+    }));
+}
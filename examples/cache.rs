@@ -0,0 +1,34 @@
+use rui::*;
+
+fn main() {
+    rui(state(0.0, |position| {
+        state(1.0, move |frequency| {
+            // Thousands of segments, only drawn again when the frequency
+            // changes, not as the cursor moves over them.
+            let plotted = frequency.clone();
+            let plot = canvas(move |rect, vger| {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                let frequency = plotted.get();
+                let y = |x: f32| rect.center().y + (x * frequency * 0.05).sin() * 100.0;
+                let mut x = rect.min_x();
+                while x < rect.max_x() {
+                    vger.stroke_segment([x, y(x)], [x + 0.25, y(x + 0.25)], 1.0, paint);
+                    x += 0.25;
+                }
+            })
+            .cache();
+            vstack((
+                zstack((
+                    plot,
+                    rectangle()
+                        .color(RED_HIGHLIGHT)
+                        .size([2.0, 400.0])
+                        .offset([position.get() * 400.0, 0.0]),
+                ))
+                .size([400.0, 400.0]),
+                hslider(position.clone()),
+                hslider(frequency),
+            ))
+        })
+    }));
+}
@@ -0,0 +1,29 @@
+use rui::*;
+
+#[derive(Clone, Bindable)]
+struct Settings {
+    volume: f32,
+    muted: bool,
+}
+
+fn main() {
+    rui(state(
+        Settings {
+            volume: 0.5,
+            muted: false,
+        },
+        |settings| {
+            vstack((
+                card(text("Changes are saved automatically.")),
+                group_box(
+                    "Audio",
+                    vstack((
+                        hslider(settings.binding().volume()),
+                        toggle(settings.binding().muted()),
+                    )),
+                ),
+            ))
+            .padding(Auto)
+        },
+    ));
+}
@@ -0,0 +1,29 @@
+use rui::*;
+use std::sync::mpsc::channel;
+use std::{thread, time};
+
+fn main() {
+    let (to_device, from_ui) = channel();
+    let (to_ui, from_device) = channel();
+
+    // Stands in for a device with a volume knob: it reports where the knob
+    // is turned to, and follows changes made in the UI.
+    thread::spawn(move || {
+        let mut volume = 0.5f32;
+        loop {
+            thread::sleep(time::Duration::from_secs(2));
+            while let Ok(v) = from_ui.try_recv() {
+                volume = v;
+            }
+            volume = (volume + 0.1) % 1.0;
+            if to_ui.send(volume).is_err() {
+                break;
+            }
+        }
+    });
+
+    let volume = ChannelBinding::from_channel(0.5, to_device, from_device);
+    volume.subscribe(|v| println!("volume is now {:.2}", v));
+
+    rui(vstack((text("Volume"), hslider(volume.clone()), knob(volume))).padding(Auto));
+}
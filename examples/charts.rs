@@ -0,0 +1,20 @@
+use rui::*;
+
+fn main() {
+    rui(state(0, |shift: State<usize>| {
+        let n = shift.get();
+        let sales: Vec<f32> = (0..6).map(|i| ((i + n) % 6) as f32 * 10.0 + 5.0).collect();
+        vstack((
+            line_chart(vec![
+                series("sales", sales.clone()),
+                series("costs", [12.0, 18.0, 15.0, 22.0, 30.0, 28.0]),
+            ])
+            .labels(&["Jan", "Feb", "Mar", "Apr", "May", "Jun"]),
+            hstack((
+                bar_chart(vec![series("sales", sales)]).labels(&["A", "B", "C", "D", "E", "F"]),
+                pie_chart(&[("rent", 1200.0), ("food", 400.0), ("fun", 250.0)]),
+            )),
+            button(text("shift"), move || shift.set(n + 1)),
+        ))
+    }));
+}
@@ -0,0 +1,21 @@
+use rui::*;
+
+fn main() {
+    rui(state(false, |show_palette| {
+        zstack((
+            text("Press Cmd+P to show the command palette").padding(Auto),
+            command_palette(
+                vec![
+                    palette_command("New File", || println!("new file")),
+                    palette_command("Open File", || println!("open file")),
+                    palette_command("Save", || println!("save")),
+                    palette_command("Close Window", || println!("close window")),
+                ],
+                show_palette.clone(),
+            ),
+        ))
+        .command("Command Palette", Some(KeyCode::KeyP), move || {
+            show_palette.set(true)
+        })
+    }));
+}
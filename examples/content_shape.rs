@@ -0,0 +1,23 @@
+use rui::*;
+
+fn main() {
+    rui(zstack((
+        hstack((
+            // The small dot is padded out to a larger target.
+            circle()
+                .size([10.0, 10.0])
+                .padding(17.0)
+                .content_shape(rectangle())
+                .tap(|| println!("dot tapped")),
+            // Only the round part of the square is tappable.
+            rectangle()
+                .size([60.0, 60.0])
+                .content_shape(circle())
+                .tap(|| println!("circle tapped")),
+        )),
+        // The overlay is drawn on top but lets taps through.
+        rectangle()
+            .color(BUTTON_BACKGROUND_COLOR)
+            .hit_test_disabled(),
+    )));
+}
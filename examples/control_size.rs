@@ -0,0 +1,24 @@
+use rui::*;
+
+fn controls(size: ControlSize) -> impl View {
+    state(false, move |on| {
+        hstack((
+            button(text("plain"), || println!("plain")).button_style(ButtonStyle::Plain),
+            button(text("bordered"), || println!("bordered")),
+            button(text("save"), || println!("save")).button_style(ButtonStyle::Prominent),
+            button(text("delete"), || println!("delete")).button_style(ButtonStyle::Destructive),
+            toggle(on),
+        ))
+        .control_size(size)
+        .padding(Auto)
+    })
+}
+
+fn main() {
+    rui(vstack((
+        controls(ControlSize::Mini),
+        controls(ControlSize::Small),
+        controls(ControlSize::Regular),
+        controls(ControlSize::Large),
+    )));
+}
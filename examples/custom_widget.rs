@@ -0,0 +1,73 @@
+use rui::*;
+use std::time::Instant;
+
+/// A switch whose knob slides across when it's clicked, written as a
+/// `Widget` the way a crate outside rui would.
+struct Switch;
+
+#[derive(Clone, Default)]
+struct SwitchState {
+    on: bool,
+    /// When it was last clicked, for the slide.
+    changed: Option<Instant>,
+}
+
+const SLIDE_SECONDS: f32 = 0.15;
+
+impl Widget for Switch {
+    type State = SwitchState;
+
+    fn name(&self) -> &str {
+        "switch"
+    }
+
+    fn layout(&self, constraints: Constraints, _cx: &mut WidgetCx<SwitchState>) -> LocalSize {
+        constraints.constrain([44.0, 24.0].into())
+    }
+
+    fn draw(&self, cx: &mut WidgetCx<SwitchState>, vger: &mut dyn Renderer) {
+        let rect = cx.rect();
+        let state = cx.state().get();
+        let t = state.changed.map_or(1.0, |changed| {
+            (changed.elapsed().as_secs_f32() / SLIDE_SECONDS).min(1.0)
+        });
+        if t < 1.0 {
+            cx.request_frame();
+        }
+        let position = if state.on { t } else { 1.0 - t };
+
+        let track = if state.on {
+            AZURE_HIGHLIGHT
+        } else {
+            BUTTON_BACKGROUND_COLOR
+        };
+        vger.fill_rect(rect, rect.height() / 2.0, Paint::Color(track));
+        let radius = rect.height() / 2.0 - 2.0;
+        let x = rect.min_x() + 2.0 + radius + position * (rect.width() - 4.0 - 2.0 * radius);
+        vger.fill_circle(
+            [x, rect.center().y].into(),
+            radius,
+            Paint::Color(CONTROL_BACKGROUND),
+        );
+    }
+
+    fn event(&self, event: &Event, cx: &mut WidgetCx<SwitchState>) {
+        if let EventKind::TouchBegin { .. } = event.kind {
+            if cx.is_enabled() && cx.rect().contains(event.position) {
+                cx.state().with_mut(|s| {
+                    s.on = !s.on;
+                    s.changed = Some(Instant::now());
+                });
+                cx.consume_event();
+            }
+        }
+    }
+
+    fn role(&self) -> Option<accesskit::Role> {
+        Some(accesskit::Role::Switch)
+    }
+}
+
+fn main() {
+    rui(hstack((text("wifi"), widget(Switch))).padding(Auto));
+}
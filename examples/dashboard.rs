@@ -0,0 +1,33 @@
+use rui::*;
+use std::{thread, time};
+
+fn main() {
+    rui(state(vec![0.0f32], |samples| {
+        let load = samples.with(|s| *s.last().unwrap());
+        let feed = samples.clone();
+        hstack((
+            sparkline(samples.clone()).sparkline_size([160.0, 40.0]),
+            gauge(bind(move || load, |_| ()), 0.0..1.0).color(if load > 0.8 {
+                RED_HIGHLIGHT
+            } else {
+                GREEN_HIGHLIGHT
+            }),
+            button(text("start"), move || {
+                let feed = feed.clone();
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        let value = ((i as f32 * 0.1).sin() + 1.0) / 2.0;
+                        feed.with_mut(|s| {
+                            s.push(value);
+                            if s.len() > 50 {
+                                s.remove(0);
+                            }
+                        });
+                        thread::sleep(time::Duration::from_millis(50));
+                    }
+                });
+            }),
+        ))
+        .padding(Auto)
+    }));
+}
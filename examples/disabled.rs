@@ -0,0 +1,27 @@
+use rui::*;
+
+#[derive(Clone)]
+struct Settings {
+    enabled: bool,
+    volume: f32,
+}
+
+fn main() {
+    rui(state(
+        Settings {
+            enabled: true,
+            volume: 0.5,
+        },
+        |s| {
+            let enabled = s.get().enabled;
+            vstack((
+                hstack((text("sound"), toggle(bind!(s, enabled)))).padding(Auto),
+                vstack((
+                    hslider(bind!(s, volume)).padding(Auto),
+                    button(text("test"), || println!("beep")).padding(Auto),
+                ))
+                .disabled(!enabled),
+            ))
+        },
+    ));
+}
@@ -0,0 +1,13 @@
+use rui::*;
+
+fn main() {
+    rui(vstack((
+        text("top").padding(Auto),
+        divider().inset(20.0),
+        hstack((
+            text("left").padding(Auto),
+            divider(),
+            text("right").padding(Auto),
+        )),
+    )));
+}
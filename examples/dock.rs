@@ -0,0 +1,26 @@
+use rui::*;
+
+fn main() {
+    let layout = DockLayout::new(DockNode::split(
+        DockAxis::Horizontal,
+        0.3,
+        DockNode::tabs(&["files", "search"]),
+        DockNode::split(
+            DockAxis::Vertical,
+            0.7,
+            DockNode::tabs(&["editor"]),
+            DockNode::tabs(&["console", "problems"]),
+        ),
+    ));
+
+    rui(state(layout, |layout| {
+        let saved = layout.clone();
+        vstack((
+            button(text("print layout"), move || {
+                println!("{}", saved.get().save())
+            })
+            .padding(Auto),
+            dock_space(layout, |panel| text(panel).padding(Auto)),
+        ))
+    }));
+}
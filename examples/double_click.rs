@@ -0,0 +1,27 @@
+use rui::*;
+
+fn main() {
+    let files = vec!["notes.txt", "photo.png", "song.mp3"];
+    rui(state(None::<usize>, move |selected| {
+        let files = files.clone();
+        list((0..files.len()).collect(), move |i| {
+            let i = *i;
+            let name = files[i];
+            let selected = selected.clone();
+            let highlighted = selected.get() == Some(i);
+            text(name)
+                .padding(Auto)
+                .background(rectangle().color(if highlighted {
+                    AZURE_HIGHLIGHT_DARK
+                } else {
+                    CLEAR_COLOR
+                }))
+                .tap_with_count(move |count| {
+                    if count == 1 {
+                        selected.set(Some(i));
+                    }
+                })
+                .on_double_click(move || println!("open {}", name))
+        })
+    }))
+}
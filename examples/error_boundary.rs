@@ -0,0 +1,21 @@
+use rui::*;
+
+fn main() {
+    rui(state(0, |count| {
+        let (increment, check) = (count.clone(), count.clone());
+        vstack((
+            button(text("Increment"), move || increment.with_mut(|c| *c += 1)),
+            error_boundary(
+                // Tapping the count panics once it gets to 3.
+                text(&format!("count: {}", count.get()))
+                    .padding(Auto)
+                    .tap(move || {
+                        if check.get() >= 3 {
+                            panic!("count too high")
+                        }
+                    }),
+                |err| text(&format!("Something went wrong: {}", err)).color(RED_HIGHLIGHT),
+            ),
+        ))
+    }));
+}
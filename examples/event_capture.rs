@@ -0,0 +1,28 @@
+use rui::*;
+
+fn main() {
+    rui(state(false, |locked| {
+        let toggle_lock = locked.clone();
+        vstack((
+            // The button handles its own taps, so the row doesn't see them.
+            hstack((
+                text("row").padding(Auto),
+                button(text("button"), || println!("button tapped")),
+            ))
+            .tap(|| println!("row tapped")),
+            // While locked, the controls below don't see any touches.
+            hstack((
+                button(text("save"), || println!("saved")),
+                button(text("delete"), || println!("deleted")),
+            ))
+            .on_event_capture(move |event| {
+                locked.get()
+                    && matches!(
+                        event.kind,
+                        EventKind::TouchBegin { .. } | EventKind::TouchEnd { .. }
+                    )
+            }),
+            button(text("lock"), move || toggle_lock.with_mut(|l| *l = !*l)),
+        ))
+    }));
+}
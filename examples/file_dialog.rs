@@ -0,0 +1,19 @@
+use rui::*;
+
+fn main() {
+    rui(state(String::from("no file"), |path| {
+        let label = path.get();
+        vstack((
+            button(text("Open..."), move || {
+                let path = path.clone();
+                open_file_dialog(&[FileFilter::new("Text", &["txt", "md"])], move |file| {
+                    if let Some(file) = file {
+                        path.set(file.display().to_string())
+                    }
+                })
+            }),
+            text(&label),
+        ))
+        .padding(Auto)
+    }));
+}
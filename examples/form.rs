@@ -0,0 +1,60 @@
+use rui::*;
+
+#[derive(Clone, Bindable)]
+struct SignUp {
+    name: String,
+    email: String,
+    password: String,
+    invite: String,
+}
+
+fn main() {
+    rui(state(
+        SignUp {
+            name: String::new(),
+            email: String::new(),
+            password: String::new(),
+            invite: String::new(),
+        },
+        |signup| {
+            let model = signup.clone();
+            form(
+                (
+                    field("Name", text_field(signup.binding().name())).validate(|s| {
+                        if s.is_empty() {
+                            Err("Name is required".into())
+                        } else {
+                            Ok(())
+                        }
+                    }),
+                    field("Email", text_field(signup.binding().email())).validate(|s| {
+                        if s.contains('@') {
+                            Ok(())
+                        } else {
+                            Err("Enter an email address".into())
+                        }
+                    }),
+                    field("Password", text_field(signup.binding().password()).secure()).validate(
+                        |s| {
+                            if s.len() >= 8 {
+                                Ok(())
+                            } else {
+                                Err("Use at least 8 characters".into())
+                            }
+                        },
+                    ),
+                    field(
+                        "Invite code",
+                        text_field(signup.binding().invite())
+                            .allow_chars(|c| c.is_ascii_alphanumeric())
+                            .max_len(6)
+                            .transform(|s| s.to_uppercase()),
+                    ),
+                ),
+                "Sign Up",
+                move || println!("signed up {}", model.get().email),
+            )
+            .padding(Auto)
+        },
+    ));
+}
@@ -0,0 +1,17 @@
+use rui::*;
+
+fn main() {
+    rui(hstack((
+        list((0..Icon::ALL.len()).collect(), |i| {
+            let i = Icon::ALL[*i];
+            hstack((icon(i), text(&format!("{:?}", i))))
+        }),
+        vstack((
+            icon(Icon::Search).icon_size(32.0).padding(Auto),
+            icon(Icon::Close).color(RED_HIGHLIGHT).padding(Auto),
+            hstack((icon(Icon::Check), icon(Icon::Star)))
+                .tint(GREEN_HIGHLIGHT)
+                .padding(Auto),
+        )),
+    )));
+}
@@ -0,0 +1,27 @@
+use rui::*;
+use std::{thread, time};
+
+/// A gradient standing in for an image loaded from disk or the network.
+fn load(i: usize) -> Option<Frame> {
+    thread::sleep(time::Duration::from_millis(200 * (i as u64 % 5)));
+    let (width, height) = (320, 240);
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.extend_from_slice(&[
+                (x * 255 / width) as u8,
+                (y * 255 / height) as u8,
+                (i * 37 % 256) as u8,
+                255,
+            ]);
+        }
+    }
+    Some(Frame::new(width as u32, height as u32, pixels))
+}
+
+fn main() {
+    let images = (0..40)
+        .map(|i| FrameSource::load(move || load(i)))
+        .collect();
+    rui(gallery(images));
+}
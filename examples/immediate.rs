@@ -0,0 +1,29 @@
+use rui::*;
+
+fn main() {
+    let mut count = 0;
+    let mut speed = 0.5;
+    let mut paused = false;
+    rui(immediate(move |ui| {
+        ui.label(&format!("count: {}", count));
+        ui.horizontal(|ui| {
+            if ui.button("-") {
+                count -= 1;
+            }
+            if ui.button("+") {
+                count += 1;
+            }
+        });
+        ui.label(&format!("speed: {:.2}", speed));
+        ui.slider(&mut speed);
+        ui.horizontal(|ui| {
+            ui.label("paused");
+            ui.toggle(&mut paused);
+        });
+        for i in 0..3 {
+            if ui.button(&format!("reset to {}", i * 10)) {
+                count = i * 10;
+            }
+        }
+    }))
+}
@@ -0,0 +1,28 @@
+use rui::*;
+use std::{thread, time};
+
+/// A knob turned on a MIDI controller.
+struct ControlChange {
+    value: u8,
+}
+
+fn main() {
+    // Stands in for a thread reading from the controller.
+    let injector = injector();
+    thread::spawn(move || {
+        for value in (0..=127).cycle().step_by(8) {
+            thread::sleep(time::Duration::from_millis(100));
+            injector.send(ControlChange { value });
+        }
+    });
+
+    rui(state(0.0, |cutoff| {
+        let cutoff2 = cutoff.clone();
+        vstack((
+            text(&format!("cutoff: {:.0}%", cutoff.get() * 100.0)),
+            knob(cutoff.clone()),
+        ))
+        .padding(Auto)
+        .on_user_event(move |cc: &ControlChange| cutoff2.set(cc.value as f32 / 127.0))
+    }));
+}
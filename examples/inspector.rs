@@ -0,0 +1,39 @@
+use rui::*;
+
+#[derive(Clone, PartialEq, Property)]
+enum Shape {
+    Circle,
+    Square,
+    Triangle,
+}
+
+#[derive(Clone, Inspectable)]
+struct Settings {
+    name: String,
+    visible: bool,
+    #[inspect(group = "Appearance")]
+    shape: Shape,
+    #[inspect(group = "Appearance")]
+    opacity: f32,
+    #[inspect(group = "Appearance")]
+    line_width: f32,
+    #[inspect(skip)]
+    revision: u32,
+}
+
+fn main() {
+    rui(state(
+        Settings {
+            name: "layer".into(),
+            visible: true,
+            shape: Shape::Circle,
+            opacity: 1.0,
+            line_width: 0.25,
+            revision: 0,
+        },
+        |settings| {
+            let title = format!("revision {}", settings.get().revision);
+            inspector(settings).padding(Auto).window_title(&title)
+        },
+    ));
+}
@@ -0,0 +1,23 @@
+use rui::*;
+
+fn main() {
+    rui(state(0, |count| {
+        let count2 = count.clone();
+        text(&format!("pressed {} times", count.get()))
+            .padding(Auto)
+            .tap(move || count2.with_mut(|x| *x += 1))
+            .style(|state| {
+                let style = StyleOverrides::default().corner_radius(8.0);
+                if state.pressed {
+                    style.background(AZURE_HIGHLIGHT).scale(0.95)
+                } else if state.hovered {
+                    style
+                        .background(BUTTON_BACKGROUND_COLOR)
+                        .border(AZURE_HIGHLIGHT, 1.0)
+                } else {
+                    style.background(BUTTON_BACKGROUND_COLOR)
+                }
+            })
+            .padding(Auto)
+    }));
+}
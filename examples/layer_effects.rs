@@ -0,0 +1,35 @@
+use rui::*;
+
+fn main() {
+    rui(state(1.0, |amount| {
+        let swatch = |mode: BlendMode| {
+            zstack((
+                rectangle().color(AZURE_HIGHLIGHT),
+                circle().color(RED_HIGHLIGHT).padding(Auto).blend_mode(mode),
+            ))
+            .size([80.0, 80.0])
+        };
+        vstack((
+            hstack((
+                swatch(BlendMode::Normal),
+                swatch(BlendMode::Multiply),
+                swatch(BlendMode::Screen),
+                swatch(BlendMode::Darken),
+                swatch(BlendMode::Lighten),
+                swatch(BlendMode::Plus),
+            )),
+            hstack((
+                text("blurred").font_size(30).blur(amount.get() * 4.0),
+                text("desaturated")
+                    .font_size(30)
+                    .saturation(1.0 - amount.get()),
+                // Faded as a whole, so the circle doesn't show
+                // through the rectangle.
+                zstack((circle(), rectangle().padding(20.0)))
+                    .compositing_group()
+                    .disabled(true),
+            )),
+            hslider(amount),
+        ))
+    }));
+}
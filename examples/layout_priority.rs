@@ -0,0 +1,11 @@
+use rui::*;
+
+fn main() {
+    // Narrow the window: the subtitle truncates before the buttons shrink.
+    rui(hstack((
+        text("A subtitle which truncates when space runs out"),
+        button(text("Open"), || println!("open")).layout_priority(1.0),
+        button(text("Share"), || println!("share")).layout_priority(1.0),
+    ))
+    .padding(Auto));
+}
@@ -0,0 +1,13 @@
+use rui::*;
+
+fn main() {
+    rui(vstack((
+        link("rui on GitHub", "https://github.com/audulus/rui").padding(Auto),
+        link("Middle click me", "https://docs.rs/rui")
+            .on_open(|url, target| match target {
+                LinkTarget::NewWindow => println!("open {} in a new window", url),
+                LinkTarget::Default => println!("open {}", url),
+            })
+            .padding(Auto),
+    )));
+}
@@ -0,0 +1,45 @@
+use rui::*;
+
+fn main() {
+    let fruit: Vec<String> = [
+        "Apple",
+        "Apricot",
+        "Banana",
+        "Blueberry",
+        "Cherry",
+        "Date",
+        "Elderberry",
+        "Fig",
+        "Grape",
+        "Guava",
+        "Kiwi",
+        "Lemon",
+        "Lime",
+        "Mango",
+        "Melon",
+        "Nectarine",
+        "Orange",
+        "Papaya",
+        "Peach",
+        "Pear",
+        "Plum",
+        "Quince",
+        "Raspberry",
+        "Strawberry",
+    ]
+    .iter()
+    .map(|name| name.to_string())
+    .collect();
+
+    rui(state(None, move |selected: State<Option<String>>| {
+        vstack((
+            text(&format!("selected: {:?}", selected.get())).padding(Auto),
+            scroll_view(
+                list(fruit.clone(), |name| text(name).padding(Auto))
+                    .selection(selected)
+                    .on_activate(|name| println!("opened {}", name))
+                    .type_select(|name| name.clone()),
+            ),
+        ))
+    }));
+}
@@ -0,0 +1,22 @@
+use rui::*;
+
+const NOTES: &str = "# rui 0.2
+
+Some highlights of this release:
+
+1. **Markdown**, rendered with `markdown`
+2. Links which open in the *browser*
+   - like [the repository](https://github.com/audulus/rui)
+
+```
+rui(markdown(NOTES));
+```
+
+---
+
+Thanks to everyone who contributed!
+";
+
+fn main() {
+    rui(markdown(NOTES).padding(Auto));
+}
@@ -0,0 +1,12 @@
+use rui::*;
+
+fn main() {
+    rui(state(1, |count| {
+        rui! {
+            vstack {
+                text(&format!("{:?}", count.get())).padding(Auto)
+                button(text("increment")) { count.with_mut(|x| *x += 1) }.padding(Auto)
+            }
+        }
+    }));
+}
@@ -0,0 +1,13 @@
+use rui::*;
+
+fn main() {
+    // The buttons are as wide as the widest label, "Don't Save".
+    rui(hstack((
+        button(text("Save").match_width("buttons"), || println!("save")),
+        button(text("Don't Save").match_width("buttons"), || {
+            println!("don't save")
+        }),
+        button(text("Cancel").match_width("buttons"), || println!("cancel")),
+    ))
+    .padding(Auto));
+}
@@ -0,0 +1,26 @@
+use rui::*;
+
+fn main() {
+    rui(state(0, |count| {
+        state(100, move |rows| {
+            let count = count.clone();
+            let rows_count = rows.get();
+            vstack((
+                hstack((
+                    button(text(&format!("tapped {} times", count.get())), move || {
+                        count.with_mut(|count| *count += 1)
+                    }),
+                    button(text("more rows"), move || {
+                        rows.with_mut(|rows| *rows += 100)
+                    }),
+                )),
+                // Tapping the counter doesn't lay out or draw the list
+                // again. Adding rows changes the key, so it does.
+                list((0..rows_count).collect(), |row| {
+                    text(&format!("row {}", row))
+                })
+                .memo(rows_count),
+            ))
+        })
+    }));
+}
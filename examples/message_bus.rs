@@ -0,0 +1,23 @@
+use rui::*;
+
+/// Asks the counter to go back to zero.
+struct Reset;
+
+fn toolbar() -> impl View {
+    read_env(|bus: MessageBus| button(text("reset"), move || bus.publish(Reset)).padding(Auto))
+}
+
+fn counter() -> impl View {
+    state(0, |count| {
+        let count2 = count.clone();
+        hstack((
+            text(&format!("{}", count.get())).padding(Auto),
+            button(text("increment"), move || count.with_mut(|x| *x += 1)).padding(Auto),
+        ))
+        .on_message(move |_: &Reset| count2.set(0))
+    })
+}
+
+fn main() {
+    rui(vstack((toolbar(), counter())));
+}
@@ -0,0 +1,32 @@
+use rui::*;
+
+fn main() {
+    rui(state(Monitors::default(), |monitors| {
+        let monitors2 = monitors.clone();
+        let all = monitors.get().all;
+        let current = monitors.get().current;
+        list((0..all.len()).collect(), move |i| {
+            let m = &all[*i];
+            let size = m.logical_size();
+            text(&format!(
+                "{}: {}x{} at {}x{}{}",
+                m.name.clone().unwrap_or_else(|| "Unnamed".into()),
+                size.width,
+                size.height,
+                m.scale_factor,
+                if m.primary { ", primary" } else { "" },
+                if current == Some(*i) {
+                    ", has this window"
+                } else {
+                    ""
+                },
+            ))
+        })
+        .padding(Auto)
+        .on_window_event(move |kind| {
+            if let WindowEventKind::DisplaysChanged(monitors) = kind {
+                monitors2.set(monitors.clone());
+            }
+        })
+    }));
+}
@@ -0,0 +1,34 @@
+use rui::*;
+
+fn main() {
+    let nodes = vec![
+        GraphNode::new("oscillator", [40.0, 300.0]).output("out"),
+        GraphNode::new("noise", [40.0, 160.0]).output("out"),
+        GraphNode::new("mix", [260.0, 220.0])
+            .input("a")
+            .input("b")
+            .output("out"),
+        GraphNode::new("speaker", [480.0, 240.0]).input("in"),
+    ];
+    let edges = vec![
+        GraphEdge {
+            from: Port { node: 0, index: 0 },
+            to: Port { node: 2, index: 0 },
+        },
+        GraphEdge {
+            from: Port { node: 2, index: 0 },
+            to: Port { node: 3, index: 0 },
+        },
+    ];
+
+    rui(state(nodes, move |nodes| {
+        state(edges.clone(), move |edges| {
+            vstack((
+                text(&format!("{} connections", edges.get().len())).padding(Auto),
+                node_graph(nodes.clone(), edges).on_select(|selection| {
+                    println!("selected {:?}", selection);
+                }),
+            ))
+        })
+    }));
+}
@@ -0,0 +1,19 @@
+use rui::*;
+
+fn main() {
+    rui(state(0, |page| {
+        pages(
+            vec![
+                any_view(text("Welcome to rui").font_size(24)),
+                any_view(vstack((
+                    text("Swipe, or use the arrow keys"),
+                    circle().color(AZURE_HIGHLIGHT).padding(Auto),
+                ))),
+                any_view(state(0.5, |v| {
+                    vstack((text("This page keeps its state"), hslider(v)))
+                })),
+            ],
+            page,
+        )
+    }));
+}
@@ -0,0 +1,19 @@
+use rui::*;
+
+fn main() {
+    rui(state(PanZoom::default(), |transform| {
+        let zoom = transform.get().zoom;
+        let reset = transform.clone();
+        vstack((
+            text(&format!("zoom: {:.2}", zoom)).padding(Auto),
+            pan_zoom(
+                vstack((
+                    circle().color(AZURE_HIGHLIGHT),
+                    button(text("reset"), move || reset.set(PanZoom::default())),
+                ))
+                .padding(Auto),
+            )
+            .transform(transform),
+        ))
+    }));
+}
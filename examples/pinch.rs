@@ -0,0 +1,34 @@
+use rui::*;
+
+#[derive(Clone)]
+struct Transform {
+    scale: f32,
+    angle: f32,
+}
+
+fn main() {
+    rui(state(
+        Transform {
+            scale: 1.0,
+            angle: 0.0,
+        },
+        |t| {
+            let (t2, t3) = (t.clone(), t.clone());
+            let Transform { scale, angle } = t.get();
+            vstack((
+                text(&format!(
+                    "scale {:.2}, angle {:.0}°",
+                    scale,
+                    angle.to_degrees()
+                ))
+                .padding(Auto),
+                circle()
+                    .color(AZURE_HIGHLIGHT)
+                    .size([100.0 * scale, 100.0 * scale])
+                    .padding(Auto)
+                    .on_pinch(move |scale, _state| t2.with_mut(|t| t.scale = scale))
+                    .on_rotate(move |angle, _state| t3.with_mut(|t| t.angle = angle)),
+            ))
+        },
+    ));
+}
@@ -0,0 +1,25 @@
+use rui::*;
+
+fn report() -> impl View {
+    vstack((
+        text("Quarterly report").font_size(24),
+        text("Everything went up, which is good."),
+        // Long enough to need a second page.
+        rectangle().color(AZURE_HIGHLIGHT).size([300.0, 1000.0]),
+    ))
+}
+
+fn main() {
+    rui(vstack((
+        report().padding(Auto),
+        button(text("Print"), || {
+            let options = PrintOptions {
+                title: "Quarterly report".into(),
+                ..Default::default()
+            };
+            if let Err(err) = print_view(&report(), options) {
+                println!("error printing: {:?}", err);
+            }
+        }),
+    )));
+}
@@ -0,0 +1,63 @@
+use rui::*;
+use std::{thread, time::Duration};
+
+#[derive(Clone)]
+struct Model {
+    count: i32,
+    loading: bool,
+}
+
+#[derive(Clone)]
+enum Msg {
+    Increment,
+    Decrement,
+    Load,
+    Loaded(i32),
+}
+
+fn update(msg: Msg, model: &mut Model) -> Cmd<Msg> {
+    match msg {
+        Msg::Increment => model.count += 1,
+        Msg::Decrement => model.count -= 1,
+        Msg::Load => {
+            model.loading = true;
+            // Stands in for fetching the count from a server.
+            return Cmd::perform(async {
+                thread::sleep(Duration::from_secs(1));
+                Msg::Loaded(42)
+            });
+        }
+        Msg::Loaded(count) => {
+            model.count = count;
+            model.loading = false;
+        }
+    }
+    Cmd::none()
+}
+
+fn view(model: &Model) -> impl View {
+    let status = if model.loading {
+        "loading…".to_string()
+    } else {
+        format!("{}", model.count)
+    };
+    vstack((
+        text(&status).padding(Auto),
+        hstack((
+            button(text("-"), emit(Msg::Decrement)).padding(Auto),
+            button(text("+"), emit(Msg::Increment)).padding(Auto),
+            button(text("load"), emit(Msg::Load)).padding(Auto),
+        )),
+    ))
+}
+
+fn main() {
+    program(
+        Model {
+            count: 0,
+            loading: false,
+        },
+        update,
+        view,
+    );
+}
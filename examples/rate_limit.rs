@@ -0,0 +1,24 @@
+use rui::*;
+use std::time::Duration;
+
+fn main() {
+    rui(state(0.5, |value| {
+        // The adapters are kept in state so they hold on to the writes
+        // they're holding back.
+        let throttled = value.clone().throttled(Duration::from_millis(250));
+        let debounced = value.clone().debounced(Duration::from_millis(500));
+        state(throttled, move |throttled| {
+            let value = value.clone();
+            state(debounced.clone(), move |debounced| {
+                vstack((
+                    text(&format!("value: {:.2}", value.get())),
+                    text("Throttled"),
+                    hslider(throttled.get()),
+                    text("Debounced"),
+                    hslider(debounced.get()),
+                ))
+                .padding(Auto)
+            })
+        })
+    }));
+}
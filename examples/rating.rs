@@ -0,0 +1,15 @@
+use rui::*;
+
+fn main() {
+    rui(state(3.0, |value| {
+        vstack((
+            rating(value.clone(), 5),
+            rating(value.clone(), 5).half_steps(true).star_size(32.0),
+            rating(value.clone(), 5)
+                .read_only(true)
+                .color(AZURE_HIGHLIGHT),
+            text(&format!("{} stars", value.get())),
+        ))
+        .padding(Auto)
+    }));
+}
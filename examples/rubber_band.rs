@@ -0,0 +1,38 @@
+use rui::*;
+
+fn main() {
+    rui(state(
+        Vec::<usize>::new(),
+        |selection: State<Vec<usize>>| {
+            let selected = selection.get();
+            vstack((
+                text(&format!("selected: {:?}", selected)).padding(Auto),
+                vstack(
+                    (0..4)
+                        .map(|row| {
+                            hstack(
+                                (0..6)
+                                    .map(|column| {
+                                        let i = row * 6 + column;
+                                        let color = if selected.contains(&i) {
+                                            AZURE_HIGHLIGHT
+                                        } else {
+                                            CONTROL_BACKGROUND
+                                        };
+                                        rectangle()
+                                            .corner_radius(5.0)
+                                            .color(color)
+                                            .size([60.0, 60.0])
+                                            .padding(Auto)
+                                            .selectable(i)
+                                    })
+                                    .collect::<Vec<_>>(),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .rubber_band_selection(selection),
+            ))
+        },
+    ));
+}
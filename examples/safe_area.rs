@@ -0,0 +1,23 @@
+use rui::*;
+
+fn main() {
+    // Leave room for the window buttons of a custom title bar. The
+    // content stays clear of them while the background fills the window.
+    rui(vstack((
+        text("content below the title bar"),
+        button(text("press me"), || println!("pressed")),
+    ))
+    .padding(Auto)
+    .background(
+        rectangle()
+            .color(BUTTON_BACKGROUND_COLOR)
+            .window_drag_region()
+            .ignore_safe_area(),
+    )
+    .respect_safe_area()
+    .safe_area_inset(SafeAreaInsets {
+        top: 28.0,
+        ..Default::default()
+    })
+    .window_decorations(false));
+}
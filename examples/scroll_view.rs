@@ -0,0 +1,21 @@
+use rui::*;
+
+fn main() {
+    rui(state(LocalOffset::zero(), |offset: State<LocalOffset>| {
+        vstack((
+            text(&format!("scrolled {:.0}", offset.get().y)),
+            hstack((
+                button(text("top"), emit(ScrollTo::start())),
+                button(
+                    text("row 50"),
+                    emit(ScrollTo::index(50, ScrollAnchor::Center)),
+                ),
+                button(text("bottom"), emit(ScrollTo::end())),
+            )),
+            scroll_view(list((0..100).collect(), |i| {
+                text(&format!("row {}", i)).padding(Auto)
+            }))
+            .offset(offset),
+        ))
+    }));
+}
@@ -0,0 +1,33 @@
+use rui::*;
+
+const FRUITS: &[&str] = &[
+    "apple",
+    "apricot",
+    "banana",
+    "blackberry",
+    "blueberry",
+    "cherry",
+    "grape",
+    "lemon",
+    "lime",
+    "mango",
+    "orange",
+    "peach",
+    "pear",
+    "plum",
+];
+
+fn main() {
+    rui(state(String::new(), |query| {
+        search_field(query)
+            .on_search(|q| println!("searching for {:?}", q))
+            .suggestions(|q| async move {
+                FRUITS
+                    .iter()
+                    .filter(|f| f.starts_with(&q))
+                    .map(|f| f.to_string())
+                    .collect()
+            })
+            .padding(Auto)
+    }));
+}
@@ -0,0 +1,18 @@
+use rui::*;
+
+fn main() {
+    let (left, right) = (signal(0), signal(0));
+    let (l, r) = (left.clone(), right.clone());
+
+    // Each count is only built again when its own signal changes.
+    rui(vstack((
+        hstack((
+            reactive(move || text(&format!("left: {}", l.get())).padding(Auto)),
+            reactive(move || text(&format!("right: {}", r.get())).padding(Auto)),
+        )),
+        hstack((
+            button(text("left"), move || left.with_mut(|x| *x += 1)).padding(Auto),
+            button(text("right"), move || right.with_mut(|x| *x += 1)).padding(Auto),
+        )),
+    )));
+}
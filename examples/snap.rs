@@ -0,0 +1,35 @@
+use rui::*;
+
+fn main() {
+    // Guides are in the coordinates of the stack the views are in.
+    let lines = vec![Guide::Vertical(200.0), Guide::Horizontal(150.0)];
+    rui(zstack((
+        canvas(|rect, vger| {
+            let paint = vger.color_paint(GROOVES);
+            let (x, y) = (200.0, 150.0);
+            vger.stroke_segment(
+                LocalPoint::new(x, 0.0),
+                LocalPoint::new(x, rect.height()),
+                1.0,
+                paint,
+            );
+            vger.stroke_segment(
+                LocalPoint::new(0.0, y),
+                LocalPoint::new(rect.width(), y),
+                1.0,
+                paint,
+            );
+        }),
+        rectangle()
+            .corner_radius(4.0)
+            .color(AZURE_HIGHLIGHT)
+            .size([80.0, 40.0])
+            .draggable()
+            .snap_to(grid(8.0) | guides(lines.clone())),
+        circle()
+            .color(RED_HIGHLIGHT)
+            .size([50.0, 50.0])
+            .draggable()
+            .snap_to(grid(8.0) | guides(lines)),
+    )));
+}
@@ -0,0 +1,23 @@
+use rui::*;
+
+fn main() {
+    rui(state(false, |request| {
+        let request2 = request.clone();
+        vstack((
+            hstack((
+                circle().color(AZURE_HIGHLIGHT).size([60.0, 60.0]),
+                text("Share me").font_size(24),
+            ))
+            .padding(Auto)
+            .background(rectangle().color(BUTTON_BACKGROUND_COLOR))
+            .snapshot(request.clone(), 2.0, |image| {
+                match image.save_png("snapshot.png") {
+                    Ok(()) => println!("saved {}x{} snapshot.png", image.width, image.height),
+                    Err(err) => println!("error saving snapshot: {:?}", err),
+                }
+            }),
+            button(text("Save snapshot.png"), move || request2.set(true)),
+        ))
+        .padding(Auto)
+    }));
+}
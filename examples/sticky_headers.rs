@@ -0,0 +1,21 @@
+use rui::*;
+
+fn main() {
+    let sections = ["Fruit", "Vegetables", "Grains", "Dairy"]
+        .iter()
+        .map(|&name| section(name, (1..=12).map(|i| format!("{} {}", name, i)).collect()))
+        .collect();
+
+    rui(scroll_view(
+        sectioned_list(
+            sections,
+            |name| {
+                text(name)
+                    .padding(Auto)
+                    .background(rectangle().color(AZURE_HIGHLIGHT_BACKGROUND))
+            },
+            |row| text(row).padding(Auto),
+        )
+        .sticky_headers(),
+    ));
+}
@@ -0,0 +1,9 @@
+use rui::stress::*;
+use rui::*;
+
+// Draws about 10k rectangles using only four distinct paints.
+// Run with RUI_FRAME_TIME=1 to print frame timings, and see
+// `cargo bench` for how much batching them by paint saves.
+fn main() {
+    rui(rect_grid(100));
+}
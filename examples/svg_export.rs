@@ -0,0 +1,22 @@
+use rui::*;
+
+fn report() -> impl View {
+    vstack((
+        text("Quarterly report").font_size(24),
+        hstack((
+            circle().color(AZURE_HIGHLIGHT),
+            circle().color(RED_HIGHLIGHT),
+            circle().color(GREEN_HIGHLIGHT),
+        )),
+        text("Everything went up, which is good.").padding(Auto),
+    ))
+    .padding(Auto)
+}
+
+fn main() {
+    let svg = render_to_svg(&report(), [400.0, 300.0].into());
+    match std::fs::write("report.svg", svg) {
+        Ok(()) => println!("wrote report.svg"),
+        Err(err) => println!("error writing report.svg: {:?}", err),
+    }
+}
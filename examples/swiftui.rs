@@ -0,0 +1,16 @@
+use rui::swiftui::*;
+use rui::*;
+
+fn main() {
+    rui(state(false, |on| {
+        VStack! {
+            Text("Settings").font(TextStyle::Title),
+            HStack! {
+                Text("Notifications").foregroundColor(AZURE_HIGHLIGHT),
+                Toggle(on.clone()),
+            },
+            Button("Reset", move || on.set(false)).frame(Some(120.0), None),
+        }
+        .padding(Auto)
+    }));
+}
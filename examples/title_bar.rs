@@ -0,0 +1,25 @@
+use rui::*;
+
+fn title_bar() -> impl View {
+    hstack((
+        circle()
+            .color(RED_HIGHLIGHT)
+            .size([12.0, 12.0])
+            .window_button(WindowButton::Close),
+        circle()
+            .color(AZURE_HIGHLIGHT)
+            .size([12.0, 12.0])
+            .window_button(WindowButton::Minimize),
+        circle()
+            .color(GREEN_HIGHLIGHT)
+            .size([12.0, 12.0])
+            .window_button(WindowButton::Maximize),
+        text("custom title bar"),
+    ))
+    .background(rectangle().color(BUTTON_BACKGROUND_COLOR))
+    .window_drag_region()
+}
+
+fn main() {
+    rui(vstack((title_bar(), text("content").padding(Auto))).window_decorations(false));
+}
@@ -0,0 +1,23 @@
+use rui::*;
+
+fn main() {
+    let languages = [
+        "C",
+        "C++",
+        "Go",
+        "Haskell",
+        "JavaScript",
+        "Python",
+        "Rust",
+        "Swift",
+    ];
+    rui(state(vec!["Rust".to_string()], move |tags| {
+        vstack((
+            text("Languages"),
+            token_field(tags.clone())
+                .suggestions(languages.iter().map(|s| s.to_string()).collect()),
+            text(&tags.get().join(", ")),
+        ))
+        .padding(Auto)
+    }));
+}
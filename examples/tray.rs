@@ -0,0 +1,18 @@
+use rui::*;
+
+fn main() {
+    rui(state(0, |syncs| {
+        text(&format!("synced {} times", syncs.get()))
+            .padding(Auto)
+            .command("Sync:Sync Now", None, move || syncs.with_mut(|s| *s += 1))
+            .tray(
+                "examples/tray_icon.png",
+                vec![
+                    TrayItem::Command("Sync:Sync Now".into()),
+                    TrayItem::ShowWindow,
+                    TrayItem::HideWindow,
+                    TrayItem::Quit,
+                ],
+            )
+    }));
+}
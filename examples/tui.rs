@@ -0,0 +1,13 @@
+use rui::*;
+
+fn main() {
+    rui_tui(state(1, |count| {
+        vstack((
+            text(&format!("{:?}", count.get())).padding(Auto),
+            button(text("increment"), move || {
+                count.with_mut(|x| *x += 1);
+            })
+            .padding(Auto),
+        ))
+    }));
+}
@@ -0,0 +1,27 @@
+use rui::*;
+use std::sync::mpsc::channel;
+use std::{thread, time};
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 180;
+
+fn main() {
+    // Stands in for a camera or a video decoder.
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        for t in 0.. {
+            let mut pixels = Vec::with_capacity((4 * WIDTH * HEIGHT) as usize);
+            for y in 0..HEIGHT {
+                for x in 0..WIDTH {
+                    pixels.extend_from_slice(&[(x + t) as u8, (y + t) as u8, 128, 255]);
+                }
+            }
+            if tx.send(Frame::new(WIDTH, HEIGHT, pixels)).is_err() {
+                break;
+            }
+            thread::sleep(time::Duration::from_millis(33));
+        }
+    });
+
+    rui(frame_view(rx).padding(Auto));
+}
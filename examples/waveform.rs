@@ -0,0 +1,42 @@
+use rui::*;
+use std::ops::Range;
+
+#[derive(Clone, Bindable)]
+struct Track {
+    samples: Vec<f32>,
+    playhead: usize,
+    selection: Option<Range<usize>>,
+}
+
+fn main() {
+    // Ten seconds of a decaying, wobbling tone at 48 kHz.
+    let samples = (0..480_000)
+        .map(|i| {
+            let t = i as f32 / 48_000.0;
+            (t * 220.0 * std::f32::consts::TAU).sin() * (t * 2.0).sin().abs() * (-t * 0.2).exp()
+        })
+        .collect();
+
+    rui(state(
+        Track {
+            samples,
+            playhead: 0,
+            selection: None,
+        },
+        |track| {
+            let t = track.get();
+            let level = t.samples.get(t.playhead).map_or(0.0, |s| s.abs());
+            let seek = track.clone();
+            let select = track.clone();
+            vstack((
+                waveform(track.binding().samples())
+                    .playhead(Some(t.playhead))
+                    .selection(t.selection.clone())
+                    .on_seek(move |sample| seek.with_mut(|t| t.playhead = sample))
+                    .on_select(move |range| select.with_mut(|t| t.selection = Some(range)))
+                    .padding(Auto),
+                level_meter(bind(move || level, |_| ())).padding(Auto),
+            ))
+        },
+    ));
+}
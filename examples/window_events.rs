@@ -0,0 +1,41 @@
+use rui::*;
+
+#[derive(Clone, Default)]
+struct AppState {
+    focused: bool,
+    unsaved: bool,
+    warned: bool,
+}
+
+fn main() {
+    rui(state(AppState::default(), |s| {
+        let (s2, s3) = (s.clone(), s.clone());
+        vstack((
+            text(if s.with(|s| s.focused) {
+                "focused"
+            } else {
+                "not focused"
+            })
+            .padding(Auto),
+            text(if s.with(|s| s.warned) {
+                "unsaved changes, close again to discard"
+            } else {
+                ""
+            })
+            .padding(Auto),
+            button(text("edit"), move || s3.with_mut(|s| s.unsaved = true)).padding(Auto),
+        ))
+        .on_window_event(move |kind| match kind {
+            WindowEventKind::Focused => s.with_mut(|s| s.focused = true),
+            WindowEventKind::Unfocused => s.with_mut(|s| s.focused = false),
+            _ => (),
+        })
+        .on_close_requested(move || {
+            s2.with_mut(|s| {
+                let close = !s.unsaved || s.warned;
+                s.warned = true;
+                close
+            })
+        })
+    }));
+}
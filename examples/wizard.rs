@@ -0,0 +1,50 @@
+use rui::*;
+
+#[derive(Clone, Bindable)]
+struct Setup {
+    step: usize,
+    name: String,
+    notifications: bool,
+    volume: f32,
+}
+
+fn main() {
+    rui(state(
+        Setup {
+            step: 0,
+            name: String::new(),
+            notifications: true,
+            volume: 0.5,
+        },
+        |setup| {
+            let name = setup.get().name;
+            let done = setup.clone();
+            wizard(
+                vec![
+                    wizard_step(
+                        "Name",
+                        vstack((
+                            text("What's your name?"),
+                            text_field(setup.binding().name()),
+                        )),
+                    )
+                    .valid(!name.is_empty()),
+                    wizard_step(
+                        "Options",
+                        vstack((
+                            hstack((
+                                text("Notifications"),
+                                toggle(setup.binding().notifications()),
+                            )),
+                            hstack((text("Volume"), hslider(setup.binding().volume()))),
+                        )),
+                    ),
+                    wizard_step("Done", text(&format!("All set, {}!", name))),
+                ],
+                setup.binding().step(),
+                move || println!("finished setting up {}", done.get().name),
+            )
+            .padding(Auto)
+        },
+    ));
+}
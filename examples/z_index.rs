@@ -0,0 +1,22 @@
+use rui::*;
+
+fn main() {
+    // Tapping a card brings it to the front.
+    rui(state(0, |front| {
+        let card = |index: i32, color| {
+            let raise = front.clone();
+            rectangle()
+                .color(color)
+                .corner_radius(5.0)
+                .size([120.0, 80.0])
+                .offset([index as f32 * 40.0, index as f32 * 30.0])
+                .tap(move || raise.set(index))
+                .z_index(if front.get() == index { 1 } else { 0 })
+        };
+        zstack((
+            card(0, AZURE_HIGHLIGHT),
+            card(1, RED_HIGHLIGHT),
+            card(2, GREEN_HIGHLIGHT),
+        ))
+    }));
+}
@@ -0,0 +1,21 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rui::*;
+
+fn controls() -> impl View {
+    state(0.5, |value: State<f32>| {
+        vstack((
+            text("controls"),
+            hslider(value.clone()),
+            hstack((
+                button(text("reset"), move || value.set(0.5)),
+                toggle(signal(false)),
+            )),
+            text_field(signal(String::new())),
+        ))
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    rui::fuzz::run(&controls(), data);
+});
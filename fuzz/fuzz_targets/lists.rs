@@ -0,0 +1,26 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rui::*;
+
+/// A list whose rows can be added and removed while they're interacted
+/// with.
+fn lists() -> impl View {
+    state(3usize, |rows: State<usize>| {
+        let (add, remove) = (rows.clone(), rows.clone());
+        vstack((
+            hstack((
+                button(text("add"), move || add.with_mut(|n| *n += 1)),
+                button(text("remove"), move || {
+                    remove.with_mut(|n| *n = n.saturating_sub(1))
+                }),
+            )),
+            list((0..rows.get()).collect(), |i| {
+                hstack((text(&format!("row {}", i)), toggle(signal(false))))
+            }),
+        ))
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    rui::fuzz::run(&lists(), data);
+});
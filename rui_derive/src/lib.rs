@@ -0,0 +1,252 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, token::Comma, Data, DeriveInput, Field, Fields, Ident, Lit, Meta,
+    NestedMeta, Type,
+};
+
+/// The named fields of a struct, or an error naming the derive.
+fn named_fields<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+) -> Result<&'a Punctuated<Field, Comma>, TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            format!("{} doesn't support generic structs", derive),
+        )
+        .to_compile_error()
+        .into());
+    }
+
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(&fields.named),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{} requires named fields", derive),
+            )
+            .to_compile_error()
+            .into()),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("{} can only be derived for structs", derive),
+        )
+        .to_compile_error()
+        .into()),
+    }
+}
+
+/// An expression for a binding to `field_name` through `binding`.
+fn field_lens(
+    name: &Ident,
+    field_name: &Ident,
+    ty: &Type,
+    binding: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {{
+        #[derive(Clone)]
+        struct FieldLens;
+
+        impl ::rui::Lens<#name, #ty> for FieldLens {
+            fn with<V, F: FnOnce(&#ty) -> V>(&self, data: &#name, f: F) -> V {
+                f(&data.#field_name)
+            }
+            fn with_mut<V, F: FnOnce(&mut #ty) -> V>(&self, data: &mut #name, f: F) -> V {
+                f(&mut data.#field_name)
+            }
+        }
+
+        ::rui::lens(#binding, FieldLens)
+    }}
+}
+
+/// Generates a binding for each field of a struct.
+///
+/// For `struct Model { name: String }`, this generates a `ModelBinding`
+/// with a `name()` method returning `impl Binding<String>`, and a
+/// `ModelBindable` trait which adds `binding()` to any `Binding<Model>`.
+/// So with `model: State<Model>`, `model.binding().name()` is a binding
+/// to the name field which doesn't clone the rest of the model.
+#[proc_macro_derive(Bindable)]
+pub fn derive_bindable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let fields = match named_fields(&input, "Bindable") {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let binding_name = format_ident!("{}Binding", name);
+    let bindable_name = format_ident!("{}Bindable", name);
+
+    let methods = fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_vis = &field.vis;
+        let ty = &field.ty;
+        let lens = field_lens(name, field_name, ty, quote!(self.binding.clone()));
+        quote! {
+            #field_vis fn #field_name(&self) -> impl ::rui::Binding<#ty> {
+                #lens
+            }
+        }
+    });
+
+    let expanded = quote! {
+        /// Bindings to each field, generated by `#[derive(Bindable)]`.
+        #[derive(Clone)]
+        #vis struct #binding_name<B> {
+            binding: B,
+        }
+
+        impl<B: ::rui::Binding<#name>> #binding_name<B> {
+            #(#methods)*
+        }
+
+        /// Adds `binding()` to bindings, generated by `#[derive(Bindable)]`.
+        #vis trait #bindable_name: ::rui::Binding<#name> + Sized {
+            fn binding(&self) -> #binding_name<Self> {
+                #binding_name {
+                    binding: self.clone(),
+                }
+            }
+        }
+
+        impl<B: ::rui::Binding<#name>> #bindable_name for B {}
+    };
+
+    expanded.into()
+}
+
+/// Turns `font_size` into `Font size`.
+fn label(ident: &Ident) -> String {
+    let s = ident.to_string().replace('_', " ");
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => s,
+    }
+}
+
+/// What `#[inspect(...)]` says about a field.
+#[derive(Default)]
+struct InspectOptions {
+    group: Option<String>,
+    skip: bool,
+}
+
+fn inspect_options(field: &Field) -> Result<InspectOptions, syn::Error> {
+    let mut options = InspectOptions::default();
+    for attr in field.attrs.iter().filter(|a| a.path.is_ident("inspect")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected inspect(...)")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => options.skip = true,
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("group") => {
+                    match nv.lit {
+                        Lit::Str(s) => options.group = Some(s.value()),
+                        lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                    }
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected `skip` or `group = \"...\"`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Lets `inspector` edit a struct's fields, each of which must be a
+/// `Property`. Mark fields `#[inspect(group = "Name")]` to group them, or
+/// `#[inspect(skip)]` to leave them out.
+#[proc_macro_derive(Inspectable, attributes(inspect))]
+pub fn derive_inspectable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let fields = match named_fields(&input, "Inspectable") {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+    let name = &input.ident;
+
+    let mut rows = vec![];
+    for field in fields {
+        let options = match inspect_options(field) {
+            Ok(options) => options,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        if options.skip {
+            continue;
+        }
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let label = label(field_name);
+        let group = match options.group {
+            Some(group) => quote!(Some(#group.into())),
+            None => quote!(None),
+        };
+        let lens = field_lens(name, field_name, ty, quote!(binding.clone()));
+        rows.push(quote! {
+            ::rui::PropertyRow {
+                name: #label.into(),
+                group: #group,
+                editor: <#ty as ::rui::Property>::editor(#lens),
+            }
+        });
+    }
+
+    let expanded = quote! {
+        impl ::rui::Inspectable for #name {
+            fn properties<B: ::rui::Binding<Self>>(binding: B) -> Vec<::rui::PropertyRow> {
+                vec![#(#rows),*]
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Lets `inspector` edit an enum of unit variants with a `picker`. The
+/// enum must be `Clone` and `PartialEq`.
+#[proc_macro_derive(Property)]
+pub fn derive_property(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let variants = match &input.data {
+        Data::Enum(data) if data.variants.iter().all(|v| v.fields.is_empty()) => &data.variants,
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "Property can only be derived for enums without fields",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let options = variants.iter().map(|v| {
+        let variant = &v.ident;
+        let label = variant.to_string();
+        quote!((#label.to_string(), #name::#variant))
+    });
+
+    let expanded = quote! {
+        impl ::rui::Property for #name {
+            fn editor<B: ::rui::Binding<Self>>(binding: B) -> ::rui::AnyView {
+                ::rui::any_view(::rui::picker(binding, vec![#(#options),*]))
+            }
+        }
+    };
+
+    expanded.into()
+}
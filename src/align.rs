@@ -1,5 +1,6 @@
 use crate::*;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum HAlignment {
     Leading,
     Center,
@@ -15,10 +16,18 @@ pub fn align_h(child: LocalRect, parent: LocalRect, align: HAlignment) -> LocalO
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum VAlignment {
     Top,
     Middle,
     Bottom,
+
+    /// The baseline of the first line of text. Views without text use
+    /// their bottom edge.
+    FirstTextBaseline,
+
+    /// The baseline of the last line of text.
+    LastTextBaseline,
 }
 
 pub fn align_v(child: LocalRect, parent: LocalRect, align: VAlignment) -> LocalOffset {
@@ -26,7 +35,9 @@ pub fn align_v(child: LocalRect, parent: LocalRect, align: VAlignment) -> LocalO
     match align {
         VAlignment::Top => [c_off.x, parent.max_y() - child.max_y()].into(),
         VAlignment::Middle => c_off,
-        VAlignment::Bottom => [c_off.x, parent.min_y() - child.min_y()].into(),
+        VAlignment::Bottom | VAlignment::FirstTextBaseline | VAlignment::LastTextBaseline => {
+            [c_off.x, parent.min_y() - child.min_y()].into()
+        }
     }
 }
 
@@ -46,7 +57,9 @@ pub fn align(
         match valign {
             VAlignment::Top => parent.max_y() - child.max_y(),
             VAlignment::Middle => c_off.y,
-            VAlignment::Bottom => parent.min_y() - child.min_y(),
+            VAlignment::Bottom | VAlignment::FirstTextBaseline | VAlignment::LastTextBaseline => {
+                parent.min_y() - child.min_y()
+            }
         },
     )
 }
@@ -0,0 +1,215 @@
+use crate::*;
+use std::collections::HashMap;
+
+pub type HorizontalAlignment = HAlignment;
+pub type VerticalAlignment = VAlignment;
+
+/// A line which the views in a stack line up on. Horizontal alignments
+/// place the children of a `vstack` across it, and vertical alignments
+/// place the children of an `hstack`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    Horizontal(HAlignment),
+    Vertical(VAlignment),
+}
+
+impl From<HAlignment> for Alignment {
+    fn from(alignment: HAlignment) -> Self {
+        Alignment::Horizontal(alignment)
+    }
+}
+
+impl From<VAlignment> for Alignment {
+    fn from(alignment: VAlignment) -> Self {
+        Alignment::Vertical(alignment)
+    }
+}
+
+/// The size of a view, passed to `alignment_guide` functions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewDimensions {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl ViewDimensions {
+    /// Where the guide for `alignment` is unless it's moved: the leading
+    /// edge, center or trailing edge across, or the bottom, middle or top
+    /// up, as layout is y-up. Text sets its baselines, and the baselines
+    /// of views without text are their bottoms.
+    pub fn default_guide(&self, alignment: impl Into<Alignment>) -> f32 {
+        match alignment.into() {
+            Alignment::Horizontal(HAlignment::Leading) => 0.0,
+            Alignment::Horizontal(HAlignment::Center) => self.width / 2.0,
+            Alignment::Horizontal(HAlignment::Trailing) => self.width,
+            Alignment::Vertical(VAlignment::Bottom)
+            | Alignment::Vertical(VAlignment::FirstTextBaseline)
+            | Alignment::Vertical(VAlignment::LastTextBaseline) => 0.0,
+            Alignment::Vertical(VAlignment::Middle) => self.height / 2.0,
+            Alignment::Vertical(VAlignment::Top) => self.height,
+        }
+    }
+}
+
+impl From<LocalSize> for ViewDimensions {
+    fn from(size: LocalSize) -> Self {
+        Self {
+            width: size.width,
+            height: size.height,
+        }
+    }
+}
+
+/// Where the guide for `alignment` is in a view of `size` which has just
+/// been laid out, using the guides it reported.
+pub(crate) fn guide(cx: &Context, alignment: Alignment, size: LocalSize) -> f32 {
+    match cx.guides.get(&alignment) {
+        Some(value) => *value,
+        None => ViewDimensions::from(size).default_guide(alignment),
+    }
+}
+
+/// Moves guides reported by a child by where the child is in its parent.
+pub(crate) fn offset_guides(guides: &mut HashMap<Alignment, f32>, offset: LocalOffset) {
+    for (alignment, value) in guides.iter_mut() {
+        *value += match alignment {
+            Alignment::Horizontal(_) => offset.x,
+            Alignment::Vertical(_) => offset.y,
+        };
+    }
+}
+
+/// Struct for the `alignment_guide` modifier.
+pub struct AlignmentGuide<V, F> {
+    child: V,
+    alignment: Alignment,
+    compute: F,
+}
+
+impl<V, F> AlignmentGuide<V, F>
+where
+    V: View,
+    F: Fn(ViewDimensions) -> f32,
+{
+    pub fn new(child: V, alignment: Alignment, compute: F) -> Self {
+        Self {
+            child,
+            alignment,
+            compute,
+        }
+    }
+}
+
+impl<V, F> View for AlignmentGuide<V, F>
+where
+    V: View,
+    F: Fn(ViewDimensions) -> f32,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".alignment_guide({:?})", self.alignment);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.child.layout(id.child(&0), constraints, cx, vger);
+        let value = (self.compute)(size.into());
+        cx.guides.insert(self.alignment, value);
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, F> private::Sealed for AlignmentGuide<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alignment_guide() {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([100.0, 100.0].into());
+        let label = rectangle()
+            .size([20.0, 10.0])
+            .alignment_guide(HorizontalAlignment::Center, |d| d.width);
+        let view = vstack((
+            hstack((label, rectangle().size([20.0, 10.0]))),
+            rectangle().size([40.0, 10.0]),
+        ));
+        view.layout(cx.root_id, [100.0, 100.0].into(), &mut cx, &mut vger);
+
+        // The label's trailing edge, 35 along the row, is on the center line.
+        let row = cx.root_id.child(&0);
+        assert_eq!(cx.layout[&row.child(&0)].offset.x, 15.0);
+        assert_eq!(cx.layout[&row].offset.x, 15.0);
+        assert_eq!(cx.layout[&cx.root_id.child(&1)].offset.x, 30.0);
+    }
+
+    #[test]
+    fn test_first_text_baseline() {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([100.0, 100.0].into());
+        let view = hstack_aligned(
+            VerticalAlignment::FirstTextBaseline,
+            (text("small").font_size(10), text("large").font_size(20)),
+        );
+        view.layout(cx.root_id, [100.0, 100.0].into(), &mut cx, &mut vger);
+
+        // Descents are a fifth of the font size, so both baselines are at 44.
+        assert_eq!(cx.layout[&cx.root_id.child(&0)].offset.y, 42.0);
+        assert_eq!(cx.layout[&cx.root_id.child(&1)].offset.y, 40.0);
+    }
+}
@@ -0,0 +1,97 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(test)]
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+// Tests run in parallel, so they count their own thread's allocations.
+#[cfg(test)]
+thread_local! {
+    static THREAD_ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    #[cfg(test)]
+    THREAD_ALLOCATIONS.with(|count| count.set(count.get() + 1));
+}
+
+/// The system allocator, counting allocations so the inspector and
+/// `RUI_FRAME_TIME` can show how many each frame makes. Install it in your
+/// app with:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: rui::CountingAllocator = rui::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// How many allocations `CountingAllocator` has made, or 0 when it isn't
+/// installed.
+pub fn allocation_count() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Allocations made laying out `view`, after it's been laid out once.
+    fn layout_allocations(view: &impl View, cx: &mut Context, vger: &mut dyn Renderer) -> usize {
+        let size = LocalSize::new(400.0, 300.0);
+        cx.layout_root(view, size, vger);
+        let start = THREAD_ALLOCATIONS.with(|count| count.get());
+        cx.layout_root(view, size, vger);
+        THREAD_ALLOCATIONS.with(|count| count.get()) - start
+    }
+
+    #[test]
+    fn test_pooled_layout() {
+        let ui = vstack((
+            hstack((circle(), rectangle(), circle())),
+            hstack((rectangle().layout_priority(1.0), rectangle())),
+            zstack((circle(), rectangle())),
+        ));
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([400.0, 300.0].into());
+        let pooled = layout_allocations(&ui, &mut cx, &mut vger);
+
+        // Without the buffers kept from the last pass, every stack
+        // allocates its own.
+        cx.layout_root(&ui, [400.0, 300.0].into(), &mut vger);
+        let start = THREAD_ALLOCATIONS.with(|count| count.get());
+        cx.pool = Pool::default();
+        cx.layout_root(&ui, [400.0, 300.0].into(), &mut vger);
+        let unpooled = THREAD_ALLOCATIONS.with(|count| count.get()) - start;
+
+        assert!(allocation_count() > 0);
+        assert_eq!(pooled, 0);
+        assert!(unpooled > 0);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::*;
+use std::any::TypeId;
+
+/// Struct for `any_view`.
+pub struct AnyView {
+    child: Box<dyn View>,
+    type_id: TypeId,
+}
+
+impl AnyView {
+    pub fn new<V: View + 'static>(child: V) -> Self {
+        Self {
+            child: Box::new(child),
+            type_id: TypeId::of::<V>(),
+        }
+    }
+
+    // The child's ID depends on its type, so switching the type of
+    // view doesn't reuse state belonging to the old one.
+    fn child_id(&self, id: ViewID) -> ViewID {
+        id.child(&self.type_id)
+    }
+}
+
+impl View for AnyView {
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(self.child_id(id), cx)
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, self.child_id(id), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(self.child_id(id), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(self.child_id(id), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(self.child_id(id), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(self.child_id(id), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(self.child_id(id), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(self.child_id(id), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(self.child_id(id), cx, vger)
+    }
+}
+
+impl private::Sealed for AnyView {}
+
+/// Erases the type of a view. Useful for views which are built
+/// recursively, or whose type is chosen at runtime.
+pub fn any_view(view: impl View + 'static) -> AnyView {
+    AnyView::new(view)
+}
@@ -0,0 +1,150 @@
+use crate::*;
+
+/// How `aspect_ratio` sizes a view which doesn't have the ratio of the
+/// space it's offered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentMode {
+    /// The largest size which fits inside the space.
+    Fit,
+
+    /// The smallest size which covers the space, overflowing it.
+    Fill,
+}
+
+/// A size with `ratio` of width to height which fits or fills `sz`. An
+/// unbounded side is worked out from the other.
+fn conform(ratio: f32, sz: LocalSize, mode: ContentMode) -> LocalSize {
+    match (sz.width.is_finite(), sz.height.is_finite()) {
+        (true, true) => {
+            let by_width = LocalSize::new(sz.width, sz.width / ratio);
+            let by_height = LocalSize::new(sz.height * ratio, sz.height);
+            let fits = by_width.height <= sz.height;
+            match (mode, fits) {
+                (ContentMode::Fit, true) | (ContentMode::Fill, false) => by_width,
+                _ => by_height,
+            }
+        }
+        (true, false) => LocalSize::new(sz.width, sz.width / ratio),
+        (false, true) => LocalSize::new(sz.height * ratio, sz.height),
+        (false, false) => LocalSize::zero(),
+    }
+}
+
+/// Struct for the `aspect_ratio` modifier.
+pub struct AspectRatio<V> {
+    child: V,
+    ratio: f32,
+    mode: ContentMode,
+}
+
+impl<V> AspectRatio<V>
+where
+    V: View,
+{
+    pub fn new(child: V, ratio: f32, mode: ContentMode) -> Self {
+        Self { child, ratio, mode }
+    }
+}
+
+impl<V> View for AspectRatio<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".aspect_ratio({:?}, {:?})", self.ratio, self.mode);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        // Images inside fill their rect too, rather than fitting in it.
+        cx.with_env(self.mode, |cx| self.child.draw(id.child(&0), cx, vger))
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let sz = if constraints.has_bounded_width() || constraints.has_bounded_height() {
+            constraints.max
+        } else {
+            self.child
+                .intrinsic_size(id.child(&0), cx, vger)
+                .unwrap_or(constraints.max)
+        };
+        let size = conform(self.ratio, sz, self.mode);
+        cx.with_env(self.mode, |cx| {
+            self.child
+                .layout(id.child(&0), Constraints::tight(size), cx, vger)
+        });
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child
+            .intrinsic_size(id.child(&0), cx, vger)
+            .map(|size| conform(self.ratio, size, self.mode))
+    }
+}
+
+impl<V> private::Sealed for AspectRatio<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conform() {
+        let sz = LocalSize::new(200.0, 100.0);
+        assert_eq!(
+            conform(1.0, sz, ContentMode::Fit),
+            LocalSize::new(100.0, 100.0)
+        );
+        assert_eq!(
+            conform(1.0, sz, ContentMode::Fill),
+            LocalSize::new(200.0, 200.0)
+        );
+        assert_eq!(
+            conform(2.0, LocalSize::new(50.0, f32::INFINITY), ContentMode::Fit),
+            LocalSize::new(50.0, 25.0)
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::*;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Where an `async_view`'s data is up to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AsyncState<T, E> {
+    Loading,
+    Ready(T),
+    Error(E),
+}
+
+#[derive(Clone)]
+struct AsyncViewState<T, E> {
+    result: AsyncState<T, E>,
+
+    /// The running task. The view's task scope aborts it when the view
+    /// goes away.
+    task: Option<TaskHandle>,
+
+    /// Incremented when a task starts, so a task which finishes as it's
+    /// replaced doesn't set the result.
+    generation: usize,
+}
+
+impl<T, E> Default for AsyncViewState<T, E> {
+    fn default() -> Self {
+        Self {
+            result: AsyncState::Loading,
+            task: None,
+            generation: 0,
+        }
+    }
+}
+
+/// Passed to an `async_view`'s body to load the data again, for example
+/// from a button shown with an error.
+#[derive(Clone)]
+pub struct Retry {
+    retry: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl Retry {
+    /// Goes back to loading and runs the future again.
+    pub fn retry(&self) {
+        (self.retry)()
+    }
+}
+
+/// Struct for `async_view`.
+pub struct AsyncView<L, F> {
+    load: L,
+    body: F,
+}
+
+impl<T, E, Fut, L, V, F> AsyncView<L, F>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+    L: Fn() -> Fut,
+    V: View,
+    F: Fn(AsyncState<T, E>, Retry) -> V,
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<AsyncViewState<T, E>> {
+        cx.with_state(
+            AsyncViewState::default(),
+            id,
+            |state: State<AsyncViewState<T, E>>, _cx| state,
+        )
+    }
+
+    /// Runs the future on another thread, unless it's already running or
+    /// has finished.
+    fn start(&self, id: ViewID, cx: &mut Context) {
+        let state = self.state(id, cx);
+        if state.with(|s| s.task.is_some()) {
+            return;
+        }
+        let future = (self.load)();
+        let generation = state.with(|s| s.generation) + 1;
+        state.with_mut(|s| {
+            s.result = AsyncState::Loading;
+            s.generation = generation;
+        });
+
+        // The task doesn't keep the state alive.
+        let weak = state.downgrade();
+        let task = cx.spawn(id, async move {
+            let result = future.await;
+            if let Some(state) = weak.upgrade() {
+                if state.with(|s| s.generation) == generation {
+                    state.with_mut(|s| {
+                        s.result = match result {
+                            Ok(value) => AsyncState::Ready(value),
+                            Err(err) => AsyncState::Error(err),
+                        }
+                    });
+                }
+            }
+        });
+        state.with_mut(|s| s.task = Some(task));
+    }
+
+    fn body(&self, id: ViewID, cx: &mut Context) -> V {
+        let state = self.state(id, cx);
+        let result = state.with(|s| s.result.clone());
+        let weak = state.downgrade();
+        let retry = Retry {
+            retry: Arc::new(move || {
+                if let Some(state) = weak.upgrade() {
+                    // The next layout starts another task.
+                    state.with_mut(|s| {
+                        if let Some(task) = s.task.take() {
+                            task.abort();
+                        }
+                        s.result = AsyncState::Loading;
+                    });
+                }
+            }),
+        };
+        (self.body)(result, retry)
+    }
+}
+
+impl<T, E, Fut, L, V, F> View for AsyncView<L, F>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+    L: Fn() -> Fut,
+    V: View,
+    F: Fn(AsyncState<T, E>, Retry) -> V,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("async_view(");
+        self.body(id, cx).print(id.child(&0), cx);
+        println!(")");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(id, cx).process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(id, cx).draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.start(id, cx);
+        self.body(id, cx)
+            .layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.body(id, cx).hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.body(id, cx).commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            AsyncViewState::default(),
+            id,
+            map,
+            |state: State<AsyncViewState<T, E>>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+        self.body(id, cx).gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.body(id, cx).access(id.child(&0), cx, nodes)
+    }
+}
+
+impl<L, F> private::Sealed for AsyncView<L, F> {}
+
+/// Shows data which loads in the background. `load` makes a future which
+/// runs on another thread when the view first appears, and `body` makes
+/// the view for where it's up to: `Loading`, `Ready` with the value, or
+/// `Error`. `body` is also passed a `Retry` to run `load` again. The
+/// future is aborted if the view goes away before it finishes.
+pub fn async_view<T, E, Fut, L, V, F>(load: L, body: F) -> AsyncView<L, F>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+    L: Fn() -> Fut + 'static,
+    V: View,
+    F: Fn(AsyncState<T, E>, Retry) -> V + 'static,
+{
+    AsyncView { load, body }
+}
@@ -0,0 +1,152 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Numbers and flags which fit in an `Atomic`.
+pub trait AtomicValue: Copy + Send + Sync + 'static {
+    fn to_bits(self) -> u64;
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! impl_atomic_int {
+    ($($t:ty),*) => {
+        $(
+            impl AtomicValue for $t {
+                fn to_bits(self) -> u64 {
+                    self as u64
+                }
+                fn from_bits(bits: u64) -> Self {
+                    bits as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+impl AtomicValue for bool {
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+    fn from_bits(bits: u64) -> Self {
+        bits != 0
+    }
+}
+
+impl AtomicValue for f32 {
+    fn to_bits(self) -> u64 {
+        f32::to_bits(self) as u64
+    }
+    fn from_bits(bits: u64) -> Self {
+        f32::from_bits(bits as u32)
+    }
+}
+
+impl AtomicValue for f64 {
+    fn to_bits(self) -> u64 {
+        f64::to_bits(self)
+    }
+    fn from_bits(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+}
+
+/// A number or flag which any thread can read and write without taking
+/// a lock, like a progress value updated by a worker. Setting it redraws
+/// the app. Only available with the `sync` feature.
+///
+/// `with_mut` reads and writes the value separately, so use `update` to
+/// change it from several threads at once.
+pub struct Atomic<T> {
+    bits: Arc<AtomicU64>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: AtomicValue> Atomic<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            bits: Arc::new(AtomicU64::new(value.to_bits())),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Changes the value with `f` in one step, even if other threads
+    /// change it at the same time. `f` may be called more than once.
+    pub fn update(&self, f: impl Fn(T) -> T) {
+        let _ = self
+            .bits
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |bits| {
+                Some(f(T::from_bits(bits)).to_bits())
+            });
+        redraw_app()
+    }
+}
+
+impl<T> Clone for Atomic<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: AtomicValue> Binding<T> for Atomic<T> {
+    fn with<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        note_untracked_read();
+        f(&self.get())
+    }
+    fn with_mut<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        let mut value = self.get();
+        let result = f(&mut value);
+        self.set(value);
+        result
+    }
+    fn get(&self) -> T {
+        note_untracked_read();
+        T::from_bits(self.bits.load(Ordering::SeqCst))
+    }
+    fn set(&self, value: T) {
+        self.bits.store(value.to_bits(), Ordering::SeqCst);
+        redraw_app()
+    }
+    fn redraw(&self) {
+        redraw_app()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_atomic() {
+        let progress = Atomic::new(0.0f32);
+        let p = progress.clone();
+        thread::spawn(move || p.set(0.5)).join().unwrap();
+        assert_eq!(progress.get(), 0.5);
+
+        let count = Atomic::new(0i32);
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let count = count.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        count.update(|n| n + 1);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        assert_eq!(count.get(), 400);
+
+        let negative = Atomic::new(-3i64);
+        negative.with_mut(|n| *n *= 2);
+        assert_eq!(negative.get(), -6);
+    }
+}
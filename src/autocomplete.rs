@@ -0,0 +1,419 @@
+use crate::*;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Default delay after the last edit before suggestions are requested.
+pub const AUTOCOMPLETE_DEBOUNCE: Duration = Duration::from_millis(200);
+const AUTOCOMPLETE_ROW_HEIGHT: f32 = 24.0;
+const AUTOCOMPLETE_PADDING: f32 = 6.0;
+const AUTOCOMPLETE_MAX_ROWS: usize = 8;
+
+type SuggestionSource = Arc<dyn Fn(String) -> Vec<String> + Send + Sync>;
+
+/// Where `query` first appears in `text`, ignoring case.
+fn find_match(text: &str, query: &str) -> Option<Range<usize>> {
+    if query.is_empty() {
+        return None;
+    }
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+    text.char_indices().find_map(|(start, _)| {
+        let mut end = start;
+        let mut chars = text[start..].chars();
+        for q in query.chars() {
+            match chars.next() {
+                Some(c) if same(c, q) => end += c.len_utf8(),
+                _ => return None,
+            }
+        }
+        Some(start..end)
+    })
+}
+
+/// Orders `suggestions` by how well they match `query`: those starting
+/// with it first, then those with a word starting with it, then those
+/// containing it anywhere, then the rest. Keeps the order within each.
+fn rank(query: &str, mut suggestions: Vec<String>) -> Vec<String> {
+    suggestions.sort_by_key(|s| match find_match(s, query) {
+        Some(r) if r.start == 0 => 0,
+        Some(r) if s[..r.start].ends_with(|c: char| !c.is_alphanumeric()) => 1,
+        Some(_) => 2,
+        None => 3,
+    });
+    suggestions
+}
+
+#[derive(Clone)]
+struct AutocompleteState {
+    field: TextFieldState,
+    suggestions: Vec<String>,
+
+    /// The suggestion chosen with the arrow keys.
+    selected: Option<usize>,
+
+    /// Incremented on each edit, so results for older queries are dropped.
+    generation: Arc<AtomicUsize>,
+}
+
+impl AutocompleteState {
+    fn new() -> Self {
+        Self {
+            field: TextFieldState::new(),
+            suggestions: vec![],
+            selected: None,
+            generation: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Completer {
+    source: SuggestionSource,
+    delay: Duration,
+}
+
+impl Completer {
+    /// Asks for suggestions on a background thread after the debounce
+    /// delay. Any request still pending is cancelled.
+    fn schedule(&self, s: &State<AutocompleteState>, query: String) {
+        let generation = s.with(|s| s.generation.clone());
+        let current = generation.fetch_add(1, Ordering::SeqCst) + 1;
+        if query.is_empty() {
+            s.with_mut(|s| {
+                s.suggestions.clear();
+                s.selected = None;
+            });
+            return;
+        }
+        let completer = self.clone();
+        let s = s.clone();
+
+        thread::spawn(move || {
+            thread::sleep(completer.delay);
+            if generation.load(Ordering::SeqCst) != current {
+                return;
+            }
+            let results = rank(&query, (completer.source)(query.clone()));
+            if generation.load(Ordering::SeqCst) == current {
+                s.with_mut(|s| {
+                    s.suggestions = results;
+                    s.selected = None;
+                });
+            }
+        });
+    }
+
+    /// Fills in the field with `value` and closes the suggestions.
+    fn accept(&self, s: &State<AutocompleteState>, text: &impl Binding<String>, value: String) {
+        s.with(|s| s.generation.fetch_add(1, Ordering::SeqCst));
+        let len = value.len();
+        text.set(value);
+        s.with_mut(|s| {
+            s.field.cursor = len;
+            s.suggestions.clear();
+            s.selected = None;
+        });
+    }
+}
+
+/// The list of suggestions, which drops down below the field it's
+/// stacked with. Fills the space it's offered, but only hits its rows.
+struct SuggestionPopup<F> {
+    suggestions: Vec<String>,
+    query: String,
+    selected: Option<usize>,
+    on_pick: F,
+}
+
+impl<F> SuggestionPopup<F>
+where
+    F: Fn(usize),
+{
+    fn rows(&self, id: ViewID, cx: &mut Context) -> Vec<LocalRect> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        (0..self.suggestions.len().min(AUTOCOMPLETE_MAX_ROWS))
+            .map(|i| {
+                LocalRect::new(
+                    [
+                        rect.min_x(),
+                        rect.min_y() - (i + 1) as f32 * AUTOCOMPLETE_ROW_HEIGHT,
+                    ]
+                    .into(),
+                    [rect.width(), AUTOCOMPLETE_ROW_HEIGHT].into(),
+                )
+            })
+            .collect()
+    }
+
+    fn hit(&self, pt: LocalPoint, id: ViewID, cx: &mut Context) -> Option<usize> {
+        self.rows(id, cx).iter().position(|r| r.contains(pt))
+    }
+}
+
+impl<F> View for SuggestionPopup<F>
+where
+    F: Fn(usize),
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("SuggestionPopup({:?})", self.suggestions);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, _vger: &mut dyn Renderer) {
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } if self.hit(event.position, id, cx).is_some() => {
+                cx.touches[*touch] = id;
+            }
+            EventKind::TouchMove { id: 0 } if self.hit(event.position, id, cx).is_some() => {
+                cx.set_cursor(CursorIcon::Hand)
+            }
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                if let Some(i) = self.hit(event.position, id, cx) {
+                    (self.on_pick)(i);
+                }
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rows = self.rows(id, cx);
+        if let (Some(first), Some(last)) = (rows.first(), rows.last()) {
+            let menu = first.union(last);
+            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            vger.fill_rect(menu, 4.0, paint);
+            let paint = vger.color_paint(GROOVES);
+            vger.stroke_rect(menu.min(), menu.max(), 4.0, 1.0, paint);
+        }
+
+        for (i, r) in rows.iter().enumerate() {
+            if self.selected == Some(i) {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
+                vger.fill_rect(*r, 4.0, paint);
+            }
+
+            // Draw the suggestion in pieces, with the match highlighted.
+            let suggestion = &self.suggestions[i];
+            let matched = find_match(suggestion, &self.query).unwrap_or(0..0);
+            let pieces = [
+                (&suggestion[..matched.start], TEXT_COLOR),
+                (&suggestion[matched.clone()], AZURE_HIGHLIGHT),
+                (&suggestion[matched.end..], TEXT_COLOR),
+            ];
+            let bounds = vger.text_bounds(suggestion, Text::DEFAULT_SIZE, None);
+            let mut x = r.min_x() + AUTOCOMPLETE_PADDING;
+            for (piece, color) in pieces {
+                if piece.is_empty() {
+                    continue;
+                }
+                vger.save();
+                vger.translate([x, r.center().y - bounds.height() / 2.0 - bounds.origin.y].into());
+                vger.text(piece, Text::DEFAULT_SIZE, color, None);
+                vger.restore();
+                x += vger.text_bounds(piece, Text::DEFAULT_SIZE, None).max_x();
+            }
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.hit(pt, id, cx).map(|_| id)
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::ListBox));
+        Some(aid)
+    }
+}
+
+impl<F> private::Sealed for SuggestionPopup<F> {}
+
+/// Struct for `autocomplete`.
+pub struct Autocomplete<B> {
+    text: B,
+    completer: Completer,
+}
+
+impl<B> Autocomplete<B>
+where
+    B: Binding<String>,
+{
+    /// Sets how long to wait after the last edit before asking for
+    /// suggestions.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.completer.delay = delay;
+        self
+    }
+
+    fn body(&self) -> impl View {
+        let text = self.text.clone();
+        let completer = self.completer.clone();
+        read_env(move |size: ControlSize| {
+            let text = text.clone();
+            let completer = completer.clone();
+            focus(move |has_focus| {
+                let text = text.clone();
+                let completer = completer.clone();
+                state(AutocompleteState::new(), move |s| {
+                    let t = text.get();
+                    let (cursor, suggestions, selected) =
+                        s.with(|s| (s.field.cursor, s.suggestions.clone(), s.selected));
+                    let count = suggestions.len().min(AUTOCOMPLETE_MAX_ROWS);
+
+                    let (s2, text2, completer2) = (s.clone(), text.clone(), completer.clone());
+                    let picks = suggestions.clone();
+                    let field = renderer_canvas(move |rect, vger| {
+                        draw_text_field(rect, &t, cursor, has_focus, size, vger)
+                    })
+                    .key(move |k| {
+                        if !has_focus {
+                            return;
+                        }
+                        let selected = selected.filter(|i| *i < count);
+                        match k {
+                            KeyPress::ArrowDown if count > 0 => {
+                                let i = selected.map(|i| (i + 1).min(count - 1)).unwrap_or(0);
+                                s2.with_mut(|s| s.selected = Some(i));
+                            }
+                            KeyPress::ArrowUp if selected.is_some() => {
+                                s2.with_mut(|s| {
+                                    s.selected = selected.and_then(|i| i.checked_sub(1))
+                                });
+                            }
+                            KeyPress::Enter => {
+                                if let Some(i) = selected {
+                                    completer2.accept(&s2, &text2, suggestions[i].clone());
+                                }
+                            }
+                            KeyPress::Escape => {
+                                s2.with(|s| s.generation.fetch_add(1, Ordering::SeqCst));
+                                s2.with_mut(|s| {
+                                    s.suggestions.clear();
+                                    s.selected = None;
+                                });
+                            }
+                            _ => {
+                                let before = text2.get();
+                                s2.with_mut(|s| s.field.key(&k, &text2));
+                                let after = text2.get();
+                                if after != before {
+                                    completer2.schedule(&s2, after);
+                                }
+                            }
+                        }
+                    });
+
+                    let query = text.get();
+                    let (text, completer) = (text.clone(), completer.clone());
+                    zstack((
+                        field,
+                        cond(
+                            has_focus && count > 0,
+                            SuggestionPopup {
+                                suggestions: picks.clone(),
+                                query,
+                                selected,
+                                on_pick: move |i: usize| {
+                                    completer.accept(&s, &text, picks[i].clone())
+                                },
+                            },
+                            EmptyView {},
+                        ),
+                    ))
+                })
+            })
+        })
+    }
+}
+
+impl<B> View for Autocomplete<B>
+where
+    B: Binding<String>,
+{
+    body_view!();
+}
+
+impl<B> private::Sealed for Autocomplete<B> {}
+
+/// A text field which suggests completions as you type. Once typing
+/// pauses, `suggestions` is called with the text on a background thread,
+/// and its results are shown below the field, best matches first, with
+/// the typed text highlighted. Results for text which has since changed
+/// are dropped. The arrow keys choose a suggestion, enter accepts it and
+/// escape closes the list.
+pub fn autocomplete<F: Fn(String) -> Vec<String> + Send + Sync + 'static>(
+    text: impl Binding<String>,
+    suggestions: F,
+) -> Autocomplete<impl Binding<String>> {
+    Autocomplete {
+        text,
+        completer: Completer {
+            source: Arc::new(suggestions),
+            delay: AUTOCOMPLETE_DEBOUNCE,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_match() {
+        assert_eq!(find_match("Hello World", "wor"), Some(6..9));
+        assert_eq!(find_match("Hello", "xyz"), None);
+        assert_eq!(find_match("Hello", ""), None);
+    }
+
+    #[test]
+    fn test_rank() {
+        let ranked = rank(
+            "ap",
+            vec![
+                "grape".into(),
+                "Green apple".into(),
+                "Apricot".into(),
+                "kiwi".into(),
+            ],
+        );
+        assert_eq!(ranked, ["Apricot", "Green apple", "grape", "kiwi"]);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::*;
+use std::sync::Arc;
+
+/// Backgrounds for initials, picked by the initials so each person keeps
+/// the same color.
+pub const AVATAR_COLORS: [Color; 5] = [
+    AZURE_HIGHLIGHT_DARK,
+    RED_HIGHLIGHT_DARK,
+    Color::hex_const("#2A8A3D"),
+    Color::hex_const("#B37B00"),
+    Color::hex_const("#6A4CB3"),
+];
+
+/// Sizes for `avatar`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AvatarSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl AvatarSize {
+    /// The diameter of the avatar.
+    pub fn diameter(self) -> f32 {
+        match self {
+            AvatarSize::Small => 24.0,
+            AvatarSize::Medium => 40.0,
+            AvatarSize::Large => 64.0,
+        }
+    }
+
+    fn font_size(self) -> u32 {
+        (self.diameter() * 0.4) as u32
+    }
+}
+
+fn avatar_color(initials: &str) -> Color {
+    let hash = initials
+        .chars()
+        .fold(0usize, |h, c| h.wrapping_mul(31).wrapping_add(c as usize));
+    AVATAR_COLORS[hash % AVATAR_COLORS.len()]
+}
+
+#[derive(Clone, Default)]
+struct AvatarState {
+    /// The frame `square` was cropped from.
+    frame: Option<Arc<Frame>>,
+    square: Option<Arc<Frame>>,
+}
+
+/// Struct for `avatar`.
+pub struct Avatar {
+    source: FrameSource,
+    initials: String,
+    size: AvatarSize,
+}
+
+impl Avatar {
+    /// Sets the size of the avatar.
+    pub fn size(self, size: AvatarSize) -> Self {
+        Self { size, ..self }
+    }
+
+    /// The latest frame cropped to a square, which is kept so the renderer
+    /// can reuse its texture.
+    fn square(&self, id: ViewID, cx: &mut Context) -> (Option<Arc<Frame>>, bool) {
+        let (frame, live) = self.source.poll();
+        let state = cx.with_state(AvatarState::default(), id, |s: State<AvatarState>, _cx| s);
+        let cropped = state.with(|s| match (&frame, &s.frame) {
+            (Some(frame), Some(old)) => Arc::ptr_eq(frame, old),
+            (None, None) => true,
+            _ => false,
+        });
+        if !cropped {
+            let square = frame.as_ref().map(|f| Arc::new(f.crop_square()));
+            state.with_mut(|s| {
+                s.frame = frame.clone();
+                s.square = square;
+            });
+        }
+        (state.with(|s| s.square.clone()), live)
+    }
+}
+
+impl View for Avatar {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("avatar({:?})", self.initials);
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {}
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let radius = rect.width().min(rect.height()) / 2.0;
+        let circle = LocalRect::new(
+            rect.center() - LocalOffset::new(radius, radius),
+            [2.0 * radius, 2.0 * radius].into(),
+        );
+
+        let (square, live) = self.square(id, cx);
+        match square {
+            Some(frame) => vger.draw_frame(id, &frame, circle, radius),
+            None => {
+                // Show the initials while loading, or if the image failed.
+                let paint = vger.color_paint(avatar_color(&self.initials));
+                vger.fill_circle(circle.center(), radius, paint);
+
+                let font_size = self.size.font_size();
+                let bounds = vger.text_bounds(&self.initials, font_size, None);
+                vger.save();
+                vger.translate(circle.center() - bounds.center());
+                vger.text(&self.initials, font_size, TEXT_COLOR, None);
+                vger.restore();
+            }
+        }
+
+        if live {
+            if let Some(window) = &cx.window {
+                window.request_redraw();
+            }
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let d = self.size.diameter();
+        let size = LocalSize::new(d, d);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if (pt - rect.center()).length() < rect.width() / 2.0 {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            AvatarState::default(),
+            id,
+            map,
+            |state: State<AvatarState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Image));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for Avatar {}
+
+/// A picture of someone, cropped to a circle. Until `image` has loaded,
+/// or if it fails to, `initials` are shown on a colored circle instead.
+/// Use `FrameSource::load` to load the image in the background.
+pub fn avatar(image: FrameSource, initials: &str) -> Avatar {
+    Avatar {
+        source: image,
+        initials: initials.into(),
+        size: AvatarSize::Medium,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avatar_color() {
+        assert_eq!(avatar_color("AB"), avatar_color("AB"));
+        assert_eq!(avatar_color(""), AVATAR_COLORS[0]);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::*;
+use std::sync::Arc;
+
+/// Where `rui` draws frames: the GPU, or the CPU when there's no suitable
+/// GPU adapter.
+pub(crate) trait Backend {
+    /// The renderer views measure text with between frames.
+    fn renderer(&mut self) -> &mut dyn Renderer;
+
+    /// Called when the window's size changes, in physical pixels.
+    fn resize(&mut self, size: PhysicalSize<u32>);
+
+    /// Called when the app is sent to the background.
+    fn suspend(&mut self) {}
+
+    /// Called when the app returns from the background.
+    fn resume(&mut self, _window: &Window) {}
+
+    /// Draws a frame with `draw` and presents it.
+    fn frame(
+        &mut self,
+        width: f32,
+        height: f32,
+        scale: f32,
+        draw: &mut dyn FnMut(&mut dyn Renderer),
+    );
+
+    /// Number of distinct paints used in the last frame.
+    fn paint_count(&self) -> usize {
+        0
+    }
+}
+
+/// Draws with vger on the GPU.
+pub(crate) struct GpuBackend {
+    instance: wgpu::Instance,
+
+    /// Mobile platforms take the window's surface away while the app is in
+    /// the background, and Android has none until the app first resumes.
+    surface: Option<wgpu::Surface>,
+    device: Arc<wgpu::Device>,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+    vger: VgerRenderer,
+}
+
+impl GpuBackend {
+    /// Returns `None` if there's no suitable GPU adapter.
+    pub(crate) async fn new(window: &Window) -> Option<Self> {
+        // log::info!("Initializing the surface...");
+
+        let backend = wgpu::util::backend_bits_from_env().unwrap_or_else(wgpu::Backends::all);
+
+        let instance = wgpu::Instance::new(backend);
+        let size = window.inner_size();
+
+        // Android has no window to draw into until the app resumes, so the
+        // surface is created then.
+        let surface = if cfg!(target_os = "android") {
+            None
+        } else {
+            Some(unsafe { instance.create_surface(&window) })
+        };
+        let adapter = wgpu::util::initialize_adapter_from_env_or_default(
+            &instance,
+            backend,
+            surface.as_ref(),
+        )
+        .await?;
+
+        let adapter_info = adapter.get_info();
+        println!("Using {} ({:?})", adapter_info.name, adapter_info.backend);
+
+        let trace_dir = std::env::var("WGPU_TRACE");
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    features: wgpu::Features::default(),
+                    limits: wgpu::Limits::default(),
+                },
+                trace_dir.ok().as_ref().map(std::path::Path::new),
+            )
+            .await
+            .ok()?;
+
+        // Without a surface to ask, pick a format Android's GPUs all have.
+        let format = match &surface {
+            Some(surface) => surface.get_preferred_format(&adapter).unwrap(),
+            None => wgpu::TextureFormat::Rgba8UnormSrgb,
+        };
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: wgpu::PresentMode::Mailbox,
+        };
+        if let Some(surface) = &surface {
+            surface.configure(&device, &config);
+        }
+
+        let device = Arc::new(device);
+        let mut vger = VgerRenderer::new(VGER::new(&device, format));
+        vger.enable_layers(device.clone(), format);
+
+        Some(Self {
+            instance,
+            surface,
+            device,
+            queue,
+            config,
+            vger,
+        })
+    }
+}
+
+impl Backend for GpuBackend {
+    fn renderer(&mut self) -> &mut dyn Renderer {
+        &mut self.vger
+    }
+
+    fn resize(&mut self, size: PhysicalSize<u32>) {
+        self.config.width = size.width.max(1);
+        self.config.height = size.height.max(1);
+        if let Some(surface) = &self.surface {
+            surface.configure(&self.device, &self.config);
+        }
+    }
+
+    fn suspend(&mut self) {
+        self.surface = None;
+    }
+
+    fn resume(&mut self, window: &Window) {
+        if self.surface.is_none() {
+            // The window may have changed size while there was no surface.
+            let size = window.inner_size();
+            self.config.width = size.width.max(1);
+            self.config.height = size.height.max(1);
+
+            let surface = unsafe { self.instance.create_surface(window) };
+            surface.configure(&self.device, &self.config);
+            self.surface = Some(surface);
+        }
+    }
+
+    fn frame(
+        &mut self,
+        width: f32,
+        height: f32,
+        scale: f32,
+        draw: &mut dyn FnMut(&mut dyn Renderer),
+    ) {
+        let surface = match &self.surface {
+            Some(surface) => surface,
+            None => return,
+        };
+
+        let frame = match surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(_) => {
+                surface.configure(&self.device, &self.config);
+                surface
+                    .get_current_texture()
+                    .expect("Failed to acquire next surface texture!")
+            }
+        };
+
+        self.vger.begin(width, height, scale);
+        draw(&mut self.vger);
+
+        let texture_view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let desc = wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: &texture_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        };
+
+        self.vger.encode(&self.device, &desc, &self.queue);
+
+        frame.present();
+    }
+
+    fn paint_count(&self) -> usize {
+        self.vger.paint_count()
+    }
+}
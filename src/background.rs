@@ -18,18 +18,25 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.process(event, id.child(&0), cx, vger);
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.background.draw(id.child(&1), cx, vger);
         self.child.draw(id.child(&0), cx, vger);
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        let child_size = self.child.layout(id.child(&0), sz, cx, vger);
-        self.background.layout(id.child(&1), child_size, cx, vger);
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let child_size = self.child.layout(id.child(&0), constraints, cx, vger);
+        self.background
+            .layout(id.child(&1), Constraints::tight(child_size), cx, vger);
         child_size
     }
 
@@ -38,7 +45,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.background.hittest(id.child(&1), pt, cx, vger)
     }
@@ -63,6 +70,19 @@ where
         //      could print a warning if there is an node produced by background.
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
 }
 
 impl<V, BG> Background<V, BG>
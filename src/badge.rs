@@ -0,0 +1,294 @@
+use crate::*;
+
+pub const CAPSULE_FONT_SIZE: u32 = 12;
+const CAPSULE_PADDING: f32 = 5.0;
+
+/// Counts above this are shown as "99+".
+pub const BADGE_MAX_COUNT: usize = 99;
+
+/// Something which can be shown in a `badge`.
+pub trait BadgeLabel {
+    /// The badge text. An empty string hides the badge.
+    fn badge_text(&self) -> String;
+}
+
+impl BadgeLabel for usize {
+    fn badge_text(&self) -> String {
+        match *self {
+            0 => String::new(),
+            n if n > BADGE_MAX_COUNT => format!("{}+", BADGE_MAX_COUNT),
+            n => n.to_string(),
+        }
+    }
+}
+
+impl BadgeLabel for u32 {
+    fn badge_text(&self) -> String {
+        (*self as usize).badge_text()
+    }
+}
+
+impl BadgeLabel for i32 {
+    fn badge_text(&self) -> String {
+        (*self.max(&0) as usize).badge_text()
+    }
+}
+
+impl BadgeLabel for &str {
+    fn badge_text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl BadgeLabel for String {
+    fn badge_text(&self) -> String {
+        self.clone()
+    }
+}
+
+fn capsule_size(text: &str, vger: &mut dyn Renderer) -> LocalSize {
+    let bounds = vger.text_bounds(text, CAPSULE_FONT_SIZE, None);
+    let height = bounds.height() + 2.0 * CAPSULE_PADDING;
+    LocalSize::new((bounds.width() + 2.0 * CAPSULE_PADDING).max(height), height)
+}
+
+/// Draws a capsule containing `text`, centered at `center`.
+fn draw_capsule(
+    text: &str,
+    center: LocalPoint,
+    fill: Color,
+    text_color: Color,
+    vger: &mut dyn Renderer,
+) {
+    let size = capsule_size(text, vger);
+    let rect = LocalRect::new(center - size.to_vector() / 2.0, size);
+
+    let paint = vger.color_paint(fill);
+    vger.fill_rect(rect, size.height / 2.0, paint);
+
+    let bounds = vger.text_bounds(text, CAPSULE_FONT_SIZE, None);
+    vger.save();
+    vger.translate(center - bounds.center());
+    vger.text(text, CAPSULE_FONT_SIZE, text_color, None);
+    vger.restore();
+}
+
+/// Struct for `capsule`.
+pub struct Capsule {
+    text: String,
+    color: Color,
+    text_color: Color,
+}
+
+impl Capsule {
+    /// Sets the fill color.
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Sets the text color.
+    pub fn text_color(self, text_color: Color) -> Self {
+        Self { text_color, ..self }
+    }
+}
+
+impl View for Capsule {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("Capsule({:?})", self.text);
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        draw_capsule(&self.text, rect.center(), self.color, self.text_color, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = capsule_size(&self.text, vger);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {
+        // do nothing
+    }
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {
+        // do nothing
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::LabelText));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for Capsule {}
+
+/// A small rounded tag showing some text.
+pub fn capsule(text: &str) -> Capsule {
+    Capsule {
+        text: text.into(),
+        color: AZURE_HIGHLIGHT_DARK,
+        text_color: TEXT_COLOR,
+    }
+}
+
+/// Struct for the `badge` modifier.
+pub struct Badge<V> {
+    child: V,
+    text: String,
+    color: Color,
+    text_color: Color,
+}
+
+impl<V> Badge<V>
+where
+    V: View,
+{
+    pub fn new(child: V, label: impl BadgeLabel) -> Self {
+        Self {
+            child,
+            text: label.badge_text(),
+            color: RED_HIGHLIGHT,
+            text_color: TEXT_COLOR,
+        }
+    }
+
+    /// Sets the badge's fill color.
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Sets the badge's text color.
+    pub fn text_color(self, text_color: Color) -> Self {
+        Self { text_color, ..self }
+    }
+}
+
+impl<V> View for Badge<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("Badge({:?}) {{", self.text);
+        (self.child).print(id.child(&0), cx);
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger);
+
+        if !self.text.is_empty() {
+            // Centered on the top right corner of the child.
+            let rect = cx.layout.entry(id).or_default().rect;
+            let corner = LocalPoint::new(rect.max_x(), rect.max_y());
+            draw_capsule(&self.text, corner, self.color, self.text_color, vger);
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let child_size = self.child.layout(id.child(&0), constraints, cx, vger);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), child_size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        child_size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V> private::Sealed for Badge<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_text() {
+        assert_eq!(0usize.badge_text(), "");
+        assert_eq!(7u32.badge_text(), "7");
+        assert_eq!(100usize.badge_text(), "99+");
+        assert_eq!((-3).badge_text(), "");
+        assert_eq!("new".badge_text(), "new");
+    }
+}
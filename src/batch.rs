@@ -0,0 +1,521 @@
+use crate::*;
+use std::sync::Arc;
+
+/// Most shapes held back at once. Finding where a shape can go looks at
+/// those before it, so this keeps that quick.
+const MAX_PENDING: usize = 256;
+
+/// A shape held back by `BatchingRenderer`, in the coordinates the
+/// renderer started with.
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    FillRect {
+        rect: LocalRect,
+        radius: f32,
+    },
+    StrokeRect {
+        min: LocalPoint,
+        max: LocalPoint,
+        radius: f32,
+        width: f32,
+    },
+    FillCircle {
+        center: LocalPoint,
+        radius: f32,
+    },
+    StrokeSegment {
+        a: LocalPoint,
+        b: LocalPoint,
+        width: f32,
+    },
+    StrokeArc {
+        center: LocalPoint,
+        radius: f32,
+        width: f32,
+        rotation: f32,
+        aperture: f32,
+    },
+    StrokeBezier {
+        a: LocalPoint,
+        b: LocalPoint,
+        c: LocalPoint,
+        width: f32,
+    },
+}
+
+impl Shape {
+    /// Moves and scales the shape from coordinates at `offset` and `zoom`.
+    fn transform(self, offset: LocalOffset, zoom: f32) -> Self {
+        let p = |p: LocalPoint| (p * zoom) + offset;
+        match self {
+            Shape::FillRect { rect, radius } => Shape::FillRect {
+                rect: LocalRect::new(p(rect.origin), rect.size * zoom),
+                radius: radius * zoom,
+            },
+            Shape::StrokeRect {
+                min,
+                max,
+                radius,
+                width,
+            } => Shape::StrokeRect {
+                min: p(min),
+                max: p(max),
+                radius: radius * zoom,
+                width: width * zoom,
+            },
+            Shape::FillCircle { center, radius } => Shape::FillCircle {
+                center: p(center),
+                radius: radius * zoom,
+            },
+            Shape::StrokeSegment { a, b, width } => Shape::StrokeSegment {
+                a: p(a),
+                b: p(b),
+                width: width * zoom,
+            },
+            Shape::StrokeArc {
+                center,
+                radius,
+                width,
+                rotation,
+                aperture,
+            } => Shape::StrokeArc {
+                center: p(center),
+                radius: radius * zoom,
+                width: width * zoom,
+                rotation,
+                aperture,
+            },
+            Shape::StrokeBezier { a, b, c, width } => Shape::StrokeBezier {
+                a: p(a),
+                b: p(b),
+                c: p(c),
+                width: width * zoom,
+            },
+        }
+    }
+
+    /// A rect the shape is within.
+    fn bounds(&self) -> LocalRect {
+        let around = |points: &[LocalPoint], outset: f32| {
+            LocalRect::from_points(points).inflate(outset, outset)
+        };
+        match *self {
+            Shape::FillRect { rect, .. } => rect,
+            Shape::StrokeRect {
+                min, max, width, ..
+            } => around(&[min, max], width),
+            Shape::FillCircle { center, radius } => around(&[center], radius),
+            Shape::StrokeSegment { a, b, width } => around(&[a, b], width),
+            Shape::StrokeArc {
+                center,
+                radius,
+                width,
+                ..
+            } => around(&[center], radius + width),
+            Shape::StrokeBezier { a, b, c, width } => around(&[a, b, c], width),
+        }
+    }
+
+    fn draw(&self, paint: Paint, vger: &mut dyn Renderer) {
+        match *self {
+            Shape::FillRect { rect, radius } => vger.fill_rect(rect, radius, paint),
+            Shape::StrokeRect {
+                min,
+                max,
+                radius,
+                width,
+            } => vger.stroke_rect(min, max, radius, width, paint),
+            Shape::FillCircle { center, radius } => vger.fill_circle(center, radius, paint),
+            Shape::StrokeSegment { a, b, width } => vger.stroke_segment(a, b, width, paint),
+            Shape::StrokeArc {
+                center,
+                radius,
+                width,
+                rotation,
+                aperture,
+            } => vger.stroke_arc(center, radius, width, rotation, aperture, paint),
+            Shape::StrokeBezier { a, b, c, width } => vger.stroke_bezier(a, b, c, width, paint),
+        }
+    }
+}
+
+/// Shapes drawn with the same paint, which can be drawn one after the
+/// other.
+struct Batch {
+    paint: Paint,
+    key: PaintKey,
+    shapes: Vec<(Shape, LocalRect)>,
+    bounds: LocalRect,
+}
+
+impl Batch {
+    fn overlaps(&self, bounds: &LocalRect) -> bool {
+        self.bounds.intersects(bounds)
+            && self
+                .shapes
+                .iter()
+                .any(|(_, shape_bounds)| shape_bounds.intersects(bounds))
+    }
+}
+
+/// Draws through another renderer, holding back shapes filled or stroked
+/// with a solid color and drawing them grouped by color. Shapes only move
+/// past others they don't overlap, so what's drawn looks the same. The
+/// held back shapes are drawn in one go, in the coordinates the renderer
+/// started with, so the renderer underneath changes its transform and
+/// paint far less often.
+///
+/// Anything else which draws, like text, paths or images, or changes the
+/// clip, opacity or layer, draws the held back shapes first.
+pub struct BatchingRenderer<'a> {
+    inner: &'a mut dyn Renderer,
+
+    /// Where shapes go, relative to where the renderer started.
+    offset: LocalOffset,
+    zoom: f32,
+    opacity: f32,
+
+    /// Bumped when the clip changes, so `restore` knows to draw first.
+    clip: usize,
+    saved: Vec<(LocalOffset, f32, f32, usize)>,
+
+    batches: Vec<Batch>,
+    pending: usize,
+
+    /// For `stats`.
+    shapes_drawn: usize,
+    batches_drawn: usize,
+}
+
+impl<'a> BatchingRenderer<'a> {
+    pub fn new(inner: &'a mut dyn Renderer) -> Self {
+        Self {
+            inner,
+            offset: LocalOffset::zero(),
+            zoom: 1.0,
+            opacity: 1.0,
+            clip: 0,
+            saved: vec![],
+            batches: vec![],
+            pending: 0,
+            shapes_drawn: 0,
+            batches_drawn: 0,
+        }
+    }
+
+    /// How many shapes have been drawn, and in how many batches.
+    pub fn stats(&self) -> (usize, usize) {
+        (self.shapes_drawn, self.batches_drawn)
+    }
+
+    fn push(&mut self, shape: Shape, paint: Paint) {
+        if let Paint::Gradient { .. } = paint {
+            // Gradients are placed in the coordinates they're drawn in.
+            self.flush();
+            shape.draw(paint, self.inner);
+            return;
+        }
+
+        let shape = shape.transform(self.offset, self.zoom);
+        let bounds = shape.bounds();
+        let key = paint.key();
+
+        // Join the latest batch with the same paint, unless a shape drawn
+        // since is in the way.
+        let mut target = None;
+        for (i, batch) in self.batches.iter().enumerate().rev() {
+            if batch.key == key {
+                target = Some(i);
+                break;
+            }
+            if batch.overlaps(&bounds) {
+                break;
+            }
+        }
+        match target {
+            Some(i) => {
+                let batch = &mut self.batches[i];
+                batch.bounds = batch.bounds.union(&bounds);
+                batch.shapes.push((shape, bounds));
+            }
+            None => self.batches.push(Batch {
+                paint,
+                key,
+                shapes: vec![(shape, bounds)],
+                bounds,
+            }),
+        }
+
+        self.pending += 1;
+        if self.pending == MAX_PENDING {
+            self.flush();
+        }
+    }
+
+    /// Draws the shapes held back.
+    pub fn flush(&mut self) {
+        if self.batches.is_empty() {
+            return;
+        }
+        self.inner.save();
+        // Back to the coordinates the renderer started with.
+        self.inner.translate(-self.offset / self.zoom);
+        self.inner.scale(1.0 / self.zoom);
+        for batch in self.batches.drain(..) {
+            for (shape, _) in &batch.shapes {
+                shape.draw(batch.paint, self.inner);
+            }
+            self.shapes_drawn += batch.shapes.len();
+            self.batches_drawn += 1;
+        }
+        self.inner.restore();
+        self.pending = 0;
+    }
+}
+
+impl<'a> Drop for BatchingRenderer<'a> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl<'a> Renderer for BatchingRenderer<'a> {
+    fn save(&mut self) {
+        self.saved
+            .push((self.offset, self.zoom, self.opacity, self.clip));
+        self.inner.save()
+    }
+
+    fn restore(&mut self) {
+        if let Some((offset, zoom, opacity, clip)) = self.saved.pop() {
+            if opacity != self.opacity || clip != self.clip {
+                self.flush();
+            }
+            self.offset = offset;
+            self.zoom = zoom;
+            self.opacity = opacity;
+            self.clip = clip;
+        }
+        self.inner.restore()
+    }
+
+    fn save_depth(&self) -> usize {
+        self.saved.len()
+    }
+
+    fn translate(&mut self, offset: LocalOffset) {
+        self.offset += offset * self.zoom;
+        self.inner.translate(offset)
+    }
+
+    fn scale(&mut self, scale: f32) {
+        self.zoom *= scale;
+        self.inner.scale(scale)
+    }
+
+    fn clip(&mut self, rect: LocalRect) {
+        self.flush();
+        self.clip += 1;
+        self.inner.clip(rect)
+    }
+
+    fn opacity(&mut self, opacity: f32) {
+        self.flush();
+        self.opacity *= opacity;
+        self.inner.opacity(opacity)
+    }
+
+    fn begin_layer(&mut self, effects: LayerEffects) {
+        self.flush();
+        self.inner.begin_layer(effects)
+    }
+
+    fn end_layer(&mut self) {
+        self.flush();
+        self.inner.end_layer()
+    }
+
+    fn draw_cached(&mut self, key: ViewID, rect: LocalRect) -> bool {
+        self.flush();
+        self.inner.draw_cached(key, rect)
+    }
+
+    fn begin_cache(&mut self, key: ViewID, rect: LocalRect) {
+        self.flush();
+        self.inner.begin_cache(key, rect)
+    }
+
+    fn end_cache(&mut self) {
+        self.flush();
+        self.inner.end_cache()
+    }
+
+    fn to_world(&self, rect: LocalRect) -> Option<WorldRect> {
+        self.inner.to_world(rect)
+    }
+
+    fn color_paint(&mut self, color: Color) -> Paint {
+        self.inner.color_paint(color)
+    }
+
+    fn fill_rect(&mut self, rect: LocalRect, radius: f32, paint: Paint) {
+        self.push(Shape::FillRect { rect, radius }, paint)
+    }
+
+    fn stroke_rect(
+        &mut self,
+        min: LocalPoint,
+        max: LocalPoint,
+        radius: f32,
+        width: f32,
+        paint: Paint,
+    ) {
+        let shape = Shape::StrokeRect {
+            min,
+            max,
+            radius,
+            width,
+        };
+        self.push(shape, paint)
+    }
+
+    fn fill_circle(&mut self, center: LocalPoint, radius: f32, paint: Paint) {
+        self.push(Shape::FillCircle { center, radius }, paint)
+    }
+
+    fn stroke_segment(&mut self, a: LocalPoint, b: LocalPoint, width: f32, paint: Paint) {
+        self.push(Shape::StrokeSegment { a, b, width }, paint)
+    }
+
+    fn stroke_arc(
+        &mut self,
+        center: LocalPoint,
+        radius: f32,
+        width: f32,
+        rotation: f32,
+        aperture: f32,
+        paint: Paint,
+    ) {
+        let shape = Shape::StrokeArc {
+            center,
+            radius,
+            width,
+            rotation,
+            aperture,
+        };
+        self.push(shape, paint)
+    }
+
+    fn stroke_bezier(
+        &mut self,
+        a: LocalPoint,
+        b: LocalPoint,
+        c: LocalPoint,
+        width: f32,
+        paint: Paint,
+    ) {
+        self.push(Shape::StrokeBezier { a, b, c, width }, paint)
+    }
+
+    fn move_to(&mut self, p: LocalPoint) {
+        self.inner.move_to(p)
+    }
+
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint) {
+        self.inner.quad_to(b, c)
+    }
+
+    fn fill(&mut self, paint: Paint) {
+        self.flush();
+        self.inner.fill(paint)
+    }
+
+    fn set_font(&mut self, font: Option<&fonts::Font>) {
+        self.inner.set_font(font)
+    }
+
+    fn draw_frame(&mut self, key: ViewID, frame: &Arc<Frame>, rect: LocalRect, radius: f32) {
+        self.flush();
+        self.inner.draw_frame(key, frame, rect, radius)
+    }
+
+    fn text(&mut self, text: &str, size: u32, color: Color, max_width: Option<f32>) {
+        self.flush();
+        self.inner.text(text, size, color, max_width)
+    }
+
+    fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        self.inner.text_bounds(text, size, max_width)
+    }
+
+    fn glyph_positions(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LocalRect> {
+        self.inner.glyph_positions(text, size, max_width)
+    }
+
+    fn line_metrics(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LineMetrics> {
+        self.inner.line_metrics(text, size, max_width)
+    }
+
+    fn baselines(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<f32> {
+        self.inner.baselines(text, size, max_width)
+    }
+
+    fn vger(&mut self) -> Option<&mut VGER> {
+        self.flush();
+        self.inner.vger()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The order `colors` appear in `svg`.
+    fn color_order(svg: &str, colors: &[&str]) -> Vec<usize> {
+        let mut found: Vec<(usize, usize)> = vec![];
+        for (i, color) in colors.iter().enumerate() {
+            found.extend(svg.match_indices(color).map(|(at, _)| (at, i)));
+        }
+        found.sort();
+        found.into_iter().map(|(_, i)| i).collect()
+    }
+
+    fn draw(rects: &[(f32, Color)]) -> (String, (usize, usize)) {
+        let mut svg = SvgRenderer::new([400.0, 100.0].into());
+        let stats = {
+            let mut vger = BatchingRenderer::new(&mut svg);
+            for (x, color) in rects {
+                vger.save();
+                vger.translate([*x, 0.0].into());
+                let paint = vger.color_paint(*color);
+                vger.fill_rect(
+                    LocalRect::new([0.0, 0.0].into(), [50.0, 50.0].into()),
+                    0.0,
+                    paint,
+                );
+                vger.restore();
+            }
+            vger.flush();
+            vger.stats()
+        };
+        (svg.finish(), stats)
+    }
+
+    #[test]
+    fn test_batching() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+        let colors = ["#ff0000", "#0000ff"];
+
+        // Apart, the reds are drawn together.
+        let (svg, stats) = draw(&[(0.0, red), (100.0, blue), (200.0, red)]);
+        assert_eq!(color_order(&svg, &colors), vec![0, 0, 1]);
+        assert_eq!(stats, (3, 2));
+        assert!(svg.contains("x=\"200\""));
+
+        // Overlapping, they keep their order.
+        let (svg, stats) = draw(&[(0.0, red), (25.0, blue), (40.0, red)]);
+        assert_eq!(color_order(&svg, &colors), vec![0, 1, 0]);
+        assert_eq!(stats, (3, 3));
+    }
+}
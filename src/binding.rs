@@ -1,3 +1,10 @@
+use crate::{note_untracked_read, redraw_app, schedule_app};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 /// Reads or writes a value owned by a source-of-truth.
 pub trait Binding<S>: Clone + 'static {
     fn with<T, F: FnOnce(&S) -> T>(&self, f: F) -> T;
@@ -13,6 +20,155 @@ pub trait Binding<S>: Clone + 'static {
     fn set(&self, value: S) {
         self.with_mut(move |s| *s = value);
     }
+
+    /// Redraws the views which read the binding, without changing its
+    /// value. Does nothing for bindings which can't tell.
+    fn redraw(&self) {}
+
+    /// Holds back writes until none have been made for `delay`, then
+    /// passes on the last one from the app's event loop. Reads see the
+    /// held back value.
+    ///
+    /// The adapter keeps the writes it's holding back, so make it once and
+    /// keep it in `state` rather than in a view's body.
+    fn debounced(self, delay: Duration) -> RateLimited<Self, S>
+    where
+        Self: Send,
+        S: Clone + Send + 'static,
+    {
+        RateLimited::new(self, delay, false)
+    }
+
+    /// Passes on writes at most once every `interval`, holding back the
+    /// latest until the interval is up. Reads see the held back value.
+    ///
+    /// As with `debounced`, make the adapter once and keep it in `state`.
+    fn throttled(self, interval: Duration) -> RateLimited<Self, S>
+    where
+        Self: Send,
+        S: Clone + Send + 'static,
+    {
+        RateLimited::new(self, interval, true)
+    }
+}
+
+struct Pending<S> {
+    /// Written but not yet passed on.
+    value: Option<S>,
+
+    /// Incremented with each write, so only the timer for the latest
+    /// passes it on.
+    generation: usize,
+
+    /// When a write was last passed on.
+    last: Option<Instant>,
+}
+
+/// Binding for `Binding::debounced` and `Binding::throttled`.
+pub struct RateLimited<B, S> {
+    binding: B,
+    delay: Duration,
+    throttle: bool,
+    pending: Arc<Mutex<Pending<S>>>,
+}
+
+impl<B: Clone, S> Clone for RateLimited<B, S> {
+    fn clone(&self) -> Self {
+        Self {
+            binding: self.binding.clone(),
+            delay: self.delay,
+            throttle: self.throttle,
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<B, S> RateLimited<B, S>
+where
+    B: Binding<S> + Send,
+    S: Clone + Send + 'static,
+{
+    fn new(binding: B, delay: Duration, throttle: bool) -> Self {
+        Self {
+            binding,
+            delay,
+            throttle,
+            pending: Arc::new(Mutex::new(Pending {
+                value: None,
+                generation: 0,
+                last: None,
+            })),
+        }
+    }
+
+    fn write(&self, value: S) {
+        let now = Instant::now();
+        let mut pending = self.pending.lock().unwrap();
+        pending.generation += 1;
+        let generation = pending.generation;
+
+        let wait = if self.throttle {
+            let due = pending.last.map_or(now, |last| last + self.delay);
+            if due <= now {
+                pending.value = None;
+                pending.last = Some(now);
+                drop(pending);
+                self.binding.set(value);
+                return;
+            }
+            due - now
+        } else {
+            self.delay
+        };
+        pending.value = Some(value);
+        drop(pending);
+        self.binding.redraw();
+
+        let this = self.clone();
+        schedule_app(now + wait, move || this.flush(generation));
+    }
+
+    /// Passes on the held back value, if nothing's been written since
+    /// `generation`.
+    fn flush(&self, generation: usize) {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.generation != generation {
+            return;
+        }
+        pending.last = Some(Instant::now());
+        let value = pending.value.take();
+        drop(pending);
+        if let Some(value) = value {
+            self.binding.set(value);
+        }
+    }
+}
+
+impl<B, S> Binding<S> for RateLimited<B, S>
+where
+    B: Binding<S> + Send,
+    S: Clone + Send + 'static,
+{
+    fn with<T, F: FnOnce(&S) -> T>(&self, f: F) -> T {
+        let pending = self.pending.lock().unwrap().value.clone();
+        match pending {
+            Some(value) => {
+                // Still a read of the binding, which the value goes to.
+                self.binding.with(|_| ());
+                f(&value)
+            }
+            None => self.binding.with(f),
+        }
+    }
+    fn with_mut<T, F: FnOnce(&mut S) -> T>(&self, f: F) -> T {
+        let mut value = self.get();
+        let t = f(&mut value);
+        self.write(value);
+        t
+    }
+    fn redraw(&self) {
+        self.binding.redraw()
+    }
 }
 
 #[derive(Clone)]
@@ -39,6 +195,138 @@ where
     }
 }
 
+/// Binds to data shared with the rest of the app. Changing it redraws the
+/// app.
+impl<S: 'static> Binding<S> for Rc<RefCell<S>> {
+    fn with<T, F: FnOnce(&S) -> T>(&self, f: F) -> T {
+        note_untracked_read();
+        f(&self.borrow())
+    }
+    fn with_mut<T, F: FnOnce(&mut S) -> T>(&self, f: F) -> T {
+        let t = f(&mut self.borrow_mut());
+        redraw_app();
+        t
+    }
+    fn redraw(&self) {
+        redraw_app()
+    }
+}
+
+/// Binds to data shared with the rest of the app, which may be on other
+/// threads. Changing it redraws the app.
+impl<S: 'static> Binding<S> for Arc<Mutex<S>> {
+    fn with<T, F: FnOnce(&S) -> T>(&self, f: F) -> T {
+        note_untracked_read();
+        f(&self.lock().unwrap())
+    }
+    fn with_mut<T, F: FnOnce(&mut S) -> T>(&self, f: F) -> T {
+        let t = f(&mut self.lock().unwrap());
+        redraw_app();
+        t
+    }
+    fn redraw(&self) {
+        redraw_app()
+    }
+}
+
+/// Struct for `binding`.
+#[derive(Clone)]
+pub struct Adapter<Get, Set> {
+    get: Get,
+    set: Set,
+}
+
+impl<S, Get, Set> Binding<S> for Adapter<Get, Set>
+where
+    Get: Fn() -> S + Clone + 'static,
+    Set: Fn(S) + Clone + 'static,
+{
+    fn with<T, F: FnOnce(&S) -> T>(&self, f: F) -> T {
+        note_untracked_read();
+        f(&(self.get)())
+    }
+    fn with_mut<T, F: FnOnce(&mut S) -> T>(&self, f: F) -> T {
+        let mut value = (self.get)();
+        let t = f(&mut value);
+        (self.set)(value);
+        redraw_app();
+        t
+    }
+    fn redraw(&self) {
+        redraw_app()
+    }
+}
+
+/// Binds to data kept anywhere in the app, read with `get` and written
+/// with `set`. Unlike `bind`, which is for deriving from other bindings,
+/// setting it redraws the app, and the views reading it are built again
+/// each frame, as they can't tell when it changes.
+pub fn binding<S, Get, Set>(get: Get, set: Set) -> Adapter<Get, Set>
+where
+    Get: Fn() -> S + Clone + 'static,
+    Set: Fn(S) + Clone + 'static,
+{
+    Adapter { get, set }
+}
+
+/// Struct for `bind_field`.
+pub struct FieldBinding<B, Get, GetMut, S> {
+    binding: B,
+    get: Get,
+    get_mut: GetMut,
+    phantom: PhantomData<fn() -> S>,
+}
+
+impl<B: Clone, Get: Clone, GetMut: Clone, S> Clone for FieldBinding<B, Get, GetMut, S> {
+    fn clone(&self) -> Self {
+        Self {
+            binding: self.binding.clone(),
+            get: self.get.clone(),
+            get_mut: self.get_mut.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<B, Get, GetMut, S, T> Binding<T> for FieldBinding<B, Get, GetMut, S>
+where
+    B: Binding<S>,
+    Get: Fn(&S) -> &T + Clone + 'static,
+    GetMut: Fn(&mut S) -> &mut T + Clone + 'static,
+    S: 'static,
+{
+    fn with<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        self.binding.with(|s| f((self.get)(s)))
+    }
+    fn with_mut<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        self.binding.with_mut(|s| f((self.get_mut)(s)))
+    }
+    fn redraw(&self) {
+        self.binding.redraw()
+    }
+}
+
+/// Binds to part of `binding`'s value, like a field, given functions
+/// returning references to it. For example
+/// `bind_field(b, |s| &s.name, |s| &mut s.name)`. Nothing is cloned.
+pub fn bind_field<B, Get, GetMut, S, T>(
+    binding: B,
+    get: Get,
+    get_mut: GetMut,
+) -> FieldBinding<B, Get, GetMut, S>
+where
+    B: Binding<S>,
+    Get: Fn(&S) -> &T + Clone + 'static,
+    GetMut: Fn(&mut S) -> &mut T + Clone + 'static,
+{
+    FieldBinding {
+        binding,
+        get,
+        get_mut,
+        phantom: PhantomData,
+    }
+}
+
 /// Constructs a new binding from a binding and an expression.
 ///
 /// For example `bind(b, x)` will create a binding to
@@ -125,19 +413,36 @@ macro_rules! bind_no_clone {
     }};
 }
 
-// WIP. Attempting again to creating nice bindings without cloning.
+/// Binding to part of another binding's value, selected by a `Lens`.
 #[derive(Clone)]
-struct Bnd2<B, L, T1> {
+pub struct Bnd2<B, L, T1> {
     binding: B,
     lens: L,
     phantom: std::marker::PhantomData<T1>,
 }
 
+/// Selects part of a value, for use with `lens`.
 pub trait Lens<T: ?Sized, U: ?Sized> {
     fn with<V, F: FnOnce(&U) -> V>(&self, data: &T, f: F) -> V;
     fn with_mut<V, F: FnOnce(&mut U) -> V>(&self, data: &mut T, f: F) -> V;
 }
 
+/// Creates a binding to the part of `binding`'s value selected by `lens`.
+/// Unlike `bind!`, this doesn't clone the whole value. `#[derive(Bindable)]`
+/// generates lenses for each field of a struct.
+pub fn lens<B, L, T0, T1>(binding: B, lens: L) -> Bnd2<B, L, T1>
+where
+    B: Binding<T1>,
+    L: Lens<T1, T0> + Clone + 'static,
+    T1: Clone + 'static,
+{
+    Bnd2 {
+        binding,
+        lens,
+        phantom: Default::default(),
+    }
+}
+
 impl<B, T0, T1, L> Binding<T0> for Bnd2<B, L, T1>
 where
     B: Binding<T1>,
@@ -150,6 +455,9 @@ where
     fn with_mut<T, F: FnOnce(&mut T0) -> T>(&self, f: F) -> T {
         self.binding.with_mut(|v| self.lens.with_mut(v, |vv| f(vv)))
     }
+    fn redraw(&self) {
+        self.binding.redraw()
+    }
 }
 
 #[derive(Clone)]
@@ -208,4 +516,70 @@ mod tests {
         b.set(42);
         assert_eq!(s.get().x, 42);
     }
+
+    #[derive(Clone, Bindable)]
+    struct DeriveTestData {
+        name: String,
+        values: Vec<f32>,
+    }
+
+    #[test]
+    fn test_derive_bindable() {
+        let dirty = Arc::new(Mutex::new(Dirty::new(None)));
+        let s = State::new(
+            DeriveTestData {
+                name: "".into(),
+                values: vec![0.0; 4],
+            },
+            dirty,
+        );
+        s.binding().name().set("rui".into());
+        s.binding().values().with_mut(|v| v[2] = 1.0);
+        assert_eq!(s.get().name, "rui");
+        assert_eq!(s.binding().values().with(|v| v[2]), 1.0);
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let _timers = lock_app_timers();
+        let dirty = Arc::new(Mutex::new(Dirty::new(None)));
+        let s = State::new(0, dirty);
+        let delay = Duration::from_millis(50);
+
+        let throttled = s.clone().throttled(delay);
+        throttled.set(1);
+        throttled.set(2);
+        assert_eq!(s.get(), 1);
+        assert_eq!(throttled.get(), 2);
+
+        let debounced = s.clone().debounced(delay * 2);
+        debounced.set(3);
+        assert_eq!(s.get(), 1);
+
+        // Held back writes are passed on by the event loop's timers.
+        run_timers(Instant::now());
+        assert_eq!(s.get(), 1);
+        std::thread::sleep(delay * 4);
+        run_timers(Instant::now());
+        assert_eq!(s.get(), 3);
+        assert_eq!(throttled.get(), 3);
+    }
+
+    #[test]
+    fn test_app_data_bindings() {
+        let shared = Rc::new(RefCell::new(BindingTestData { x: 1 }));
+        let x = bind_field(shared.clone(), |d| &d.x, |d| &mut d.x);
+        x.set(2);
+        assert_eq!(shared.borrow().x, 2);
+
+        let locked = Arc::new(Mutex::new(3usize));
+        locked.set(4);
+        assert_eq!(*locked.lock().unwrap(), 4);
+
+        let (get, set) = (shared.clone(), shared.clone());
+        let adapted = binding(move || get.borrow().x, move |x| set.borrow_mut().x = x);
+        adapted.with_mut(|x| *x += 1);
+        assert_eq!(x.get(), 3);
+        assert_eq!(adapted.get(), 3);
+    }
 }
@@ -9,16 +9,22 @@ pub trait Body: View {
         self.body().print(id, cx)
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.body().process(event, id, cx, vger)
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.body().draw(id, cx, vger)
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.body().layout(id, sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.body().layout(id, constraints, cx, vger)
     }
 
     fn hittest(
@@ -26,7 +32,7 @@ pub trait Body: View {
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.body().hittest(id, pt, cx, vger)
     }
@@ -43,22 +49,22 @@ macro_rules! body_view {
             self.body().print(id, cx)
         }
 
-        fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+        fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
             self.body().process(event, id, cx, vger)
         }
 
-        fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+        fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
             self.body().draw(id, cx, vger)
         }
 
         fn layout(
             &self,
             id: ViewID,
-            sz: LocalSize,
+            constraints: Constraints,
             cx: &mut Context,
-            vger: &mut VGER,
+            vger: &mut dyn Renderer,
         ) -> LocalSize {
-            self.body().layout(id, sz, cx, vger)
+            self.body().layout(id, constraints, cx, vger)
         }
 
         fn hittest(
@@ -66,7 +72,7 @@ macro_rules! body_view {
             id: ViewID,
             pt: LocalPoint,
             cx: &mut Context,
-            vger: &mut VGER,
+            vger: &mut dyn Renderer,
         ) -> Option<ViewID> {
             self.body().hittest(id, pt, cx, vger)
         }
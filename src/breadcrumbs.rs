@@ -0,0 +1,363 @@
+use crate::*;
+
+pub const BREADCRUMB_HEIGHT: f32 = 24.0;
+const BREADCRUMB_PADDING: f32 = 6.0;
+const BREADCRUMB_SEPARATOR_WIDTH: f32 = 12.0;
+const BREADCRUMB_MENU_ROW_HEIGHT: f32 = 22.0;
+const BREADCRUMB_MORE: &str = "...";
+
+/// A piece of a breadcrumb bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Crumb {
+    Item(usize),
+
+    /// Stands in for the items which don't fit.
+    More,
+}
+
+/// Which crumbs fit in `width`, given the width of each item and of the
+/// `More` crumb. The first and last items stay, with as many of those
+/// just before the last as fit.
+fn fit_crumbs(widths: &[f32], more: f32, width: f32) -> Vec<Crumb> {
+    let n = widths.len();
+    let total =
+        |w: f32, count: usize| w + count.saturating_sub(1) as f32 * BREADCRUMB_SEPARATOR_WIDTH;
+    if n <= 2 || total(widths.iter().sum(), n) <= width {
+        return (0..n).map(Crumb::Item).collect();
+    }
+
+    let mut tail = vec![n - 1];
+    let mut used = widths[0] + more + widths[n - 1];
+    for i in (1..n - 1).rev() {
+        if total(used + widths[i], tail.len() + 3) > width {
+            break;
+        }
+        used += widths[i];
+        tail.insert(0, i);
+    }
+    if tail[0] == 1 {
+        return (0..n).map(Crumb::Item).collect();
+    }
+
+    let mut crumbs = vec![Crumb::Item(0), Crumb::More];
+    crumbs.extend(tail.into_iter().map(Crumb::Item));
+    crumbs
+}
+
+#[derive(Clone, Default)]
+struct BreadcrumbState {
+    /// The crumb a touch began on.
+    pressed: Option<Crumb>,
+
+    menu_open: bool,
+}
+
+/// Struct for `breadcrumbs`.
+pub struct Breadcrumbs<F> {
+    items: Vec<String>,
+    on_select: F,
+}
+
+impl<F> Breadcrumbs<F>
+where
+    F: Fn(usize),
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<BreadcrumbState> {
+        cx.with_state(
+            BreadcrumbState::default(),
+            id,
+            |state: State<BreadcrumbState>, _cx| state,
+        )
+    }
+
+    fn label(&self, crumb: Crumb) -> &str {
+        match crumb {
+            Crumb::Item(i) => &self.items[i],
+            Crumb::More => BREADCRUMB_MORE,
+        }
+    }
+
+    fn width(&self, label: &str, vger: &mut dyn Renderer) -> f32 {
+        vger.text_bounds(label, Text::DEFAULT_SIZE, None).width() + 2.0 * BREADCRUMB_PADDING
+    }
+
+    /// The crumbs which fit, and where they go.
+    fn crumbs(&self, rect: LocalRect, vger: &mut dyn Renderer) -> Vec<(Crumb, LocalRect)> {
+        let widths: Vec<f32> = self.items.iter().map(|s| self.width(s, vger)).collect();
+        let more = self.width(BREADCRUMB_MORE, vger);
+        let mut x = rect.min_x();
+        fit_crumbs(&widths, more, rect.width())
+            .into_iter()
+            .map(|crumb| {
+                let w = match crumb {
+                    Crumb::Item(i) => widths[i],
+                    Crumb::More => more,
+                };
+                let r = LocalRect::new([x, rect.min_y()].into(), [w, rect.height()].into());
+                x += w + BREADCRUMB_SEPARATOR_WIDTH;
+                (crumb, r)
+            })
+            .collect()
+    }
+
+    /// The items collapsed into the `More` crumb, and the rects of their
+    /// rows in its menu, which drops down below it.
+    fn menu(
+        &self,
+        crumbs: &[(Crumb, LocalRect)],
+        vger: &mut dyn Renderer,
+    ) -> Vec<(usize, LocalRect)> {
+        let more = match crumbs.iter().find(|(c, _)| *c == Crumb::More) {
+            Some((_, r)) => *r,
+            None => return vec![],
+        };
+        let shown: Vec<usize> = crumbs
+            .iter()
+            .filter_map(|(c, _)| match c {
+                Crumb::Item(i) => Some(*i),
+                Crumb::More => None,
+            })
+            .collect();
+        let hidden: Vec<usize> = (0..self.items.len())
+            .filter(|i| !shown.contains(i))
+            .collect();
+        let w = hidden
+            .iter()
+            .map(|i| self.width(&self.items[*i], vger))
+            .fold(more.width(), f32::max);
+        hidden
+            .into_iter()
+            .enumerate()
+            .map(|(row, i)| {
+                let y = more.min_y() - (row + 1) as f32 * BREADCRUMB_MENU_ROW_HEIGHT;
+                (
+                    i,
+                    LocalRect::new(
+                        [more.min_x(), y].into(),
+                        [w, BREADCRUMB_MENU_ROW_HEIGHT].into(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn hit(
+        &self,
+        pt: LocalPoint,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<Crumb> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let crumbs = self.crumbs(rect, vger);
+        if self.state(id, cx).with(|s| s.menu_open) {
+            if let Some((i, _)) = self
+                .menu(&crumbs, vger)
+                .iter()
+                .find(|(_, r)| r.contains(pt))
+            {
+                return Some(Crumb::Item(*i));
+            }
+        }
+        crumbs.iter().find(|(_, r)| r.contains(pt)).map(|(c, _)| *c)
+    }
+}
+
+fn draw_crumb_label(vger: &mut dyn Renderer, label: &str, rect: LocalRect, color: Color) {
+    let bounds = vger.text_bounds(label, Text::DEFAULT_SIZE, None);
+    vger.save();
+    vger.translate(
+        [
+            rect.min_x() + BREADCRUMB_PADDING - bounds.origin.x,
+            rect.center().y - bounds.height() / 2.0 - bounds.origin.y,
+        ]
+        .into(),
+    );
+    vger.text(label, Text::DEFAULT_SIZE, color, None);
+    vger.restore();
+}
+
+impl<F> View for Breadcrumbs<F>
+where
+    F: Fn(usize),
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("breadcrumbs({:?})", self.items);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let state = self.state(id, cx);
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } => {
+                let hit = self.hit(event.position, id, cx, vger);
+                if hit.is_some() {
+                    cx.touches[*touch] = id;
+                    state.with_mut(|s| s.pressed = hit);
+                } else if state.with(|s| s.menu_open) {
+                    // Clicking anywhere else closes the menu.
+                    state.with_mut(|s| s.menu_open = false);
+                }
+            }
+            EventKind::TouchMove { id: 0 } if self.hit(event.position, id, cx, vger).is_some() => {
+                cx.set_cursor(CursorIcon::Hand)
+            }
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                let hit = self.hit(event.position, id, cx, vger);
+                let pressed = state.with(|s| s.pressed);
+                match hit.filter(|h| Some(*h) == pressed) {
+                    Some(Crumb::Item(i)) => {
+                        state.with_mut(|s| s.menu_open = false);
+                        (self.on_select)(i);
+                    }
+                    Some(Crumb::More) => state.with_mut(|s| s.menu_open = !s.menu_open),
+                    None => (),
+                }
+                state.with_mut(|s| s.pressed = None);
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| s.pressed = None);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let crumbs = self.crumbs(rect, vger);
+        let last = self.items.len().saturating_sub(1);
+
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        for (n, (crumb, r)) in crumbs.iter().enumerate() {
+            // The current item isn't a link.
+            let color = if *crumb == Crumb::Item(last) {
+                TEXT_COLOR
+            } else {
+                LINK_COLOR
+            };
+            draw_crumb_label(vger, self.label(*crumb), *r, color);
+
+            if n + 1 < crumbs.len() {
+                let x = r.max_x() + BREADCRUMB_SEPARATOR_WIDTH / 2.0;
+                let y = r.center().y;
+                vger.stroke_segment([x - 2.0, y + 4.0].into(), [x + 2.0, y].into(), 1.0, paint);
+                vger.stroke_segment([x + 2.0, y].into(), [x - 2.0, y - 4.0].into(), 1.0, paint);
+            }
+        }
+
+        if self.state(id, cx).with(|s| s.menu_open) {
+            let rows = self.menu(&crumbs, vger);
+            if let (Some((_, first)), Some((_, last))) = (rows.first(), rows.last()) {
+                let menu = first.union(last);
+                let paint = vger.color_paint(CONTROL_BACKGROUND);
+                vger.fill_rect(menu, 4.0, paint);
+                let paint = vger.color_paint(GROOVES);
+                vger.stroke_rect(menu.min(), menu.max(), 4.0, 1.0, paint);
+            }
+            for (i, r) in &rows {
+                draw_crumb_label(vger, &self.items[*i], *r, LINK_COLOR);
+            }
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = LocalSize::new(constraints.biggest().width, BREADCRUMB_HEIGHT);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if rect.contains(pt) || self.hit(pt, id, cx, vger).is_some() {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            BreadcrumbState::default(),
+            id,
+            map,
+            |state: State<BreadcrumbState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Navigation));
+        Some(aid)
+    }
+}
+
+impl<F> private::Sealed for Breadcrumbs<F> {}
+
+/// A path like `Home > Documents > Letters`, calling `on_select` with the
+/// index of the item clicked. When the path doesn't fit, the items in
+/// the middle collapse into a menu.
+pub fn breadcrumbs<F: Fn(usize) + 'static>(items: Vec<String>, on_select: F) -> Breadcrumbs<F> {
+    Breadcrumbs { items, on_select }
+}
+
+/// Breadcrumbs for a stack of screens, like those pushed as a user
+/// navigates deeper. Clicking an item pops the screens above it.
+pub fn stack_breadcrumbs(stack: impl Binding<Vec<String>>) -> Breadcrumbs<impl Fn(usize)> {
+    let items = stack.get();
+    breadcrumbs(items, move |i| stack.with_mut(|s| s.truncate(i + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_crumbs() {
+        let widths = [50.0, 50.0, 50.0, 50.0, 50.0];
+        let all: Vec<_> = (0..5).map(Crumb::Item).collect();
+        assert_eq!(fit_crumbs(&widths, 20.0, 1000.0), all);
+
+        // Room for the first, more and two at the end.
+        let width = 50.0 + 20.0 + 100.0 + 3.0 * BREADCRUMB_SEPARATOR_WIDTH;
+        assert_eq!(
+            fit_crumbs(&widths, 20.0, width),
+            vec![Crumb::Item(0), Crumb::More, Crumb::Item(3), Crumb::Item(4)]
+        );
+
+        // The last item stays even when nothing fits.
+        assert_eq!(
+            fit_crumbs(&widths, 20.0, 10.0),
+            vec![Crumb::Item(0), Crumb::More, Crumb::Item(4)]
+        );
+    }
+}
@@ -1,16 +1,271 @@
 pub use crate::*;
 use accesskit::Role;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 pub const BUTTON_CORNER_RADIUS: f32 = 5.0;
 
-/// Calls a function when the button is tapped.
-pub fn button<F: Fn() + 'static>(view: impl View + 'static, f: F) -> impl View {
-    view.padding(Auto)
-        .background(
-            rectangle()
-                .corner_radius(BUTTON_CORNER_RADIUS)
-                .color(BUTTON_BACKGROUND_COLOR),
-        )
-        .tap(f)
-        .role(Role::Button)
+/// Sent with an `Injector` while a repeating button is held down.
+struct ButtonRepeat {
+    id: ViewID,
+    generation: usize,
+}
+
+#[derive(Clone, Default)]
+struct ButtonState {
+    /// Incremented when the button is pressed or released, so the thread
+    /// repeating an earlier press stops.
+    generation: Arc<AtomicUsize>,
+}
+
+/// Struct for `button`.
+pub struct Button<V, F> {
+    label: V,
+    action: F,
+    on_press: Option<Box<dyn Fn()>>,
+    on_release: Option<Box<dyn Fn()>>,
+    repeat: Option<(Duration, Duration)>,
+    key: Option<KeyPress>,
+}
+
+impl<V, F> Button<V, F>
+where
+    V: View,
+    F: Fn() + 'static,
+{
+    /// Calls the action as soon as the button is pressed, then again after
+    /// `initial_delay`, then every `interval` until it's released. For
+    /// scroll arrows and steppers.
+    pub fn repeats(mut self, initial_delay: Duration, interval: Duration) -> Self {
+        self.repeat = Some((initial_delay, interval));
+        self
+    }
+
+    /// Calls `f` when the button is pressed down.
+    pub fn on_press(mut self, f: impl Fn() + 'static) -> Self {
+        self.on_press = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` when a press of the button ends, whether or not the
+    /// action is called.
+    pub fn on_release(mut self, f: impl Fn() + 'static) -> Self {
+        self.on_release = Some(Box::new(f));
+        self
+    }
+
+    /// Also calls the action when `key` is pressed, for example
+    /// `KeyPress::Enter` for the default button of a dialog.
+    pub fn key_equivalent(mut self, key: KeyPress) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> ButtonState {
+        cx.get_state(id, ButtonState::default).get()
+    }
+
+    /// Sends `ButtonRepeat` events from another thread until the press
+    /// ends.
+    fn start_repeating(&self, id: ViewID, cx: &mut Context) {
+        if let Some((initial_delay, interval)) = self.repeat {
+            let generation = self.state(id, cx).generation;
+            let current = generation.fetch_add(1, Ordering::SeqCst) + 1;
+            thread::spawn(move || {
+                thread::sleep(initial_delay);
+                while generation.load(Ordering::SeqCst) == current {
+                    injector().send(ButtonRepeat {
+                        id,
+                        generation: current,
+                    });
+                    thread::sleep(interval);
+                }
+            });
+        }
+    }
+
+    fn stop_repeating(&self, id: ViewID, cx: &mut Context) {
+        if self.repeat.is_some() {
+            self.state(id, cx).generation.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl<V, F> View for Button<V, F>
+where
+    V: View,
+    F: Fn() + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("button(");
+        self.label.print(id.child(&0), cx);
+        println!(")");
+    }
+
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let touches = cx.touches;
+        self.label.process(event, vid.child(&0), cx, vger);
+        if cx.event_consumed() {
+            return;
+        }
+        match &event.kind {
+            EventKind::TouchBegin { id }
+                if self.hittest(vid, event.position, cx, vger).is_some() =>
+            {
+                cx.touches[*id] = vid;
+                cx.consume_event();
+                if let Some(f) = &self.on_press {
+                    f();
+                }
+                if self.repeat.is_some() {
+                    (self.action)();
+                    self.start_repeating(vid, cx);
+                }
+            }
+            EventKind::TouchEnd { id } if touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
+                if self.repeat.is_some() {
+                    self.stop_repeating(vid, cx);
+                } else {
+                    (self.action)();
+                }
+                if let Some(f) = &self.on_release {
+                    f();
+                }
+            }
+            EventKind::TouchCancel { id } if touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
+                self.stop_repeating(vid, cx);
+                if let Some(f) = &self.on_release {
+                    f();
+                }
+            }
+            EventKind::Key(key, _) if Some(key) == self.key.as_ref() => {
+                cx.consume_event();
+                (self.action)()
+            }
+            EventKind::User(payload) => {
+                if let Some(repeat) = payload.downcast_ref::<ButtonRepeat>() {
+                    let generation = self.state(vid, cx).generation;
+                    if repeat.id == vid && repeat.generation == generation.load(Ordering::SeqCst) {
+                        (self.action)();
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.label.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.label.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.label.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.label.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = cx.get_state(id, ButtonState::default);
+        map.insert(id, Box::new(state));
+        self.label.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.label.access(id.child(&0), cx, nodes)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.label.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, F> private::Sealed for Button<V, F> {}
+
+/// Calls a function when the button is tapped. The button is sized by
+/// the `control_size` and drawn with the `button_style` of the views
+/// around it.
+pub fn button<F: Fn() + 'static>(view: impl View + 'static, f: F) -> Button<impl View, F> {
+    Button {
+        label: Padding::control(view)
+            .background(read_env(|size: ControlSize| {
+                read_env(move |style: ButtonStyle| {
+                    rectangle()
+                        .corner_radius(BUTTON_CORNER_RADIUS * size.scale())
+                        .color(style.background_color())
+                })
+            }))
+            .role(Role::Button),
+        action: f,
+        on_press: None,
+        on_release: None,
+        repeat: None,
+        key: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_button_events() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let log = Rc::new(RefCell::new(vec![]));
+        let (a, b, c) = (log.clone(), log.clone(), log.clone());
+        let ui = button(text("ok"), move || a.borrow_mut().push("action"))
+            .on_press(move || b.borrow_mut().push("press"))
+            .on_release(move || c.borrow_mut().push("release"))
+            .key_equivalent(KeyPress::Enter);
+        ui.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+
+        let kinds = [
+            EventKind::TouchBegin { id: 0 },
+            EventKind::TouchEnd { id: 0 },
+            EventKind::Key(KeyPress::Enter, ModifiersState::empty()),
+        ];
+        for kind in kinds {
+            let event = Event {
+                kind,
+                position: [5.0, 5.0].into(),
+            };
+            cx.process_root(&ui, &event, &mut vger);
+        }
+        assert_eq!(*log.borrow(), vec!["press", "action", "release", "action"]);
+    }
 }
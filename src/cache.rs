@@ -0,0 +1,363 @@
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// What a `Cache` last drew with: its key and size, and the states it
+/// read.
+struct Drawn {
+    key: Option<u64>,
+    size: LocalSize,
+    reads: Vec<StateRead>,
+}
+
+/// What a `Cache` with a key was last laid out with and at, and what
+/// laying out its child left.
+struct LaidOut {
+    key: u64,
+    constraints: Constraints,
+    generation: u64,
+    size: LocalSize,
+    guides: HashMap<Alignment, f32>,
+    reads: Vec<StateRead>,
+
+    /// The views laid out within, whose layouts are kept while it's
+    /// reused.
+    ids: Vec<ViewID>,
+
+    /// The sizes measured for `match_width` and `match_height` within,
+    /// and the matched sizes they were laid out with.
+    measured: HashMap<MatchKey, f32>,
+    matched: Vec<(MatchKey, Option<f32>)>,
+}
+
+/// Passes on sizes measured within a `Cache`, as if measured again.
+fn note_measured(cx: &mut Context, measured: &HashMap<MatchKey, f32>) {
+    for (key, size) in measured {
+        let outer = cx.measured_sizes.entry(*key).or_insert(0.0);
+        *outer = outer.max(*size);
+    }
+}
+
+#[derive(Clone, Default)]
+struct CacheState {
+    drawn: Arc<Mutex<Option<Drawn>>>,
+    laid_out: Arc<Mutex<Option<LaidOut>>>,
+}
+
+/// Whether none of `reads` have changed, noting them for a cache around
+/// us, where they still matter.
+fn unchanged(reads: &[StateRead]) -> bool {
+    note_reads(reads);
+    !reads.iter().any(|read| read.changed())
+}
+
+/// Struct for the `cache` and `memo` modifiers.
+pub struct Cache<V> {
+    child: V,
+
+    /// A hash of what the child is built from, for `memo`.
+    key: Option<u64>,
+}
+
+impl<V> Cache<V>
+where
+    V: View,
+{
+    pub fn new(child: V) -> Self {
+        Self { child, key: None }
+    }
+
+    /// Also keeps the child's layout, until `key` changes.
+    pub fn memo<K: Hash>(child: V, key: K) -> Self {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        Self {
+            child,
+            key: Some(hasher.finish()),
+        }
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> CacheState {
+        cx.get_state(id, CacheState::default).get()
+    }
+
+    /// Whether what was last drawn at `size` is still what the child
+    /// would draw.
+    fn valid(&self, id: ViewID, cx: &mut Context, size: LocalSize) -> bool {
+        let state = self.state(id, cx);
+        let drawn = state.drawn.lock().unwrap();
+        match &*drawn {
+            Some(drawn) if drawn.key == self.key && drawn.size == size => unchanged(&drawn.reads),
+            _ => false,
+        }
+    }
+
+    /// The size the child was last laid out at, if it has a key and
+    /// would be laid out the same within `constraints`.
+    fn laid_out(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        constraints: Constraints,
+    ) -> Option<LocalSize> {
+        let key = self.key?;
+        let state = self.state(id, cx);
+        let laid_out = state.laid_out.lock().unwrap();
+        match &*laid_out {
+            Some(laid_out)
+                if laid_out.key == key
+                    && laid_out.constraints == constraints
+                    && laid_out.generation == cx.layout_generation
+                    && laid_out
+                        .matched
+                        .iter()
+                        .all(|(key, size)| cx.matched_sizes.get(key) == size.as_ref())
+                    && unchanged(&laid_out.reads) =>
+            {
+                cx.guides = laid_out.guides.clone();
+                note_measured(cx, &laid_out.measured);
+                for id in &laid_out.ids {
+                    cx.layout.keep(*id);
+                }
+                Some(laid_out.size)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<V> View for Cache<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        match self.key {
+            Some(key) => println!(".memo({})", key),
+            None => println!(".cache()"),
+        }
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.child_rect(id);
+        vger.save();
+        if !(self.valid(id, cx, rect.size) && vger.draw_cached(id, rect)) {
+            vger.begin_cache(id, rect);
+            let ((), reads) = record_reads(|| self.child.draw(id.child(&0), cx, vger));
+            vger.end_cache();
+            *self.state(id, cx).drawn.lock().unwrap() = Some(Drawn {
+                key: self.key,
+                size: rect.size,
+                reads,
+            });
+        }
+        vger.restore();
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        // The layouts of the views within are still there from when the
+        // child was last laid out.
+        if let Some(size) = self.laid_out(id, cx, constraints) {
+            cx.note_child_rect(id, LocalRect::new(LocalPoint::zero(), size));
+            return size;
+        }
+
+        let outer_measured = std::mem::take(&mut cx.measured_sizes);
+        let start = cx.layout.begin_record();
+        let (child_size, reads) =
+            record_reads(|| self.child.layout(id.child(&0), constraints, cx, vger));
+        let ids = cx.layout.end_record(start);
+        let measured = std::mem::replace(&mut cx.measured_sizes, outer_measured);
+        note_measured(cx, &measured);
+
+        if let Some(key) = self.key {
+            let matched = measured
+                .keys()
+                .map(|key| (*key, cx.matched_sizes.get(key).copied()))
+                .collect();
+            *self.state(id, cx).laid_out.lock().unwrap() = Some(LaidOut {
+                key,
+                constraints,
+                generation: cx.layout_generation,
+                size: child_size,
+                guides: cx.guides.clone(),
+                reads,
+                ids,
+                measured,
+                matched,
+            });
+        }
+
+        cx.note_child_rect(id, LocalRect::new(LocalPoint::zero(), child_size));
+        child_size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = cx.get_state(id, CacheState::default);
+        map.insert(id, Box::new(state));
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V> private::Sealed for Cache<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_invalidation() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let data = State::new(0, Arc::new(Mutex::new(Dirty::new(None))));
+        let other = State::new(0, Arc::new(Mutex::new(Dirty::new(None))));
+        let read = data.clone();
+        let ui = state(0, move |_: State<i32>| {
+            read.get();
+            rectangle()
+        })
+        .cache();
+
+        assert!(!ui.valid(cx.root_id, &mut cx, size));
+        cx.layout_root(&ui, size, &mut vger);
+        ui.draw(cx.root_id, &mut cx, &mut vger);
+        assert!(ui.valid(cx.root_id, &mut cx, size));
+        assert!(!ui.valid(cx.root_id, &mut cx, LocalSize::new(50.0, 50.0)));
+
+        other.set(1);
+        assert!(ui.valid(cx.root_id, &mut cx, size));
+        data.set(1);
+        assert!(!ui.valid(cx.root_id, &mut cx, size));
+    }
+
+    #[test]
+    fn test_memo_layout() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let builds = Rc::new(Cell::new(0));
+        let memo = |key: i32| {
+            let builds = builds.clone();
+            state(0, move |_: State<i32>| {
+                builds.set(builds.get() + 1);
+                rectangle()
+            })
+            .memo(key)
+        };
+
+        cx.layout_root(&memo(1), size, &mut vger);
+        cx.layout_root(&memo(1), size, &mut vger);
+        assert_eq!(builds.get(), 1);
+        cx.layout_root(&memo(1), LocalSize::new(50.0, 50.0), &mut vger);
+        assert_eq!(builds.get(), 2);
+        cx.layout_root(&memo(2), LocalSize::new(50.0, 50.0), &mut vger);
+        assert_eq!(builds.get(), 3);
+    }
+
+    #[test]
+    fn test_memo_keeps_layout() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = hstack((rectangle(), rectangle())).memo(1);
+        let second = cx.root_id.child(&0).child(&1);
+
+        cx.layout_root(&ui, size, &mut vger);
+        cx.layout_root(&ui, size, &mut vger);
+        cx.layout.collect();
+        assert_eq!(cx.layout[&second].rect.width(), 50.0);
+        assert_eq!(cx.child_rect(cx.root_id).size, size);
+
+        // Views which have gone are dropped.
+        cx.layout_root(&rectangle(), size, &mut vger);
+        cx.layout.collect();
+        assert!(!cx.layout.contains_key(&second));
+    }
+
+    #[test]
+    fn test_relayout_triggers() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let layouts = Rc::new(Cell::new(0));
+        let layouts2 = layouts.clone();
+        let ui = rectangle()
+            .geom(move |_| layouts2.set(layouts2.get() + 1))
+            .memo(1);
+
+        assert!(cx.layout_if_needed(&ui, size, &mut vger));
+        assert!(!cx.layout_if_needed(&ui, size, &mut vger));
+        let resized = LocalSize::new(50.0, 50.0);
+        assert!(cx.layout_if_needed(&ui, resized, &mut vger));
+        assert_eq!(layouts.get(), 2);
+
+        cx.invalidate_layout(cx.root_id);
+        assert!(cx.layout_if_needed(&ui, resized, &mut vger));
+        assert_eq!(layouts.get(), 3);
+
+        let insets = SafeAreaInsets {
+            top: 10.0,
+            ..SafeAreaInsets::default()
+        };
+        cx.set_env(insets);
+        assert!(cx.layout_if_needed(&ui, resized, &mut vger));
+        cx.set_env(insets);
+        assert!(!cx.layout_if_needed(&ui, resized, &mut vger));
+        assert_eq!(layouts.get(), 4);
+    }
+}
@@ -7,33 +7,42 @@ pub struct Canvas<F> {
 
 impl<F> View for Canvas<F>
 where
-    F: Fn(LocalRect, &mut VGER),
+    F: Fn(LocalRect, &mut dyn Renderer),
 {
     fn print(&self, _id: ViewID, _cx: &mut Context) {
         println!("canvas");
     }
 
-    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut VGER) {
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
         // do nothing
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         let rect = cx.layout.entry(id).or_insert(LayoutBox::default()).rect;
 
         vger.save();
         (self.func)(rect, vger);
         vger.restore();
+
+        inspector_highlight(cx, id, rect, vger);
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, _vger: &mut VGER) -> LocalSize {
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
         cx.layout.insert(
             id,
             LayoutBox {
-                rect: LocalRect::new(LocalPoint::zero(), sz),
+                rect: LocalRect::new(LocalPoint::zero(), size),
                 offset: LocalOffset::zero(),
             },
         );
-        sz
+        size
     }
 
     fn hittest(
@@ -41,7 +50,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        _vger: &mut VGER,
+        _vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         let rect = cx.layout.entry(id).or_insert(LayoutBox::default()).rect;
 
@@ -67,7 +76,19 @@ where
 }
 
 /// Canvas for GPU drawing with VGER. See https://github.com/audulus/vger-rs.
+/// Draws nothing with renderers other than vger.
 pub fn canvas<F: Fn(LocalRect, &mut VGER) + 'static>(f: F) -> impl View {
+    Canvas {
+        func: move |rect, renderer: &mut dyn Renderer| {
+            if let Some(vger) = renderer.vger() {
+                f(rect, vger)
+            }
+        },
+    }
+}
+
+/// Like `canvas`, but draws with whichever renderer is in use.
+pub(crate) fn renderer_canvas<F: Fn(LocalRect, &mut dyn Renderer) + 'static>(f: F) -> impl View {
     Canvas { func: f }
 }
 
@@ -0,0 +1,180 @@
+use crate::*;
+
+pub const CARD_CORNER_RADIUS: f32 = 8.0;
+pub const CARD_PADDING: f32 = 10.0;
+pub const GROUP_BOX_TITLE_SIZE: u32 = 14;
+
+/// Struct for `card` and `group_box`.
+pub struct Card<V> {
+    child: V,
+    title: Option<String>,
+    color: Color,
+    border_color: Color,
+    corner_radius: f32,
+    padding: f32,
+}
+
+impl<V> Card<V>
+where
+    V: View,
+{
+    /// Sets the background color.
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Sets the border color. Use `CLEAR_COLOR` for no border.
+    pub fn border_color(self, border_color: Color) -> Self {
+        Self {
+            border_color,
+            ..self
+        }
+    }
+
+    /// Sets the corner radius.
+    pub fn corner_radius(self, corner_radius: f32) -> Self {
+        Self {
+            corner_radius,
+            ..self
+        }
+    }
+
+    /// Sets the space between the edge and the content.
+    pub fn padding(self, padding: f32) -> Self {
+        Self { padding, ..self }
+    }
+
+    /// Height of the title, including the space below it.
+    fn title_height(&self, vger: &mut dyn Renderer) -> f32 {
+        match &self.title {
+            Some(title) => {
+                vger.text_bounds(title, GROUP_BOX_TITLE_SIZE, None).height() + self.padding
+            }
+            None => 0.0,
+        }
+    }
+
+    fn child_offset(&self) -> LocalOffset {
+        LocalOffset::new(self.padding, self.padding)
+    }
+}
+
+impl<V> View for Card<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("Card({:?}) {{", self.title);
+        (self.child).print(id.child(&0), cx);
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let mut local_event = event.clone();
+        local_event.position -= self.child_offset();
+        self.child.process(&local_event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        let paint = vger.color_paint(self.color);
+        vger.fill_rect(rect, self.corner_radius, paint);
+
+        let paint = vger.color_paint(self.border_color);
+        vger.stroke_rect(rect.min(), rect.max(), self.corner_radius, 1.0, paint);
+
+        if let Some(title) = &self.title {
+            let bounds = vger.text_bounds(title, GROUP_BOX_TITLE_SIZE, None);
+            vger.save();
+            vger.translate(
+                [
+                    rect.min_x() + self.padding - bounds.min_x(),
+                    rect.max_y() - self.padding - bounds.max_y(),
+                ]
+                .into(),
+            );
+            vger.text(title, GROUP_BOX_TITLE_SIZE, TEXT_COLOR, None);
+            vger.restore();
+        }
+
+        vger.save();
+        vger.translate(self.child_offset());
+        self.child.draw(id.child(&0), cx, vger);
+        vger.restore();
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let title_height = self.title_height(vger);
+        let inset = LocalSize::new(2.0 * self.padding, 2.0 * self.padding + title_height);
+        let child_size = self
+            .child
+            .layout(id.child(&0), constraints.deflate(inset), cx, vger);
+
+        let size = child_size + inset;
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child
+            .hittest(id.child(&0), pt - self.child_offset(), cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+}
+
+impl<V> private::Sealed for Card<V> {}
+
+/// Puts a view on a rounded, bordered background with padding.
+pub fn card<V: View>(content: V) -> Card<V> {
+    Card {
+        child: content,
+        title: None,
+        color: BUTTON_BACKGROUND_COLOR,
+        border_color: GROOVES,
+        corner_radius: CARD_CORNER_RADIUS,
+        padding: CARD_PADDING,
+    }
+}
+
+/// A `card` with a title above its content, for grouping related controls.
+pub fn group_box<V: View>(title: &str, content: V) -> Card<V> {
+    Card {
+        title: Some(title.into()),
+        ..card(content)
+    }
+}
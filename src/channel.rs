@@ -0,0 +1,135 @@
+use crate::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type Subscriber<S> = Box<dyn Fn(&S) + Send>;
+
+/// A value shared with something outside the UI, like a network client,
+/// a device driver or another thread, over a pair of channels. Changes made
+/// by the UI are sent on the sender, and values arriving on the receiver
+/// show up in the UI.
+///
+/// Values are only passed on when they change, and values which arrived
+/// on the receiver aren't sent back, so the two sides don't keep echoing
+/// each other.
+pub struct ChannelBinding<S> {
+    value: Arc<Mutex<S>>,
+    sender: Sender<S>,
+    subscribers: Arc<Mutex<Vec<Subscriber<S>>>>,
+
+    /// How many times the value has changed, so views built from it are
+    /// built again.
+    version: Arc<AtomicU64>,
+}
+
+impl<S> Clone for ChannelBinding<S> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            sender: self.sender.clone(),
+            subscribers: self.subscribers.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl<S> ChannelBinding<S>
+where
+    S: Clone + PartialEq + Send + 'static,
+{
+    /// Starts with `value`, and keeps taking values from `receiver` until
+    /// its sender is dropped. Make this once, outside the view tree, and
+    /// clone it into views.
+    pub fn from_channel(value: S, sender: Sender<S>, receiver: Receiver<S>) -> Self {
+        let binding = Self {
+            value: Arc::new(Mutex::new(value)),
+            sender,
+            subscribers: Default::default(),
+            version: Default::default(),
+        };
+        let this = binding.clone();
+        thread::spawn(move || {
+            for value in receiver {
+                this.update(value, false);
+            }
+        });
+        binding
+    }
+
+    /// Calls `f` with each new value, whether it came from the UI or the
+    /// receiver.
+    pub fn subscribe(&self, f: impl Fn(&S) + Send + 'static) {
+        self.subscribers.lock().unwrap().push(Box::new(f));
+    }
+
+    fn update(&self, value: S, send: bool) {
+        {
+            let mut current = self.value.lock().unwrap();
+            if *current == value {
+                return;
+            }
+            *current = value.clone();
+        }
+        self.version.fetch_add(1, Ordering::SeqCst);
+        if send {
+            // Nobody's listening if the receiver's gone, which is fine.
+            let _ = self.sender.send(value.clone());
+        }
+        for f in self.subscribers.lock().unwrap().iter() {
+            f(&value);
+        }
+        redraw_app();
+    }
+}
+
+impl<S> Binding<S> for ChannelBinding<S>
+where
+    S: Clone + PartialEq + Send + 'static,
+{
+    fn with<T, F: FnOnce(&S) -> T>(&self, f: F) -> T {
+        note_read(&self.version);
+        f(&self.value.lock().unwrap())
+    }
+    fn with_mut<T, F: FnOnce(&mut S) -> T>(&self, f: F) -> T {
+        let mut value = self.get();
+        let t = f(&mut value);
+        self.update(value, true);
+        t
+    }
+    fn redraw(&self) {
+        redraw_app()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use std::time::Duration;
+
+    #[test]
+    fn test_channel_binding() {
+        let (to_device, from_ui) = channel();
+        let (to_ui, from_device) = channel();
+        let b = ChannelBinding::from_channel(0, to_device, from_device);
+        let seen = Arc::new(Mutex::new(vec![]));
+        let seen2 = seen.clone();
+        b.subscribe(move |v| seen2.lock().unwrap().push(*v));
+
+        b.set(1);
+        b.set(1);
+        assert_eq!(from_ui.try_recv(), Ok(1));
+        assert!(from_ui.try_recv().is_err());
+
+        to_ui.send(2).unwrap();
+        while seen.lock().unwrap().len() < 2 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(b.get(), 2);
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+        // Not echoed back.
+        assert!(from_ui.try_recv().is_err());
+    }
+}
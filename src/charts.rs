@@ -0,0 +1,669 @@
+use crate::*;
+use std::f32::consts::PI;
+use std::time::{Duration, Instant};
+
+const LABEL_SIZE: u32 = 12;
+const TICK_COUNT: usize = 5;
+const CHART_MARGIN: f32 = 8.0;
+const POINT_RADIUS: f32 = 3.0;
+const LINE_WIDTH: f32 = 2.0;
+
+/// How close the mouse must be to a point for its tooltip to show.
+const HOVER_DISTANCE: f32 = 12.0;
+
+/// Fraction of each category's width taken by its bars.
+const BAR_FILL: f32 = 0.8;
+
+/// How long charts take to animate to new data.
+pub const CHART_ANIMATION_TIME: Duration = Duration::from_millis(300);
+
+/// Colors for series which don't have their own, in order.
+pub const CHART_COLORS: [Color; 5] = [
+    AZURE_HIGHLIGHT,
+    RED_HIGHLIGHT,
+    GREEN_HIGHLIGHT,
+    Color::hex_const("#FFB000"),
+    Color::hex_const("#A070FF"),
+];
+
+/// Named values for a chart.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub values: Vec<f32>,
+    pub color: Option<Color>,
+}
+
+impl Series {
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+}
+
+/// Creates a series for `line_chart` or `bar_chart`.
+pub fn series(name: &str, values: impl Into<Vec<f32>>) -> Series {
+    Series {
+        name: name.into(),
+        values: values.into(),
+        color: None,
+    }
+}
+
+/// Ticks for an axis from `lo` to `hi`, about `count` steps apart, at
+/// round numbers. Also returns how many decimals the labels need.
+pub(crate) fn nice_ticks(lo: f32, hi: f32, count: usize) -> (Vec<f32>, usize) {
+    let raw = (hi - lo).max(f32::EPSILON) / count as f32;
+    let magnitude = 10f32.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(10.0 * magnitude);
+    let start = (lo / step).floor() as i32;
+    let end = (hi / step).ceil() as i32;
+    let ticks = (start..=end).map(|k| k as f32 * step).collect();
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    (ticks, decimals)
+}
+
+/// Draws `text` with its vertical center at `pt`. `align` is 0 to put its
+/// left at `pt`, 0.5 to center it and 1 to put its right there.
+fn draw_label(vger: &mut dyn Renderer, text: &str, pt: LocalPoint, align: f32, color: Color) {
+    let bounds = vger.text_bounds(text, LABEL_SIZE, None);
+    vger.save();
+    vger.translate(
+        [
+            pt.x - bounds.width() * align - bounds.origin.x,
+            pt.y - bounds.height() / 2.0 - bounds.origin.y,
+        ]
+        .into(),
+    );
+    vger.text(text, LABEL_SIZE, color, None);
+    vger.restore();
+}
+
+fn direction(angle: f32) -> LocalOffset {
+    LocalOffset::new(angle.cos(), angle.sin())
+}
+
+/// Fills a pie slice from angle `a0` to `a1`, counterclockwise.
+fn fill_wedge(
+    vger: &mut dyn Renderer,
+    center: LocalPoint,
+    radius: f32,
+    a0: f32,
+    a1: f32,
+    paint: Paint,
+) {
+    let start = center + direction(a0) * radius;
+    vger.move_to(center);
+    vger.quad_to(center.lerp(start, 0.5), start);
+
+    // Each quadratic covers at most an eighth of a circle.
+    let steps = ((a1 - a0) / (PI / 4.0)).ceil().max(1.0) as usize;
+    let da = (a1 - a0) / steps as f32;
+    let mut end = start;
+    for i in 0..steps {
+        let mid = a0 + (i as f32 + 0.5) * da;
+        end = center + direction(a0 + (i + 1) as f32 * da) * radius;
+        vger.quad_to(center + direction(mid) * (radius / (da / 2.0).cos()), end);
+    }
+    vger.quad_to(end.lerp(center, 0.5), center);
+    vger.fill(paint);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ChartKind {
+    Line,
+    Bar,
+    Pie,
+}
+
+#[derive(Clone, Default)]
+struct ChartState {
+    /// Values animated from and to.
+    from: Vec<Vec<f32>>,
+    to: Vec<Vec<f32>>,
+    start: Option<Instant>,
+
+    /// Series and index of the value under the mouse.
+    hover: Option<(usize, usize)>,
+}
+
+impl ChartState {
+    /// The values to draw at `now`, and whether they're still animating.
+    fn values(&self, now: Instant) -> (Vec<Vec<f32>>, bool) {
+        let t = match self.start {
+            Some(start) => (now - start).as_secs_f32() / CHART_ANIMATION_TIME.as_secs_f32(),
+            None => 1.0,
+        };
+        if t >= 1.0 {
+            return (self.to.clone(), false);
+        }
+        let t = 1.0 - (1.0 - t).powi(3);
+        let values = self
+            .to
+            .iter()
+            .enumerate()
+            .map(|(i, to)| {
+                to.iter()
+                    .enumerate()
+                    .map(|(j, v)| {
+                        let from = self.from.get(i).and_then(|f| f.get(j)).unwrap_or(v);
+                        from + (v - from) * t
+                    })
+                    .collect()
+            })
+            .collect();
+        (values, true)
+    }
+}
+
+/// Where a line or bar chart's values go.
+struct Plot {
+    rect: LocalRect,
+    y_min: f32,
+    y_max: f32,
+    ticks: Vec<f32>,
+    decimals: usize,
+}
+
+impl Plot {
+    fn y(&self, value: f32) -> f32 {
+        self.rect.min_y() + (value - self.y_min) / (self.y_max - self.y_min) * self.rect.height()
+    }
+
+    fn label(&self, value: f32) -> String {
+        format!("{:.*}", self.decimals, value)
+    }
+}
+
+/// Struct for `line_chart`, `bar_chart` and `pie_chart`.
+pub struct Chart {
+    kind: ChartKind,
+    series: Vec<Series>,
+    labels: Vec<String>,
+    y_range: Option<(f32, f32)>,
+}
+
+impl Chart {
+    /// Labels for the points along the x axis, or the slices of a pie.
+    pub fn labels(self, labels: &[&str]) -> Self {
+        Self {
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// Sets the range of the y axis, instead of fitting it to the data.
+    pub fn y_range(self, range: std::ops::Range<f32>) -> Self {
+        Self {
+            y_range: Some((range.start, range.end)),
+            ..self
+        }
+    }
+
+    fn color(&self, series: usize) -> Color {
+        self.series[series]
+            .color
+            .unwrap_or(CHART_COLORS[series % CHART_COLORS.len()])
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<ChartState> {
+        cx.with_state(
+            ChartState::default(),
+            id,
+            |state: State<ChartState>, _cx| state,
+        )
+    }
+
+    fn plot(&self, rect: LocalRect, values: &[Vec<f32>], vger: &mut dyn Renderer) -> Plot {
+        let (lo, hi) = self.y_range.unwrap_or_else(|| {
+            let all = values.iter().flatten();
+            let lo = all.clone().fold(0.0f32, |a, b| a.min(*b));
+            let hi = all.fold(lo, |a, b| a.max(*b));
+            (lo, if hi > lo { hi } else { lo + 1.0 })
+        });
+        let (mut ticks, decimals) = nice_ticks(lo, hi, TICK_COUNT);
+        let (y_min, y_max) = match self.y_range {
+            Some(range) => {
+                ticks.retain(|t| *t >= lo && *t <= hi);
+                range
+            }
+            None => (ticks[0], ticks[ticks.len() - 1]),
+        };
+
+        let mut plot = Plot {
+            rect,
+            y_min,
+            y_max,
+            ticks,
+            decimals,
+        };
+        let label_width = plot
+            .ticks
+            .iter()
+            .map(|t| vger.text_bounds(&plot.label(*t), LABEL_SIZE, None).width())
+            .fold(0.0, f32::max);
+        let left = label_width + CHART_MARGIN;
+        let bottom = if self.labels.is_empty() {
+            CHART_MARGIN
+        } else {
+            LABEL_SIZE as f32 + 2.0 * CHART_MARGIN
+        };
+        plot.rect = LocalRect::new(
+            [rect.min_x() + left, rect.min_y() + bottom].into(),
+            [
+                (rect.width() - left - CHART_MARGIN).max(0.0),
+                (rect.height() - bottom - CHART_MARGIN).max(0.0),
+            ]
+            .into(),
+        );
+        plot
+    }
+
+    /// The x coordinate of a line chart's point, or the center of a bar
+    /// chart's category.
+    fn x(&self, plot: &Plot, index: usize, count: usize) -> f32 {
+        let rect = plot.rect;
+        match self.kind {
+            ChartKind::Bar => rect.min_x() + (index as f32 + 0.5) * rect.width() / count as f32,
+            _ if count <= 1 => rect.center().x,
+            _ => rect.min_x() + index as f32 * rect.width() / (count - 1) as f32,
+        }
+    }
+
+    fn category_count(&self, values: &[Vec<f32>]) -> usize {
+        values
+            .iter()
+            .map(|v| v.len())
+            .max()
+            .unwrap_or(0)
+            .max(self.labels.len())
+    }
+
+    fn bar_rect(&self, plot: &Plot, values: &[Vec<f32>], series: usize, index: usize) -> LocalRect {
+        let count = self.category_count(values);
+        let width = plot.rect.width() / count as f32 * BAR_FILL / values.len() as f32;
+        let left = self.x(plot, index, count) - width * values.len() as f32 / 2.0;
+        let base = plot.y(0.0f32.clamp(plot.y_min, plot.y_max));
+        let top = plot.y(values[series][index]);
+        LocalRect::new(
+            [left + series as f32 * width, base.min(top)].into(),
+            [width, (top - base).abs()].into(),
+        )
+    }
+
+    fn pie_geometry(rect: LocalRect) -> (LocalPoint, f32) {
+        let radius = (rect.width().min(rect.height()) / 2.0 - CHART_MARGIN).max(0.0);
+        (rect.center(), radius)
+    }
+
+    /// The start and end angles of each slice, clockwise from the top.
+    fn slices(values: &[f32]) -> Vec<(f32, f32)> {
+        let total: f32 = values.iter().map(|v| v.max(0.0)).sum();
+        let mut sum = 0.0;
+        values
+            .iter()
+            .map(|v| {
+                let start = sum;
+                sum += v.max(0.0) / total.max(f32::EPSILON);
+                (PI / 2.0 - sum * 2.0 * PI, PI / 2.0 - start * 2.0 * PI)
+            })
+            .collect()
+    }
+
+    /// Finds the value under `pt`.
+    fn hover_at(
+        &self,
+        pt: LocalPoint,
+        rect: LocalRect,
+        values: &[Vec<f32>],
+        vger: &mut dyn Renderer,
+    ) -> Option<(usize, usize)> {
+        if self.kind == ChartKind::Pie {
+            let (center, radius) = Self::pie_geometry(rect);
+            let d = pt - center;
+            if d.length() > radius {
+                return None;
+            }
+            let mut angle = d.y.atan2(d.x);
+            while angle > PI / 2.0 {
+                angle -= 2.0 * PI;
+            }
+            while angle < PI / 2.0 - 2.0 * PI {
+                angle += 2.0 * PI;
+            }
+            let slices = Self::slices(values.first()?);
+            return slices
+                .iter()
+                .position(|(a0, a1)| angle >= *a0 && angle < *a1)
+                .map(|i| (0, i));
+        }
+
+        let plot = self.plot(rect, values, vger);
+        let count = self.category_count(values);
+        for (s, series) in values.iter().enumerate() {
+            for (i, value) in series.iter().enumerate() {
+                let hit = match self.kind {
+                    ChartKind::Bar => self.bar_rect(&plot, values, s, i).contains(pt),
+                    _ => {
+                        let p = LocalPoint::new(self.x(&plot, i, count), plot.y(*value));
+                        (p - pt).length() < HOVER_DISTANCE
+                    }
+                };
+                if hit {
+                    return Some((s, i));
+                }
+            }
+        }
+        None
+    }
+
+    fn tooltip(&self, values: &[Vec<f32>], (series, index): (usize, usize)) -> String {
+        let value = values[series][index];
+        let label = self.labels.get(index);
+        match (self.kind, label) {
+            (ChartKind::Pie, _) => {
+                let total: f32 = values[0].iter().map(|v| v.max(0.0)).sum();
+                let percent = 100.0 * value / total.max(f32::EPSILON);
+                match label {
+                    Some(label) => format!("{}: {} ({:.0}%)", label, value, percent),
+                    None => format!("{} ({:.0}%)", value, percent),
+                }
+            }
+            (_, Some(label)) => format!("{}, {}: {}", label, self.series[series].name, value),
+            (_, None) => format!("{}: {}", self.series[series].name, value),
+        }
+    }
+
+    fn draw_tooltip(&self, vger: &mut dyn Renderer, text: &str, at: LocalPoint, bounds: LocalRect) {
+        let size = vger.text_bounds(text, LABEL_SIZE, None).size;
+        let padding = 6.0;
+        let width = size.width + 2.0 * padding;
+        let height = LABEL_SIZE as f32 + 2.0 * padding;
+        let x = (at.x + padding)
+            .min(bounds.max_x() - width)
+            .max(bounds.min_x());
+        let y = (at.y + padding)
+            .min(bounds.max_y() - height)
+            .max(bounds.min_y());
+        let rect = LocalRect::new([x, y].into(), [width, height].into());
+        let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+        vger.fill_rect(rect, 4.0, paint);
+        draw_label(
+            vger,
+            text,
+            [x + padding, rect.center().y].into(),
+            0.0,
+            TEXT_COLOR,
+        );
+    }
+
+    fn draw_axes(&self, vger: &mut dyn Renderer, plot: &Plot, count: usize) {
+        let grid = vger.color_paint(GROOVES);
+        for tick in &plot.ticks {
+            let y = plot.y(*tick);
+            vger.stroke_segment(
+                [plot.rect.min_x(), y].into(),
+                [plot.rect.max_x(), y].into(),
+                1.0,
+                grid,
+            );
+            draw_label(
+                vger,
+                &plot.label(*tick),
+                [plot.rect.min_x() - CHART_MARGIN / 2.0, y].into(),
+                1.0,
+                MEDIUM_GRAY,
+            );
+        }
+        let y = plot.rect.min_y() - CHART_MARGIN - LABEL_SIZE as f32 / 2.0;
+        for (i, label) in self.labels.iter().enumerate().take(count) {
+            let x = self.x(plot, i, count);
+            draw_label(vger, label, [x, y].into(), 0.5, MEDIUM_GRAY);
+        }
+    }
+}
+
+impl View for Chart {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("{:?} chart of {} series", self.kind, self.series.len());
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::TouchMove { id: 0 } = &event.kind {
+            let rect = cx.layout.entry(id).or_default().rect;
+            let state = self.state(id, cx);
+            let values = state.with(|s| s.to.clone());
+            let hover = if rect.contains(event.position) {
+                self.hover_at(event.position, rect, &values, vger)
+            } else {
+                None
+            };
+            if hover != state.with(|s| s.hover) {
+                state.with_mut(|s| s.hover = hover);
+            }
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let now = Instant::now();
+
+        // Animate from what's drawn to the new data when it changes.
+        let state = self.state(id, cx);
+        let data: Vec<Vec<f32>> = self.series.iter().map(|s| s.values.clone()).collect();
+        if state.with(|s| s.to != data) {
+            let first = state.with(|s| s.start.is_none() && s.to.is_empty());
+            let (from, _) = state.with(|s| s.values(now));
+            state.with_mut(|s| {
+                s.from = from;
+                s.to = data.clone();
+                s.start = if first { None } else { Some(now) };
+                s.hover = None;
+            });
+        }
+        let (values, animating) = state.with(|s| s.values(now));
+        if animating {
+            if let Some(window) = &cx.window {
+                window.request_redraw();
+            }
+        }
+        let hover = state.with(|s| s.hover);
+
+        match self.kind {
+            ChartKind::Pie => {
+                let (center, radius) = Self::pie_geometry(rect);
+                let pie = values.first().cloned().unwrap_or_default();
+                for (i, (a0, a1)) in Self::slices(&pie).iter().enumerate() {
+                    let mut color = CHART_COLORS[i % CHART_COLORS.len()];
+                    if hover == Some((0, i)) {
+                        color.a = 0.7;
+                    }
+                    let paint = vger.color_paint(color);
+                    fill_wedge(vger, center, radius, *a0, *a1, paint);
+                }
+                for (i, (a0, a1)) in Self::slices(&pie).iter().enumerate() {
+                    if let Some(label) = self.labels.get(i) {
+                        if a1 - a0 > 0.3 {
+                            let at = center + direction((a0 + a1) / 2.0) * radius * 0.65;
+                            draw_label(vger, label, at, 0.5, BLACK);
+                        }
+                    }
+                }
+            }
+            ChartKind::Line | ChartKind::Bar => {
+                let plot = self.plot(rect, &values, vger);
+                let count = self.category_count(&values);
+                self.draw_axes(vger, &plot, count);
+
+                for (s, series) in values.iter().enumerate() {
+                    let paint = vger.color_paint(self.color(s));
+                    if self.kind == ChartKind::Bar {
+                        for i in 0..series.len() {
+                            vger.fill_rect(self.bar_rect(&plot, &values, s, i), 0.0, paint);
+                        }
+                        continue;
+                    }
+                    let points: Vec<LocalPoint> = series
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| [self.x(&plot, i, count), plot.y(*v)].into())
+                        .collect();
+                    for pair in points.windows(2) {
+                        vger.stroke_segment(pair[0], pair[1], LINE_WIDTH, paint);
+                    }
+                    for (i, p) in points.iter().enumerate() {
+                        let radius = if hover == Some((s, i)) {
+                            2.0 * POINT_RADIUS
+                        } else {
+                            POINT_RADIUS
+                        };
+                        vger.fill_circle(*p, radius, paint);
+                    }
+                }
+            }
+        }
+
+        if let Some(hover) = hover {
+            if hover.0 < values.len() && hover.1 < values[hover.0].len() {
+                let text = self.tooltip(&values, hover);
+                let at = match self.kind {
+                    ChartKind::Pie => {
+                        let (center, radius) = Self::pie_geometry(rect);
+                        let (a0, a1) = Self::slices(&values[0])[hover.1];
+                        center + direction((a0 + a1) / 2.0) * radius
+                    }
+                    ChartKind::Bar => {
+                        let plot = self.plot(rect, &values, vger);
+                        let bar = self.bar_rect(&plot, &values, hover.0, hover.1);
+                        LocalPoint::new(bar.center().x, bar.max_y())
+                    }
+                    ChartKind::Line => {
+                        let plot = self.plot(rect, &values, vger);
+                        let count = self.category_count(&values);
+                        let value = values[hover.0][hover.1];
+                        LocalPoint::new(self.x(&plot, hover.1, count), plot.y(value))
+                    }
+                };
+                self.draw_tooltip(vger, &text, at, rect);
+            }
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            ChartState::default(),
+            id,
+            map,
+            |state: State<ChartState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Image));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for Chart {}
+
+/// Plots each series as a line, with an axis fitted to the values.
+/// Hovering over a point shows its value.
+pub fn line_chart(series: Vec<Series>) -> Chart {
+    Chart {
+        kind: ChartKind::Line,
+        series,
+        labels: vec![],
+        y_range: None,
+    }
+}
+
+/// Plots series as bars, grouped by index. Give the groups names with
+/// `labels`.
+pub fn bar_chart(series: Vec<Series>) -> Chart {
+    Chart {
+        kind: ChartKind::Bar,
+        series,
+        labels: vec![],
+        y_range: None,
+    }
+}
+
+/// A pie of labelled values, such as `&[("rent", 1200.0), ("food", 400.0)]`.
+pub fn pie_chart(slices: &[(&str, f32)]) -> Chart {
+    Chart {
+        kind: ChartKind::Pie,
+        series: vec![series("", slices.iter().map(|s| s.1).collect::<Vec<_>>())],
+        labels: slices.iter().map(|s| s.0.to_string()).collect(),
+        y_range: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_ticks() {
+        let (ticks, decimals) = nice_ticks(0.0, 97.0, 5);
+        assert_eq!(ticks, vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+        assert_eq!(decimals, 0);
+
+        let (ticks, decimals) = nice_ticks(-3.0, 7.0, 5);
+        assert_eq!(ticks, vec![-4.0, -2.0, 0.0, 2.0, 4.0, 6.0, 8.0]);
+        assert_eq!(decimals, 0);
+
+        let (ticks, decimals) = nice_ticks(0.0, 1.0, 5);
+        assert_eq!(ticks.len(), 6);
+        assert_eq!(decimals, 1);
+    }
+}
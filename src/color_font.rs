@@ -0,0 +1,221 @@
+//! Font fallback and color glyphs for the software renderer.
+//!
+//! Text is split into runs drawn with the first font in the fallback chain
+//! which has each character, so mixed scripts and emoji don't come out as
+//! boxes. Emoji fonts draw in color: COLR fonts (Windows) with layers of
+//! outlines, CBDT and sbix fonts (Linux and macOS) with bitmaps. vger
+//! draws with its built-in font alone.
+
+use crate::*;
+use std::ops::Range;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([
+        *data.get(offset)?,
+        *data.get(offset + 1)?,
+    ]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes([
+        *data.get(offset)?,
+        *data.get(offset + 1)?,
+        *data.get(offset + 2)?,
+        *data.get(offset + 3)?,
+    ]))
+}
+
+/// Finds a table in an OpenType font, or the first font of a collection.
+pub(crate) fn font_table<'a>(data: &'a [u8], tag: &[u8; 4]) -> Option<&'a [u8]> {
+    let base = if data.get(0..4)? == b"ttcf" {
+        read_u32(data, 12)? as usize
+    } else {
+        0
+    };
+    let num_tables = read_u16(data, base + 4)? as usize;
+    for i in 0..num_tables {
+        let record = base + 12 + 16 * i;
+        if data.get(record..record + 4)? == tag {
+            let offset = read_u32(data, record + 8)? as usize;
+            let length = read_u32(data, record + 12)? as usize;
+            return data.get(offset..offset + length);
+        }
+    }
+    None
+}
+
+/// The layers of color glyphs in a COLR (version 0) font, with colors
+/// from its CPAL palette.
+pub(crate) struct ColorLayers {
+    /// Glyph id, first layer and number of layers, sorted by glyph id.
+    base_glyphs: Vec<(u16, u16, u16)>,
+
+    /// Glyph id and palette index of each layer.
+    layers: Vec<(u16, u16)>,
+    palette: Vec<Color>,
+}
+
+impl ColorLayers {
+    pub(crate) fn parse(font: &[u8]) -> Option<Self> {
+        let colr = font_table(font, b"COLR")?;
+        let cpal = font_table(font, b"CPAL")?;
+
+        let num_base = read_u16(colr, 2)? as usize;
+        let base_offset = read_u32(colr, 4)? as usize;
+        let layer_offset = read_u32(colr, 8)? as usize;
+        let num_layers = read_u16(colr, 12)? as usize;
+
+        let base_glyphs = (0..num_base)
+            .map(|i| {
+                let r = base_offset + 6 * i;
+                Some((
+                    read_u16(colr, r)?,
+                    read_u16(colr, r + 2)?,
+                    read_u16(colr, r + 4)?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let layers = (0..num_layers)
+            .map(|i| {
+                let r = layer_offset + 4 * i;
+                Some((read_u16(colr, r)?, read_u16(colr, r + 2)?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // Use the first palette.
+        let num_entries = read_u16(cpal, 2)? as usize;
+        let records_offset = read_u32(cpal, 8)? as usize;
+        let first = read_u16(cpal, 12)? as usize;
+        let palette = (0..num_entries)
+            .map(|i| {
+                let r = records_offset + 4 * (first + i);
+                let bgra = cpal.get(r..r + 4)?;
+                Some(Color::new(
+                    bgra[2] as f32 / 255.0,
+                    bgra[1] as f32 / 255.0,
+                    bgra[0] as f32 / 255.0,
+                    bgra[3] as f32 / 255.0,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            base_glyphs,
+            layers,
+            palette,
+        })
+    }
+
+    /// The layers of a glyph, bottom first, if it's a color glyph. Layers
+    /// without a color are drawn in the text color.
+    pub(crate) fn layers(&self, glyph: u16) -> Option<Vec<(u16, Option<Color>)>> {
+        let i = self
+            .base_glyphs
+            .binary_search_by_key(&glyph, |b| b.0)
+            .ok()?;
+        let (_, first, count) = self.base_glyphs[i];
+        let first = first as usize;
+        let layers = self.layers.get(first..first + count as usize)?;
+        Some(
+            layers
+                .iter()
+                .map(|(glyph, index)| (*glyph, self.palette.get(*index as usize).copied()))
+                .collect(),
+        )
+    }
+}
+
+/// Whether `c` belongs with the character before it, like an emoji's
+/// skin tone or the joiner between emoji in a sequence.
+fn joins_previous(c: char) -> bool {
+    matches!(c as u32, 0x200D | 0xFE00..=0xFE0F | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F | 0x20E3)
+}
+
+/// Splits text into runs, each drawn with the font index `font_for`
+/// chooses for its first character.
+pub(crate) fn font_runs(
+    text: &str,
+    mut font_for: impl FnMut(char) -> usize,
+) -> Vec<(usize, Range<usize>)> {
+    let mut runs: Vec<(usize, Range<usize>)> = vec![];
+    let mut after_joiner = false;
+    for (i, c) in text.char_indices() {
+        let end = i + c.len_utf8();
+        let font = match runs.last() {
+            Some((font, _)) if joins_previous(c) || after_joiner => *font,
+            _ => font_for(c),
+        };
+        after_joiner = c == '\u{200D}';
+        match runs.last_mut() {
+            Some((last, range)) if *last == font => range.end = end,
+            _ => runs.push((font, i..end)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_runs() {
+        let has = |font: usize, c: char| match font {
+            0 => c.is_ascii(),
+            _ => true,
+        };
+        let font_for = |c: char| (0..2).find(|f| has(*f, c)).unwrap();
+
+        let text = "hi 👍🏽 ok";
+        let runs = font_runs(text, font_for);
+        let strs: Vec<_> = runs.iter().map(|(f, r)| (*f, &text[r.clone()])).collect();
+        assert_eq!(strs, vec![(0, "hi "), (1, "👍🏽"), (0, " ok")]);
+
+        // A family emoji is one run, though it's several characters.
+        let text = "👩\u{200D}👩\u{200D}👧";
+        assert_eq!(font_runs(text, font_for), vec![(1, 0..text.len())]);
+    }
+
+    #[test]
+    fn test_color_layers() {
+        fn u16s(v: &[u16]) -> Vec<u8> {
+            v.iter().flat_map(|x| x.to_be_bytes()).collect()
+        }
+
+        // COLR: one base glyph (5) with two layers.
+        let mut colr = u16s(&[0, 1]);
+        colr.extend(&14u32.to_be_bytes());
+        colr.extend(&20u32.to_be_bytes());
+        colr.extend(u16s(&[2]));
+        colr.extend(u16s(&[5, 0, 2]));
+        colr.extend(u16s(&[6, 0, 7, 0xFFFF]));
+
+        // CPAL: one palette with one red entry.
+        let mut cpal = u16s(&[0, 1, 1, 1]);
+        cpal.extend(&14u32.to_be_bytes());
+        cpal.extend(u16s(&[0]));
+        cpal.extend(&[0, 0, 255, 255]);
+
+        // A font with just those tables.
+        let mut font = vec![0, 1, 0, 0];
+        font.extend(u16s(&[2, 0, 0, 0]));
+        let colr_offset = 12 + 32;
+        let cpal_offset = colr_offset + colr.len();
+        for (tag, offset, len) in [
+            (b"COLR", colr_offset, colr.len()),
+            (b"CPAL", cpal_offset, cpal.len()),
+        ] {
+            font.extend(tag);
+            font.extend(&0u32.to_be_bytes());
+            font.extend(&(offset as u32).to_be_bytes());
+            font.extend(&(len as u32).to_be_bytes());
+        }
+        font.extend(&colr);
+        font.extend(&cpal);
+
+        let layers = ColorLayers::parse(&font).unwrap();
+        assert!(layers.layers(4).is_none());
+        let red = Color::new(1.0, 0.0, 0.0, 1.0);
+        assert_eq!(layers.layers(5), Some(vec![(6, Some(red)), (7, None)]));
+    }
+}
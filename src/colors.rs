@@ -8,6 +8,7 @@ pub const AZURE_HIGHLIGHT: Color = Color::hex_const("#00D4FF");
 pub const AZURE_HIGHLIGHT_DARK: Color = Color::hex_const("#009BBA");
 pub const AZURE_HIGHLIGHT_BACKGROUND: Color = Color::hex_const("#000F14");
 pub const GREEN_HIGHLIGHT: Color = Color::hex_const("#3BC455");
+pub const LINK_COLOR: Color = AZURE_HIGHLIGHT;
 
 pub const BUTTON_BACKGROUND_COLOR: Color = Color {
     r: 0.1,
@@ -45,3 +46,6 @@ pub const MEDIUM_GRAY: Color = Color {
 
 pub const GROOVES: Color = Color::hex_const("#252A2B");
 pub const GROOVES_DARK: Color = Color::hex_const("#0D0D0D");
+
+/// Opacity disabled controls are drawn with.
+pub const DISABLED_OPACITY: f32 = 0.4;
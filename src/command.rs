@@ -33,7 +33,7 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         if let EventKind::Command(name) = &event.kind {
             if *name == self.name {
                 (self.func)();
@@ -42,12 +42,18 @@ where
         self.child.process(event, id.child(&0), cx, vger)
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger)
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.child.layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -55,7 +61,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -81,6 +87,19 @@ where
         // XXX: how does accesskit handle menu commands?
         None
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
 }
 
 impl<V, F> private::Sealed for Command<V, F>
@@ -256,7 +275,7 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         if let EventKind::Command(name) = &event.kind {
             self.cmds.foreach_cmd(&mut |cmd| {
                 if cmd.name() == *name {
@@ -267,12 +286,18 @@ where
         self.child.process(event, id.child(&0), cx, vger)
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger)
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.child.layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -280,7 +305,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -307,6 +332,10 @@ where
     ) -> Option<accesskit::NodeId> {
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
 }
 
 impl<V, C> private::Sealed for CommandGroup<V, C> {}
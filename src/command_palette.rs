@@ -0,0 +1,200 @@
+use crate::*;
+use std::rc::Rc;
+
+const PALETTE_MAX_RESULTS: usize = 8;
+const PALETTE_WIDTH: f32 = 400.0;
+const PALETTE_ROW_HEIGHT: f32 = 30.0;
+
+/// A command listed in a `command_palette`.
+#[derive(Clone)]
+pub struct PaletteCommand {
+    name: String,
+    func: Rc<dyn Fn()>,
+}
+
+/// Creates a command for `command_palette` which calls `f` when chosen.
+pub fn palette_command<F: Fn() + 'static>(name: &str, f: F) -> PaletteCommand {
+    PaletteCommand {
+        name: name.into(),
+        func: Rc::new(f),
+    }
+}
+
+/// Scores how well `query` matches `candidate`, ignoring case. The
+/// characters of the query must appear in order. Consecutive characters
+/// and characters at the start of words score higher.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut chars = candidate.chars().flat_map(|c| c.to_lowercase()).enumerate();
+    let mut prev: Option<(usize, char)> = None;
+    let mut last_match = None;
+
+    for q in query.chars().flat_map(|c| c.to_lowercase()) {
+        loop {
+            let (i, c) = chars.next()?;
+            let word_start = match prev {
+                None => true,
+                Some((_, p)) => p == ' ' || p == '_' || p == '-',
+            };
+            prev = Some((i, c));
+            if c == q {
+                score += 1;
+                if word_start {
+                    score += 2;
+                }
+                if last_match.is_some() && last_match == i.checked_sub(1) {
+                    score += 3;
+                }
+                last_match = Some(i);
+                break;
+            }
+        }
+    }
+
+    Some(score)
+}
+
+/// Indices of the commands matching `query`, best first.
+fn filter_commands(commands: &[PaletteCommand], query: &str) -> Vec<usize> {
+    let mut matches: Vec<(usize, i32)> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(i, cmd)| fuzzy_score(query, &cmd.name).map(|score| (i, score)))
+        .collect();
+
+    // Stable, so equal scores keep their order.
+    matches.sort_by_key(|m| std::cmp::Reverse(m.1));
+    matches.truncate(PALETTE_MAX_RESULTS);
+    matches.into_iter().map(|(i, _)| i).collect()
+}
+
+#[derive(Clone, Default)]
+struct PaletteState {
+    query: String,
+    selected: usize,
+}
+
+fn dismiss(s: &State<PaletteState>, is_presented: &impl Binding<bool>) {
+    s.with_mut(|s| *s = PaletteState::default());
+    is_presented.set(false);
+}
+
+/// Overlay for searching and running commands. Type to filter, use the
+/// arrow keys to select and return to run the command. Escape dismisses.
+/// Shows nothing unless `is_presented` is true.
+pub fn command_palette(
+    commands: Vec<PaletteCommand>,
+    is_presented: impl Binding<bool>,
+) -> impl View {
+    state(PaletteState::default(), move |s| {
+        let query = s.with(|s| s.query.clone());
+        let matches = filter_commands(&commands, &query);
+        let selected = s.with(|s| s.selected).min(matches.len().saturating_sub(1));
+        let selected_command = matches.get(selected).cloned();
+
+        let commands = commands.clone();
+        let is_presented = is_presented.clone();
+        let presented = is_presented.get();
+        let n = matches.len();
+        let rows = matches.clone();
+        let row_commands = commands.clone();
+        let row_presented = is_presented.clone();
+        let row_state = s.clone();
+
+        cond(
+            presented,
+            vstack((
+                text(if query.is_empty() {
+                    "Search commands"
+                } else {
+                    &query
+                })
+                .color(if query.is_empty() {
+                    MEDIUM_GRAY
+                } else {
+                    TEXT_COLOR
+                })
+                .padding(Auto)
+                .background(
+                    rectangle()
+                        .corner_radius(BUTTON_CORNER_RADIUS)
+                        .color(CONTROL_BACKGROUND),
+                ),
+                list(rows, move |i| {
+                    let i = *i;
+                    let commands = row_commands.clone();
+                    let is_presented = row_presented.clone();
+                    let s = row_state.clone();
+                    text(&commands[i].name)
+                        .padding(Auto)
+                        .background(rectangle().color(if Some(i) == selected_command {
+                            AZURE_HIGHLIGHT_BACKGROUND
+                        } else {
+                            CLEAR_COLOR
+                        }))
+                        .tap(move || {
+                            dismiss(&s, &is_presented);
+                            (commands[i].func)();
+                        })
+                }),
+            ))
+            .padding(Auto)
+            .background(
+                rectangle()
+                    .corner_radius(BUTTON_CORNER_RADIUS)
+                    .color(BUTTON_BACKGROUND_COLOR),
+            )
+            .size([
+                PALETTE_WIDTH,
+                PALETTE_ROW_HEIGHT * (PALETTE_MAX_RESULTS + 2) as f32,
+            ])
+            .key(move |k| match k {
+                KeyPress::ArrowDown => {
+                    s.with_mut(|s| s.selected = (selected + 1).min(n.saturating_sub(1)))
+                }
+                KeyPress::ArrowUp => s.with_mut(|s| s.selected = selected.saturating_sub(1)),
+                KeyPress::Enter => {
+                    if let Some(i) = selected_command {
+                        dismiss(&s, &is_presented);
+                        (commands[i].func)();
+                    }
+                }
+                KeyPress::Escape => dismiss(&s, &is_presented),
+                KeyPress::Backspace => s.with_mut(|s| {
+                    s.query.pop();
+                    s.selected = 0;
+                }),
+                KeyPress::Space => s.with_mut(|s| {
+                    s.query.push(' ');
+                    s.selected = 0;
+                }),
+                KeyPress::Character(c) => s.with_mut(|s| {
+                    s.query.push_str(c);
+                    s.selected = 0;
+                }),
+                _ => (),
+            }),
+            EmptyView {},
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("xz", "Open File").is_none());
+        assert!(fuzzy_score("", "Open File").is_some());
+        assert!(fuzzy_score("of", "Open File") > fuzzy_score("pi", "Open File"));
+
+        let commands = vec![
+            palette_command("Close Window", || ()),
+            palette_command("Open File", || ()),
+            palette_command("Save", || ()),
+        ];
+        assert_eq!(filter_commands(&commands, "o"), vec![1, 0]);
+        assert_eq!(filter_commands(&commands, "sv"), vec![2]);
+    }
+}
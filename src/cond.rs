@@ -20,7 +20,7 @@ where
         }
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         if self.cond {
             self.if_true.process(event, id.child(&0), cx, vger)
         } else {
@@ -28,7 +28,7 @@ where
         }
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         if self.cond {
             self.if_true.draw(id.child(&0), cx, vger)
         } else {
@@ -36,11 +36,17 @@ where
         }
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
         if self.cond {
-            self.if_true.layout(id.child(&0), sz, cx, vger)
+            self.if_true.layout(id.child(&0), constraints, cx, vger)
         } else {
-            self.if_false.layout(id.child(&1), sz, cx, vger)
+            self.if_false.layout(id.child(&1), constraints, cx, vger)
         }
     }
 
@@ -49,7 +55,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         if self.cond {
             self.if_true.hittest(id.child(&0), pt, cx, vger)
@@ -86,6 +92,19 @@ where
             self.if_false.access(id.child(&1), cx, nodes)
         }
     }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        if self.cond {
+            self.if_true.intrinsic_size(id.child(&0), cx, vger)
+        } else {
+            self.if_false.intrinsic_size(id.child(&1), cx, vger)
+        }
+    }
 }
 
 impl<V0, V1> private::Sealed for Cond<V0, V1> {}
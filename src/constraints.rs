@@ -0,0 +1,153 @@
+use crate::*;
+
+/// What a view is offered in layout: the least and the most it can be on
+/// each axis. A max of `f32::INFINITY` is unbounded, like along a scroll
+/// view, where views take the size they need.
+///
+/// Views used to be offered a single size. To lay out a view written that
+/// way, treat `constraints.max` as the old proposal, pass children
+/// `Constraints::loose` of what was proposed to them, and return the size
+/// through `constrain`. Sizes and `[width, height]` arrays convert to loose
+/// constraints, so `view.layout(id, size.into(), cx, vger)` still works.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+    pub min: LocalSize,
+    pub max: LocalSize,
+}
+
+impl Constraints {
+    pub fn new(min: LocalSize, max: LocalSize) -> Self {
+        Self { min, max }
+    }
+
+    /// Exactly `size`.
+    pub fn tight(size: LocalSize) -> Self {
+        Self::new(size, size)
+    }
+
+    /// Anything up to `max`.
+    pub fn loose(max: LocalSize) -> Self {
+        Self::new(LocalSize::zero(), max)
+    }
+
+    /// Any size at all.
+    pub fn unbounded() -> Self {
+        Self::loose(LocalSize::new(f32::INFINITY, f32::INFINITY))
+    }
+
+    pub fn has_bounded_width(&self) -> bool {
+        self.max.width.is_finite()
+    }
+
+    pub fn has_bounded_height(&self) -> bool {
+        self.max.height.is_finite()
+    }
+
+    pub fn is_tight(&self) -> bool {
+        self.min == self.max
+    }
+
+    /// The same maximums, with no minimums.
+    pub fn loosen(&self) -> Self {
+        Self::loose(self.max)
+    }
+
+    /// The nearest size to `size` which is allowed.
+    pub fn constrain(&self, size: LocalSize) -> LocalSize {
+        LocalSize::new(
+            size.width.max(self.min.width).min(self.max.width),
+            size.height.max(self.min.height).min(self.max.height),
+        )
+    }
+
+    /// The biggest size allowed, or the least on unbounded axes. The size
+    /// of views which take whatever they're given, like shapes.
+    pub fn biggest(&self) -> LocalSize {
+        LocalSize::new(
+            if self.has_bounded_width() {
+                self.max.width
+            } else {
+                self.min.width
+            },
+            if self.has_bounded_height() {
+                self.max.height
+            } else {
+                self.min.height
+            },
+        )
+    }
+
+    /// Less `inset` on both the minimums and the maximums, for what's
+    /// within padding or a border.
+    pub fn deflate(&self, inset: LocalSize) -> Self {
+        let shrink = |length: f32, by: f32| (length - by).max(0.0);
+        Self::new(
+            LocalSize::new(
+                shrink(self.min.width, inset.width),
+                shrink(self.min.height, inset.height),
+            ),
+            LocalSize::new(
+                shrink(self.max.width, inset.width),
+                shrink(self.max.height, inset.height),
+            ),
+        )
+    }
+
+    /// Fixes the width, the height or both, within what's allowed.
+    pub fn tighten(&self, width: Option<f32>, height: Option<f32>) -> Self {
+        let fix = |length: Option<f32>, min: f32, max: f32| match length {
+            Some(length) => {
+                let length = length.max(min).min(max);
+                (length, length)
+            }
+            None => (min, max),
+        };
+        let (min_width, max_width) = fix(width, self.min.width, self.max.width);
+        let (min_height, max_height) = fix(height, self.min.height, self.max.height);
+        Self::new(
+            LocalSize::new(min_width, min_height),
+            LocalSize::new(max_width, max_height),
+        )
+    }
+}
+
+impl From<LocalSize> for Constraints {
+    fn from(size: LocalSize) -> Self {
+        Self::loose(size)
+    }
+}
+
+impl From<[f32; 2]> for Constraints {
+    fn from(size: [f32; 2]) -> Self {
+        Self::loose(size.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraints() {
+        let loose = Constraints::from([100.0, 50.0]);
+        assert_eq!(
+            loose.constrain([150.0, 20.0].into()),
+            LocalSize::new(100.0, 20.0)
+        );
+        assert_eq!(loose.biggest(), LocalSize::new(100.0, 50.0));
+
+        let framed = loose.tighten(Some(40.0), None);
+        assert!(!framed.is_tight());
+        assert_eq!(
+            framed.constrain(LocalSize::zero()),
+            LocalSize::new(40.0, 0.0)
+        );
+
+        let padded = Constraints::tight([30.0, 30.0].into()).deflate([10.0, 40.0].into());
+        assert_eq!(padded.min, LocalSize::new(20.0, 0.0));
+
+        let unbounded = Constraints::unbounded();
+        assert!(!unbounded.has_bounded_width());
+        assert_eq!(unbounded.biggest(), LocalSize::zero());
+    }
+}
@@ -0,0 +1,230 @@
+use crate::*;
+
+/// Struct for the `content_shape` modifier.
+pub struct ContentShape<V, S> {
+    child: V,
+    shape: S,
+}
+
+impl<V, S> ContentShape<V, S>
+where
+    V: View,
+    S: Shape + 'static,
+{
+    pub fn new(child: V, shape: S) -> Self {
+        Self { child, shape }
+    }
+}
+
+impl<V, S> View for ContentShape<V, S>
+where
+    V: View,
+    S: Shape + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".content_shape()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let child_size = self.child.layout(id.child(&0), constraints, cx, vger);
+
+        cx.note_child_rect(id, LocalRect::new(LocalPoint::zero(), child_size));
+        child_size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.child_rect(id);
+        if self.shape.contains(rect, pt) {
+            self.child.hittest(id.child(&0), pt, cx, vger).or(Some(id))
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, S> private::Sealed for ContentShape<V, S> {}
+
+/// Struct for the `hit_test_disabled` modifier.
+pub struct HitTestDisabled<V> {
+    child: V,
+}
+
+impl<V> HitTestDisabled<V>
+where
+    V: View,
+{
+    pub fn new(child: V) -> Self {
+        Self { child }
+    }
+}
+
+impl<V> View for HitTestDisabled<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".hit_test_disabled()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        // Pointer events go to the views underneath. Keys and the rest
+        // still reach the view.
+        match event.kind {
+            EventKind::TouchBegin { .. }
+            | EventKind::TouchMove { .. }
+            | EventKind::TouchEnd { .. }
+            | EventKind::TouchCancel { .. }
+            | EventKind::Scroll { .. } => (),
+            _ => self.child.process(event, id.child(&0), cx, vger),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        _id: ViewID,
+        _pt: LocalPoint,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        None
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V> private::Sealed for HitTestDisabled<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_shape() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+
+        // A small view padded out, so the padding is tappable too.
+        let ui = rectangle()
+            .size([10.0, 10.0])
+            .padding(Auto)
+            .content_shape(rectangle());
+        cx.layout_root(&ui, size, &mut vger);
+        let corner = LocalPoint::new(1.0, 1.0);
+        assert!(ui.hittest(cx.root_id, corner, &mut cx, &mut vger).is_some());
+
+        // A circle misses the corners of the view.
+        let ui = rectangle().content_shape(circle());
+        cx.layout_root(&ui, size, &mut vger);
+        assert!(ui.hittest(cx.root_id, corner, &mut cx, &mut vger).is_none());
+        let center = LocalPoint::new(50.0, 50.0);
+        assert!(ui.hittest(cx.root_id, center, &mut cx, &mut vger).is_some());
+
+        let ui = rectangle().hit_test_disabled();
+        cx.layout_root(&ui, size, &mut vger);
+        assert!(ui.hittest(cx.root_id, center, &mut cx, &mut vger).is_none());
+    }
+
+    #[test]
+    fn test_rounded_rectangle_contains() {
+        let rect = LocalRect::new(LocalPoint::zero(), [100.0, 100.0].into());
+        let shape = rectangle().corner_radius(20.0);
+        assert!(!shape.contains(rect, [1.0, 1.0].into()));
+        assert!(shape.contains(rect, [10.0, 10.0].into()));
+        assert!(shape.contains(rect, [50.0, 1.0].into()));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::*;
 use euclid::*;
-use std::any::Any;
-use std::collections::HashMap;
+use std::any::{Any, TypeId};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 
 pub type LocalSpace = vger::defs::LocalSpace;
 pub type WorldSpace = vger::defs::WorldSpace;
@@ -14,7 +15,9 @@ pub type WorldPoint = Point2D<f32, WorldSpace>;
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use tao::event_loop::EventLoopProxy;
 use tao::window::Window;
@@ -44,6 +47,11 @@ impl ViewID {
     pub fn access_id(&self) -> accesskit::NodeId {
         accesskit::NodeId(std::num::NonZeroU64::new(self.id).unwrap())
     }
+
+    /// The view with the accessibility id `aid`.
+    pub(crate) fn from_access_id(aid: accesskit::NodeId) -> Self {
+        Self { id: aid.0.get() }
+    }
 }
 
 pub const DEBUG_LAYOUT: bool = false;
@@ -54,6 +62,86 @@ pub(crate) struct LayoutBox {
     pub offset: LocalOffset,
 }
 
+/// Layout information for all views, noting which were laid out by the
+/// latest pass, or used since. Entries for views which have gone are
+/// dropped by `collect`, which runs with state collection.
+#[derive(Default)]
+pub(crate) struct LayoutMap {
+    boxes: HashMap<ViewID, LayoutBox>,
+    laid_out: HashSet<ViewID>,
+
+    /// Ids used while recording, for `memo`, and how many recordings are
+    /// in progress.
+    recorded: Vec<ViewID>,
+    recording: usize,
+}
+
+impl LayoutMap {
+    pub fn insert(&mut self, id: ViewID, layout_box: LayoutBox) -> Option<LayoutBox> {
+        self.keep(id);
+        self.boxes.insert(id, layout_box)
+    }
+
+    pub fn entry(&mut self, id: ViewID) -> Entry<'_, ViewID, LayoutBox> {
+        self.keep(id);
+        self.boxes.entry(id)
+    }
+
+    /// Notes that `id` is still in use, so `collect` keeps its entry.
+    pub fn keep(&mut self, id: ViewID) {
+        self.laid_out.insert(id);
+        if self.recording > 0 {
+            self.recorded.push(id);
+        }
+    }
+
+    /// Forgets which views were laid out, before laying out the tree.
+    pub fn begin_pass(&mut self) {
+        self.laid_out.clear();
+        // In case a view panicked while recording.
+        self.recorded.clear();
+        self.recording = 0;
+    }
+
+    /// How many views were laid out since `begin_pass`, or used since.
+    pub fn laid_out(&self) -> usize {
+        self.laid_out.len()
+    }
+
+    /// Starts noting the ids used, for a view which will skip laying out
+    /// its child later and needs to keep the child's entries. Pass what
+    /// this returns to `end_record`.
+    pub fn begin_record(&mut self) -> usize {
+        self.recording += 1;
+        self.recorded.len()
+    }
+
+    /// The ids used since `begin_record` returned `start`.
+    pub fn end_record(&mut self, start: usize) -> Vec<ViewID> {
+        let ids: HashSet<ViewID> = self.recorded[start..].iter().copied().collect();
+        self.recording -= 1;
+        if self.recording == 0 {
+            self.recorded.clear();
+        }
+        ids.into_iter().collect()
+    }
+
+    /// Drops the entries of views which weren't laid out by the latest
+    /// pass or used since, so views which come and go don't grow the map.
+    pub fn collect(&mut self) {
+        let laid_out = &self.laid_out;
+        self.boxes.retain(|id, _| laid_out.contains(id));
+    }
+}
+
+impl std::ops::Deref for LayoutMap {
+    type Target = HashMap<ViewID, LayoutBox>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.boxes
+    }
+}
+
 // This could use a better name.
 pub struct Dirty {
     pub dirty: bool,
@@ -69,14 +157,98 @@ impl Dirty {
     }
 }
 
+/// Wakes up the event loop.
+pub(crate) fn wake(dirty: &Mutex<Dirty>) {
+    if let Some(proxy) = &dirty.lock().unwrap().event_loop_proxy {
+        if let Err(err) = proxy.send_event(()) {
+            println!("error waking up event loop: {:?}", err);
+        }
+    }
+}
+
+/// The running app's dirty flag, for values kept outside the view tree.
+static APP_DIRTY: Mutex<Option<Arc<Mutex<Dirty>>>> = Mutex::new(None);
+
+/// Makes `dirty` the one `redraw_app` sets.
+pub(crate) fn set_app_dirty(dirty: Arc<Mutex<Dirty>>) {
+    *APP_DIRTY.lock().unwrap() = Some(dirty);
+}
+
+/// Redraws the running app, if there is one. Can be called from any thread.
+pub(crate) fn redraw_app() {
+    if let Some(dirty) = &*APP_DIRTY.lock().unwrap() {
+        dirty.lock().unwrap().dirty = true;
+        wake(dirty);
+    }
+}
+
+type AppTimer = (Instant, Box<dyn FnOnce() + Send>);
+
+/// Callbacks waiting for the event loop to run them, with when they're due.
+static TIMERS: Mutex<Vec<AppTimer>> = Mutex::new(Vec::new());
+
+/// Has the event loop call `f` at `due`, on its own thread. Can be called
+/// from any thread.
+pub(crate) fn schedule_app(due: Instant, f: impl FnOnce() + Send + 'static) {
+    TIMERS.lock().unwrap().push((due, Box::new(f)));
+    if let Some(dirty) = &*APP_DIRTY.lock().unwrap() {
+        wake(dirty);
+    }
+}
+
+/// Runs the callbacks given to `schedule_app` which are due by `now`, and
+/// says when the next one is.
+pub(crate) fn run_timers(now: Instant) -> Option<Instant> {
+    let due: Vec<_> = {
+        let mut timers = TIMERS.lock().unwrap();
+        let (due, later) = timers.drain(..).partition(|(at, _)| *at <= now);
+        *timers = later;
+        due
+    };
+    // The callbacks may schedule more.
+    for (_, f) in due {
+        f();
+    }
+    TIMERS.lock().unwrap().iter().map(|(at, _)| *at).min()
+}
+
+/// Keeps tests which run the timers from running each other's callbacks.
+#[cfg(test)]
+pub(crate) fn lock_app_timers() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// The environment every view starts with.
+fn default_env() -> HashMap<TypeId, Rc<dyn Any>> {
+    let mut env: HashMap<TypeId, Rc<dyn Any>> = HashMap::new();
+    env.insert(TypeId::of::<MessageBus>(), Rc::new(MessageBus::new_app()));
+    env
+}
+
 /// Restricts what we can store in a StateMap (instead of just using Any)
 pub trait AnyState {
     /// So we can downcast.
     fn as_any(&self) -> &dyn Any;
+
+    /// The type of the state, for the inspector.
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 pub type StateMap = HashMap<ViewID, Box<dyn AnyState>>;
 
+/// How soon after a click another must come to count as a double click,
+/// unless it's changed with the `double_click_interval` modifier.
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How far apart clicks can be and still count as a double click.
+const DOUBLE_CLICK_DISTANCE: f32 = 4.0;
+
+/// How many touches (and the mouse) can be tracked at once.
+pub(crate) const MAX_TOUCHES: usize = 16;
+
 /// The Context stores all UI state. A user of the library
 /// shouldn't have to interact with it directly.
 pub struct Context {
@@ -84,16 +256,16 @@ pub struct Context {
     pub(crate) state_map: StateMap,
 
     /// Layout information for all views.
-    pub(crate) layout: HashMap<ViewID, LayoutBox>,
+    pub(crate) layout: LayoutMap,
 
     /// Which views each touch (or mouse pointer) is interacting with.
-    pub(crate) touches: [ViewID; 16],
+    pub(crate) touches: [ViewID; MAX_TOUCHES],
 
     /// Points at which touches (or click-drags) started.
-    pub(crate) starts: [LocalPoint; 16],
+    pub(crate) starts: [LocalPoint; MAX_TOUCHES],
 
     /// Previous touch/mouse positions.
-    pub(crate) previous_position: [LocalPoint; 16],
+    pub(crate) previous_position: [LocalPoint; MAX_TOUCHES],
 
     /// The root view ID. This should be randomized for security reasons.
     pub(crate) root_id: ViewID,
@@ -104,26 +276,328 @@ pub struct Context {
     /// Did state change?
     pub(crate) dirty: Arc<Mutex<Dirty>>,
 
-    /// The tao window
-    pub(crate) window: Window,
+    /// The tao window. `None` for a headless context.
+    pub(crate) window: Option<Window>,
 
     /// The current title of the window
     pub(crate) window_title: String,
+
+    /// Does the window have native decorations?
+    pub(crate) window_decorations: bool,
+
+    /// Set by a close window button. The event loop exits when it sees this.
+    pub(crate) close_requested: bool,
+
+    /// Set by `on_close_requested` to keep the window open.
+    pub(crate) close_vetoed: bool,
+
+    /// The tray icon requested by the `tray` modifier.
+    #[cfg(feature = "tray")]
+    pub(crate) tray: Option<TrayRequest>,
+
+    /// The view hierarchy inspector.
+    pub(crate) inspector: Inspector,
+
+    /// Values passed down the view tree, keyed by type. See `with_env`.
+    pub(crate) env: HashMap<TypeId, Rc<dyn Any>>,
+
+    /// The mouse cursor the views want, set while processing mouse moves.
+    pub(crate) cursor: CursorIcon,
+
+    /// The button of the last mouse press or release.
+    pub(crate) mouse_button: MouseButton,
+
+    /// The modifier keys held down.
+    pub(crate) modifiers: ModifiersState,
+
+    /// Views which take secret text, like passwords, so what's typed into
+    /// them isn't recorded.
+    pub(crate) secure_ids: HashSet<ViewID>,
+
+    /// The displays, and which one the window is on.
+    pub(crate) monitors: Monitors,
+
+    /// Alignment guides reported by the view just laid out, from its
+    /// bottom left corner. See `alignment_guide`.
+    pub(crate) guides: HashMap<Alignment, f32>,
+
+    /// Buffers for layout to reuse.
+    pub(crate) pool: Pool,
+
+    /// Sizes of the groups of views matching widths or heights, used while
+    /// laying out. See `match_width`.
+    pub(crate) matched_sizes: HashMap<MatchKey, f32>,
+
+    /// Largest sizes of the views in each group, measured while laying out.
+    pub(crate) measured_sizes: HashMap<MatchKey, f32>,
+
+    /// How many clicks in quick succession the latest press makes, so 2
+    /// for a double click.
+    pub(crate) click_count: usize,
+
+    /// When and where the latest press was.
+    pub(crate) last_click: Option<(Instant, LocalPoint)>,
+
+    /// See `DOUBLE_CLICK_INTERVAL`.
+    pub(crate) double_click_interval: Duration,
+
+    /// Set by a view which has handled the event being processed, so the
+    /// views after it don't see it.
+    pub(crate) consumed: bool,
+
+    /// Indices set with `z_index`, read by the stack the view is in.
+    pub(crate) z_indices: HashMap<ViewID, i32>,
+
+    /// The tasks each view has spawned. See `spawn`.
+    pub(crate) tasks: HashMap<ViewID, TaskScope>,
+
+    /// What views note about themselves while a query is taken. See
+    /// `query`.
+    pub(crate) query: Option<QueryNotes>,
+
+    /// The size the view tree was last laid out at. See
+    /// `layout_if_needed`.
+    pub(crate) root_size: Option<LocalSize>,
+
+    /// Set when the view tree has to be laid out again before it's drawn,
+    /// even at the same size.
+    pub(crate) layout_needed: bool,
+
+    /// Counts calls to `invalidate_layout`, so layouts kept from before
+    /// one aren't used.
+    pub(crate) layout_generation: u64,
+    /// What the scroll view being drawn shows of its content, in the
+    /// content's coordinates. See `sticky_headers`.
+    pub(crate) scroll_viewport: Option<LocalRect>,
 }
 
 impl Context {
     pub fn new(event_loop_proxy: Option<EventLoopProxy<()>>, window: Window) -> Self {
         Self {
-            state_map: HashMap::new(),
-            layout: HashMap::new(),
-            touches: [ViewID::default(); 16],
-            starts: [LocalPoint::zero(); 16],
-            previous_position: [LocalPoint::zero(); 16],
             root_id: ViewID::default(),
-            focused_id: None,
             dirty: Arc::new(Mutex::new(Dirty::new(event_loop_proxy))),
-            window,
+            window: Some(window),
+            ..Self::headless()
+        }
+    }
+
+    /// Creates a context which isn't attached to a window, for running
+    /// views in tests and benchmarks.
+    pub fn headless() -> Self {
+        Self {
+            state_map: HashMap::new(),
+            layout: LayoutMap::default(),
+            touches: [ViewID::default(); MAX_TOUCHES],
+            starts: [LocalPoint::zero(); MAX_TOUCHES],
+            previous_position: [LocalPoint::zero(); MAX_TOUCHES],
+            // Not the default id, so the root can have an accessibility node.
+            root_id: ViewID::default().child(&0),
+            focused_id: None,
+            dirty: Arc::new(Mutex::new(Dirty::new(None))),
+            window: None,
             window_title: "rui".into(),
+            window_decorations: true,
+            close_requested: false,
+            close_vetoed: false,
+            #[cfg(feature = "tray")]
+            tray: None,
+            inspector: Inspector::default(),
+            env: default_env(),
+            cursor: CursorIcon::Default,
+            mouse_button: MouseButton::Left,
+            modifiers: ModifiersState::default(),
+            secure_ids: HashSet::new(),
+            monitors: Monitors::default(),
+            guides: HashMap::new(),
+            pool: Pool::default(),
+            matched_sizes: HashMap::new(),
+            measured_sizes: HashMap::new(),
+            click_count: 0,
+            last_click: None,
+            double_click_interval: DOUBLE_CLICK_INTERVAL,
+            consumed: false,
+            z_indices: HashMap::new(),
+            tasks: HashMap::new(),
+            query: None,
+            root_size: None,
+            layout_needed: true,
+            layout_generation: 0,
+            scroll_viewport: None,
+        }
+    }
+
+    /// Counts the clicks for a press, before it's processed. A press adds
+    /// to the clicks before it if it comes soon enough after them, close
+    /// enough by.
+    fn count_click(&mut self, event: &Event) {
+        if let EventKind::TouchBegin { .. } = event.kind {
+            let now = Instant::now();
+            let repeated = matches!(self.last_click, Some((time, at))
+                if now - time < self.double_click_interval
+                    && (event.position - at).length() < DOUBLE_CLICK_DISTANCE);
+            self.click_count = if repeated { self.click_count + 1 } else { 1 };
+            self.last_click = Some((now, event.position));
+        }
+    }
+
+    /// Sends an event from the window to the views.
+    pub(crate) fn process_root(&mut self, view: &dyn View, event: &Event, vger: &mut dyn Renderer) {
+        self.count_click(event);
+        self.consumed = false;
+        view.process(event, self.root_id, self, vger);
+        self.deliver_messages(view, vger);
+    }
+
+    /// Stops the event being processed going to any more views: siblings
+    /// after this one, and ancestors which check `event_consumed`.
+    pub(crate) fn consume_event(&mut self) {
+        self.consumed = true;
+    }
+
+    /// Whether a view has handled the event being processed.
+    pub(crate) fn event_consumed(&self) -> bool {
+        self.consumed
+    }
+
+    /// How many clicks in quick succession the latest press makes, so 2
+    /// for a double click.
+    pub fn click_count(&self) -> usize {
+        self.click_count
+    }
+
+    pub(crate) fn window(&self) -> &Window {
+        self.window
+            .as_ref()
+            .expect("headless context has no window")
+    }
+
+    /// Whether keys go to a view taking secret text.
+    pub(crate) fn secure_focus(&self) -> bool {
+        matches!(self.focused_id, Some(id) if self.secure_ids.contains(&id))
+    }
+
+    /// The displays connected to the computer, and which one the window
+    /// is on. Empty when there's no window.
+    pub fn monitors(&self) -> &Monitors {
+        &self.monitors
+    }
+
+    /// Shows `icon` for the mouse cursor. Call it while processing a mouse
+    /// move which is over the view.
+    pub fn set_cursor(&mut self, icon: CursorIcon) {
+        self.cursor = icon;
+    }
+
+    /// Sets an environment value while calling `f`, so views below can
+    /// read it with `get_env`.
+    pub fn with_env<E: Clone + 'static, R, F: FnOnce(&mut Self) -> R>(
+        &mut self,
+        value: E,
+        f: F,
+    ) -> R {
+        let value = self.pool.env_value(value);
+        let prev = self.env.insert(TypeId::of::<E>(), value);
+        let result = f(self);
+        let value = match prev {
+            Some(prev) => self.env.insert(TypeId::of::<E>(), prev),
+            None => self.env.remove(&TypeId::of::<E>()),
+        };
+        if let Some(value) = value {
+            self.pool.recycle_env_value::<E>(value);
+        }
+        result
+    }
+
+    /// Sets an environment value for the whole view tree, like a theme or
+    /// a font scale, and lays the tree out again if it changed. Views
+    /// setting their own with `with_env` still override it below them.
+    pub fn set_env<E: Clone + PartialEq + 'static>(&mut self, value: E) {
+        if self.get_env::<E>().as_ref() != Some(&value) {
+            self.env.insert(TypeId::of::<E>(), Rc::new(value));
+            self.invalidate_layout(self.root_id);
+        }
+    }
+
+    /// Has the view tree laid out again before the next frame, because the
+    /// size of view `id` changed other than through state, like when
+    /// something it measures changed. Layouts kept by `cache` and `memo`
+    /// from before aren't used.
+    pub fn invalidate_layout(&mut self, _id: ViewID) {
+        self.layout_needed = true;
+        self.layout_generation += 1;
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+
+    /// Lays out the view tree at `size` if it's not the size it was last
+    /// laid out at, or state or the environment changed since, or a view
+    /// called `invalidate_layout`. Returns whether it was laid out.
+    pub(crate) fn layout_if_needed(
+        &mut self,
+        view: &dyn View,
+        size: LocalSize,
+        vger: &mut dyn Renderer,
+    ) -> bool {
+        if !self.layout_needed && self.root_size == Some(size) {
+            return false;
+        }
+        // Cleared first, so views invalidating while they're laid out are
+        // laid out again next frame.
+        self.layout_needed = false;
+        self.root_size = Some(size);
+        self.layout_root(view, size, vger);
+        true
+    }
+
+    /// Records where the child of the single-child view `id` was laid out,
+    /// for the view to find with `child_rect` later. It's kept under an id
+    /// of its own because the view's entry belongs to the view around it,
+    /// which fills it in after `layout` returns.
+    pub(crate) fn note_child_rect(&mut self, id: ViewID, rect: LocalRect) {
+        self.layout.insert(
+            id.child(&1),
+            LayoutBox {
+                rect,
+                offset: LocalOffset::zero(),
+            },
+        );
+    }
+
+    /// Where the child of `id` was laid out, as recorded by
+    /// `note_child_rect`.
+    pub(crate) fn child_rect(&self, id: ViewID) -> LocalRect {
+        self.layout
+            .get(&id.child(&1))
+            .map(|layout_box| layout_box.rect)
+            .unwrap_or_default()
+    }
+
+    /// Gets the environment value of type `E` set by the closest
+    /// enclosing view, if any.
+    pub fn get_env<E: Clone + 'static>(&self) -> Option<E> {
+        self.env
+            .get(&TypeId::of::<E>())
+            .and_then(|value| value.downcast_ref::<E>())
+            .cloned()
+    }
+
+    /// The state for `id`, created from `default` if there isn't one yet,
+    /// so the default isn't built on every call.
+    pub(crate) fn get_state<S: Clone + 'static>(
+        &mut self,
+        id: ViewID,
+        default: impl FnOnce() -> S,
+    ) -> State<S> {
+        let d = self.dirty.clone();
+        let s = self
+            .state_map
+            .entry(id)
+            .or_insert_with(|| Box::new(State::new(default(), d)));
+
+        match s.as_any().downcast_ref::<State<S>>() {
+            Some(state) => state.clone(),
+            None => panic!("state has wrong type"),
         }
     }
 
@@ -165,7 +639,12 @@ impl Context {
         }
     }
 
-    pub fn with_state_aux<S: Clone + 'static, T, R, F: Fn(State<S>, &mut Self, &mut T) -> R>(
+    pub fn with_state_aux<
+        S: Clone + 'static,
+        T: ?Sized,
+        R,
+        F: Fn(State<S>, &mut Self, &mut T) -> R,
+    >(
         &mut self,
         default: S,
         id: ViewID,
@@ -185,3 +664,22 @@ impl Context {
         }
     }
 }
+
+#[cfg(test)]
+impl Context {
+    /// Presses and releases the mouse at `position`, for tests which tap
+    /// a view.
+    pub(crate) fn tap_at(
+        &mut self,
+        view: &dyn View,
+        position: LocalPoint,
+        vger: &mut dyn Renderer,
+    ) {
+        for kind in [
+            EventKind::TouchBegin { id: 0 },
+            EventKind::TouchEnd { id: 0 },
+        ] {
+            self.process_root(view, &Event { kind, position }, vger);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::*;
+use std::marker::PhantomData;
+
+/// How big controls are drawn. Set with the `control_size` modifier.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ControlSize {
+    Mini,
+    Small,
+    #[default]
+    Regular,
+    Large,
+}
+
+impl ControlSize {
+    /// How much bigger controls are drawn than at the regular size.
+    pub fn scale(self) -> f32 {
+        match self {
+            ControlSize::Mini => 0.6,
+            ControlSize::Small => 0.8,
+            ControlSize::Regular => 1.0,
+            ControlSize::Large => 1.25,
+        }
+    }
+
+    /// `size` scaled for controls of this size, for fonts.
+    pub fn font_size(self, size: u32) -> u32 {
+        (size as f32 * self.scale()).round() as u32
+    }
+}
+
+/// How buttons are drawn. Set with the `button_style` modifier.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ButtonStyle {
+    /// Just the label, without a background.
+    Plain,
+
+    /// The label on a gray background.
+    #[default]
+    Bordered,
+
+    /// Highlighted, for the button people will most likely want.
+    Prominent,
+
+    /// Highlighted in red, for buttons which delete things or can't be
+    /// undone.
+    Destructive,
+}
+
+impl ButtonStyle {
+    /// The color of the button's background.
+    pub fn background_color(self) -> Color {
+        match self {
+            ButtonStyle::Plain => CLEAR_COLOR,
+            ButtonStyle::Bordered => BUTTON_BACKGROUND_COLOR,
+            ButtonStyle::Prominent => AZURE_HIGHLIGHT_DARK,
+            ButtonStyle::Destructive => RED_HIGHLIGHT_DARK,
+        }
+    }
+}
+
+impl Context {
+    /// The size controls are drawn at here.
+    pub fn control_size(&self) -> ControlSize {
+        self.get_env().unwrap_or_default()
+    }
+
+    /// The style buttons are drawn with here.
+    pub fn button_style(&self) -> ButtonStyle {
+        self.get_env().unwrap_or_default()
+    }
+}
+
+/// Struct for `read_env`.
+pub struct ReadEnv<E, F> {
+    func: F,
+    phantom: PhantomData<fn() -> E>,
+}
+
+impl<E, V, F> ReadEnv<E, F>
+where
+    E: Clone + Default + 'static,
+    V: View,
+    F: Fn(E) -> V,
+{
+    fn body(&self, cx: &Context) -> V {
+        (self.func)(cx.get_env::<E>().unwrap_or_default())
+    }
+}
+
+impl<E, V, F> View for ReadEnv<E, F>
+where
+    E: Clone + Default + 'static,
+    V: View,
+    F: Fn(E) -> V,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.body(cx).print(id, cx)
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(cx).process(event, id, cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(cx).draw(id, cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.body(cx).layout(id, constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.body(cx).hittest(id, pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.body(cx).commands(id, cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.body(cx).gc(id, cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.body(cx).access(id, cx, nodes)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.body(cx).intrinsic_size(id, cx, vger)
+    }
+}
+
+impl<E, F> private::Sealed for ReadEnv<E, F> {}
+
+/// Makes a view from an environment value, or its default if it isn't
+/// set. The view has the same id, so its state survives the value
+/// changing.
+pub fn read_env<E, V, F>(f: F) -> ReadEnv<E, F>
+where
+    E: Clone + Default + 'static,
+    V: View,
+    F: Fn(E) -> V + 'static,
+{
+    ReadEnv {
+        func: f,
+        phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_size() {
+        let size = LocalSize::new(200.0, 200.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let regular = button(text("ok"), || ());
+        let small = button(text("ok"), || ()).control_size(ControlSize::Small);
+        let large = button(text("ok"), || ()).control_size(ControlSize::Large);
+        let regular = regular.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+        let small = small.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+        let large = large.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+        assert!(small.height < regular.height);
+        assert!(large.height > regular.height);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::*;
+
+/// Whether controls respond to input. Set with the `disabled` modifier and
+/// read with `cx.is_enabled()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Enabled(pub bool);
+
+impl Context {
+    /// Whether the views being visited respond to input. False inside a
+    /// view which is `disabled`.
+    pub fn is_enabled(&self) -> bool {
+        self.get_env::<Enabled>() != Some(Enabled(false))
+    }
+}
+
+/// Struct for the `disabled` modifier.
+pub struct Disabled<V> {
+    child: V,
+    disabled: bool,
+}
+
+impl<V> Disabled<V>
+where
+    V: View,
+{
+    pub fn new(child: V, disabled: bool) -> Self {
+        Self { child, disabled }
+    }
+
+    fn with_enabled<R>(&self, cx: &mut Context, f: impl FnOnce(&mut Context) -> R) -> R {
+        let enabled = cx.is_enabled() && !self.disabled;
+        cx.with_env(Enabled(enabled), f)
+    }
+}
+
+impl<V> View for Disabled<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".disabled({:?})", self.disabled);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if cx.is_enabled() && !self.disabled {
+            self.child.process(event, id.child(&0), cx, vger)
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        // Only fade where the view becomes disabled, so nested disabled
+        // views aren't faded twice.
+        if cx.is_enabled() && self.disabled {
+            vger.save();
+            vger.opacity(DISABLED_OPACITY);
+            self.with_enabled(cx, |cx| self.child.draw(id.child(&0), cx, vger));
+            vger.restore();
+        } else {
+            self.with_enabled(cx, |cx| self.child.draw(id.child(&0), cx, vger))
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.with_enabled(cx, |cx| {
+            self.child.layout(id.child(&0), constraints, cx, vger)
+        })
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.with_enabled(cx, |cx| self.child.hittest(id.child(&0), pt, cx, vger))
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.with_enabled(cx, |cx| self.child.commands(id.child(&0), cx, cmds))
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.with_enabled(cx, |cx| self.child.gc(id.child(&0), cx, map))
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.with_enabled(cx, |cx| self.child.access(id.child(&0), cx, nodes))
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.with_enabled(cx, |cx| self.child.intrinsic_size(id.child(&0), cx, vger))
+    }
+}
+
+impl<V> private::Sealed for Disabled<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn taps(disabled: bool) -> usize {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = count.clone();
+        let ui = rectangle()
+            .tap(move || {
+                c.fetch_add(1, Ordering::SeqCst);
+            })
+            .disabled(disabled);
+        ui.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        count.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_disabled() {
+        assert_eq!(taps(false), 1);
+        assert_eq!(taps(true), 0);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::*;
+
+/// Struct for `divider`.
+pub struct Divider {
+    color: Color,
+    inset: f32,
+}
+
+impl Divider {
+    /// Sets the line color.
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Leaves space at both ends of the line.
+    pub fn inset(self, inset: f32) -> Self {
+        Self { inset, ..self }
+    }
+
+    /// One physical pixel, so the line is crisp at any scale.
+    fn thickness(cx: &Context) -> f32 {
+        match &cx.window {
+            Some(window) => 1.0 / window.scale_factor() as f32,
+            None => 1.0,
+        }
+    }
+
+    /// A divider in a horizontal stack runs vertically.
+    fn is_vertical(cx: &Context) -> bool {
+        cx.get_env::<StackOrientation>() == Some(StackOrientation::Horizontal)
+    }
+}
+
+impl View for Divider {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("divider");
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let line = if Divider::is_vertical(cx) {
+            rect.inflate(0.0, -self.inset)
+        } else {
+            rect.inflate(-self.inset, 0.0)
+        };
+
+        let paint = vger.color_paint(self.color);
+        vger.fill_rect(line, 0.0, paint);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let thickness = Divider::thickness(cx);
+        let sz = constraints.biggest();
+        let size = if Divider::is_vertical(cx) {
+            LocalSize::new(thickness, sz.height)
+        } else {
+            LocalSize::new(sz.width, thickness)
+        };
+
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        _id: ViewID,
+        _pt: LocalPoint,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        None
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {
+        // do nothing
+    }
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {
+        // do nothing
+    }
+
+    fn access(
+        &self,
+        _id: ViewID,
+        _cx: &mut Context,
+        _nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        None
+    }
+}
+
+impl private::Sealed for Divider {}
+
+/// A thin line separating views. Horizontal, unless it's in an `hstack`.
+pub fn divider() -> Divider {
+    Divider {
+        color: GROOVES,
+        inset: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env() {
+        let mut cx = Context::headless();
+        assert_eq!(cx.get_env::<StackOrientation>(), None);
+        cx.with_env(StackOrientation::Horizontal, |cx| {
+            assert!(Divider::is_vertical(cx));
+            cx.with_env(StackOrientation::Vertical, |cx| {
+                assert!(!Divider::is_vertical(cx));
+            });
+            assert!(Divider::is_vertical(cx));
+        });
+        assert_eq!(cx.get_env::<StackOrientation>(), None);
+    }
+}
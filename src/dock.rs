@@ -0,0 +1,1022 @@
+use crate::*;
+
+const DOCK_TAB_HEIGHT: f32 = 24.0;
+const DOCK_TAB_PADDING: f32 = 10.0;
+const DOCK_TAB_TEXT_SIZE: u32 = 13;
+
+/// How close the mouse must be to a divider to drag it.
+const DOCK_DIVIDER_HIT: f32 = 4.0;
+
+/// How far a tab must be dragged before it comes loose.
+const DOCK_DRAG_THRESHOLD: f32 = 4.0;
+
+/// The size of a panel when it's first floated, including its title bar.
+const DOCK_FLOAT_SIZE: [f32; 2] = [240.0, 180.0];
+
+/// Which way a `DockNode::Split` divides its space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DockAxis {
+    /// Side by side, the first on the left.
+    Horizontal,
+
+    /// One above the other, the first on top.
+    Vertical,
+}
+
+/// Part of a `DockLayout`: panels shown as tabs, or two nodes sharing
+/// the space between them.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DockNode {
+    Tabs {
+        panels: Vec<String>,
+        active: usize,
+    },
+    Split {
+        axis: DockAxis,
+
+        /// The fraction of the space taken by `first`.
+        ratio: f32,
+        first: Box<DockNode>,
+        second: Box<DockNode>,
+    },
+}
+
+/// Where a panel is dropped, relative to the tabs it's dropped on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DockZone {
+    /// Adds a tab.
+    Tab,
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Where to dock a panel: in a zone of the tabs holding `beside`, or of
+/// the whole dock space if `beside` is `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DockTarget {
+    pub beside: Option<String>,
+    pub zone: DockZone,
+}
+
+/// A panel floating over the docked ones. Its rect includes its title bar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FloatingPanel {
+    pub panel: String,
+    pub rect: LocalRect,
+}
+
+/// The arrangement of panels in a `dock_space`. Panels are named, and
+/// `save` and `load` turn a layout into text so it can be kept between
+/// runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DockLayout {
+    pub root: DockNode,
+    pub floating: Vec<FloatingPanel>,
+}
+
+impl DockNode {
+    pub fn tabs(panels: &[&str]) -> Self {
+        Self::Tabs {
+            panels: panels.iter().map(|p| p.to_string()).collect(),
+            active: 0,
+        }
+    }
+
+    pub fn split(axis: DockAxis, ratio: f32, first: DockNode, second: DockNode) -> Self {
+        Self::Split {
+            axis,
+            ratio,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Tabs { panels, .. } if panels.is_empty())
+    }
+
+    /// The tabs holding `panel`.
+    fn find_mut(&mut self, panel: &str) -> Option<&mut DockNode> {
+        match self {
+            Self::Tabs { panels, .. } => {
+                if panels.iter().any(|p| p == panel) {
+                    Some(self)
+                } else {
+                    None
+                }
+            }
+            Self::Split { first, second, .. } => match first.find_mut(panel) {
+                Some(node) => Some(node),
+                None => second.find_mut(panel),
+            },
+        }
+    }
+
+    /// The node reached by going to the `first` (0) or `second` (1) child
+    /// of each split in turn.
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut DockNode> {
+        match (path.split_first(), self) {
+            (None, node) => Some(node),
+            (Some((i, rest)), Self::Split { first, second, .. }) => {
+                if *i == 0 { first } else { second }.node_mut(rest)
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes `panel`, collapsing any split left with an empty side.
+    fn remove(&mut self, panel: &str) -> bool {
+        match self {
+            Self::Tabs { panels, active } => match panels.iter().position(|p| p == panel) {
+                Some(i) => {
+                    panels.remove(i);
+                    if i < *active || *active == panels.len() {
+                        *active = active.saturating_sub(1);
+                    }
+                    true
+                }
+                None => false,
+            },
+            Self::Split { first, second, .. } => {
+                if first.remove(panel) {
+                    if first.is_empty() {
+                        *self = std::mem::replace(second, DockNode::tabs(&[]));
+                    }
+                    true
+                } else if second.remove(panel) {
+                    if second.is_empty() {
+                        *self = std::mem::replace(first, DockNode::tabs(&[]));
+                    }
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, panel: &str, zone: DockZone) {
+        if self.is_empty() || zone == DockZone::Tab {
+            if let Self::Tabs { panels, active } = self {
+                panels.push(panel.into());
+                *active = panels.len() - 1;
+                return;
+            }
+        }
+        let old = std::mem::replace(self, DockNode::tabs(&[]));
+        let new = DockNode::tabs(&[panel]);
+        *self = match zone {
+            DockZone::Left => DockNode::split(DockAxis::Horizontal, 0.5, new, old),
+            DockZone::Top => DockNode::split(DockAxis::Vertical, 0.5, new, old),
+            DockZone::Bottom => DockNode::split(DockAxis::Vertical, 0.5, old, new),
+            _ => DockNode::split(DockAxis::Horizontal, 0.5, old, new),
+        };
+    }
+
+    fn save(&self, s: &mut String) {
+        match self {
+            Self::Tabs { panels, active } => {
+                s.push_str(&format!("tabs {} {}", active, panels.len()));
+                for panel in panels {
+                    s.push(' ');
+                    s.push_str(&escape(panel));
+                }
+            }
+            Self::Split {
+                axis,
+                ratio,
+                first,
+                second,
+            } => {
+                let axis = match axis {
+                    DockAxis::Horizontal => "h",
+                    DockAxis::Vertical => "v",
+                };
+                s.push_str(&format!("split {} {} ", axis, ratio));
+                first.save(s);
+                s.push(' ');
+                second.save(s);
+            }
+        }
+    }
+
+    fn load<'a>(parts: &mut impl Iterator<Item = &'a str>) -> Option<Self> {
+        match parts.next()? {
+            "tabs" => {
+                let active = parts.next()?.parse().ok()?;
+                let n: usize = parts.next()?.parse().ok()?;
+                let panels = (0..n)
+                    .map(|_| parts.next().map(unescape))
+                    .collect::<Option<_>>()?;
+                Some(Self::Tabs { panels, active })
+            }
+            "split" => {
+                let axis = match parts.next()? {
+                    "h" => DockAxis::Horizontal,
+                    "v" => DockAxis::Vertical,
+                    _ => return None,
+                };
+                let ratio = parts.next()?.parse().ok()?;
+                let first = Self::load(parts)?;
+                let second = Self::load(parts)?;
+                Some(Self::split(axis, ratio, first, second))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl DockLayout {
+    pub fn new(root: DockNode) -> Self {
+        Self {
+            root,
+            floating: vec![],
+        }
+    }
+
+    /// Takes `panel` out of the layout.
+    pub fn remove(&mut self, panel: &str) {
+        if !self.root.remove(panel) {
+            self.floating.retain(|f| f.panel != panel);
+        }
+    }
+
+    /// Moves `panel` to `target`.
+    pub fn dock(&mut self, panel: &str, target: DockTarget) {
+        self.remove(panel);
+        match target.beside.as_deref().and_then(|b| self.root.find_mut(b)) {
+            Some(node) => node.insert(panel, target.zone),
+            None => self.root.insert(panel, target.zone),
+        }
+    }
+
+    /// Floats `panel` at `rect`, in front of the other floating panels.
+    pub fn float(&mut self, panel: &str, rect: LocalRect) {
+        self.remove(panel);
+        self.floating.push(FloatingPanel {
+            panel: panel.into(),
+            rect,
+        });
+    }
+
+    /// Writes the layout as text.
+    pub fn save(&self) -> String {
+        let mut s = String::new();
+        self.root.save(&mut s);
+        for f in &self.floating {
+            s.push_str(&format!(
+                "\nfloat {} {} {} {} {}",
+                f.rect.origin.x,
+                f.rect.origin.y,
+                f.rect.width(),
+                f.rect.height(),
+                escape(&f.panel)
+            ));
+        }
+        s
+    }
+
+    /// Reads a layout written by `save`.
+    pub fn load(s: &str) -> Option<Self> {
+        let mut lines = s.lines();
+        let root = DockNode::load(&mut lines.next()?.split_whitespace())?;
+        let mut floating = vec![];
+        for line in lines {
+            let mut parts = line.split_whitespace();
+            if parts.next()? != "float" {
+                return None;
+            }
+            let mut num = || parts.next()?.parse::<f32>().ok();
+            let rect = LocalRect::new([num()?, num()?].into(), [num()?, num()?].into());
+            let panel = unescape(parts.next()?);
+            floating.push(FloatingPanel { panel, rect });
+        }
+        Some(Self { root, floating })
+    }
+}
+
+/// Where a tabs node is drawn.
+struct TabsRegion {
+    rect: LocalRect,
+    panels: Vec<String>,
+    active: usize,
+}
+
+/// Where a split's divider is drawn.
+struct SplitRegion {
+    path: Vec<usize>,
+    axis: DockAxis,
+    rect: LocalRect,
+    divider: f32,
+}
+
+fn regions(
+    node: &DockNode,
+    rect: LocalRect,
+    path: &mut Vec<usize>,
+    tabs: &mut Vec<TabsRegion>,
+    splits: &mut Vec<SplitRegion>,
+) {
+    match node {
+        DockNode::Tabs { panels, active } => tabs.push(TabsRegion {
+            rect,
+            panels: panels.clone(),
+            active: *active,
+        }),
+        DockNode::Split {
+            axis,
+            ratio,
+            first,
+            second,
+        } => {
+            let (a, b, divider) = match axis {
+                DockAxis::Horizontal => {
+                    let w = rect.width() * ratio;
+                    let a = LocalRect::new(rect.origin, [w, rect.height()].into());
+                    let b = LocalRect::new(
+                        [rect.min_x() + w, rect.min_y()].into(),
+                        [rect.width() - w, rect.height()].into(),
+                    );
+                    (a, b, rect.min_x() + w)
+                }
+                DockAxis::Vertical => {
+                    let h = rect.height() * ratio;
+                    let a = LocalRect::new(
+                        [rect.min_x(), rect.max_y() - h].into(),
+                        [rect.width(), h].into(),
+                    );
+                    let b = LocalRect::new(rect.origin, [rect.width(), rect.height() - h].into());
+                    (a, b, rect.max_y() - h)
+                }
+            };
+            splits.push(SplitRegion {
+                path: path.clone(),
+                axis: *axis,
+                rect,
+                divider,
+            });
+            path.push(0);
+            regions(first, a, path, tabs, splits);
+            path.pop();
+            path.push(1);
+            regions(second, b, path, tabs, splits);
+            path.pop();
+        }
+    }
+}
+
+/// The strip along the top of `rect` for tabs or a title.
+fn title_bar(rect: LocalRect) -> LocalRect {
+    LocalRect::new(
+        [rect.min_x(), rect.max_y() - DOCK_TAB_HEIGHT].into(),
+        [rect.width(), DOCK_TAB_HEIGHT].into(),
+    )
+}
+
+/// What's left of `rect` below its title bar.
+fn content(rect: LocalRect) -> LocalRect {
+    LocalRect::new(
+        rect.origin,
+        [rect.width(), (rect.height() - DOCK_TAB_HEIGHT).max(0.0)].into(),
+    )
+}
+
+/// The part of `rect` a panel dropped in `zone` ends up in.
+fn zone_rect(rect: LocalRect, zone: DockZone) -> LocalRect {
+    let (w, h) = (rect.width() / 2.0, rect.height() / 2.0);
+    match zone {
+        DockZone::Tab => rect,
+        DockZone::Left => LocalRect::new(rect.origin, [w, rect.height()].into()),
+        DockZone::Right => LocalRect::new(
+            [rect.min_x() + w, rect.min_y()].into(),
+            [w, rect.height()].into(),
+        ),
+        DockZone::Top => LocalRect::new(
+            [rect.min_x(), rect.min_y() + h].into(),
+            [rect.width(), h].into(),
+        ),
+        DockZone::Bottom => LocalRect::new(rect.origin, [rect.width(), h].into()),
+    }
+}
+
+fn tab_rects(region: &TabsRegion, vger: &mut dyn Renderer) -> Vec<LocalRect> {
+    let bar = title_bar(region.rect);
+    let mut x = bar.min_x();
+    region
+        .panels
+        .iter()
+        .map(|panel| {
+            let w =
+                vger.text_bounds(panel, DOCK_TAB_TEXT_SIZE, None).width() + 2.0 * DOCK_TAB_PADDING;
+            let r = LocalRect::new([x, bar.min_y()].into(), [w, DOCK_TAB_HEIGHT].into());
+            x += w;
+            r
+        })
+        .collect()
+}
+
+fn draw_title(vger: &mut dyn Renderer, title: &str, rect: LocalRect) {
+    let bounds = vger.text_bounds(title, DOCK_TAB_TEXT_SIZE, None);
+    vger.save();
+    vger.translate(
+        [
+            rect.min_x() + DOCK_TAB_PADDING - bounds.origin.x,
+            rect.center().y - bounds.height() / 2.0 - bounds.origin.y,
+        ]
+        .into(),
+    );
+    vger.text(title, DOCK_TAB_TEXT_SIZE, TEXT_COLOR, None);
+    vger.restore();
+}
+
+#[derive(Clone, Debug)]
+enum DockDrag {
+    Divider {
+        path: Vec<usize>,
+        axis: DockAxis,
+        rect: LocalRect,
+    },
+    Panel {
+        panel: String,
+
+        /// Where the touch is relative to the panel's floating rect.
+        grab: LocalOffset,
+        start: LocalPoint,
+        current: LocalPoint,
+        moved: bool,
+    },
+}
+
+#[derive(Clone, Default)]
+struct DockState {
+    drag: Option<DockDrag>,
+}
+
+/// Struct for `dock_space`.
+pub struct DockSpace<B, F> {
+    layout: B,
+    func: F,
+}
+
+impl<B, V, F> DockSpace<B, F>
+where
+    B: Binding<DockLayout>,
+    V: View,
+    F: Fn(&str) -> V,
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<DockState> {
+        cx.with_state(DockState::default(), id, |state: State<DockState>, _cx| {
+            state
+        })
+    }
+
+    fn regions(&self, rect: LocalRect) -> (Vec<TabsRegion>, Vec<SplitRegion>) {
+        let (mut tabs, mut splits) = (vec![], vec![]);
+        self.layout
+            .with(|l| regions(&l.root, rect, &mut vec![], &mut tabs, &mut splits));
+        (tabs, splits)
+    }
+
+    /// The visible panels and where their content goes, back to front.
+    fn panes(&self, rect: LocalRect) -> Vec<(String, LocalRect)> {
+        let (tabs, _) = self.regions(rect);
+        let mut panes: Vec<_> = tabs
+            .iter()
+            .filter_map(|r| Some((r.panels.get(r.active)?.clone(), content(r.rect))))
+            .collect();
+        self.layout.with(|l| {
+            for f in &l.floating {
+                panes.push((f.panel.clone(), content(f.rect)));
+            }
+        });
+        panes
+    }
+
+    /// Where `panel` would go if dropped at `pt`, and the rect to preview,
+    /// or `None` to float it.
+    fn drop_target(
+        &self,
+        rect: LocalRect,
+        pt: LocalPoint,
+        panel: &str,
+    ) -> Option<(DockTarget, LocalRect)> {
+        let covered = self.layout.with(|l| {
+            l.floating
+                .iter()
+                .any(|f| f.panel != panel && f.rect.contains(pt))
+        });
+        if covered || !rect.contains(pt) {
+            return None;
+        }
+        if self.layout.with(|l| l.root.is_empty()) {
+            let target = DockTarget {
+                beside: None,
+                zone: DockZone::Tab,
+            };
+            return Some((target, rect));
+        }
+
+        let (tabs, _) = self.regions(rect);
+        let region = tabs.iter().find(|r| r.rect.contains(pt))?;
+
+        // Panels can't be docked beside themselves.
+        let beside = region.panels.iter().find(|p| *p != panel)?.clone();
+
+        let zone = if title_bar(region.rect).contains(pt) {
+            DockZone::Tab
+        } else {
+            let c = content(region.rect);
+            let fx = (pt.x - c.min_x()) / c.width();
+            let fy = (pt.y - c.min_y()) / c.height();
+            if fx < 0.25 {
+                DockZone::Left
+            } else if fx > 0.75 {
+                DockZone::Right
+            } else if fy > 0.75 {
+                DockZone::Top
+            } else if fy < 0.25 {
+                DockZone::Bottom
+            } else {
+                return None;
+            }
+        };
+        let target = DockTarget {
+            beside: Some(beside),
+            zone,
+        };
+        Some((target, zone_rect(region.rect, zone)))
+    }
+
+    /// Where `panel` goes when it floats with the touch at `pt`.
+    fn float_rect(&self, panel: &str, grab: LocalOffset, pt: LocalPoint) -> LocalRect {
+        let size = self.layout.with(|l| {
+            l.floating
+                .iter()
+                .find(|f| f.panel == panel)
+                .map(|f| f.rect.size)
+        });
+        LocalRect::new(pt - grab, size.unwrap_or_else(|| DOCK_FLOAT_SIZE.into()))
+    }
+
+    fn is_floating(&self, panel: &str) -> bool {
+        self.layout
+            .with(|l| l.floating.iter().any(|f| f.panel == panel))
+    }
+
+    /// Starts dragging a floating panel's title bar, a tab or a divider.
+    fn begin_drag(
+        &self,
+        rect: LocalRect,
+        pt: LocalPoint,
+        vger: &mut dyn Renderer,
+    ) -> Option<DockDrag> {
+        let floating = self.layout.with(|l| l.floating.clone());
+        for (i, f) in floating.iter().enumerate().rev() {
+            if title_bar(f.rect).contains(pt) {
+                self.layout.with_mut(|l| {
+                    let f = l.floating.remove(i);
+                    l.floating.push(f);
+                });
+                return Some(DockDrag::Panel {
+                    panel: f.panel.clone(),
+                    grab: pt - f.rect.origin,
+                    start: pt,
+                    current: pt,
+                    moved: false,
+                });
+            }
+            if f.rect.contains(pt) {
+                return None;
+            }
+        }
+
+        let (tabs, splits) = self.regions(rect);
+        for region in &tabs {
+            for (i, tab) in tab_rects(region, vger).iter().enumerate() {
+                if tab.contains(pt) {
+                    let panel = region.panels[i].clone();
+                    self.layout.with_mut(|l| {
+                        if let Some(DockNode::Tabs { active, .. }) = l.root.find_mut(&panel) {
+                            *active = i;
+                        }
+                    });
+                    let grab = LocalOffset::new(
+                        pt.x - tab.min_x(),
+                        DOCK_FLOAT_SIZE[1] - (tab.max_y() - pt.y),
+                    );
+                    return Some(DockDrag::Panel {
+                        panel,
+                        grab,
+                        start: pt,
+                        current: pt,
+                        moved: false,
+                    });
+                }
+            }
+        }
+
+        splits.iter().find_map(|s| {
+            let near = match s.axis {
+                DockAxis::Horizontal => (pt.x - s.divider).abs() < DOCK_DIVIDER_HIT,
+                DockAxis::Vertical => (pt.y - s.divider).abs() < DOCK_DIVIDER_HIT,
+            };
+            if near && s.rect.contains(pt) {
+                Some(DockDrag::Divider {
+                    path: s.path.clone(),
+                    axis: s.axis,
+                    rect: s.rect,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    fn drag(&self, drag: DockDrag, pt: LocalPoint, cx: &mut Context) -> DockDrag {
+        match drag {
+            DockDrag::Divider { path, axis, rect } => {
+                let ratio = match axis {
+                    DockAxis::Horizontal => (pt.x - rect.min_x()) / rect.width(),
+                    DockAxis::Vertical => (rect.max_y() - pt.y) / rect.height(),
+                };
+                self.layout.with_mut(|l| {
+                    if let Some(DockNode::Split { ratio: r, .. }) = l.root.node_mut(&path) {
+                        *r = ratio.clamp(0.1, 0.9);
+                    }
+                });
+                cx.set_cursor(match axis {
+                    DockAxis::Horizontal => CursorIcon::ColResize,
+                    DockAxis::Vertical => CursorIcon::RowResize,
+                });
+                DockDrag::Divider { path, axis, rect }
+            }
+            DockDrag::Panel {
+                panel,
+                grab,
+                start,
+                moved,
+                ..
+            } => {
+                let moved = moved || (pt - start).length() > DOCK_DRAG_THRESHOLD;
+
+                // Floating panels follow the touch; docked ones stay put
+                // until they're dropped.
+                if moved && self.is_floating(&panel) {
+                    let float = self.float_rect(&panel, grab, pt);
+                    self.layout.with_mut(|l| l.float(&panel, float));
+                }
+                DockDrag::Panel {
+                    panel,
+                    grab,
+                    start,
+                    current: pt,
+                    moved,
+                }
+            }
+        }
+    }
+
+    fn drop(&self, drag: DockDrag, rect: LocalRect, pt: LocalPoint) {
+        if let DockDrag::Panel {
+            panel,
+            grab,
+            moved: true,
+            ..
+        } = drag
+        {
+            match self.drop_target(rect, pt, &panel) {
+                Some((target, _)) => self.layout.with_mut(|l| l.dock(&panel, target)),
+                None => {
+                    let float = self.float_rect(&panel, grab, pt);
+                    self.layout.with_mut(|l| l.float(&panel, float));
+                }
+            }
+        }
+    }
+}
+
+impl<B, V, F> View for DockSpace<B, F>
+where
+    B: Binding<DockLayout>,
+    V: View,
+    F: Fn(&str) -> V,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("dock_space {{");
+        let rect = cx.layout.entry(id).or_default().rect;
+        for (panel, _) in self.panes(rect) {
+            (self.func)(&panel).print(id.child(&panel), cx);
+        }
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let pt = event.position;
+        let state = self.state(id, cx);
+        let panes = self.panes(rect);
+
+        let forward = |panel: &str, cx: &mut Context, vger: &mut dyn Renderer| {
+            // Once a panel has handled the event, the rest don't see it.
+            if cx.event_consumed() {
+                return;
+            }
+            let child_id = id.child(&panel);
+            let offset = cx.layout.entry(child_id).or_default().offset;
+            let mut local_event = event.clone();
+            local_event.position -= offset;
+            (self.func)(panel).process(&local_event, child_id, cx, vger);
+        };
+
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } => {
+                if !rect.contains(pt) {
+                    return;
+                }
+                if let Some(drag) = self.begin_drag(rect, pt, vger) {
+                    cx.touches[*touch] = id;
+                    state.with_mut(|s| s.drag = Some(drag));
+                    return;
+                }
+
+                // Only the panel in front gets the touch.
+                if let Some((panel, _)) = panes.iter().rev().find(|(_, r)| r.contains(pt)) {
+                    forward(panel, cx, vger);
+                }
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                if let Some(drag) = state.with(|s| s.drag.clone()) {
+                    let drag = self.drag(drag, pt, cx);
+                    state.with_mut(|s| s.drag = Some(drag));
+                }
+            }
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                if let Some(drag) = state.with(|s| s.drag.clone()) {
+                    self.drop(drag, rect, pt);
+                }
+                state.with_mut(|s| s.drag = None);
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| s.drag = None);
+            }
+            _ => {
+                for (panel, _) in &panes {
+                    forward(panel, cx, vger);
+                }
+            }
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let (tabs, splits) = self.regions(rect);
+
+        let draw_pane = |panel: &str, cx: &mut Context, vger: &mut dyn Renderer| {
+            let child_id = id.child(&panel);
+            let layout_box = *cx.layout.entry(child_id).or_default();
+            vger.save();
+            vger.clip(layout_box.rect.translate(layout_box.offset));
+            vger.translate(layout_box.offset);
+            (self.func)(panel).draw(child_id, cx, vger);
+            vger.restore();
+        };
+
+        for region in &tabs {
+            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            vger.fill_rect(title_bar(region.rect), 0.0, paint);
+            for (i, tab) in tab_rects(region, vger).iter().enumerate() {
+                if i == region.active {
+                    let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+                    vger.fill_rect(*tab, 0.0, paint);
+                }
+                draw_title(vger, &region.panels[i], *tab);
+            }
+            if let Some(panel) = region.panels.get(region.active) {
+                draw_pane(panel, cx, vger);
+            }
+        }
+
+        let paint = vger.color_paint(GROOVES);
+        for s in &splits {
+            let (a, b) = match s.axis {
+                DockAxis::Horizontal => (
+                    [s.divider, s.rect.min_y()].into(),
+                    [s.divider, s.rect.max_y()].into(),
+                ),
+                DockAxis::Vertical => (
+                    [s.rect.min_x(), s.divider].into(),
+                    [s.rect.max_x(), s.divider].into(),
+                ),
+            };
+            vger.stroke_segment(a, b, 2.0, paint);
+        }
+
+        let floating = self.layout.with(|l| l.floating.clone());
+        for f in &floating {
+            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            vger.fill_rect(f.rect, 4.0, paint);
+            let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+            vger.fill_rect(title_bar(f.rect), 0.0, paint);
+            draw_title(vger, &f.panel, title_bar(f.rect));
+            draw_pane(&f.panel, cx, vger);
+            let paint = vger.color_paint(GROOVES);
+            vger.stroke_rect(f.rect.min(), f.rect.max(), 4.0, 1.0, paint);
+        }
+
+        // Show where a dragged panel would go.
+        if let Some(DockDrag::Panel {
+            panel,
+            grab,
+            current,
+            moved: true,
+            ..
+        }) = self.state(id, cx).with(|s| s.drag.clone())
+        {
+            let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(0.3));
+            match self.drop_target(rect, current, &panel) {
+                Some((_, preview)) => vger.fill_rect(preview, 0.0, paint),
+                None if !self.is_floating(&panel) => {
+                    vger.fill_rect(self.float_rect(&panel, grab, current), 4.0, paint)
+                }
+                None => (),
+            }
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let rect = LocalRect::new(LocalPoint::zero(), constraints.biggest());
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect,
+                offset: LocalOffset::zero(),
+            },
+        );
+        for (panel, content) in self.panes(rect) {
+            let child_id = id.child(&panel);
+            (self.func)(&panel).layout(child_id, Constraints::tight(content.size), cx, vger);
+            cx.layout.insert(
+                child_id,
+                LayoutBox {
+                    rect: LocalRect::new(LocalPoint::zero(), content.size),
+                    offset: content.origin.to_vector(),
+                },
+            );
+        }
+        rect.size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if !rect.contains(pt) {
+            return None;
+        }
+        let panes = self.panes(rect);
+        match panes.iter().rev().find(|(_, r)| r.contains(pt)) {
+            Some((panel, content)) => {
+                let child_id = id.child(panel);
+                (self.func)(panel)
+                    .hittest(child_id, pt - content.origin.to_vector(), cx, vger)
+                    .or(Some(id))
+            }
+            None => Some(id),
+        }
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        for (panel, _) in self.panes(rect) {
+            (self.func)(&panel).commands(id.child(&panel), cx, cmds)
+        }
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            DockState::default(),
+            id,
+            map,
+            |state: State<DockState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+        let rect = cx.layout.entry(id).or_default().rect;
+        for (panel, _) in self.panes(rect) {
+            (self.func)(&panel).gc(id.child(&panel), cx, map)
+        }
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let mut node = accesskit::Node::new(id.access_id(), accesskit::Role::Group);
+        let rect = cx.layout.entry(id).or_default().rect;
+        for (panel, _) in self.panes(rect) {
+            if let Some(i) = (self.func)(&panel).access(id.child(&panel), cx, nodes) {
+                node.children.push(i)
+            }
+        }
+        nodes.push(node);
+        Some(id.access_id())
+    }
+}
+
+impl<B, F> private::Sealed for DockSpace<B, F> {}
+
+/// Panels arranged by `layout`, like an IDE's. `f` makes the view for
+/// each panel from its name. Drag a tab onto the tabs of another panel to
+/// join them, onto an edge of a panel to dock beside it, or elsewhere to
+/// float it. Dividers between panels can be dragged to resize them.
+pub fn dock_space<B, V, F>(layout: B, f: F) -> DockSpace<B, F>
+where
+    B: Binding<DockLayout>,
+    V: View,
+    F: Fn(&str) -> V,
+{
+    DockSpace { layout, func: f }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> DockLayout {
+        DockLayout::new(DockNode::split(
+            DockAxis::Horizontal,
+            0.25,
+            DockNode::tabs(&["files", "search"]),
+            DockNode::tabs(&["editor"]),
+        ))
+    }
+
+    #[test]
+    fn test_dock() {
+        let mut l = layout();
+        l.dock(
+            "files",
+            DockTarget {
+                beside: Some("editor".into()),
+                zone: DockZone::Bottom,
+            },
+        );
+        assert_eq!(
+            l.root,
+            DockNode::split(
+                DockAxis::Horizontal,
+                0.25,
+                DockNode::tabs(&["search"]),
+                DockNode::split(
+                    DockAxis::Vertical,
+                    0.5,
+                    DockNode::tabs(&["editor"]),
+                    DockNode::tabs(&["files"]),
+                ),
+            )
+        );
+
+        // Emptying the left side collapses its split.
+        l.float(
+            "search",
+            LocalRect::new([10.0, 10.0].into(), [100.0, 80.0].into()),
+        );
+        assert!(matches!(
+            l.root,
+            DockNode::Split {
+                axis: DockAxis::Vertical,
+                ..
+            }
+        ));
+        assert_eq!(l.floating.len(), 1);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let mut l = layout();
+        l.float(
+            "my search",
+            LocalRect::new([10.0, 10.0].into(), [100.0, 80.0].into()),
+        );
+        assert_eq!(DockLayout::load(&l.save()), Some(l));
+        assert_eq!(DockLayout::load("tabs 0 2 a"), None);
+    }
+}
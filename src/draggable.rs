@@ -0,0 +1,384 @@
+use crate::*;
+use std::ops::BitOr;
+
+/// How near an edge has to be dragged to a guide to snap to it.
+pub const SNAP_DISTANCE: f32 = 6.0;
+
+/// How far guide lines are drawn either way while dragging.
+const GUIDE_EXTENT: f32 = 10_000.0;
+
+/// A line a `draggable` view's edges and center snap to, at an x or y in
+/// the coordinates of the stack or list the view is in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Guide {
+    /// A vertical line at an x.
+    Vertical(f32),
+
+    /// A horizontal line at a y.
+    Horizontal(f32),
+}
+
+/// Where a `draggable` view snaps to as it's dragged. Made by `grid` and
+/// `guides`, and combined with `|`: `grid(8.0) | guides(lines)`. Guides
+/// win over the grid when they're near.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snap {
+    grid: Option<f32>,
+    guides: Vec<Guide>,
+}
+
+/// Snaps positions to multiples of `spacing`.
+pub fn grid(spacing: f32) -> Snap {
+    Snap {
+        grid: Some(spacing),
+        guides: vec![],
+    }
+}
+
+/// Snaps edges and centers to `guides` within `SNAP_DISTANCE`.
+pub fn guides(guides: Vec<Guide>) -> Snap {
+    Snap { grid: None, guides }
+}
+
+impl BitOr for Snap {
+    type Output = Snap;
+
+    fn bitor(mut self, rhs: Snap) -> Snap {
+        self.guides.extend(rhs.guides);
+        Snap {
+            grid: rhs.grid.or(self.grid),
+            guides: self.guides,
+        }
+    }
+}
+
+impl Snap {
+    /// Where a view laid out at `rect`, dragged to `position`, snaps to,
+    /// and the guides it snapped to.
+    pub fn snap(&self, position: LocalOffset, rect: LocalRect) -> (LocalOffset, Vec<Guide>) {
+        let rect = rect.translate(position);
+        let mut snapped = position;
+        let mut active = vec![];
+
+        // The nearest guide to any of the edges or the center on an axis.
+        let nearest = |lines: Vec<f32>, edges: [f32; 3]| {
+            let mut best: Option<(f32, f32)> = None;
+            for line in lines {
+                for edge in edges.iter() {
+                    let d = line - edge;
+                    let nearer = match best {
+                        Some((_, best_d)) => d.abs() < best_d.abs(),
+                        None => d.abs() <= SNAP_DISTANCE,
+                    };
+                    if nearer {
+                        best = Some((line, d));
+                    }
+                }
+            }
+            best
+        };
+        let verticals = self
+            .guides
+            .iter()
+            .filter_map(|g| match g {
+                Guide::Vertical(x) => Some(*x),
+                _ => None,
+            })
+            .collect();
+        let horizontals = self
+            .guides
+            .iter()
+            .filter_map(|g| match g {
+                Guide::Horizontal(y) => Some(*y),
+                _ => None,
+            })
+            .collect();
+        let round = |length: f32, spacing: f32| (length / spacing).round() * spacing;
+
+        match nearest(verticals, [rect.min_x(), rect.center().x, rect.max_x()]) {
+            Some((x, d)) => {
+                snapped.x += d;
+                active.push(Guide::Vertical(x));
+            }
+            None => {
+                if let Some(spacing) = self.grid {
+                    snapped.x += round(rect.min_x(), spacing) - rect.min_x();
+                }
+            }
+        }
+        match nearest(horizontals, [rect.min_y(), rect.center().y, rect.max_y()]) {
+            Some((y, d)) => {
+                snapped.y += d;
+                active.push(Guide::Horizontal(y));
+            }
+            None => {
+                if let Some(spacing) = self.grid {
+                    snapped.y += round(rect.min_y(), spacing) - rect.min_y();
+                }
+            }
+        }
+        (snapped, active)
+    }
+}
+
+#[derive(Clone, Default)]
+struct DraggableState {
+    /// Where the view is, if there's no binding for it.
+    position: LocalOffset,
+
+    /// Where the touch and the view were when the drag started.
+    start: Option<(LocalPoint, LocalOffset)>,
+
+    /// The guides snapped to, shown while dragging.
+    guides: Vec<Guide>,
+}
+
+/// Struct for the `draggable` modifier.
+pub struct Draggable<V, B> {
+    child: V,
+    binding: Option<B>,
+    snap: Snap,
+}
+
+impl<V, B> Draggable<V, B> {
+    pub fn new(child: V) -> Self {
+        Self {
+            child,
+            binding: None,
+            snap: Snap::default(),
+        }
+    }
+
+    /// Keeps how far the view has been dragged in `binding`, so it can be
+    /// read and set.
+    pub fn position<B2: Binding<LocalOffset>>(self, binding: B2) -> Draggable<V, B2> {
+        Draggable {
+            child: self.child,
+            binding: Some(binding),
+            snap: self.snap,
+        }
+    }
+
+    /// Snaps the view as it's dragged, showing the guides it snaps to.
+    pub fn snap_to(self, snap: Snap) -> Self {
+        Self { snap, ..self }
+    }
+}
+
+impl<V, B> Draggable<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<DraggableState> {
+        cx.get_state(id, DraggableState::default)
+    }
+
+    fn get(&self, id: ViewID, cx: &mut Context) -> LocalOffset {
+        match &self.binding {
+            Some(binding) => binding.get(),
+            None => self.state(id, cx).with(|s| s.position),
+        }
+    }
+
+    fn set(&self, id: ViewID, cx: &mut Context, position: LocalOffset) {
+        match &self.binding {
+            Some(binding) => binding.set(position),
+            None => self.state(id, cx).with_mut(|s| s.position = position),
+        }
+    }
+}
+
+impl<V, B> View for Draggable<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".draggable({:?})", self.get(id, cx));
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        // Positions stay where the view was laid out, so they don't move
+        // with it.
+        let position = self.get(id, cx);
+        let mut local_event = event.clone();
+        local_event.position -= position;
+        self.child.process(&local_event, id.child(&0), cx, vger);
+
+        let layout_box = *cx.layout.entry(id).or_default();
+        let rect = layout_box.rect;
+        let state = self.state(id, cx);
+        match &event.kind {
+            EventKind::TouchBegin { id: touch }
+                if !cx.event_consumed() && rect.contains(local_event.position) =>
+            {
+                cx.touches[*touch] = id;
+                state.with_mut(|s| s.start = Some((event.position, position)));
+                cx.consume_event();
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                if let Some((start, from)) = state.with(|s| s.start) {
+                    let (snapped, guides) = self.snap.snap(
+                        from + (event.position - start),
+                        rect.translate(layout_box.offset),
+                    );
+                    if snapped != position {
+                        self.set(id, cx, snapped);
+                    }
+                    if guides != state.with(|s| s.guides.clone()) {
+                        state.with_mut(|s| s.guides = guides);
+                    }
+                }
+            }
+            EventKind::TouchEnd { id: touch } | EventKind::TouchCancel { id: touch }
+                if cx.touches[*touch] == id =>
+            {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| {
+                    s.start = None;
+                    s.guides.clear();
+                });
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let position = self.get(id, cx);
+        vger.save();
+        vger.translate(position);
+        self.child.draw(id.child(&0), cx, vger);
+        vger.restore();
+
+        // Over the view, though views drawn after it can cover them.
+        let origin = cx.layout.entry(id).or_default().offset;
+        let paint = vger.color_paint(RED_HIGHLIGHT);
+        for guide in self.state(id, cx).with(|s| s.guides.clone()) {
+            let (a, b) = match guide {
+                Guide::Vertical(x) => (
+                    LocalPoint::new(x, -GUIDE_EXTENT),
+                    LocalPoint::new(x, GUIDE_EXTENT),
+                ),
+                Guide::Horizontal(y) => (
+                    LocalPoint::new(-GUIDE_EXTENT, y),
+                    LocalPoint::new(GUIDE_EXTENT, y),
+                ),
+            };
+            vger.stroke_segment(a - origin, b - origin, 1.0, paint);
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.child.layout(id.child(&0), constraints, cx, vger);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let position = self.get(id, cx);
+        let rect = cx.layout.entry(id).or_default().rect;
+        let pt = pt - position;
+        self.child.hittest(id.child(&0), pt, cx, vger).or_else(|| {
+            if rect.contains(pt) {
+                Some(id)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = self.state(id, cx);
+        map.insert(id, Box::new(state));
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, B> private::Sealed for Draggable<V, B> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap() {
+        let rect = LocalRect::new(LocalPoint::zero(), [20.0, 10.0].into());
+        let snap = grid(8.0) | guides(vec![Guide::Vertical(50.0), Guide::Horizontal(100.0)]);
+
+        // The right edge snaps to the guide, the bottom to the grid.
+        let (snapped, active) = snap.snap([27.0, 13.0].into(), rect);
+        assert_eq!(snapped, LocalOffset::new(30.0, 16.0));
+        assert_eq!(active, vec![Guide::Vertical(50.0)]);
+
+        // The center snaps to the guide, the left edge to the grid.
+        let (snapped, active) = snap.snap([3.0, 97.0].into(), rect);
+        assert_eq!(snapped, LocalOffset::new(0.0, 95.0));
+        assert_eq!(active, vec![Guide::Horizontal(100.0)]);
+    }
+
+    #[test]
+    fn test_draggable() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let position = State::new(LocalOffset::zero(), cx.dirty.clone());
+        let ui = rectangle()
+            .size([10.0, 10.0])
+            .draggable()
+            .position(position.clone())
+            .snap_to(grid(8.0));
+        cx.layout_root(&ui, size, &mut vger);
+
+        for (kind, pt) in [
+            (EventKind::TouchBegin { id: 0 }, [5.0, 5.0]),
+            (EventKind::TouchMove { id: 0 }, [22.0, 14.0]),
+            (EventKind::TouchEnd { id: 0 }, [22.0, 14.0]),
+        ]
+        .iter()
+        {
+            let event = Event {
+                kind: kind.clone(),
+                position: (*pt).into(),
+            };
+            cx.process_root(&ui, &event, &mut vger);
+        }
+        assert_eq!(position.get(), LocalOffset::new(16.0, 8.0));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::*;
+
+/// Struct for the `env` modifier.
+pub struct EnvView<V, E> {
+    child: V,
+    value: E,
+}
+
+impl<V, E> EnvView<V, E>
+where
+    V: View,
+    E: Clone + 'static,
+{
+    pub fn new(child: V, value: E) -> Self {
+        Self { child, value }
+    }
+}
+
+impl<V, E> View for EnvView<V, E>
+where
+    V: View,
+    E: Clone + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".env()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.process(event, id.child(&0), cx, vger)
+        })
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.draw(id.child(&0), cx, vger)
+        })
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.layout(id.child(&0), constraints, cx, vger)
+        })
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.hittest(id.child(&0), pt, cx, vger)
+        })
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.commands(id.child(&0), cx, cmds)
+        })
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.gc(id.child(&0), cx, map)
+        })
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.access(id.child(&0), cx, nodes)
+        })
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        cx.with_env(self.value.clone(), |cx| {
+            self.child.intrinsic_size(id.child(&0), cx, vger)
+        })
+    }
+}
+
+impl<V, E> private::Sealed for EnvView<V, E> {}
+
+/// Color that icons below are drawn in, instead of their own colors.
+/// Set with the `tint` modifier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TintColor(pub Color);
+
+/// Struct for the `font` modifier.
+pub struct FontView<V> {
+    child: V,
+    font: fonts::Font,
+}
+
+impl<V> FontView<V>
+where
+    V: View,
+{
+    pub fn new(child: V, font: fonts::Font) -> Self {
+        Self { child, font }
+    }
+
+    /// Calls `f` with the font selected, then goes back to the font of the
+    /// views outside.
+    fn with_font<R>(
+        &self,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+        f: impl FnOnce(&mut Context, &mut dyn Renderer) -> R,
+    ) -> R {
+        let outer = cx.get_env::<fonts::Font>();
+        vger.set_font(Some(&self.font));
+        let result = cx.with_env(self.font.clone(), |cx| f(cx, vger));
+        vger.set_font(outer.as_ref());
+        result
+    }
+}
+
+impl<V> View for FontView<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".font({:?})", self.font);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.with_font(cx, vger, |cx, vger| {
+            self.child.process(event, id.child(&0), cx, vger)
+        })
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.with_font(cx, vger, |cx, vger| self.child.draw(id.child(&0), cx, vger))
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.with_font(cx, vger, |cx, vger| {
+            self.child.layout(id.child(&0), constraints, cx, vger)
+        })
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.with_font(cx, vger, |cx, vger| {
+            self.child.hittest(id.child(&0), pt, cx, vger)
+        })
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.with_font(cx, vger, |cx, vger| {
+            self.child.intrinsic_size(id.child(&0), cx, vger)
+        })
+    }
+}
+
+impl<V> private::Sealed for FontView<V> {}
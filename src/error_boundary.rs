@@ -0,0 +1,265 @@
+use crate::*;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// The message a panic was raised with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/// Struct for `error_boundary`.
+pub struct ErrorBoundary<V, F> {
+    child: V,
+    fallback: F,
+}
+
+impl<V, R, F> ErrorBoundary<V, F>
+where
+    V: View,
+    R: View,
+    F: Fn(&str) -> R,
+{
+    fn error(&self, id: ViewID, cx: &mut Context) -> Option<String> {
+        cx.with_state(None, id, |state: State<Option<String>>, _cx| state.get())
+    }
+
+    /// Runs `f` on the child, catching any panic. Returns `None` if it
+    /// panicked, after which the fallback shows instead. The environment
+    /// and the renderer's saved states are put back as they were, since
+    /// the views which set them didn't get to undo them.
+    fn guard<T>(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+        f: impl FnOnce(&mut Context, &mut dyn Renderer) -> T,
+    ) -> Option<T> {
+        let env = cx.env.clone();
+        let depth = vger.save_depth();
+        match catch_unwind(AssertUnwindSafe(|| f(cx, vger))) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                cx.env = env;
+                while vger.save_depth() > depth {
+                    vger.restore();
+                }
+                let message = panic_message(payload.as_ref());
+                println!("error_boundary caught a panic: {}", message);
+                cx.with_state(None, id, |state: State<Option<String>>, _cx| {
+                    state.set(Some(message.clone()))
+                });
+                None
+            }
+        }
+    }
+}
+
+impl<V, R, F> View for ErrorBoundary<V, F>
+where
+    V: View,
+    R: View,
+    F: Fn(&str) -> R,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("error_boundary(");
+        match self.error(id, cx) {
+            Some(err) => (self.fallback)(&err).print(id.child(&1), cx),
+            None => self.child.print(id.child(&0), cx),
+        }
+        println!(")");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        match self.error(id, cx) {
+            Some(err) => (self.fallback)(&err).process(event, id.child(&1), cx, vger),
+            None => {
+                self.guard(id, cx, vger, |cx, vger| {
+                    self.child.process(event, id.child(&0), cx, vger)
+                });
+            }
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        match self.error(id, cx) {
+            Some(err) => (self.fallback)(&err).draw(id.child(&1), cx, vger),
+            None => {
+                // The fallback is laid out next time around.
+                self.guard(id, cx, vger, |cx, vger| {
+                    self.child.draw(id.child(&0), cx, vger)
+                });
+            }
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        if self.error(id, cx).is_none() {
+            if let Some(size) = self.guard(id, cx, vger, |cx, vger| {
+                self.child.layout(id.child(&0), constraints, cx, vger)
+            }) {
+                return size;
+            }
+        }
+        let err = self.error(id, cx).unwrap_or_default();
+        (self.fallback)(&err).layout(id.child(&1), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        match self.error(id, cx) {
+            Some(err) => (self.fallback)(&err).hittest(id.child(&1), pt, cx, vger),
+            None => self
+                .guard(id, cx, vger, |cx, vger| {
+                    self.child.hittest(id.child(&0), pt, cx, vger)
+                })
+                .flatten(),
+        }
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        match self.error(id, cx) {
+            Some(err) => (self.fallback)(&err).commands(id.child(&1), cx, cmds),
+            None => self.child.commands(id.child(&0), cx, cmds),
+        }
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(None, id, map, |state: State<Option<String>>, _cx, map| {
+            map.insert(id, Box::new(state));
+        });
+        match self.error(id, cx) {
+            Some(err) => (self.fallback)(&err).gc(id.child(&1), cx, map),
+            None => self.child.gc(id.child(&0), cx, map),
+        }
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        match self.error(id, cx) {
+            Some(err) => (self.fallback)(&err).access(id.child(&1), cx, nodes),
+            None => self.child.access(id.child(&0), cx, nodes),
+        }
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, F> private::Sealed for ErrorBoundary<V, F> {}
+
+/// Contains panics in `view`. If it panics while processing an event,
+/// drawing or laying out, the panic is logged and `fallback` is shown in
+/// its place, called with the panic message, rather than the whole app
+/// going down.
+pub fn error_boundary<V: View, R: View, F: Fn(&str) -> R + 'static>(
+    view: V,
+    fallback: F,
+) -> ErrorBoundary<V, F> {
+    ErrorBoundary {
+        child: view,
+        fallback,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Panics when it's drawn.
+    struct Panics;
+
+    impl View for Panics {
+        fn print(&self, _id: ViewID, _cx: &mut Context) {
+            println!("Panics");
+        }
+        fn process(
+            &self,
+            _event: &Event,
+            _id: ViewID,
+            _cx: &mut Context,
+            _vger: &mut dyn Renderer,
+        ) {
+        }
+        fn draw(&self, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+            panic!("boom")
+        }
+        fn layout(
+            &self,
+            _id: ViewID,
+            constraints: Constraints,
+            _cx: &mut Context,
+            _vger: &mut dyn Renderer,
+        ) -> LocalSize {
+            constraints.max
+        }
+        fn hittest(
+            &self,
+            _id: ViewID,
+            _pt: LocalPoint,
+            _cx: &mut Context,
+            _vger: &mut dyn Renderer,
+        ) -> Option<ViewID> {
+            None
+        }
+        fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+        fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+        fn access(
+            &self,
+            _id: ViewID,
+            _cx: &mut Context,
+            _nodes: &mut Vec<accesskit::Node>,
+        ) -> Option<accesskit::NodeId> {
+            None
+        }
+    }
+
+    impl private::Sealed for Panics {}
+
+    #[test]
+    fn test_fallback() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        // The padding saves the renderer and the disabled modifier sets
+        // the environment around the panic.
+        let ui = error_boundary(Panics.padding(Auto).disabled(true), |err| {
+            text(&format!("oops: {}", err))
+        });
+        cx.layout_root(&ui, size, &mut vger);
+        ui.draw(cx.root_id, &mut cx, &mut vger);
+        assert_eq!(vger.save_depth(), 0);
+        assert!(cx.is_enabled());
+
+        cx.layout_root(&ui, size, &mut vger);
+        let query = cx.query(&ui, &mut vger);
+        assert_eq!(query.by_label("oops: boom").len(), 1);
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = catch_unwind(|| panic!("bad {}", 42)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "bad 42");
+    }
+}
@@ -0,0 +1,146 @@
+use crate::*;
+
+/// Struct for the `on_event_capture` modifier.
+pub struct OnEventCapture<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> OnEventCapture<V, F>
+where
+    V: View,
+    F: Fn(&Event) -> bool + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self { child: v, func: f }
+    }
+}
+
+impl<V, F> View for OnEventCapture<V, F>
+where
+    V: View,
+    F: Fn(&Event) -> bool + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".on_event_capture()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if (self.func)(event) {
+            cx.consume_event();
+        } else {
+            self.child.process(event, id.child(&0), cx, vger);
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, F> private::Sealed for OnEventCapture<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_consumed_events() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let (front, back, outer) = (
+            Rc::new(Cell::new(0)),
+            Rc::new(Cell::new(0)),
+            Rc::new(Cell::new(0)),
+        );
+        let (f, b, o) = (front.clone(), back.clone(), outer.clone());
+        let ui = zstack((
+            rectangle().tap(move || b.set(b.get() + 1)),
+            rectangle().tap(move || f.set(f.get() + 1)),
+        ))
+        .tap(move || o.set(o.get() + 1));
+        cx.layout_root(&ui, size, &mut vger);
+
+        // Only the view on top gets the tap.
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        assert_eq!((front.get(), back.get(), outer.get()), (1, 0, 0));
+
+        // Capturing the touches stops them reaching the views inside.
+        let ui = ui.on_event_capture(|event| matches!(event.kind, EventKind::TouchBegin { .. }));
+        cx.layout_root(&ui, size, &mut vger);
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        assert_eq!((front.get(), back.get(), outer.get()), (1, 0, 0));
+
+        // The same goes for the rows of a list.
+        let seen = Rc::new(Cell::new(0));
+        let s = seen.clone();
+        let ui = list(vec![0, 1, 2], move |_| {
+            let s = s.clone();
+            rectangle().on_event_capture(move |_| {
+                s.set(s.get() + 1);
+                true
+            })
+        });
+        cx.layout_root(&ui, size, &mut vger);
+        let event = Event {
+            kind: EventKind::Key(KeyPress::Enter, ModifiersState::empty()),
+            position: LocalPoint::zero(),
+        };
+        cx.process_root(&ui, &event, &mut vger);
+        assert_eq!(seen.get(), 1);
+    }
+}
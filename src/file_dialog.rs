@@ -0,0 +1,128 @@
+use crate::*;
+use futures::executor::block_on;
+use std::future::Future;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Instant;
+
+/// Restricts a file dialog to some file types.
+#[derive(Clone, Debug)]
+pub struct FileFilter {
+    name: String,
+    extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// `extensions` don't include the dot, for example `&["png", "jpg"]`.
+    pub fn new(name: &str, extensions: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+}
+
+fn dialog(filters: &[FileFilter]) -> rfd::AsyncFileDialog {
+    filters
+        .iter()
+        .fold(rfd::AsyncFileDialog::new(), |dialog, filter| {
+            dialog.add_filter(&filter.name, &filter.extensions)
+        })
+}
+
+async fn pick_file(filters: Vec<FileFilter>) -> Option<PathBuf> {
+    let file = dialog(&filters).pick_file().await;
+    file.map(|file| file.path().to_path_buf())
+}
+
+async fn save_file(filters: Vec<FileFilter>, file_name: String) -> Option<PathBuf> {
+    let file = dialog(&filters).set_file_name(&file_name).save_file().await;
+    file.map(|file| file.path().to_path_buf())
+}
+
+/// Waits for the dialog, then has the event loop call `f` with what was
+/// chosen, so `f` runs on the UI thread like any other callback.
+async fn deliver<F: FnOnce(Option<PathBuf>) + Send + 'static>(
+    file: impl Future<Output = Option<PathBuf>>,
+    f: F,
+) {
+    let file = file.await;
+    schedule_app(Instant::now(), move || f(file));
+}
+
+/// Shows a native dialog for choosing a file to open, without blocking.
+/// `f` is called on the UI thread with the chosen path, or `None` if the
+/// dialog was cancelled.
+pub fn open_file_dialog<F: FnOnce(Option<PathBuf>) + Send + 'static>(filters: &[FileFilter], f: F) {
+    let file = pick_file(filters.to_vec());
+    thread::spawn(move || block_on(deliver(file, f)));
+}
+
+/// Shows a native dialog for choosing where to save a file, without
+/// blocking. `f` is called like with `open_file_dialog`.
+pub fn save_file_dialog<F: FnOnce(Option<PathBuf>) + Send + 'static>(
+    filters: &[FileFilter],
+    file_name: &str,
+    f: F,
+) {
+    let file = save_file(filters.to_vec(), file_name.to_string());
+    thread::spawn(move || block_on(deliver(file, f)));
+}
+
+impl Context {
+    /// Like `open_file_dialog`, but the dialog is a task of the view at
+    /// `id`, so `f` isn't called if the view goes away first.
+    pub fn open_file_dialog<F: FnOnce(Option<PathBuf>) + Send + 'static>(
+        &mut self,
+        id: ViewID,
+        filters: &[FileFilter],
+        f: F,
+    ) -> TaskHandle {
+        self.spawn(id, deliver(pick_file(filters.to_vec()), f))
+    }
+
+    /// Like `save_file_dialog`, as a task of the view at `id`.
+    pub fn save_file_dialog<F: FnOnce(Option<PathBuf>) + Send + 'static>(
+        &mut self,
+        id: ViewID,
+        filters: &[FileFilter],
+        file_name: &str,
+        f: F,
+    ) -> TaskHandle {
+        let file = save_file(filters.to_vec(), file_name.to_string());
+        self.spawn(id, deliver(file, f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::ready;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_deliver_on_ui_thread() {
+        let _timers = lock_app_timers();
+        let mut cx = Context::headless();
+        let id = cx.root_id;
+        cx.get_state(id, || 0);
+
+        let chosen = Arc::new(Mutex::new(None));
+        let c = chosen.clone();
+        let path = PathBuf::from("notes.txt");
+        let file = ready(Some(path.clone()));
+        let task = cx.spawn(
+            id,
+            deliver(file, move |file| {
+                *c.lock().unwrap() = Some((file, thread::current().id()));
+            }),
+        );
+        assert_eq!(task.join(), TaskEnd::Finished);
+
+        // Nothing is called until the event loop runs the callback.
+        assert!(chosen.lock().unwrap().is_none());
+        run_timers(Instant::now());
+        let chosen = chosen.lock().unwrap().take();
+        assert_eq!(chosen, Some((Some(path), thread::current().id())));
+    }
+}
@@ -15,31 +15,40 @@ where
         println!(")");
     }
 
-    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut VGER) {
-        match &event.kind {
-            EventKind::TouchBegin { .. } => {
-                if let Some(_) = self.hittest(vid, event.position, cx, vger) {
-                    cx.focused_id = Some(vid);
-                    cx.dirty.lock().unwrap().dirty = true;
-                }
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::TouchBegin { .. } = &event.kind {
+            if self.hittest(vid, event.position, cx, vger).is_some() {
+                cx.focused_id = Some(vid);
+                cx.dirty.lock().unwrap().dirty = true;
             }
-            EventKind::Key(KeyPress::Escape, _) => {
-                if cx.focused_id == Some(vid) {
-                    cx.focused_id = None;
-                    cx.dirty.lock().unwrap().dirty = true;
-                }
+        }
+        (self.func)(Some(vid) == cx.focused_id).process(event, vid.child(&0), cx, vger);
+
+        // Unfocus after processing, so the focused view also sees the escape.
+        if let EventKind::Key(KeyPress::Escape, _) = &event.kind {
+            if cx.focused_id == Some(vid) {
+                cx.focused_id = None;
+                cx.dirty.lock().unwrap().dirty = true;
             }
-            _ => (),
         }
-        (self.func)(Some(vid) == cx.focused_id).process(event, vid.child(&0), cx, vger)
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         (self.func)(Some(id) == cx.focused_id).draw(id.child(&0), cx, vger)
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        (self.func)(Some(id) == cx.focused_id).layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        // Disabled views give up the focus.
+        if !cx.is_enabled() && cx.focused_id == Some(id) {
+            cx.focused_id = None;
+        }
+        (self.func)(Some(id) == cx.focused_id).layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -47,7 +56,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         (self.func)(Some(id) == cx.focused_id).hittest(id.child(&0), pt, cx, vger)
     }
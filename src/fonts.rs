@@ -0,0 +1,242 @@
+//! Fonts apps can register and select, on top of the system's fonts.
+//!
+//! Only the software renderer (the `software` feature) draws with them.
+//! vger has a single built-in font, so on the GPU text is drawn with it
+//! whatever font is selected, and without the software renderer's
+//! fallback fonts for emoji or its shaping.
+//!
+//! ```no_run
+//! # #[cfg(feature = "software")]
+//! # {
+//! use rui::*;
+//!
+//! fonts::register("assets/Brand-Bold.ttf", "Brand").unwrap();
+//! let view = text("hello").font(fonts::Font::new("Brand").bold());
+//! render_to_image(&view, [200.0, 50.0].into(), 2.0)
+//!     .save_png("hello.png")
+//!     .unwrap();
+//! # }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const REGULAR: u16 = 400;
+pub const BOLD: u16 = 700;
+
+/// Selects a font by family, weight and style. Set it for a view and the
+/// views inside it with the `font` modifier.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Font {
+    pub family: String,
+
+    /// From 100 (thin) to 900 (black).
+    pub weight: u16,
+    pub italic: bool,
+}
+
+impl Font {
+    pub fn new(family: &str) -> Self {
+        Self {
+            family: family.into(),
+            weight: REGULAR,
+            italic: false,
+        }
+    }
+
+    pub fn weight(self, weight: u16) -> Self {
+        Self { weight, ..self }
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(BOLD)
+    }
+
+    pub fn italic(self) -> Self {
+        Self {
+            italic: true,
+            ..self
+        }
+    }
+}
+
+/// Where a font comes from: its contents, or a file to read.
+pub enum FontSource {
+    Bytes(Vec<u8>),
+    Path(PathBuf),
+}
+
+impl From<&[u8]> for FontSource {
+    fn from(bytes: &[u8]) -> Self {
+        FontSource::Bytes(bytes.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for FontSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        FontSource::Bytes(bytes)
+    }
+}
+
+impl From<&Path> for FontSource {
+    fn from(path: &Path) -> Self {
+        FontSource::Path(path.into())
+    }
+}
+
+impl From<PathBuf> for FontSource {
+    fn from(path: PathBuf) -> Self {
+        FontSource::Path(path)
+    }
+}
+
+impl From<&str> for FontSource {
+    fn from(path: &str) -> Self {
+        FontSource::Path(path.into())
+    }
+}
+
+struct Registry {
+    db: fontdb::Database,
+    system_loaded: bool,
+
+    /// Faces registered under family names of the app's choosing, by
+    /// lowercase family name.
+    aliases: HashMap<String, Vec<fontdb::ID>>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry {
+        db: fontdb::Database::new(),
+        system_loaded: false,
+        aliases: HashMap::new(),
+    });
+}
+
+fn with_registry<R>(f: impl FnOnce(&mut Registry) -> R) -> R {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        if !registry.system_loaded {
+            registry.db.load_system_fonts();
+            registry.system_loaded = true;
+        }
+        f(&mut registry)
+    })
+}
+
+/// Registers a font, so it can be selected with `family` as well as its
+/// own family name. A file with several faces registers them all.
+pub fn register(source: impl Into<FontSource>, family: &str) -> std::io::Result<()> {
+    with_registry(|registry| {
+        let before = registry.db.faces().len();
+        match source.into() {
+            FontSource::Bytes(bytes) => registry.db.load_font_data(bytes),
+            FontSource::Path(path) => registry.db.load_font_file(path)?,
+        }
+        let ids: Vec<_> = registry.db.faces()[before..]
+            .iter()
+            .map(|face| face.id)
+            .collect();
+        if ids.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "no fonts found",
+            ));
+        }
+        registry
+            .aliases
+            .entry(family.to_lowercase())
+            .or_default()
+            .extend(ids);
+        Ok(())
+    })
+}
+
+/// The families of all the fonts on the system and registered by the app.
+pub fn families() -> Vec<String> {
+    with_registry(|registry| {
+        let mut families: Vec<String> = registry
+            .db
+            .faces()
+            .iter()
+            .map(|face| face.family.clone())
+            .collect();
+        families.extend(registry.aliases.keys().cloned());
+        families.sort_by_key(|f| f.to_lowercase());
+        families.dedup_by_key(|f| f.to_lowercase());
+        families
+    })
+}
+
+/// Picks the face closest to `weight` and `italic` from `(weight,
+/// italic)` candidates, preferring the right style. Like CSS, lighter
+/// weights are preferred for light requests and heavier ones for bold.
+pub(crate) fn best_match(candidates: &[(u16, bool)], weight: u16, italic: bool) -> Option<usize> {
+    let cost = |&(w, i): &(u16, bool)| {
+        let style = if i == italic { 0 } else { 10_000 };
+        let w = w as i32;
+        let weight = weight as i32;
+        let direction = if weight > 500 {
+            if w >= weight {
+                0
+            } else {
+                1000
+            }
+        } else if w <= weight {
+            0
+        } else {
+            1000
+        };
+        style + direction + (w - weight).abs()
+    };
+    (0..candidates.len()).min_by_key(|i| cost(&candidates[*i]))
+}
+
+/// Calls `f` with the data and collection index of the face best matching
+/// `font`, if there's a family of that name.
+pub(crate) fn with_font_data<R>(font: &Font, f: impl FnOnce(&[u8], u32) -> R) -> Option<R> {
+    with_registry(|registry| {
+        let family = font.family.to_lowercase();
+        let ids: Vec<fontdb::ID> = match registry.aliases.get(&family) {
+            Some(ids) => ids.clone(),
+            None => registry
+                .db
+                .faces()
+                .iter()
+                .filter(|face| face.family.to_lowercase() == family)
+                .map(|face| face.id)
+                .collect(),
+        };
+        let candidates: Vec<(u16, bool)> = ids
+            .iter()
+            .filter_map(|id| {
+                let face = registry.db.face(*id)?;
+                Some((face.weight.0, face.style != fontdb::Style::Normal))
+            })
+            .collect();
+        let best = best_match(&candidates, font.weight, font.italic)?;
+        registry.db.with_face_data(ids[best], f)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_match() {
+        let faces = [(300, false), (400, false), (700, false), (400, true)];
+        assert_eq!(best_match(&faces, REGULAR, false), Some(1));
+        assert_eq!(best_match(&faces, BOLD, false), Some(2));
+        assert_eq!(best_match(&faces, REGULAR, true), Some(3));
+
+        // 600 has no exact match, so go heavier.
+        assert_eq!(best_match(&faces, 600, false), Some(2));
+
+        // 350 goes lighter.
+        assert_eq!(best_match(&faces, 350, false), Some(0));
+
+        assert_eq!(best_match(&[], REGULAR, false), None);
+    }
+}
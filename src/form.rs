@@ -0,0 +1,176 @@
+use crate::*;
+
+pub const FORM_LABEL_FONT_SIZE: u32 = 14;
+pub const FORM_ERROR_FONT_SIZE: u32 = 12;
+
+/// A control which can be wrapped in a `field`.
+pub trait FormInput: View + Clone {
+    type Value;
+
+    /// The current value of the control, for validation.
+    fn value(&self) -> Self::Value;
+}
+
+type Validator<T> = Box<dyn Fn(&T) -> Result<(), String>>;
+
+/// Struct for `field`.
+pub struct Field<I: FormInput> {
+    label: String,
+    input: I,
+    validators: Vec<Validator<I::Value>>,
+}
+
+impl<I: FormInput> Field<I> {
+    /// Adds a validation rule. The message from the first rule which
+    /// fails is shown below the field.
+    pub fn validate<F: Fn(&I::Value) -> Result<(), String> + 'static>(mut self, f: F) -> Self {
+        self.validators.push(Box::new(f));
+        self
+    }
+
+    fn body(&self) -> impl View {
+        let error = self.error().unwrap_or_default();
+        vstack((
+            text(&self.label).font_size(FORM_LABEL_FONT_SIZE),
+            self.input.clone(),
+            text(&error)
+                .font_size(FORM_ERROR_FONT_SIZE)
+                .color(RED_HIGHLIGHT),
+        ))
+    }
+}
+
+impl<I: FormInput> View for Field<I> {
+    body_view!();
+}
+
+impl<I: FormInput> private::Sealed for Field<I> {}
+
+/// Labels a control and validates its value.
+pub fn field<I: FormInput>(label: &str, input: I) -> Field<I> {
+    Field {
+        label: label.into(),
+        input,
+        validators: vec![],
+    }
+}
+
+/// A view which can report a validation error to a `form`.
+pub trait FormField: View {
+    fn error(&self) -> Option<String>;
+}
+
+impl<I: FormInput> FormField for Field<I> {
+    fn error(&self) -> Option<String> {
+        let value = self.input.value();
+        self.validators.iter().find_map(|f| f(&value).err())
+    }
+}
+
+/// Allows a `form` to collect the errors from a tuple of fields.
+pub trait FieldTuple: ViewTuple {
+    fn errors(&self) -> Vec<String>;
+}
+
+macro_rules! impl_field_tuple {
+    ($($t:ident $i:tt),+) => {
+        impl<$($t: FormField),+> FieldTuple for ($($t,)+) {
+            fn errors(&self) -> Vec<String> {
+                vec![$(self.$i.error()),+].into_iter().flatten().collect()
+            }
+        }
+    };
+}
+
+impl_field_tuple!(A 0);
+impl_field_tuple!(A 0, B 1);
+impl_field_tuple!(A 0, B 1, C 2);
+impl_field_tuple!(A 0, B 1, C 2, D 3);
+impl_field_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_field_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_field_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_field_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// Stacks fields vertically above a submit button, which is
+/// disabled until all the fields are valid.
+pub fn form<T: FieldTuple + 'static, F: Fn() + 'static>(
+    fields: T,
+    submit: &str,
+    on_submit: F,
+) -> impl View {
+    let valid = fields.errors().is_empty();
+    vstack((
+        vstack(fields),
+        button(text(submit), on_submit).disabled(!valid),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_form_errors() {
+        let dirty = Arc::new(Mutex::new(Dirty::new(None)));
+        let email = State::new(String::from("rui"), dirty.clone());
+        let volume = State::new(0.5, dirty);
+
+        let fields = (
+            field("Email", text_field(email.clone())).validate(|s| {
+                if s.contains('@') {
+                    Ok(())
+                } else {
+                    Err("Not an email address".into())
+                }
+            }),
+            field("Volume", hslider(volume)).validate(|v| {
+                if *v <= 1.0 {
+                    Ok(())
+                } else {
+                    Err("Too loud".into())
+                }
+            }),
+        );
+        assert_eq!(fields.errors(), vec![String::from("Not an email address")]);
+
+        email.set("rui@example.com".into());
+        assert!(fields.errors().is_empty());
+    }
+
+    /// Taps the submit button of a form with `email` entered, and returns
+    /// whether the form was submitted.
+    fn submit(email: &str) -> bool {
+        let size = LocalSize::new(300.0, 200.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let email = State::new(String::from(email), Arc::new(Mutex::new(Dirty::new(None))));
+        let submitted = Rc::new(Cell::new(false));
+        let s = submitted.clone();
+        let ui = form(
+            (field("Email", text_field(email)).validate(|s| {
+                if s.contains('@') {
+                    Ok(())
+                } else {
+                    Err("Not an email address".into())
+                }
+            }),),
+            "Submit",
+            move || s.set(true),
+        );
+        cx.layout_root(&ui, size, &mut vger);
+        let query = cx.query(&ui, &mut vger);
+        let button = query.by_role(accesskit::Role::Button).by_label("Submit");
+        let rect = button.nth(0).unwrap().rect.unwrap();
+        cx.tap_at(&ui, rect.center().cast_unit(), &mut vger);
+        submitted.get()
+    }
+
+    #[test]
+    fn test_form_submit() {
+        assert!(submit("rui@example.com"));
+        assert!(!submit("rui"));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::*;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+/// A texture holding the frames drawn by one `frame_view`, reused until
+/// the frame size changes.
+struct FrameTexture {
+    texture: wgpu::Texture,
+    size: (u32, u32),
+
+    /// The frame in the texture, so it's only uploaded when it changes.
+    uploaded: Option<Arc<Frame>>,
+
+    /// Where to draw, in clip space, and how to round the corners.
+    uniforms: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Draws `frame_view` frames on the GPU, in passes of their own, since
+/// vger can't sample our textures. What's drawn after a frame goes in
+/// another vger scene, so frames are drawn in order with everything else.
+pub(crate) struct FramePass {
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    textures: HashMap<ViewID, FrameTexture>,
+}
+
+impl FramePass {
+    pub(crate) fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(&wgpu::ShaderModuleDescriptor {
+            label: Some("frame_pass"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("frame_pass.wgsl").into()),
+        });
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("frame_pass"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("frame_pass"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("frame_pass"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                }],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("frame_pass"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            layout,
+            sampler,
+            textures: HashMap::new(),
+        }
+    }
+
+    fn create_texture(&self, device: &wgpu::Device, size: (u32, u32)) -> FrameTexture {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("frame_view"),
+            size: wgpu::Extent3d {
+                width: size.0.max(1),
+                height: size.1.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("frame_view"),
+            size: 32,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("frame_view"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        FrameTexture {
+            texture,
+            size,
+            uploaded: None,
+            uniforms,
+            bind_group,
+        }
+    }
+
+    /// Drops the textures of views which didn't draw one of `frames`.
+    pub(crate) fn retain(&mut self, frames: &[FrameDraw]) {
+        self.textures
+            .retain(|key, _| frames.iter().any(|draw| draw.key == *key));
+    }
+
+    /// Draws `frames` over `target`, which is `size` physical pixels.
+    pub(crate) fn draw(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        frames: &[FrameDraw],
+        size: PhysicalSize<u32>,
+        scale: f32,
+    ) {
+        if frames.is_empty() {
+            return;
+        }
+
+        let (width, height) = (size.width as f32 / scale, size.height as f32 / scale);
+
+        for draw in frames {
+            let frame_size = (draw.frame.width, draw.frame.height);
+            if self.textures.get(&draw.key).map(|t| t.size) != Some(frame_size) {
+                let texture = self.create_texture(device, frame_size);
+                self.textures.insert(draw.key, texture);
+            }
+            let texture = self.textures.get_mut(&draw.key).unwrap();
+
+            let uploaded = texture.uploaded.as_ref();
+            if uploaded.filter(|f| Arc::ptr_eq(f, &draw.frame)).is_none() {
+                queue.write_texture(
+                    wgpu::ImageCopyTexture {
+                        texture: &texture.texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    &draw.frame.pixels,
+                    wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: NonZeroU32::new(4 * frame_size.0),
+                        rows_per_image: NonZeroU32::new(frame_size.1),
+                    },
+                    wgpu::Extent3d {
+                        width: frame_size.0,
+                        height: frame_size.1,
+                        depth_or_array_layers: 1,
+                    },
+                );
+                texture.uploaded = Some(draw.frame.clone());
+            }
+
+            // Layout is y-up like clip space, so only scale and shift. The shape
+            // is for rounding the corners.
+            let rect = [
+                draw.rect.min_x() / width * 2.0 - 1.0,
+                draw.rect.min_y() / height * 2.0 - 1.0,
+                draw.rect.max_x() / width * 2.0 - 1.0,
+                draw.rect.max_y() / height * 2.0 - 1.0,
+                draw.rect.width() * scale,
+                draw.rect.height() * scale,
+                draw.radius
+                    .min(draw.rect.width() / 2.0)
+                    .min(draw.rect.height() / 2.0)
+                    * scale,
+                0.0,
+            ];
+            let bytes: Vec<u8> = rect.iter().flat_map(|x| x.to_le_bytes()).collect();
+            queue.write_buffer(&texture.uniforms, 0, &bytes);
+        }
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("frame_pass"),
+                color_attachments: &[wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+
+            for draw in frames {
+                // The scissor in physical pixels, y-down.
+                let clip = draw
+                    .clip
+                    .unwrap_or_else(|| LocalRect::new(LocalPoint::zero(), [width, height].into()));
+                let x0 = (clip.min_x() * scale).max(0.0) as u32;
+                let y0 = ((height - clip.max_y()) * scale).max(0.0) as u32;
+                let x1 = ((clip.max_x() * scale).max(0.0) as u32).min(size.width);
+                let y1 = (((height - clip.min_y()) * scale).max(0.0) as u32).min(size.height);
+                if x1 <= x0 || y1 <= y0 {
+                    continue;
+                }
+                pass.set_scissor_rect(x0, y0, x1 - x0, y1 - y0);
+
+                pass.set_bind_group(0, &self.textures[&draw.key].bind_group, &[]);
+                pass.draw(0..4, 0..1);
+            }
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+}
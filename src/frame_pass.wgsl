@@ -0,0 +1,44 @@
+// Draws a frame_view's texture over the rect in `locals`.
+
+struct Locals {
+    // min x, min y, max x, max y, in clip space.
+    rect: vec4<f32>;
+    // width, height and corner radius, in physical pixels.
+    shape: vec4<f32>;
+};
+
+[[group(0), binding(0)]]
+var<uniform> locals: Locals;
+
+[[group(0), binding(1)]]
+var frame_texture: texture_2d<f32>;
+
+[[group(0), binding(2)]]
+var frame_sampler: sampler;
+
+struct VertexOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]] uv: vec2<f32>;
+};
+
+[[stage(vertex)]]
+fn vs_main([[builtin(vertex_index)]] index: u32) -> VertexOutput {
+    let corner = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(mix(locals.rect.xy, locals.rect.zw, corner), 0.0, 1.0);
+    // Frames' rows go top to bottom.
+    out.uv = vec2<f32>(corner.x, 1.0 - corner.y);
+    return out;
+}
+
+[[stage(fragment)]]
+fn fs_main(vertex: VertexOutput) -> [[location(0)]] vec4<f32> {
+    let color = textureSample(frame_texture, frame_sampler, vertex.uv);
+
+    // Distance outside the rounded rect, antialiased over a pixel.
+    let half = locals.shape.xy * 0.5;
+    let r = locals.shape.z;
+    let q = abs((vertex.uv - 0.5) * locals.shape.xy) - half + r;
+    let d = length(max(q, vec2<f32>(0.0, 0.0))) + min(max(q.x, q.y), 0.0) - r;
+    return vec4<f32>(color.rgb, color.a * clamp(0.5 - d, 0.0, 1.0));
+}
@@ -0,0 +1,321 @@
+use crate::*;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+
+/// An image of RGBA pixels, 8 bits per channel and not premultiplied, with
+/// rows top to bottom.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Frame {
+    /// Panics if `pixels` doesn't hold `width * height` pixels.
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        assert_eq!(pixels.len(), 4 * width as usize * height as usize);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The largest square in the middle of the frame.
+    pub fn crop_square(&self) -> Frame {
+        let side = self.width.min(self.height);
+        let (x0, y0) = ((self.width - side) / 2, (self.height - side) / 2);
+        let mut pixels = Vec::with_capacity(4 * side as usize * side as usize);
+        for y in y0..y0 + side {
+            let start = 4 * (y * self.width + x0) as usize;
+            pixels.extend_from_slice(&self.pixels[start..start + 4 * side as usize]);
+        }
+        Frame::new(side, side, pixels)
+    }
+}
+
+type FrameCallback = Box<dyn FnMut() -> Option<Frame> + Send>;
+type LoadCallback = Box<dyn FnOnce() -> Option<Frame> + Send>;
+
+enum Feed {
+    Channel(Receiver<Frame>),
+    Callback(FrameCallback),
+
+    /// A single frame, loaded on another thread when first polled.
+    Load(Option<LoadCallback>),
+}
+
+struct FrameSourceInner {
+    feed: Feed,
+    latest: Option<Arc<Frame>>,
+    live: bool,
+}
+
+/// Where a `frame_view` gets its frames. Clones share the same stream, so
+/// views rebuilt by `state` keep showing the latest frame.
+#[derive(Clone)]
+pub struct FrameSource {
+    inner: Arc<Mutex<FrameSourceInner>>,
+}
+
+impl FrameSource {
+    fn new(feed: Feed) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(FrameSourceInner {
+                feed,
+                latest: None,
+                live: true,
+            })),
+        }
+    }
+
+    /// Frames from a function, called each time the view draws. Returning
+    /// `None` keeps the previous frame.
+    pub fn from_fn(f: impl FnMut() -> Option<Frame> + Send + 'static) -> Self {
+        Self::new(Feed::Callback(Box::new(f)))
+    }
+
+    /// A single frame from `load`, which runs on another thread the first
+    /// time the frame is needed. The source stops being live when it
+    /// finishes, with no frame if `load` returns `None`.
+    pub fn load(load: impl FnOnce() -> Option<Frame> + Send + 'static) -> Self {
+        Self::new(Feed::Load(Some(Box::new(load))))
+    }
+
+    /// Takes any new frames, returning the latest and whether more may come.
+    pub(crate) fn poll(&self) -> (Option<Arc<Frame>>, bool) {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+        if let Feed::Load(load) = &mut inner.feed {
+            if let Some(load) = load.take() {
+                let (tx, rx) = channel();
+                std::thread::spawn(move || {
+                    if let Some(frame) = load() {
+                        let _ = tx.send(frame);
+                    }
+                });
+                inner.feed = Feed::Channel(rx);
+            }
+        }
+        if inner.live {
+            match &mut inner.feed {
+                Feed::Channel(receiver) => loop {
+                    match receiver.try_recv() {
+                        Ok(frame) => inner.latest = Some(Arc::new(frame)),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            inner.live = false;
+                            break;
+                        }
+                    }
+                },
+                Feed::Callback(f) => {
+                    if let Some(frame) = f() {
+                        inner.latest = Some(Arc::new(frame));
+                    }
+                }
+                Feed::Load(_) => (),
+            }
+        }
+        (inner.latest.clone(), inner.live)
+    }
+}
+
+impl From<Receiver<Frame>> for FrameSource {
+    fn from(receiver: Receiver<Frame>) -> Self {
+        Self::new(Feed::Channel(receiver))
+    }
+}
+
+/// The largest rect with the frame's aspect ratio which fits in `rect`,
+/// centered.
+pub(crate) fn aspect_fit(width: u32, height: u32, rect: LocalRect) -> LocalRect {
+    if width == 0 || height == 0 {
+        return LocalRect::new(rect.center(), LocalSize::zero());
+    }
+    let (w, h) = (width as f32, height as f32);
+    let scale = (rect.width() / w).min(rect.height() / h);
+    let size = LocalSize::new(w * scale, h * scale);
+    LocalRect::new(rect.center() - size.to_vector() / 2.0, size)
+}
+
+/// The smallest rect with the frame's aspect ratio which covers `rect`,
+/// centered.
+pub(crate) fn aspect_fill(width: u32, height: u32, rect: LocalRect) -> LocalRect {
+    if width == 0 || height == 0 {
+        return LocalRect::new(rect.center(), LocalSize::zero());
+    }
+    let (w, h) = (width as f32, height as f32);
+    let scale = (rect.width() / w).max(rect.height() / h);
+    let size = LocalSize::new(w * scale, h * scale);
+    LocalRect::new(rect.center() - size.to_vector() / 2.0, size)
+}
+
+/// Struct for `frame_view`.
+pub struct FrameView {
+    source: FrameSource,
+}
+
+impl View for FrameView {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("frame_view");
+    }
+
+    fn process(&self, _event: &Event, _vid: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {}
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        // Keep drawing while frames may still arrive, like a video.
+        let (frame, live) = self.source.poll();
+        if live {
+            if let Some(window) = &cx.window {
+                window.request_redraw();
+            }
+        }
+
+        if let Some(frame) = frame {
+            if cx.get_env::<ContentMode>() == Some(ContentMode::Fill) {
+                vger.save();
+                vger.clip(rect);
+                vger.draw_frame(
+                    id,
+                    &frame,
+                    aspect_fill(frame.width, frame.height, rect),
+                    0.0,
+                );
+                vger.restore();
+            } else {
+                vger.draw_frame(id, &frame, aspect_fit(frame.width, frame.height, rect), 0.0);
+            }
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Image));
+        Some(aid)
+    }
+
+    fn intrinsic_size(
+        &self,
+        _id: ViewID,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        // A pixel of the frame to a point, once there is one.
+        let inner = self.source.inner.lock().unwrap();
+        let frame = inner.latest.as_ref()?;
+        Some(LocalSize::new(frame.width as f32, frame.height as f32))
+    }
+}
+
+impl private::Sealed for FrameView {}
+
+/// Shows a stream of frames, like a camera preview or a video, scaled to
+/// fit the space it's given. Takes a `Receiver<Frame>` or a `FrameSource`.
+/// The view redraws continuously until the channel disconnects.
+pub fn frame_view(source: impl Into<FrameSource>) -> FrameView {
+    FrameView {
+        source: source.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aspect_fit() {
+        let rect = LocalRect::new(LocalPoint::zero(), [200.0, 100.0].into());
+        let fit = aspect_fit(640, 480, rect);
+        assert_eq!(fit.size, LocalSize::new(400.0 / 3.0, 100.0));
+        assert_eq!(fit.center(), rect.center());
+    }
+
+    #[test]
+    fn test_crop_square() {
+        let frame = Frame::new(3, 1, (0..12).collect());
+        assert_eq!(frame.crop_square(), Frame::new(1, 1, vec![4, 5, 6, 7]));
+    }
+
+    #[test]
+    fn test_frame_source() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let source = FrameSource::from(rx);
+        tx.send(Frame::new(1, 1, vec![0; 4])).unwrap();
+        tx.send(Frame::new(2, 1, vec![0; 8])).unwrap();
+        let (frame, live) = source.poll();
+        assert_eq!(frame.unwrap().width, 2);
+        assert!(live);
+
+        drop(tx);
+        let (frame, live) = source.poll();
+        assert!(frame.is_some());
+        assert!(!live);
+    }
+
+    #[test]
+    fn test_frame_source_load() {
+        let source = FrameSource::load(|| Some(Frame::new(1, 1, vec![0; 4])));
+        let (frame, mut live) = source.poll();
+        assert!(frame.is_none() || !live);
+        while live {
+            std::thread::yield_now();
+            live = source.poll().1;
+        }
+        assert_eq!(source.poll().0.unwrap().width, 1);
+
+        let failed = FrameSource::load(|| None);
+        while failed.poll().1 {
+            std::thread::yield_now();
+        }
+        assert!(failed.poll().0.is_none());
+    }
+}
@@ -0,0 +1,238 @@
+//! Runs views against sequences of events decoded from fuzzer input, to
+//! find event sequences which break them. The targets in `fuzz/` use
+//! this with cargo-fuzz:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| {
+//!     rui::fuzz::run(&my_view(), data);
+//! });
+//! ```
+//!
+//! Any panic is a failure, including a `RefCell` borrowed twice or a
+//! `State` accessed from within its own `with_mut`, and is reported with
+//! the actions which led to it. Deadlocks show up as fuzzer timeouts.
+
+use crate::*;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+/// Something the user does, decoded from fuzzer input.
+#[derive(Clone, Debug)]
+pub enum Action {
+    Event(Event),
+    Resize(LocalSize),
+}
+
+/// Keys the fuzzer presses.
+const KEYS: [KeyPress; 12] = [
+    KeyPress::Character("a"),
+    KeyPress::Character(" "),
+    KeyPress::Enter,
+    KeyPress::Tab,
+    KeyPress::Escape,
+    KeyPress::Backspace,
+    KeyPress::Delete,
+    KeyPress::ArrowLeft,
+    KeyPress::ArrowRight,
+    KeyPress::ArrowUp,
+    KeyPress::ArrowDown,
+    KeyPress::Home,
+];
+
+/// Bounds checked after each frame.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// How many views can have layout kept for them, after collection.
+    pub max_layout: usize,
+
+    /// How many views can have state kept for them, after collection.
+    pub max_state: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_layout: 10_000,
+            max_state: 10_000,
+        }
+    }
+}
+
+/// The size the view starts at.
+const START_SIZE: [f32; 2] = [400.0, 300.0];
+
+/// Decodes `data` into actions. Every input decodes to something, so the
+/// fuzzer doesn't waste time on inputs which are thrown away.
+pub fn actions(data: &[u8]) -> Vec<Action> {
+    let mut bytes = data.iter().copied();
+    let mut next = move || bytes.next().unwrap_or(0);
+    let mut size = LocalSize::from(START_SIZE);
+    let mut actions = vec![];
+
+    for _ in 0..data.len() / 3 {
+        let tag = next();
+        // Positions cover a bit more than the view, to test the edges.
+        let position = |x: u8, y: u8| {
+            LocalPoint::new(
+                x as f32 / 255.0 * size.width * 1.2 - size.width * 0.1,
+                y as f32 / 255.0 * size.height * 1.2 - size.height * 0.1,
+            )
+        };
+        let touch = (tag >> 4) as usize % 2;
+        let (kind, position) = match tag % 8 {
+            0 => (
+                EventKind::TouchBegin { id: touch },
+                position(next(), next()),
+            ),
+            1 | 2 => (EventKind::TouchMove { id: touch }, position(next(), next())),
+            3 => (EventKind::TouchEnd { id: touch }, position(next(), next())),
+            4 => (
+                EventKind::TouchCancel { id: touch },
+                position(next(), next()),
+            ),
+            5 => {
+                let key = KEYS[next() as usize % KEYS.len()].clone();
+                let mut mods = ModifiersState::default();
+                if tag & 0x10 != 0 {
+                    mods = mods | ModifiersState::SHIFT;
+                }
+                (EventKind::Key(key, mods), LocalPoint::zero())
+            }
+            6 => {
+                let delta = [next() as i8 as f32, next() as i8 as f32];
+                (
+                    EventKind::Scroll {
+                        delta: delta.into(),
+                    },
+                    LocalPoint::zero(),
+                )
+            }
+            _ => {
+                size = [next() as f32 * 4.0, next() as f32 * 4.0].into();
+                actions.push(Action::Resize(size));
+                continue;
+            }
+        };
+        actions.push(Action::Event(Event { kind, position }));
+    }
+    actions
+}
+
+/// Runs `view` against the actions decoded from `data`, with the default
+/// `Limits`.
+pub fn run(view: &impl View, data: &[u8]) {
+    run_with_limits(view, data, Limits::default())
+}
+
+/// Runs `view` against the actions decoded from `data`, drawing a frame
+/// after each one as the app would, and panics if the view panics or goes
+/// over `limits`.
+pub fn run_with_limits(view: &impl View, data: &[u8], limits: Limits) {
+    let actions = actions(data);
+    let mut cx = Context::headless();
+    let mut vger = SvgRenderer::new(START_SIZE.into());
+    let mut size = LocalSize::from(START_SIZE);
+
+    let frame = |cx: &mut Context, size: LocalSize, vger: &mut SvgRenderer| {
+        cx.layout_root(view, size, vger);
+        view.draw(cx.root_id, cx, vger);
+
+        let mut new_map = StateMap::new();
+        view.gc(cx.root_id, cx, &mut new_map);
+        cx.state_map = new_map;
+        cx.retain_tasks();
+        cx.layout.collect();
+
+        let mut nodes = vec![];
+        view.access(cx.root_id, cx, &mut nodes);
+
+        assert!(
+            cx.layout.len() <= limits.max_layout,
+            "layout kept for {} views",
+            cx.layout.len()
+        );
+        assert!(
+            cx.state_map.len() <= limits.max_state,
+            "state kept for {} views",
+            cx.state_map.len()
+        );
+    };
+
+    for (i, action) in actions.iter().enumerate() {
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            if i == 0 {
+                frame(&mut cx, size, &mut vger);
+            }
+            match action {
+                Action::Event(event) => cx.process_root(view, event, &mut vger),
+                Action::Resize(new_size) => size = *new_size,
+            }
+            frame(&mut cx, size, &mut vger);
+        }));
+
+        if let Err(payload) = result {
+            eprintln!("view failed after these actions:");
+            for action in &actions[..=i] {
+                eprintln!("  {:?}", action);
+            }
+            resume_unwind(payload);
+        }
+    }
+    cx.abort_tasks();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_fuzz_run() {
+        let data: Vec<u8> = (0..3000u32).map(|i| (i * 7919 % 251) as u8).collect();
+        assert_eq!(actions(&data).len(), 1000);
+
+        let ui = state(0.5, |value: State<f32>| {
+            vstack((
+                text("fuzz"),
+                hslider(value.clone()),
+                button(text("reset"), move || value.set(0.5)),
+                text_field(signal(String::new())),
+            ))
+        });
+        run(&ui, &data);
+
+        let overflowing = Limits {
+            max_layout: 1,
+            ..Limits::default()
+        };
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            run_with_limits(&ui, &data, overflowing)
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fuzz_layout_collected() {
+        // Each tap on the button shows rows with new ids.
+        let taps = Rc::new(Cell::new(0));
+        let counted = taps.clone();
+        let ui = state(0, move |start: State<usize>| {
+            let taps = counted.clone();
+            let first = start.get();
+            vstack((
+                button(text("more"), move || {
+                    taps.set(taps.get() + 1);
+                    start.set(first + 3);
+                }),
+                list((first..first + 3).collect(), |i| text(&i.to_string())),
+            ))
+        });
+        let data: Vec<u8> = [0, 128, 181, 3, 128, 181].repeat(50);
+        let limits = Limits {
+            max_layout: 30,
+            ..Limits::default()
+        };
+        run_with_limits(&ui, &data, limits);
+        assert_eq!(taps.get(), 50);
+    }
+}
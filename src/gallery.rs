@@ -0,0 +1,333 @@
+use crate::*;
+
+pub const GALLERY_THUMBNAIL_SIZE: f32 = 96.0;
+const GALLERY_SPACING: f32 = 8.0;
+const GALLERY_CORNER_RADIUS: f32 = 4.0;
+
+/// Size of the button which closes the viewer.
+const GALLERY_CLOSE_SIZE: f32 = 32.0;
+
+#[derive(Clone, Default)]
+struct GalleryState {
+    /// How far the grid is scrolled down.
+    scroll: f32,
+
+    /// The thumbnail a touch began on.
+    pressed: Option<usize>,
+
+    /// The image showing in the viewer.
+    open: Option<usize>,
+}
+
+/// Struct for `gallery`.
+pub struct Gallery {
+    images: Vec<FrameSource>,
+    thumbnail_size: f32,
+}
+
+impl Gallery {
+    /// Sets the size of the thumbnails in the grid.
+    pub fn thumbnail_size(self, thumbnail_size: f32) -> Self {
+        Self {
+            thumbnail_size,
+            ..self
+        }
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<GalleryState> {
+        cx.with_state(
+            GalleryState::default(),
+            id,
+            |state: State<GalleryState>, _cx| state,
+        )
+    }
+
+    fn columns(&self, width: f32) -> usize {
+        (((width - GALLERY_SPACING) / (self.thumbnail_size + GALLERY_SPACING)) as usize).max(1)
+    }
+
+    /// How far the grid can scroll.
+    fn max_scroll(&self, rect: LocalRect) -> f32 {
+        let rows = self.images.len().div_ceil(self.columns(rect.width()));
+        let height = rows as f32 * (self.thumbnail_size + GALLERY_SPACING) + GALLERY_SPACING;
+        (height - rect.height()).max(0.0)
+    }
+
+    /// Where each thumbnail which can be seen goes. The others aren't
+    /// drawn, so their images aren't loaded until they're scrolled to.
+    fn visible(&self, rect: LocalRect, scroll: f32) -> Vec<(usize, LocalRect)> {
+        let columns = self.columns(rect.width());
+        let step = self.thumbnail_size + GALLERY_SPACING;
+        (0..self.images.len())
+            .map(|i| {
+                let (row, column) = (i / columns, i % columns);
+                let x = rect.min_x() + GALLERY_SPACING + column as f32 * step;
+                let y = rect.max_y() + scroll - (row + 1) as f32 * step;
+                let size = [self.thumbnail_size, self.thumbnail_size].into();
+                (i, LocalRect::new([x, y].into(), size))
+            })
+            .filter(|(_, r)| r.intersects(&rect))
+            .collect()
+    }
+
+    fn close_rect(rect: LocalRect) -> LocalRect {
+        LocalRect::new(
+            [
+                rect.max_x() - GALLERY_CLOSE_SIZE,
+                rect.max_y() - GALLERY_CLOSE_SIZE,
+            ]
+            .into(),
+            [GALLERY_CLOSE_SIZE, GALLERY_CLOSE_SIZE].into(),
+        )
+    }
+
+    /// Each image gets its own viewer, so its zoom is kept.
+    fn viewer(&self, index: usize) -> impl View {
+        pan_zoom(frame_view(self.images[index].clone())).zoom_range(1.0..10.0)
+    }
+
+    fn viewer_id(id: ViewID, index: usize) -> ViewID {
+        id.child(&index)
+    }
+}
+
+impl View for Gallery {
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("gallery({} images) {{", self.images.len());
+        if let Some(i) = self.state(id, cx).with(|s| s.open) {
+            self.viewer(i).print(Self::viewer_id(id, i), cx);
+        }
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let state = self.state(id, cx);
+        let n = self.images.len();
+
+        if let Some(i) = state.with(|s| s.open).filter(|i| *i < n) {
+            match &event.kind {
+                EventKind::Key(KeyPress::Escape, _) => state.with_mut(|s| s.open = None),
+                EventKind::Key(KeyPress::ArrowLeft, _) if i > 0 => {
+                    state.with_mut(|s| s.open = Some(i - 1))
+                }
+                EventKind::Key(KeyPress::ArrowRight, _) if i + 1 < n => {
+                    state.with_mut(|s| s.open = Some(i + 1))
+                }
+                EventKind::TouchBegin { .. } if Self::close_rect(rect).contains(event.position) => {
+                    state.with_mut(|s| s.open = None)
+                }
+                _ => self
+                    .viewer(i)
+                    .process(event, Self::viewer_id(id, i), cx, vger),
+            }
+            return;
+        }
+
+        let scroll = state.with(|s| s.scroll);
+        let hit = |pt: LocalPoint| {
+            self.visible(rect, scroll)
+                .into_iter()
+                .find(|(_, r)| r.contains(pt))
+                .map(|(i, _)| i)
+        };
+        match &event.kind {
+            EventKind::Scroll { delta } if rect.contains(event.position) => {
+                let scroll = (scroll + delta.y).clamp(0.0, self.max_scroll(rect));
+                state.with_mut(|s| s.scroll = scroll);
+            }
+            EventKind::TouchBegin { id: touch } => {
+                if let Some(i) = hit(event.position) {
+                    cx.touches[*touch] = id;
+                    state.with_mut(|s| s.pressed = Some(i));
+                }
+            }
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                let pressed = state.with(|s| s.pressed);
+                state.with_mut(|s| {
+                    s.pressed = None;
+                    if hit(event.position) == pressed {
+                        s.open = pressed;
+                    }
+                });
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| s.pressed = None);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let state = self.state(id, cx);
+
+        vger.save();
+        vger.clip(rect);
+
+        if let Some(i) = state.with(|s| s.open).filter(|i| *i < self.images.len()) {
+            let paint = vger.color_paint(BLACK);
+            vger.fill_rect(rect, 0.0, paint);
+            self.viewer(i).draw(Self::viewer_id(id, i), cx, vger);
+
+            let close =
+                Self::close_rect(rect).inner_rect(euclid::SideOffsets2D::new_all_same(10.0));
+            let paint = vger.color_paint(TEXT_COLOR);
+            vger.stroke_segment(close.min(), close.max(), 2.0, paint);
+            vger.stroke_segment(
+                [close.min_x(), close.max_y()].into(),
+                [close.max_x(), close.min_y()].into(),
+                2.0,
+                paint,
+            );
+        } else {
+            let (scroll, pressed) = state.with(|s| (s.scroll, s.pressed));
+            let mut loading = false;
+            for (i, r) in self.visible(rect, scroll) {
+                let color = if pressed == Some(i) {
+                    BUTTON_BACKGROUND_COLOR
+                } else {
+                    CONTROL_BACKGROUND
+                };
+                let paint = vger.color_paint(color);
+                vger.fill_rect(r, GALLERY_CORNER_RADIUS, paint);
+
+                let (frame, live) = self.images[i].poll();
+                loading |= live;
+                match frame {
+                    Some(frame) => {
+                        let fit = aspect_fit(frame.width, frame.height, r);
+                        vger.draw_frame(
+                            id.child(&(i, "thumbnail")),
+                            &frame,
+                            fit,
+                            GALLERY_CORNER_RADIUS,
+                        );
+                    }
+                    None if !live => {
+                        // The image failed to load.
+                        let paint = vger.color_paint(RED_HIGHLIGHT);
+                        let c = r.center();
+                        vger.stroke_segment(
+                            c - LocalOffset::new(8.0, 8.0),
+                            c + LocalOffset::new(8.0, 8.0),
+                            2.0,
+                            paint,
+                        );
+                        vger.stroke_segment(
+                            c + LocalOffset::new(-8.0, 8.0),
+                            c + LocalOffset::new(8.0, -8.0),
+                            2.0,
+                            paint,
+                        );
+                    }
+                    None => (),
+                }
+            }
+
+            // Keep drawing until the thumbnails which can be seen arrive.
+            if loading {
+                if let Some(window) = &cx.window {
+                    window.request_redraw();
+                }
+            }
+        }
+
+        vger.restore();
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let rect = LocalRect::new(LocalPoint::zero(), constraints.biggest());
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect,
+                offset: LocalOffset::zero(),
+            },
+        );
+
+        // Keep the grid in range when the view shrinks.
+        let state = self.state(id, cx);
+        let max = self.max_scroll(rect);
+        if state.with(|s| s.scroll > max) {
+            state.with_mut(|s| s.scroll = max);
+        }
+
+        if let Some(i) = state.with(|s| s.open).filter(|i| *i < self.images.len()) {
+            self.viewer(i)
+                .layout(Self::viewer_id(id, i), rect.size.into(), cx, vger);
+        }
+        rect.size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            GalleryState::default(),
+            id,
+            map,
+            |state: State<GalleryState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+        if let Some(i) = self
+            .state(id, cx)
+            .with(|s| s.open)
+            .filter(|i| *i < self.images.len())
+        {
+            self.viewer(i).gc(Self::viewer_id(id, i), cx, map);
+        }
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Grid));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for Gallery {}
+
+/// A grid of thumbnails which scrolls with the mouse wheel. Images load
+/// as they come into view, so use `FrameSource::load` to load them in the
+/// background. Clicking a thumbnail shows the image filling the view,
+/// where it can be zoomed with the wheel or by pinching and dragged
+/// around. The arrow keys go to the previous or next image, and escape
+/// or the close button goes back to the grid.
+pub fn gallery(images: Vec<FrameSource>) -> Gallery {
+    Gallery {
+        images,
+        thumbnail_size: GALLERY_THUMBNAIL_SIZE,
+    }
+}
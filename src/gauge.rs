@@ -0,0 +1,148 @@
+use crate::*;
+use std::f32::consts::PI;
+
+pub const GAUGE_SIZE: f32 = 32.0;
+
+/// Half the angle the gauge's arc covers. It's open at the bottom.
+const GAUGE_APERTURE: f32 = 0.75 * PI;
+
+/// Struct for `gauge`.
+pub struct Gauge<B> {
+    value: B,
+    range: std::ops::Range<f32>,
+    size: f32,
+    color: Color,
+    track_color: Color,
+    line_width: f32,
+}
+
+impl<B> Gauge<B> {
+    /// Sets the width and height of the gauge.
+    pub fn gauge_size(self, size: f32) -> Self {
+        Self { size, ..self }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Sets the color of the arc behind the value.
+    pub fn track_color(self, track_color: Color) -> Self {
+        Self {
+            track_color,
+            ..self
+        }
+    }
+
+    pub fn line_width(self, line_width: f32) -> Self {
+        Self { line_width, ..self }
+    }
+}
+
+impl<B> View for Gauge<B>
+where
+    B: Binding<f32>,
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("gauge({}, {:?})", self.value.get(), self.range);
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let center = rect.center();
+        let radius = (self.size - self.line_width) / 2.0;
+
+        let paint = vger.color_paint(self.track_color);
+        vger.stroke_arc(
+            center,
+            radius,
+            self.line_width,
+            PI / 2.0,
+            GAUGE_APERTURE,
+            paint,
+        );
+
+        // Fill clockwise from the lower left.
+        let span = self.range.end - self.range.start;
+        let fraction = if span > 0.0 {
+            ((self.value.get() - self.range.start) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if fraction > 0.0 {
+            let start = PI / 2.0 + GAUGE_APERTURE;
+            let aperture = fraction * GAUGE_APERTURE;
+            let paint = vger.color_paint(self.color);
+            vger.stroke_arc(
+                center,
+                radius,
+                self.line_width,
+                start - aperture,
+                aperture,
+                paint,
+            );
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = LocalSize::new(self.size, self.size);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        _id: ViewID,
+        _pt: LocalPoint,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        None
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Meter));
+        Some(aid)
+    }
+}
+
+impl<B> private::Sealed for Gauge<B> {}
+
+/// A small dial showing where `value` is in `range`, for dashboards and
+/// list rows. It's `GAUGE_SIZE` square unless changed with `gauge_size`.
+pub fn gauge<B: Binding<f32>>(value: B, range: std::ops::Range<f32>) -> Gauge<B> {
+    Gauge {
+        value,
+        range,
+        size: GAUGE_SIZE,
+        color: AZURE_HIGHLIGHT,
+        track_color: CONTROL_BACKGROUND,
+        line_width: 3.0,
+    }
+}
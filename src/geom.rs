@@ -17,16 +17,22 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.process(event, id.child(&0), cx, vger);
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger);
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        let sz = self.child.layout(id.child(&0), sz, cx, vger);
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let sz = self.child.layout(id.child(&0), constraints, cx, vger);
         (self.func)(sz);
         sz
     }
@@ -36,7 +42,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -57,6 +63,10 @@ where
     ) -> Option<accesskit::NodeId> {
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
 }
 
 impl<V, F> private::Sealed for Geom<V, F> {}
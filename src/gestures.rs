@@ -1,4 +1,26 @@
 pub use crate::*;
+use std::time::Duration;
+
+/// What a `Tap` calls. Closures taking nothing are called for each tap,
+/// and those wrapped by `tap_with_count` with the click count too.
+pub trait TapFunc {
+    fn call(&self, click_count: usize);
+}
+
+impl<F: Fn()> TapFunc for F {
+    fn call(&self, _click_count: usize) {
+        self()
+    }
+}
+
+/// A closure taking the click count, for `tap_with_count`.
+pub struct CountedTap<F>(pub F);
+
+impl<F: Fn(usize)> TapFunc for CountedTap<F> {
+    fn call(&self, click_count: usize) {
+        (self.0)(click_count)
+    }
+}
 
 /// Struct for the `tap` gesture.
 pub struct Tap<V, F> {
@@ -9,7 +31,7 @@ pub struct Tap<V, F> {
 impl<V, F> Tap<V, F>
 where
     V: View,
-    F: Fn() + 'static,
+    F: TapFunc + 'static,
 {
     pub fn new(v: V, f: F) -> Self {
         Self { child: v, func: f }
@@ -19,7 +41,7 @@ where
 impl<V, F> View for Tap<V, F>
 where
     V: View,
-    F: Fn() + 'static,
+    F: TapFunc + 'static,
 {
     fn print(&self, id: ViewID, cx: &mut Context) {
         println!("Tap {{");
@@ -27,29 +49,45 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        // Controls inside get the first look, so a button in a tappable
+        // row works. Which view has each touch is checked from before
+        // they do.
+        let touches = cx.touches;
+        self.child.process(event, vid.child(&0), cx, vger);
+        if cx.event_consumed() {
+            return;
+        }
         match &event.kind {
             EventKind::TouchBegin { id } => {
                 if let Some(_) = self.hittest(vid, event.position, cx, vger) {
                     cx.touches[*id] = vid;
+                    cx.consume_event();
                 }
             }
-            EventKind::TouchEnd { id } => {
-                if cx.touches[*id] == vid {
-                    cx.touches[*id] = ViewID::default();
-                    (self.func)();
-                }
+            EventKind::TouchEnd { id } if touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
+                self.func.call(cx.click_count);
+            }
+            EventKind::TouchCancel { id } if touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
             }
             _ => (),
         }
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger)
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.child.layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -57,7 +95,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -78,10 +116,204 @@ where
     ) -> Option<accesskit::NodeId> {
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
 }
 
 impl<V, F> private::Sealed for Tap<V, F> {}
 
+/// Struct for the `on_double_click` gesture.
+pub struct OnDoubleClick<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> OnDoubleClick<V, F>
+where
+    V: View,
+    F: Fn() + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self { child: v, func: f }
+    }
+}
+
+impl<V, F> View for OnDoubleClick<V, F>
+where
+    V: View,
+    F: Fn() + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".on_double_click()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        // Taps and selection inside still see the clicks.
+        self.child.process(event, id.child(&0), cx, vger);
+        if let EventKind::TouchBegin { .. } = &event.kind {
+            if cx.click_count == 2 && self.hittest(id, event.position, cx, vger).is_some() {
+                (self.func)();
+            }
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, F> private::Sealed for OnDoubleClick<V, F> {}
+
+/// Struct for the `double_click_interval` modifier.
+pub struct DoubleClickInterval<V> {
+    child: V,
+    interval: Duration,
+}
+
+impl<V> DoubleClickInterval<V>
+where
+    V: View,
+{
+    pub fn new(v: V, interval: Duration) -> Self {
+        Self { child: v, interval }
+    }
+}
+
+impl<V> View for DoubleClickInterval<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".double_click_interval({:?})", self.interval);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        // Clicks are counted before the views see them, so this is kept
+        // in the context for the next.
+        cx.double_click_interval = self.interval;
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V> private::Sealed for DoubleClickInterval<V> {}
+
 pub enum GestureState {
     Began,
     Changed,
@@ -115,41 +347,238 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let touches = cx.touches;
+        self.child.process(event, vid.child(&0), cx, vger);
+        if cx.event_consumed() {
+            return;
+        }
         match &event.kind {
             EventKind::TouchBegin { id } => {
                 if let Some(_) = self.hittest(vid, event.position, cx, vger) {
                     cx.touches[*id] = vid;
                     cx.starts[*id] = event.position;
                     cx.previous_position[*id] = event.position;
+                    cx.consume_event();
                 }
             }
-            EventKind::TouchMove { id } => {
-                if cx.touches[*id] == vid {
-                    let delta = event.position - cx.previous_position[*id];
-                    (self.func)(delta, GestureState::Changed);
-                    cx.previous_position[*id] = event.position;
+            EventKind::TouchMove { id } if touches[*id] == vid => {
+                let delta = event.position - cx.previous_position[*id];
+                (self.func)(delta, GestureState::Changed);
+                cx.previous_position[*id] = event.position;
+            }
+            EventKind::TouchEnd { id } if touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
+                (self.func)(
+                    event.position - cx.previous_position[*id],
+                    GestureState::Ended,
+                );
+            }
+            EventKind::TouchCancel { id } if touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
+                (self.func)(LocalOffset::zero(), GestureState::Ended);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, F> private::Sealed for Drag<V, F> {}
+
+/// Tracks the first two touches which begin in a view, for two-finger
+/// gestures.
+#[derive(Clone, Default)]
+pub(crate) struct TouchPair {
+    touches: Vec<(usize, LocalPoint)>,
+
+    /// Where the touches were when the gesture began.
+    start: Option<[LocalPoint; 2]>,
+}
+
+impl TouchPair {
+    fn current(&self) -> [LocalPoint; 2] {
+        [self.touches[0].1, self.touches[1].1]
+    }
+
+    /// Updates the touches, returning the gesture state along with the
+    /// start and current positions of the touches if the gesture changed.
+    ///
+    /// Two-finger gestures take priority over taps and drags: when the
+    /// second touch begins, any views tracking the touches lose them. Other
+    /// two-finger gestures keep going, so pinches and rotations can be
+    /// recognized at the same time.
+    pub(crate) fn update(
+        &mut self,
+        event: &Event,
+        hit: bool,
+        cx: &mut Context,
+    ) -> Option<(GestureState, [LocalPoint; 2], [LocalPoint; 2])> {
+        match &event.kind {
+            EventKind::TouchBegin { id } if hit && self.touches.len() < 2 => {
+                self.touches.push((*id, event.position));
+                if self.touches.len() == 2 {
+                    for (id, _) in &self.touches {
+                        cx.touches[*id] = ViewID::default();
+                    }
+                    let start = self.current();
+                    self.start = Some(start);
+                    return Some((GestureState::Began, start, start));
                 }
             }
-            EventKind::TouchEnd { id } => {
-                if cx.touches[*id] == vid {
-                    cx.touches[*id] = ViewID::default();
-                    (self.func)(
-                        event.position - cx.previous_position[*id],
-                        GestureState::Ended,
-                    );
+            EventKind::TouchMove { id } => {
+                if let Some(touch) = self.touches.iter_mut().find(|(t, _)| t == id) {
+                    touch.1 = event.position;
+                    if let Some(start) = self.start {
+                        return Some((GestureState::Changed, start, self.current()));
+                    }
                 }
             }
+            EventKind::TouchEnd { id } | EventKind::TouchCancel { id }
+                if self.touches.iter().any(|(t, _)| t == id) =>
+            {
+                let result = self
+                    .start
+                    .take()
+                    .map(|start| (GestureState::Ended, start, self.current()));
+                self.touches.retain(|(t, _)| t != id);
+                return result;
+            }
             _ => (),
         }
+        None
+    }
+}
+
+/// Ratio of the distance between two touches to the distance between
+/// where they started.
+pub(crate) fn pinch_scale(start: [LocalPoint; 2], current: [LocalPoint; 2]) -> f32 {
+    let d0 = start[0].distance_to(start[1]);
+    if d0 > 0.0 {
+        current[0].distance_to(current[1]) / d0
+    } else {
+        1.0
+    }
+}
+
+/// Angle in radians (counterclockwise) through which the line between two
+/// touches has turned since they started.
+fn rotation_angle(start: [LocalPoint; 2], current: [LocalPoint; 2]) -> f32 {
+    let a0 = (start[1] - start[0]).angle_from_x_axis().radians;
+    let a1 = (current[1] - current[0]).angle_from_x_axis().radians;
+    let mut angle = a1 - a0;
+    if angle > std::f32::consts::PI {
+        angle -= 2.0 * std::f32::consts::PI;
+    } else if angle <= -std::f32::consts::PI {
+        angle += 2.0 * std::f32::consts::PI;
+    }
+    angle
+}
+
+/// Struct for the `on_pinch` gesture.
+pub struct Pinch<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> Pinch<V, F>
+where
+    V: View,
+    F: Fn(f32, GestureState) + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self { child: v, func: f }
+    }
+}
+
+impl<V, F> View for Pinch<V, F>
+where
+    V: View,
+    F: Fn(f32, GestureState) + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("Pinch {{");
+        (self.child).print(id.child(&0), cx);
+        println!("}}");
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, vid.child(&0), cx, vger);
+
+        let hit = self.hittest(vid, event.position, cx, vger).is_some();
+        let change = cx.with_state(TouchPair::default(), vid, |pair, cx| {
+            pair.with_mut(|pair| pair.update(event, hit, cx))
+        });
+        if let Some((state, start, current)) = change {
+            (self.func)(pinch_scale(start, current), state);
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger)
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.child.layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -157,7 +586,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -167,6 +596,9 @@ where
     }
 
     fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(TouchPair::default(), id, map, |pair, _cx, map| {
+            map.insert(id, Box::new(pair));
+        });
         self.child.gc(id.child(&0), cx, map)
     }
 
@@ -178,6 +610,132 @@ where
     ) -> Option<accesskit::NodeId> {
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
 }
 
-impl<V, F> private::Sealed for Drag<V, F> {}
+impl<V, F> private::Sealed for Pinch<V, F> {}
+
+/// Struct for the `on_rotate` gesture.
+pub struct Rotate<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> Rotate<V, F>
+where
+    V: View,
+    F: Fn(f32, GestureState) + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self { child: v, func: f }
+    }
+}
+
+impl<V, F> View for Rotate<V, F>
+where
+    V: View,
+    F: Fn(f32, GestureState) + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("Rotate {{");
+        (self.child).print(id.child(&0), cx);
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, vid.child(&0), cx, vger);
+
+        let hit = self.hittest(vid, event.position, cx, vger).is_some();
+        let change = cx.with_state(TouchPair::default(), vid, |pair, cx| {
+            pair.with_mut(|pair| pair.update(event, hit, cx))
+        });
+        if let Some((state, start, current)) = change {
+            (self.func)(rotation_angle(start, current), state);
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(TouchPair::default(), id, map, |pair, _cx, map| {
+            map.insert(id, Box::new(pair));
+        });
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, F> private::Sealed for Rotate<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_finger_gestures() {
+        let start = [LocalPoint::new(0.0, 0.0), LocalPoint::new(10.0, 0.0)];
+        let current = [LocalPoint::new(0.0, 0.0), LocalPoint::new(0.0, 20.0)];
+        assert_eq!(pinch_scale(start, current), 2.0);
+        assert!((rotation_angle(start, current) - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+
+        let current = [LocalPoint::new(0.0, 0.0), LocalPoint::new(-10.0, -1.0)];
+        assert!(rotation_angle(start, current) < -3.0);
+    }
+
+    #[test]
+    fn test_click_count() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let counts = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let c = counts.clone();
+        let ui = rectangle().tap_with_count(move |n| c.borrow_mut().push(n));
+        ui.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+
+        cx.tap_at(&ui, [10.0, 10.0].into(), &mut vger);
+        cx.tap_at(&ui, [10.0, 10.0].into(), &mut vger);
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        assert_eq!(*counts.borrow(), vec![1, 2, 1]);
+    }
+}
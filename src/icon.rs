@@ -0,0 +1,299 @@
+use crate::*;
+
+pub const ICON_SIZE: f32 = 20.0;
+
+/// Icons are designed on a grid of this many units square.
+const GRID: f32 = 24.0;
+
+/// Width of an icon's lines in grid units.
+const LINE_WIDTH: f32 = 2.0;
+
+/// Symbols for common UI glyphs, drawn with `icon`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Icon {
+    Search,
+    ChevronLeft,
+    ChevronRight,
+    ChevronUp,
+    ChevronDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+    Close,
+    Check,
+    Plus,
+    Minus,
+    Menu,
+    More,
+    Info,
+    Warning,
+    Star,
+}
+
+impl Icon {
+    pub const ALL: [Icon; 18] = [
+        Icon::Search,
+        Icon::ChevronLeft,
+        Icon::ChevronRight,
+        Icon::ChevronUp,
+        Icon::ChevronDown,
+        Icon::ArrowLeft,
+        Icon::ArrowRight,
+        Icon::ArrowUp,
+        Icon::ArrowDown,
+        Icon::Close,
+        Icon::Check,
+        Icon::Plus,
+        Icon::Minus,
+        Icon::Menu,
+        Icon::More,
+        Icon::Info,
+        Icon::Warning,
+        Icon::Star,
+    ];
+
+    /// What the icon is made of, in grid units with y up.
+    fn strokes(self) -> Vec<IconStroke> {
+        use IconStroke::*;
+        let p = |x: f32, y: f32| LocalPoint::new(x, y);
+        let polyline = |pts: &[(f32, f32)]| -> Vec<IconStroke> {
+            pts.windows(2)
+                .map(|w| Line(p(w[0].0, w[0].1), p(w[1].0, w[1].1)))
+                .collect()
+        };
+        match self {
+            Icon::Search => vec![Ring(p(10.5, 13.5), 6.5), Line(p(15.0, 9.0), p(20.0, 4.0))],
+            Icon::ChevronLeft => polyline(&[(15.0, 18.0), (9.0, 12.0), (15.0, 6.0)]),
+            Icon::ChevronRight => polyline(&[(9.0, 18.0), (15.0, 12.0), (9.0, 6.0)]),
+            Icon::ChevronUp => polyline(&[(6.0, 9.0), (12.0, 15.0), (18.0, 9.0)]),
+            Icon::ChevronDown => polyline(&[(6.0, 15.0), (12.0, 9.0), (18.0, 15.0)]),
+            Icon::ArrowLeft => {
+                let mut s = polyline(&[(12.0, 19.0), (5.0, 12.0), (12.0, 5.0)]);
+                s.push(Line(p(5.0, 12.0), p(19.0, 12.0)));
+                s
+            }
+            Icon::ArrowRight => {
+                let mut s = polyline(&[(12.0, 19.0), (19.0, 12.0), (12.0, 5.0)]);
+                s.push(Line(p(5.0, 12.0), p(19.0, 12.0)));
+                s
+            }
+            Icon::ArrowUp => {
+                let mut s = polyline(&[(5.0, 12.0), (12.0, 19.0), (19.0, 12.0)]);
+                s.push(Line(p(12.0, 5.0), p(12.0, 19.0)));
+                s
+            }
+            Icon::ArrowDown => {
+                let mut s = polyline(&[(5.0, 12.0), (12.0, 5.0), (19.0, 12.0)]);
+                s.push(Line(p(12.0, 5.0), p(12.0, 19.0)));
+                s
+            }
+            Icon::Close => vec![
+                Line(p(6.0, 6.0), p(18.0, 18.0)),
+                Line(p(6.0, 18.0), p(18.0, 6.0)),
+            ],
+            Icon::Check => polyline(&[(5.0, 12.0), (10.0, 7.0), (20.0, 17.0)]),
+            Icon::Plus => vec![
+                Line(p(12.0, 5.0), p(12.0, 19.0)),
+                Line(p(5.0, 12.0), p(19.0, 12.0)),
+            ],
+            Icon::Minus => vec![Line(p(5.0, 12.0), p(19.0, 12.0))],
+            Icon::Menu => vec![
+                Line(p(4.0, 18.0), p(20.0, 18.0)),
+                Line(p(4.0, 12.0), p(20.0, 12.0)),
+                Line(p(4.0, 6.0), p(20.0, 6.0)),
+            ],
+            Icon::More => vec![
+                Dot(p(5.0, 12.0), 1.5),
+                Dot(p(12.0, 12.0), 1.5),
+                Dot(p(19.0, 12.0), 1.5),
+            ],
+            Icon::Info => vec![
+                Ring(p(12.0, 12.0), 9.0),
+                Line(p(12.0, 7.0), p(12.0, 12.0)),
+                Dot(p(12.0, 16.0), 1.25),
+            ],
+            Icon::Warning => {
+                let mut s = polyline(&[(12.0, 21.0), (2.0, 3.5), (22.0, 3.5), (12.0, 21.0)]);
+                s.push(Line(p(12.0, 9.0), p(12.0, 14.0)));
+                s.push(Dot(p(12.0, 6.5), 1.25));
+                s
+            }
+            Icon::Star => {
+                let points = (0..10)
+                    .map(|i| {
+                        let r = if i % 2 == 0 { 10.0 } else { 4.2 };
+                        let theta =
+                            std::f32::consts::FRAC_PI_2 + i as f32 * std::f32::consts::PI / 5.0;
+                        p(12.0 + r * theta.cos(), 11.0 + r * theta.sin())
+                    })
+                    .collect();
+                vec![Polygon(points)]
+            }
+        }
+    }
+}
+
+/// Part of an icon.
+enum IconStroke {
+    Line(LocalPoint, LocalPoint),
+    /// An outlined circle.
+    Ring(LocalPoint, f32),
+    /// A filled circle.
+    Dot(LocalPoint, f32),
+    /// A filled polygon.
+    Polygon(Vec<LocalPoint>),
+}
+
+/// Struct for `icon`.
+pub struct IconView {
+    icon: Icon,
+    size: f32,
+    color: Option<Color>,
+}
+
+impl IconView {
+    /// Sets the width and height of the icon.
+    pub fn icon_size(self, size: f32) -> Self {
+        Self { size, ..self }
+    }
+
+    /// Sets the icon's color. Otherwise it's the `tint` color, or the text
+    /// color.
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+}
+
+impl View for IconView {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("icon({:?})", self.icon);
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let color = self
+            .color
+            .or_else(|| cx.get_env::<TintColor>().map(|tint| tint.0))
+            .unwrap_or(TEXT_COLOR);
+        let paint = vger.color_paint(color);
+
+        let scale = self.size / GRID;
+        let pt = |p: LocalPoint| rect.origin + p.to_vector() * scale;
+        let width = LINE_WIDTH * scale;
+
+        for stroke in self.icon.strokes() {
+            match stroke {
+                IconStroke::Line(a, b) => vger.stroke_segment(pt(a), pt(b), width, paint),
+                IconStroke::Ring(center, radius) => vger.stroke_arc(
+                    pt(center),
+                    radius * scale,
+                    width,
+                    0.0,
+                    std::f32::consts::PI,
+                    paint,
+                ),
+                IconStroke::Dot(center, radius) => {
+                    vger.fill_circle(pt(center), radius * scale, paint)
+                }
+                IconStroke::Polygon(points) => {
+                    vger.move_to(pt(points[0]));
+                    for (a, b) in points.iter().zip(points.iter().cycle().skip(1)) {
+                        vger.quad_to(pt(a.lerp(*b, 0.5)), pt(*b));
+                    }
+                    vger.fill(paint);
+                }
+            }
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = LocalSize::new(self.size, self.size);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        _id: ViewID,
+        _cx: &mut Context,
+        _nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        None
+    }
+}
+
+impl private::Sealed for IconView {}
+
+/// Draws one of rui's built-in icons, `ICON_SIZE` square unless changed
+/// with `icon_size`.
+pub fn icon(icon: Icon) -> IconView {
+    IconView {
+        icon,
+        size: ICON_SIZE,
+        color: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icons_fit_grid() {
+        let inside = |p: LocalPoint, r: f32| {
+            p.x - r >= 0.0 && p.y - r >= 0.0 && p.x + r <= GRID && p.y + r <= GRID
+        };
+        for icon in Icon::ALL {
+            for stroke in icon.strokes() {
+                let fits = match stroke {
+                    IconStroke::Line(a, b) => inside(a, 0.0) && inside(b, 0.0),
+                    IconStroke::Ring(c, r) | IconStroke::Dot(c, r) => inside(c, r),
+                    IconStroke::Polygon(points) => points.iter().all(|p| inside(*p, 0.0)),
+                };
+                assert!(fits, "{:?} doesn't fit the grid", icon);
+            }
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use crate::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::panic::Location;
+
+/// Identifies a control made by `Ui`: where it was called from, and how
+/// many times that call ran before it in the same pass.
+type Key = (&'static Location<'static>, usize);
+
+/// Input for controls, kept until the next pass picks it up.
+#[derive(Clone, Default)]
+struct Input {
+    clicked: HashSet<Key>,
+    values: HashMap<Key, f32>,
+    toggles: HashMap<Key, bool>,
+}
+
+impl Input {
+    fn is_empty(&self) -> bool {
+        self.clicked.is_empty() && self.values.is_empty() && self.toggles.is_empty()
+    }
+}
+
+/// What a pass asked to show.
+enum Item {
+    Label(String),
+    Button { key: Key, label: String },
+    Slider { key: Key, value: f32 },
+    Toggle { key: Key, value: bool },
+    Row(Vec<Item>),
+}
+
+impl Item {
+    fn view(&self, input: &State<Input>) -> AnyView {
+        match self {
+            Item::Label(label) => any_view(text(label)),
+            Item::Button { key, label } => {
+                let (key, input) = (*key, input.clone());
+                any_view(button(text(label), move || {
+                    input.with_mut(|input| input.clicked.insert(key));
+                }))
+            }
+            Item::Slider { key, value } => {
+                let (key, value) = (*key, *value);
+                let (input, input2) = (input.clone(), input.clone());
+                any_view(hslider(bind(
+                    move || input.with(|input| *input.values.get(&key).unwrap_or(&value)),
+                    move |value| {
+                        input2.with_mut(|input| input.values.insert(key, value));
+                    },
+                )))
+            }
+            Item::Toggle { key, value } => {
+                let (key, value) = (*key, *value);
+                let (input, input2) = (input.clone(), input.clone());
+                any_view(toggle(bind(
+                    move || input.with(|input| *input.toggles.get(&key).unwrap_or(&value)),
+                    move |value| {
+                        input2.with_mut(|input| input.toggles.insert(key, value));
+                    },
+                )))
+            }
+            Item::Row(items) => any_view(hstack(
+                items
+                    .iter()
+                    .map(|item| item.view(input))
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
+
+/// Makes the controls of an `immediate` view. Each control is told
+/// apart by where it's called from, so no ids are needed. Calls made in a
+/// loop are told apart by the order they're made in.
+pub struct Ui {
+    items: Vec<Item>,
+    input: Input,
+    calls: HashMap<&'static Location<'static>, usize>,
+}
+
+impl Ui {
+    #[track_caller]
+    fn key(&mut self) -> Key {
+        let location = Location::caller();
+        let count = self.calls.entry(location).or_insert(0);
+        *count += 1;
+        (location, *count - 1)
+    }
+
+    pub fn label(&mut self, label: &str) {
+        self.items.push(Item::Label(label.into()));
+    }
+
+    /// Shows a button. Returns true once after it's clicked.
+    #[track_caller]
+    pub fn button(&mut self, label: &str) -> bool {
+        let key = self.key();
+        self.items.push(Item::Button {
+            key,
+            label: label.into(),
+        });
+        self.input.clicked.contains(&key)
+    }
+
+    /// Shows a slider for a value from 0 to 1. Returns true if the slider
+    /// changed it.
+    #[track_caller]
+    pub fn slider(&mut self, value: &mut f32) -> bool {
+        let key = self.key();
+        let changed = match self.input.values.get(&key) {
+            Some(new_value) => {
+                *value = *new_value;
+                true
+            }
+            None => false,
+        };
+        self.items.push(Item::Slider { key, value: *value });
+        changed
+    }
+
+    /// Shows a toggle. Returns true if it was flipped.
+    #[track_caller]
+    pub fn toggle(&mut self, value: &mut bool) -> bool {
+        let key = self.key();
+        let changed = match self.input.toggles.get(&key) {
+            Some(new_value) => {
+                *value = *new_value;
+                true
+            }
+            None => false,
+        };
+        self.items.push(Item::Toggle { key, value: *value });
+        changed
+    }
+
+    /// Lays out the controls made in `f` side by side.
+    pub fn horizontal(&mut self, f: impl FnOnce(&mut Ui)) {
+        let items = std::mem::take(&mut self.items);
+        f(self);
+        let row = std::mem::replace(&mut self.items, items);
+        self.items.push(Item::Row(row));
+    }
+}
+
+/// Struct for `immediate`.
+pub struct Immediate<F> {
+    func: RefCell<F>,
+
+    /// The controls from the last pass.
+    items: RefCell<Vec<Item>>,
+}
+
+impl<F> Immediate<F>
+where
+    F: FnMut(&mut Ui) + 'static,
+{
+    fn input(&self, id: ViewID, cx: &mut Context) -> State<Input> {
+        cx.get_state(id, Input::default)
+    }
+
+    /// Runs `func` to find the controls to show, handing it the input
+    /// since the last pass.
+    fn run(&self, id: ViewID, cx: &mut Context) {
+        let input = self.input(id, cx);
+        let pending = input.get();
+        if !pending.is_empty() {
+            // Runs another pass, so controls made before the ones which
+            // got input show what it changed.
+            input.set(Input::default());
+        }
+        let mut ui = Ui {
+            items: vec![],
+            input: pending,
+            calls: HashMap::new(),
+        };
+        (self.func.borrow_mut())(&mut ui);
+        *self.items.borrow_mut() = ui.items;
+    }
+
+    fn body(&self, id: ViewID, cx: &mut Context) -> impl View {
+        let input = self.input(id, cx);
+        let items = self.items.borrow();
+        vstack(
+            items
+                .iter()
+                .map(|item| item.view(&input))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl<F> View for Immediate<F>
+where
+    F: FnMut(&mut Ui) + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("immediate(");
+        self.body(id, cx).print(id.child(&0), cx);
+        println!(")");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(id, cx).process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(id, cx).draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.run(id, cx);
+        self.body(id, cx)
+            .layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.body(id, cx).hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.body(id, cx).commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let input = self.input(id, cx);
+        map.insert(id, Box::new(input));
+        self.body(id, cx).gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.body(id, cx).access(id.child(&0), cx, nodes)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.body(id, cx).intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<F> private::Sealed for Immediate<F> {}
+
+/// A view whose controls are made anew each frame by `f`, in the
+/// immediate mode style, for quick debug panels and tools. `f` keeps its
+/// own data, and controls report what happened to them as they're made:
+///
+/// ```ignore
+/// let mut count = 0;
+/// rui(immediate(move |ui| {
+///     ui.label(&format!("{}", count));
+///     if ui.button("increment") {
+///         count += 1;
+///     }
+/// }));
+/// ```
+pub fn immediate<F: FnMut(&mut Ui) + 'static>(f: F) -> Immediate<F> {
+    Immediate {
+        func: RefCell::new(f),
+        items: RefCell::new(vec![]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_immediate() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let clicks = Rc::new(Cell::new(0));
+        let clicks2 = clicks.clone();
+        let ui = immediate(move |ui| {
+            if ui.button("click") {
+                clicks2.set(clicks2.get() + 1);
+            }
+        });
+        cx.layout_root(&ui, size, &mut vger);
+
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        assert_eq!(clicks.get(), 0);
+        cx.layout_root(&ui, size, &mut vger);
+        assert_eq!(clicks.get(), 1);
+        cx.layout_root(&ui, size, &mut vger);
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn test_call_site_keys() {
+        let mut ui = Ui {
+            items: vec![],
+            input: Input::default(),
+            calls: HashMap::new(),
+        };
+        let keys: Vec<_> = (0..2).map(|_| ui.key()).collect();
+        let other = ui.key();
+        assert_eq!(keys[0].0, keys[1].0);
+        assert_ne!(keys[0], keys[1]);
+        assert_ne!(keys[0].0, other.0);
+    }
+}
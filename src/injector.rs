@@ -0,0 +1,150 @@
+use crate::*;
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Events sent with an `Injector`, waiting for the event loop.
+static USER_EVENTS: Mutex<Vec<UserEvent>> = Mutex::new(Vec::new());
+
+/// An event sent with an `Injector`. Use `on_user_event` to receive them.
+pub type UserEvent = Arc<dyn Any + Send + Sync>;
+
+/// Sends events into the UI from other threads, for example from a MIDI
+/// controller, gamepad or other hardware. Events sent before the UI starts
+/// are delivered once it does.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Injector;
+
+impl Injector {
+    /// Queues `event` for the views, and wakes up the event loop.
+    pub fn send<T: Any + Send + Sync>(&self, event: T) {
+        USER_EVENTS.lock().unwrap().push(Arc::new(event));
+        redraw_app();
+    }
+}
+
+/// Returns a handle for sending events to the UI from other threads.
+/// Views receive them with `on_user_event`.
+pub fn injector() -> Injector {
+    Injector
+}
+
+/// Takes the events sent so far, in the order they were sent.
+pub(crate) fn take_user_events() -> Vec<UserEvent> {
+    std::mem::take(&mut *USER_EVENTS.lock().unwrap())
+}
+
+/// Struct for `on_user_event`.
+pub struct OnUserEvent<V, T, F> {
+    child: V,
+    func: F,
+    phantom: PhantomData<fn(&T)>,
+}
+
+impl<V, T, F> OnUserEvent<V, T, F>
+where
+    V: View,
+    T: Any,
+    F: Fn(&T) + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self {
+            child: v,
+            func: f,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<V, T, F> View for OnUserEvent<V, T, F>
+where
+    V: View,
+    T: Any,
+    F: Fn(&T) + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".on_user_event()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::User(payload) = &event.kind {
+            if let Some(value) = payload.downcast_ref::<T>() {
+                (self.func)(value);
+            }
+        }
+        self.child.process(event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, T, F> private::Sealed for OnUserEvent<V, T, F> {}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::MutexGuard;
+
+    static USER_EVENTS_TEST: Mutex<()> = Mutex::new(());
+
+    /// Keeps tests which send user events from taking each other's.
+    pub(crate) fn lock_user_events() -> MutexGuard<'static, ()> {
+        USER_EVENTS_TEST
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[test]
+    fn test_injector() {
+        let _lock = lock_user_events();
+        injector().send(42u8);
+        injector().send("hello");
+        let events = take_user_events();
+        assert_eq!(events[0].downcast_ref::<u8>(), Some(&42));
+        assert_eq!(events[1].downcast_ref::<&str>(), Some(&"hello"));
+    }
+}
@@ -0,0 +1,214 @@
+use crate::*;
+
+const INSPECTOR_FONT_SIZE: u32 = 14;
+const INSPECTOR_LINE_HEIGHT: f32 = 18.0;
+
+/// State for the view hierarchy inspector, toggled with
+/// Ctrl+Shift+I (Cmd+Shift+I on macOS).
+#[derive(Default)]
+pub(crate) struct Inspector {
+    /// Is the inspector showing?
+    pub enabled: bool,
+
+    /// The view under the mouse.
+    pub hovered: Option<ViewID>,
+
+    /// How many allocations the last frame made, counted by
+    /// `CountingAllocator` if the app installs it.
+    pub frame_allocations: usize,
+}
+
+impl Inspector {
+    /// Is this key press the chord which toggles the inspector?
+    pub fn is_toggle(key: KeyCode, mods: ModifiersState) -> bool {
+        key == KeyCode::KeyI && mods.shift_key() && (mods.control_key() || mods.super_key())
+    }
+}
+
+/// Outlines the view if the inspector is showing and it's under the mouse.
+/// Also notes where the view is for a query.
+pub(crate) fn inspector_highlight(
+    cx: &mut Context,
+    id: ViewID,
+    rect: LocalRect,
+    vger: &mut dyn Renderer,
+) {
+    cx.note_rect(id, rect, vger);
+    if cx.inspector.enabled && cx.inspector.hovered == Some(id) {
+        let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(0.3));
+        vger.fill_rect(rect, 0.0, paint);
+        let paint = vger.color_paint(AZURE_HIGHLIGHT);
+        vger.stroke_rect(rect.min(), rect.max(), 0.0, 1.0, paint);
+    }
+}
+
+/// Shortens a type name by dropping module paths, so
+/// `rui::state::State<alloc::string::String>` is `State<String>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short += segment.rsplit("::").next().unwrap_or_default();
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short + segment.rsplit("::").next().unwrap_or_default()
+}
+
+/// The value of a state, if it's of a type we know how to show, or
+/// otherwise its type.
+fn state_value(state: &dyn AnyState) -> String {
+    macro_rules! debug_states {
+        ($($t:ty),*) => {
+            $(
+                if let Some(state) = state.as_any().downcast_ref::<State<$t>>() {
+                    return state.with(|value| format!("{:?}", value));
+                }
+            )*
+        };
+    }
+    debug_states!(
+        bool,
+        i32,
+        i64,
+        u32,
+        u64,
+        usize,
+        f32,
+        f64,
+        String,
+        Option<String>
+    );
+    short_type_name(state.type_name())
+}
+
+/// The lines the inspector panel shows: what's under the mouse, then the
+/// views in the tree with their labels and values, then the states.
+pub(crate) fn inspector_lines(cx: &Context, query: &Query) -> Vec<String> {
+    let mut lines = vec![
+        "inspector".to_string(),
+        format!("{} views laid out", cx.layout.laid_out()),
+        format!("{} states", cx.state_map.len()),
+    ];
+
+    if allocation_count() > 0 {
+        lines.push(format!(
+            "{} allocations last frame",
+            cx.inspector.frame_allocations
+        ));
+    }
+
+    match cx.inspector.hovered {
+        Some(id) => {
+            let layout_box = cx.layout.get(&id).cloned().unwrap_or_default();
+            lines.push(format!("hovered: {:?}", id));
+            lines.push(format!("rect: {:?}", layout_box.rect));
+            lines.push(format!("offset: {:?}", layout_box.offset));
+        }
+        None => lines.push("hovered: none".to_string()),
+    }
+
+    if let Some(id) = cx.focused_id {
+        lines.push(format!("focused: {:?}", id));
+    }
+
+    lines.push(String::new());
+    for (depth, node) in query.tree() {
+        let mut line = format!("{}{:?}", "  ".repeat(depth), node.role);
+        if let Some(label) = &node.label {
+            line += &format!(" {:?}", label);
+        }
+        if let Some(value) = &node.value {
+            line += &format!(" = {}", value);
+        }
+        line += &format!(" {:?}", node.id);
+        if let Some(layout_box) = cx.layout.get(&node.id) {
+            let rect = layout_box.rect;
+            line += &format!(
+                " [{:.0}, {:.0} {:.0}x{:.0}]",
+                rect.origin.x, rect.origin.y, rect.size.width, rect.size.height
+            );
+        }
+        lines.push(line);
+    }
+
+    lines.push(String::new());
+    let mut states: Vec<_> = cx
+        .state_map
+        .values()
+        .map(|state| state_value(state.as_ref()))
+        .collect();
+    states.sort();
+    lines.extend(states.into_iter().map(|state| format!("state: {}", state)));
+    lines
+}
+
+/// Draws the inspector panel down the left of the window, with as many
+/// of the `lines` as fit.
+pub(crate) fn draw_inspector_panel(
+    lines: &[String],
+    window_size: LocalSize,
+    vger: &mut dyn Renderer,
+) {
+    let fit = ((window_size.height - 20.0) / INSPECTOR_LINE_HEIGHT - 1.0).max(0.0) as usize;
+    let lines = &lines[..lines.len().min(fit)];
+
+    let height = INSPECTOR_LINE_HEIGHT * (lines.len() as f32 + 1.0);
+    let panel = LocalRect::new(
+        [10.0, window_size.height - height - 10.0].into(),
+        [400.0, height].into(),
+    );
+
+    vger.save();
+    let paint = vger.color_paint(BLACK.alpha(0.8));
+    vger.fill_rect(panel, 5.0, paint);
+
+    vger.translate([panel.min_x() + 10.0, panel.max_y()].into());
+    for line in lines {
+        vger.translate([0.0, -INSPECTOR_LINE_HEIGHT].into());
+        vger.text(line, INSPECTOR_FONT_SIZE, TEXT_COLOR, None);
+    }
+    vger.restore();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(
+            short_type_name("rui::state::State<alloc::vec::Vec<my_app::Item>>"),
+            "State<Vec<Item>>"
+        );
+    }
+
+    #[test]
+    fn test_inspector_lines() {
+        let size = LocalSize::new(200.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = state(String::from("rui"), |name| {
+            vstack((text(&name.get()), toggle(signal(true))))
+        });
+        // Views laid out before aren't counted once they're gone.
+        let gone = vstack((text("a"), text("b"), text("c")));
+        gone.layout(cx.root_id.child(&1), size.into(), &mut cx, &mut vger);
+        cx.layout_root(&ui, size, &mut vger);
+        let query = cx.query(&ui, &mut vger);
+        let lines = inspector_lines(&cx, &query);
+        assert_eq!(lines[1], "6 views laid out");
+        assert!(cx.layout.len() > 6);
+
+        let label = lines
+            .iter()
+            .find(|line| line.starts_with("  LabelText \"rui\" ViewID"))
+            .expect("label row");
+        assert!(label.ends_with(']'), "{}", label);
+        assert!(lines.contains(&"state: \"rui\"".to_string()));
+    }
+}
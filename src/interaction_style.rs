@@ -0,0 +1,254 @@
+use crate::*;
+
+/// How the user is interacting with a view, for the `style` modifier.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InteractionState {
+    /// The mouse is over the view.
+    pub hovered: bool,
+    /// A touch or mouse button went down on the view and hasn't come up.
+    pub pressed: bool,
+    /// A view within has the keyboard focus.
+    pub focused: bool,
+    /// The view is `disabled`.
+    pub disabled: bool,
+}
+
+/// Changes to how a view is drawn, returned from the `style` modifier's
+/// function. Anything left as `None` is drawn as usual.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StyleOverrides {
+    /// Filled behind the view.
+    pub background: Option<Color>,
+    /// Stroked around the view, `border_width` wide.
+    pub border: Option<Color>,
+    pub border_width: f32,
+    /// For the background and border.
+    pub corner_radius: f32,
+    pub opacity: Option<f32>,
+    /// Scales the view about its center, like shrinking a pressed button.
+    pub scale: Option<f32>,
+}
+
+impl StyleOverrides {
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn border(mut self, color: Color, width: f32) -> Self {
+        self.border = Some(color);
+        self.border_width = width;
+        self
+    }
+
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+}
+
+/// What `Styled` tracks from the events it sees.
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Tracked {
+    hovered: bool,
+
+    /// The touch pressing the view.
+    pressed: Option<usize>,
+
+    /// The view within which took the focus.
+    focus: Option<ViewID>,
+}
+
+/// Struct for the `style` modifier.
+pub struct Styled<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> Styled<V, F>
+where
+    V: View,
+    F: Fn(InteractionState) -> StyleOverrides + 'static,
+{
+    pub fn new(child: V, func: F) -> Self {
+        Self { child, func }
+    }
+
+    fn interaction(&self, id: ViewID, cx: &mut Context) -> InteractionState {
+        let tracked = cx.get_state(id, Tracked::default).get();
+        InteractionState {
+            hovered: tracked.hovered,
+            pressed: tracked.pressed.is_some(),
+            focused: tracked.focus.is_some() && tracked.focus == cx.focused_id,
+            disabled: !cx.is_enabled(),
+        }
+    }
+}
+
+impl<V, F> View for Styled<V, F>
+where
+    V: View,
+    F: Fn(InteractionState) -> StyleOverrides + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".style()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let state = cx.get_state(id, Tracked::default);
+        let mut tracked = state.get();
+        let mut inside = || {
+            self.child
+                .hittest(id.child(&0), event.position, cx, vger)
+                .is_some()
+        };
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } if inside() => tracked.pressed = Some(*touch),
+            EventKind::TouchEnd { id: touch } | EventKind::TouchCancel { id: touch }
+                if tracked.pressed == Some(*touch) =>
+            {
+                tracked.pressed = None
+            }
+            EventKind::TouchMove { id: 0 } => tracked.hovered = inside(),
+            _ => (),
+        }
+
+        let focused = cx.focused_id;
+        self.child.process(event, id.child(&0), cx, vger);
+        if cx.focused_id != focused && cx.focused_id.is_some() {
+            tracked.focus = cx.focused_id;
+        }
+
+        // Only redraw when something changed.
+        if tracked != state.get() {
+            state.set(tracked);
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let style = (self.func)(self.interaction(id, cx));
+        let rect = cx.child_rect(id);
+
+        vger.save();
+        if let Some(opacity) = style.opacity {
+            vger.opacity(opacity);
+        }
+        if let Some(scale) = style.scale {
+            let center = rect.center().to_vector();
+            vger.translate(center);
+            vger.scale(scale);
+            vger.translate(-center);
+        }
+        if let Some(color) = style.background {
+            vger.fill_rect(rect, style.corner_radius, Paint::Color(color));
+        }
+        self.child.draw(id.child(&0), cx, vger);
+        if let Some(color) = style.border {
+            vger.stroke_rect(
+                rect.min(),
+                rect.max(),
+                style.corner_radius,
+                style.border_width,
+                Paint::Color(color),
+            );
+        }
+        vger.restore();
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let child_size = self.child.layout(id.child(&0), constraints, cx, vger);
+
+        cx.note_child_rect(id, LocalRect::new(LocalPoint::zero(), child_size));
+        child_size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = cx.get_state(id, Tracked::default);
+        map.insert(id, Box::new(state));
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, F> private::Sealed for Styled<V, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interaction_state() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = rectangle().style(|_| StyleOverrides::default());
+        cx.layout_root(&ui, size, &mut vger);
+
+        let event = |kind, position: [f32; 2]| Event {
+            kind,
+            position: position.into(),
+        };
+        let mut send = |kind, position| {
+            cx.process_root(&ui, &event(kind, position), &mut vger);
+            ui.interaction(cx.root_id, &mut cx)
+        };
+
+        assert!(send(EventKind::TouchMove { id: 0 }, [50.0, 50.0]).hovered);
+        assert!(send(EventKind::TouchBegin { id: 0 }, [50.0, 50.0]).pressed);
+        let state = send(EventKind::TouchEnd { id: 0 }, [50.0, 50.0]);
+        assert!(state.hovered && !state.pressed);
+        assert!(!send(EventKind::TouchMove { id: 0 }, [150.0, 50.0]).hovered);
+    }
+}
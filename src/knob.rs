@@ -16,7 +16,7 @@ pub fn knob(value: impl Binding<f32>) -> impl View {
         circle().color(CLEAR_COLOR).drag(move |off, _state| {
             value.with_mut(|v| *v = (*v + (off.x + off.y) / 400.0).clamp(0.0, 1.0));
         }),
-        canvas(move |sz, vger| {
+        renderer_canvas(move |sz, vger| {
             let c = sz.center();
             let r = sz.width().min(sz.height()) / 2.0;
 
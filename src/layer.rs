@@ -0,0 +1,167 @@
+use crate::*;
+
+/// How a layer's colors combine with what's drawn under it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// Drawn over what's under it.
+    #[default]
+    Normal,
+
+    /// Darkens: the colors are multiplied.
+    Multiply,
+
+    /// Lightens: the inverses of the colors are multiplied.
+    Screen,
+
+    /// The darker of the two colors.
+    Darken,
+
+    /// The lighter of the two colors.
+    Lighten,
+
+    /// The colors are added.
+    Plus,
+}
+
+/// What's applied to a layer as it's composited. See `Renderer::begin_layer`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerEffects {
+    pub blend_mode: BlendMode,
+
+    /// How far each point is spread, in layout units.
+    pub blur: f32,
+
+    /// 0 for grays, 1 for the colors as drawn, more to make them more
+    /// vivid.
+    pub saturation: f32,
+}
+
+impl Default for LayerEffects {
+    fn default() -> Self {
+        Self {
+            blend_mode: BlendMode::Normal,
+            blur: 0.0,
+            saturation: 1.0,
+        }
+    }
+}
+
+impl LayerEffects {
+    /// The standard deviation of the gaussian blurring the layer.
+    pub fn blur_sigma(&self) -> f32 {
+        self.blur / 3.0
+    }
+}
+
+/// Struct for the `blend_mode`, `compositing_group`, `blur` and
+/// `saturation` modifiers.
+pub struct Layer<V> {
+    child: V,
+    effects: LayerEffects,
+}
+
+impl<V> Layer<V>
+where
+    V: View,
+{
+    pub fn new(child: V, effects: LayerEffects) -> Self {
+        Self { child, effects }
+    }
+}
+
+impl<V> View for Layer<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".layer({:?})", self.effects);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        vger.save();
+        vger.begin_layer(self.effects);
+        self.child.draw(id.child(&0), cx, vger);
+        vger.end_layer();
+        vger.restore();
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V> private::Sealed for Layer<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_svg() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = zstack((
+            rectangle(),
+            circle().blend_mode(BlendMode::Multiply).blur(6.0),
+        ));
+        cx.layout_root(&ui, size, &mut vger);
+        ui.draw(cx.root_id, &mut cx, &mut vger);
+
+        let svg = vger.finish();
+        assert!(svg.contains("mix-blend-mode:multiply"));
+        assert!(svg.contains("<feGaussianBlur stdDeviation=\"2\""));
+        assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+    }
+}
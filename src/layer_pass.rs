@@ -0,0 +1,736 @@
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Something drawn into a target, in order.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Segment {
+    /// Drawn with a vger scene: the index of one of `Layers::scenes`, or
+    /// `None` for the renderer's own.
+    Scene(Option<usize>),
+
+    /// A layer, composited from its texture.
+    Layer(usize),
+
+    /// A `frame_view` frame: the index of one of the renderer's frames.
+    Frame(usize),
+}
+
+/// A layer begun this frame.
+pub(crate) struct LayerDraw {
+    effects: LayerEffects,
+    opacity: f32,
+
+    /// The clip when the layer began, in its target's coordinates.
+    clip: Option<LocalRect>,
+
+    /// What's drawn into the layer, in order.
+    segments: Vec<Segment>,
+
+    /// The size of the layer's texture, in physical pixels.
+    size: PhysicalSize<u32>,
+
+    /// Where the layer's texture goes in its target, or `None` to cover
+    /// it.
+    rect: Option<LocalRect>,
+
+    /// The view whose drawing the layer's texture is kept for.
+    cache: Option<ViewID>,
+}
+
+/// Draws layers on the GPU. vger draws a frame in one go, so each layer,
+/// and what's drawn after one ends or after a `frame_view` frame, is drawn
+/// with a vger scene of its own.
+/// Layers' scenes are drawn into textures, which are composited in order
+/// with what's drawn before and after them.
+pub(crate) struct Layers {
+    pass: LayerPass,
+
+    /// Scenes kept from frame to frame, since they're slow to create.
+    scenes: Vec<VGER>,
+
+    /// How many of `scenes` are used this frame.
+    used: usize,
+
+    /// What's drawn into the window, in order.
+    segments: Vec<Segment>,
+    layers: Vec<LayerDraw>,
+
+    /// The layers begun and not ended, innermost last.
+    open: Vec<usize>,
+
+    /// The window's size in physical pixels, and its scale.
+    window: (PhysicalSize<u32>, f32),
+
+    /// Textures kept for `begin_cache`, by view, and the views drawn from
+    /// them this frame.
+    cached: HashMap<ViewID, (wgpu::Texture, PhysicalSize<u32>)>,
+    cached_drawn: HashSet<ViewID>,
+}
+
+impl Layers {
+    pub(crate) fn new(device: Arc<wgpu::Device>, format: wgpu::TextureFormat) -> Self {
+        Self {
+            pass: LayerPass::new(device, format),
+            scenes: vec![],
+            used: 0,
+            segments: vec![Segment::Scene(None)],
+            layers: vec![],
+            open: vec![],
+            window: (PhysicalSize::new(0, 0), 1.0),
+            cached: HashMap::new(),
+            cached_drawn: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn begin(&mut self, size: PhysicalSize<u32>, scale: f32) {
+        self.used = 0;
+        self.segments = vec![Segment::Scene(None)];
+        self.layers.clear();
+        self.open.clear();
+        self.window = (size, scale);
+
+        // Forget the drawings of views which stopped drawing.
+        let drawn = std::mem::take(&mut self.cached_drawn);
+        self.cached.retain(|key, _| drawn.contains(key));
+    }
+
+    pub(crate) fn scene(&mut self, index: usize) -> &mut VGER {
+        &mut self.scenes[index]
+    }
+
+    /// Whether any layers or frames were drawn this frame.
+    pub(crate) fn drawn(&self) -> bool {
+        self.segments.len() > 1
+    }
+
+    /// The size of the layer drawn into next, in physical pixels, or
+    /// `None` for the window.
+    pub(crate) fn target_size(&self) -> Option<PhysicalSize<u32>> {
+        self.open.last().map(|layer| self.layers[*layer].size)
+    }
+
+    /// Where what's drawn next goes: the innermost open layer, or the
+    /// window.
+    fn target(&mut self) -> &mut Vec<Segment> {
+        match self.open.last() {
+            Some(layer) => &mut self.layers[*layer].segments,
+            None => &mut self.segments,
+        }
+    }
+
+    fn next_scene(&mut self) -> usize {
+        if self.used == self.scenes.len() {
+            let scene = VGER::new(&self.pass.device, self.pass.format);
+            self.scenes.push(scene);
+        }
+        self.used += 1;
+        self.used - 1
+    }
+
+    fn push_layer(&mut self, layer: LayerDraw) -> usize {
+        let index = self.layers.len();
+        self.target().push(Segment::Layer(index));
+        self.layers.push(layer);
+        index
+    }
+
+    /// Begins a layer, returning the scene to draw into it with.
+    pub(crate) fn begin_layer(
+        &mut self,
+        effects: LayerEffects,
+        opacity: f32,
+        clip: Option<LocalRect>,
+    ) -> usize {
+        let scene = self.next_scene();
+        let layer = self.push_layer(LayerDraw {
+            effects,
+            opacity,
+            clip,
+            segments: vec![Segment::Scene(Some(scene))],
+            size: self.target_size().unwrap_or(self.window.0),
+            rect: None,
+            cache: None,
+        });
+        self.open.push(layer);
+        scene
+    }
+
+    /// Begins a layer of `size` physical pixels at `rect`, kept for
+    /// `draw_cached` with `key`, returning the scene to draw into it with.
+    pub(crate) fn begin_cache(
+        &mut self,
+        key: ViewID,
+        size: PhysicalSize<u32>,
+        rect: LocalRect,
+        opacity: f32,
+        clip: Option<LocalRect>,
+    ) -> usize {
+        self.cached_drawn.insert(key);
+        let scene = self.next_scene();
+        let layer = self.push_layer(LayerDraw {
+            effects: LayerEffects::default(),
+            opacity,
+            clip,
+            segments: vec![Segment::Scene(Some(scene))],
+            size,
+            rect: Some(rect),
+            cache: Some(key),
+        });
+        self.open.push(layer);
+        scene
+    }
+
+    /// Composites the texture kept for `key` at `rect`, returning the
+    /// scene to draw what's after it with, or `None` if there isn't one of
+    /// `size` physical pixels.
+    pub(crate) fn draw_cached(
+        &mut self,
+        key: ViewID,
+        size: PhysicalSize<u32>,
+        rect: LocalRect,
+        opacity: f32,
+        clip: Option<LocalRect>,
+    ) -> Option<usize> {
+        match self.cached.get(&key) {
+            Some((_, cached_size)) if *cached_size == size => {
+                self.cached_drawn.insert(key);
+                self.push_layer(LayerDraw {
+                    effects: LayerEffects::default(),
+                    opacity,
+                    clip,
+                    segments: vec![],
+                    size,
+                    rect: Some(rect),
+                    cache: Some(key),
+                });
+                let scene = self.next_scene();
+                self.target().push(Segment::Scene(Some(scene)));
+                Some(scene)
+            }
+            _ => None,
+        }
+    }
+
+    /// Ends the innermost layer, returning the scene to draw what's after
+    /// it with.
+    pub(crate) fn end_layer(&mut self) -> Option<usize> {
+        self.open.pop()?;
+        let scene = self.next_scene();
+        self.target().push(Segment::Scene(Some(scene)));
+        Some(scene)
+    }
+
+    /// Draws the `index`th of the renderer's frames, returning the scene
+    /// to draw what's after it with.
+    pub(crate) fn draw_frame(&mut self, index: usize) -> usize {
+        self.target().push(Segment::Frame(index));
+        let scene = self.next_scene();
+        self.target().push(Segment::Scene(Some(scene)));
+        scene
+    }
+
+    /// Draws the window's segments into the render pass's target, `main`
+    /// drawing what isn't in a scene of our own, and `frame_pass` the
+    /// renderer's `frames`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn encode(
+        &mut self,
+        main: &mut VGER,
+        frame_pass: &mut FramePass,
+        frames: &[FrameDraw],
+        device: &wgpu::Device,
+        render_pass: &wgpu::RenderPassDescriptor,
+        queue: &wgpu::Queue,
+    ) {
+        let attachment = &render_pass.color_attachments[0];
+        let sizes: Vec<_> = self
+            .layers
+            .iter()
+            .map(|layer| match layer.cache {
+                Some(_) => None,
+                None => Some(layer.size),
+            })
+            .collect();
+        let views = self.pass.textures(&sizes);
+        let segments = std::mem::take(&mut self.segments);
+        self.encode_segments(
+            main,
+            frame_pass,
+            frames,
+            &segments,
+            attachment.view,
+            self.window.0,
+            attachment.ops.load,
+            &views,
+            device,
+            queue,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn encode_segments(
+        &mut self,
+        main: &mut VGER,
+        frame_pass: &mut FramePass,
+        frames: &[FrameDraw],
+        segments: &[Segment],
+        target: &wgpu::TextureView,
+        target_size: PhysicalSize<u32>,
+        load: wgpu::LoadOp<wgpu::Color>,
+        views: &[Option<wgpu::TextureView>],
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        for (i, segment) in segments.iter().enumerate() {
+            let load = if i == 0 { load } else { wgpu::LoadOp::Load };
+            match *segment {
+                Segment::Scene(scene) => {
+                    let desc = wgpu::RenderPassDescriptor {
+                        label: None,
+                        color_attachments: &[wgpu::RenderPassColorAttachment {
+                            view: target,
+                            resolve_target: None,
+                            ops: wgpu::Operations { load, store: true },
+                        }],
+                        depth_stencil_attachment: None,
+                    };
+                    let vger = match scene {
+                        Some(index) => &mut self.scenes[index],
+                        None => &mut *main,
+                    };
+                    vger.encode(device, &desc, queue);
+                }
+                Segment::Layer(layer) => {
+                    let size = self.layers[layer].size;
+                    let cached_view;
+                    let view = match self.layers[layer].cache {
+                        Some(key) => {
+                            if !matches!(self.cached.get(&key), Some((_, cached_size)) if *cached_size == size)
+                            {
+                                let texture = self.pass.create_texture(size);
+                                self.cached.insert(key, (texture, size));
+                            }
+                            cached_view = self.cached[&key]
+                                .0
+                                .create_view(&wgpu::TextureViewDescriptor::default());
+                            &cached_view
+                        }
+                        None => views[layer].as_ref().unwrap(),
+                    };
+                    let inner = std::mem::take(&mut self.layers[layer].segments);
+                    if !inner.is_empty() {
+                        self.encode_segments(
+                            main,
+                            frame_pass,
+                            frames,
+                            &inner,
+                            view,
+                            size,
+                            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            views,
+                            device,
+                            queue,
+                        );
+                    }
+                    let scale = self.window.1;
+                    self.pass.composite(
+                        queue,
+                        (view, size),
+                        (target, target_size),
+                        &self.layers[layer],
+                        scale,
+                    );
+                }
+                Segment::Frame(index) => {
+                    // Targets begin with a scene, which loaded them.
+                    let scale = self.window.1;
+                    let frame = std::slice::from_ref(&frames[index]);
+                    frame_pass.draw(device, queue, target, frame, target_size, scale);
+                }
+            }
+        }
+    }
+}
+
+/// Composites layers' textures, with their effects.
+struct LayerPass {
+    device: Arc<wgpu::Device>,
+    format: wgpu::TextureFormat,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+
+    /// Blurs across, into `blurred`.
+    blur_pipeline: wgpu::RenderPipeline,
+
+    /// Blurs down and composites, for each blend mode.
+    pipelines: HashMap<BlendMode, wgpu::RenderPipeline>,
+
+    /// A texture for each layer, the size of what it's drawn onto, reused
+    /// until its size changes.
+    textures: Vec<(wgpu::Texture, PhysicalSize<u32>)>,
+    blurred: Option<(wgpu::Texture, PhysicalSize<u32>)>,
+}
+
+/// How layers with each blend mode are composited. Colors are
+/// premultiplied. Where the blend modes need both alphas, these take
+/// what's under the layer to be opaque.
+fn blend_state(mode: BlendMode) -> wgpu::BlendState {
+    use wgpu::{BlendComponent, BlendFactor, BlendOperation};
+    let component = |src_factor, dst_factor, operation| BlendComponent {
+        src_factor,
+        dst_factor,
+        operation,
+    };
+    let color = match mode {
+        BlendMode::Normal => BlendComponent::OVER,
+        BlendMode::Multiply => component(
+            BlendFactor::Dst,
+            BlendFactor::OneMinusSrcAlpha,
+            BlendOperation::Add,
+        ),
+        BlendMode::Screen => component(
+            BlendFactor::One,
+            BlendFactor::OneMinusSrc,
+            BlendOperation::Add,
+        ),
+        // The shader draws the layer over white for darken, and over
+        // black for lighten, so transparent pixels leave the target be.
+        BlendMode::Darken => component(BlendFactor::One, BlendFactor::One, BlendOperation::Min),
+        BlendMode::Lighten => component(BlendFactor::One, BlendFactor::One, BlendOperation::Max),
+        BlendMode::Plus => component(BlendFactor::One, BlendFactor::One, BlendOperation::Add),
+    };
+    wgpu::BlendState {
+        color,
+        alpha: BlendComponent::OVER,
+    }
+}
+
+impl LayerPass {
+    fn new(device: Arc<wgpu::Device>, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(&wgpu::ShaderModuleDescriptor {
+            label: Some("layer_pass"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("layer_pass.wgsl").into()),
+        });
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("layer_pass"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("layer_pass"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = |blend: Option<wgpu::BlendState>| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("layer_pass"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    targets: &[wgpu::ColorTargetState {
+                        format,
+                        blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    }],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+
+        let blur_pipeline = pipeline(None);
+        let pipelines = [
+            BlendMode::Normal,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Darken,
+            BlendMode::Lighten,
+            BlendMode::Plus,
+        ]
+        .iter()
+        .map(|&mode| (mode, pipeline(Some(blend_state(mode)))))
+        .collect();
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("layer_pass"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            device,
+            format,
+            layout,
+            sampler,
+            blur_pipeline,
+            pipelines,
+            textures: vec![],
+            blurred: None,
+        }
+    }
+
+    fn create_texture(&self, size: PhysicalSize<u32>) -> wgpu::Texture {
+        self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("layer"),
+            size: wgpu::Extent3d {
+                width: size.width.max(1),
+                height: size.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        })
+    }
+
+    /// Views of a texture of each size, for the layers which aren't kept
+    /// for a view.
+    fn textures(&mut self, sizes: &[Option<PhysicalSize<u32>>]) -> Vec<Option<wgpu::TextureView>> {
+        let mut views = vec![];
+        let mut used = 0;
+        for size in sizes {
+            views.push(size.map(|size| {
+                if self.textures.len() == used {
+                    self.textures.push((self.create_texture(size), size));
+                } else if self.textures[used].1 != size {
+                    self.textures[used] = (self.create_texture(size), size);
+                }
+                used += 1;
+                self.textures[used - 1]
+                    .0
+                    .create_view(&wgpu::TextureViewDescriptor::default())
+            }));
+        }
+        views
+    }
+
+    /// Composites `source`, holding `layer`, onto `target`. Each comes
+    /// with its size in physical pixels.
+    fn composite(
+        &mut self,
+        queue: &wgpu::Queue,
+        (source, source_size): (&wgpu::TextureView, PhysicalSize<u32>),
+        (target, target_size): (&wgpu::TextureView, PhysicalSize<u32>),
+        layer: &LayerDraw,
+        scale: f32,
+    ) {
+        let (width, height) = (source_size.width as f32, source_size.height as f32);
+        let sigma = layer.effects.blur_sigma() * scale;
+        let darken = (layer.effects.blend_mode == BlendMode::Darken) as u32 as f32;
+
+        // Where the layer goes, in the target's clip space.
+        let (target_width, target_height) = (
+            target_size.width as f32 / scale,
+            target_size.height as f32 / scale,
+        );
+        let rect = layer.rect.unwrap_or_else(|| {
+            LocalRect::new(LocalPoint::zero(), [target_width, target_height].into())
+        });
+        let rect = [
+            rect.min_x() / target_width * 2.0 - 1.0,
+            rect.min_y() / target_height * 2.0 - 1.0,
+            rect.max_x() / target_width * 2.0 - 1.0,
+            rect.max_y() / target_height * 2.0 - 1.0,
+        ];
+
+        if sigma > 0.0 {
+            if !matches!(&self.blurred, Some((_, size)) if *size == source_size) {
+                self.blurred = Some((self.create_texture(source_size), source_size));
+            }
+            let blurred = &self.blurred.as_ref().unwrap().0;
+            let blurred = blurred.create_view(&wgpu::TextureViewDescriptor::default());
+            self.draw(
+                queue,
+                &self.blur_pipeline,
+                source,
+                (&blurred, source_size),
+                wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                [
+                    -1.0,
+                    -1.0,
+                    1.0,
+                    1.0,
+                    1.0 / width,
+                    0.0,
+                    sigma,
+                    1.0,
+                    1.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                ],
+                None,
+                scale,
+            );
+            self.draw(
+                queue,
+                &self.pipelines[&layer.effects.blend_mode],
+                &blurred,
+                (target, target_size),
+                wgpu::LoadOp::Load,
+                [
+                    rect[0],
+                    rect[1],
+                    rect[2],
+                    rect[3],
+                    0.0,
+                    1.0 / height,
+                    sigma,
+                    layer.effects.saturation,
+                    layer.opacity,
+                    darken,
+                    0.0,
+                    0.0,
+                ],
+                layer.clip,
+                scale,
+            );
+        } else {
+            self.draw(
+                queue,
+                &self.pipelines[&layer.effects.blend_mode],
+                source,
+                (target, target_size),
+                wgpu::LoadOp::Load,
+                [
+                    rect[0],
+                    rect[1],
+                    rect[2],
+                    rect[3],
+                    0.0,
+                    0.0,
+                    0.0,
+                    layer.effects.saturation,
+                    layer.opacity,
+                    darken,
+                    0.0,
+                    0.0,
+                ],
+                layer.clip,
+                scale,
+            );
+        }
+    }
+
+    /// Draws `source` over the part of `target` given by `locals` with
+    /// `pipeline`, within `clip`, in the target's coordinates.
+    #[allow(clippy::too_many_arguments)]
+    fn draw(
+        &self,
+        queue: &wgpu::Queue,
+        pipeline: &wgpu::RenderPipeline,
+        source: &wgpu::TextureView,
+        (target, target_size): (&wgpu::TextureView, PhysicalSize<u32>),
+        load: wgpu::LoadOp<wgpu::Color>,
+        locals: [f32; 12],
+        clip: Option<LocalRect>,
+        scale: f32,
+    ) {
+        let uniforms = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("layer"),
+            size: 48,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bytes: Vec<u8> = locals.iter().flat_map(|x| x.to_le_bytes()).collect();
+        queue.write_buffer(&uniforms, 0, &bytes);
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("layer"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        // The scissor in physical pixels, y-down.
+        let (width, height) = (target_size.width, target_size.height);
+        let target_height = height as f32 / scale;
+        let (x0, y0, x1, y1) = match clip {
+            Some(clip) => (
+                (clip.min_x() * scale).max(0.0) as u32,
+                ((target_height - clip.max_y()) * scale).max(0.0) as u32,
+                ((clip.max_x() * scale).max(0.0) as u32).min(width),
+                (((target_height - clip.min_y()) * scale).max(0.0) as u32).min(height),
+            ),
+            None => (0, 0, width, height),
+        };
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("layer_pass"),
+                color_attachments: &[wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations { load, store: true },
+                }],
+                depth_stencil_attachment: None,
+            });
+            if x1 > x0 && y1 > y0 {
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.set_scissor_rect(x0, y0, x1 - x0, y1 - y0);
+                pass.draw(0..4, 0..1);
+            }
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+}
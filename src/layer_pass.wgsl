@@ -0,0 +1,71 @@
+// Composites a layer's texture, blurred along one axis, saturated and
+// faded by `locals`.
+
+struct Locals {
+    // Where the texture goes in the target, in clip space: the minimum x
+    // and y, then the maximum.
+    rect: vec4<f32>;
+    // One pixel along the blur, in uv, then the blur's standard deviation
+    // in pixels and the saturation.
+    blur: vec4<f32>;
+    // The opacity, then 1 to composite over white, for darken.
+    color: vec4<f32>;
+};
+
+[[group(0), binding(0)]]
+var<uniform> locals: Locals;
+
+[[group(0), binding(1)]]
+var layer_texture: texture_2d<f32>;
+
+[[group(0), binding(2)]]
+var layer_sampler: sampler;
+
+struct VertexOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]] uv: vec2<f32>;
+};
+
+[[stage(vertex)]]
+fn vs_main([[builtin(vertex_index)]] index: u32) -> VertexOutput {
+    let corner = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(mix(locals.rect.xy, locals.rect.zw, corner), 0.0, 1.0);
+    // Textures' rows go top to bottom.
+    out.uv = vec2<f32>(corner.x, 1.0 - corner.y);
+    return out;
+}
+
+[[stage(fragment)]]
+fn fs_main(vertex: VertexOutput) -> [[location(0)]] vec4<f32> {
+    let step = locals.blur.xy;
+    let sigma = locals.blur.z;
+    var color = textureSampleLevel(layer_texture, layer_sampler, vertex.uv, 0.0);
+
+    if (sigma > 0.0) {
+        var total = 1.0;
+        var i = 1.0;
+        loop {
+            if (i > ceil(sigma * 3.0)) {
+                break;
+            }
+            let weight = exp(-i * i / (2.0 * sigma * sigma));
+            let offset = step * i;
+            color = color + weight * (
+                textureSampleLevel(layer_texture, layer_sampler, vertex.uv + offset, 0.0) +
+                textureSampleLevel(layer_texture, layer_sampler, vertex.uv - offset, 0.0)
+            );
+            total = total + 2.0 * weight;
+            i = i + 1.0;
+        }
+        color = color / total;
+    }
+
+    // Colors are premultiplied, so the gray and the color stay within
+    // the alpha.
+    let gray = dot(color.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let rgb = clamp(mix(vec3<f32>(gray), color.rgb, locals.blur.w), vec3<f32>(0.0), vec3<f32>(color.a));
+
+    let faded = vec4<f32>(rgb, color.a) * locals.color.x;
+    return vec4<f32>(faded.rgb + (1.0 - faded.a) * locals.color.y, faded.a);
+}
@@ -0,0 +1,119 @@
+use crate::*;
+
+/// Struct for the `layout_priority` modifier.
+pub struct LayoutPriority<V> {
+    child: V,
+    priority: f32,
+}
+
+impl<V> LayoutPriority<V>
+where
+    V: View,
+{
+    pub fn new(child: V, priority: f32) -> Self {
+        Self { child, priority }
+    }
+}
+
+impl<V> View for LayoutPriority<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".layout_priority({:?})", self.priority);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.priority
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V> private::Sealed for LayoutPriority<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_priority() {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([100.0, 20.0].into());
+        let view = hstack((
+            text("a subtitle which is too long"),
+            rectangle().size([60.0, 20.0]).layout_priority(1.0),
+        ));
+        view.layout(cx.root_id, [100.0, 20.0].into(), &mut cx, &mut vger);
+
+        let subtitle = cx.layout[&cx.root_id.child(&0)].rect.width();
+        let action = cx.layout[&cx.root_id.child(&1)].rect.width();
+        assert_eq!(action, 60.0);
+        assert!(subtitle > 0.0 && subtitle <= 40.0);
+
+        // Modifiers after the priority keep it.
+        let view = hstack((
+            text("a subtitle which is too long"),
+            rectangle()
+                .size([50.0, 20.0])
+                .layout_priority(1.0)
+                .padding(PaddingParam::Px(5.0))
+                .tap(|| ()),
+        ));
+        view.layout(cx.root_id, [100.0, 20.0].into(), &mut cx, &mut vger);
+        assert_eq!(cx.layout[&cx.root_id.child(&1)].rect.width(), 60.0);
+    }
+}
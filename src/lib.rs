@@ -1,14 +1,40 @@
 // #![feature(type_alias_impl_trait)]
 
+// So code generated by rui_derive, which refers to ::rui, works inside rui too.
+extern crate self as rui;
+
+pub use rui_derive::{Bindable, Inspectable, Property};
+
 mod view;
 pub use view::*;
 
+mod constraints;
+pub use constraints::*;
+
 mod binding;
 pub use binding::*;
 
 mod state;
 pub use state::*;
 
+mod channel;
+pub use channel::*;
+
+mod signal;
+pub use signal::*;
+
+mod injector;
+pub use injector::*;
+
+mod message;
+pub use message::*;
+
+mod program;
+pub use program::*;
+
+mod immediate;
+pub use immediate::*;
+
 mod text;
 pub use text::*;
 
@@ -18,6 +44,9 @@ pub use button::*;
 mod stack;
 pub use stack::*;
 
+mod layout_priority;
+pub use layout_priority::*;
+
 mod context;
 pub use context::*;
 
@@ -27,12 +56,30 @@ pub use padding::*;
 mod shapes;
 pub use shapes::*;
 
+mod content_shape;
+pub use content_shape::*;
+
+mod z_index;
+pub use z_index::*;
+
+mod layer;
+pub use layer::*;
+
+mod cache;
+pub use cache::*;
+
 mod paint;
 pub use paint::*;
 
+mod renderer;
+pub use renderer::*;
+
 mod gestures;
 pub use gestures::*;
 
+mod event_capture;
+pub use event_capture::*;
+
 mod background;
 pub use background::*;
 
@@ -57,6 +104,9 @@ pub use list::*;
 mod size;
 pub use size::*;
 
+mod aspect_ratio;
+pub use aspect_ratio::*;
+
 mod body;
 pub use body::*;
 
@@ -75,6 +125,27 @@ pub use key::*;
 mod text_editor;
 pub use text_editor::*;
 
+mod text_field;
+pub use text_field::*;
+
+mod form;
+pub use form::*;
+
+mod command_palette;
+pub use command_palette::*;
+
+mod search_field;
+pub use search_field::*;
+
+mod badge;
+pub use badge::*;
+
+mod divider;
+pub use divider::*;
+
+mod card;
+pub use card::*;
+
 mod focus;
 pub use focus::*;
 
@@ -87,22 +158,235 @@ pub use toggle::*;
 mod align;
 pub use align::*;
 
+mod alignment_guide;
+pub use alignment_guide::*;
+
+mod match_size;
+pub use match_size::*;
+
 mod role;
 pub use role::*;
 
+mod widget;
+pub use widget::*;
+
 mod window;
 pub use window::*;
 
+mod safe_area;
+pub use safe_area::*;
+
+mod mobile;
+#[cfg(target_os = "android")]
+pub use mobile::*;
+
+mod monitor;
+pub use monitor::*;
+
+mod env_view;
+pub use env_view::*;
+
+mod disabled;
+pub use disabled::*;
+
+mod interaction_style;
+pub use interaction_style::*;
+
+mod control_style;
+pub use control_style::*;
+
+pub mod fonts;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend;
+#[cfg(not(target_arch = "wasm32"))]
+use backend::*;
+
+mod any_view;
+pub use any_view::*;
+
+pub mod stress;
+
+mod inspector;
+pub(crate) use inspector::*;
+
+mod alloc_count;
+pub use alloc_count::*;
+
+mod pool;
+pub(crate) use pool::*;
+
+mod batch;
+pub use batch::*;
+
+mod record;
+pub use record::*;
+
+mod query;
+pub use query::*;
+
+mod markup;
+
+#[cfg(feature = "sync")]
+mod atomic;
+#[cfg(feature = "sync")]
+pub use atomic::*;
+
+#[cfg(feature = "tray")]
+mod tray;
+#[cfg(feature = "tray")]
+pub use tray::*;
+
+#[cfg(target_arch = "wasm32")]
+mod web;
+#[cfg(target_arch = "wasm32")]
+pub use web::rui;
+
+mod link;
+pub use link::*;
+
+mod charts;
+pub use charts::*;
+
+mod sparkline;
+pub use sparkline::*;
+
+mod gauge;
+pub use gauge::*;
+
+mod frame_view;
+pub use frame_view::*;
+
+mod waveform;
+pub use waveform::*;
+
+mod pan_zoom;
+pub use pan_zoom::*;
+
+mod scroll;
+pub use scroll::*;
+
+mod rubber_band;
+pub use rubber_band::*;
+
+mod draggable;
+pub use draggable::*;
+
+mod node_graph;
+pub use node_graph::*;
+
+mod dock;
+pub use dock::*;
+
+mod property_inspector;
+pub use property_inspector::*;
+
+mod breadcrumbs;
+pub use breadcrumbs::*;
+
+mod pages;
+pub use pages::*;
+
+mod wizard;
+pub use wizard::*;
+
+mod gallery;
+pub use gallery::*;
+
+mod avatar;
+pub use avatar::*;
+
+mod rating;
+pub use rating::*;
+
+mod token_field;
+pub use token_field::*;
+
+mod autocomplete;
+pub use autocomplete::*;
+
+mod error_boundary;
+pub use error_boundary::*;
+
+mod async_view;
+pub use async_view::*;
+
+mod task;
+pub use task::*;
+
+mod frame_pass;
+use frame_pass::*;
+
+mod layer_pass;
+use layer_pass::*;
+
+#[cfg(feature = "tui")]
+mod tui;
+#[cfg(feature = "tui")]
+pub use tui::*;
+
+#[cfg(feature = "software")]
+mod color_font;
+#[cfg(feature = "software")]
+pub(crate) use color_font::*;
+
+#[cfg(feature = "software")]
+mod shaping;
+#[cfg(feature = "software")]
+pub(crate) use shaping::*;
+
+#[cfg(feature = "software")]
+mod software;
+#[cfg(feature = "software")]
+pub use software::*;
+
+#[cfg(feature = "software")]
+mod snapshot;
+#[cfg(feature = "software")]
+pub use snapshot::*;
+
+mod svg_export;
+pub use svg_export::*;
+
+mod print;
+pub use print::*;
+
+#[cfg(feature = "icons")]
+mod icon;
+#[cfg(feature = "icons")]
+pub use icon::*;
+
+#[cfg(feature = "swiftui")]
+pub mod swiftui;
+
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "markdown")]
+pub use markdown::*;
+
+#[cfg(feature = "svg")]
+mod svg_icon;
+#[cfg(feature = "svg")]
+pub use svg_icon::*;
+
+#[cfg(feature = "dialogs")]
+mod file_dialog;
+#[cfg(feature = "dialogs")]
+pub use file_dialog::*;
+
 use futures::executor::block_on;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use vger::color::*;
 use vger::*;
 
 use tao::{
     accelerator::Accelerator,
-    dpi::PhysicalSize,
+    dpi::{LogicalSize, PhysicalSize},
     event,
-    event::{ElementState, WindowEvent},
+    event::{ElementState, TouchPhase, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     keyboard::ModifiersState,
     menu::{MenuBar as Menu, MenuItem, MenuItemAttributes},
@@ -118,79 +402,8 @@ pub(crate) mod private {
 
 pub type KeyCode = tao::keyboard::KeyCode;
 pub type KeyPress = tao::keyboard::Key<'static>;
-
-struct Setup {
-    size: PhysicalSize<u32>,
-    surface: wgpu::Surface,
-    adapter: wgpu::Adapter,
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-}
-
-async fn setup(window: &Window) -> Setup {
-    #[cfg(target_arch = "wasm32")]
-    {
-        use winit::platform::web::WindowExtWebSys;
-        let query_string = web_sys::window().unwrap().location().search().unwrap();
-        let level: log::Level = parse_url_query_string(&query_string, "RUST_LOG")
-            .map(|x| x.parse().ok())
-            .flatten()
-            .unwrap_or(log::Level::Error);
-        console_log::init_with_level(level).expect("could not initialize logger");
-        std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-        // On wasm, append the canvas to the document body
-        web_sys::window()
-            .and_then(|win| win.document())
-            .and_then(|doc| doc.body())
-            .and_then(|body| {
-                body.append_child(&web_sys::Element::from(window.canvas()))
-                    .ok()
-            })
-            .expect("couldn't append canvas to document body");
-    }
-
-    // log::info!("Initializing the surface...");
-
-    let backend = wgpu::util::backend_bits_from_env().unwrap_or_else(wgpu::Backends::all);
-
-    let instance = wgpu::Instance::new(backend);
-    let (size, surface) = unsafe {
-        let size = window.inner_size();
-        let surface = instance.create_surface(&window);
-        (size, surface)
-    };
-    let adapter =
-        wgpu::util::initialize_adapter_from_env_or_default(&instance, backend, Some(&surface))
-            .await
-            .expect("No suitable GPU adapters found on the system!");
-
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let adapter_info = adapter.get_info();
-        println!("Using {} ({:?})", adapter_info.name, adapter_info.backend);
-    }
-
-    let trace_dir = std::env::var("WGPU_TRACE");
-    let (device, queue) = adapter
-        .request_device(
-            &wgpu::DeviceDescriptor {
-                label: None,
-                features: wgpu::Features::default(),
-                limits: wgpu::Limits::default(),
-            },
-            trace_dir.ok().as_ref().map(std::path::Path::new),
-        )
-        .await
-        .expect("Unable to find a suitable GPU adapter!");
-
-    Setup {
-        size,
-        surface,
-        adapter,
-        device,
-        queue,
-    }
-}
+pub type CursorIcon = tao::window::CursorIcon;
+pub type MouseButton = tao::event::MouseButton;
 
 #[derive(Clone, Eq, PartialEq)]
 pub struct CommandInfo {
@@ -279,44 +492,151 @@ fn build_menubar(
     make_menu_rec(&items, 0, command_map)
 }
 
+/// Sends a window event to the views. Returns false if the event was
+/// a close request which an `on_close_requested` handler vetoed.
+fn process_window_event(
+    kind: WindowEventKind,
+    position: LocalPoint,
+    view: &impl View,
+    cx: &mut Context,
+    vger: &mut dyn Renderer,
+    recorder: &mut EventRecorder,
+) -> bool {
+    cx.close_vetoed = false;
+    let event = view::Event {
+        kind: EventKind::Window(kind),
+        position,
+    };
+    recorder.record(&event);
+    cx.process_root(view, &event, vger);
+    !cx.close_vetoed
+}
+
+/// Checks the displays, telling the views if they've changed.
+fn update_monitors(
+    position: LocalPoint,
+    view: &impl View,
+    cx: &mut Context,
+    vger: &mut dyn Renderer,
+    recorder: &mut EventRecorder,
+) {
+    let monitors = Monitors::from_window(cx.window());
+    if monitors != cx.monitors {
+        cx.monitors = monitors.clone();
+        let kind = WindowEventKind::DisplaysChanged(monitors);
+        process_window_event(kind, position, view, cx, vger, recorder);
+    }
+}
+
+/// How far one line of a mouse wheel scrolls, in points.
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+/// Converts a mouse wheel or trackpad scroll to points, y-up.
+fn scroll_delta(delta: event::MouseScrollDelta, scale: f32) -> LocalOffset {
+    // tao may add kinds of delta, so this isn't a match.
+    if let event::MouseScrollDelta::LineDelta(x, y) = delta {
+        LocalOffset::new(x, -y) * SCROLL_LINE_HEIGHT
+    } else if let event::MouseScrollDelta::PixelDelta(p) = delta {
+        LocalOffset::new(p.x as f32, -p.y as f32) / scale
+    } else {
+        LocalOffset::zero()
+    }
+}
+
+/// Maps a platform touch id to an index into the touch arrays in
+/// `Context`, allocating one when the touch begins. Index 0 is the mouse.
+fn touch_slot(slots: &mut [Option<u64>; MAX_TOUCHES], id: u64, begin: bool) -> Option<usize> {
+    if let Some(i) = slots.iter().position(|slot| *slot == Some(id)) {
+        return Some(i);
+    }
+    if !begin {
+        return None;
+    }
+    let i = slots.iter().skip(1).position(|slot| slot.is_none())? + 1;
+    slots[i] = Some(id);
+    Some(i)
+}
+
+#[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+fn software_backend(window: &Window) -> Box<dyn Backend> {
+    eprintln!("No suitable GPU adapter, drawing in software");
+    Box::new(SoftwareBackend::new(window))
+}
+
+#[cfg(all(not(feature = "software"), not(target_arch = "wasm32")))]
+fn software_backend(_window: &Window) -> Box<dyn Backend> {
+    panic!("No suitable GPU adapters found on the system! Keep rui's default software feature on to draw without one.")
+}
+
 /// Call this function to run your UI.
+#[cfg(not(target_arch = "wasm32"))]
 pub fn rui(view: impl View + 'static) {
     let event_loop = EventLoop::new();
 
     let builder = WindowBuilder::new().with_title("rui");
     let window = builder.build(&event_loop).unwrap();
 
-    let setup = block_on(setup(&window));
-    let surface = setup.surface;
-    let device = setup.device;
-    let size = setup.size;
-    let adapter = setup.adapter;
-    let queue = setup.queue;
-
-    let mut config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        format: surface.get_preferred_format(&adapter).unwrap(),
-        width: size.width,
-        height: size.height,
-        present_mode: wgpu::PresentMode::Mailbox,
+    // Set RUI_SOFTWARE to draw on the CPU even when there's a GPU.
+    let gpu = if env::var("RUI_SOFTWARE").is_ok() {
+        None
+    } else {
+        block_on(GpuBackend::new(&window))
+    };
+    let mut backend: Box<dyn Backend> = match gpu {
+        Some(gpu) => Box::new(gpu),
+        None => software_backend(&window),
     };
-    surface.configure(&device, &config);
 
-    let mut vger = VGER::new(&device, wgpu::TextureFormat::Bgra8UnormSrgb);
     let mut cx = Context::new(Some(event_loop.create_proxy()), window);
+    set_app_dirty(cx.dirty.clone());
     let mut mouse_position = LocalPoint::zero();
 
     let mut commands = Vec::new();
     view.commands(cx.root_id, &mut cx, &mut commands);
     let mut command_map = HashMap::new();
-    cx.window
+    cx.window()
         .set_menu(Some(build_menubar(&commands, &mut command_map)));
 
     let mut modifiers = ModifiersState::default();
 
+    // The cursor shown, so it's only set on the window when it changes.
+    let mut cursor = CursorIcon::Default;
+
     let mut access_nodes = vec![];
 
-    event_loop.run(move |event, _, control_flow| {
+    // Set RUI_FRAME_TIME to print how long each frame takes to lay out and draw.
+    let print_frame_time = env::var("RUI_FRAME_TIME").is_ok();
+
+    // Set RUI_RECORD to a path to record events, and RUI_REPLAY to replay them.
+    let mut recorder = EventRecorder::from_env();
+    let mut replay_events: VecDeque<RecordedEvent> = match env::var("RUI_REPLAY") {
+        Ok(path) => read_events(&path)
+            .unwrap_or_else(|err| panic!("error reading events from {:?}: {:?}", path, err))
+            .into(),
+        Err(_) => Default::default(),
+    };
+    let replay_start = std::time::Instant::now();
+
+    // Minimizing shows up as a resize to zero.
+    let mut minimized = false;
+
+    // Platform ids of the touches in progress.
+    let mut touch_slots = [None; MAX_TOUCHES];
+
+    #[cfg(feature = "tray")]
+    let mut tray: Option<Tray> = None;
+    #[cfg(feature = "tray")]
+    let mut tray_request: Option<TrayRequest> = None;
+
+    update_monitors(
+        mouse_position,
+        &view,
+        &mut cx,
+        backend.renderer(),
+        &mut recorder,
+    );
+
+    event_loop.run(move |event, _target, control_flow| {
         // ControlFlow::Poll continuously runs the event loop, even if the OS hasn't
         // dispatched any events. This is ideal for games and similar applications.
         // *control_flow = ControlFlow::Poll;
@@ -331,8 +651,69 @@ pub fn rui(view: impl View + 'static) {
                 event: WindowEvent::CloseRequested,
                 ..
             } => {
-                println!("The close button was pressed; stopping");
-                *control_flow = ControlFlow::Exit
+                let kind = WindowEventKind::CloseRequested;
+                if process_window_event(
+                    kind,
+                    mouse_position,
+                    &view,
+                    &mut cx,
+                    backend.renderer(),
+                    &mut recorder,
+                ) {
+                    println!("The close button was pressed; stopping");
+                    cx.abort_tasks();
+                    *control_flow = ControlFlow::Exit
+                }
+            }
+            event::Event::WindowEvent {
+                event: WindowEvent::Focused(focused),
+                ..
+            } => {
+                let kind = if focused {
+                    WindowEventKind::Focused
+                } else {
+                    WindowEventKind::Unfocused
+                };
+                process_window_event(
+                    kind,
+                    mouse_position,
+                    &view,
+                    &mut cx,
+                    backend.renderer(),
+                    &mut recorder,
+                );
+                // Displays are often plugged in while using another app.
+                update_monitors(
+                    mouse_position,
+                    &view,
+                    &mut cx,
+                    backend.renderer(),
+                    &mut recorder,
+                );
+            }
+            event::Event::WindowEvent {
+                event: WindowEvent::Moved(position),
+                ..
+            } => {
+                let kind = WindowEventKind::Moved {
+                    x: position.x,
+                    y: position.y,
+                };
+                process_window_event(
+                    kind,
+                    mouse_position,
+                    &view,
+                    &mut cx,
+                    backend.renderer(),
+                    &mut recorder,
+                );
+                update_monitors(
+                    mouse_position,
+                    &view,
+                    &mut cx,
+                    backend.renderer(),
+                    &mut recorder,
+                );
             }
             event::Event::WindowEvent {
                 event:
@@ -344,15 +725,121 @@ pub fn rui(view: impl View + 'static) {
                 ..
             } => {
                 // println!("Resizing to {:?}", size);
-                config.width = size.width.max(1);
-                config.height = size.height.max(1);
-                surface.configure(&device, &config);
-                cx.window.request_redraw();
+                let now_minimized = size.width == 0 || size.height == 0;
+                if now_minimized != minimized {
+                    minimized = now_minimized;
+                    let kind = if minimized {
+                        WindowEventKind::Minimized
+                    } else {
+                        WindowEventKind::Restored
+                    };
+                    process_window_event(
+                        kind,
+                        mouse_position,
+                        &view,
+                        &mut cx,
+                        backend.renderer(),
+                        &mut recorder,
+                    );
+                }
+                backend.resize(size);
+                if !minimized {
+                    // Recorded, so replays lay out at the same sizes.
+                    let scale = cx.window().scale_factor() as f32;
+                    let kind = WindowEventKind::Resized {
+                        width: size.width as f32 / scale,
+                        height: size.height as f32 / scale,
+                    };
+                    process_window_event(
+                        kind,
+                        mouse_position,
+                        &view,
+                        &mut cx,
+                        backend.renderer(),
+                        &mut recorder,
+                    );
+                }
+                update_monitors(
+                    mouse_position,
+                    &view,
+                    &mut cx,
+                    backend.renderer(),
+                    &mut recorder,
+                );
+                cx.window().request_redraw();
+            }
+            event::Event::Suspended => {
+                backend.suspend();
+            }
+            event::Event::Resumed => {
+                backend.resume(cx.window());
+                cx.window().request_redraw();
             }
             event::Event::UserEvent(_) => {
                 // println!("received user event");
             }
             event::Event::MainEventsCleared => {
+                if cx.close_requested {
+                    cx.close_requested = false;
+                    let kind = WindowEventKind::CloseRequested;
+                    if process_window_event(
+                        kind,
+                        mouse_position,
+                        &view,
+                        &mut cx,
+                        backend.renderer(),
+                        &mut recorder,
+                    ) {
+                        cx.abort_tasks();
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+                }
+
+                // Create the tray icon, if the views want a new one.
+                #[cfg(feature = "tray")]
+                if cx.tray != tray_request {
+                    tray = cx
+                        .tray
+                        .as_ref()
+                        .and_then(|request| Tray::new(request, _target));
+                    tray_request = cx.tray.clone();
+                }
+
+                // Dispatch events sent with an `Injector`.
+                for payload in take_user_events() {
+                    let event = Event {
+                        kind: EventKind::User(payload),
+                        position: mouse_position,
+                    };
+                    cx.process_root(&view, &event, backend.renderer());
+                }
+
+                // Dispatch any replayed events which are due.
+                while let Some(recorded) = replay_events.front() {
+                    if recorded.time <= replay_start.elapsed() {
+                        if let EventKind::Window(WindowEventKind::Resized { width, height }) =
+                            recorded.event.kind
+                        {
+                            cx.window()
+                                .set_inner_size(LogicalSize::new(width as f64, height as f64));
+                        }
+                        cx.process_root(&view, &recorded.event, backend.renderer());
+                        replay_events.pop_front();
+                    } else {
+                        *control_flow = ControlFlow::WaitUntil(replay_start + recorded.time);
+                        break;
+                    }
+                }
+
+                // Run timers which are due, and wake up for the next.
+                if let Some(due) = run_timers(std::time::Instant::now()) {
+                    *control_flow = match *control_flow {
+                        ControlFlow::WaitUntil(replay) => ControlFlow::WaitUntil(replay.min(due)),
+                        _ => ControlFlow::WaitUntil(due),
+                    };
+                }
+
                 // Application update code.
 
                 // Queue a RedrawRequested event.
@@ -370,7 +857,7 @@ pub fn rui(view: impl View + 'static) {
                         commands = new_commands;
 
                         command_map.clear();
-                        cx.window
+                        cx.window()
                             .set_menu(Some(build_menubar(&commands, &mut command_map)));
                     }
 
@@ -379,6 +866,8 @@ pub fn rui(view: impl View + 'static) {
                     view.gc(cx.root_id, &mut cx, &mut new_map);
                     // println!("collected {} states", cx.state_map.len() - new_map.len());
                     cx.state_map = new_map;
+                    cx.retain_tasks();
+                    cx.layout.collect();
 
                     // Get a new accesskit tree.
                     let mut nodes = vec![];
@@ -397,7 +886,8 @@ pub fn rui(view: impl View + 'static) {
                         // println!("access nodes unchanged");
                     }
 
-                    cx.window.request_redraw();
+                    cx.layout_needed = true;
+                    cx.window().request_redraw();
 
                     cx.dirty.lock().unwrap().dirty = false;
                 }
@@ -411,66 +901,71 @@ pub fn rui(view: impl View + 'static) {
 
                 // println!("RedrawRequested");
 
-                let frame = match surface.get_current_texture() {
-                    Ok(frame) => frame,
-                    Err(_) => {
-                        surface.configure(&device, &config);
-                        surface
-                            .get_current_texture()
-                            .expect("Failed to acquire next surface texture!")
-                    }
-                };
-
-                let window_size = cx.window.inner_size();
-                let scale = cx.window.scale_factor() as f32;
+                let window_size = cx.window().inner_size();
+                let scale = cx.window().scale_factor() as f32;
                 // println!("window_size: {:?}", window_size);
                 let width = window_size.width as f32 / scale;
                 let height = window_size.height as f32 / scale;
 
-                vger.begin(width, height, scale);
-
-                view.layout(cx.root_id, [width, height].into(), &mut cx, &mut vger);
-                view.draw(cx.root_id, &mut cx, &mut vger);
-
-                let texture_view = frame
-                    .texture
-                    .create_view(&wgpu::TextureViewDescriptor::default());
-
-                let desc = wgpu::RenderPassDescriptor {
-                    label: None,
-                    color_attachments: &[wgpu::RenderPassColorAttachment {
-                        view: &texture_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: true,
-                        },
-                    }],
-                    depth_stencil_attachment: None,
-                };
+                let frame_start = std::time::Instant::now();
+                let allocations_start = allocation_count();
+
+                cx.set_env(SafeAreaInsets::from_window(cx.window()));
+                backend.frame(width, height, scale, &mut |vger| {
+                    // Solid shapes are drawn grouped by paint.
+                    let vger = &mut BatchingRenderer::new(vger);
+                    // Only laid out again when the window has resized, or
+                    // something has changed since the last frame.
+                    cx.layout_if_needed(&view, [width, height].into(), vger);
+                    if cx.inspector.enabled {
+                        // Views note what they show as they're drawn.
+                        cx.begin_query();
+                        view.draw(cx.root_id, &mut cx, vger);
+                        let query = cx.end_query(&view);
+                        let lines = inspector_lines(&cx, &query);
+                        draw_inspector_panel(&lines, [width, height].into(), vger);
+                    } else {
+                        view.draw(cx.root_id, &mut cx, vger);
+                    }
+                });
 
-                vger.encode(&device, &desc, &queue);
+                cx.inspector.frame_allocations = allocation_count() - allocations_start;
 
-                frame.present();
+                if print_frame_time {
+                    let allocations = if allocation_count() > 0 {
+                        format!(", {} allocations", cx.inspector.frame_allocations)
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "frame time: {:?}, {} paints{}",
+                        frame_start.elapsed(),
+                        backend.paint_count(),
+                        allocations
+                    );
+                }
             }
             event::Event::WindowEvent {
-                event: WindowEvent::MouseInput { state, .. },
+                event: WindowEvent::MouseInput { state, button, .. },
                 ..
             } => {
+                cx.mouse_button = button;
                 match state {
                     ElementState::Pressed => {
                         let event = view::Event {
                             kind: EventKind::TouchBegin { id: 0 },
                             position: mouse_position,
                         };
-                        view.process(&event, cx.root_id, &mut cx, &mut vger)
+                        recorder.record(&event);
+                        cx.process_root(&view, &event, backend.renderer())
                     }
                     ElementState::Released => {
                         let event = view::Event {
                             kind: EventKind::TouchEnd { id: 0 },
                             position: mouse_position,
                         };
-                        view.process(&event, cx.root_id, &mut cx, &mut vger)
+                        recorder.record(&event);
+                        cx.process_root(&view, &event, backend.renderer())
                     }
                     _ => {}
                 };
@@ -479,28 +974,97 @@ pub fn rui(view: impl View + 'static) {
                 event: WindowEvent::CursorMoved { position, .. },
                 ..
             } => {
-                let scale = cx.window.scale_factor() as f32;
+                let scale = cx.window().scale_factor() as f32;
                 mouse_position = [
                     position.x as f32 / scale,
-                    (config.height as f32 - position.y as f32) / scale,
+                    (cx.window().inner_size().height as f32 - position.y as f32) / scale,
                 ]
                 .into();
                 let event = view::Event {
                     kind: EventKind::TouchMove { id: 0 },
                     position: mouse_position,
                 };
-                view.process(&event, cx.root_id, &mut cx, &mut vger)
+                recorder.record(&event);
+                cx.cursor = CursorIcon::Default;
+                cx.process_root(&view, &event, backend.renderer());
+                if cx.cursor != cursor {
+                    cursor = cx.cursor;
+                    cx.window().set_cursor_icon(cursor);
+                }
+
+                if cx.inspector.enabled {
+                    let hovered =
+                        view.hittest(cx.root_id, mouse_position, &mut cx, backend.renderer());
+                    if hovered != cx.inspector.hovered {
+                        cx.inspector.hovered = hovered;
+                        cx.window().request_redraw();
+                    }
+                }
+            }
+            event::Event::WindowEvent {
+                event: WindowEvent::MouseWheel { delta, .. },
+                ..
+            } => {
+                let scale = cx.window().scale_factor() as f32;
+                let event = view::Event {
+                    kind: EventKind::Scroll {
+                        delta: scroll_delta(delta, scale),
+                    },
+                    position: mouse_position,
+                };
+                recorder.record(&event);
+                cx.process_root(&view, &event, backend.renderer());
+            }
+            event::Event::WindowEvent {
+                event: WindowEvent::Touch(touch),
+                ..
+            } => {
+                let begin = touch.phase == TouchPhase::Started;
+                if let Some(slot) = touch_slot(&mut touch_slots, touch.id, begin) {
+                    let kind = match touch.phase {
+                        TouchPhase::Started => EventKind::TouchBegin { id: slot },
+                        TouchPhase::Moved => EventKind::TouchMove { id: slot },
+                        TouchPhase::Ended => EventKind::TouchEnd { id: slot },
+                        TouchPhase::Cancelled => EventKind::TouchCancel { id: slot },
+                    };
+                    if let TouchPhase::Ended | TouchPhase::Cancelled = touch.phase {
+                        touch_slots[slot] = None;
+                    }
+
+                    let scale = cx.window().scale_factor() as f32;
+                    let event = view::Event {
+                        kind,
+                        position: [
+                            touch.location.x as f32 / scale,
+                            (cx.window().inner_size().height as f32 - touch.location.y as f32)
+                                / scale,
+                        ]
+                        .into(),
+                    };
+                    recorder.record(&event);
+                    cx.process_root(&view, &event, backend.renderer());
+                }
             }
             event::Event::WindowEvent {
                 event: WindowEvent::KeyboardInput { event, .. },
                 ..
             } => {
-                if event.state == ElementState::Pressed {
+                if event.state == ElementState::Pressed
+                    && Inspector::is_toggle(event.physical_key, modifiers)
+                {
+                    cx.inspector.enabled = !cx.inspector.enabled;
+                    cx.window().request_redraw();
+                } else if event.state == ElementState::Pressed {
                     let event = view::Event {
                         kind: EventKind::Key(event.logical_key, modifiers),
                         position: mouse_position,
                     };
-                    view.process(&event, cx.root_id, &mut cx, &mut vger)
+                    if cx.secure_focus() {
+                        recorder.record_secret(&event);
+                    } else {
+                        recorder.record(&event);
+                    }
+                    cx.process_root(&view, &event, backend.renderer())
                 }
             }
             event::Event::WindowEvent {
@@ -508,18 +1072,28 @@ pub fn rui(view: impl View + 'static) {
                 ..
             } => {
                 modifiers = mods;
+                cx.modifiers = mods;
                 // println!("modifiers changed: {:?}", modifiers);
             }
             event::Event::MenuEvent { menu_id, .. } => {
                 //println!("menu event");
 
-                if let Some(command) = command_map.get(&menu_id) {
+                #[cfg(feature = "tray")]
+                let command = match tray.as_ref().and_then(|t| t.menu_event(menu_id, &mut cx)) {
+                    Some(command) => command,
+                    None => command_map.get(&menu_id).cloned(),
+                };
+                #[cfg(not(feature = "tray"))]
+                let command = command_map.get(&menu_id).cloned();
+
+                if let Some(command) = command {
                     //println!("found command {:?}", command);
                     let event = view::Event {
-                        kind: EventKind::Command(command.clone()),
+                        kind: EventKind::Command(command),
                         position: mouse_position,
                     };
-                    view.process(&event, cx.root_id, &mut cx, &mut vger)
+                    recorder.record(&event);
+                    cx.process_root(&view, &event, backend.renderer())
                 }
             }
             _ => (),
@@ -0,0 +1,178 @@
+use crate::*;
+
+/// Space between a link's baseline and its underline.
+const UNDERLINE_OFFSET: f32 = 2.0;
+
+/// How a link was clicked, for `on_open`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// An ordinary click.
+    Default,
+
+    /// A middle click, which browsers take to mean a new tab or window.
+    NewWindow,
+}
+
+/// Opens a URL with the system's default handler, usually a web browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    use std::process::Command;
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = Command::new("xdg-open");
+
+    command.arg(url).spawn().map(|_| ())
+}
+
+type OpenCallback = Box<dyn Fn(&str, LinkTarget)>;
+
+/// Struct for `link`.
+pub struct Link {
+    label: String,
+    url: String,
+    size: u32,
+    on_open: Option<OpenCallback>,
+}
+
+impl Link {
+    pub fn font_size(self, size: u32) -> Self {
+        Self { size, ..self }
+    }
+
+    /// Calls `f` with the URL instead of opening it in the browser.
+    pub fn on_open(self, f: impl Fn(&str, LinkTarget) + 'static) -> Self {
+        Self {
+            on_open: Some(Box::new(f)),
+            ..self
+        }
+    }
+
+    fn open(&self, target: LinkTarget) {
+        match &self.on_open {
+            Some(f) => f(&self.url, target),
+            #[cfg(not(target_arch = "wasm32"))]
+            None => {
+                if let Err(err) = open_url(&self.url) {
+                    println!("error opening {:?}: {:?}", self.url, err);
+                }
+            }
+            #[cfg(target_arch = "wasm32")]
+            None => println!("opening links isn't supported on the web yet"),
+        }
+    }
+}
+
+impl View for Link {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("link({:?}, {:?})", self.label, self.url);
+    }
+
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let inside = self.hittest(vid, event.position, cx, vger).is_some();
+        match &event.kind {
+            EventKind::TouchBegin { id } if inside => cx.touches[*id] = vid,
+            EventKind::TouchMove { id: 0 } if inside => cx.set_cursor(CursorIcon::Hand),
+            EventKind::TouchEnd { id } if cx.touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
+                if inside {
+                    let target = if *id == 0 && cx.mouse_button == MouseButton::Middle {
+                        LinkTarget::NewWindow
+                    } else {
+                        LinkTarget::Default
+                    };
+                    self.open(target);
+                }
+            }
+            EventKind::TouchCancel { id } if cx.touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default()
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let origin = vger.text_bounds(&self.label, self.size, None).origin;
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        vger.save();
+        vger.translate([-origin.x, -origin.y].into());
+        vger.text(&self.label, self.size, LINK_COLOR, None);
+        vger.restore();
+
+        // The baseline is at -origin.y, above any descenders.
+        let y = (-origin.y - UNDERLINE_OFFSET).max(0.5);
+        let paint = vger.color_paint(LINK_COLOR);
+        vger.stroke_segment([0.0, y].into(), [rect.width(), y].into(), 1.0, paint);
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = vger.text_bounds(&self.label, self.size, None).size;
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Link));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for Link {}
+
+/// Underlined text which opens `url` in the browser when clicked.
+pub fn link(label: &str, url: &str) -> Link {
+    Link {
+        label: label.into(),
+        url: url.into(),
+        size: Text::DEFAULT_SIZE,
+        on_open: None,
+    }
+}
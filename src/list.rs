@@ -1,5 +1,6 @@
 use crate::*;
 use std::hash::Hash;
+use std::time::{Duration, Instant};
 
 pub struct List<ID, F> {
     ids: Vec<ID>,
@@ -20,14 +21,28 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
-        for child in &self.ids {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let request = ScrollRequest::from_event(event);
+        for (index, child) in self.ids.iter().enumerate() {
             let child_id = id.child(child);
-            let offset = cx
-                .layout
-                .entry(child_id)
-                .or_insert(LayoutBox::default())
-                .offset;
+            let layout_box = *cx.layout.entry(child_id).or_default();
+            let offset = layout_box.offset;
+
+            if let Some(request) = request {
+                let target = match request.target {
+                    ScrollTarget::Index(i) => i == index,
+                    ScrollTarget::Id(key) => key == scroll_key(child),
+                    _ => false,
+                };
+                if target {
+                    request.answer(event, layout_box.rect.translate(offset));
+                }
+            }
+
+            // Once a row has handled the event, the rest don't see it.
+            if cx.event_consumed() {
+                continue;
+            }
 
             let mut local_event = event.clone();
             local_event.position -= offset;
@@ -36,43 +51,72 @@ where
         }
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         for child in &self.ids {
             let child_id = id.child(child);
-            let offset = cx
-                .layout
-                .entry(child_id)
-                .or_insert(LayoutBox::default())
-                .offset;
+            let layout_box = *cx.layout.entry(child_id).or_default();
 
             vger.save();
 
-            vger.translate(offset);
+            vger.translate(layout_box.offset);
 
             ((self.func)(child)).draw(child_id, cx, vger);
 
+            if DEBUG_LAYOUT || cx.inspector.enabled {
+                let paint = vger.color_paint(CONTROL_BACKGROUND);
+                vger.stroke_rect(
+                    layout_box.rect.min(),
+                    layout_box.rect.max(),
+                    0.0,
+                    1.0,
+                    paint,
+                );
+            }
+
             vger.restore();
         }
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let sz = constraints.max;
         let n = self.ids.len() as f32;
         let proposed_child_size = LocalSize::new(sz.width, sz.height / n);
 
-        let mut y = sz.height;
         let mut height_sum = 0.0;
+        let mut sizes = Vec::with_capacity(self.ids.len());
         for child in &self.ids {
             let child_id = id.child(child);
-            let child_size = ((self.func)(child)).layout(child_id, proposed_child_size, cx, vger);
+            let child_size =
+                ((self.func)(child)).layout(child_id, proposed_child_size.into(), cx, vger);
+            height_sum += child_size.height;
+            sizes.push(child_size);
+        }
 
+        // Unbounded, as in a scroll view, the list is as tall as its rows.
+        let mut y = if constraints.has_bounded_height() {
+            sz.height
+        } else {
+            height_sum
+        };
+        let width = if constraints.has_bounded_width() {
+            sz.width
+        } else {
+            sizes.iter().map(|s| s.width).fold(0.0, f32::max)
+        };
+        for (child, child_size) in self.ids.iter().zip(sizes) {
             y -= child_size.height;
-            cx.layout.entry(child_id).or_default().offset =
-                [(sz.width - child_size.width) / 2.0, y].into();
-
-            height_sum += child_size.height;
+            let layout_box = cx.layout.entry(id.child(child)).or_default();
+            layout_box.rect = LocalRect::new(LocalPoint::origin(), child_size);
+            layout_box.offset = [(width - child_size.width) / 2.0, y].into();
         }
 
-        LocalSize::new(sz.width, height_sum)
+        constraints.constrain(LocalSize::new(width, height_sum))
     }
 
     fn hittest(
@@ -80,7 +124,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         let mut hit = None;
         for child in &self.ids {
@@ -150,3 +194,700 @@ impl<ID, F> private::Sealed for List<ID, F> {}
 pub fn list<ID: Hash, V: View, F: Fn(&ID) -> V + 'static>(ids: Vec<ID>, f: F) -> List<ID, F> {
     List { ids, func: f }
 }
+
+impl<ID, F> List<ID, F> {
+    /// Lets rows be selected by clicking them, or with the keyboard once
+    /// the list has the focus, keeping the selected row's id in `binding`.
+    /// The arrow keys, Page Up, Page Down, Home and End move the selection.
+    pub fn selection<B: Binding<Option<ID>>>(self, binding: B) -> SelectList<ID, F, B> {
+        SelectList {
+            list: self,
+            selection: binding,
+            on_activate: None,
+            label: None,
+        }
+    }
+}
+
+/// How long type-select waits for the next key before starting over.
+const TYPE_SELECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+type ActivateCallback<ID> = Box<dyn Fn(&ID)>;
+type RowLabel<ID> = Box<dyn Fn(&ID) -> String>;
+
+/// What's been typed for type-select.
+#[derive(Clone, Default)]
+struct TypeSelect {
+    typed: String,
+    last_key: Option<Instant>,
+}
+
+/// Struct for `List::selection`.
+pub struct SelectList<ID, F, B> {
+    list: List<ID, F>,
+    selection: B,
+    on_activate: Option<ActivateCallback<ID>>,
+    label: Option<RowLabel<ID>>,
+}
+
+impl<ID, F, B> SelectList<ID, F, B> {
+    /// Calls `f` with the selected row when Enter is pressed.
+    pub fn on_activate(mut self, f: impl Fn(&ID) + 'static) -> Self {
+        self.on_activate = Some(Box::new(f));
+        self
+    }
+
+    /// Selects the row whose label, from `label`, starts with what's typed.
+    pub fn type_select(mut self, label: impl Fn(&ID) -> String + 'static) -> Self {
+        self.label = Some(Box::new(label));
+        self
+    }
+}
+
+impl<ID, V, F, B> SelectList<ID, F, B>
+where
+    ID: Hash + Clone + PartialEq + 'static,
+    V: View,
+    F: Fn(&ID) -> V,
+    B: Binding<Option<ID>>,
+{
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selection.get()?;
+        self.list.ids.iter().position(|row| *row == selected)
+    }
+
+    /// Where the row at `index` is, across the whole list.
+    fn row_rect(&self, id: ViewID, cx: &mut Context, index: usize) -> LocalRect {
+        let width = cx.layout.entry(id).or_default().rect.width();
+        let layout_box = *cx
+            .layout
+            .entry(id.child(&self.list.ids[index]))
+            .or_default();
+        let row = layout_box.rect.translate(layout_box.offset);
+        LocalRect::new([0.0, row.min_y()].into(), [width, row.height()].into())
+    }
+
+    /// Selects the row at `index`, scrolling it into sight.
+    fn select(&self, index: usize, cx: &mut Context) {
+        let row = self.list.ids[index].clone();
+        cx.publish(ScrollTo::id(&row, ScrollAnchor::Nearest).immediate());
+        self.selection.set(Some(row));
+    }
+
+    /// The row a page up or down from the row at `from`. A page is what
+    /// the scroll view the list is in shows, or the whole list.
+    fn page(&self, id: ViewID, cx: &mut Context, from: usize, down: bool) -> usize {
+        let page = match ScrollProbe::noted(id, cx) {
+            Some(viewport) => viewport.rect.height(),
+            None => cx.layout.entry(id).or_default().rect.height(),
+        };
+        let mut index = from;
+        let mut travelled = 0.0;
+        loop {
+            let next = if down {
+                Some(index + 1).filter(|&next| next < self.list.ids.len())
+            } else {
+                index.checked_sub(1)
+            };
+            let next = match next {
+                Some(next) => next,
+                None => return index,
+            };
+            travelled += self.row_rect(id, cx, next).height();
+            if travelled > page && index != from {
+                return index;
+            }
+            index = next;
+        }
+    }
+
+    /// The row type-select picks after `key` is typed. Typing the same
+    /// letter again moves on to the next row starting with it.
+    fn type_select_row(&self, id: ViewID, cx: &mut Context, key: &str) -> Option<usize> {
+        let label = self.label.as_ref()?;
+        let state = cx.get_state(id, TypeSelect::default);
+        let now = Instant::now();
+        let typed = state.with_mut(|s| {
+            let stale = match s.last_key {
+                Some(last) => now - last > TYPE_SELECT_TIMEOUT,
+                None => true,
+            };
+            if stale {
+                s.typed.clear();
+            }
+            s.typed.push_str(&key.to_lowercase());
+            s.last_key = Some(now);
+            s.typed.clone()
+        });
+
+        let first = typed.chars().next()?;
+        let repeated = typed.chars().all(|c| c == first);
+        let prefix = if repeated { first.to_string() } else { typed };
+        let n = self.list.ids.len();
+        let start = match self.selected_index() {
+            Some(selected) if repeated => selected + 1,
+            Some(selected) => selected,
+            None => 0,
+        };
+        (0..n)
+            .map(|i| (start + i) % n)
+            .find(|&i| label(&self.list.ids[i]).to_lowercase().starts_with(&prefix))
+    }
+
+    fn key(&self, key: &KeyPress, mods: ModifiersState, id: ViewID, cx: &mut Context) {
+        let n = self.list.ids.len();
+        if n == 0 {
+            return;
+        }
+        let current = self.selected_index();
+        let target = match key {
+            KeyPress::ArrowDown => current.map_or(0, |i| (i + 1).min(n - 1)),
+            KeyPress::ArrowUp => current.map_or(n - 1, |i| i.saturating_sub(1)),
+            KeyPress::PageDown => self.page(id, cx, current.unwrap_or(0), true),
+            KeyPress::PageUp => self.page(id, cx, current.unwrap_or(0), false),
+            KeyPress::Home => 0,
+            KeyPress::End => n - 1,
+            KeyPress::Enter => {
+                if let (Some(f), Some(row)) = (&self.on_activate, self.selection.get()) {
+                    f(&row);
+                }
+                cx.consume_event();
+                return;
+            }
+            KeyPress::Escape => {
+                cx.focused_id = None;
+                return;
+            }
+            KeyPress::Character(c) if !mods.control_key() && !mods.super_key() => {
+                match self.type_select_row(id, cx, c) {
+                    Some(index) => index,
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+        if Some(target) != current {
+            self.select(target, cx);
+        }
+        cx.consume_event();
+    }
+}
+
+impl<ID, V, F, B> View for SelectList<ID, F, B>
+where
+    ID: Hash + Clone + PartialEq + 'static,
+    V: View,
+    F: Fn(&ID) -> V,
+    B: Binding<Option<ID>>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("selection({:?})", self.selected_index());
+        self.list.print(id, cx);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        ScrollProbe::note(event, id, cx);
+        self.list.process(event, id, cx, vger);
+
+        match &event.kind {
+            EventKind::TouchBegin { .. } if !cx.event_consumed() => {
+                let n = self.list.ids.len();
+                if let Some(index) =
+                    (0..n).find(|&i| self.row_rect(id, cx, i).contains(event.position))
+                {
+                    cx.focused_id = Some(id);
+                    self.select(index, cx);
+                }
+            }
+            EventKind::Key(key, mods) if cx.focused_id == Some(id) => self.key(key, *mods, id, cx),
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let Some(index) = self.selected_index() {
+            let rect = self.row_rect(id, cx, index);
+            let color = if cx.focused_id == Some(id) {
+                AZURE_HIGHLIGHT_DARK
+            } else {
+                CONTROL_BACKGROUND
+            };
+            let paint = vger.color_paint(color);
+            vger.fill_rect(rect, 0.0, paint);
+        }
+        self.list.draw(id, cx, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.list.layout(id, constraints, cx, vger);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        self.list
+            .hittest(id, pt, cx, vger)
+            .or_else(|| if rect.contains(pt) { Some(id) } else { None })
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.list.commands(id, cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = cx.get_state(id, TypeSelect::default);
+        map.insert(id, Box::new(state));
+        self.list.gc(id, cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.list.access(id, cx, nodes)
+    }
+}
+
+impl<ID, F, B> private::Sealed for SelectList<ID, F, B> {}
+
+/// A section of a `sectioned_list`: a header, and the rows under it.
+pub struct Section<S, ID> {
+    pub header: S,
+    pub rows: Vec<ID>,
+}
+
+pub fn section<S, ID>(header: S, rows: Vec<ID>) -> Section<S, ID> {
+    Section { header, rows }
+}
+
+/// Struct for `sectioned_list`.
+pub struct SectionedList<S, ID, HF, F> {
+    sections: Vec<Section<S, ID>>,
+    header_func: HF,
+    func: F,
+    sticky: bool,
+}
+
+impl<S, ID, HF, F> SectionedList<S, ID, HF, F> {
+    /// Pins each section's header to the top of the scroll view the list
+    /// is in while the section is in sight, so the next section's header
+    /// pushes it off as it comes up.
+    pub fn sticky_headers(self) -> Self {
+        Self {
+            sticky: true,
+            ..self
+        }
+    }
+}
+
+impl<S, ID, HV, V, HF, F> SectionedList<S, ID, HF, F>
+where
+    S: Hash,
+    ID: Hash,
+    HV: View,
+    V: View,
+    HF: Fn(&S) -> HV,
+    F: Fn(&ID) -> V,
+{
+    fn header_id(id: ViewID, header: &S) -> ViewID {
+        id.child(&("header", header))
+    }
+
+    /// Where a whole section is, from the top of its header to the bottom
+    /// of its last row.
+    fn section_id(id: ViewID, header: &S) -> ViewID {
+        id.child(&("section", header))
+    }
+
+    fn each_header(&self, id: ViewID, mut f: impl FnMut(ViewID, &dyn View)) {
+        for section in &self.sections {
+            let header = (self.header_func)(&section.header);
+            f(Self::header_id(id, &section.header), &header);
+        }
+    }
+
+    fn each_row(&self, id: ViewID, mut f: impl FnMut(ViewID, &dyn View)) {
+        for section in &self.sections {
+            for row in &section.rows {
+                f(id.child(row), &(self.func)(row));
+            }
+        }
+    }
+
+    /// Answers a `ScrollRequest` for a section or a row. With sticky
+    /// headers, rows are scrolled to below their pinned header.
+    fn answer(&self, request: &ScrollRequest, event: &Event, id: ViewID, cx: &mut Context) {
+        let mut index = 0;
+        for section in &self.sections {
+            let header_height = if self.sticky {
+                cx.layout
+                    .entry(Self::header_id(id, &section.header))
+                    .or_default()
+                    .rect
+                    .height()
+            } else {
+                0.0
+            };
+            if request.target == ScrollTarget::Id(scroll_key(&section.header)) {
+                let rect = cx
+                    .layout
+                    .entry(Self::section_id(id, &section.header))
+                    .or_default()
+                    .rect;
+                request.answer(event, rect);
+            }
+            for row in &section.rows {
+                let target = match request.target {
+                    ScrollTarget::Index(i) => i == index,
+                    ScrollTarget::Id(key) => key == scroll_key(row),
+                    _ => false,
+                };
+                if target {
+                    let layout_box = *cx.layout.entry(id.child(row)).or_default();
+                    let mut rect = layout_box.rect.translate(layout_box.offset);
+                    rect.size.height += header_height;
+                    request.answer(event, rect);
+                }
+                index += 1;
+            }
+        }
+    }
+}
+
+impl<S, ID, HV, V, HF, F> View for SectionedList<S, ID, HF, F>
+where
+    S: Hash,
+    ID: Hash,
+    HV: View,
+    V: View,
+    HF: Fn(&S) -> HV,
+    F: Fn(&ID) -> V,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("SectionedList {{");
+        for section in &self.sections {
+            (self.header_func)(&section.header).print(Self::header_id(id, &section.header), cx);
+            for row in &section.rows {
+                ((self.func)(row)).print(id.child(row), cx);
+            }
+        }
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        ScrollProbe::note(event, id, cx);
+        if let Some(request) = ScrollRequest::from_event(event) {
+            self.answer(request, event, id, cx);
+        }
+
+        // Headers are drawn over the rows, so they get events first.
+        let mut process = |child_id: ViewID, child: &dyn View| {
+            if cx.event_consumed() {
+                return;
+            }
+            let offset = cx.layout.entry(child_id).or_default().offset;
+            let mut local_event = event.clone();
+            local_event.position -= offset;
+            child.process(&local_event, child_id, cx, vger);
+        };
+        self.each_header(id, &mut process);
+        self.each_row(id, &mut process);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if self.sticky {
+            // The top of what's shown, in the list's coordinates.
+            let origin = ScrollProbe::noted(id, cx).map_or(LocalOffset::zero(), |b| b.offset);
+            let top = cx
+                .scroll_viewport
+                .map(|viewport| viewport.max_y() - origin.y);
+            for section in &self.sections {
+                let bounds = cx
+                    .layout
+                    .entry(Self::section_id(id, &section.header))
+                    .or_default()
+                    .rect;
+                let layout_box = cx
+                    .layout
+                    .entry(Self::header_id(id, &section.header))
+                    .or_default();
+                let height = layout_box.rect.height();
+                let unpinned = bounds.max_y() - height;
+                // Moved where it's drawn, so events find it there too.
+                layout_box.offset.y = match top {
+                    Some(top) => (top - height).min(unpinned).max(bounds.min_y()),
+                    None => unpinned,
+                };
+            }
+        }
+
+        let mut draw = |child_id: ViewID, child: &dyn View| {
+            let offset = cx.layout.entry(child_id).or_default().offset;
+            vger.save();
+            vger.translate(offset);
+            child.draw(child_id, cx, vger);
+            vger.restore();
+        };
+        self.each_row(id, &mut draw);
+        self.each_header(id, &mut draw);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let sz = constraints.max;
+        let n = self
+            .sections
+            .iter()
+            .map(|section| section.rows.len() + 1)
+            .sum::<usize>() as f32;
+        let proposed_child_size = LocalSize::new(sz.width, sz.height / n);
+
+        // Headers and rows, top to bottom.
+        let mut children = vec![];
+        let mut height_sum = 0.0;
+        let mut width: f32 = 0.0;
+        let mut layout = |child_id: ViewID, child: &dyn View| {
+            let child_size = child.layout(child_id, proposed_child_size.into(), cx, vger);
+            height_sum += child_size.height;
+            width = width.max(child_size.width);
+            children.push((child_id, child_size));
+        };
+        for section in &self.sections {
+            let header = (self.header_func)(&section.header);
+            layout(Self::header_id(id, &section.header), &header);
+            for row in &section.rows {
+                layout(id.child(row), &(self.func)(row));
+            }
+        }
+
+        let mut y = if constraints.has_bounded_height() {
+            sz.height
+        } else {
+            height_sum
+        };
+        if constraints.has_bounded_width() {
+            width = sz.width;
+        }
+        let mut children = children.into_iter();
+        for section in &self.sections {
+            let top = y;
+            for (child_id, child_size) in children.by_ref().take(section.rows.len() + 1) {
+                y -= child_size.height;
+                let layout_box = cx.layout.entry(child_id).or_default();
+                layout_box.rect = LocalRect::new(LocalPoint::origin(), child_size);
+                layout_box.offset = [(width - child_size.width) / 2.0, y].into();
+            }
+            cx.layout.insert(
+                Self::section_id(id, &section.header),
+                LayoutBox {
+                    rect: LocalRect::new([0.0, y].into(), [width, top - y].into()),
+                    offset: LocalOffset::zero(),
+                },
+            );
+        }
+
+        constraints.constrain(LocalSize::new(width, height_sum))
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        // Later hits are on top.
+        let mut hit = None;
+        let mut hittest = |child_id: ViewID, child: &dyn View| {
+            let offset = cx.layout.entry(child_id).or_default().offset;
+            if let Some(h) = child.hittest(child_id, pt - offset, cx, vger) {
+                hit = Some(h)
+            }
+        };
+        self.each_row(id, &mut hittest);
+        self.each_header(id, &mut hittest);
+        hit
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.each_header(id, |child_id, child| child.commands(child_id, cx, cmds));
+        self.each_row(id, |child_id, child| child.commands(child_id, cx, cmds));
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.each_header(id, |child_id, child| child.gc(child_id, cx, map));
+        self.each_row(id, |child_id, child| child.gc(child_id, cx, map));
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let mut node = accesskit::Node::new(id.access_id(), accesskit::Role::List);
+        for section in &self.sections {
+            let header = (self.header_func)(&section.header);
+            if let Some(i) = header.access(Self::header_id(id, &section.header), cx, nodes) {
+                node.children.push(i)
+            }
+            for row in &section.rows {
+                if let Some(i) = ((self.func)(row)).access(id.child(row), cx, nodes) {
+                    node.children.push(i)
+                }
+            }
+        }
+        nodes.push(node);
+        Some(id.access_id())
+    }
+}
+
+impl<S, ID, HF, F> private::Sealed for SectionedList<S, ID, HF, F> {}
+
+/// A `list` in sections, each with a header made by `header` and rows
+/// made by `f`. Call `sticky_headers` to keep headers in sight as the list
+/// scrolls. `ScrollTo::id` scrolls to a section by its header.
+pub fn sectioned_list<S, ID, HV, V, HF, F>(
+    sections: Vec<Section<S, ID>>,
+    header: HF,
+    f: F,
+) -> SectionedList<S, ID, HF, F>
+where
+    S: Hash,
+    ID: Hash,
+    HV: View,
+    V: View,
+    HF: Fn(&S) -> HV + 'static,
+    F: Fn(&ID) -> V + 'static,
+{
+    SectionedList {
+        sections,
+        header_func: header,
+        func: f,
+        sticky: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_sticky_headers() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let offset = State::new(LocalOffset::zero(), cx.dirty.clone());
+        let sections = ["a", "b", "c"]
+            .iter()
+            .map(|&name| section(name, (0..5).map(|i| format!("{}{}", name, i)).collect()))
+            .collect();
+        let ui = scroll_view(
+            sectioned_list(
+                sections,
+                |_| rectangle().size([100.0, 10.0]),
+                |_| rectangle().size([100.0, 20.0]),
+            )
+            .sticky_headers(),
+        )
+        .offset(offset.clone());
+        cx.layout_root(&ui, size, &mut vger);
+
+        let list_id = cx.root_id.child(&0);
+        let mut header_y = |name: &str, scrolled: f32, cx: &mut Context| {
+            offset.set(LocalOffset::new(0.0, scrolled));
+            ui.draw(cx.root_id, cx, &mut vger);
+            cx.layout[&list_id.child(&("header", name))].offset.y
+        };
+        // Sections are 110 high, from 330 at the top.
+        assert_eq!(header_y("a", 0.0, &mut cx), 320.0);
+        assert_eq!(header_y("a", 50.0, &mut cx), 270.0);
+        assert_eq!(header_y("a", 105.0, &mut cx), 220.0);
+        assert_eq!(header_y("b", 105.0, &mut cx), 210.0);
+
+        cx.publish(ScrollTo::index(5, ScrollAnchor::Start).immediate());
+        cx.deliver_messages(&ui, &mut vger);
+        assert_eq!(offset.get().y, 110.0);
+    }
+
+    #[test]
+    fn test_keyboard_selection() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let selected = State::new(None, cx.dirty.clone());
+        let activated = Rc::new(RefCell::new(vec![]));
+        let activated2 = activated.clone();
+        let names = [
+            "apple",
+            "apricot",
+            "banana",
+            "blueberry",
+            "cherry",
+            "date",
+            "fig",
+            "grape",
+        ];
+        let ui = list(names.to_vec(), |_| rectangle().size([100.0, 20.0]))
+            .selection(selected.clone())
+            .on_activate(move |name| activated2.borrow_mut().push(*name))
+            .type_select(|name| name.to_string());
+        cx.layout_root(&ui, size, &mut vger);
+
+        let click = Event {
+            kind: EventKind::TouchBegin { id: 0 },
+            position: [50.0, 90.0].into(),
+        };
+        cx.process_root(&ui, &click, &mut vger);
+        assert_eq!(selected.get(), Some("apple"));
+
+        let mut press = |key: KeyPress| {
+            let event = Event {
+                kind: EventKind::Key(key, ModifiersState::empty()),
+                position: LocalPoint::zero(),
+            };
+            cx.process_root(&ui, &event, &mut vger);
+            selected.get().unwrap()
+        };
+        assert_eq!(press(KeyPress::ArrowDown), "apricot");
+        assert_eq!(press(KeyPress::End), "grape");
+        assert_eq!(press(KeyPress::ArrowDown), "grape");
+        assert_eq!(press(KeyPress::Home), "apple");
+        assert_eq!(press(KeyPress::PageDown), "date");
+        assert_eq!(press(KeyPress::PageUp), "apple");
+        assert_eq!(press(KeyPress::Character("b")), "banana");
+        assert_eq!(press(KeyPress::Character("b")), "blueberry");
+        assert_eq!(press(KeyPress::Enter), "blueberry");
+        assert_eq!(*activated.borrow(), vec!["blueberry"]);
+        press(KeyPress::Escape);
+        assert_eq!(press(KeyPress::ArrowUp), "blueberry");
+    }
+}
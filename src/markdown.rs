@@ -0,0 +1,511 @@
+use crate::*;
+use pulldown_cmark::{Event as MdEvent, HeadingLevel, Parser, Tag};
+use std::rc::Rc;
+
+const HEADING_SIZES: [u32; 6] = [32, 26, 22, 20, 18, 18];
+const CODE_SIZE: u32 = 15;
+const LIST_INDENT: f32 = 24.0;
+const BLOCK_SPACING: f32 = 8.0;
+const CODE_PADDING: f32 = 8.0;
+const LINE_SPACING: f32 = 1.4;
+const EMPHASIS_COLOR: Color = Color::hex_const("#FFFFFF");
+
+/// Text with one style, within a block of markdown.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Span {
+    text: String,
+    strong: bool,
+    emphasis: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+/// Markdown is drawn as a column of these.
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    Heading(usize, Vec<Span>),
+    Paragraph(Vec<Span>),
+
+    /// A paragraph in a list. Only the first paragraph of an item has a
+    /// marker.
+    Item {
+        depth: usize,
+        marker: String,
+        spans: Vec<Span>,
+    },
+    Code(String),
+    Image {
+        url: String,
+        alt: String,
+    },
+    Rule,
+}
+
+fn heading_level(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Parses markdown into blocks. HTML is ignored.
+fn parse(source: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut spans: Vec<Span> = vec![];
+    let mut style = Span::default();
+
+    // The next number of each list we're in, or None if it's bulleted.
+    let mut lists: Vec<Option<u64>> = vec![];
+    let mut marker: Option<String> = None;
+    let mut code: Option<String> = None;
+    let mut image: Option<(String, String)> = None;
+
+    let flush = |blocks: &mut Vec<Block>,
+                 spans: &mut Vec<Span>,
+                 marker: &mut Option<String>,
+                 lists: &[Option<u64>]| {
+        if spans.is_empty() {
+            return;
+        }
+        let spans = std::mem::take(spans);
+        blocks.push(if lists.is_empty() {
+            Block::Paragraph(spans)
+        } else {
+            Block::Item {
+                depth: lists.len() - 1,
+                marker: marker.take().unwrap_or_default(),
+                spans,
+            }
+        });
+    };
+
+    for event in Parser::new(source) {
+        match event {
+            MdEvent::Start(Tag::Emphasis) => style.emphasis = true,
+            MdEvent::End(Tag::Emphasis) => style.emphasis = false,
+            MdEvent::Start(Tag::Strong) => style.strong = true,
+            MdEvent::End(Tag::Strong) => style.strong = false,
+            MdEvent::Start(Tag::Link(_, url, _)) => style.link = Some(url.to_string()),
+            MdEvent::End(Tag::Link(..)) => style.link = None,
+            MdEvent::Start(Tag::Image(_, url, _)) => image = Some((url.to_string(), String::new())),
+            MdEvent::End(Tag::Image(..)) => {
+                if let Some((url, alt)) = image.take() {
+                    flush(&mut blocks, &mut spans, &mut marker, &lists);
+                    blocks.push(Block::Image { url, alt });
+                }
+            }
+            MdEvent::Start(Tag::CodeBlock(_)) => code = Some(String::new()),
+            MdEvent::End(Tag::CodeBlock(_)) => {
+                if let Some(code) = code.take() {
+                    blocks.push(Block::Code(code.trim_end().into()));
+                }
+            }
+            MdEvent::Start(Tag::List(start)) => {
+                flush(&mut blocks, &mut spans, &mut marker, &lists);
+                lists.push(start);
+            }
+            MdEvent::End(Tag::List(_)) => {
+                lists.pop();
+            }
+            MdEvent::Start(Tag::Item) => {
+                marker = Some(match lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}.", *n - 1)
+                    }
+                    _ => "•".into(),
+                });
+            }
+            MdEvent::End(Tag::Heading(level, ..)) => {
+                blocks.push(Block::Heading(
+                    heading_level(level),
+                    std::mem::take(&mut spans),
+                ));
+            }
+            MdEvent::End(Tag::Paragraph) | MdEvent::End(Tag::Item) => {
+                flush(&mut blocks, &mut spans, &mut marker, &lists)
+            }
+            MdEvent::Text(text) => {
+                if let Some(code) = &mut code {
+                    code.push_str(&text);
+                } else if let Some((_, alt)) = &mut image {
+                    alt.push_str(&text);
+                } else {
+                    spans.push(Span {
+                        text: text.to_string(),
+                        ..style.clone()
+                    });
+                }
+            }
+            MdEvent::Code(text) => spans.push(Span {
+                text: text.to_string(),
+                code: true,
+                ..style.clone()
+            }),
+            MdEvent::SoftBreak => spans.push(Span {
+                text: " ".into(),
+                ..style.clone()
+            }),
+            MdEvent::HardBreak => spans.push(Span {
+                text: "\n".into(),
+                ..style.clone()
+            }),
+            MdEvent::Rule => blocks.push(Block::Rule),
+            _ => (),
+        }
+    }
+    flush(&mut blocks, &mut spans, &mut marker, &lists);
+    blocks
+}
+
+/// A word placed by `Flow::place`.
+struct Placed {
+    text: String,
+    span: usize,
+    rect: LocalRect,
+}
+
+/// Styled text wrapped to the width it's given, for a block of markdown.
+struct Flow {
+    spans: Vec<Span>,
+    size: u32,
+    indent: f32,
+
+    /// Drawn in the space to the left of the indent, for list items.
+    marker: String,
+    strong: bool,
+}
+
+impl Flow {
+    fn line_height(&self) -> f32 {
+        self.size as f32 * LINE_SPACING
+    }
+
+    /// Breaks the text into words and places them in lines from the top
+    /// left, wrapping at `width`. Returns the words and the height.
+    fn place(&self, width: f32, vger: &mut dyn Renderer) -> (Vec<Placed>, f32) {
+        let line_height = self.line_height();
+        let space = self.size as f32 * 0.3;
+        let left = self.indent;
+
+        let mut placed = vec![];
+        let (mut x, mut line) = (left, 0);
+        for (i, span) in self.spans.iter().enumerate() {
+            for (j, piece) in span.text.split('\n').enumerate() {
+                if j > 0 {
+                    x = left;
+                    line += 1;
+                }
+                let words = piece.split(' ');
+                for (k, word) in words.enumerate() {
+                    if k > 0 {
+                        x += space;
+                    }
+                    if word.is_empty() {
+                        continue;
+                    }
+                    let w = vger.text_bounds(word, self.size, None).width();
+                    if x + w > width && x > left {
+                        x = left;
+                        line += 1;
+                    }
+                    placed.push(Placed {
+                        text: word.into(),
+                        span: i,
+                        rect: LocalRect::new([x, line as f32].into(), [w, line_height].into()),
+                    });
+                    x += w;
+                }
+            }
+        }
+
+        // Now the number of lines is known, put the first at the top.
+        let height = (line + 1) as f32 * line_height;
+        for word in &mut placed {
+            word.rect.origin.y = height - (word.rect.origin.y + 1.0) * line_height;
+        }
+        (placed, height)
+    }
+
+    fn link_at(&self, pt: LocalPoint, width: f32, vger: &mut dyn Renderer) -> Option<String> {
+        let (placed, _) = self.place(width, vger);
+        placed
+            .iter()
+            .find(|word| word.rect.contains(pt))
+            .and_then(|word| self.spans[word.span].link.clone())
+    }
+}
+
+impl View for Flow {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        let text: String = self.spans.iter().map(|span| span.text.as_str()).collect();
+        println!("markdown text({:?})", text);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let width = cx.layout.entry(id).or_default().rect.width();
+        let link = self.link_at(event.position, width, vger);
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } if link.is_some() => cx.touches[*touch] = id,
+            EventKind::TouchMove { id: 0 } if link.is_some() => cx.set_cursor(CursorIcon::Hand),
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(url) = link {
+                    if let Err(err) = open_url(&url) {
+                        println!("error opening {:?}: {:?}", url, err);
+                    }
+                }
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default()
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let (placed, height) = self.place(rect.width(), vger);
+        let baseline = self.line_height() * 0.3;
+
+        if !self.marker.is_empty() {
+            let top = height - self.line_height() + baseline;
+            vger.save();
+            vger.translate([self.indent - LIST_INDENT, top].into());
+            vger.text(&self.marker, self.size, TEXT_COLOR, None);
+            vger.restore();
+        }
+
+        for word in placed {
+            let span = &self.spans[word.span];
+            let color = if span.link.is_some() {
+                LINK_COLOR
+            } else if span.code {
+                AZURE_HIGHLIGHT
+            } else if span.emphasis {
+                EMPHASIS_COLOR
+            } else {
+                TEXT_COLOR
+            };
+            let origin = word.rect.origin + LocalOffset::new(0.0, baseline);
+
+            if span.code {
+                let paint = vger.color_paint(CONTROL_BACKGROUND);
+                vger.fill_rect(word.rect.inflate(2.0, -2.0), 3.0, paint);
+            }
+
+            // There's no bold font to choose, so draw strong text twice,
+            // a little apart.
+            let strong = span.strong || self.strong;
+            let passes: &[f32] = if strong {
+                &[0.0, self.size as f32 / 30.0]
+            } else {
+                &[0.0]
+            };
+            for dx in passes {
+                vger.save();
+                vger.translate(origin.to_vector() + LocalOffset::new(*dx, 0.0));
+                vger.text(&word.text, self.size, color, None);
+                vger.restore();
+            }
+
+            if span.link.is_some() {
+                let y = origin.y - 2.0;
+                let paint = vger.color_paint(LINK_COLOR);
+                vger.stroke_segment(
+                    [word.rect.min_x(), y].into(),
+                    [word.rect.max_x(), y].into(),
+                    1.0,
+                    paint,
+                );
+            }
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let width = constraints.biggest().width;
+        let (_, height) = self.place(width, vger);
+        let size = constraints.constrain(LocalSize::new(width, height));
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let width = cx.layout.entry(id).or_default().rect.width();
+        self.link_at(pt, width, vger).map(|_| id)
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::StaticText));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for Flow {}
+
+fn flow(spans: Vec<Span>, size: u32) -> Flow {
+    Flow {
+        spans,
+        size,
+        indent: 0.0,
+        marker: String::new(),
+        strong: false,
+    }
+}
+
+fn block_view(block: &Block) -> AnyView {
+    let body = Text::DEFAULT_SIZE;
+    match block {
+        Block::Heading(level, spans) => any_view(
+            Flow {
+                strong: true,
+                ..flow(spans.clone(), HEADING_SIZES[level - 1])
+            }
+            .padding(BLOCK_SPACING),
+        ),
+        Block::Paragraph(spans) => any_view(flow(spans.clone(), body).padding(BLOCK_SPACING)),
+        Block::Item {
+            depth,
+            marker,
+            spans,
+        } => any_view(
+            Flow {
+                indent: (depth + 1) as f32 * LIST_INDENT,
+                marker: marker.clone(),
+                ..flow(spans.clone(), body)
+            }
+            .padding(BLOCK_SPACING / 2.0),
+        ),
+        Block::Code(code) => {
+            let span = Span {
+                text: code.clone(),
+                ..Span::default()
+            };
+            any_view(
+                flow(vec![span], CODE_SIZE)
+                    .padding(CODE_PADDING)
+                    .background(rectangle().corner_radius(5.0).color(CONTROL_BACKGROUND))
+                    .padding(BLOCK_SPACING),
+            )
+        }
+        #[cfg(feature = "svg")]
+        Block::Image { url, .. } if url.ends_with(".svg") && !url.contains("://") => {
+            any_view(icon_svg(url.as_str()).padding(BLOCK_SPACING))
+        }
+        Block::Image { alt, .. } => {
+            let span = Span {
+                text: format!("[{}]", alt),
+                emphasis: true,
+                ..Span::default()
+            };
+            any_view(flow(vec![span], body).padding(BLOCK_SPACING))
+        }
+        Block::Rule => any_view(divider().padding(BLOCK_SPACING)),
+    }
+}
+
+/// Displays markdown: headings, emphasis, lists, code, links and rules.
+/// Links open in the browser. Images are drawn if they're local SVG files
+/// and the `svg` feature is on, otherwise their description is shown.
+///
+/// ```no_run
+/// # use rui::*;
+/// rui(markdown("# Release notes\n\n- Faster *and* **smaller**\n- See [the docs](https://docs.rs/rui)"));
+/// ```
+pub fn markdown(source: &str) -> impl View {
+    let blocks = Rc::new(parse(source));
+    list((0..blocks.len()).collect(), move |i| {
+        block_view(&blocks[*i])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> Span {
+        Span {
+            text: text.into(),
+            ..Span::default()
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let blocks = parse(
+            "# Title\n\nSome *fine* `code`.\n\n1. one\n2. [two](https://a.b)\n   - nested\n\n```\nlet x = 1;\n```\n\n---\n",
+        );
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Heading(1, vec![plain("Title")]),
+                Block::Paragraph(vec![
+                    plain("Some "),
+                    Span {
+                        emphasis: true,
+                        ..plain("fine")
+                    },
+                    plain(" "),
+                    Span {
+                        code: true,
+                        ..plain("code")
+                    },
+                    plain("."),
+                ]),
+                Block::Item {
+                    depth: 0,
+                    marker: "1.".into(),
+                    spans: vec![plain("one")],
+                },
+                Block::Item {
+                    depth: 0,
+                    marker: "2.".into(),
+                    spans: vec![Span {
+                        link: Some("https://a.b".into()),
+                        ..plain("two")
+                    }],
+                },
+                Block::Item {
+                    depth: 1,
+                    marker: "•".into(),
+                    spans: vec![plain("nested")],
+                },
+                Block::Code("let x = 1;".into()),
+                Block::Rule,
+            ]
+        );
+    }
+}
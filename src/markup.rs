@@ -0,0 +1,103 @@
+/// Builds a view from markup-like syntax, expanding to the usual
+/// function calls and modifiers.
+///
+/// - `vstack { a b c }` becomes `vstack((a, b, c))`. Any function
+///   taking a tuple of views works, so up to 8 children.
+/// - `text("hi")` and other calls are passed through.
+/// - A block after a call becomes a closure passed as its last argument,
+///   so `button(text("ok")) { println!("ok") }` becomes
+///   `button(text("ok"), move || { println!("ok") })`.
+/// - Modifiers can follow any of the above: `hstack { .. }.padding(Auto)`.
+/// - `if (cond) { a } else { b }` becomes `cond(cond, a, b)`.
+/// - `{ expr }` inserts an arbitrary expression.
+///
+/// For example:
+///
+/// ```no_run
+/// # use rui::*;
+/// rui(rui! {
+///     vstack {
+///         text("hello")
+///         button(text("ok")) { println!("ok") }
+///         hstack { circle() rectangle().corner_radius(5.0) }.padding(Auto)
+///     }
+/// }
+/// .padding(Auto));
+/// ```
+///
+/// Each view is a step of macro recursion, so very large trees may
+/// need a higher `#![recursion_limit]`.
+#[macro_export]
+macro_rules! rui {
+    // Done parsing a sequence of views.
+    (@seq tuple [$($acc:expr,)*]) => {
+        ($($acc,)*)
+    };
+    (@seq single [$view:expr,]) => {
+        $view
+    };
+
+    // Parse the next view in a sequence.
+    (@seq $mode:ident [$($acc:expr,)*] if ($c:expr) { $($t:tt)* } else { $($f:tt)* } $($rest:tt)*) => {
+        $crate::rui!(@mods $mode [$($acc,)*] ($crate::cond($c, $crate::rui!($($t)*), $crate::rui!($($f)*))) $($rest)*)
+    };
+    (@seq $mode:ident [$($acc:expr,)*] { $e:expr } $($rest:tt)*) => {
+        $crate::rui!(@mods $mode [$($acc,)*] ($e) $($rest)*)
+    };
+    (@seq $mode:ident [$($acc:expr,)*] $f:ident { $($children:tt)* } $($rest:tt)*) => {
+        $crate::rui!(@mods $mode [$($acc,)*] ($f($crate::rui!(@seq tuple [] $($children)*))) $($rest)*)
+    };
+    (@seq $mode:ident [$($acc:expr,)*] $f:ident () { $($block:tt)* } $($rest:tt)*) => {
+        $crate::rui!(@mods $mode [$($acc,)*] ($f(move || { $($block)* })) $($rest)*)
+    };
+    (@seq $mode:ident [$($acc:expr,)*] $f:ident ( $($args:tt)* ) { $($block:tt)* } $($rest:tt)*) => {
+        $crate::rui!(@mods $mode [$($acc,)*] ($f($($args)*, move || { $($block)* })) $($rest)*)
+    };
+    (@seq $mode:ident [$($acc:expr,)*] $f:ident ( $($args:tt)* ) $($rest:tt)*) => {
+        $crate::rui!(@mods $mode [$($acc,)*] ($f($($args)*)) $($rest)*)
+    };
+
+    // Apply modifiers to the view just parsed, then continue the sequence.
+    (@mods $mode:ident [$($acc:expr,)*] ($view:expr) . $m:ident ( $($args:tt)* ) $($rest:tt)*) => {
+        $crate::rui!(@mods $mode [$($acc,)*] ($view.$m($($args)*)) $($rest)*)
+    };
+    (@mods $mode:ident [$($acc:expr,)*] ($view:expr) $($rest:tt)*) => {
+        $crate::rui!(@seq $mode [$($acc,)* $view,] $($rest)*)
+    };
+
+    ($($body:tt)+) => {
+        $crate::rui!(@seq single [] $($body)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_rui_macro() {
+        let flag = true;
+        let _ = rui! {
+            vstack {
+                text("hi")
+                button(text("ok")) { println!("ok") }
+                hstack { circle() rectangle().corner_radius(5.0) }.padding(Auto)
+                if (flag) { text("yes") } else { text("no").font_size(12) }
+                { EmptyView {} }
+            }
+        };
+    }
+
+    #[test]
+    fn test_rui_macro_state() {
+        let _ = state(0, |count: State<usize>| {
+            rui! {
+                hstack {
+                    text(&format!("{}", count.get()))
+                    button(text("+")) { count.with_mut(|c| *c += 1) }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,185 @@
+use crate::*;
+
+/// A group of views matching widths or heights, and which of the two.
+pub(crate) type MatchKey = (&'static str, bool);
+
+/// Struct for the `match_width` and `match_height` modifiers.
+pub struct MatchSize<V> {
+    child: V,
+    group: &'static str,
+    height: bool,
+}
+
+impl<V> MatchSize<V>
+where
+    V: View,
+{
+    pub fn width(child: V, group: &'static str) -> Self {
+        Self {
+            child,
+            group,
+            height: false,
+        }
+    }
+
+    pub fn height(child: V, group: &'static str) -> Self {
+        Self {
+            child,
+            group,
+            height: true,
+        }
+    }
+}
+
+impl<V> View for MatchSize<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        if self.height {
+            println!(".match_height({:?})", self.group);
+        } else {
+            println!(".match_width({:?})", self.group);
+        }
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let mut local_event = event.clone();
+        local_event.position -= cx.layout.entry(id.child(&0)).or_default().offset;
+        self.child.process(&local_event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        vger.save();
+        vger.translate(cx.layout.entry(id.child(&0)).or_default().offset);
+        self.child.draw(id.child(&0), cx, vger);
+        vger.restore();
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let child_size = self.child.layout(id.child(&0), constraints, cx, vger);
+        let natural = if self.height {
+            child_size.height
+        } else {
+            child_size.width
+        };
+
+        let key = (self.group, self.height);
+        let measured = cx.measured_sizes.entry(key).or_insert(0.0);
+        *measured = measured.max(natural);
+        let matched = match cx.matched_sizes.get(&key) {
+            Some(matched) => matched.max(natural),
+            None => natural,
+        };
+
+        let size = if self.height {
+            LocalSize::new(child_size.width, matched)
+        } else {
+            LocalSize::new(matched, child_size.height)
+        };
+        let offset = ((size - child_size) / 2.0).to_vector();
+        offset_guides(&mut cx.guides, offset);
+        let layout_box = cx.layout.entry(id.child(&0)).or_default();
+        layout_box.rect = LocalRect::new(LocalPoint::zero(), child_size);
+        layout_box.offset = offset;
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let offset = cx.layout.entry(id.child(&0)).or_default().offset;
+        self.child.hittest(id.child(&0), pt - offset, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        let size = self.child.intrinsic_size(id.child(&0), cx, vger)?;
+        let matched = cx.matched_sizes.get(&(self.group, self.height));
+        Some(match matched {
+            Some(h) if self.height => LocalSize::new(size.width, size.height.max(*h)),
+            Some(w) => LocalSize::new(size.width.max(*w), size.height),
+            None => size,
+        })
+    }
+}
+
+impl<V> private::Sealed for MatchSize<V> {}
+
+impl Context {
+    /// Lays out the whole view tree. Views matching sizes use the sizes
+    /// their groups had last time, so if any have changed the tree is laid
+    /// out again with the new ones.
+    pub(crate) fn layout_root(
+        &mut self,
+        view: &dyn View,
+        size: LocalSize,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.measured_sizes.clear();
+        self.layout.begin_pass();
+        let result = view.layout(self.root_id, size.into(), self, vger);
+        if self.measured_sizes == self.matched_sizes {
+            return result;
+        }
+        self.matched_sizes = std::mem::take(&mut self.measured_sizes);
+        view.layout(self.root_id, size.into(), self, vger)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_width() {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([200.0, 100.0].into());
+        let view = vstack((
+            rectangle().size([30.0, 10.0]).match_width("buttons"),
+            rectangle().size([50.0, 10.0]).match_width("buttons"),
+        ));
+        cx.layout_root(&view, [200.0, 100.0].into(), &mut vger);
+
+        let first = cx.root_id.child(&0);
+        assert_eq!(cx.layout[&first].rect.width(), 50.0);
+        assert_eq!(cx.layout[&first.child(&0)].offset.x, 10.0);
+        assert_eq!(cx.layout[&cx.root_id.child(&1)].rect.width(), 50.0);
+    }
+}
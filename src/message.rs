@@ -0,0 +1,216 @@
+use crate::*;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// A message published with `Context::publish` or a `MessageBus`. Use
+/// `on_message` to receive them.
+pub type Message = Rc<dyn Any>;
+
+/// Carries messages between views which don't know about each other, like
+/// a toolbar button asking a list to scroll to the top. The context's bus
+/// is in the environment, so read it with `read_env` to publish from an
+/// action:
+///
+/// ```ignore
+/// read_env(|bus: MessageBus| button(text("top"), move || bus.publish(ScrollToTop)))
+/// ```
+#[derive(Clone, Default)]
+pub struct MessageBus {
+    queue: Rc<RefCell<VecDeque<Message>>>,
+}
+
+thread_local! {
+    /// The bus of the context made last on this thread. See `emit`.
+    static APP_BUS: RefCell<MessageBus> = RefCell::new(MessageBus::default());
+}
+
+impl MessageBus {
+    /// Makes the bus for a new context, which `emit` publishes to.
+    pub(crate) fn new_app() -> Self {
+        let bus = Self::default();
+        APP_BUS.with(|app| *app.borrow_mut() = bus.clone());
+        bus
+    }
+
+    /// Queues `message`, for the views with `on_message` to see once the
+    /// event being processed has been.
+    pub fn publish<T: Any>(&self, message: T) {
+        self.queue.borrow_mut().push_back(Rc::new(message));
+    }
+
+    fn pop(&self) -> Option<Message> {
+        self.queue.borrow_mut().pop_front()
+    }
+}
+
+/// An action publishing `message`, for a button or `tap` to emit a
+/// message without reaching for the bus: `button(text("+"), emit(Msg::Add))`.
+pub fn emit<T: Any + Clone>(message: T) -> impl Fn() + 'static {
+    move || APP_BUS.with(|bus| bus.borrow().publish(message.clone()))
+}
+
+/// A tap publishing a message, for the `emits` modifier.
+pub struct Emit<T>(pub T);
+
+impl<T: Any + Clone> TapFunc for Emit<T> {
+    fn call(&self, _click_count: usize) {
+        APP_BUS.with(|bus| bus.borrow().publish(self.0.clone()))
+    }
+}
+
+impl Context {
+    /// Queues `message` for the views with `on_message`. See `MessageBus`.
+    pub fn publish<T: Any>(&mut self, message: T) {
+        self.message_bus().publish(message)
+    }
+
+    fn message_bus(&self) -> MessageBus {
+        self.get_env::<MessageBus>().unwrap_or_default()
+    }
+
+    /// Sends the messages published so far to the views, along with any
+    /// they publish in turn.
+    pub(crate) fn deliver_messages(&mut self, view: &dyn View, vger: &mut dyn Renderer) {
+        let bus = self.message_bus();
+        while let Some(message) = bus.pop() {
+            let event = Event {
+                kind: EventKind::Message(message),
+                position: LocalPoint::zero(),
+            };
+            self.consumed = false;
+            view.process(&event, self.root_id, self, vger);
+        }
+    }
+}
+
+/// Struct for `on_message`.
+pub struct OnMessage<V, T, F> {
+    child: V,
+    func: F,
+    phantom: PhantomData<fn(&T)>,
+}
+
+impl<V, T, F> OnMessage<V, T, F>
+where
+    V: View,
+    T: Any,
+    F: Fn(&T) + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self {
+            child: v,
+            func: f,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<V, T, F> View for OnMessage<V, T, F>
+where
+    V: View,
+    T: Any,
+    F: Fn(&T) + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".on_message()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::Message(message) = &event.kind {
+            if let Some(value) = message.downcast_ref::<T>() {
+                (self.func)(value);
+            }
+        }
+        self.child.process(event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, T, F> private::Sealed for OnMessage<V, T, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct ScrollToTop;
+
+    #[test]
+    fn test_message_bus() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let seen = Rc::new(Cell::new(0));
+        let seen2 = seen.clone();
+        let ui = vstack((
+            rectangle().on_message(move |_: &ScrollToTop| seen2.set(seen2.get() + 1)),
+            rectangle().tap(|| ()),
+        ));
+
+        cx.publish(ScrollToTop);
+        cx.publish(0u8);
+        let event = Event {
+            kind: EventKind::Command("noop".into()),
+            position: LocalPoint::zero(),
+        };
+        cx.process_root(&ui, &event, &mut vger);
+        assert_eq!(seen.get(), 1);
+    }
+}
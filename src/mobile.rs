@@ -0,0 +1,44 @@
+//! Entry points for iOS and Android, which start an app built as a library
+//! rather than by calling its `main`.
+
+/// Defines the functions iOS and Android start the app with, which call
+/// `main`. Build the app as a library (`crate-type = ["staticlib",
+/// "cdylib"]`) with this alongside its `main`:
+///
+/// ```no_run
+/// use rui::*;
+///
+/// fn main() {
+///     rui(text("hello"));
+/// }
+///
+/// mobile_main!(main);
+/// ```
+///
+/// On iOS, the app's Xcode project calls `start_app`. On Android, the
+/// app's `NativeActivity` calls `ANativeActivity_onCreate`, which runs
+/// `main` on a thread of its own.
+#[macro_export]
+macro_rules! mobile_main {
+    ($main:path) => {
+        #[cfg(target_os = "ios")]
+        #[no_mangle]
+        pub extern "C" fn start_app() {
+            $main()
+        }
+
+        #[cfg(target_os = "android")]
+        #[no_mangle]
+        pub unsafe extern "C" fn ANativeActivity_onCreate(
+            activity: *mut std::os::raw::c_void,
+            saved_state: *mut std::os::raw::c_void,
+            saved_state_size: usize,
+        ) {
+            $crate::ndk_glue::init(activity as _, saved_state as _, saved_state_size, $main)
+        }
+    };
+}
+
+#[cfg(target_os = "android")]
+#[doc(hidden)]
+pub use ndk_glue;
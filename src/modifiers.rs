@@ -1,13 +1,58 @@
 use crate::*;
 use accesskit::Role;
+use std::hash::Hash;
+use std::time::Duration;
 
 pub trait Modifiers: View + Sized {
     /// Adds space around a view. Can be either `Auto` or `Px(number_of_pixels)`
     fn padding(self, param: impl Into<PaddingParam>) -> Padding<Self>;
 
+    /// Sets how readily the view is given space in a stack. When there
+    /// isn't room for everything, views with lower priorities shrink or
+    /// truncate first. The default is 0.
+    fn layout_priority(self, priority: f32) -> LayoutPriority<Self>;
+
+    /// Lays the view out at its intrinsic size, like the natural size of
+    /// text, whatever it's offered. Views without one, like shapes, still
+    /// take what they're offered.
+    fn fixed_size(self) -> FixedSize<Self>;
+
+    /// Sizes the view to `ratio` of width to height, fitting in or filling
+    /// the space it's offered. Images inside are drawn the same way.
+    fn aspect_ratio(self, ratio: f32, mode: ContentMode) -> AspectRatio<Self>;
+
+    /// Makes the view as wide as the widest view in `group`, anywhere in
+    /// the window, centering it in the extra space. For rows of buttons.
+    fn match_width(self, group: &'static str) -> MatchSize<Self>;
+
+    /// Makes the view as tall as the tallest view in `group`.
+    fn match_height(self, group: &'static str) -> MatchSize<Self>;
+
+    /// Moves where the view lines up with its siblings in a stack. `f` is
+    /// passed the view's size and returns where the guide for `alignment`
+    /// is, from the view's leading edge or bottom. Stacks pass guides set
+    /// inside them on, so a row can line up on a view inside it.
+    fn alignment_guide<F: Fn(ViewDimensions) -> f32 + 'static>(
+        self,
+        alignment: impl Into<Alignment>,
+        f: F,
+    ) -> AlignmentGuide<Self, F>;
+
     /// Calls a function in response to a tap.
     fn tap<F: Fn() + 'static>(self, f: F) -> Tap<Self, F>;
 
+    /// Calls a function in response to a tap, with how many clicks in
+    /// quick succession it makes, so 2 for a double click.
+    fn tap_with_count<F: Fn(usize) + 'static>(self, f: F) -> Tap<Self, CountedTap<F>>;
+
+    /// Calls a function when the view is double clicked. Taps inside still
+    /// see both clicks.
+    fn on_double_click<F: Fn() + 'static>(self, f: F) -> OnDoubleClick<Self, F>;
+
+    /// Sets how soon after a click another must come to count as a double
+    /// click. Applies to the whole window.
+    fn double_click_interval(self, interval: Duration) -> DoubleClickInterval<Self>;
+
     /// Puts a view behind another. The background view inherits the size of the view.
     fn background<BG: View + 'static>(self, background: BG) -> Background<Self, BG>;
 
@@ -18,6 +63,14 @@ pub trait Modifiers: View + Sized {
     /// Calls a function in response to a drag.
     fn drag<F: Fn(LocalOffset, GestureState) + 'static>(self, f: F) -> Drag<Self, F>;
 
+    /// Calls a function with the scale of a two-finger pinch, relative to
+    /// where the fingers started.
+    fn on_pinch<F: Fn(f32, GestureState) + 'static>(self, f: F) -> Pinch<Self, F>;
+
+    /// Calls a function with the angle in radians of a two-finger rotation,
+    /// relative to where the fingers started.
+    fn on_rotate<F: Fn(f32, GestureState) + 'static>(self, f: F) -> Rotate<Self, F>;
+
     /// Applies an offset to the view in local space.
     fn offset<Off: Into<LocalOffset>>(self, offset: Off) -> Offset<Self>;
 
@@ -42,15 +95,193 @@ pub trait Modifiers: View + Sized {
 
     /// Make the window full screen.
     fn fullscreen(self) -> FullscreenView<Self>;
+
+    /// Shows or hides the native title bar and window border.
+    fn window_decorations(self, decorations: bool) -> DecorationsView<Self>;
+
+    /// Dragging the view moves the window, and double-clicking it
+    /// maximizes the window. For custom title bars.
+    fn window_drag_region(self) -> DragRegion<Self>;
+
+    /// Makes the view close, minimize or maximize the window when tapped.
+    fn window_button(self, button: WindowButton) -> WindowButtonView<Self>;
+
+    /// Calls a function when the window is focused, minimized, moved etc.
+    fn on_window_event<F: Fn(&WindowEventKind) + 'static>(self, f: F) -> OnWindowEvent<Self, F>;
+
+    /// Calls a function with events of type `T` sent with an `Injector`.
+    fn on_user_event<T: std::any::Any, F: Fn(&T) + 'static>(self, f: F) -> OnUserEvent<Self, T, F>;
+
+    /// Calls a function with messages of type `T` published by other
+    /// views. See `MessageBus`.
+    fn on_message<T: std::any::Any, F: Fn(&T) + 'static>(self, f: F) -> OnMessage<Self, T, F>;
+
+    /// Publishes `message` when the view is tapped. For buttons, pass
+    /// `emit(message)` as the action instead.
+    fn emits<T: std::any::Any + Clone>(self, message: T) -> Tap<Self, Emit<T>>;
+
+    /// Calls a function with each event before the view sees it. If it
+    /// returns true, the event is consumed: neither the view nor the views
+    /// after it see it.
+    fn on_event_capture<F: Fn(&Event) -> bool + 'static>(self, f: F) -> OnEventCapture<Self, F>;
+
+    /// Makes the view hit where `shape` is, at the view's size, rather
+    /// than where it draws. Pad a small view first to make it easier to
+    /// tap.
+    fn content_shape<S: Shape + 'static>(self, shape: S) -> ContentShape<Self, S>;
+
+    /// Lets touches pass through the view to the views underneath, for
+    /// decorative overlays.
+    fn hit_test_disabled(self) -> HitTestDisabled<Self>;
+
+    /// Draws the view above the views next to it in a stack with lower
+    /// indices, and below those with higher ones, and hittests it to
+    /// match. Views with the same index keep their order. Defaults to 0.
+    fn z_index(self, index: i32) -> ZIndex<Self>;
+
+    /// Combines the view's colors with what's under it using `mode`.
+    fn blend_mode(self, mode: BlendMode) -> Layer<Self>;
+
+    /// Draws the view into a layer of its own, then composites the layer,
+    /// so effects like `disabled`'s fading apply to it as a whole rather
+    /// than to each thing inside it.
+    fn compositing_group(self) -> Layer<Self>;
+
+    /// Blurs the view, spreading each point by about `radius`.
+    fn blur(self, radius: f32) -> Layer<Self>;
+
+    /// Changes how colorful the view is: 0 for grays, 1 for the colors as
+    /// drawn, more to make them more vivid.
+    fn saturation(self, saturation: f32) -> Layer<Self>;
+
+    /// Keeps what the view draws and reuses it on later frames, until a
+    /// state read while drawing it changes or its size does. For complex
+    /// views which rarely change, like a chart behind an animation. Data
+    /// passed in from outside isn't tracked, so read it from a `State`
+    /// within the view.
+    fn cache(self) -> Cache<Self>;
+
+    /// Like `cache`, and also keeps the view's layout, so the closures
+    /// within aren't called to lay it out or draw it again until `key`
+    /// changes. Pass what the view is built from, or a hash of it.
+    fn memo<K: Hash>(self, key: K) -> Cache<Self>;
+
+    /// Calls a function when the user tries to close the window. Return
+    /// false to keep the window open, for example to save changes first.
+    fn on_close_requested<F: Fn() -> bool + 'static>(self, f: F) -> OnCloseRequested<Self, F>;
+
+    /// Shows an icon in the system tray, with a menu of `items`.
+    #[cfg(feature = "tray")]
+    fn tray(self, icon: impl Into<std::path::PathBuf>, items: Vec<TrayItem>) -> TrayView<Self>;
+
+    /// Shows a count or some text in a capsule at the top right corner.
+    /// A zero count hides the badge.
+    fn badge(self, label: impl BadgeLabel) -> Badge<Self>;
+
+    /// Renders the view into an image at `scale` pixels per point when
+    /// `request` is set, then calls `f` with the image.
+    #[cfg(feature = "software")]
+    fn snapshot<B: Binding<bool>, F: Fn(Frame) + 'static>(
+        self,
+        request: B,
+        scale: f32,
+        f: F,
+    ) -> Snapshot<Self, B, F>;
+
+    /// Sets an environment value for the view and the views inside it,
+    /// which they read with `cx.get_env`.
+    fn env<E: Clone + 'static>(self, value: E) -> EnvView<Self, E>;
+
+    /// Stops the view and the controls inside it responding to input when
+    /// `disabled` is true. They're drawn faded and can't take the focus.
+    fn disabled(self, disabled: bool) -> Disabled<Self>;
+
+    /// Restyles the view for how it's being interacted with: hovered,
+    /// pressed, focused or disabled.
+    fn style<F: Fn(InteractionState) -> StyleOverrides + 'static>(self, f: F) -> Styled<Self, F>;
+
+    /// Sets how big the controls inside the view are drawn.
+    fn control_size(self, size: ControlSize) -> EnvView<Self, ControlSize>;
+
+    /// Sets how the buttons inside the view are drawn.
+    fn button_style(self, style: ButtonStyle) -> EnvView<Self, ButtonStyle>;
+
+    /// Draws icons inside the view in a single color.
+    fn tint(self, color: Color) -> EnvView<Self, TintColor>;
+
+    /// Moves the view in from the edges by the safe area insets, so it
+    /// isn't covered by notches, title bars or docked panels.
+    fn respect_safe_area(self) -> SafeArea<Self>;
+
+    /// Extends the view back out over the insets an enclosing
+    /// `respect_safe_area` moved it in by. For full-bleed backgrounds.
+    fn ignore_safe_area(self) -> SafeArea<Self>;
+
+    /// Adds to the safe area insets inside the view, for example the
+    /// height of a custom title bar or a panel drawn over the content.
+    fn safe_area_inset(self, insets: SafeAreaInsets) -> SafeArea<Self>;
+
+    /// Selects the font for text inside the view. See `fonts::register`.
+    /// Only the software renderer draws with it.
+    fn font(self, font: fonts::Font) -> FontView<Self>;
+
+    /// Marks a view so `ScrollTo::id(key, anchor)` scrolls to it.
+    fn scroll_id<K: Hash + ?Sized>(self, key: &K) -> ScrollId<Self>;
+
+    /// Selects the views marked `selectable` within this one which are
+    /// dragged over, keeping their keys in `selection`. Hold Shift to add
+    /// to what's selected.
+    fn rubber_band_selection<K: Clone + PartialEq + 'static, B: Binding<Vec<K>>>(
+        self,
+        selection: B,
+    ) -> RubberBand<Self, B, K>;
+
+    /// Marks a view as an item `rubber_band_selection` can select, by `key`.
+    fn selectable<K: Clone + 'static>(self, key: K) -> Selectable<Self, K>;
+
+    /// Lets the view be dragged around. Use `position` to keep where it's
+    /// been dragged to, and `snap_to` to snap it to a grid or guides.
+    fn draggable(self) -> Draggable<Self, State<LocalOffset>>;
 }
 
 impl<V: View + 'static> Modifiers for V {
     fn padding(self, param: impl Into<PaddingParam>) -> Padding<Self> {
         Padding::new(self, param.into())
     }
+    fn layout_priority(self, priority: f32) -> LayoutPriority<Self> {
+        LayoutPriority::new(self, priority)
+    }
+    fn fixed_size(self) -> FixedSize<Self> {
+        FixedSize::new(self)
+    }
+    fn aspect_ratio(self, ratio: f32, mode: ContentMode) -> AspectRatio<Self> {
+        AspectRatio::new(self, ratio, mode)
+    }
+    fn match_width(self, group: &'static str) -> MatchSize<Self> {
+        MatchSize::width(self, group)
+    }
+    fn match_height(self, group: &'static str) -> MatchSize<Self> {
+        MatchSize::height(self, group)
+    }
+    fn alignment_guide<F: Fn(ViewDimensions) -> f32 + 'static>(
+        self,
+        alignment: impl Into<Alignment>,
+        f: F,
+    ) -> AlignmentGuide<Self, F> {
+        AlignmentGuide::new(self, alignment.into(), f)
+    }
     fn tap<F: Fn() + 'static>(self, f: F) -> Tap<Self, F> {
         Tap::new(self, f)
     }
+    fn tap_with_count<F: Fn(usize) + 'static>(self, f: F) -> Tap<Self, CountedTap<F>> {
+        Tap::new(self, CountedTap(f))
+    }
+    fn on_double_click<F: Fn() + 'static>(self, f: F) -> OnDoubleClick<Self, F> {
+        OnDoubleClick::new(self, f)
+    }
+    fn double_click_interval(self, interval: Duration) -> DoubleClickInterval<Self> {
+        DoubleClickInterval::new(self, interval)
+    }
     fn background<BG: View + 'static>(self, background: BG) -> Background<Self, BG> {
         Background::new(self, background)
     }
@@ -60,6 +291,12 @@ impl<V: View + 'static> Modifiers for V {
     fn drag<F: Fn(LocalOffset, GestureState) + 'static>(self, f: F) -> Drag<Self, F> {
         Drag::new(self, f)
     }
+    fn on_pinch<F: Fn(f32, GestureState) + 'static>(self, f: F) -> Pinch<Self, F> {
+        Pinch::new(self, f)
+    }
+    fn on_rotate<F: Fn(f32, GestureState) + 'static>(self, f: F) -> Rotate<Self, F> {
+        Rotate::new(self, f)
+    }
     fn offset<Off: Into<LocalOffset>>(self, offset: Off) -> Offset<Self> {
         Offset::new(self, offset.into())
     }
@@ -89,4 +326,151 @@ impl<V: View + 'static> Modifiers for V {
     fn fullscreen(self) -> FullscreenView<Self> {
         FullscreenView::new(self)
     }
+    fn window_decorations(self, decorations: bool) -> DecorationsView<Self> {
+        DecorationsView::new(self, decorations)
+    }
+    fn window_drag_region(self) -> DragRegion<Self> {
+        DragRegion::new(self)
+    }
+    fn window_button(self, button: WindowButton) -> WindowButtonView<Self> {
+        WindowButtonView::new(self, button)
+    }
+    fn on_window_event<F: Fn(&WindowEventKind) + 'static>(self, f: F) -> OnWindowEvent<Self, F> {
+        OnWindowEvent::new(self, f)
+    }
+    fn on_user_event<T: std::any::Any, F: Fn(&T) + 'static>(self, f: F) -> OnUserEvent<Self, T, F> {
+        OnUserEvent::new(self, f)
+    }
+    fn on_message<T: std::any::Any, F: Fn(&T) + 'static>(self, f: F) -> OnMessage<Self, T, F> {
+        OnMessage::new(self, f)
+    }
+    fn emits<T: std::any::Any + Clone>(self, message: T) -> Tap<Self, Emit<T>> {
+        Tap::new(self, Emit(message))
+    }
+    fn on_event_capture<F: Fn(&Event) -> bool + 'static>(self, f: F) -> OnEventCapture<Self, F> {
+        OnEventCapture::new(self, f)
+    }
+    fn content_shape<S: Shape + 'static>(self, shape: S) -> ContentShape<Self, S> {
+        ContentShape::new(self, shape)
+    }
+    fn hit_test_disabled(self) -> HitTestDisabled<Self> {
+        HitTestDisabled::new(self)
+    }
+    fn z_index(self, index: i32) -> ZIndex<Self> {
+        ZIndex::new(self, index)
+    }
+    fn blend_mode(self, mode: BlendMode) -> Layer<Self> {
+        Layer::new(
+            self,
+            LayerEffects {
+                blend_mode: mode,
+                ..Default::default()
+            },
+        )
+    }
+    fn compositing_group(self) -> Layer<Self> {
+        Layer::new(self, LayerEffects::default())
+    }
+    fn blur(self, radius: f32) -> Layer<Self> {
+        Layer::new(
+            self,
+            LayerEffects {
+                blur: radius,
+                ..Default::default()
+            },
+        )
+    }
+    fn saturation(self, saturation: f32) -> Layer<Self> {
+        Layer::new(
+            self,
+            LayerEffects {
+                saturation,
+                ..Default::default()
+            },
+        )
+    }
+    fn cache(self) -> Cache<Self> {
+        Cache::new(self)
+    }
+    fn memo<K: Hash>(self, key: K) -> Cache<Self> {
+        Cache::memo(self, key)
+    }
+    fn on_close_requested<F: Fn() -> bool + 'static>(self, f: F) -> OnCloseRequested<Self, F> {
+        OnCloseRequested::new(self, f)
+    }
+    #[cfg(feature = "tray")]
+    fn tray(self, icon: impl Into<std::path::PathBuf>, items: Vec<TrayItem>) -> TrayView<Self> {
+        TrayView::new(self, icon, items)
+    }
+    fn badge(self, label: impl BadgeLabel) -> Badge<Self> {
+        Badge::new(self, label)
+    }
+    #[cfg(feature = "software")]
+    fn snapshot<B: Binding<bool>, F: Fn(Frame) + 'static>(
+        self,
+        request: B,
+        scale: f32,
+        f: F,
+    ) -> Snapshot<Self, B, F> {
+        Snapshot::new(self, request, scale, f)
+    }
+
+    fn env<E: Clone + 'static>(self, value: E) -> EnvView<Self, E> {
+        EnvView::new(self, value)
+    }
+
+    fn disabled(self, disabled: bool) -> Disabled<Self> {
+        Disabled::new(self, disabled)
+    }
+
+    fn style<F: Fn(InteractionState) -> StyleOverrides + 'static>(self, f: F) -> Styled<Self, F> {
+        Styled::new(self, f)
+    }
+
+    fn control_size(self, size: ControlSize) -> EnvView<Self, ControlSize> {
+        EnvView::new(self, size)
+    }
+
+    fn button_style(self, style: ButtonStyle) -> EnvView<Self, ButtonStyle> {
+        EnvView::new(self, style)
+    }
+
+    fn tint(self, color: Color) -> EnvView<Self, TintColor> {
+        EnvView::new(self, TintColor(color))
+    }
+
+    fn respect_safe_area(self) -> SafeArea<Self> {
+        SafeArea::respect(self)
+    }
+
+    fn ignore_safe_area(self) -> SafeArea<Self> {
+        SafeArea::ignore(self)
+    }
+
+    fn safe_area_inset(self, insets: SafeAreaInsets) -> SafeArea<Self> {
+        SafeArea::inset(self, insets)
+    }
+
+    fn font(self, font: fonts::Font) -> FontView<Self> {
+        FontView::new(self, font)
+    }
+
+    fn scroll_id<K: Hash + ?Sized>(self, key: &K) -> ScrollId<Self> {
+        ScrollId::new(self, key)
+    }
+
+    fn rubber_band_selection<K: Clone + PartialEq + 'static, B: Binding<Vec<K>>>(
+        self,
+        selection: B,
+    ) -> RubberBand<Self, B, K> {
+        RubberBand::new(self, selection)
+    }
+
+    fn selectable<K: Clone + 'static>(self, key: K) -> Selectable<Self, K> {
+        Selectable::new(self, key)
+    }
+
+    fn draggable(self) -> Draggable<Self, State<LocalOffset>> {
+        Draggable::new(self)
+    }
 }
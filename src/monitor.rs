@@ -0,0 +1,102 @@
+use crate::*;
+use tao::dpi::{PhysicalPosition, PhysicalSize};
+use tao::monitor::MonitorHandle;
+
+/// A display connected to the computer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+
+    /// Where the top left corner is on the desktop, in physical pixels.
+    pub position: PhysicalPosition<i32>,
+
+    /// The size in physical pixels. tao doesn't say how much of this is
+    /// covered by task bars and docks.
+    pub size: PhysicalSize<u32>,
+
+    /// Physical pixels per logical pixel.
+    pub scale_factor: f64,
+
+    /// Is this the main display, where the menu bar or task bar is?
+    pub primary: bool,
+}
+
+impl MonitorInfo {
+    fn new(handle: &MonitorHandle, primary: bool) -> Self {
+        Self {
+            name: handle.name(),
+            position: handle.position(),
+            size: handle.size(),
+            scale_factor: handle.scale_factor(),
+            primary,
+        }
+    }
+
+    /// The size in logical pixels, which views are laid out in.
+    pub fn logical_size(&self) -> LocalSize {
+        let scale = self.scale_factor as f32;
+        [
+            self.size.width as f32 / scale,
+            self.size.height as f32 / scale,
+        ]
+        .into()
+    }
+}
+
+/// The displays connected to the computer, and which one the window is
+/// on. Read them with `cx.monitors()`. Views are sent
+/// `WindowEventKind::DisplaysChanged` when these change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Monitors {
+    pub all: Vec<MonitorInfo>,
+
+    /// The index in `all` of the monitor the window is on.
+    pub current: Option<usize>,
+}
+
+impl Monitors {
+    pub(crate) fn from_window(window: &Window) -> Self {
+        let primary = window.primary_monitor();
+        let current = window.current_monitor();
+        let handles: Vec<MonitorHandle> = window.available_monitors().collect();
+        Self {
+            all: handles
+                .iter()
+                .map(|m| MonitorInfo::new(m, Some(m) == primary.as_ref()))
+                .collect(),
+            current: current.and_then(|c| handles.iter().position(|m| *m == c)),
+        }
+    }
+
+    /// The monitor the window is on.
+    pub fn current(&self) -> Option<&MonitorInfo> {
+        self.current.map(|i| &self.all[i])
+    }
+
+    /// The main display.
+    pub fn primary(&self) -> Option<&MonitorInfo> {
+        self.all.iter().find(|m| m.primary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logical_size() {
+        let monitor = MonitorInfo {
+            name: None,
+            position: PhysicalPosition::new(0, 0),
+            size: PhysicalSize::new(2880, 1800),
+            scale_factor: 2.0,
+            primary: true,
+        };
+        assert_eq!(monitor.logical_size(), LocalSize::new(1440.0, 900.0));
+        let monitors = Monitors {
+            all: vec![monitor],
+            current: Some(0),
+        };
+        assert_eq!(monitors.current(), monitors.primary());
+    }
+}
@@ -0,0 +1,587 @@
+use crate::*;
+
+const NODE_WIDTH: f32 = 140.0;
+const NODE_HEADER: f32 = 24.0;
+const NODE_PADDING: f32 = 6.0;
+const NODE_RADIUS: f32 = 6.0;
+const NODE_TITLE_SIZE: u32 = 14;
+const PORT_SPACING: f32 = 20.0;
+const PORT_RADIUS: f32 = 5.0;
+const PORT_LABEL_SIZE: u32 = 11;
+
+/// How close the mouse must be to a port to grab it.
+const PORT_HIT_RADIUS: f32 = 2.0 * PORT_RADIUS;
+
+/// A box in a `node_graph`, with inputs down its left side and outputs
+/// down its right.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphNode {
+    pub title: String,
+
+    /// Where the bottom left corner is, in the graph's coordinates.
+    pub position: LocalPoint,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+impl GraphNode {
+    pub fn new(title: &str, position: impl Into<LocalPoint>) -> Self {
+        Self {
+            title: title.into(),
+            position: position.into(),
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
+    pub fn input(mut self, name: &str) -> Self {
+        self.inputs.push(name.into());
+        self
+    }
+
+    pub fn output(mut self, name: &str) -> Self {
+        self.outputs.push(name.into());
+        self
+    }
+
+    pub fn rect(&self) -> LocalRect {
+        let rows = self.inputs.len().max(self.outputs.len()) as f32;
+        LocalRect::new(
+            self.position,
+            [NODE_WIDTH, NODE_HEADER + rows * PORT_SPACING + NODE_PADDING].into(),
+        )
+    }
+
+    fn port_y(&self, index: usize) -> f32 {
+        self.rect().max_y() - NODE_HEADER - (index as f32 + 0.5) * PORT_SPACING
+    }
+
+    pub fn input_position(&self, index: usize) -> LocalPoint {
+        [self.rect().min_x(), self.port_y(index)].into()
+    }
+
+    pub fn output_position(&self, index: usize) -> LocalPoint {
+        [self.rect().max_x(), self.port_y(index)].into()
+    }
+}
+
+/// An input or output of a node: the node's index, and the port's index
+/// among the node's inputs or outputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Port {
+    pub node: usize,
+    pub index: usize,
+}
+
+/// A connection from an output to an input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GraphEdge {
+    pub from: Port,
+    pub to: Port,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GraphDrag {
+    /// Moving the selected nodes.
+    Nodes { last: LocalPoint },
+
+    /// Dragging a new connection out of an output.
+    Connect { from: Port, to: LocalPoint },
+
+    /// Selecting the nodes a rect touches.
+    Select {
+        start: LocalPoint,
+        current: LocalPoint,
+    },
+}
+
+#[derive(Clone, Default)]
+struct GraphState {
+    drag: Option<GraphDrag>,
+    selection: Vec<usize>,
+}
+
+type EdgeCallback = Box<dyn Fn(GraphEdge)>;
+type SelectCallback = Box<dyn Fn(&[usize])>;
+
+/// The graph itself, which `NodeGraph` puts in a `pan_zoom`.
+pub struct GraphCanvas<N, E> {
+    nodes: N,
+    edges: E,
+    on_connect: Option<EdgeCallback>,
+    on_disconnect: Option<EdgeCallback>,
+    on_select: Option<SelectCallback>,
+}
+
+/// Draws a label with its left, middle or right at `pt`, for `align` of
+/// 0, 0.5 or 1, centered vertically.
+fn draw_label(
+    vger: &mut dyn Renderer,
+    text: &str,
+    size: u32,
+    pt: LocalPoint,
+    align: f32,
+    color: Color,
+) {
+    let bounds = vger.text_bounds(text, size, None);
+    vger.save();
+    vger.translate(
+        [
+            pt.x - bounds.width() * align - bounds.origin.x,
+            pt.y - bounds.height() / 2.0 - bounds.origin.y,
+        ]
+        .into(),
+    );
+    vger.text(text, size, color, None);
+    vger.restore();
+}
+
+/// Draws a connection as an S-shaped curve, leaving `a` to the right and
+/// arriving at `b` from the left.
+fn draw_edge(vger: &mut dyn Renderer, a: LocalPoint, b: LocalPoint, paint: Paint) {
+    let dx = ((b.x - a.x).abs() / 2.0).max(40.0);
+    let middle = a.lerp(b, 0.5);
+    vger.stroke_bezier(a, [a.x + dx / 2.0, a.y].into(), middle, 2.0, paint);
+    vger.stroke_bezier(middle, [b.x - dx / 2.0, b.y].into(), b, 2.0, paint);
+}
+
+impl<N, E> GraphCanvas<N, E>
+where
+    N: Binding<Vec<GraphNode>>,
+    E: Binding<Vec<GraphEdge>>,
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<GraphState> {
+        cx.with_state(
+            GraphState::default(),
+            id,
+            |state: State<GraphState>, _cx| state,
+        )
+    }
+
+    /// The topmost node under `pt`.
+    fn node_at(&self, pt: LocalPoint) -> Option<usize> {
+        self.nodes
+            .with(|nodes| nodes.iter().rposition(|node| node.rect().contains(pt)))
+    }
+
+    fn output_at(&self, pt: LocalPoint) -> Option<Port> {
+        self.nodes.with(|nodes| {
+            nodes.iter().enumerate().rev().find_map(|(node, n)| {
+                (0..n.outputs.len())
+                    .find(|i| n.output_position(*i).distance_to(pt) < PORT_HIT_RADIUS)
+                    .map(|index| Port { node, index })
+            })
+        })
+    }
+
+    fn input_at(&self, pt: LocalPoint) -> Option<Port> {
+        self.nodes.with(|nodes| {
+            nodes.iter().enumerate().rev().find_map(|(node, n)| {
+                (0..n.inputs.len())
+                    .find(|i| n.input_position(*i).distance_to(pt) < PORT_HIT_RADIUS)
+                    .map(|index| Port { node, index })
+            })
+        })
+    }
+
+    fn select(&self, state: &State<GraphState>, selection: Vec<usize>) {
+        if state.with(|s| s.selection != selection) {
+            if let Some(f) = &self.on_select {
+                f(&selection);
+            }
+            state.with_mut(|s| s.selection = selection);
+        }
+    }
+
+    fn connect(&self, edge: GraphEdge) {
+        match &self.on_connect {
+            Some(f) => f(edge),
+            None => self.edges.with_mut(|edges| {
+                // An input takes one connection.
+                edges.retain(|e| e.to != edge.to);
+                edges.push(edge);
+            }),
+        }
+    }
+
+    fn disconnect(&self, edge: GraphEdge) {
+        match &self.on_disconnect {
+            Some(f) => f(edge),
+            None => self.edges.with_mut(|edges| edges.retain(|e| *e != edge)),
+        }
+    }
+
+    fn begin_drag(&self, pt: LocalPoint) -> Option<GraphDrag> {
+        if let Some(from) = self.output_at(pt) {
+            return Some(GraphDrag::Connect { from, to: pt });
+        }
+
+        // Dragging a connection off an input disconnects it, and picks it
+        // up to connect elsewhere.
+        if let Some(to) = self.input_at(pt) {
+            let edge = self
+                .edges
+                .with(|edges| edges.iter().find(|e| e.to == to).copied());
+            if let Some(edge) = edge {
+                self.disconnect(edge);
+                return Some(GraphDrag::Connect {
+                    from: edge.from,
+                    to: pt,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+impl<N, E> View for GraphCanvas<N, E>
+where
+    N: Binding<Vec<GraphNode>>,
+    E: Binding<Vec<GraphEdge>>,
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!(
+            "node_graph({} nodes, {} edges)",
+            self.nodes.with(|n| n.len()),
+            self.edges.with(|e| e.len())
+        );
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, _vger: &mut dyn Renderer) {
+        let pt = event.position;
+        let state = self.state(id, cx);
+
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } => {
+                let shift = cx.modifiers.shift_key();
+                let drag = if let Some(drag) = self.begin_drag(pt) {
+                    Some(drag)
+                } else if let Some(node) = self.node_at(pt) {
+                    let mut selection = state.with(|s| s.selection.clone());
+                    if shift {
+                        if let Some(i) = selection.iter().position(|n| *n == node) {
+                            selection.remove(i);
+                        } else {
+                            selection.push(node);
+                        }
+                    } else if !selection.contains(&node) {
+                        selection = vec![node];
+                    }
+                    self.select(&state, selection);
+                    Some(GraphDrag::Nodes { last: pt })
+                } else if shift {
+                    Some(GraphDrag::Select {
+                        start: pt,
+                        current: pt,
+                    })
+                } else {
+                    // Leave the touch to pan_zoom.
+                    self.select(&state, vec![]);
+                    None
+                };
+                if drag.is_some() {
+                    cx.touches[*touch] = id;
+                    state.with_mut(|s| s.drag = drag);
+                }
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                match state.with(|s| s.drag) {
+                    Some(GraphDrag::Nodes { last }) => {
+                        let selection = state.with(|s| s.selection.clone());
+                        self.nodes.with_mut(|nodes| {
+                            for i in selection {
+                                if let Some(node) = nodes.get_mut(i) {
+                                    node.position += pt - last;
+                                }
+                            }
+                        });
+                        state.with_mut(|s| s.drag = Some(GraphDrag::Nodes { last: pt }));
+                    }
+                    Some(GraphDrag::Connect { from, .. }) => {
+                        state.with_mut(|s| s.drag = Some(GraphDrag::Connect { from, to: pt }));
+                    }
+                    Some(GraphDrag::Select { start, .. }) => {
+                        let rect = LocalRect::from_points([start, pt]);
+                        let selection = self.nodes.with(|nodes| {
+                            (0..nodes.len())
+                                .filter(|i| nodes[*i].rect().intersects(&rect))
+                                .collect()
+                        });
+                        self.select(&state, selection);
+                        state.with_mut(|s| s.drag = Some(GraphDrag::Select { start, current: pt }));
+                    }
+                    None => (),
+                }
+            }
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                if let Some(GraphDrag::Connect { from, .. }) = state.with(|s| s.drag) {
+                    if let Some(to) = self.input_at(pt).filter(|to| to.node != from.node) {
+                        self.connect(GraphEdge { from, to });
+                    }
+                }
+                state.with_mut(|s| s.drag = None);
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| s.drag = None);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let state = self.state(id, cx);
+        let (drag, selection) = state.with(|s| (s.drag, s.selection.clone()));
+
+        self.nodes.with(|nodes| {
+            let paint = vger.color_paint(TEXT_COLOR);
+            self.edges.with(|edges| {
+                for edge in edges {
+                    let from = nodes.get(edge.from.node);
+                    let to = nodes.get(edge.to.node);
+                    if let (Some(from), Some(to)) = (from, to) {
+                        let a = from.output_position(edge.from.index);
+                        let b = to.input_position(edge.to.index);
+                        draw_edge(vger, a, b, paint);
+                    }
+                }
+            });
+
+            for (i, node) in nodes.iter().enumerate() {
+                let rect = node.rect();
+                let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+                vger.fill_rect(rect, NODE_RADIUS, paint);
+                let outline = if selection.contains(&i) {
+                    AZURE_HIGHLIGHT
+                } else {
+                    GROOVES
+                };
+                let paint = vger.color_paint(outline);
+                vger.stroke_rect(rect.min(), rect.max(), NODE_RADIUS, 1.5, paint);
+
+                let title = [rect.min_x() + 8.0, rect.max_y() - NODE_HEADER / 2.0].into();
+                draw_label(vger, &node.title, NODE_TITLE_SIZE, title, 0.0, TEXT_COLOR);
+
+                let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                for (j, name) in node.inputs.iter().enumerate() {
+                    let pt = node.input_position(j);
+                    vger.fill_circle(pt, PORT_RADIUS, paint);
+                    let label = [pt.x + 2.0 * PORT_RADIUS, pt.y].into();
+                    draw_label(vger, name, PORT_LABEL_SIZE, label, 0.0, TEXT_COLOR);
+                }
+                for (j, name) in node.outputs.iter().enumerate() {
+                    let pt = node.output_position(j);
+                    vger.fill_circle(pt, PORT_RADIUS, paint);
+                    let label = [pt.x - 2.0 * PORT_RADIUS, pt.y].into();
+                    draw_label(vger, name, PORT_LABEL_SIZE, label, 1.0, TEXT_COLOR);
+                }
+            }
+
+            match drag {
+                Some(GraphDrag::Connect { from, to }) => {
+                    if let Some(node) = nodes.get(from.node) {
+                        let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                        draw_edge(vger, node.output_position(from.index), to, paint);
+                    }
+                }
+                Some(GraphDrag::Select { start, current }) => {
+                    let rect = LocalRect::from_points([start, current]);
+                    let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                    vger.stroke_rect(rect.min(), rect.max(), 0.0, 1.0, paint);
+                }
+                _ => (),
+            }
+        });
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        _pt: LocalPoint,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        // The graph has no edges.
+        Some(id)
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            GraphState::default(),
+            id,
+            map,
+            |state: State<GraphState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Canvas));
+        Some(aid)
+    }
+}
+
+impl<N, E> private::Sealed for GraphCanvas<N, E> {}
+
+/// Struct for `node_graph`.
+pub struct NodeGraph<N, E, B> {
+    inner: PanZoomView<GraphCanvas<N, E>, B>,
+}
+
+impl<N, E, B> NodeGraph<N, E, B> {
+    /// Calls `f` when a connection is dropped on an input, instead of
+    /// adding it to the edges.
+    pub fn on_connect(mut self, f: impl Fn(GraphEdge) + 'static) -> Self {
+        self.inner.child.on_connect = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` when a connection is dragged off its input, instead of
+    /// removing it from the edges.
+    pub fn on_disconnect(mut self, f: impl Fn(GraphEdge) + 'static) -> Self {
+        self.inner.child.on_disconnect = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` with the indices of the selected nodes when they change.
+    pub fn on_select(mut self, f: impl Fn(&[usize]) + 'static) -> Self {
+        self.inner.child.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// Keeps the pan and zoom in `binding`.
+    pub fn transform<B2: Binding<PanZoom>>(self, binding: B2) -> NodeGraph<N, E, B2> {
+        NodeGraph {
+            inner: self.inner.transform(binding),
+        }
+    }
+}
+
+impl<N, E, B> View for NodeGraph<N, E, B>
+where
+    N: Binding<Vec<GraphNode>>,
+    E: Binding<Vec<GraphEdge>>,
+    B: Binding<PanZoom>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.inner.print(id, cx)
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.inner.process(event, id, cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.inner.draw(id, cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.inner.layout(id, constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.inner.hittest(id, pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.inner.commands(id, cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.inner.gc(id, cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.inner.access(id, cx, nodes)
+    }
+}
+
+impl<N, E, B> private::Sealed for NodeGraph<N, E, B> {}
+
+/// An editor for a graph of nodes joined by connections from outputs to
+/// inputs, in a `pan_zoom`. Drag nodes to move them, and drag from an
+/// output to an input to connect them. Shift-drag the background to
+/// select nodes; dragging it otherwise pans.
+pub fn node_graph<N, E>(nodes: N, edges: E) -> NodeGraph<N, E, State<PanZoom>>
+where
+    N: Binding<Vec<GraphNode>>,
+    E: Binding<Vec<GraphEdge>>,
+{
+    NodeGraph {
+        inner: pan_zoom(GraphCanvas {
+            nodes,
+            edges,
+            on_connect: None,
+            on_disconnect: None,
+            on_select: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ports() {
+        let node = GraphNode::new("add", [0.0, 0.0])
+            .input("a")
+            .input("b")
+            .output("sum");
+        let rect = node.rect();
+        assert_eq!(
+            rect.height(),
+            NODE_HEADER + 2.0 * PORT_SPACING + NODE_PADDING
+        );
+        assert_eq!(node.input_position(0).x, 0.0);
+        assert_eq!(node.output_position(0).x, NODE_WIDTH);
+        assert!(node.input_position(0).y > node.input_position(1).y);
+        assert!(rect.contains(node.input_position(1) + LocalOffset::new(1.0, 0.0)));
+    }
+}
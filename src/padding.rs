@@ -4,6 +4,19 @@ use crate::*;
 pub struct Padding<V> {
     child: V,
     padding: f32,
+
+    /// Whether the padding grows and shrinks with the control size.
+    scaled: bool,
+}
+
+impl<V> Padding<V> {
+    fn amount(&self, cx: &Context) -> f32 {
+        if self.scaled {
+            self.padding * cx.control_size().scale()
+        } else {
+            self.padding
+        }
+    }
 }
 
 impl<V> View for Padding<V>
@@ -16,27 +29,37 @@ where
         println!("}}");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let padding = self.amount(cx);
         let mut local_event = event.clone();
-        local_event.position -= LocalOffset::new(self.padding, self.padding);
+        local_event.position -= LocalOffset::new(padding, padding);
         self.child.process(&local_event, id.child(&0), cx, vger);
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let padding = self.amount(cx);
         vger.save();
-        vger.translate([self.padding, self.padding]);
+        vger.translate([padding, padding].into());
         self.child.draw(id.child(&0), cx, vger);
         vger.restore();
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let padding = self.amount(cx);
         let child_size = self.child.layout(
             id.child(&0),
-            sz - [2.0 * self.padding, 2.0 * self.padding].into(),
+            constraints.deflate([2.0 * padding, 2.0 * padding].into()),
             cx,
             vger,
         );
-        child_size + LocalSize::new(2.0 * self.padding, 2.0 * self.padding)
+        offset_guides(&mut cx.guides, [padding, padding].into());
+        child_size + LocalSize::new(2.0 * padding, 2.0 * padding)
     }
 
     fn hittest(
@@ -44,11 +67,12 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
+        let padding = self.amount(cx);
         self.child.hittest(
             id.child(&0),
-            pt - LocalOffset::new(self.padding, self.padding),
+            pt - LocalOffset::new(padding, padding),
             cx,
             vger,
         )
@@ -70,6 +94,22 @@ where
     ) -> Option<accesskit::NodeId> {
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        let padding = self.amount(cx);
+        self.child
+            .intrinsic_size(id.child(&0), cx, vger)
+            .map(|size| size + LocalSize::new(2.0 * padding, 2.0 * padding))
+    }
 }
 
 pub enum PaddingParam {
@@ -99,6 +139,17 @@ where
                 PaddingParam::Auto => 5.0,
                 PaddingParam::Px(px) => px,
             },
+            scaled: false,
+        }
+    }
+
+    /// Padding for the label of a control, which grows and shrinks with
+    /// the control size.
+    pub(crate) fn control(child: V) -> Self {
+        Self {
+            child,
+            padding: 5.0,
+            scaled: true,
         }
     }
 }
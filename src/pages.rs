@@ -0,0 +1,349 @@
+use crate::*;
+use std::time::{Duration, Instant};
+
+/// How long pages take to slide into place.
+pub const PAGE_ANIMATION_TIME: Duration = Duration::from_millis(250);
+
+/// Fraction of a page a swipe must cover to turn it.
+const PAGE_SWIPE_THRESHOLD: f32 = 0.25;
+
+const PAGE_DOT_RADIUS: f32 = 3.0;
+const PAGE_DOT_SPACING: f32 = 12.0;
+
+#[derive(Clone, Default)]
+struct PagesState {
+    /// Positions animated from and to, in pages.
+    from: f32,
+    to: Option<f32>,
+    start: Option<Instant>,
+
+    /// Where a swipe began, and how far it has moved.
+    drag: Option<LocalPoint>,
+    drag_offset: f32,
+}
+
+impl PagesState {
+    /// The position at `now`, and whether it's still animating.
+    fn position(&self, now: Instant, width: f32) -> (f32, bool) {
+        let to = self.to.unwrap_or_default();
+        if self.drag.is_some() {
+            return (to - self.drag_offset / width.max(1.0), false);
+        }
+        let t = match self.start {
+            Some(start) => (now - start).as_secs_f32() / PAGE_ANIMATION_TIME.as_secs_f32(),
+            None => 1.0,
+        };
+        if t >= 1.0 {
+            return (to, false);
+        }
+        let t = 1.0 - (1.0 - t).powi(3);
+        (self.from + (to - self.from) * t, true)
+    }
+}
+
+/// The page a swipe of `offset` points ends on, from `index`.
+fn swipe_target(index: usize, count: usize, offset: f32, width: f32) -> usize {
+    if offset < -width * PAGE_SWIPE_THRESHOLD {
+        (index + 1).min(count.saturating_sub(1))
+    } else if offset > width * PAGE_SWIPE_THRESHOLD {
+        index.saturating_sub(1)
+    } else {
+        index
+    }
+}
+
+/// Struct for `pages`.
+pub struct Pages<V, B> {
+    children: Vec<V>,
+    index: B,
+    swipe: bool,
+    indicator: bool,
+}
+
+impl<V, B> Pages<V, B> {
+    /// Whether dots along the bottom show which page is showing.
+    pub fn indicator(self, indicator: bool) -> Self {
+        Self { indicator, ..self }
+    }
+
+    /// Whether swiping and arrow keys turn the pages, rather than just
+    /// `index`.
+    pub fn swipe(self, swipe: bool) -> Self {
+        Self { swipe, ..self }
+    }
+}
+
+impl<V, B> Pages<V, B>
+where
+    V: View,
+    B: Binding<usize>,
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<PagesState> {
+        cx.with_state(
+            PagesState::default(),
+            id,
+            |state: State<PagesState>, _cx| state,
+        )
+    }
+
+    fn current(&self) -> usize {
+        self.index.get().min(self.children.len().saturating_sub(1))
+    }
+
+    /// Turns to `index`, sliding from `position`.
+    fn turn(&self, state: &State<PagesState>, position: f32, index: usize) {
+        state.with_mut(|s| {
+            s.from = position;
+            s.to = Some(index as f32);
+            s.start = Some(Instant::now());
+            s.drag = None;
+            s.drag_offset = 0.0;
+        });
+        self.index.set(index);
+    }
+
+    /// The position to draw at, in pages, starting an animation if the
+    /// index has changed.
+    fn position(&self, id: ViewID, cx: &mut Context, width: f32) -> f32 {
+        let now = Instant::now();
+        let state = self.state(id, cx);
+        let target = self.current() as f32;
+        if state.with(|s| s.to != Some(target)) {
+            let first = state.with(|s| s.to.is_none());
+            let (from, _) = state.with(|s| s.position(now, width));
+            state.with_mut(|s| {
+                s.from = from;
+                s.to = Some(target);
+                s.start = if first { None } else { Some(now) };
+                s.drag = None;
+            });
+        }
+        let (position, animating) = state.with(|s| s.position(now, width));
+        if animating {
+            if let Some(window) = &cx.window {
+                window.request_redraw();
+            }
+        }
+        position
+    }
+}
+
+impl<V, B> View for Pages<V, B>
+where
+    V: View,
+    B: Binding<usize>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("pages({}) {{", self.current());
+        for (i, child) in self.children.iter().enumerate() {
+            child.print(id.child(&i), cx);
+        }
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let current = self.current();
+        if let Some(child) = self.children.get(current) {
+            child.process(event, id.child(&current), cx, vger);
+        }
+        if !self.swipe {
+            return;
+        }
+
+        let rect = cx.layout.entry(id).or_default().rect;
+        let inside = rect.contains(event.position);
+        let state = self.state(id, cx);
+        let width = rect.width();
+        let last = self.children.len().saturating_sub(1);
+
+        match &event.kind {
+            // Swipes the page doesn't take turn it.
+            EventKind::TouchBegin { id: touch }
+                if inside && cx.touches[*touch] == ViewID::default() =>
+            {
+                cx.touches[*touch] = id;
+                state.with_mut(|s| {
+                    s.drag = Some(event.position);
+                    s.drag_offset = 0.0;
+                });
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                if let Some(start) = state.with(|s| s.drag) {
+                    let mut offset = event.position.x - start.x;
+
+                    // Resist swiping past the ends.
+                    if (current == 0 && offset > 0.0) || (current == last && offset < 0.0) {
+                        offset /= 3.0;
+                    }
+                    state.with_mut(|s| s.drag_offset = offset);
+                }
+            }
+            EventKind::TouchEnd { id: touch } | EventKind::TouchCancel { id: touch }
+                if cx.touches[*touch] == id =>
+            {
+                cx.touches[*touch] = ViewID::default();
+                let offset = state.with(|s| s.drag_offset);
+                let position = current as f32 - offset / width.max(1.0);
+                let target = swipe_target(current, self.children.len(), offset, width);
+                self.turn(&state, position, target);
+            }
+
+            // Arrow keys turn the page under the mouse, unless something
+            // else has the keyboard.
+            EventKind::Key(key, _) if inside && cx.focused_id.is_none() => {
+                let target = match key {
+                    KeyPress::ArrowLeft => current.saturating_sub(1),
+                    KeyPress::ArrowRight => (current + 1).min(last),
+                    _ => current,
+                };
+                if target != current {
+                    let (position, _) = state.with(|s| s.position(Instant::now(), width));
+                    self.turn(&state, position, target);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let position = self.position(id, cx, rect.width());
+
+        vger.save();
+        vger.clip(rect);
+        for (i, child) in self.children.iter().enumerate() {
+            let x = (i as f32 - position) * rect.width();
+            if x.abs() < rect.width() {
+                vger.save();
+                vger.translate([x, 0.0].into());
+                child.draw(id.child(&i), cx, vger);
+                vger.restore();
+            }
+        }
+        vger.restore();
+
+        if self.indicator && self.children.len() > 1 {
+            let n = self.children.len();
+            let x0 = rect.center().x - (n - 1) as f32 * PAGE_DOT_SPACING / 2.0;
+            let y = rect.min_y() + 2.0 * PAGE_DOT_SPACING;
+            let current = self.current();
+            for i in 0..n {
+                let color = if i == current {
+                    AZURE_HIGHLIGHT
+                } else {
+                    MEDIUM_GRAY
+                };
+                let paint = vger.color_paint(color);
+                let center = [x0 + i as f32 * PAGE_DOT_SPACING, y].into();
+                vger.fill_circle(center, PAGE_DOT_RADIUS, paint);
+            }
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        for (i, child) in self.children.iter().enumerate() {
+            child.layout(id.child(&i), constraints, cx, vger);
+        }
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let current = self.current();
+        let hit = match self.children.get(current) {
+            Some(child) => child.hittest(id.child(&current), pt, cx, vger),
+            None => None,
+        };
+        match hit {
+            Some(hit) => Some(hit),
+            None if self.swipe && rect.contains(pt) => Some(id),
+            None => None,
+        }
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        let current = self.current();
+        if let Some(child) = self.children.get(current) {
+            child.commands(id.child(&current), cx, cmds);
+        }
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            PagesState::default(),
+            id,
+            map,
+            |state: State<PagesState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+
+        // Every page's state stays alive, not just the one showing.
+        for (i, child) in self.children.iter().enumerate() {
+            child.gc(id.child(&i), cx, map);
+        }
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let current = self.current();
+        let child = self.children.get(current)?;
+        child.access(id.child(&current), cx, nodes)
+    }
+}
+
+impl<V, B> private::Sealed for Pages<V, B> {}
+
+/// Shows `children` one at a time, with the index of the one showing in
+/// `index`. Swipe or use the arrow keys to turn the pages, which snap
+/// into place. Dots along the bottom show where you are. Pages keep
+/// their state while they're off screen. Use `any_view` for pages of
+/// different types.
+pub fn pages<V: View, B: Binding<usize>>(children: Vec<V>, index: B) -> Pages<V, B> {
+    Pages {
+        children,
+        index,
+        swipe: true,
+        indicator: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swipe_target() {
+        assert_eq!(swipe_target(1, 3, -50.0, 100.0), 2);
+        assert_eq!(swipe_target(1, 3, 50.0, 100.0), 0);
+        assert_eq!(swipe_target(1, 3, 10.0, 100.0), 1);
+        assert_eq!(swipe_target(2, 3, -50.0, 100.0), 2);
+        assert_eq!(swipe_target(0, 3, 50.0, 100.0), 0);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::*;
 
 /// Specifies how a region should be filled.
+#[derive(Clone, Copy, Debug)]
 pub enum Paint {
     /// Fill a region with a solid color.
     Color(Color),
@@ -14,7 +15,46 @@ pub enum Paint {
     },
 }
 
+/// Identifies a paint by value so identical paints can share
+/// a single vger paint within a frame.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum PaintKey {
+    Color([u32; 4]),
+    Gradient([u32; 12]),
+}
+
+fn color_bits(color: &Color) -> [u32; 4] {
+    [
+        color.r.to_bits(),
+        color.g.to_bits(),
+        color.b.to_bits(),
+        color.a.to_bits(),
+    ]
+}
+
 impl Paint {
+    /// The same paint with its alpha multiplied by `opacity`.
+    pub fn with_opacity(self, opacity: f32) -> Paint {
+        if opacity == 1.0 {
+            return self;
+        }
+        let fade = |c: Color| c.alpha(c.a * opacity);
+        match self {
+            Paint::Color(color) => Paint::Color(fade(color)),
+            Paint::Gradient {
+                start,
+                end,
+                inner_color,
+                outer_color,
+            } => Paint::Gradient {
+                start,
+                end,
+                inner_color: fade(inner_color),
+                outer_color: fade(outer_color),
+            },
+        }
+    }
+
     pub fn vger_paint(&self, vger: &mut VGER) -> PaintIndex {
         match self {
             Paint::Color(color) => vger.color_paint(*color),
@@ -26,4 +66,48 @@ impl Paint {
             } => vger.linear_gradient(*start, *end, *inner_color, *outer_color, 0.0),
         }
     }
+
+    pub(crate) fn key(&self) -> PaintKey {
+        match self {
+            Paint::Color(color) => PaintKey::Color(color_bits(color)),
+            Paint::Gradient {
+                start,
+                end,
+                inner_color,
+                outer_color,
+            } => {
+                let inner = color_bits(inner_color);
+                let outer = color_bits(outer_color);
+                PaintKey::Gradient([
+                    start.x.to_bits(),
+                    start.y.to_bits(),
+                    end.x.to_bits(),
+                    end.y.to_bits(),
+                    inner[0],
+                    inner[1],
+                    inner[2],
+                    inner[3],
+                    outer[0],
+                    outer[1],
+                    outer[2],
+                    outer[3],
+                ])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_paint_key() {
+        let a = Paint::Color(AZURE_HIGHLIGHT);
+        let b = Paint::Color(AZURE_HIGHLIGHT);
+        let c = Paint::Color(RED_HIGHLIGHT.alpha(0.5));
+        assert_eq!(a.key(), b.key());
+        assert_ne!(a.key(), c.key());
+    }
 }
@@ -0,0 +1,304 @@
+use crate::*;
+use std::ops::Range;
+
+/// How much one point of scrolling zooms `pan_zoom`, as a power of e.
+const PAN_ZOOM_WHEEL_RATE: f32 = 0.005;
+
+/// How `pan_zoom` shows its content: a point `p` in the content is drawn
+/// at `p * zoom + offset`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PanZoom {
+    pub offset: LocalOffset,
+    pub zoom: f32,
+}
+
+impl Default for PanZoom {
+    fn default() -> Self {
+        Self {
+            offset: LocalOffset::zero(),
+            zoom: 1.0,
+        }
+    }
+}
+
+impl PanZoom {
+    /// Maps a point in the view to the content's coordinates.
+    pub fn to_content(&self, pt: LocalPoint) -> LocalPoint {
+        ((pt - self.offset).to_vector() / self.zoom).to_point()
+    }
+
+    /// Maps a point in the content to the view's coordinates.
+    pub fn from_content(&self, pt: LocalPoint) -> LocalPoint {
+        (pt.to_vector() * self.zoom + self.offset).to_point()
+    }
+
+    /// Changes the zoom, keeping the content under `anchor`, in the view's
+    /// coordinates, in place.
+    pub fn zoomed(&self, zoom: f32, anchor: LocalPoint) -> Self {
+        let content = self.to_content(anchor);
+        Self {
+            offset: anchor.to_vector() - content.to_vector() * zoom,
+            zoom,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct PanZoomState {
+    /// The transform, if there's no binding for it.
+    transform: PanZoom,
+
+    /// Where the dragging mouse or finger was last.
+    drag: Option<LocalPoint>,
+
+    pair: TouchPair,
+
+    /// The transform when a pinch began.
+    pinch_start: PanZoom,
+}
+
+/// Struct for `pan_zoom`.
+pub struct PanZoomView<V, B> {
+    pub(crate) child: V,
+    binding: Option<B>,
+    zoom_range: (f32, f32),
+}
+
+impl<V, B> PanZoomView<V, B> {
+    /// Keeps the transform in `binding`, so it can be read, saved or set.
+    pub fn transform<B2: Binding<PanZoom>>(self, binding: B2) -> PanZoomView<V, B2> {
+        PanZoomView {
+            child: self.child,
+            binding: Some(binding),
+            zoom_range: self.zoom_range,
+        }
+    }
+
+    /// Limits how far the content can be zoomed out and in.
+    pub fn zoom_range(self, range: Range<f32>) -> Self {
+        Self {
+            zoom_range: (range.start, range.end),
+            ..self
+        }
+    }
+}
+
+impl<V, B> PanZoomView<V, B>
+where
+    V: View,
+    B: Binding<PanZoom>,
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<PanZoomState> {
+        cx.with_state(
+            PanZoomState::default(),
+            id,
+            |state: State<PanZoomState>, _cx| state,
+        )
+    }
+
+    fn get(&self, id: ViewID, cx: &mut Context) -> PanZoom {
+        match &self.binding {
+            Some(binding) => binding.get(),
+            None => self.state(id, cx).with(|s| s.transform),
+        }
+    }
+
+    fn set(&self, id: ViewID, cx: &mut Context, transform: PanZoom) {
+        match &self.binding {
+            Some(binding) => binding.set(transform),
+            None => self.state(id, cx).with_mut(|s| s.transform = transform),
+        }
+    }
+
+    fn clamp_zoom(&self, zoom: f32) -> f32 {
+        zoom.clamp(self.zoom_range.0, self.zoom_range.1)
+    }
+}
+
+impl<V, B> View for PanZoomView<V, B>
+where
+    V: View,
+    B: Binding<PanZoom>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("pan_zoom({:?}) {{", self.get(id, cx));
+        (self.child).print(id.child(&0), cx);
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let inside = rect.contains(event.position);
+        let t = self.get(id, cx);
+
+        // The wheel zooms at the mouse.
+        if let EventKind::Scroll { delta } = &event.kind {
+            if inside {
+                let zoom = self.clamp_zoom(t.zoom * (-delta.y * PAN_ZOOM_WHEEL_RATE).exp());
+                self.set(id, cx, t.zoomed(zoom, event.position));
+            }
+            return;
+        }
+
+        let mut local_event = event.clone();
+        local_event.position = t.to_content(event.position);
+        self.child.process(&local_event, id.child(&0), cx, vger);
+
+        // Pinching zooms about the middle of the touches, and pans with it.
+        let state = self.state(id, cx);
+        let change = state.with_mut(|s| s.pair.update(event, inside, cx));
+        match change {
+            Some((GestureState::Began, _, _)) => state.with_mut(|s| {
+                s.pinch_start = t;
+                s.drag = None;
+            }),
+            Some((_, start, current)) => {
+                let start_t = state.with(|s| s.pinch_start);
+                let middle = |p: [LocalPoint; 2]| p[0].lerp(p[1], 0.5);
+                let anchor = start_t.to_content(middle(start));
+                let zoom = self.clamp_zoom(start_t.zoom * pinch_scale(start, current));
+                let offset = middle(current).to_vector() - anchor.to_vector() * zoom;
+                self.set(id, cx, PanZoom { offset, zoom });
+            }
+            None => (),
+        }
+
+        // Dragging pans, unless the content took the touch.
+        match &event.kind {
+            EventKind::TouchBegin { id: touch }
+                if inside && change.is_none() && cx.touches[*touch] == ViewID::default() =>
+            {
+                cx.touches[*touch] = id;
+                state.with_mut(|s| s.drag = Some(event.position));
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                if let Some(last) = state.with(|s| s.drag) {
+                    let offset = t.offset + (event.position - last);
+                    self.set(id, cx, PanZoom { offset, ..t });
+                    state.with_mut(|s| s.drag = Some(event.position));
+                }
+                cx.set_cursor(CursorIcon::Grabbing);
+            }
+            EventKind::TouchEnd { id: touch } | EventKind::TouchCancel { id: touch }
+                if cx.touches[*touch] == id =>
+            {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| s.drag = None);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let t = self.get(id, cx);
+
+        vger.save();
+        vger.clip(rect);
+        vger.translate(t.offset);
+        vger.scale(t.zoom);
+        self.child.draw(id.child(&0), cx, vger);
+        vger.restore();
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        self.child.layout(id.child(&0), constraints, cx, vger);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if !rect.contains(pt) {
+            return None;
+        }
+        let t = self.get(id, cx);
+        self.child
+            .hittest(id.child(&0), t.to_content(pt), cx, vger)
+            .or(Some(id))
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            PanZoomState::default(),
+            id,
+            map,
+            |state: State<PanZoomState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, B> private::Sealed for PanZoomView<V, B> {}
+
+/// A canvas for `child` without edges: dragging pans, and the mouse wheel
+/// or pinching zooms. Events reach `child` in its own coordinates, so it
+/// can be laid out and hit tested as if it weren't moved. Use `transform`
+/// to read or set where it's looking.
+pub fn pan_zoom<V: View>(child: V) -> PanZoomView<V, State<PanZoom>> {
+    PanZoomView {
+        child,
+        binding: None,
+        zoom_range: (0.1, 10.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoomed() {
+        let t = PanZoom {
+            offset: [10.0, 20.0].into(),
+            zoom: 2.0,
+        };
+        let anchor = LocalPoint::new(50.0, 60.0);
+        let content = t.to_content(anchor);
+        assert_eq!(t.from_content(content), anchor);
+
+        let zoomed = t.zoomed(4.0, anchor);
+        assert_eq!(zoomed.zoom, 4.0);
+        assert_eq!(zoomed.from_content(content), anchor);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::*;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Scratch buffers and environment values kept from one pass over the
+/// view tree to the next. The tree is laid out and drawn again every time
+/// something changes, so views which need somewhere to work take a buffer
+/// from here and give it back when they're done, and `with_env` reuses the
+/// boxes its values went in, rather than allocating new ones each pass.
+/// Laying out a tree of stacks a second time allocates nothing.
+#[derive(Default)]
+pub(crate) struct Pool {
+    floats: Vec<Vec<f32>>,
+    indices: Vec<Vec<usize>>,
+    guides: Vec<HashMap<Alignment, f32>>,
+    env_values: HashMap<TypeId, Vec<Rc<dyn Any>>>,
+}
+
+impl Pool {
+    /// An empty list of numbers.
+    pub fn floats(&mut self) -> Vec<f32> {
+        self.floats.pop().unwrap_or_default()
+    }
+
+    /// Keeps a list from `floats` for reuse.
+    pub fn recycle_floats(&mut self, mut floats: Vec<f32>) {
+        floats.clear();
+        self.floats.push(floats);
+    }
+
+    /// An empty list of indices.
+    pub fn indices(&mut self) -> Vec<usize> {
+        self.indices.pop().unwrap_or_default()
+    }
+
+    /// Keeps a list from `indices` for reuse.
+    pub fn recycle_indices(&mut self, mut indices: Vec<usize>) {
+        indices.clear();
+        self.indices.push(indices);
+    }
+
+    /// An empty set of alignment guides.
+    pub fn guides(&mut self) -> HashMap<Alignment, f32> {
+        self.guides.pop().unwrap_or_default()
+    }
+
+    /// Keeps a set of guides from `guides` for reuse.
+    pub fn recycle_guides(&mut self, mut guides: HashMap<Alignment, f32>) {
+        guides.clear();
+        self.guides.push(guides);
+    }
+
+    /// `value` ready to put in the environment, in a box given back to
+    /// `recycle_env_value` earlier if there is one.
+    pub fn env_value<E: 'static>(&mut self, value: E) -> Rc<dyn Any> {
+        if let Some(mut boxed) = self
+            .env_values
+            .get_mut(&TypeId::of::<E>())
+            .and_then(|values| values.pop())
+        {
+            if let Some(slot) = Rc::get_mut(&mut boxed).and_then(|any| any.downcast_mut::<E>()) {
+                *slot = value;
+                return boxed;
+            }
+        }
+        Rc::new(value)
+    }
+
+    /// Keeps an environment value taken out of the environment, unless
+    /// something else still holds it.
+    pub fn recycle_env_value<E: 'static>(&mut self, value: Rc<dyn Any>) {
+        if Rc::strong_count(&value) == 1 {
+            self.env_values
+                .entry(TypeId::of::<E>())
+                .or_default()
+                .push(value);
+        }
+    }
+}
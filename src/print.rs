@@ -0,0 +1,115 @@
+use crate::*;
+
+/// A4 paper, in points.
+pub const PAGE_A4: LocalSize = LocalSize::new(595.0, 842.0);
+
+/// US Letter paper, in points.
+pub const PAGE_LETTER: LocalSize = LocalSize::new(612.0, 792.0);
+
+/// How to lay out a view on paper, for `print_view`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrintOptions {
+    pub page_size: LocalSize,
+
+    /// Space left blank around each page.
+    pub margin: f32,
+
+    /// Shown in the print dialog and used as the default file name.
+    pub title: String,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            page_size: PAGE_A4,
+            margin: 36.0,
+            title: "rui".into(),
+        }
+    }
+}
+
+/// Lays out `view` within the margins of a page and returns an SVG
+/// document for each page. Views taller than a page are split across as
+/// many pages as they need.
+pub fn paginate(view: &impl View, options: &PrintOptions) -> Vec<String> {
+    let margin = options.margin;
+    let content = LocalSize::new(
+        (options.page_size.width - 2.0 * margin).max(1.0),
+        (options.page_size.height - 2.0 * margin).max(1.0),
+    );
+
+    let mut cx = Context::headless();
+    let mut renderer = SvgRenderer::new(options.page_size);
+    let size = cx.layout_root(view, content, &mut renderer);
+    let pages = (size.height / content.height).ceil().max(1.0) as usize;
+
+    (0..pages)
+        .map(|page| {
+            // Layout is y-up, so the first page shows the top of the view.
+            let bottom = size.height - (page + 1) as f32 * content.height;
+            let mut renderer = SvgRenderer::new(options.page_size);
+            renderer.clip(LocalRect::new([margin, margin].into(), content));
+            renderer.translate([margin, margin - bottom].into());
+            view.draw(cx.root_id, &mut cx, &mut renderer);
+            renderer.finish()
+        })
+        .collect()
+}
+
+/// An HTML document with `pages` on separate sheets, which shows the print
+/// dialog when opened.
+fn print_html(pages: &[String], options: &PrintOptions) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<title>{}</title>\n<style>\n\
+         @page {{ size: {}px {}px; margin: 0 }}\n\
+         body {{ margin: 0 }}\n\
+         svg {{ display: block; break-after: page }}\n\
+         </style>\n</head>\n<body onload=\"window.print()\">\n{}</body>\n</html>\n",
+        options.title.replace('<', "&lt;"),
+        options.page_size.width,
+        options.page_size.height,
+        pages.concat()
+    )
+}
+
+/// Prints `view`, laid out across pages as with `paginate`. The pages open
+/// in the browser, which shows its print dialog, so they can be printed or
+/// saved as a PDF.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn print_view(view: &impl View, options: PrintOptions) -> std::io::Result<()> {
+    let pages = paginate(view, &options);
+    let name: String = options
+        .title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let path = std::env::temp_dir().join(format!("{}.html", name));
+    std::fs::write(&path, print_html(&pages, &options))?;
+    open_url(&path.to_string_lossy())
+}
+
+impl Context {
+    /// See `print_view`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn print(&self, view: &impl View, options: PrintOptions) -> std::io::Result<()> {
+        print_view(view, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        let options = PrintOptions::default();
+        let tall = rectangle().size([100.0, 2000.0]);
+        // 770 points fit between the margins of A4.
+        let pages = paginate(&tall, &options);
+        assert_eq!(pages.len(), 3);
+        assert!(print_html(&pages, &options).contains("size: 595px 842px"));
+
+        let short = rectangle().size([100.0, 100.0]);
+        assert_eq!(paginate(&short, &options).len(), 1);
+    }
+}
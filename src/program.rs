@@ -0,0 +1,241 @@
+use crate::*;
+use std::any::Any;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+type Effect<Msg> = Pin<Box<dyn Future<Output = Msg> + Send>>;
+
+/// Work for a `program` to do after an update, which ends in more
+/// messages.
+pub struct Cmd<Msg> {
+    messages: Vec<Msg>,
+    effects: Vec<Effect<Msg>>,
+}
+
+impl<Msg> Cmd<Msg> {
+    /// Nothing to do.
+    pub fn none() -> Self {
+        Self {
+            messages: vec![],
+            effects: vec![],
+        }
+    }
+
+    /// Sends `msg` straight back to `update`.
+    pub fn msg(msg: Msg) -> Self {
+        Self {
+            messages: vec![msg],
+            effects: vec![],
+        }
+    }
+
+    /// Runs `future` on another thread, and sends what it returns to
+    /// `update`. It's aborted if the program goes away first.
+    pub fn perform(future: impl Future<Output = Msg> + Send + 'static) -> Self {
+        Self {
+            messages: vec![],
+            effects: vec![Box::pin(future)],
+        }
+    }
+
+    /// Does all of `cmds`.
+    pub fn batch(cmds: impl IntoIterator<Item = Cmd<Msg>>) -> Self {
+        let mut batch = Self::none();
+        for cmd in cmds {
+            batch.messages.extend(cmd.messages);
+            batch.effects.extend(cmd.effects);
+        }
+        batch
+    }
+}
+
+/// Struct for `program_view`.
+pub struct Program<M, Msg, U, F> {
+    init: M,
+    update: U,
+    view: F,
+    phantom: PhantomData<fn(Msg)>,
+}
+
+impl<M, Msg, U, V, F> Program<M, Msg, U, F>
+where
+    M: Clone + 'static,
+    Msg: Any + Clone + Send + Sync,
+    U: Fn(Msg, &mut M) -> Cmd<Msg> + 'static,
+    V: View,
+    F: Fn(&M) -> V + 'static,
+{
+    fn model(&self, id: ViewID, cx: &mut Context) -> State<M> {
+        cx.get_state(id, || self.init.clone())
+    }
+
+    fn body(&self, id: ViewID, cx: &mut Context) -> V {
+        self.model(id, cx).with(|model| (self.view)(model))
+    }
+
+    /// Updates the model with `msg`, and starts on what the update asks
+    /// for.
+    fn update(&self, msg: Msg, id: ViewID, cx: &mut Context) {
+        let cmd = self
+            .model(id, cx)
+            .with_mut(|model| (self.update)(msg, model));
+        for msg in cmd.messages {
+            cx.publish(msg);
+        }
+        for effect in cmd.effects {
+            // Sent back from the task's thread, so it's a user event.
+            cx.spawn(id, async move { injector().send(effect.await) });
+        }
+    }
+}
+
+impl<M, Msg, U, V, F> View for Program<M, Msg, U, F>
+where
+    M: Clone + 'static,
+    Msg: Any + Clone + Send + Sync,
+    U: Fn(Msg, &mut M) -> Cmd<Msg> + 'static,
+    V: View,
+    F: Fn(&M) -> V + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("program(");
+        self.body(id, cx).print(id.child(&0), cx);
+        println!(")");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let msg = match &event.kind {
+            EventKind::Message(message) => message.downcast_ref::<Msg>().cloned(),
+            EventKind::User(payload) => payload.downcast_ref::<Msg>().cloned(),
+            _ => None,
+        };
+        match msg {
+            Some(msg) => self.update(msg, id, cx),
+            None => self.body(id, cx).process(event, id.child(&0), cx, vger),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(id, cx).draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.body(id, cx)
+            .layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.body(id, cx).hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.body(id, cx).commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let model = self.model(id, cx);
+        map.insert(id, Box::new(model));
+        self.body(id, cx).gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.body(id, cx).access(id.child(&0), cx, nodes)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.body(id, cx).intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<M, Msg, U, F> private::Sealed for Program<M, Msg, U, F> {}
+
+/// The view for a `program`, to run it as part of a bigger view.
+pub fn program_view<M, Msg, U, V, F>(init: M, update: U, view: F) -> Program<M, Msg, U, F>
+where
+    M: Clone + 'static,
+    Msg: Any + Clone + Send + Sync,
+    U: Fn(Msg, &mut M) -> Cmd<Msg> + 'static,
+    V: View,
+    F: Fn(&M) -> V + 'static,
+{
+    Program {
+        init,
+        update,
+        view,
+        phantom: PhantomData,
+    }
+}
+
+/// Runs an app in the Elm style. The model starts as `init` and is shown
+/// by `view`. The views send messages with `emits` or `emit`, and
+/// `update` changes the model for each one, returning a `Cmd` for any
+/// more work, like loading something.
+pub fn program<M, Msg, U, V, F>(init: M, update: U, view: F)
+where
+    M: Clone + 'static,
+    Msg: Any + Clone + Send + Sync,
+    U: Fn(Msg, &mut M) -> Cmd<Msg> + 'static,
+    V: View + 'static,
+    F: Fn(&M) -> V + 'static,
+{
+    rui(program_view(init, update, view))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    enum Msg {
+        Add(i32),
+        Double,
+    }
+
+    #[test]
+    fn test_program() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = program_view(
+            1,
+            |msg, count: &mut i32| match msg {
+                Msg::Add(n) => {
+                    *count += n;
+                    Cmd::msg(Msg::Double)
+                }
+                Msg::Double => {
+                    *count *= 2;
+                    Cmd::none()
+                }
+            },
+            |_| rectangle().emits(Msg::Add(2)),
+        );
+        cx.layout_root(&ui, size, &mut vger);
+
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        assert_eq!(ui.model(cx.root_id, &mut cx).get(), 6);
+    }
+}
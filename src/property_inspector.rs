@@ -0,0 +1,159 @@
+use crate::*;
+use accesskit::Role;
+
+pub const PROPERTY_LABEL_SIZE: u32 = 14;
+
+/// A value `inspector` knows how to edit. Derive it for enums without
+/// fields with `#[derive(Property)]`.
+pub trait Property: Clone + 'static {
+    fn editor<B: Binding<Self>>(binding: B) -> AnyView;
+}
+
+impl Property for bool {
+    fn editor<B: Binding<Self>>(binding: B) -> AnyView {
+        any_view(toggle(binding))
+    }
+}
+
+impl Property for f32 {
+    fn editor<B: Binding<Self>>(binding: B) -> AnyView {
+        let value = binding.get();
+        any_view(hstack((
+            hslider(binding),
+            text(&format!("{:.2}", value)).font_size(PROPERTY_LABEL_SIZE),
+        )))
+    }
+}
+
+impl Property for String {
+    fn editor<B: Binding<Self>>(binding: B) -> AnyView {
+        any_view(text_field(binding))
+    }
+}
+
+/// A field of an `Inspectable` model, with its editor.
+pub struct PropertyRow {
+    pub name: String,
+    pub group: Option<String>,
+    pub editor: AnyView,
+}
+
+/// A model whose fields `inspector` can edit. Derive it with
+/// `#[derive(Inspectable)]`.
+pub trait Inspectable: Clone + 'static {
+    fn properties<B: Binding<Self>>(binding: B) -> Vec<PropertyRow>;
+}
+
+/// Chooses one of `options`, shown side by side.
+pub fn picker<T: Clone + PartialEq + 'static>(
+    value: impl Binding<T>,
+    options: Vec<(String, T)>,
+) -> impl View {
+    let current = value.get();
+    hstack(
+        options
+            .into_iter()
+            .map(|(label, option)| {
+                let color = if option == current {
+                    AZURE_HIGHLIGHT_BACKGROUND
+                } else {
+                    BUTTON_BACKGROUND_COLOR
+                };
+                let value = value.clone();
+                text(&label)
+                    .padding(Auto)
+                    .background(rectangle().corner_radius(BUTTON_CORNER_RADIUS).color(color))
+                    .tap(move || value.set(option.clone()))
+                    .role(Role::RadioButton)
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Groups rows by their group, in the order groups first appear. Rows
+/// without a group come first.
+fn group_rows(rows: Vec<PropertyRow>, query: &str) -> Vec<(Option<String>, Vec<PropertyRow>)> {
+    let query = query.to_lowercase();
+    let mut groups: Vec<(Option<String>, Vec<PropertyRow>)> = vec![(None, vec![])];
+    for row in rows {
+        if !row.name.to_lowercase().contains(&query) {
+            continue;
+        }
+        match groups.iter_mut().find(|(g, _)| *g == row.group) {
+            Some((_, rows)) => rows.push(row),
+            None => groups.push((row.group.clone(), vec![row])),
+        }
+    }
+    groups.retain(|(_, rows)| !rows.is_empty());
+    groups
+}
+
+/// Edits the fields of `model` with controls chosen by their types: a
+/// toggle for `bool`, a slider for `f32`, a text field for `String` and a
+/// picker for enums deriving `Property`. Fields can be grouped, and found
+/// by name with the search field at the top.
+pub fn inspector<T: Inspectable>(model: impl Binding<T>) -> impl View {
+    state(String::new(), move |query| {
+        let groups = group_rows(T::properties(model.clone()), &query.get());
+        let groups = groups
+            .into_iter()
+            .map(|(group, rows)| {
+                let rows = rows
+                    .into_iter()
+                    .map(|row| hstack((text(&row.name).font_size(PROPERTY_LABEL_SIZE), row.editor)))
+                    .collect::<Vec<_>>();
+                match group {
+                    Some(group) => any_view(group_box(&group, vstack(rows))),
+                    None => any_view(vstack(rows)),
+                }
+            })
+            .collect::<Vec<_>>();
+        vstack((search_field(query), vstack(groups)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, group: Option<&str>) -> PropertyRow {
+        PropertyRow {
+            name: name.into(),
+            group: group.map(|g| g.into()),
+            editor: any_view(text(name)),
+        }
+    }
+
+    #[test]
+    fn test_group_rows() {
+        let rows = vec![
+            row("Width", Some("Size")),
+            row("Name", None),
+            row("Color", Some("Style")),
+            row("Height", Some("Size")),
+        ];
+        let groups = group_rows(rows, "");
+        let names: Vec<_> = groups
+            .iter()
+            .map(|(g, rows)| {
+                (
+                    g.clone(),
+                    rows.iter().map(|r| r.name.clone()).collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (None, vec!["Name".to_string()]),
+                (Some("Size".into()), vec!["Width".into(), "Height".into()]),
+                (Some("Style".into()), vec!["Color".into()]),
+            ]
+        );
+
+        let rows = vec![row("Width", Some("Size")), row("Name", None)];
+        let groups = group_rows(rows, "wid");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0.as_deref(), Some("Size"));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::*;
+use accesskit::Role;
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+
+/// What views note about themselves while a query is taken.
+#[derive(Default)]
+pub(crate) struct QueryNotes {
+    labels: HashMap<ViewID, String>,
+    values: HashMap<ViewID, String>,
+    rects: HashMap<ViewID, WorldRect>,
+}
+
+impl Context {
+    /// Notes the text a view shows, while a query is taken.
+    pub(crate) fn note_label(&mut self, id: ViewID, label: &str) {
+        if let Some(notes) = &mut self.query {
+            notes.labels.insert(id, label.into());
+        }
+    }
+
+    /// Notes the value of a control, while a query is taken.
+    pub(crate) fn note_value(&mut self, id: ViewID, value: &str) {
+        if let Some(notes) = &mut self.query {
+            notes.values.insert(id, value.into());
+        }
+    }
+
+    /// Notes where a view is drawn, while a query is taken.
+    pub(crate) fn note_rect(&mut self, id: ViewID, rect: LocalRect, vger: &dyn Renderer) {
+        if let Some(notes) = &mut self.query {
+            if let Some(rect) = vger.to_world(rect) {
+                notes.rects.insert(id, rect);
+            }
+        }
+    }
+
+    /// Draws `view` and returns what's in it. Lay the view out first.
+    /// Positions are only found with renderers which keep track of them,
+    /// like `SvgRenderer`.
+    pub fn query(&mut self, view: &dyn View, vger: &mut dyn Renderer) -> Query {
+        self.begin_query();
+        view.draw(self.root_id, self, vger);
+        self.end_query(view)
+    }
+
+    /// Has views note what they show as they're drawn, for `end_query`.
+    pub(crate) fn begin_query(&mut self) {
+        self.query = Some(QueryNotes::default());
+    }
+
+    /// Returns what was noted since `begin_query`.
+    pub(crate) fn end_query(&mut self, view: &dyn View) -> Query {
+        let mut nodes = vec![];
+        let root = view.access(self.root_id, self, &mut nodes);
+        let notes = self.query.take().unwrap_or_default();
+        Query::new(root, nodes, notes)
+    }
+}
+
+/// A view found by a `Query`.
+#[derive(Clone, Debug)]
+pub struct QueryNode {
+    pub id: ViewID,
+    pub role: Role,
+
+    /// The text the view shows. Views without text of their own, like
+    /// buttons, are labelled with the text within them.
+    pub label: Option<String>,
+
+    /// The value of a control, like a slider's or a toggle's.
+    pub value: Option<String>,
+
+    /// Where the view is, relative to the root view. Views which don't
+    /// draw anything themselves cover what's within them.
+    pub rect: Option<WorldRect>,
+
+    /// Index past the last node within this one.
+    end: usize,
+}
+
+/// A snapshot of the views in a view tree which have accessibility roles,
+/// with their labels, values and positions, so tests can check what's
+/// shown and where without comparing pixels. Made by `View::query` or
+/// `Context::query`.
+///
+/// A query selects some of the views, to begin with all of them. Narrow
+/// it down with `by_role` and `by_label`, which search the views selected
+/// and those within them.
+#[derive(Clone)]
+pub struct Query {
+    /// All the nodes, depth first.
+    nodes: Rc<Vec<QueryNode>>,
+
+    /// The nodes selected, in order.
+    selected: Vec<usize>,
+}
+
+impl Query {
+    fn new(
+        root: Option<accesskit::NodeId>,
+        nodes: Vec<accesskit::Node>,
+        notes: QueryNotes,
+    ) -> Self {
+        let by_id: HashMap<_, _> = nodes.iter().map(|node| (node.id, node)).collect();
+        let mut flat = vec![];
+        if let Some(root) = root {
+            Self::flatten(root, &by_id, &notes, &mut flat);
+        }
+        let selected = (0..flat.len()).collect();
+        Self {
+            nodes: Rc::new(flat),
+            selected,
+        }
+    }
+
+    /// Adds the node `aid` and those within it to `flat`, depth first.
+    fn flatten(
+        aid: accesskit::NodeId,
+        by_id: &HashMap<accesskit::NodeId, &accesskit::Node>,
+        notes: &QueryNotes,
+        flat: &mut Vec<QueryNode>,
+    ) {
+        let node = match by_id.get(&aid) {
+            Some(node) => node,
+            None => return,
+        };
+        let id = ViewID::from_access_id(aid);
+        let index = flat.len();
+        flat.push(QueryNode {
+            id,
+            role: node.role,
+            label: notes.labels.get(&id).cloned(),
+            value: notes.values.get(&id).cloned(),
+            rect: notes.rects.get(&id).cloned(),
+            end: index + 1,
+        });
+        for child in &node.children {
+            Self::flatten(*child, by_id, notes, flat);
+        }
+        let end = flat.len();
+
+        let within = &flat[index + 1..end];
+        let label = within
+            .iter()
+            .filter_map(|node| notes.labels.get(&node.id))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let rect = within
+            .iter()
+            .filter_map(|node| node.rect)
+            .reduce(|a, b| a.union(&b));
+
+        let node = &mut flat[index];
+        node.end = end;
+        if node.label.is_none() && !label.is_empty() {
+            node.label = Some(label);
+        }
+        if node.rect.is_none() {
+            node.rect = rect;
+        }
+    }
+
+    /// Selects the nodes matching `f` among those selected and within
+    /// them.
+    fn select(&self, f: impl Fn(&QueryNode) -> bool) -> Self {
+        let mut selected = BTreeSet::new();
+        for &index in &self.selected {
+            let within = index..self.nodes[index].end;
+            selected.extend(within.filter(|&i| f(&self.nodes[i])));
+        }
+        Self {
+            nodes: self.nodes.clone(),
+            selected: selected.into_iter().collect(),
+        }
+    }
+
+    pub fn by_role(&self, role: Role) -> Self {
+        self.select(|node| node.role == role)
+    }
+
+    pub fn by_label(&self, label: &str) -> Self {
+        self.select(|node| node.label.as_deref() == Some(label))
+    }
+
+    /// The `n`th node selected, counting from 0, in the order they're in
+    /// the view tree.
+    pub fn nth(&self, n: usize) -> Option<&QueryNode> {
+        self.selected.get(n).map(|&index| &self.nodes[index])
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &QueryNode> {
+        self.selected.iter().map(move |&index| &self.nodes[index])
+    }
+
+    /// The nodes selected, each with how deeply it's nested within the
+    /// others selected.
+    pub(crate) fn tree(&self) -> Vec<(usize, &QueryNode)> {
+        let mut ends: Vec<usize> = vec![];
+        let mut tree = vec![];
+        for &index in &self.selected {
+            while matches!(ends.last(), Some(end) if *end <= index) {
+                ends.pop();
+            }
+            let node = &self.nodes[index];
+            tree.push((ends.len(), node));
+            ends.push(node.end);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let ui = vstack((
+            text("title"),
+            hstack((button(text("ok"), || ()), button(text("cancel"), || ()))),
+            toggle(signal(true)),
+        ));
+        let query = ui.query([200.0, 200.0].into());
+
+        let buttons = query.by_role(Role::Button);
+        assert_eq!(buttons.len(), 2);
+        assert_eq!(buttons.nth(1).unwrap().label.as_deref(), Some("cancel"));
+        assert_eq!(
+            query.by_label("title").nth(0).unwrap().role,
+            Role::LabelText
+        );
+
+        let ok = buttons.by_label("ok").nth(0).unwrap().rect.unwrap();
+        let cancel = buttons.nth(1).unwrap().rect.unwrap();
+        assert!(ok.max_x() <= cancel.min_x());
+        let label = query.by_role(Role::LabelText).by_label("ok");
+        assert!(ok.contains(label.nth(0).unwrap().rect.unwrap().center()));
+
+        let switches = query.by_role(Role::Switch);
+        assert_eq!(switches.nth(0).unwrap().value.as_deref(), Some("true"));
+    }
+}
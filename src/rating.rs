@@ -0,0 +1,297 @@
+use crate::*;
+
+pub const RATING_STAR_SIZE: f32 = 20.0;
+const RATING_SPACING: f32 = 4.0;
+
+/// The rating for a touch at `x` along stars of `size`, with no gaps,
+/// starting at zero. Rounds up so touching any part of a star counts it.
+fn rating_at(x: f32, size: f32, max: usize, half_steps: bool) -> f32 {
+    let step = if half_steps { 0.5 } else { 1.0 };
+    let stars = (x / size / step).ceil() * step;
+    stars.clamp(step, max as f32)
+}
+
+/// Fills a five pointed star centered at `center`.
+fn fill_star(vger: &mut dyn Renderer, center: LocalPoint, radius: f32, paint: Paint) {
+    let points: Vec<LocalPoint> = (0..10)
+        .map(|i| {
+            let r = if i % 2 == 0 { radius } else { radius * 0.45 };
+            let theta = std::f32::consts::FRAC_PI_2 + i as f32 * std::f32::consts::PI / 5.0;
+            center + LocalOffset::new(theta.cos(), theta.sin()) * r
+        })
+        .collect();
+    vger.move_to(points[0]);
+    for (i, p) in points.iter().enumerate() {
+        let next = points[(i + 1) % points.len()];
+        vger.quad_to(p.lerp(next, 0.5), next);
+    }
+    vger.fill(paint);
+}
+
+#[derive(Clone, Default)]
+struct RatingState {
+    /// The rating under the mouse, shown instead of the value.
+    hover: Option<f32>,
+}
+
+/// Struct for `rating`.
+pub struct Rating<B> {
+    value: B,
+    max: usize,
+    half_steps: bool,
+    read_only: bool,
+    color: Color,
+    star_size: f32,
+}
+
+impl<B> Rating<B>
+where
+    B: Binding<f32>,
+{
+    /// Allows ratings in halves of a star.
+    pub fn half_steps(self, half_steps: bool) -> Self {
+        Self { half_steps, ..self }
+    }
+
+    /// Shows the rating without letting the user change it.
+    pub fn read_only(self, read_only: bool) -> Self {
+        Self { read_only, ..self }
+    }
+
+    /// Sets the color of the filled stars.
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Sets the size of each star.
+    pub fn star_size(self, star_size: f32) -> Self {
+        Self { star_size, ..self }
+    }
+
+    fn step(&self) -> f32 {
+        if self.half_steps {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// The rating for a touch at `pt`.
+    fn rating(&self, pt: LocalPoint, rect: LocalRect) -> f32 {
+        let stride = self.star_size + RATING_SPACING;
+        let x = pt.x - rect.min_x();
+        // Fold the gaps into the stars before them.
+        let star = (x / stride).floor();
+        let within = (x - star * stride).min(self.star_size);
+        rating_at(
+            star * self.star_size + within,
+            self.star_size,
+            self.max,
+            self.half_steps,
+        )
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<RatingState> {
+        cx.with_state(
+            RatingState::default(),
+            id,
+            |state: State<RatingState>, _cx| state,
+        )
+    }
+}
+
+impl<B> View for Rating<B>
+where
+    B: Binding<f32>,
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("rating({} of {})", self.value.get(), self.max);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, _vger: &mut dyn Renderer) {
+        if self.read_only {
+            return;
+        }
+        let rect = cx.layout.entry(id).or_default().rect;
+        let state = self.state(id, cx);
+        let inside = rect.contains(event.position);
+
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } if inside => {
+                cx.touches[*touch] = id;
+                cx.focused_id = Some(id);
+                self.value.set(self.rating(event.position, rect));
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                self.value.set(self.rating(event.position, rect));
+            }
+            EventKind::TouchMove { id: 0 } => {
+                let hover = if inside {
+                    Some(self.rating(event.position, rect))
+                } else {
+                    None
+                };
+                if hover != state.with(|s| s.hover) {
+                    state.with_mut(|s| s.hover = hover);
+                }
+            }
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+            }
+            EventKind::Key(key, _) if cx.focused_id == Some(id) => {
+                let value = self.value.get();
+                let target = match key {
+                    KeyPress::ArrowLeft | KeyPress::ArrowDown => value - self.step(),
+                    KeyPress::ArrowRight | KeyPress::ArrowUp => value + self.step(),
+                    KeyPress::Home => 0.0,
+                    KeyPress::End => self.max as f32,
+                    KeyPress::Escape => {
+                        cx.focused_id = None;
+                        value
+                    }
+                    _ => value,
+                };
+                let target = target.clamp(0.0, self.max as f32);
+                if target != value {
+                    self.value.set(target);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let hover = self.state(id, cx).with(|s| s.hover);
+        let value = hover.unwrap_or_else(|| self.value.get());
+
+        // Show the preview a little lighter than the value.
+        let color = if hover.is_some() {
+            self.color.alpha(0.7)
+        } else {
+            self.color
+        };
+        let radius = self.star_size / 2.0;
+        for i in 0..self.max {
+            let star = LocalRect::new(
+                [
+                    rect.min_x() + i as f32 * (self.star_size + RATING_SPACING),
+                    rect.min_y(),
+                ]
+                .into(),
+                [self.star_size, self.star_size].into(),
+            );
+            let paint = vger.color_paint(GROOVES);
+            fill_star(vger, star.center(), radius, paint);
+
+            let filled = (value - i as f32).clamp(0.0, 1.0);
+            if filled > 0.0 {
+                vger.save();
+                vger.clip(LocalRect::new(
+                    star.origin,
+                    [star.width() * filled, star.height()].into(),
+                ));
+                let paint = vger.color_paint(color);
+                fill_star(vger, star.center(), radius, paint);
+                vger.restore();
+            }
+        }
+
+        if cx.focused_id == Some(id) {
+            let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(0.5));
+            vger.stroke_rect(rect.min(), rect.max(), 4.0, 1.0, paint);
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let width = self.max as f32 * (self.star_size + RATING_SPACING) - RATING_SPACING;
+        let size = LocalSize::new(width.max(0.0), self.star_size);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            RatingState::default(),
+            id,
+            map,
+            |state: State<RatingState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Slider));
+        Some(aid)
+    }
+}
+
+impl<B> private::Sealed for Rating<B> {}
+
+/// A row of `max` stars for rating something, with the number of stars in
+/// `value`. Hovering previews the rating, clicking or dragging sets it,
+/// and once clicked the arrow keys adjust it.
+pub fn rating<B: Binding<f32>>(value: B, max: usize) -> Rating<B> {
+    Rating {
+        value,
+        max,
+        half_steps: false,
+        read_only: false,
+        color: Color::hex_const("#FFB000"),
+        star_size: RATING_STAR_SIZE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rating_at() {
+        assert_eq!(rating_at(0.0, 20.0, 5, false), 1.0);
+        assert_eq!(rating_at(25.0, 20.0, 5, false), 2.0);
+        assert_eq!(rating_at(25.0, 20.0, 5, true), 1.5);
+        assert_eq!(rating_at(500.0, 20.0, 5, true), 5.0);
+    }
+}
@@ -0,0 +1,433 @@
+use crate::*;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// An event along with when it happened, relative to the start of recording.
+#[derive(Clone, Debug)]
+pub struct RecordedEvent {
+    pub time: Duration,
+    pub event: Event,
+}
+
+// Keys we know how to write out by name. Others are recorded as Unidentified.
+pub(crate) const NAMED_KEYS: &[(&str, KeyPress)] = &[
+    ("Enter", KeyPress::Enter),
+    ("Tab", KeyPress::Tab),
+    ("Space", KeyPress::Space),
+    ("ArrowDown", KeyPress::ArrowDown),
+    ("ArrowLeft", KeyPress::ArrowLeft),
+    ("ArrowRight", KeyPress::ArrowRight),
+    ("ArrowUp", KeyPress::ArrowUp),
+    ("End", KeyPress::End),
+    ("Home", KeyPress::Home),
+    ("PageDown", KeyPress::PageDown),
+    ("PageUp", KeyPress::PageUp),
+    ("Backspace", KeyPress::Backspace),
+    ("Delete", KeyPress::Delete),
+    ("Escape", KeyPress::Escape),
+];
+
+// Strings are written without whitespace of any kind, so each line splits
+// cleanly on it. Whitespace other than spaces and newlines is written as
+// its code point, like \u{9} for a tab.
+pub(crate) fn escape(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            ' ' => result.push_str("\\s"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_whitespace() => result.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+pub(crate) fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('s') => result.push(' '),
+                Some('n') => result.push('\n'),
+                Some('u') => {
+                    let code: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                    if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                        result.push(c)
+                    }
+                }
+                Some(c) => result.push(c),
+                None => (),
+            }
+        } else {
+            result.push(c)
+        }
+    }
+    result
+}
+
+/// Keys typed so far in replays. `KeyPress` holds a `&'static str`, so
+/// each distinct key is leaked once, like the ones tao hands us, rather
+/// than once for every event.
+fn intern(s: String) -> &'static str {
+    thread_local! {
+        static INTERNED: std::cell::RefCell<std::collections::HashSet<&'static str>> =
+            Default::default();
+    }
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+        match interned.get(s.as_str()) {
+            Some(s) => s,
+            None => {
+                let s: &'static str = Box::leak(s.into_boxed_str());
+                interned.insert(s);
+                s
+            }
+        }
+    })
+}
+
+fn write_key(key: &KeyPress) -> String {
+    if let KeyPress::Character(c) = key {
+        return format!("char:{}", escape(c));
+    }
+    for (name, named_key) in NAMED_KEYS {
+        if named_key == key {
+            return name.to_string();
+        }
+    }
+    "Unidentified".into()
+}
+
+fn read_key(s: &str) -> KeyPress {
+    if let Some(c) = s.strip_prefix("char:") {
+        return KeyPress::Character(intern(unescape(c)));
+    }
+    for (name, named_key) in NAMED_KEYS {
+        if *name == s {
+            return named_key.clone();
+        }
+    }
+    KeyPress::Unidentified
+}
+
+// Ids past the touches `Context` tracks would index out of bounds.
+fn read_touch(s: &str) -> Option<usize> {
+    s.parse().ok().filter(|id| *id < MAX_TOUCHES)
+}
+
+fn write_modifiers(mods: &ModifiersState) -> String {
+    let mut s = String::new();
+    if mods.shift_key() {
+        s.push('s')
+    }
+    if mods.control_key() {
+        s.push('c')
+    }
+    if mods.alt_key() {
+        s.push('a')
+    }
+    if mods.super_key() {
+        s.push('m')
+    }
+    if s.is_empty() {
+        s.push('-')
+    }
+    s
+}
+
+fn read_modifiers(s: &str) -> ModifiersState {
+    let mut mods = ModifiersState::default();
+    for c in s.chars() {
+        match c {
+            's' => mods = mods | ModifiersState::SHIFT,
+            'c' => mods = mods | ModifiersState::CONTROL,
+            'a' => mods = mods | ModifiersState::ALT,
+            'm' => mods = mods | ModifiersState::SUPER,
+            _ => (),
+        }
+    }
+    mods
+}
+
+impl RecordedEvent {
+    /// Writes the event as a single line of text. `None` for user events
+    /// and messages, whose payloads can't be written; whatever sent them
+    /// will send them again on replay.
+    pub fn to_line(&self) -> Option<String> {
+        let kind = match &self.event.kind {
+            EventKind::PressButton(name) => format!("press_button {}", escape(name)),
+            EventKind::TouchBegin { id } => format!("touch_begin {}", id),
+            EventKind::TouchMove { id } => format!("touch_move {}", id),
+            EventKind::TouchEnd { id } => format!("touch_end {}", id),
+            EventKind::TouchCancel { id } => format!("touch_cancel {}", id),
+            EventKind::Scroll { delta } => format!("scroll {} {}", delta.x, delta.y),
+            EventKind::Command(name) => format!("command {}", escape(name)),
+            EventKind::Key(key, mods) => {
+                format!("key {} {}", write_key(key), write_modifiers(mods))
+            }
+            EventKind::User(_) | EventKind::Message(_) => return None,
+            EventKind::Window(kind) => match kind {
+                WindowEventKind::Focused => "window focused".into(),
+                WindowEventKind::Unfocused => "window unfocused".into(),
+                WindowEventKind::Minimized => "window minimized".into(),
+                WindowEventKind::Restored => "window restored".into(),
+                WindowEventKind::Moved { x, y } => format!("window moved {} {}", x, y),
+                WindowEventKind::Resized { width, height } => {
+                    format!("window resized {} {}", width, height)
+                }
+                WindowEventKind::CloseRequested => "window close_requested".into(),
+                WindowEventKind::DisplaysChanged(_) => "window displays_changed".into(),
+            },
+        };
+        Some(format!(
+            "{} {} {} {}",
+            self.time.as_micros(),
+            self.event.position.x,
+            self.event.position.y,
+            kind
+        ))
+    }
+
+    /// Reads an event written by `to_line`.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let time = Duration::from_micros(parts.next()?.parse().ok()?);
+        let x = parts.next()?.parse().ok()?;
+        let y = parts.next()?.parse().ok()?;
+
+        let kind = match parts.next()? {
+            "press_button" => EventKind::PressButton(unescape(parts.next()?)),
+            "touch_begin" => EventKind::TouchBegin {
+                id: read_touch(parts.next()?)?,
+            },
+            "touch_move" => EventKind::TouchMove {
+                id: read_touch(parts.next()?)?,
+            },
+            "touch_end" => EventKind::TouchEnd {
+                id: read_touch(parts.next()?)?,
+            },
+            "touch_cancel" => EventKind::TouchCancel {
+                id: read_touch(parts.next()?)?,
+            },
+            "scroll" => EventKind::Scroll {
+                delta: LocalOffset::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+            },
+            "command" => EventKind::Command(unescape(parts.next()?)),
+            "key" => EventKind::Key(read_key(parts.next()?), read_modifiers(parts.next()?)),
+            "window" => EventKind::Window(match parts.next()? {
+                "focused" => WindowEventKind::Focused,
+                "unfocused" => WindowEventKind::Unfocused,
+                "minimized" => WindowEventKind::Minimized,
+                "restored" => WindowEventKind::Restored,
+                "moved" => WindowEventKind::Moved {
+                    x: parts.next()?.parse().ok()?,
+                    y: parts.next()?.parse().ok()?,
+                },
+                "resized" => WindowEventKind::Resized {
+                    width: parts.next()?.parse().ok()?,
+                    height: parts.next()?.parse().ok()?,
+                },
+                "close_requested" => WindowEventKind::CloseRequested,
+                // The displays aren't recorded, since they won't be the
+                // same where the events are replayed.
+                "displays_changed" => WindowEventKind::DisplaysChanged(Monitors::default()),
+                _ => return None,
+            }),
+            _ => return None,
+        };
+
+        Some(Self {
+            time,
+            event: Event {
+                kind,
+                position: LocalPoint::new(x, y),
+            },
+        })
+    }
+}
+
+/// Writes events to a file as they are processed. Enabled by setting
+/// the RUI_RECORD environment variable to a path.
+pub(crate) struct EventRecorder {
+    start: Instant,
+    file: Option<File>,
+}
+
+impl EventRecorder {
+    pub fn from_env() -> Self {
+        let file = std::env::var("RUI_RECORD")
+            .ok()
+            .and_then(|path| match File::create(&path) {
+                Ok(file) => Some(file),
+                Err(err) => {
+                    println!("error creating event recording {:?}: {:?}", path, err);
+                    None
+                }
+            });
+        Self {
+            start: Instant::now(),
+            file,
+        }
+    }
+
+    pub fn record(&mut self, event: &Event) {
+        if let Some(file) = &mut self.file {
+            let recorded = RecordedEvent {
+                time: self.start.elapsed(),
+                event: event.clone(),
+            };
+            // Write each event immediately so a crash doesn't lose the
+            // events which led up to it.
+            if let Some(line) = recorded.to_line() {
+                if let Err(err) = writeln!(file, "{}", line) {
+                    println!("error recording event: {:?}", err);
+                }
+            }
+        }
+    }
+
+    /// Records a key typed into a secure field, without what was typed.
+    pub fn record_secret(&mut self, event: &Event) {
+        match &event.kind {
+            EventKind::Key(KeyPress::Character(_) | KeyPress::Space, mods) => self.record(&Event {
+                kind: EventKind::Key(KeyPress::Unidentified, *mods),
+                position: event.position,
+            }),
+            _ => self.record(event),
+        }
+    }
+}
+
+/// Reads events recorded with RUI_RECORD.
+pub fn read_events(path: impl AsRef<Path>) -> std::io::Result<Vec<RecordedEvent>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut events = vec![];
+    for line in reader.lines() {
+        if let Some(event) = RecordedEvent::from_line(&line?) {
+            events.push(event)
+        }
+    }
+    Ok(events)
+}
+
+/// Processes recorded events against a view, laying it out before
+/// each event as the event loop would. `size` is the window's size until
+/// a recorded resize changes it. Use with `stress::headless` to reproduce
+/// a bug report in a test.
+pub fn replay(
+    view: &impl View,
+    events: &[RecordedEvent],
+    mut size: LocalSize,
+    cx: &mut Context,
+    vger: &mut dyn Renderer,
+) {
+    for recorded in events {
+        if let EventKind::Window(WindowEventKind::Resized { width, height }) = recorded.event.kind {
+            size = [width, height].into();
+        }
+        cx.layout_root(view, size, vger);
+        cx.process_root(view, &recorded.event, vger);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn round_trip(kind: EventKind) -> EventKind {
+        let recorded = RecordedEvent {
+            time: Duration::from_millis(1500),
+            event: Event {
+                kind,
+                position: [1.5, 2.0].into(),
+            },
+        };
+        let read = RecordedEvent::from_line(&recorded.to_line().unwrap()).unwrap();
+        assert_eq!(read.time, recorded.time);
+        assert_eq!(read.event.position, recorded.event.position);
+        read.event.kind
+    }
+
+    #[test]
+    fn test_record_touch() {
+        match round_trip(EventKind::TouchMove { id: 3 }) {
+            EventKind::TouchMove { id } => assert_eq!(id, 3),
+            _ => panic!("wrong event kind"),
+        }
+
+        // Ids past the touches tracked aren't read.
+        assert!(RecordedEvent::from_line("0 0 0 touch_begin 16").is_none());
+    }
+
+    #[test]
+    fn test_record_command() {
+        match round_trip(EventKind::Command("File:Save As".into())) {
+            EventKind::Command(name) => assert_eq!(name, "File:Save As"),
+            _ => panic!("wrong event kind"),
+        }
+    }
+
+    #[test]
+    fn test_record_key() {
+        let mods = ModifiersState::SHIFT | ModifiersState::SUPER;
+        match round_trip(EventKind::Key(KeyPress::Character("a b\\"), mods)) {
+            EventKind::Key(key, read_mods) => {
+                assert_eq!(key, KeyPress::Character("a b\\"));
+                assert_eq!(read_mods, mods);
+            }
+            _ => panic!("wrong event kind"),
+        }
+        match round_trip(EventKind::Key(
+            KeyPress::ArrowLeft,
+            ModifiersState::default(),
+        )) {
+            EventKind::Key(key, _) => assert_eq!(key, KeyPress::ArrowLeft),
+            _ => panic!("wrong event kind"),
+        }
+    }
+
+    #[test]
+    fn test_record_window() {
+        match round_trip(EventKind::Window(WindowEventKind::Moved { x: -10, y: 20 })) {
+            EventKind::Window(kind) => assert_eq!(kind, WindowEventKind::Moved { x: -10, y: 20 }),
+            _ => panic!("wrong event kind"),
+        }
+        let resized = WindowEventKind::Resized {
+            width: 320.5,
+            height: 240.0,
+        };
+        match round_trip(EventKind::Window(resized.clone())) {
+            EventKind::Window(kind) => assert_eq!(kind, resized),
+            _ => panic!("wrong event kind"),
+        }
+    }
+
+    #[test]
+    fn test_record_whitespace() {
+        let name = "a\tb\r\u{a0}c\u{3000}d \n\\";
+        assert!(!escape(name).contains(char::is_whitespace));
+        match round_trip(EventKind::Command(name.into())) {
+            EventKind::Command(read) => assert_eq!(read, name),
+            _ => panic!("wrong event kind"),
+        }
+    }
+
+    #[test]
+    fn test_intern_keys() {
+        let a = read_key("char:x");
+        let b = read_key("char:x");
+        match (a, b) {
+            (KeyPress::Character(a), KeyPress::Character(b)) => {
+                assert_eq!(a, "x");
+                assert!(std::ptr::eq(a, b));
+            }
+            _ => panic!("wrong key"),
+        }
+    }
+}
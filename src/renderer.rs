@@ -0,0 +1,543 @@
+use crate::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Something views can draw with. Views draw through this trait rather
+/// than vger directly, so rui can render with other backends.
+pub trait Renderer {
+    fn save(&mut self);
+    fn restore(&mut self);
+
+    /// How many `save`s are still to be restored.
+    fn save_depth(&self) -> usize;
+
+    fn translate(&mut self, offset: LocalOffset);
+
+    /// Scales what's drawn next about the current origin, until `restore`.
+    fn scale(&mut self, scale: f32);
+
+    /// Restricts drawing to `rect`, until `restore`.
+    fn clip(&mut self, rect: LocalRect);
+
+    /// Multiplies the opacity of what's drawn next by `opacity`, until
+    /// `restore`. Renderers which can't blend ignore this.
+    fn opacity(&mut self, _opacity: f32) {}
+
+    /// Draws what's drawn next into a layer of its own, until `end_layer`
+    /// composites it onto what's under it with `effects`. The opacity so
+    /// far applies to the layer as a whole. Renderers which can't draw
+    /// layers draw straight through, without the effects.
+    fn begin_layer(&mut self, _effects: LayerEffects) {}
+
+    /// Ends the layer begun by the last `begin_layer`.
+    fn end_layer(&mut self) {}
+
+    /// Draws what was last drawn between `begin_cache` and `end_cache`
+    /// with `key`, returning false when nothing is kept for `rect` at the
+    /// current scale.
+    fn draw_cached(&mut self, _key: ViewID, _rect: LocalRect) -> bool {
+        false
+    }
+
+    /// Draws what's drawn next into `rect`, clipped to it, keeping it for
+    /// `draw_cached` with `key` on later frames. Renderers which can't keep
+    /// drawings draw straight through.
+    fn begin_cache(&mut self, _key: ViewID, _rect: LocalRect) {}
+
+    /// Ends the drawing begun by the last `begin_cache`.
+    fn end_cache(&mut self) {}
+
+    /// Where `rect` in the current coordinates is drawn, in the root
+    /// view's. `None` from renderers which don't keep track.
+    fn to_world(&self, _rect: LocalRect) -> Option<WorldRect> {
+        None
+    }
+
+    fn color_paint(&mut self, color: Color) -> Paint {
+        Paint::Color(color)
+    }
+
+    fn fill_rect(&mut self, rect: LocalRect, radius: f32, paint: Paint);
+    fn stroke_rect(
+        &mut self,
+        min: LocalPoint,
+        max: LocalPoint,
+        radius: f32,
+        width: f32,
+        paint: Paint,
+    );
+    fn fill_circle(&mut self, center: LocalPoint, radius: f32, paint: Paint);
+    fn stroke_segment(&mut self, a: LocalPoint, b: LocalPoint, width: f32, paint: Paint);
+    fn stroke_arc(
+        &mut self,
+        center: LocalPoint,
+        radius: f32,
+        width: f32,
+        rotation: f32,
+        aperture: f32,
+        paint: Paint,
+    );
+
+    fn stroke_bezier(
+        &mut self,
+        a: LocalPoint,
+        b: LocalPoint,
+        c: LocalPoint,
+        width: f32,
+        paint: Paint,
+    );
+
+    /// Starts a new contour of the path filled by `fill`.
+    fn move_to(&mut self, p: LocalPoint);
+
+    /// Adds a quadratic bezier to the current contour.
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint);
+
+    /// Fills the path built with `move_to` and `quad_to`, then starts a
+    /// new one.
+    fn fill(&mut self, paint: Paint);
+
+    /// Selects the font for text, or the renderer's default font. Renderers
+    /// which can't choose fonts, like vger, ignore this.
+    fn set_font(&mut self, _font: Option<&fonts::Font>) {}
+
+    /// Draws `frame` stretched over `rect`, with its corners rounded by
+    /// `radius`. `key` identifies the view drawing it, so renderers can
+    /// reuse its texture from frame to frame. Renderers which can't draw
+    /// images ignore this.
+    fn draw_frame(&mut self, _key: ViewID, _frame: &Arc<Frame>, _rect: LocalRect, _radius: f32) {}
+
+    fn text(&mut self, text: &str, size: u32, color: Color, max_width: Option<f32>);
+    fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect;
+    fn glyph_positions(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LocalRect>;
+    fn line_metrics(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LineMetrics>;
+
+    /// The height of each line's baseline, in the same coordinates as
+    /// `text_bounds`. Renderers which don't know it estimate it from
+    /// `line_metrics`, with the first baseline at zero.
+    fn baselines(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<f32> {
+        let lines = self.line_metrics(text, size, max_width);
+        let n = lines.len();
+        if n < 2 {
+            return vec![0.0; n];
+        }
+        let spacing =
+            (lines[0].bounds.center().y - lines[n - 1].bounds.center().y) / (n - 1) as f32;
+        (0..n).map(|i| -(i as f32) * spacing).collect()
+    }
+
+    /// The vger renderer, for views like `canvas` which draw with it
+    /// directly. `None` for other backends.
+    fn vger(&mut self) -> Option<&mut VGER> {
+        None
+    }
+}
+
+/// Renders with vger on the GPU. Text is drawn with vger's built-in font:
+/// choosing fonts, fallback fonts for emoji and shaping are only in the
+/// software renderer.
+pub struct VgerRenderer {
+    vger: VGER,
+
+    /// Paints created so far this frame, so views drawn with the same
+    /// paint share a single vger paint. Cleared when a frame begins.
+    paints: HashMap<PaintKey, PaintIndex>,
+
+    /// The transform and scissor, mirrored from vger so frames can be
+    /// placed in window coordinates.
+    offset: LocalOffset,
+    zoom: f32,
+    clip: Option<LocalRect>,
+    opacity: f32,
+    saved: Vec<(LocalOffset, f32, Option<LocalRect>, f32)>,
+
+    /// Frames drawn so far this frame. vger can't sample our textures, so
+    /// `frame_pass` draws these in passes of their own.
+    frames: Vec<FrameDraw>,
+    frame_pass: Option<FramePass>,
+
+    /// Draws layers, once a backend calls `enable_layers`. Until then,
+    /// views are drawn without their layer effects.
+    layers: Option<Layers>,
+
+    /// The scene in `layers` being drawn into, or `None` for `vger`.
+    scene: Option<usize>,
+
+    /// The window's width, height and scale, for beginning scenes.
+    window: (f32, f32, f32),
+
+    /// For each `begin_cache` not yet ended, what to go back to.
+    caches: Vec<CacheDraw>,
+}
+
+/// See `VgerRenderer::caches`.
+struct CacheDraw {
+    saved: Vec<(LocalOffset, f32, Option<LocalRect>, f32)>,
+    state: (LocalOffset, f32, Option<LocalRect>, f32),
+}
+
+/// `rect`, in a target's coordinates, moved to whole physical pixels, and
+/// its size in them.
+pub(crate) fn pixel_bounds(rect: LocalRect, scale: f32) -> (LocalRect, PhysicalSize<u32>) {
+    let origin = (rect.origin * scale).round();
+    let size = (rect.size * scale).ceil().max([1.0, 1.0].into());
+    (
+        LocalRect::new(origin / scale, size / scale),
+        PhysicalSize::new(size.width as u32, size.height as u32),
+    )
+}
+
+/// A frame for `FramePass` to draw, in the coordinates of the target it's
+/// drawn into.
+pub(crate) struct FrameDraw {
+    pub key: ViewID,
+    pub frame: Arc<Frame>,
+    pub rect: LocalRect,
+    pub radius: f32,
+    pub clip: Option<LocalRect>,
+}
+
+impl VgerRenderer {
+    pub fn new(vger: VGER) -> Self {
+        Self {
+            vger,
+            paints: HashMap::new(),
+            offset: LocalOffset::zero(),
+            zoom: 1.0,
+            clip: None,
+            opacity: 1.0,
+            saved: vec![],
+            frames: vec![],
+            frame_pass: None,
+            layers: None,
+            scene: None,
+            window: (0.0, 0.0, 1.0),
+            caches: vec![],
+        }
+    }
+
+    /// Lets views be drawn into layers, for effects like `blur`, and
+    /// `frame_view` frames be drawn.
+    pub(crate) fn enable_layers(&mut self, device: Arc<wgpu::Device>, format: wgpu::TextureFormat) {
+        self.frame_pass = Some(FramePass::new(&device, format));
+        self.layers = Some(Layers::new(device, format));
+    }
+
+    pub fn begin(&mut self, window_width: f32, window_height: f32, device_px_ratio: f32) {
+        self.paints.clear();
+        self.offset = LocalOffset::zero();
+        self.zoom = 1.0;
+        self.clip = None;
+        self.opacity = 1.0;
+        self.saved.clear();
+        self.frames.clear();
+        self.scene = None;
+        self.window = (window_width, window_height, device_px_ratio);
+        self.caches.clear();
+        if let Some(layers) = &mut self.layers {
+            let size = PhysicalSize::new(
+                (window_width * device_px_ratio).round() as u32,
+                (window_height * device_px_ratio).round() as u32,
+            );
+            layers.begin(size, device_px_ratio);
+        }
+        self.vger
+            .begin(window_width, window_height, device_px_ratio);
+    }
+
+    pub fn encode(
+        &mut self,
+        device: &wgpu::Device,
+        render_pass: &wgpu::RenderPassDescriptor,
+        queue: &wgpu::Queue,
+    ) {
+        match (&mut self.layers, &mut self.frame_pass) {
+            (Some(layers), Some(frame_pass)) if layers.drawn() => {
+                frame_pass.retain(&self.frames);
+                layers.encode(
+                    &mut self.vger,
+                    frame_pass,
+                    &self.frames,
+                    device,
+                    render_pass,
+                    queue,
+                )
+            }
+            (_, frame_pass) => {
+                // No frames were drawn, so none of their textures are needed.
+                if let Some(frame_pass) = frame_pass {
+                    frame_pass.retain(&[]);
+                }
+                self.vger.encode(device, render_pass, queue)
+            }
+        }
+    }
+
+    /// The vger scene being drawn into.
+    fn scene(&mut self) -> &mut VGER {
+        match (self.scene, &mut self.layers) {
+            (Some(index), Some(layers)) => layers.scene(index),
+            _ => &mut self.vger,
+        }
+    }
+
+    /// Starts drawing into another scene, bringing it to the transform
+    /// and scissor drawing had reached, with the same saves to restore.
+    fn switch_scene(&mut self, index: usize) {
+        self.scene = Some(index);
+        self.paints.clear();
+
+        let (mut width, mut height, scale) = self.window;
+        if let Some(size) = self.layers.as_ref().and_then(|layers| layers.target_size()) {
+            width = size.width as f32 / scale;
+            height = size.height as f32 / scale;
+        }
+        let current = (self.offset, self.zoom, self.clip, self.opacity);
+        let states: Vec<_> = self.saved.iter().copied().chain(Some(current)).collect();
+        let vger = self.scene();
+        vger.begin(width, height, scale);
+        let (mut offset, mut zoom) = (LocalOffset::zero(), 1.0);
+        for (i, (state_offset, state_zoom, clip, _)) in states.into_iter().enumerate() {
+            if i > 0 {
+                vger.save();
+            }
+            vger.translate((state_offset - offset) / zoom);
+            vger.scale([state_zoom / zoom, state_zoom / zoom]);
+            if let Some(clip) = clip {
+                vger.scissor(
+                    clip.translate(-state_offset)
+                        .scale(1.0 / state_zoom, 1.0 / state_zoom),
+                );
+            }
+            offset = state_offset;
+            zoom = state_zoom;
+        }
+    }
+
+    /// Maps a rect in the current coordinates to the window's.
+    fn to_window(&self, rect: LocalRect) -> LocalRect {
+        rect.scale(self.zoom, self.zoom).translate(self.offset)
+    }
+
+    /// Number of distinct paints used so far this frame.
+    pub fn paint_count(&self) -> usize {
+        self.paints.len()
+    }
+
+    fn paint(&mut self, paint: Paint) -> PaintIndex {
+        let paint = paint.with_opacity(self.opacity);
+        if let Some(index) = self.paints.get(&paint.key()) {
+            return *index;
+        }
+        let index = paint.vger_paint(self.scene());
+        self.paints.insert(paint.key(), index);
+        index
+    }
+}
+
+impl Renderer for VgerRenderer {
+    fn save(&mut self) {
+        self.saved
+            .push((self.offset, self.zoom, self.clip, self.opacity));
+        self.scene().save()
+    }
+
+    fn restore(&mut self) {
+        if let Some((offset, zoom, clip, opacity)) = self.saved.pop() {
+            self.offset = offset;
+            self.zoom = zoom;
+            self.clip = clip;
+            self.opacity = opacity;
+        }
+        self.scene().restore()
+    }
+
+    fn save_depth(&self) -> usize {
+        self.saved.len()
+    }
+
+    fn translate(&mut self, offset: LocalOffset) {
+        self.offset += offset * self.zoom;
+        self.scene().translate(offset)
+    }
+
+    fn scale(&mut self, scale: f32) {
+        self.zoom *= scale;
+        self.scene().scale([scale, scale])
+    }
+
+    fn clip(&mut self, rect: LocalRect) {
+        self.clip = Some(self.to_window(rect));
+        self.scene().scissor(rect)
+    }
+
+    fn opacity(&mut self, opacity: f32) {
+        self.opacity *= opacity;
+    }
+
+    fn begin_layer(&mut self, effects: LayerEffects) {
+        let effects = LayerEffects {
+            blur: effects.blur * self.zoom,
+            ..effects
+        };
+        let (opacity, clip) = (self.opacity, self.clip);
+        if let Some(layers) = &mut self.layers {
+            let scene = layers.begin_layer(effects, opacity, clip);
+            self.opacity = 1.0;
+            self.switch_scene(scene);
+        }
+    }
+
+    fn end_layer(&mut self) {
+        if let Some(scene) = self.layers.as_mut().and_then(|layers| layers.end_layer()) {
+            self.switch_scene(scene);
+        }
+    }
+
+    fn draw_cached(&mut self, key: ViewID, rect: LocalRect) -> bool {
+        let (bounds, size) = pixel_bounds(self.to_window(rect), self.window.2);
+        let (opacity, clip) = (self.opacity, self.clip);
+        let scene = self
+            .layers
+            .as_mut()
+            .and_then(|layers| layers.draw_cached(key, size, bounds, opacity, clip));
+        if let Some(scene) = scene {
+            self.switch_scene(scene);
+        }
+        scene.is_some()
+    }
+
+    fn begin_cache(&mut self, key: ViewID, rect: LocalRect) {
+        let (bounds, size) = pixel_bounds(self.to_window(rect), self.window.2);
+        let (opacity, clip) = (self.opacity, self.clip);
+        if let Some(layers) = &mut self.layers {
+            let scene = layers.begin_cache(key, size, bounds, opacity, clip);
+            self.caches.push(CacheDraw {
+                saved: std::mem::take(&mut self.saved),
+                state: (self.offset, self.zoom, self.clip, self.opacity),
+            });
+
+            // Draw relative to the texture.
+            self.offset -= bounds.origin.to_vector();
+            self.clip = None;
+            self.opacity = 1.0;
+            self.switch_scene(scene);
+        }
+    }
+
+    fn end_cache(&mut self) {
+        if let Some(cache) = self.caches.pop() {
+            let (offset, zoom, clip, opacity) = cache.state;
+            self.saved = cache.saved;
+            self.offset = offset;
+            self.zoom = zoom;
+            self.clip = clip;
+            self.opacity = opacity;
+            self.end_layer();
+        }
+    }
+
+    fn fill_rect(&mut self, rect: LocalRect, radius: f32, paint: Paint) {
+        let paint = self.paint(paint);
+        self.scene().fill_rect(rect, radius, paint)
+    }
+
+    fn stroke_rect(
+        &mut self,
+        min: LocalPoint,
+        max: LocalPoint,
+        radius: f32,
+        width: f32,
+        paint: Paint,
+    ) {
+        let paint = self.paint(paint);
+        self.scene().stroke_rect(min, max, radius, width, paint)
+    }
+
+    fn fill_circle(&mut self, center: LocalPoint, radius: f32, paint: Paint) {
+        let paint = self.paint(paint);
+        self.scene().fill_circle(center, radius, paint)
+    }
+
+    fn stroke_segment(&mut self, a: LocalPoint, b: LocalPoint, width: f32, paint: Paint) {
+        let paint = self.paint(paint);
+        self.scene().stroke_segment(a, b, width, paint)
+    }
+
+    fn stroke_arc(
+        &mut self,
+        center: LocalPoint,
+        radius: f32,
+        width: f32,
+        rotation: f32,
+        aperture: f32,
+        paint: Paint,
+    ) {
+        let paint = self.paint(paint);
+        self.scene()
+            .stroke_arc(center, radius, width, rotation, aperture, paint)
+    }
+
+    fn stroke_bezier(
+        &mut self,
+        a: LocalPoint,
+        b: LocalPoint,
+        c: LocalPoint,
+        width: f32,
+        paint: Paint,
+    ) {
+        let paint = self.paint(paint);
+        self.scene().stroke_bezier(a, b, c, width, paint)
+    }
+
+    fn move_to(&mut self, p: LocalPoint) {
+        self.scene().move_to(p)
+    }
+
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint) {
+        self.scene().quad_to(b, c)
+    }
+
+    fn fill(&mut self, paint: Paint) {
+        let paint = self.paint(paint);
+        self.scene().fill(paint)
+    }
+
+    fn draw_frame(&mut self, key: ViewID, frame: &Arc<Frame>, rect: LocalRect, radius: f32) {
+        self.frames.push(FrameDraw {
+            key,
+            frame: frame.clone(),
+            rect: self.to_window(rect),
+            radius: radius * self.zoom,
+            clip: self.clip,
+        });
+
+        // Draw what's after the frame over it.
+        let index = self.frames.len() - 1;
+        if let Some(layers) = &mut self.layers {
+            let scene = layers.draw_frame(index);
+            self.switch_scene(scene);
+        }
+    }
+
+    fn text(&mut self, text: &str, size: u32, color: Color, max_width: Option<f32>) {
+        let color = color.alpha(color.a * self.opacity);
+        self.scene().text(text, size, color, max_width)
+    }
+
+    fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        self.vger.text_bounds(text, size, max_width)
+    }
+
+    fn glyph_positions(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LocalRect> {
+        self.vger.glyph_positions(text, size, max_width)
+    }
+
+    fn line_metrics(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LineMetrics> {
+        self.vger.line_metrics(text, size, max_width)
+    }
+
+    fn vger(&mut self) -> Option<&mut VGER> {
+        Some(self.scene())
+    }
+}
@@ -5,6 +5,7 @@ use accesskit::Role;
 pub struct RoleView<V> {
     child: V,
     role: Role,
+    value: Option<String>,
 }
 
 impl<V> RoleView<V>
@@ -12,7 +13,17 @@ where
     V: View,
 {
     pub fn new(v: V, role: Role) -> Self {
-        Self { child: v, role }
+        Self {
+            child: v,
+            role,
+            value: None,
+        }
+    }
+
+    /// Sets the value of the control, like a slider's, for queries.
+    pub(crate) fn value(mut self, value: impl ToString) -> Self {
+        self.value = Some(value.to_string());
+        self
     }
 }
 
@@ -25,16 +36,22 @@ where
         println!(".role()");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.process(&event, id.child(&0), cx, vger);
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger);
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.child.layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -42,7 +59,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -62,6 +79,9 @@ where
         nodes: &mut Vec<accesskit::Node>,
     ) -> Option<accesskit::NodeId> {
         let child_aid = self.child.access(id.child(&0), cx, nodes);
+        if let Some(value) = &self.value {
+            cx.note_value(id, value);
+        }
         let aid = id.access_id();
         nodes.push(accesskit::Node {
             children: match child_aid {
@@ -72,6 +92,19 @@ where
         });
         Some(aid)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
 }
 
 impl<V> private::Sealed for RoleView<V> {}
@@ -0,0 +1,358 @@
+use crate::*;
+use std::any::Any;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Sent through a `rubber_band_selection` container while dragging, to
+/// find the items within the band. Like `ScrollRequest`, its position
+/// starts at the origin of the container.
+struct SelectionProbe<K> {
+    band: LocalRect,
+    found: RefCell<Vec<K>>,
+}
+
+#[derive(Clone)]
+struct RubberBandState<K> {
+    /// Where the drag started and where it is now.
+    band: Option<(LocalPoint, LocalPoint)>,
+
+    /// What was selected before the drag, kept when Shift is held.
+    base: Vec<K>,
+}
+
+impl<K> Default for RubberBandState<K> {
+    fn default() -> Self {
+        Self {
+            band: None,
+            base: vec![],
+        }
+    }
+}
+
+/// Struct for the `rubber_band_selection` modifier.
+pub struct RubberBand<V, B, K> {
+    child: V,
+    selection: B,
+    phantom: PhantomData<K>,
+}
+
+impl<V, B, K> RubberBand<V, B, K>
+where
+    V: View,
+    B: Binding<Vec<K>>,
+    K: Clone + PartialEq + 'static,
+{
+    pub fn new(child: V, selection: B) -> Self {
+        Self {
+            child,
+            selection,
+            phantom: Default::default(),
+        }
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<RubberBandState<K>> {
+        cx.get_state(id, RubberBandState::default)
+    }
+
+    /// Selects the items within the band, along with those selected before.
+    fn select(
+        &self,
+        band: LocalRect,
+        base: Vec<K>,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) {
+        let probe = Rc::new(SelectionProbe {
+            band,
+            found: RefCell::new(vec![]),
+        });
+        let event = Event {
+            kind: EventKind::Message(probe.clone() as Rc<dyn Any>),
+            position: LocalPoint::zero(),
+        };
+        // A band with no area, like a click's, covers nothing.
+        if !band.is_empty() {
+            let consumed = cx.consumed;
+            cx.consumed = false;
+            self.child.process(&event, id.child(&0), cx, vger);
+            cx.consumed = consumed;
+        }
+
+        let mut selection = base;
+        for key in probe.found.take() {
+            if !selection.contains(&key) {
+                selection.push(key);
+            }
+        }
+        if selection != self.selection.get() {
+            self.selection.set(selection);
+        }
+    }
+}
+
+impl<V, B, K> View for RubberBand<V, B, K>
+where
+    V: View,
+    B: Binding<Vec<K>>,
+    K: Clone + PartialEq + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".rubber_band_selection()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger);
+
+        let rect = cx.layout.entry(id).or_default().rect;
+        let state = self.state(id, cx);
+        match &event.kind {
+            // Dragging from the items themselves selects, unless they
+            // took the touch.
+            EventKind::TouchBegin { id: touch }
+                if rect.contains(event.position) && !cx.event_consumed() =>
+            {
+                cx.touches[*touch] = id;
+                let base = if cx.modifiers.shift_key() {
+                    self.selection.get()
+                } else {
+                    vec![]
+                };
+                state.with_mut(|s| {
+                    s.band = Some((event.position, event.position));
+                    s.base = base.clone();
+                });
+                if base != self.selection.get() {
+                    self.selection.set(base);
+                }
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                if let Some((start, _)) = state.with(|s| s.band) {
+                    state.with_mut(|s| s.band = Some((start, event.position)));
+                    let band = LocalRect::from_points([start, event.position]);
+                    let base = state.with(|s| s.base.clone());
+                    self.select(band, base, id, cx, vger);
+                }
+            }
+            EventKind::TouchEnd { id: touch } | EventKind::TouchCancel { id: touch }
+                if cx.touches[*touch] == id =>
+            {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| s.band = None);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger);
+
+        if let Some((start, end)) = self.state(id, cx).with(|s| s.band) {
+            let band = LocalRect::from_points([start, end]);
+            let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(0.2));
+            vger.fill_rect(band, 0.0, paint);
+            let paint = vger.color_paint(AZURE_HIGHLIGHT);
+            vger.stroke_rect(band.min(), band.max(), 0.0, 1.0, paint);
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.child.layout(id.child(&0), constraints, cx, vger);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        self.child.hittest(id.child(&0), pt, cx, vger).or_else(|| {
+            if rect.contains(pt) {
+                Some(id)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = self.state(id, cx);
+        map.insert(id, Box::new(state));
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, B, K> private::Sealed for RubberBand<V, B, K> {}
+
+/// Struct for the `selectable` modifier.
+pub struct Selectable<V, K> {
+    child: V,
+    key: K,
+}
+
+impl<V, K> Selectable<V, K> {
+    pub fn new(child: V, key: K) -> Self {
+        Self { child, key }
+    }
+}
+
+impl<V, K> View for Selectable<V, K>
+where
+    V: View,
+    K: Clone + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".selectable()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::Message(message) = &event.kind {
+            if let Some(probe) = message.downcast_ref::<SelectionProbe<K>>() {
+                let rect = cx.child_rect(id);
+                if rect
+                    .translate(-event.position.to_vector())
+                    .intersects(&probe.band)
+                {
+                    probe.found.borrow_mut().push(self.key.clone());
+                }
+            }
+        }
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.child.layout(id.child(&0), constraints, cx, vger);
+        cx.note_child_rect(id, LocalRect::new(LocalPoint::zero(), size));
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V, K> private::Sealed for Selectable<V, K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rubber_band_selection() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let selection = State::new(Vec::<i32>::new(), cx.dirty.clone());
+        let ui = hstack(
+            (0..4)
+                .map(|i| rectangle().size([25.0, 100.0]).selectable(i))
+                .collect::<Vec<_>>(),
+        )
+        .rubber_band_selection(selection.clone());
+        cx.layout_root(&ui, size, &mut vger);
+
+        let mut drag = |from: [f32; 2], to: [f32; 2], cx: &mut Context| {
+            let events = [
+                (EventKind::TouchBegin { id: 0 }, from),
+                (EventKind::TouchMove { id: 0 }, to),
+                (EventKind::TouchEnd { id: 0 }, to),
+            ];
+            for (kind, position) in events.iter() {
+                let event = Event {
+                    kind: kind.clone(),
+                    position: (*position).into(),
+                };
+                cx.process_root(&ui, &event, &mut vger);
+            }
+            selection.get()
+        };
+        assert_eq!(drag([10.0, 10.0], [60.0, 90.0], &mut cx), vec![0, 1, 2]);
+        assert_eq!(drag([90.0, 10.0], [80.0, 20.0], &mut cx), vec![3]);
+        cx.modifiers = ModifiersState::SHIFT;
+        assert_eq!(drag([5.0, 50.0], [10.0, 60.0], &mut cx), vec![3, 0]);
+        cx.modifiers = ModifiersState::default();
+        assert_eq!(drag([5.0, 50.0], [5.0, 50.0], &mut cx), vec![]);
+    }
+}
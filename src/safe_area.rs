@@ -0,0 +1,268 @@
+use crate::*;
+
+/// Insets from the edges of the window which content shouldn't be
+/// covered by, such as a phone's notch or home indicator, a custom title
+/// bar or a docked panel. Read them with `cx.get_env::<SafeAreaInsets>()`
+/// in any view, or use the `respect_safe_area` modifier.
+/// Add to them with `safe_area_inset`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SafeAreaInsets {
+    pub top: f32,
+    pub bottom: f32,
+    pub left: f32,
+    pub right: f32,
+}
+
+impl SafeAreaInsets {
+    /// Reads the insets of the window in logical pixels. On iOS the
+    /// window's inner rect is the safe area. Elsewhere there are no insets.
+    pub(crate) fn from_window(window: &Window) -> Self {
+        #[cfg(target_os = "ios")]
+        if let Ok(position) = window.inner_position() {
+            let scale = window.scale_factor() as f32;
+            let inner = window.inner_size();
+            let outer = window.outer_size();
+            let right = outer.width as i32 - inner.width as i32 - position.x;
+            let bottom = outer.height as i32 - inner.height as i32 - position.y;
+            return Self {
+                top: position.y as f32 / scale,
+                bottom: bottom.max(0) as f32 / scale,
+                left: position.x as f32 / scale,
+                right: right.max(0) as f32 / scale,
+            };
+        }
+
+        let _ = window;
+        Self::default()
+    }
+}
+
+impl std::ops::Add for SafeAreaInsets {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            top: self.top + other.top,
+            bottom: self.bottom + other.bottom,
+            left: self.left + other.left,
+            right: self.right + other.right,
+        }
+    }
+}
+
+impl SafeAreaInsets {
+    fn width(&self) -> f32 {
+        self.left + self.right
+    }
+
+    fn height(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+/// Insets which an enclosing `respect_safe_area` has already moved its
+/// content in by, so `ignore_safe_area` can extend back out.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct ConsumedSafeArea(SafeAreaInsets);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SafeAreaMode {
+    Respect,
+    Ignore,
+    Inset(SafeAreaInsets),
+}
+
+/// Struct for the `respect_safe_area`, `ignore_safe_area` and
+/// `safe_area_inset` modifiers.
+pub struct SafeArea<V> {
+    child: V,
+    mode: SafeAreaMode,
+}
+
+impl<V> SafeArea<V>
+where
+    V: View,
+{
+    pub fn respect(child: V) -> Self {
+        Self {
+            child,
+            mode: SafeAreaMode::Respect,
+        }
+    }
+
+    pub fn ignore(child: V) -> Self {
+        Self {
+            child,
+            mode: SafeAreaMode::Ignore,
+        }
+    }
+
+    pub fn inset(child: V, insets: SafeAreaInsets) -> Self {
+        Self {
+            child,
+            mode: SafeAreaMode::Inset(insets),
+        }
+    }
+
+    /// How far the child is moved in from this view's edges, which is
+    /// negative when ignoring.
+    fn edges(&self, cx: &Context) -> SafeAreaInsets {
+        match self.mode {
+            SafeAreaMode::Respect => cx.get_env::<SafeAreaInsets>().unwrap_or_default(),
+            SafeAreaMode::Ignore => {
+                let consumed = cx.get_env::<ConsumedSafeArea>().unwrap_or_default().0;
+                SafeAreaInsets {
+                    top: -consumed.top,
+                    bottom: -consumed.bottom,
+                    left: -consumed.left,
+                    right: -consumed.right,
+                }
+            }
+            SafeAreaMode::Inset(_) => SafeAreaInsets::default(),
+        }
+    }
+
+    /// Runs `f` with the environment the child sees.
+    fn with_child_env<R>(&self, cx: &mut Context, f: impl FnOnce(&mut Context) -> R) -> R {
+        let insets = cx.get_env::<SafeAreaInsets>().unwrap_or_default();
+        let consumed = cx.get_env::<ConsumedSafeArea>().unwrap_or_default().0;
+        match self.mode {
+            SafeAreaMode::Respect => cx.with_env(SafeAreaInsets::default(), |cx| {
+                cx.with_env(ConsumedSafeArea(consumed + insets), f)
+            }),
+            SafeAreaMode::Ignore => cx.with_env(insets + consumed, |cx| {
+                cx.with_env(ConsumedSafeArea::default(), f)
+            }),
+            SafeAreaMode::Inset(extra) => cx.with_env(insets + extra, f),
+        }
+    }
+
+    fn child_offset(&self, id: ViewID, cx: &mut Context) -> LocalOffset {
+        cx.layout.entry(id.child(&0)).or_default().offset
+    }
+}
+
+impl<V> View for SafeArea<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        match self.mode {
+            SafeAreaMode::Respect => println!(".respect_safe_area()"),
+            SafeAreaMode::Ignore => println!(".ignore_safe_area()"),
+            SafeAreaMode::Inset(insets) => println!(".safe_area_inset({:?})", insets),
+        }
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let mut local_event = event.clone();
+        local_event.position -= self.child_offset(id, cx);
+        self.child.process(&local_event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let offset = self.child_offset(id, cx);
+        vger.save();
+        vger.translate(offset);
+        self.with_child_env(cx, |cx| self.child.draw(id.child(&0), cx, vger));
+        vger.restore();
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let edges = self.edges(cx);
+        let inner = constraints.deflate([edges.width(), edges.height()].into());
+        let child_size =
+            self.with_child_env(cx, |cx| self.child.layout(id.child(&0), inner, cx, vger));
+        offset_guides(&mut cx.guides, [edges.left, edges.bottom].into());
+        let size = LocalSize::new(
+            child_size.width + edges.width(),
+            child_size.height + edges.height(),
+        );
+        let layout_box = cx.layout.entry(id.child(&0)).or_default();
+        layout_box.rect = LocalRect::new(LocalPoint::zero(), child_size);
+        layout_box.offset = [edges.left, edges.bottom].into();
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let offset = self.child_offset(id, cx);
+        self.child.hittest(id.child(&0), pt - offset, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        let edges = self.edges(cx);
+        self.with_child_env(cx, |cx| self.child.intrinsic_size(id.child(&0), cx, vger))
+            .map(|size| LocalSize::new(size.width + edges.width(), size.height + edges.height()))
+    }
+}
+
+impl<V> private::Sealed for SafeArea<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_respect_and_ignore() {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([100.0, 100.0].into());
+        let insets = SafeAreaInsets {
+            top: 10.0,
+            bottom: 20.0,
+            left: 0.0,
+            right: 0.0,
+        };
+        let content = rectangle();
+        let view = content.ignore_safe_area().respect_safe_area();
+        let sz = cx.with_env(insets, |cx| {
+            view.layout(cx.root_id, [100.0, 100.0].into(), cx, &mut vger)
+        });
+        assert_eq!(sz, LocalSize::new(100.0, 100.0));
+
+        let inner = cx.root_id.child(&0);
+        assert_eq!(cx.layout[&inner].offset, LocalOffset::new(0.0, 20.0));
+        let content = inner.child(&0);
+        assert_eq!(cx.layout[&content].offset, LocalOffset::new(0.0, -20.0));
+        // The rectangle is laid out over the whole window again.
+        assert_eq!(cx.layout[&content].rect.size, LocalSize::new(100.0, 100.0));
+    }
+}
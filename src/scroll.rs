@@ -0,0 +1,925 @@
+use crate::*;
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// How long `ScrollTo` takes to get there, when it's animated, and how
+/// long content pulled past its ends takes to spring back.
+const SCROLL_ANIMATION: Duration = Duration::from_millis(300);
+
+/// How far content can be pulled past its ends, by default.
+pub const SCROLL_OVERSCROLL: f32 = 80.0;
+
+/// How quickly a flick slows down: its velocity falls by a factor of e
+/// every this many seconds.
+const MOMENTUM_TIME: f32 = 0.325;
+
+/// How slowly a flick has to be going to stop, in points a second.
+const STOP_VELOCITY: f32 = 1.0;
+
+/// How fast a drag has to be going when it's let go of to flick.
+const FLICK_VELOCITY: f32 = 50.0;
+
+/// How long a drag can be held still before it's let go of and still
+/// flick.
+const FLICK_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// How long a flick bouncing off an end takes to reach the farthest it
+/// goes past it.
+const BOUNCE_TIME: f32 = 0.1;
+
+/// Where `length` shows when it's past `min` or `max`, pulled less and
+/// less far the farther it goes, up to `overscroll` past the end.
+fn rubber_band(length: f32, min: f32, max: f32, overscroll: f32) -> f32 {
+    let resist = |excess: f32| {
+        if overscroll > 0.0 {
+            overscroll * (1.0 - 1.0 / (excess * 0.55 / overscroll + 1.0))
+        } else {
+            0.0
+        }
+    };
+    if length < min {
+        min - resist(min - length)
+    } else if length > max {
+        max + resist(length - max)
+    } else {
+        length
+    }
+}
+
+/// Where a flick from `from` at `velocity` has coasted to after `t`
+/// seconds, slowing down exponentially, and whether it's still moving. A
+/// flick which would go past `min` or `max` bounces off, by up to
+/// `overscroll`, and comes to rest there.
+fn coast(from: f32, velocity: f32, t: f32, min: f32, max: f32, overscroll: f32) -> (f32, bool) {
+    let travel = velocity * MOMENTUM_TIME;
+    let decay = (-t / MOMENTUM_TIME).exp();
+    let rest = from + travel;
+    let edge = if rest > max {
+        max
+    } else if rest < min {
+        min
+    } else {
+        return (
+            from + travel * (1.0 - decay),
+            (velocity * decay).abs() > STOP_VELOCITY,
+        );
+    };
+
+    let reached = -MOMENTUM_TIME * (1.0 - (edge - from) / travel).ln();
+    if t < reached {
+        return (from + travel * (1.0 - decay), true);
+    }
+    // A critically damped spring from the edge, at the speed it got there.
+    let since = t - reached;
+    let limit = overscroll * std::f32::consts::E / BOUNCE_TIME;
+    let velocity = (velocity * (-reached / MOMENTUM_TIME).exp()).clamp(-limit, limit);
+    let position = edge + velocity * since * (-since / BOUNCE_TIME).exp();
+    (position, velocity != 0.0 && since < 6.0 * BOUNCE_TIME)
+}
+
+/// Which ways a `scroll_view` scrolls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollAxis {
+    Vertical,
+    Horizontal,
+    Both,
+}
+
+impl ScrollAxis {
+    fn vertical(self) -> bool {
+        self != ScrollAxis::Horizontal
+    }
+
+    fn horizontal(self) -> bool {
+        self != ScrollAxis::Vertical
+    }
+}
+
+/// Where `ScrollTo` puts what it scrolls to in the scroll view. Start is
+/// the top, or the left when scrolling sideways.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollAnchor {
+    Start,
+    Center,
+    End,
+
+    /// Scrolls as little as will show it, or not at all if it's shown.
+    Nearest,
+}
+
+/// What `ScrollTo` scrolls to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollTarget {
+    /// The start of the content.
+    Start,
+
+    /// The end of the content, like the newest message in a chat.
+    End,
+
+    /// A view marked with `scroll_id`, or a `list` row, by its id. Made
+    /// by `ScrollTo::id`.
+    Id(u64),
+
+    /// A `list` row, counting from 0.
+    Index(usize),
+}
+
+/// A message asking the scroll views showing `target` to scroll to it.
+/// Publish it on the `MessageBus`, for example with
+/// `button(text("top"), emit(ScrollTo::start()))`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollTo {
+    pub target: ScrollTarget,
+    pub anchor: ScrollAnchor,
+
+    /// Whether to scroll smoothly, rather than jump.
+    pub animated: bool,
+}
+
+impl ScrollTo {
+    pub fn new(target: ScrollTarget, anchor: ScrollAnchor) -> Self {
+        Self {
+            target,
+            anchor,
+            animated: true,
+        }
+    }
+
+    /// Scrolls to the view marked with `scroll_id(key)`, or the `list` row
+    /// with the id `key`.
+    pub fn id<K: Hash + ?Sized>(key: &K, anchor: ScrollAnchor) -> Self {
+        Self::new(ScrollTarget::Id(scroll_key(key)), anchor)
+    }
+
+    /// Scrolls to the `list` row at `index`.
+    pub fn index(index: usize, anchor: ScrollAnchor) -> Self {
+        Self::new(ScrollTarget::Index(index), anchor)
+    }
+
+    pub fn start() -> Self {
+        Self::new(ScrollTarget::Start, ScrollAnchor::Start)
+    }
+
+    pub fn end() -> Self {
+        Self::new(ScrollTarget::End, ScrollAnchor::End)
+    }
+
+    /// Jumps there rather than scrolling smoothly.
+    pub fn immediate(self) -> Self {
+        Self {
+            animated: false,
+            ..self
+        }
+    }
+}
+
+/// The id `ScrollTarget::Id` identifies `key` by.
+pub(crate) fn scroll_key<K: Hash + ?Sized>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Sent by a scroll view through its content to find where a target is.
+/// Its position starts at the origin of the content, so as it's passed
+/// down into each view's coordinates, it stays where that is.
+pub(crate) struct ScrollRequest {
+    pub(crate) target: ScrollTarget,
+    found: Cell<Option<LocalRect>>,
+}
+
+impl ScrollRequest {
+    /// The request in `event`, if there is one.
+    pub(crate) fn from_event(event: &Event) -> Option<&Self> {
+        match &event.kind {
+            EventKind::Message(message) => message.downcast_ref::<Self>(),
+            _ => None,
+        }
+    }
+
+    /// Reports that the target is at `rect`, in the coordinates of the
+    /// view `event` was passed to. The first view to answer is used.
+    pub(crate) fn answer(&self, event: &Event, rect: LocalRect) {
+        if self.found.get().is_none() {
+            self.found
+                .set(Some(rect.translate(-event.position.to_vector())));
+        }
+    }
+}
+
+/// Where views note what `ScrollProbe` finds, under their own id.
+const PROBE_KEY: &str = "scroll viewport";
+
+/// Sent through a scroll view's content each time it's laid out, so views
+/// which need to know where they are in it, like lists with sticky
+/// headers, can note it. Like `ScrollRequest`, its position starts at the
+/// origin of the content.
+pub(crate) struct ScrollProbe {
+    viewport: LocalSize,
+}
+
+impl ScrollProbe {
+    /// Notes where the view `id` is in the scroll view's content, if
+    /// `event` is a probe.
+    pub(crate) fn note(event: &Event, id: ViewID, cx: &mut Context) {
+        if let EventKind::Message(message) = &event.kind {
+            if let Some(probe) = message.downcast_ref::<Self>() {
+                cx.layout.insert(
+                    id.child(&PROBE_KEY),
+                    LayoutBox {
+                        rect: LocalRect::new(LocalPoint::zero(), probe.viewport),
+                        offset: -event.position.to_vector(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Where the view `id` is in the content of the scroll view it's in,
+    /// and the size the scroll view shows, if it's been noted.
+    pub(crate) fn noted(id: ViewID, cx: &Context) -> Option<LayoutBox> {
+        cx.layout.get(&id.child(&PROBE_KEY)).copied()
+    }
+}
+
+/// How the content is moving to the offset scrolled to.
+#[derive(Clone, Copy)]
+enum ScrollMotion {
+    /// Easing from where it was, after a `ScrollTo`, or springing back
+    /// after being pulled past an end.
+    Ease { from: LocalOffset, start: Instant },
+
+    /// Coasting after a flick, slowing down.
+    Coast {
+        from: LocalOffset,
+        velocity: LocalOffset,
+        start: Instant,
+    },
+}
+
+/// A drag of the content.
+#[derive(Clone, Copy)]
+struct ScrollDrag {
+    /// Where the mouse or finger was last, and when.
+    last: LocalPoint,
+    time: Instant,
+
+    /// The offset dragged to, which can be past the ends.
+    offset: LocalOffset,
+
+    /// How fast the offset is changing, in points a second.
+    velocity: LocalOffset,
+}
+
+#[derive(Clone, Default)]
+struct ScrollState {
+    /// How far the content is scrolled, if there's no binding for it.
+    offset: LocalOffset,
+
+    drag: Option<ScrollDrag>,
+
+    motion: Option<ScrollMotion>,
+}
+
+/// Struct for `scroll_view`.
+pub struct ScrollView<V, B> {
+    child: V,
+    binding: Option<B>,
+    axis: ScrollAxis,
+    overscroll: f32,
+}
+
+impl<V, B> ScrollView<V, B> {
+    /// Keeps how far the content is scrolled in `binding`, from its top
+    /// left, so it can be read, saved and restored, or set to scroll.
+    pub fn offset<B2: Binding<LocalOffset>>(self, binding: B2) -> ScrollView<V, B2> {
+        ScrollView {
+            child: self.child,
+            binding: Some(binding),
+            axis: self.axis,
+            overscroll: self.overscroll,
+        }
+    }
+
+    /// Sets which ways the content scrolls. Vertical by default.
+    pub fn axis(self, axis: ScrollAxis) -> Self {
+        Self { axis, ..self }
+    }
+
+    /// Sets how far the content can be dragged or flicked past its ends
+    /// before springing back. `SCROLL_OVERSCROLL` by default, and 0 stops
+    /// it at the ends.
+    pub fn overscroll(self, overscroll: f32) -> Self {
+        Self { overscroll, ..self }
+    }
+}
+
+impl<V, B> ScrollView<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<ScrollState> {
+        cx.get_state(id, ScrollState::default)
+    }
+
+    /// The offset scrolled to, which may still be being animated to.
+    fn target(&self, id: ViewID, cx: &mut Context) -> LocalOffset {
+        match &self.binding {
+            Some(binding) => binding.get(),
+            None => self.state(id, cx).with(|s| s.offset),
+        }
+    }
+
+    /// The offset the content is shown at now, and whether it's moving.
+    fn offset_now(&self, id: ViewID, cx: &mut Context) -> (LocalOffset, bool) {
+        let target = self.target(id, cx);
+        let max = self.max_offset(id, cx);
+        let (drag, motion) = self.state(id, cx).with(|s| (s.drag, s.motion));
+        if let Some(drag) = drag {
+            let offset = LocalOffset::new(
+                rubber_band(drag.offset.x, 0.0, max.width, self.overscroll),
+                rubber_band(drag.offset.y, 0.0, max.height, self.overscroll),
+            );
+            return (self.clamp_axes(offset), false);
+        }
+        match motion {
+            Some(ScrollMotion::Ease { from, start }) => {
+                let t = start.elapsed().as_secs_f32() / SCROLL_ANIMATION.as_secs_f32();
+                if t < 1.0 {
+                    let eased = 1.0 - (1.0 - t).powi(3);
+                    (from.lerp(target, eased), true)
+                } else {
+                    (target, false)
+                }
+            }
+            // Unless the offset has been set since.
+            Some(ScrollMotion::Coast {
+                from,
+                velocity,
+                start,
+            }) if self.clamp(id, cx, from + velocity * MOMENTUM_TIME) == target => {
+                let t = start.elapsed().as_secs_f32();
+                let (x, moving_x) = coast(from.x, velocity.x, t, 0.0, max.width, self.overscroll);
+                let (y, moving_y) = coast(from.y, velocity.y, t, 0.0, max.height, self.overscroll);
+                if moving_x || moving_y {
+                    (LocalOffset::new(x, y), true)
+                } else {
+                    (target, false)
+                }
+            }
+            _ => (target, false),
+        }
+    }
+
+    fn set_target(&self, id: ViewID, cx: &mut Context, offset: LocalOffset) {
+        match &self.binding {
+            Some(binding) => binding.set(offset),
+            None => self.state(id, cx).with_mut(|s| s.offset = offset),
+        }
+    }
+
+    /// Scrolls to `offset`, within the content, smoothly if `animated`.
+    fn scroll(&self, id: ViewID, cx: &mut Context, offset: LocalOffset, animated: bool) {
+        let offset = self.clamp(id, cx, offset);
+        let (now, _) = self.offset_now(id, cx);
+        let motion = if animated && now != offset {
+            Some(ScrollMotion::Ease {
+                from: now,
+                start: Instant::now(),
+            })
+        } else {
+            None
+        };
+        self.state(id, cx).with_mut(|s| s.motion = motion);
+        self.set_target(id, cx, offset);
+    }
+
+    /// Starts dragging the content from where it's shown, stopping it if
+    /// it's moving.
+    fn begin_drag(&self, id: ViewID, cx: &mut Context, position: LocalPoint) {
+        let (now, _) = self.offset_now(id, cx);
+        self.scroll(id, cx, now, false);
+        let offset = self.clamp(id, cx, now);
+        self.state(id, cx).with_mut(|s| {
+            s.drag = Some(ScrollDrag {
+                last: position,
+                time: Instant::now(),
+                offset,
+                velocity: LocalOffset::zero(),
+            })
+        });
+    }
+
+    fn drag(&self, id: ViewID, cx: &mut Context, position: LocalPoint) {
+        let state = self.state(id, cx);
+        let mut drag = match state.with(|s| s.drag) {
+            Some(drag) => drag,
+            None => return,
+        };
+        let moved = position - drag.last;
+        let delta = self.clamp_axes(LocalOffset::new(-moved.x, moved.y));
+        let now = Instant::now();
+        let dt = (now - drag.time).as_secs_f32();
+        if dt > 0.0 {
+            // Smoothed, so one uneven event doesn't throw it.
+            drag.velocity = (delta / dt).lerp(drag.velocity, 0.2);
+        }
+        drag.last = position;
+        drag.time = now;
+        drag.offset += delta;
+        state.with_mut(|s| s.drag = Some(drag));
+
+        let target = self.clamp(id, cx, drag.offset);
+        if target != self.target(id, cx) {
+            self.set_target(id, cx, target);
+        }
+    }
+
+    /// Lets go of the content, flicking it if it was moving quickly, or
+    /// springing it back if it was pulled past an end.
+    fn end_drag(&self, id: ViewID, cx: &mut Context) {
+        let (shown, _) = self.offset_now(id, cx);
+        let state = self.state(id, cx);
+        let drag = match state.with_mut(|s| s.drag.take()) {
+            Some(drag) => drag,
+            None => return,
+        };
+        let start = Instant::now();
+        let motion = if self.clamp(id, cx, shown) != shown {
+            Some(ScrollMotion::Ease { from: shown, start })
+        } else if drag.time.elapsed() < FLICK_TIMEOUT && drag.velocity.length() > FLICK_VELOCITY {
+            let rest = self.clamp(id, cx, shown + drag.velocity * MOMENTUM_TIME);
+            self.set_target(id, cx, rest);
+            Some(ScrollMotion::Coast {
+                from: shown,
+                velocity: drag.velocity,
+                start,
+            })
+        } else {
+            None
+        };
+        state.with_mut(|s| s.motion = motion);
+    }
+
+    /// The viewport, and the size of the content.
+    fn sizes(&self, id: ViewID, cx: &mut Context) -> (LocalRect, LocalSize) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let content = cx.child_rect(id).size;
+        (rect, content)
+    }
+
+    /// How far the content can be scrolled.
+    fn max_offset(&self, id: ViewID, cx: &mut Context) -> LocalSize {
+        let (rect, content) = self.sizes(id, cx);
+        (content - rect.size).max(LocalSize::zero())
+    }
+
+    /// Zeroes `offset` on the axes which don't scroll.
+    fn clamp_axes(&self, offset: LocalOffset) -> LocalOffset {
+        LocalOffset::new(
+            if self.axis.horizontal() {
+                offset.x
+            } else {
+                0.0
+            },
+            if self.axis.vertical() { offset.y } else { 0.0 },
+        )
+    }
+
+    /// Keeps `offset` within the content, on the axes which scroll.
+    fn clamp(&self, id: ViewID, cx: &mut Context, offset: LocalOffset) -> LocalOffset {
+        let max = self.max_offset(id, cx);
+        self.clamp_axes(LocalOffset::new(
+            offset.x.clamp(0.0, max.width),
+            offset.y.clamp(0.0, max.height),
+        ))
+    }
+
+    /// Where the content's origin is in the scroll view, scrolled by
+    /// `offset`.
+    fn content_origin(&self, id: ViewID, cx: &mut Context, offset: LocalOffset) -> LocalOffset {
+        let (rect, content) = self.sizes(id, cx);
+        LocalOffset::new(-offset.x, rect.height() - content.height + offset.y)
+    }
+
+    /// The offset which puts `rect`, in the content's coordinates, at
+    /// `anchor`.
+    fn offset_to(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        rect: LocalRect,
+        anchor: ScrollAnchor,
+    ) -> LocalOffset {
+        let (viewport, content) = self.sizes(id, cx);
+        let current = self.target(id, cx);
+        let align = |start: f32, end: f32, current: f32, length: f32| match anchor {
+            ScrollAnchor::Start => start,
+            ScrollAnchor::Center => (start + end - length) / 2.0,
+            ScrollAnchor::End => end - length,
+            ScrollAnchor::Nearest if start < current => start,
+            ScrollAnchor::Nearest if end > current + length => end - length,
+            ScrollAnchor::Nearest => current,
+        };
+        // Measured down from the top, as the offset is.
+        LocalOffset::new(
+            align(rect.min_x(), rect.max_x(), current.x, viewport.width()),
+            align(
+                content.height - rect.max_y(),
+                content.height - rect.min_y(),
+                current.y,
+                viewport.height(),
+            ),
+        )
+    }
+
+    /// Finds where `target` is in the content.
+    fn find(
+        &self,
+        target: ScrollTarget,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalRect> {
+        let request = Rc::new(ScrollRequest {
+            target,
+            found: Cell::new(None),
+        });
+        self.dispatch(request.clone(), id, cx, vger);
+        request.found.get()
+    }
+
+    /// Sends `message` through the content, from the content's origin.
+    fn dispatch(
+        &self,
+        message: Rc<dyn Any>,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) {
+        let event = Event {
+            kind: EventKind::Message(message),
+            position: LocalPoint::zero(),
+        };
+        let consumed = cx.consumed;
+        cx.consumed = false;
+        self.child.process(&event, id.child(&0), cx, vger);
+        cx.consumed = consumed;
+    }
+
+    fn scroll_to(&self, to: &ScrollTo, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let offset = match to.target {
+            ScrollTarget::Start => LocalOffset::zero(),
+            ScrollTarget::End => {
+                let (rect, content) = self.sizes(id, cx);
+                (content - rect.size).to_vector()
+            }
+            target => match self.find(target, id, cx, vger) {
+                Some(rect) => self.offset_to(id, cx, rect, to.anchor),
+                None => return,
+            },
+        };
+        self.scroll(id, cx, offset, to.animated);
+    }
+}
+
+impl<V, B> View for ScrollView<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("scroll_view({:?}) {{", self.target(id, cx));
+        (self.child).print(id.child(&0), cx);
+        println!("}}");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::Message(message) = &event.kind {
+            if let Some(to) = message.downcast_ref::<ScrollTo>() {
+                self.scroll_to(to, id, cx, vger);
+            }
+            // The content of this scroll view has been probed already.
+            if message.is::<ScrollProbe>() {
+                return;
+            }
+        }
+
+        let rect = cx.layout.entry(id).or_default().rect;
+        let inside = rect.contains(event.position);
+        let (offset, moving) = self.offset_now(id, cx);
+        let origin = self.content_origin(id, cx, offset);
+
+        // Content scrolled out of sight can't be touched, and a touch on
+        // moving content stops it rather than going to the content.
+        let touched = matches!(event.kind, EventKind::TouchBegin { .. });
+        if !(touched && (!inside || moving)) {
+            let mut local_event = event.clone();
+            local_event.position -= origin;
+            self.child.process(&local_event, id.child(&0), cx, vger);
+        }
+
+        // Scroll views inside get the first go at the wheel.
+        match &event.kind {
+            EventKind::Scroll { delta } if inside && !cx.event_consumed() => {
+                let scrolled = LocalOffset::new(offset.x + delta.x, offset.y + delta.y);
+                self.scroll(id, cx, scrolled, false);
+                if self.clamp(id, cx, scrolled) != offset {
+                    cx.consume_event();
+                }
+            }
+            // Dragging scrolls, unless the content took the touch.
+            EventKind::TouchBegin { id: touch }
+                if inside && cx.touches[*touch] == ViewID::default() =>
+            {
+                cx.touches[*touch] = id;
+                self.begin_drag(id, cx, event.position);
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                self.drag(id, cx, event.position);
+            }
+            EventKind::TouchEnd { id: touch } | EventKind::TouchCancel { id: touch }
+                if cx.touches[*touch] == id =>
+            {
+                cx.touches[*touch] = ViewID::default();
+                self.end_drag(id, cx);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let (offset, animating) = self.offset_now(id, cx);
+        let origin = self.content_origin(id, cx, offset);
+
+        let viewport = LocalRect::new(-origin.to_point(), rect.size);
+        let outer_viewport = cx.scroll_viewport.replace(viewport);
+
+        vger.save();
+        vger.clip(rect);
+        vger.translate(origin);
+        self.child.draw(id.child(&0), cx, vger);
+        vger.restore();
+        cx.scroll_viewport = outer_viewport;
+
+        if animating {
+            if let Some(window) = &cx.window {
+                window.request_redraw();
+            }
+        }
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+
+        // The content is as long as it likes along the axes which scroll.
+        let max = LocalSize::new(
+            if self.axis.horizontal() {
+                f32::INFINITY
+            } else {
+                size.width
+            },
+            if self.axis.vertical() {
+                f32::INFINITY
+            } else {
+                size.height
+            },
+        );
+        let content = self
+            .child
+            .layout(id.child(&0), Constraints::loose(max), cx, vger);
+
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        cx.note_child_rect(id, LocalRect::new(LocalPoint::zero(), content));
+        self.dispatch(Rc::new(ScrollProbe { viewport: size }), id, cx, vger);
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        if !rect.contains(pt) {
+            return None;
+        }
+        let (offset, _) = self.offset_now(id, cx);
+        let origin = self.content_origin(id, cx, offset);
+        self.child
+            .hittest(id.child(&0), pt - origin, cx, vger)
+            .or(Some(id))
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = self.state(id, cx);
+        map.insert(id, Box::new(state));
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let mut node = accesskit::Node::new(id.access_id(), accesskit::Role::ScrollView);
+        if let Some(child) = self.child.access(id.child(&0), cx, nodes) {
+            node.children.push(child);
+        }
+        nodes.push(node);
+        Some(id.access_id())
+    }
+}
+
+impl<V, B> private::Sealed for ScrollView<V, B> {}
+
+/// Shows `child` at its full length, scrolled by the mouse wheel or by
+/// dragging, which carries on for a while after a flick. Publish
+/// `ScrollTo` to scroll to something in it, and use `offset` to keep how
+/// far it's scrolled. Put a `list` in one to scroll the list.
+pub fn scroll_view<V: View>(child: V) -> ScrollView<V, State<LocalOffset>> {
+    ScrollView {
+        child,
+        binding: None,
+        axis: ScrollAxis::Vertical,
+        overscroll: SCROLL_OVERSCROLL,
+    }
+}
+
+/// Struct for the `scroll_id` modifier.
+pub struct ScrollId<V> {
+    child: V,
+    key: u64,
+}
+
+impl<V> ScrollId<V> {
+    pub fn new<K: Hash + ?Sized>(child: V, key: &K) -> Self {
+        Self {
+            child,
+            key: scroll_key(key),
+        }
+    }
+}
+
+impl<V> View for ScrollId<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".scroll_id({})", self.key);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let Some(request) = ScrollRequest::from_event(event) {
+            if request.target == ScrollTarget::Id(self.key) {
+                let rect = cx.child_rect(id);
+                request.answer(event, rect);
+            }
+        }
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.child.layout(id.child(&0), constraints, cx, vger);
+        cx.note_child_rect(id, LocalRect::new(LocalPoint::zero(), size));
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V> private::Sealed for ScrollId<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_to() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let offset = State::new(LocalOffset::zero(), cx.dirty.clone());
+        let ui = scroll_view(list((0..20).collect(), |i| {
+            rectangle()
+                .size([100.0, 20.0])
+                .scroll_id(&format!("row {}", i))
+        }))
+        .offset(offset.clone());
+        cx.layout_root(&ui, size, &mut vger);
+
+        let mut scroll = |to: ScrollTo| {
+            cx.publish(to.immediate());
+            cx.deliver_messages(&ui, &mut vger);
+            offset.get().y
+        };
+        assert_eq!(scroll(ScrollTo::end()), 300.0);
+        assert_eq!(scroll(ScrollTo::index(2, ScrollAnchor::Start)), 40.0);
+        assert_eq!(scroll(ScrollTo::id("row 10", ScrollAnchor::Center)), 160.0);
+        assert_eq!(scroll(ScrollTo::index(12, ScrollAnchor::Nearest)), 160.0);
+        assert_eq!(scroll(ScrollTo::index(15, ScrollAnchor::Nearest)), 220.0);
+        assert_eq!(scroll(ScrollTo::id(&5, ScrollAnchor::End)), 20.0);
+        assert_eq!(scroll(ScrollTo::id("missing", ScrollAnchor::Start)), 20.0);
+        assert_eq!(scroll(ScrollTo::start()), 0.0);
+    }
+
+    #[test]
+    fn test_momentum() {
+        // A flick coasts to a stop MOMENTUM_TIME times its speed away.
+        let (x, moving) = coast(0.0, 1000.0, 0.1, 0.0, 1000.0, 80.0);
+        assert!(x > 0.0 && x < 325.0 && moving);
+        let (x, moving) = coast(0.0, 1000.0, 10.0, 0.0, 1000.0, 80.0);
+        assert!((x - 325.0).abs() < 0.5 && !moving);
+
+        // Past an end, it bounces off and comes back.
+        let farthest = (0..100)
+            .map(|i| coast(0.0, 1000.0, i as f32 * 0.02, 0.0, 100.0, 80.0).0)
+            .fold(0.0, f32::max);
+        assert!(farthest > 100.0 && farthest <= 180.0);
+        assert_eq!(coast(0.0, 1000.0, 10.0, 0.0, 100.0, 80.0), (100.0, false));
+        let stopped = (0..100)
+            .map(|i| coast(0.0, 1000.0, i as f32 * 0.02, 0.0, 100.0, 0.0).0)
+            .fold(0.0, f32::max);
+        assert_eq!(stopped, 100.0);
+
+        assert_eq!(rubber_band(50.0, 0.0, 100.0, 80.0), 50.0);
+        let pulled = rubber_band(300.0, 0.0, 100.0, 80.0);
+        assert!(pulled > 100.0 && pulled < 180.0);
+        assert_eq!(rubber_band(-300.0, 0.0, 100.0, 0.0), 0.0);
+    }
+}
@@ -0,0 +1,435 @@
+use crate::*;
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default delay after the last edit before a search runs.
+pub const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+type SearchCallback = Rc<dyn Fn(String)>;
+type SuggestionSource = Arc<dyn Fn(String) -> BoxFuture<'static, Vec<String>> + Send + Sync>;
+
+#[derive(Clone)]
+struct SearchState {
+    field: TextFieldState,
+    suggestions: Vec<String>,
+
+    /// Waiting for editing to pause, or for suggestions. Aborted by the
+    /// next edit.
+    task: Option<TaskHandle>,
+}
+
+impl SearchState {
+    fn new() -> Self {
+        Self {
+            field: TextFieldState::new(),
+            suggestions: vec![],
+            task: None,
+        }
+    }
+}
+
+/// Sent back from a search field's task once editing pauses, and again
+/// with the suggestions.
+struct Searched {
+    id: ViewID,
+    query: String,
+    suggestions: Option<Vec<String>>,
+}
+
+#[derive(Clone)]
+struct Searcher {
+    on_search: Option<SearchCallback>,
+    suggestions: Option<SuggestionSource>,
+    delay: Duration,
+}
+
+impl Searcher {
+    /// Searches once the debounce delay has passed, unless there's
+    /// another edit in the meantime.
+    fn schedule(&self, s: &State<SearchState>, id: ViewID, cx: &mut Context, query: String) {
+        if let Some(task) = s.with_mut(|s| s.task.take()) {
+            task.abort();
+        }
+        let delay = self.delay;
+        let source = self.suggestions.clone();
+        let task = cx.spawn(id, async move {
+            task::delay(delay).await;
+            injector().send(Searched {
+                id,
+                query: query.clone(),
+                suggestions: None,
+            });
+            if let Some(source) = source {
+                let suggestions = if query.is_empty() {
+                    vec![]
+                } else {
+                    source(query.clone()).await
+                };
+                injector().send(Searched {
+                    id,
+                    query,
+                    suggestions: Some(suggestions),
+                });
+            }
+        });
+        s.with_mut(|s| s.task = Some(task));
+    }
+
+    fn set_text(&self, s: &State<SearchState>, text: &impl Binding<String>, value: String) {
+        let len = value.len();
+        text.set(value);
+        s.with_mut(|s| {
+            s.field.cursor = len;
+            s.suggestions.clear();
+        });
+    }
+}
+
+/// The clear button at the right end of a search field. Fills the
+/// space it's offered, but only hits where the button is drawn.
+struct ClearButton {
+    visible: bool,
+}
+
+impl ClearButton {
+    fn geom(&self, id: ViewID, cx: &mut Context) -> (LocalPoint, f32) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let radius = TEXT_FIELD_HEIGHT / 4.0;
+        (
+            [rect.max_x() - TEXT_FIELD_HEIGHT / 2.0, rect.center().y].into(),
+            radius,
+        )
+    }
+}
+
+impl View for ClearButton {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("ClearButton");
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if self.visible {
+            let (center, radius) = self.geom(id, cx);
+            let paint = vger.color_paint(MEDIUM_GRAY);
+            vger.fill_circle(center, radius, paint);
+
+            let d = radius / 2.0;
+            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            let a = LocalOffset::new(d, d);
+            let b = LocalOffset::new(d, -d);
+            vger.stroke_segment(center - a, center + a, 1.5, paint);
+            vger.stroke_segment(center - b, center + b, 1.5, paint);
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let (center, radius) = self.geom(id, cx);
+        if self.visible && pt.distance_to(center) < radius {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {
+        // do nothing
+    }
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {
+        // do nothing
+    }
+
+    fn access(
+        &self,
+        _id: ViewID,
+        _cx: &mut Context,
+        _nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        None
+    }
+}
+
+impl private::Sealed for ClearButton {}
+
+/// Struct for `search_field`.
+pub struct SearchField<B> {
+    text: B,
+    searcher: Searcher,
+}
+
+impl<B> SearchField<B>
+where
+    B: Binding<String>,
+{
+    /// Calls `f` with the search text once editing pauses.
+    pub fn on_search<F: Fn(String) + 'static>(mut self, f: F) -> Self {
+        self.searcher.on_search = Some(Rc::new(f));
+        self
+    }
+
+    /// Shows suggestions below the field, from the future `f` makes once
+    /// editing pauses. The future runs as a task, and is dropped if
+    /// there's another edit before it's done. Tapping a suggestion fills
+    /// in the field.
+    pub fn suggestions<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<String>> + Send + 'static,
+    {
+        self.searcher.suggestions = Some(Arc::new(move |query| Box::pin(f(query))));
+        self
+    }
+
+    /// Sets how long to wait after the last edit before searching.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.searcher.delay = delay;
+        self
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<SearchState> {
+        cx.get_state(id, SearchState::new)
+    }
+
+    fn body(&self, s: State<SearchState>) -> impl View {
+        let query = self.text.clone();
+        let searcher = self.searcher.clone();
+        read_env(move |size: ControlSize| {
+            let (s, query, searcher) = (s.clone(), query.clone(), searcher.clone());
+            focus(move |has_focus| {
+                let suggestions = s.with(|s| s.suggestions.clone());
+                let show_suggestions = has_focus && !suggestions.is_empty();
+                let (s, query, searcher) = (s.clone(), query.clone(), searcher.clone());
+                vstack((
+                    search_row(&s, &query, &searcher, has_focus, size),
+                    cond(
+                        show_suggestions,
+                        list((0..suggestions.len()).collect(), move |i| {
+                            let suggestion = suggestions[*i].clone();
+                            let (s, query, searcher) = (s.clone(), query.clone(), searcher.clone());
+                            text(&suggestion)
+                                .padding(Auto)
+                                .tap(move || searcher.set_text(&s, &query, suggestion.clone()))
+                        }),
+                        EmptyView {},
+                    ),
+                ))
+            })
+        })
+    }
+}
+
+fn search_row(
+    s: &State<SearchState>,
+    text: &impl Binding<String>,
+    searcher: &Searcher,
+    has_focus: bool,
+    size: ControlSize,
+) -> impl View {
+    let t = text.get();
+    let cursor = s.with(|s| s.field.cursor);
+    let (s2, text2, searcher2) = (s.clone(), text.clone(), searcher.clone());
+    let (s, text, searcher) = (s.clone(), text.clone(), searcher.clone());
+
+    zstack((
+        renderer_canvas(move |rect, vger| draw_text_field(rect, &t, cursor, has_focus, size, vger))
+            .key(move |k| {
+                if has_focus {
+                    if k == KeyPress::Escape {
+                        searcher.set_text(&s, &text, String::new());
+                    } else {
+                        s.with_mut(|s| s.field.key(&k, &text));
+                    }
+                }
+            }),
+        ClearButton {
+            visible: !text2.with(|t| t.is_empty()),
+        }
+        .tap(move || searcher2.set_text(&s2, &text2, String::new())),
+    ))
+}
+
+impl<B> View for SearchField<B>
+where
+    B: Binding<String>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        println!("search_field(");
+        let s = self.state(id, cx);
+        self.body(s).print(id.child(&0), cx);
+        println!(")");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let s = self.state(id, cx);
+        if let EventKind::User(payload) = &event.kind {
+            if let Some(searched) = payload.downcast_ref::<Searched>() {
+                // Results for text which has since changed are dropped.
+                if searched.id == id && searched.query == self.text.get() {
+                    match &searched.suggestions {
+                        Some(suggestions) => s.with_mut(|s| s.suggestions = suggestions.clone()),
+                        None => {
+                            if let Some(on_search) = &self.searcher.on_search {
+                                on_search(searched.query.clone())
+                            }
+                        }
+                    }
+                }
+                return;
+            }
+        }
+
+        let before = self.text.get();
+        self.body(s.clone()).process(event, id.child(&0), cx, vger);
+        let after = self.text.get();
+        if after != before {
+            self.searcher.schedule(&s, id, cx, after);
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let s = self.state(id, cx);
+        self.body(s).draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let s = self.state(id, cx);
+        self.body(s).layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let s = self.state(id, cx);
+        self.body(s).hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        let s = self.state(id, cx);
+        self.body(s).commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let s = self.state(id, cx);
+        map.insert(id, Box::new(s.clone()));
+        self.body(s).gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let s = self.state(id, cx);
+        self.body(s).access(id.child(&0), cx, nodes)
+    }
+}
+
+impl<B> private::Sealed for SearchField<B> {}
+
+/// Text field for searching, with a clear button. Escape clears the
+/// search. Use `on_search` to be notified once editing pauses, and
+/// `suggestions` to show a dropdown of completions.
+pub fn search_field(text: impl Binding<String>) -> SearchField<impl Binding<String>> {
+    SearchField {
+        text,
+        searcher: Searcher {
+            on_search: None,
+            suggestions: None,
+            delay: SEARCH_DEBOUNCE,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::time::Instant;
+
+    #[test]
+    fn test_search_field() {
+        let _timers = lock_app_timers();
+        let _lock = crate::injector::tests::lock_user_events();
+        let size = LocalSize::new(200.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let query = State::new(String::new(), cx.dirty.clone());
+        let searched = Rc::new(RefCell::new(vec![]));
+        let s = searched.clone();
+        let ui = search_field(query.clone())
+            .debounce(Duration::from_millis(10))
+            .on_search(move |q| s.borrow_mut().push(q))
+            .suggestions(|q| async move { vec![format!("{}!", q)] });
+        cx.layout_root(&ui, size, &mut vger);
+
+        let mut send = |kind: EventKind, cx: &mut Context| {
+            let event = Event {
+                kind,
+                position: [20.0, 75.0].into(),
+            };
+            cx.process_root(&ui, &event, &mut vger);
+        };
+        send(EventKind::TouchBegin { id: 0 }, &mut cx);
+        send(EventKind::TouchEnd { id: 0 }, &mut cx);
+        for key in ["a", "b"] {
+            let mods = ModifiersState::empty();
+            send(EventKind::Key(KeyPress::Character(key), mods), &mut cx);
+        }
+        assert_eq!(query.get(), "ab");
+
+        // Only the search for the last edit runs, once editing pauses.
+        std::thread::sleep(Duration::from_millis(20));
+        run_timers(Instant::now());
+        for task in cx.tasks(cx.root_id) {
+            task.join();
+        }
+        for payload in take_user_events() {
+            send(EventKind::User(payload), &mut cx);
+        }
+        assert_eq!(*searched.borrow(), vec!["ab".to_string()]);
+        let s: State<SearchState> = cx.get_state(cx.root_id, SearchState::new);
+        assert_eq!(s.with(|s| s.suggestions.clone()), vec!["ab!".to_string()]);
+    }
+}
@@ -1,13 +1,23 @@
 use crate::*;
 
+/// A shape which can say whether a point is inside it, for `content_shape`.
+pub trait Shape {
+    /// Returns true if `pt` is inside the shape when it fills `rect`.
+    fn contains(&self, rect: LocalRect, pt: LocalPoint) -> bool;
+}
+
 /// Struct for `circle`.
 pub struct Circle {
     paint: Paint,
 }
 
 impl Circle {
+    fn geom_rect(&self, id: ViewID, cx: &mut Context) -> LocalRect {
+        cx.layout.entry(id).or_default().rect
+    }
+
     fn geom(&self, id: ViewID, cx: &mut Context) -> (LocalPoint, f32) {
-        let rect = cx.layout.entry(id).or_insert(LayoutBox::default()).rect;
+        let rect = self.geom_rect(id, cx);
 
         (rect.center(), rect.size.width.min(rect.size.height) / 2.0)
     }
@@ -24,26 +34,35 @@ impl View for Circle {
         println!("circle");
     }
 
-    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut VGER) {
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
         // do nothing
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         let (center, radius) = self.geom(id, cx);
 
-        let paint = self.paint.vger_paint(vger);
-        vger.fill_circle(center, radius, paint);
+        vger.fill_circle(center, radius, self.paint);
+
+        let rect = self.geom_rect(id, cx);
+        inspector_highlight(cx, id, rect, vger);
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, _vger: &mut VGER) -> LocalSize {
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
         cx.layout.insert(
             id,
             LayoutBox {
-                rect: LocalRect::new(LocalPoint::zero(), sz),
+                rect: LocalRect::new(LocalPoint::zero(), size),
                 offset: LocalOffset::zero(),
             },
         );
-        sz
+        size
     }
 
     fn hittest(
@@ -51,7 +70,7 @@ impl View for Circle {
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        _vger: &mut VGER,
+        _vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         let (center, radius) = self.geom(id, cx);
 
@@ -82,6 +101,12 @@ impl View for Circle {
 
 impl private::Sealed for Circle {}
 
+impl Shape for Circle {
+    fn contains(&self, rect: LocalRect, pt: LocalPoint) -> bool {
+        pt.distance_to(rect.center()) < rect.size.width.min(rect.size.height) / 2.0
+    }
+}
+
 /// Renders a circle which expands to fill available space.
 pub fn circle() -> Circle {
     Circle {
@@ -122,26 +147,34 @@ impl View for Rectangle {
         println!("rectangle");
     }
 
-    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut VGER) {
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
         // do nothing
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         let rect = self.geom(id, cx);
 
-        let paint = self.paint.vger_paint(vger);
-        vger.fill_rect(rect, self.corner_radius, paint);
+        vger.fill_rect(rect, self.corner_radius, self.paint);
+
+        inspector_highlight(cx, id, rect, vger);
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, _vger: &mut VGER) -> LocalSize {
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
         cx.layout.insert(
             id,
             LayoutBox {
-                rect: LocalRect::new(LocalPoint::zero(), sz),
+                rect: LocalRect::new(LocalPoint::zero(), size),
                 offset: LocalOffset::zero(),
             },
         );
-        sz
+        size
     }
 
     fn hittest(
@@ -149,7 +182,7 @@ impl View for Rectangle {
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        _vger: &mut VGER,
+        _vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         let rect = self.geom(id, cx);
 
@@ -180,6 +213,26 @@ impl View for Rectangle {
 
 impl private::Sealed for Rectangle {}
 
+impl Shape for Rectangle {
+    fn contains(&self, rect: LocalRect, pt: LocalPoint) -> bool {
+        if !rect.contains(pt) {
+            return false;
+        }
+        // Outside the corners, the point has to be within the radius of
+        // the nearest corner's center.
+        let radius = self
+            .corner_radius
+            .min(rect.width() / 2.0)
+            .min(rect.height() / 2.0);
+        let inner = rect.inflate(-radius, -radius);
+        let nearest = LocalPoint::new(
+            pt.x.clamp(inner.min_x(), inner.max_x()),
+            pt.y.clamp(inner.min_y(), inner.max_y()),
+        );
+        pt.distance_to(nearest) <= radius
+    }
+}
+
 /// Renders a rectangle which expands to fill available space.
 pub fn rectangle() -> Rectangle {
     Rectangle {
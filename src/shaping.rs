@@ -0,0 +1,106 @@
+//! Text shaping with rustybuzz for the software renderer, so scripts which
+//! join or reorder letters, like Arabic and Devanagari, draw correctly,
+//! along with ligatures and kerning. vger draws text unshaped.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A glyph positioned by shaping, in pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ShapedGlyph {
+    pub(crate) glyph: u16,
+
+    /// Byte offset of the first character the glyph draws.
+    pub(crate) cluster: usize,
+    pub(crate) x_advance: f32,
+    pub(crate) x_offset: f32,
+    pub(crate) y_offset: f32,
+}
+
+/// Shapes a run of text drawn with one font, in visual order.
+pub(crate) fn shape_run(data: &[u8], index: u32, px: f32, text: &str) -> Vec<ShapedGlyph> {
+    let face = match rustybuzz::Face::from_slice(data, index) {
+        Some(face) => face,
+        None => return vec![],
+    };
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.guess_segment_properties();
+    let output = rustybuzz::shape(&face, &[], buffer);
+
+    let s = px / face.units_per_em() as f32;
+    output
+        .glyph_infos()
+        .iter()
+        .zip(output.glyph_positions())
+        .map(|(info, pos)| ShapedGlyph {
+            glyph: info.glyph_id as u16,
+            cluster: info.cluster as usize,
+            x_advance: pos.x_advance as f32 * s,
+            x_offset: pos.x_offset as f32 * s,
+            y_offset: pos.y_offset as f32 * s,
+        })
+        .collect()
+}
+
+/// Number of runs to keep shaped. Text is drawn every frame, so the cache
+/// only needs to hold what's on screen.
+const SHAPE_CACHE_SIZE: usize = 1024;
+
+/// Shaped runs by font, size and text.
+#[derive(Default)]
+pub(crate) struct ShapeCache {
+    runs: HashMap<(usize, u32, String), Rc<Vec<ShapedGlyph>>>,
+}
+
+impl ShapeCache {
+    pub(crate) fn get_or_shape(
+        &mut self,
+        font: usize,
+        px: f32,
+        text: &str,
+        shape: impl FnOnce() -> Vec<ShapedGlyph>,
+    ) -> Rc<Vec<ShapedGlyph>> {
+        let key = (font, px.to_bits(), text.to_string());
+        if let Some(run) = self.runs.get(&key) {
+            return run.clone();
+        }
+        if self.runs.len() >= SHAPE_CACHE_SIZE {
+            self.runs.clear();
+        }
+        let run = Rc::new(shape());
+        self.runs.insert(key, run.clone());
+        run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_cache() {
+        let glyph = |glyph| ShapedGlyph {
+            glyph,
+            cluster: 0,
+            x_advance: 10.0,
+            x_offset: 0.0,
+            y_offset: 0.0,
+        };
+        let mut cache = ShapeCache::default();
+        let mut shaped = 0;
+        for _ in 0..2 {
+            cache.get_or_shape(0, 12.0, "fi", || {
+                shaped += 1;
+                vec![glyph(1)]
+            });
+        }
+        assert_eq!(shaped, 1);
+
+        // Other sizes and fonts are shaped separately.
+        let run = cache.get_or_shape(1, 12.0, "fi", || vec![glyph(2)]);
+        assert_eq!(run[0].glyph, 2);
+        let run = cache.get_or_shape(0, 14.0, "fi", || vec![glyph(3)]);
+        assert_eq!(run[0].glyph, 3);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A value which isn't tied to a view, like a `State`, but can be made
+/// anywhere and shared between views. Reading a signal while a view is
+/// built subscribes that view to it, so changing the signal only builds
+/// again the views which read it. See `reactive`.
+pub struct Signal<T> {
+    value: Arc<Mutex<T>>,
+
+    /// How many times the value has changed.
+    version: Arc<AtomicU64>,
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl<T> Signal<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Arc::new(Mutex::new(value)),
+            version: Default::default(),
+        }
+    }
+}
+
+impl<T> Binding<T> for Signal<T>
+where
+    T: 'static,
+{
+    fn with<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        note_read(&self.version);
+        f(&self.value.lock().unwrap())
+    }
+    fn with_mut<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        let r = f(&mut self.value.lock().unwrap());
+        self.redraw();
+        r
+    }
+    fn redraw(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+        redraw_app();
+    }
+}
+
+/// Makes a `Signal` holding `value`.
+pub fn signal<T>(value: T) -> Signal<T> {
+    Signal::new(value)
+}
+
+/// A view built by `f`, which is only built again when a signal or state
+/// it read changes. Wrap the parts of a view which read signals in this,
+/// so the rest isn't built again with them.
+pub fn reactive<V: View + 'static, F: Fn() -> V + 'static>(f: F) -> impl View + 'static {
+    state((), move |_| f())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_signal_subscription() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let (a, b) = (signal(0), signal(0));
+        let builds = Rc::new(Cell::new(0));
+        let builds2 = builds.clone();
+        let a2 = a.clone();
+        let ui = hstack((
+            reactive(move || {
+                builds2.set(builds2.get() + 1);
+                rectangle().size([a2.get() as f32, 10.0])
+            }),
+            rectangle(),
+        ));
+
+        cx.layout_root(&ui, size, &mut vger);
+        b.set(1);
+        cx.layout_root(&ui, size, &mut vger);
+        assert_eq!(builds.get(), 1);
+        a.set(1);
+        cx.layout_root(&ui, size, &mut vger);
+        assert_eq!(builds.get(), 2);
+    }
+}
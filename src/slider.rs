@@ -3,6 +3,7 @@ use crate::*;
 const SLIDER_WIDTH: f32 = 4.0;
 const SLIDER_THUMB_RADIUS: f32 = 10.0;
 
+#[derive(Clone)]
 pub struct HSlider<B> {
     value: B,
     thumb: Color,
@@ -27,7 +28,7 @@ where
             let x = value.get() * w;
             let value = value.clone();
 
-            canvas(move |sz, vger| {
+            renderer_canvas(move |sz, vger| {
                 let c = sz.center();
                 let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
                 vger.fill_rect(
@@ -42,7 +43,7 @@ where
                     paint,
                 );
                 let paint = vger.color_paint(thumb_color);
-                vger.fill_circle([x, c.y], SLIDER_THUMB_RADIUS, paint);
+                vger.fill_circle([x, c.y].into(), SLIDER_THUMB_RADIUS, paint);
             })
             .geom(move |sz| {
                 if sz.width != w {
@@ -54,6 +55,7 @@ where
             })
         })
         .role(accesskit::Role::Slider)
+        .value(self.value.get())
     }
 
     pub fn thumb_color(self, thumb_color: Color) -> Self {
@@ -66,6 +68,17 @@ where
 
 impl<B> private::Sealed for HSlider<B> {}
 
+impl<B> FormInput for HSlider<B>
+where
+    B: Binding<f32>,
+{
+    type Value = f32;
+
+    fn value(&self) -> f32 {
+        self.value.get()
+    }
+}
+
 /// Horizontal slider built from other Views.
 pub fn hslider(value: impl Binding<f32>) -> HSlider<impl Binding<f32>> {
     HSlider {
@@ -98,7 +111,7 @@ where
             let y = value.get() * h;
             let value = value.clone();
 
-            canvas(move |sz, vger| {
+            renderer_canvas(move |sz, vger| {
                 let c = sz.center();
                 let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
                 vger.fill_rect(
@@ -107,7 +120,7 @@ where
                     paint,
                 );
                 let paint = vger.color_paint(thumb_color);
-                vger.fill_circle([c.x, y], SLIDER_THUMB_RADIUS, paint);
+                vger.fill_circle([c.x, y].into(), SLIDER_THUMB_RADIUS, paint);
             })
             .geom(move |sz| {
                 if sz.height != h {
@@ -0,0 +1,153 @@
+use crate::*;
+use std::path::Path;
+use tiny_skia::Pixmap;
+
+/// Copies a pixmap's premultiplied pixels into a frame.
+fn pixmap_frame(pixmap: &Pixmap) -> Frame {
+    let mut pixels = Vec::with_capacity(4 * pixmap.pixels().len());
+    for p in pixmap.pixels() {
+        let c = p.demultiply();
+        pixels.extend_from_slice(&[c.red(), c.green(), c.blue(), c.alpha()]);
+    }
+    Frame::new(pixmap.width(), pixmap.height(), pixels)
+}
+
+/// A renderer for drawing `size` points at `scale` pixels per point.
+fn offscreen_renderer(size: LocalSize, scale: f32) -> SoftwareRenderer {
+    SoftwareRenderer::new(
+        (size.width * scale).ceil() as u32,
+        (size.height * scale).ceil() as u32,
+        scale,
+    )
+}
+
+impl Frame {
+    /// Encodes the frame as a PNG.
+    pub fn to_png(&self) -> Option<Vec<u8>> {
+        frame_pixmap(self)?.encode_png().ok()
+    }
+
+    /// Writes the frame to a PNG file.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let png = self
+            .to_png()
+            .ok_or_else(|| std::io::Error::other("couldn't encode PNG"))?;
+        std::fs::write(path, png)
+    }
+}
+
+/// Lays out and draws `view` offscreen at `size`, with `scale` pixels per
+/// point, on a transparent background. The view gets fresh state, so this
+/// suits views which don't depend on what the user's done, like icons and
+/// design assets. Use `snapshot` to capture a view on screen.
+pub fn render_to_image(view: &impl View, size: LocalSize, scale: f32) -> Frame {
+    let mut cx = Context::headless();
+    let mut renderer = offscreen_renderer(size, scale);
+    cx.layout_root(view, size, &mut renderer);
+    view.draw(cx.root_id, &mut cx, &mut renderer);
+    pixmap_frame(renderer.pixmap())
+}
+
+/// Struct for the `snapshot` modifier.
+pub struct Snapshot<V, B, F> {
+    child: V,
+    request: B,
+    scale: f32,
+    func: F,
+}
+
+impl<V, B, F> Snapshot<V, B, F>
+where
+    V: View,
+    B: Binding<bool>,
+    F: Fn(Frame) + 'static,
+{
+    pub fn new(child: V, request: B, scale: f32, func: F) -> Self {
+        Self {
+            child,
+            request,
+            scale,
+            func,
+        }
+    }
+}
+
+impl<V, B, F> View for Snapshot<V, B, F>
+where
+    V: View,
+    B: Binding<bool>,
+    F: Fn(Frame) + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".snapshot()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger);
+
+        if self.request.get() {
+            self.request.set(false);
+            // Draw again offscreen, in the same state and layout.
+            let size = cx.layout.entry(id).or_default().rect.size;
+            let mut renderer = offscreen_renderer(size, self.scale);
+            self.child.draw(id.child(&0), cx, &mut renderer);
+            (self.func)(pixmap_frame(renderer.pixmap()));
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.child.layout(id.child(&0), constraints, cx, vger);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, B, F> private::Sealed for Snapshot<V, B, F> {}
@@ -0,0 +1,1236 @@
+//! Draws on the CPU with tiny-skia, for machines without a suitable GPU
+//! adapter, like VMs, CI and remote desktops.
+
+use crate::*;
+use fontdue::layout::{CoordinateSystem, GlyphPosition, Layout, LayoutSettings, TextStyle};
+use fontdue::{Font, FontSettings};
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use softbuffer::GraphicsContext;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tiny_skia::{
+    ClipMask, FillRule, FilterQuality, GradientStop, LineCap, LinearGradient, PathBuilder, Pattern,
+    Pixmap, PixmapPaint, PremultipliedColorU8, SpreadMode, Stroke, Transform,
+};
+
+/// Fonts to try for text, in order. Set RUI_FONT to the path of a .ttf
+/// file to use that instead. vger draws with Roboto, so text measures a
+/// little differently with these.
+const FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+
+/// Fonts for characters the main font doesn't have, like other scripts
+/// and emoji. All of these which are installed are used, in order.
+const FALLBACK_FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/noto/NotoColorEmoji.ttf",
+    "/usr/share/fonts/noto/NotoColorEmoji.ttf",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Apple Color Emoji.ttc",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\seguiemj.ttf",
+    "C:\\Windows\\Fonts\\seguisym.ttf",
+];
+
+fn load_font_data(data: Vec<u8>, index: u32) -> Option<(Font, FallbackFace)> {
+    let settings = FontSettings {
+        collection_index: index,
+        ..FontSettings::default()
+    };
+    let font = Font::from_bytes(data.as_slice(), settings).ok()?;
+    let colors = ColorLayers::parse(&data);
+    Some((
+        font,
+        FallbackFace {
+            data,
+            index,
+            colors,
+        },
+    ))
+}
+
+fn load_font(path: &str) -> Option<(Font, FallbackFace)> {
+    load_font_data(std::fs::read(path).ok()?, 0)
+}
+
+/// Loads the main font and any fallback fonts.
+fn load_fonts() -> (Vec<Font>, Vec<FallbackFace>) {
+    let env_path = std::env::var("RUI_FONT").ok();
+    let main = env_path
+        .iter()
+        .map(|p| p.as_str())
+        .chain(FONT_PATHS.iter().copied())
+        .find_map(load_font);
+    main.into_iter()
+        .chain(
+            FALLBACK_FONT_PATHS
+                .iter()
+                .filter_map(|path| load_font(path)),
+        )
+        .unzip()
+}
+
+/// What's needed to shape text with a font and draw its color glyphs.
+struct FallbackFace {
+    data: Vec<u8>,
+
+    /// Index of the font in a collection.
+    index: u32,
+    colors: Option<ColorLayers>,
+}
+
+/// A CBDT or sbix bitmap glyph, decoded.
+struct BitmapGlyph {
+    pixmap: Pixmap,
+    /// Offset from the glyph origin to the bottom left, in bitmap pixels.
+    x: f32,
+    y: f32,
+    pixels_per_em: f32,
+}
+
+fn decode_bitmap_glyph(data: &[u8], glyph: u16, px: f32) -> Option<BitmapGlyph> {
+    let face = ttf_parser::Face::from_slice(data, 0).ok()?;
+    let image = face.glyph_raster_image(ttf_parser::GlyphId(glyph), px.round() as u16)?;
+    if image.format != ttf_parser::RasterImageFormat::PNG {
+        return None;
+    }
+    Some(BitmapGlyph {
+        pixmap: Pixmap::decode_png(image.data).ok()?,
+        x: image.x as f32,
+        y: image.y as f32,
+        pixels_per_em: image.pixels_per_em as f32,
+    })
+}
+
+/// A `frame_view` frame converted for tiny-skia, reused while the view
+/// keeps drawing the same frame.
+struct CachedFrame {
+    frame: Arc<Frame>,
+    pixmap: Pixmap,
+    drawn: bool,
+}
+
+pub(crate) fn frame_pixmap(frame: &Frame) -> Option<Pixmap> {
+    let mut pixmap = Pixmap::new(frame.width, frame.height)?;
+    for (dst, src) in pixmap
+        .pixels_mut()
+        .iter_mut()
+        .zip(frame.pixels.chunks_exact(4))
+    {
+        let a = src[3] as u32;
+        let premultiply = |c: u8| ((c as u32 * a + 127) / 255) as u8;
+        *dst = PremultipliedColorU8::from_rgba(
+            premultiply(src[0]),
+            premultiply(src[1]),
+            premultiply(src[2]),
+            src[3],
+        )?;
+    }
+    Some(pixmap)
+}
+
+fn skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba(
+        color.r.clamp(0.0, 1.0),
+        color.g.clamp(0.0, 1.0),
+        color.b.clamp(0.0, 1.0),
+        color.a.clamp(0.0, 1.0),
+    )
+    .unwrap_or(tiny_skia::Color::BLACK)
+}
+
+fn skia_paint(paint: Paint) -> tiny_skia::Paint<'static> {
+    let mut skia = tiny_skia::Paint {
+        anti_alias: true,
+        ..Default::default()
+    };
+    match paint {
+        Paint::Color(color) => skia.set_color(skia_color(color)),
+        Paint::Gradient {
+            start,
+            end,
+            inner_color,
+            outer_color,
+        } => {
+            let stops = vec![
+                GradientStop::new(0.0, skia_color(inner_color)),
+                GradientStop::new(1.0, skia_color(outer_color)),
+            ];
+            match LinearGradient::new(
+                tiny_skia::Point::from_xy(start.x, start.y),
+                tiny_skia::Point::from_xy(end.x, end.y),
+                stops,
+                SpreadMode::Pad,
+                Transform::identity(),
+            ) {
+                Some(shader) => skia.shader = shader,
+                None => skia.set_color(skia_color(inner_color)),
+            }
+        }
+    }
+    skia
+}
+
+fn rounded_rect_path(rect: LocalRect, radius: f32) -> Option<tiny_skia::Path> {
+    let r = radius
+        .min(rect.width() / 2.0)
+        .min(rect.height() / 2.0)
+        .max(0.0);
+    let (x0, y0, x1, y1) = (rect.min_x(), rect.min_y(), rect.max_x(), rect.max_y());
+    let mut pb = PathBuilder::new();
+    pb.move_to(x0 + r, y0);
+    pb.line_to(x1 - r, y0);
+    pb.quad_to(x1, y0, x1, y0 + r);
+    pb.line_to(x1, y1 - r);
+    pb.quad_to(x1, y1, x1 - r, y1);
+    pb.line_to(x0 + r, y1);
+    pb.quad_to(x0, y1, x0, y1 - r);
+    pb.line_to(x0, y0 + r);
+    pb.quad_to(x0, y0, x0 + r, y0);
+    pb.close();
+    pb.finish()
+}
+
+fn skia_blend_mode(mode: BlendMode) -> tiny_skia::BlendMode {
+    match mode {
+        BlendMode::Normal => tiny_skia::BlendMode::SourceOver,
+        BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+        BlendMode::Screen => tiny_skia::BlendMode::Screen,
+        BlendMode::Darken => tiny_skia::BlendMode::Darken,
+        BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+        BlendMode::Plus => tiny_skia::BlendMode::Plus,
+    }
+}
+
+/// Blurs `pixmap` with a gaussian of standard deviation `sigma` pixels,
+/// across then down.
+fn blur_pixmap(pixmap: &mut Pixmap, sigma: f32) {
+    let radius = (sigma * 3.0).ceil() as i32;
+    if radius < 1 {
+        return;
+    }
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = kernel.iter().sum();
+
+    let (width, height) = (pixmap.width() as i32, pixmap.height() as i32);
+    let mut channels: Vec<[f32; 4]> = pixmap
+        .pixels()
+        .iter()
+        .map(|p| [p.red(), p.green(), p.blue(), p.alpha()].map(|c| c as f32))
+        .collect();
+    for (dx, dy) in [(1, 0), (0, 1)] {
+        let source = channels.clone();
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0.0; 4];
+                for (k, weight) in (-radius..=radius).zip(&kernel) {
+                    let sx = (x + k * dx).clamp(0, width - 1);
+                    let sy = (y + k * dy).clamp(0, height - 1);
+                    let pixel = source[(sy * width + sx) as usize];
+                    for c in 0..4 {
+                        sum[c] += pixel[c] * weight;
+                    }
+                }
+                channels[(y * width + x) as usize] = sum.map(|c| c / total);
+            }
+        }
+    }
+
+    for (dst, src) in pixmap.pixels_mut().iter_mut().zip(channels) {
+        let a = src[3].round().clamp(0.0, 255.0) as u8;
+        let c = |v: f32| v.round().clamp(0.0, a as f32) as u8;
+        if let Some(color) = PremultipliedColorU8::from_rgba(c(src[0]), c(src[1]), c(src[2]), a) {
+            *dst = color;
+        }
+    }
+}
+
+/// Mixes each pixel of `pixmap` with its gray by `saturation`: 0 for the
+/// gray, 1 for the pixel, more to move further from the gray.
+fn saturate_pixmap(pixmap: &mut Pixmap, saturation: f32) {
+    for pixel in pixmap.pixels_mut() {
+        let (r, g, b, a) = (
+            pixel.red() as f32,
+            pixel.green() as f32,
+            pixel.blue() as f32,
+            pixel.alpha(),
+        );
+        let gray = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let c = |v: f32| {
+            (gray + (v - gray) * saturation)
+                .round()
+                .clamp(0.0, a as f32) as u8
+        };
+        if let Some(color) = PremultipliedColorU8::from_rgba(c(r), c(g), c(b), a) {
+            *pixel = color;
+        }
+    }
+}
+
+/// Points along an arc centered on `rotation`, spanning `aperture` radians
+/// either side, like vger's arcs.
+/// Draws a pixmap kept for `begin_cache` onto `target` at `bounds`, in
+/// the target's coordinates.
+fn composite_cached(
+    target: &mut Pixmap,
+    pixmap: &Pixmap,
+    bounds: LocalRect,
+    scale: f32,
+    opacity: f32,
+    mask: Option<&ClipMask>,
+) {
+    let paint = PixmapPaint {
+        opacity,
+        blend_mode: tiny_skia::BlendMode::SourceOver,
+        quality: FilterQuality::Nearest,
+    };
+    let x = (bounds.min_x() * scale).round() as i32;
+    let y = target.height() as i32 - (bounds.max_y() * scale).round() as i32;
+    target.draw_pixmap(x, y, pixmap.as_ref(), &paint, Transform::identity(), mask);
+}
+
+fn arc_points(center: LocalPoint, radius: f32, rotation: f32, aperture: f32) -> Vec<LocalPoint> {
+    let steps = ((aperture * radius).abs().ceil() as usize).clamp(4, 256);
+    (0..=steps)
+        .map(|i| {
+            let theta = rotation - aperture + 2.0 * aperture * i as f32 / steps as f32;
+            center + LocalOffset::new(theta.cos(), theta.sin()) * radius
+        })
+        .collect()
+}
+
+/// Renders into a tiny-skia pixmap on the CPU. `rui` draws with this when
+/// there's no suitable GPU adapter.
+pub struct SoftwareRenderer {
+    pixmap: Pixmap,
+    scale: f32,
+
+    offset: LocalOffset,
+    zoom: f32,
+    clip: Option<LocalRect>,
+    opacity: f32,
+    saved: Vec<(LocalOffset, f32, Option<LocalRect>, f32)>,
+
+    /// `clip` rasterized, for tiny-skia.
+    mask: Option<ClipMask>,
+
+    /// The path being built for `fill`.
+    path: PathBuilder,
+
+    /// The main font, then fallbacks, then fonts selected with `set_font`.
+    fonts: Vec<Font>,
+    faces: Vec<FallbackFace>,
+    fallback_count: usize,
+    layout: Layout,
+
+    /// Index in `fonts` of the selected font, which comes before the
+    /// fallbacks.
+    selected: Option<usize>,
+    loaded: HashMap<fonts::Font, Option<usize>>,
+
+    /// Decoded bitmap glyphs, by font, glyph and size.
+    bitmaps: HashMap<(usize, u16, u32), Option<BitmapGlyph>>,
+    shapes: ShapeCache,
+
+    /// Frames drawn by `frame_view`s, by view.
+    frames: HashMap<ViewID, CachedFrame>,
+
+    /// For each layer begun, the pixmap it's composited onto, its effects
+    /// and the opacity it's composited with.
+    layers: Vec<(Pixmap, LayerEffects, f32)>,
+
+    /// Pixmaps kept for `begin_cache`, by view, and whether they've been
+    /// drawn this frame.
+    cached: HashMap<ViewID, (Pixmap, bool)>,
+
+    /// For each `begin_cache` not yet ended, its view, the pixmap it's
+    /// drawn onto and where, and the saves, transform, clip and opacity to
+    /// go back to.
+    caches: Vec<CacheDraw>,
+}
+
+/// See `SoftwareRenderer::caches`.
+struct CacheDraw {
+    key: ViewID,
+    below: Pixmap,
+    bounds: LocalRect,
+    saved: Vec<(LocalOffset, f32, Option<LocalRect>, f32)>,
+    state: (LocalOffset, f32, Option<LocalRect>, f32),
+}
+
+impl SoftwareRenderer {
+    /// Creates a renderer drawing into a pixmap of `width` by `height`
+    /// physical pixels.
+    pub fn new(width: u32, height: u32, scale: f32) -> Self {
+        let (fonts, faces) = load_fonts();
+        if fonts.is_empty() {
+            println!(
+                "rui couldn't find a font, so text won't be drawn. Set RUI_FONT to a .ttf file."
+            );
+        }
+        Self {
+            pixmap: Pixmap::new(width.max(1), height.max(1)).unwrap(),
+            scale,
+            offset: LocalOffset::zero(),
+            zoom: 1.0,
+            clip: None,
+            opacity: 1.0,
+            saved: vec![],
+            mask: None,
+            path: PathBuilder::new(),
+            fallback_count: fonts.len(),
+            fonts,
+            faces,
+            layout: Layout::new(CoordinateSystem::PositiveYUp),
+            selected: None,
+            loaded: HashMap::new(),
+            bitmaps: HashMap::new(),
+            shapes: ShapeCache::default(),
+            frames: HashMap::new(),
+            layers: vec![],
+            cached: HashMap::new(),
+            caches: vec![],
+        }
+    }
+
+    /// Clears the pixmap and resizes it, ready for a new frame.
+    pub fn begin(&mut self, width: u32, height: u32, scale: f32) {
+        let (width, height) = (width.max(1), height.max(1));
+        if self.pixmap.width() != width || self.pixmap.height() != height {
+            self.pixmap = Pixmap::new(width, height).unwrap();
+        }
+        self.pixmap.fill(tiny_skia::Color::BLACK);
+        self.scale = scale;
+        self.offset = LocalOffset::zero();
+        self.zoom = 1.0;
+        self.clip = None;
+        self.opacity = 1.0;
+        self.saved.clear();
+        self.mask = None;
+        self.path = PathBuilder::new();
+        if let Some((pixmap, _, _)) = self.layers.drain(..).next() {
+            self.pixmap = pixmap;
+        }
+        if let Some(cache) = self.caches.drain(..).next() {
+            self.pixmap = cache.below;
+        }
+
+        // Forget the drawings of views which stopped drawing.
+        self.cached.retain(|_, (_, drawn)| *drawn);
+        for (_, drawn) in self.cached.values_mut() {
+            *drawn = false;
+        }
+
+        // Forget frames of views which stopped drawing.
+        self.frames.retain(|_, cached| cached.drawn);
+        for cached in self.frames.values_mut() {
+            cached.drawn = false;
+        }
+    }
+
+    /// The pixels drawn so far.
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    /// Maps the current coordinate space, which is y-up in layout units,
+    /// to the pixmap's y-down pixels.
+    fn transform(&self) -> Transform {
+        let s = self.scale;
+        Transform::from_row(
+            s * self.zoom,
+            0.0,
+            0.0,
+            -s * self.zoom,
+            s * self.offset.x,
+            self.pixmap.height() as f32 - s * self.offset.y,
+        )
+    }
+
+    /// Pixels per layout unit for text, which is laid out at the size it's
+    /// drawn.
+    fn text_scale(&self) -> f32 {
+        self.scale * self.zoom
+    }
+
+    /// Maps a rect in the current coordinates to unscaled, y-up window
+    /// coordinates.
+    fn to_window(&self, rect: LocalRect) -> LocalRect {
+        rect.scale(self.zoom, self.zoom).translate(self.offset)
+    }
+
+    fn update_mask(&mut self) {
+        self.mask = self.clip.and_then(|clip| {
+            let s = self.scale;
+            let height = self.pixmap.height() as f32;
+            let rect = tiny_skia::Rect::from_xywh(
+                clip.min_x() * s,
+                height - clip.max_y() * s,
+                clip.width() * s,
+                clip.height() * s,
+            )?;
+            let mut mask = ClipMask::new();
+            mask.set_path(
+                self.pixmap.width(),
+                self.pixmap.height(),
+                &PathBuilder::from_rect(rect),
+                FillRule::Winding,
+                false,
+            )?;
+            Some(mask)
+        });
+    }
+
+    /// Whether the clip leaves nothing to draw into.
+    fn clipped_out(&self) -> bool {
+        matches!(self.clip, Some(clip) if clip.is_empty())
+    }
+
+    fn fill_path(&mut self, path: Option<tiny_skia::Path>, paint: Paint) {
+        if self.clipped_out() {
+            return;
+        }
+        if let Some(path) = path {
+            let transform = self.transform();
+            self.pixmap.fill_path(
+                &path,
+                &skia_paint(paint.with_opacity(self.opacity)),
+                FillRule::Winding,
+                transform,
+                self.mask.as_ref(),
+            );
+        }
+    }
+
+    fn stroke_path(&mut self, path: Option<tiny_skia::Path>, width: f32, paint: Paint) {
+        if self.clipped_out() {
+            return;
+        }
+        if let Some(path) = path {
+            let stroke = Stroke {
+                width,
+                line_cap: LineCap::Round,
+                ..Default::default()
+            };
+            let transform = self.transform();
+            self.pixmap.stroke_path(
+                &path,
+                &skia_paint(paint.with_opacity(self.opacity)),
+                &stroke,
+                transform,
+                self.mask.as_ref(),
+            );
+        }
+    }
+
+    /// Lays out text in physical pixels, as vger does.
+    /// Each character is drawn with the first font which has it.
+    fn layout_text(&mut self, text: &str, size: u32, max_width: Option<f32>) -> bool {
+        let chain: Vec<usize> = self
+            .selected
+            .into_iter()
+            .chain(0..self.fallback_count)
+            .collect();
+        if chain.is_empty() {
+            return false;
+        }
+        self.layout.reset(&LayoutSettings {
+            max_width: max_width.map(|w| w * self.text_scale()),
+            ..LayoutSettings::default()
+        });
+        let fonts = &self.fonts;
+        let runs = font_runs(text, |c| {
+            chain
+                .iter()
+                .copied()
+                .find(|i| fonts[*i].lookup_glyph_index(c) != 0)
+                .unwrap_or(chain[0])
+        });
+        let px = size as f32 * self.text_scale();
+        for (font, range) in runs {
+            self.layout
+                .append(fonts, &TextStyle::new(&text[range], px, font));
+        }
+        true
+    }
+
+    fn glyph_rects(&self) -> Vec<LocalRect> {
+        self.layout
+            .glyphs()
+            .iter()
+            .map(|glyph| {
+                let s = self.text_scale();
+                // Bitmap glyphs like emoji have no outline to measure, so
+                // take them to be a square em.
+                let (width, height) = if glyph.width == 0 && !glyph.parent.is_whitespace() {
+                    (glyph.key.px, glyph.key.px)
+                } else {
+                    (glyph.width as f32, glyph.height as f32)
+                };
+                LocalRect::new(
+                    [glyph.x / s, glyph.y / s].into(),
+                    [width / s, height / s].into(),
+                )
+            })
+            .collect()
+    }
+
+    /// Draws a color bitmap glyph, like an emoji, with its origin at `(x, y)`
+    /// in pixels. Does nothing if the font has no bitmap for the glyph.
+    fn draw_bitmap_glyph(&mut self, font: usize, glyph: u16, px: f32, x: f32, y: f32) {
+        let faces = &self.faces;
+        let bitmap = self
+            .bitmaps
+            .entry((font, glyph, px.to_bits()))
+            .or_insert_with(|| decode_bitmap_glyph(&faces[font].data, glyph, px));
+        if let Some(bitmap) = bitmap {
+            let s = px / bitmap.pixels_per_em;
+            let left = x + bitmap.x * s;
+            let top = y - (bitmap.y + bitmap.pixmap.height() as f32) * s;
+            let paint = PixmapPaint {
+                opacity: self.opacity,
+                quality: FilterQuality::Bilinear,
+                ..Default::default()
+            };
+            self.pixmap.draw_pixmap(
+                0,
+                0,
+                bitmap.pixmap.as_ref(),
+                &paint,
+                Transform::from_row(s, 0.0, 0.0, s, left, top),
+                self.mask.as_ref(),
+            );
+        }
+    }
+
+    /// Draws glyphs the layout put in one font, shaped together so letters
+    /// join and kern, starting where the layout put the first.
+    fn draw_run(&mut self, run: &[GlyphPosition], origin_x: f32, origin_y: f32, color: Color) {
+        let first = run[0];
+        let font = first.font_index;
+        let px = first.key.px;
+        let metrics = self.fonts[font].metrics_indexed(first.key.glyph_index, px);
+        let baseline_y = origin_y - (first.y - metrics.ymin as f32);
+        let mut pen = origin_x + first.x - metrics.xmin as f32;
+
+        let text: String = run.iter().map(|glyph| glyph.parent).collect();
+        let face = &self.faces[font];
+        let shaped = self.shapes.get_or_shape(font, px, &text, || {
+            shape_run(&face.data, face.index, px, &text)
+        });
+
+        if shaped.is_empty() {
+            // The font couldn't be shaped, so draw what the layout has.
+            for glyph in run {
+                let xmin = self.fonts[font]
+                    .metrics_indexed(glyph.key.glyph_index, px)
+                    .xmin;
+                let pen = origin_x + glyph.x - xmin as f32;
+                let blank = glyph.parent.is_whitespace();
+                self.draw_glyph(
+                    font,
+                    glyph.key.glyph_index,
+                    px,
+                    (pen, baseline_y),
+                    blank,
+                    color,
+                );
+            }
+            return;
+        }
+
+        for glyph in shaped.iter() {
+            let blank = text[glyph.cluster..]
+                .chars()
+                .next()
+                .filter(|c| !c.is_whitespace())
+                .is_none();
+            self.draw_glyph(
+                font,
+                glyph.glyph,
+                px,
+                (pen + glyph.x_offset, baseline_y - glyph.y_offset),
+                blank,
+                color,
+            );
+            pen += glyph.x_advance;
+        }
+    }
+
+    /// Draws a glyph with its origin at `(x, baseline_y)` in pixels.
+    fn draw_glyph(
+        &mut self,
+        font: usize,
+        index: u16,
+        px: f32,
+        (x, baseline_y): (f32, f32),
+        blank: bool,
+        color: Color,
+    ) {
+        // Color glyphs are layers of outlines, each with a color.
+        let layers = self.faces[font]
+            .colors
+            .as_ref()
+            .and_then(|colors| colors.layers(index));
+        let layers = layers.unwrap_or_else(|| vec![(index, None)]);
+
+        let mut drawn = false;
+        for (layer, layer_color) in layers {
+            let (metrics, coverage) = self.fonts[font].rasterize_indexed(layer, px);
+            if metrics.width == 0 {
+                continue;
+            }
+            drawn = true;
+            let left = (x + metrics.xmin as f32).round() as i32;
+            let top = baseline_y - (metrics.ymin as f32 + metrics.height as f32);
+            let color = match layer_color {
+                Some(c) => Color::new(c.r, c.g, c.b, c.a * color.a),
+                None => color,
+            };
+            self.blend_glyph(left, top.round() as i32, metrics.width, &coverage, color);
+        }
+
+        if !drawn && !blank {
+            self.draw_bitmap_glyph(font, index, px, x, baseline_y);
+        }
+    }
+
+    /// Blends a glyph's coverage into the pixmap with its top left at
+    /// `(x, y)` in pixels.
+    fn blend_glyph(&mut self, x: i32, y: i32, width: usize, coverage: &[u8], color: Color) {
+        let s = self.scale;
+        let pixmap_height = self.pixmap.height() as f32;
+        let clip = self.clip.map(|clip| {
+            (
+                clip.min_x() * s,
+                pixmap_height - clip.max_y() * s,
+                clip.max_x() * s,
+                pixmap_height - clip.min_y() * s,
+            )
+        });
+        let (pw, ph) = (self.pixmap.width() as i32, self.pixmap.height() as i32);
+        let pixels = self.pixmap.pixels_mut();
+        for (i, &c) in coverage.iter().enumerate() {
+            let px = x + (i % width) as i32;
+            let py = y + (i / width) as i32;
+            if c == 0 || px < 0 || py < 0 || px >= pw || py >= ph {
+                continue;
+            }
+            if let Some((x0, y0, x1, y1)) = clip {
+                let (fx, fy) = (px as f32 + 0.5, py as f32 + 0.5);
+                if fx < x0 || fx > x1 || fy < y0 || fy > y1 {
+                    continue;
+                }
+            }
+            let a = color.a.clamp(0.0, 1.0) * c as f32 / 255.0;
+            let dst = &mut pixels[(py * pw + px) as usize];
+            let mix = |src: f32, dst: u8| (src * a * 255.0 + dst as f32 * (1.0 - a)) as u8;
+            if let Some(blended) = PremultipliedColorU8::from_rgba(
+                mix(color.r.clamp(0.0, 1.0), dst.red()),
+                mix(color.g.clamp(0.0, 1.0), dst.green()),
+                mix(color.b.clamp(0.0, 1.0), dst.blue()),
+                mix(1.0, dst.alpha()),
+            ) {
+                *dst = blended;
+            }
+        }
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn save(&mut self) {
+        self.saved
+            .push((self.offset, self.zoom, self.clip, self.opacity));
+    }
+
+    fn restore(&mut self) {
+        if let Some((offset, zoom, clip, opacity)) = self.saved.pop() {
+            self.offset = offset;
+            self.zoom = zoom;
+            self.opacity = opacity;
+            if clip != self.clip {
+                self.clip = clip;
+                self.update_mask();
+            }
+        }
+    }
+
+    fn save_depth(&self) -> usize {
+        self.saved.len()
+    }
+
+    fn translate(&mut self, offset: LocalOffset) {
+        self.offset += offset * self.zoom;
+    }
+
+    fn scale(&mut self, scale: f32) {
+        self.zoom *= scale;
+    }
+
+    fn clip(&mut self, rect: LocalRect) {
+        let rect = self.to_window(rect);
+        self.clip = Some(match self.clip {
+            Some(clip) => clip.intersection(&rect).unwrap_or_default(),
+            None => rect,
+        });
+        self.update_mask();
+    }
+
+    fn opacity(&mut self, opacity: f32) {
+        self.opacity *= opacity;
+    }
+
+    fn begin_layer(&mut self, effects: LayerEffects) {
+        let layer = Pixmap::new(self.pixmap.width(), self.pixmap.height()).unwrap();
+        let below = std::mem::replace(&mut self.pixmap, layer);
+        let effects = LayerEffects {
+            blur: effects.blur * self.zoom,
+            ..effects
+        };
+        self.layers.push((below, effects, self.opacity));
+        self.opacity = 1.0;
+    }
+
+    fn end_layer(&mut self) {
+        if let Some((below, effects, opacity)) = self.layers.pop() {
+            let mut layer = std::mem::replace(&mut self.pixmap, below);
+            if effects.blur > 0.0 {
+                blur_pixmap(&mut layer, effects.blur_sigma() * self.scale);
+            }
+            if effects.saturation != 1.0 {
+                saturate_pixmap(&mut layer, effects.saturation);
+            }
+            let paint = PixmapPaint {
+                opacity,
+                blend_mode: skia_blend_mode(effects.blend_mode),
+                quality: FilterQuality::Nearest,
+            };
+            self.pixmap.draw_pixmap(
+                0,
+                0,
+                layer.as_ref(),
+                &paint,
+                Transform::identity(),
+                self.mask.as_ref(),
+            );
+        }
+    }
+
+    fn draw_cached(&mut self, key: ViewID, rect: LocalRect) -> bool {
+        let (bounds, size) = pixel_bounds(self.to_window(rect), self.scale);
+        match self.cached.get_mut(&key) {
+            Some((pixmap, drawn))
+                if pixmap.width() == size.width && pixmap.height() == size.height =>
+            {
+                *drawn = true;
+                composite_cached(
+                    &mut self.pixmap,
+                    pixmap,
+                    bounds,
+                    self.scale,
+                    self.opacity,
+                    self.mask.as_ref(),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn begin_cache(&mut self, key: ViewID, rect: LocalRect) {
+        let (bounds, size) = pixel_bounds(self.to_window(rect), self.scale);
+        let pixmap = Pixmap::new(size.width, size.height).unwrap();
+        let below = std::mem::replace(&mut self.pixmap, pixmap);
+        self.caches.push(CacheDraw {
+            key,
+            below,
+            bounds,
+            saved: std::mem::take(&mut self.saved),
+            state: (self.offset, self.zoom, self.clip, self.opacity),
+        });
+
+        // Draw relative to the pixmap.
+        self.offset -= bounds.origin.to_vector();
+        self.clip = None;
+        self.opacity = 1.0;
+        self.update_mask();
+    }
+
+    fn end_cache(&mut self) {
+        if let Some(cache) = self.caches.pop() {
+            let pixmap = std::mem::replace(&mut self.pixmap, cache.below);
+            let (offset, zoom, clip, opacity) = cache.state;
+            self.saved = cache.saved;
+            self.offset = offset;
+            self.zoom = zoom;
+            self.clip = clip;
+            self.opacity = opacity;
+            self.update_mask();
+
+            composite_cached(
+                &mut self.pixmap,
+                &pixmap,
+                cache.bounds,
+                self.scale,
+                self.opacity,
+                self.mask.as_ref(),
+            );
+            self.cached.insert(cache.key, (pixmap, true));
+        }
+    }
+
+    fn fill_rect(&mut self, rect: LocalRect, radius: f32, paint: Paint) {
+        self.fill_path(rounded_rect_path(rect, radius), paint);
+    }
+
+    fn stroke_rect(
+        &mut self,
+        min: LocalPoint,
+        max: LocalPoint,
+        radius: f32,
+        width: f32,
+        paint: Paint,
+    ) {
+        let rect = LocalRect::new(min, (max - min).to_size());
+        self.stroke_path(rounded_rect_path(rect, radius), width, paint);
+    }
+
+    fn fill_circle(&mut self, center: LocalPoint, radius: f32, paint: Paint) {
+        self.fill_path(PathBuilder::from_circle(center.x, center.y, radius), paint);
+    }
+
+    fn stroke_segment(&mut self, a: LocalPoint, b: LocalPoint, width: f32, paint: Paint) {
+        let mut pb = PathBuilder::new();
+        pb.move_to(a.x, a.y);
+        pb.line_to(b.x, b.y);
+        self.stroke_path(pb.finish(), width, paint);
+    }
+
+    fn stroke_arc(
+        &mut self,
+        center: LocalPoint,
+        radius: f32,
+        width: f32,
+        rotation: f32,
+        aperture: f32,
+        paint: Paint,
+    ) {
+        let points = arc_points(center, radius, rotation, aperture);
+        let mut pb = PathBuilder::new();
+        pb.move_to(points[0].x, points[0].y);
+        for pt in &points[1..] {
+            pb.line_to(pt.x, pt.y);
+        }
+        self.stroke_path(pb.finish(), width, paint);
+    }
+
+    fn stroke_bezier(
+        &mut self,
+        a: LocalPoint,
+        b: LocalPoint,
+        c: LocalPoint,
+        width: f32,
+        paint: Paint,
+    ) {
+        let mut pb = PathBuilder::new();
+        pb.move_to(a.x, a.y);
+        pb.quad_to(b.x, b.y, c.x, c.y);
+        self.stroke_path(pb.finish(), width, paint);
+    }
+
+    fn move_to(&mut self, p: LocalPoint) {
+        self.path.move_to(p.x, p.y);
+    }
+
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint) {
+        self.path.quad_to(b.x, b.y, c.x, c.y);
+    }
+
+    fn fill(&mut self, paint: Paint) {
+        let path = std::mem::replace(&mut self.path, PathBuilder::new());
+        self.fill_path(path.finish(), paint);
+    }
+
+    fn draw_frame(&mut self, key: ViewID, frame: &Arc<Frame>, rect: LocalRect, radius: f32) {
+        if self.clipped_out() || rect.is_empty() {
+            return;
+        }
+        let cached = self.frames.get(&key);
+        if cached.filter(|c| Arc::ptr_eq(&c.frame, frame)).is_none() {
+            let pixmap = match frame_pixmap(frame) {
+                Some(pixmap) => pixmap,
+                None => return,
+            };
+            self.frames.insert(
+                key,
+                CachedFrame {
+                    frame: frame.clone(),
+                    pixmap,
+                    drawn: false,
+                },
+            );
+        }
+        let rect = self.to_window(rect);
+        let cached = self.frames.get_mut(&key).unwrap();
+        cached.drawn = true;
+
+        // Frame rows go top to bottom, like the pixmap's.
+        let s = self.scale;
+        let transform = Transform::from_row(
+            s * rect.width() / frame.width as f32,
+            0.0,
+            0.0,
+            s * rect.height() / frame.height as f32,
+            s * rect.min_x(),
+            self.pixmap.height() as f32 - s * rect.max_y(),
+        );
+
+        if radius > 0.0 {
+            // Fill the rounded rect with the frame as a pattern.
+            let height = self.pixmap.height() as f32;
+            let path = rounded_rect_path(rect, radius * self.zoom)
+                .and_then(|path| path.transform(Transform::from_row(s, 0.0, 0.0, -s, 0.0, height)));
+            if let Some(path) = path {
+                let paint = tiny_skia::Paint {
+                    shader: Pattern::new(
+                        cached.pixmap.as_ref(),
+                        SpreadMode::Pad,
+                        FilterQuality::Bilinear,
+                        1.0,
+                        transform,
+                    ),
+                    anti_alias: true,
+                    ..Default::default()
+                };
+                self.pixmap.fill_path(
+                    &path,
+                    &paint,
+                    FillRule::Winding,
+                    Transform::identity(),
+                    self.mask.as_ref(),
+                );
+            }
+            return;
+        }
+
+        let paint = PixmapPaint {
+            opacity: self.opacity,
+            quality: FilterQuality::Bilinear,
+            ..Default::default()
+        };
+        self.pixmap.draw_pixmap(
+            0,
+            0,
+            cached.pixmap.as_ref(),
+            &paint,
+            transform,
+            self.mask.as_ref(),
+        );
+    }
+
+    fn set_font(&mut self, font: Option<&fonts::Font>) {
+        self.selected = font.and_then(|font| {
+            if let Some(index) = self.loaded.get(font) {
+                return *index;
+            }
+            let loaded =
+                fonts::with_font_data(font, |data, index| load_font_data(data.to_vec(), index))
+                    .flatten();
+            let index = loaded.map(|(font, face)| {
+                self.fonts.push(font);
+                self.faces.push(face);
+                self.fonts.len() - 1
+            });
+            self.loaded.insert(font.clone(), index);
+            index
+        });
+    }
+
+    fn text(&mut self, text: &str, size: u32, color: Color, max_width: Option<f32>) {
+        if self.clipped_out() || !self.layout_text(text, size, max_width) {
+            return;
+        }
+        let color = color.alpha(color.a * self.opacity);
+        let origin_x = self.offset.x * self.scale;
+        let origin_y = self.pixmap.height() as f32 - self.offset.y * self.scale;
+        let glyphs = self.layout.glyphs().clone();
+        let lines: Vec<_> = match self.layout.lines() {
+            Some(lines) => lines
+                .iter()
+                .map(|line| line.glyph_start..line.glyph_end.min(glyphs.len()))
+                .collect(),
+            None => std::iter::once(0..glyphs.len()).collect(),
+        };
+        for line in lines {
+            let line = &glyphs[line];
+            let mut start = 0;
+            while start < line.len() {
+                let font = line[start].font_index;
+                let end = line[start..]
+                    .iter()
+                    .position(|glyph| glyph.font_index != font)
+                    .map_or(line.len(), |n| start + n);
+                self.draw_run(&line[start..end], origin_x, origin_y, color);
+                start = end;
+            }
+        }
+    }
+
+    fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        if !self.layout_text(text, size, max_width) {
+            return LocalRect::zero();
+        }
+        self.glyph_rects()
+            .iter()
+            .fold(None, |bounds: Option<LocalRect>, rect| {
+                Some(bounds.map_or(*rect, |b| b.union(rect)))
+            })
+            .unwrap_or_default()
+    }
+
+    fn glyph_positions(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LocalRect> {
+        if !self.layout_text(text, size, max_width) {
+            return vec![];
+        }
+        self.glyph_rects()
+    }
+
+    fn line_metrics(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LineMetrics> {
+        if !self.layout_text(text, size, max_width) {
+            return vec![];
+        }
+        let rects = self.glyph_rects();
+        let lines = match self.layout.lines() {
+            Some(lines) => lines.clone(),
+            None => return vec![],
+        };
+        lines
+            .iter()
+            .map(|line| {
+                let bounds = rects[line.glyph_start..line.glyph_end.min(rects.len())]
+                    .iter()
+                    .fold(None, |bounds: Option<LocalRect>, rect| {
+                        Some(bounds.map_or(*rect, |b| b.union(rect)))
+                    })
+                    .unwrap_or_default();
+                LineMetrics {
+                    glyph_start: line.glyph_start,
+                    glyph_end: line.glyph_end,
+                    bounds,
+                }
+            })
+            .collect()
+    }
+
+    fn baselines(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<f32> {
+        if !self.layout_text(text, size, max_width) {
+            return vec![];
+        }
+        let s = self.text_scale();
+        match self.layout.lines() {
+            Some(lines) => lines.iter().map(|line| line.baseline_y / s).collect(),
+            None => vec![],
+        }
+    }
+}
+
+/// A window's raw handle, so the graphics context doesn't borrow the
+/// window, which the `Context` owns.
+struct WindowHandle(RawWindowHandle);
+
+unsafe impl HasRawWindowHandle for WindowHandle {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.0
+    }
+}
+
+fn graphics_context(window: &Window) -> GraphicsContext<WindowHandle> {
+    unsafe { GraphicsContext::new(WindowHandle(window.raw_window_handle())) }
+        .unwrap_or_else(|_| panic!("Unable to draw to the window in software!"))
+}
+
+/// Draws on the CPU and copies frames to the window with softbuffer.
+pub(crate) struct SoftwareBackend {
+    context: GraphicsContext<WindowHandle>,
+    renderer: SoftwareRenderer,
+    size: PhysicalSize<u32>,
+    buffer: Vec<u32>,
+}
+
+impl SoftwareBackend {
+    pub(crate) fn new(window: &Window) -> Self {
+        let size = window.inner_size();
+        Self {
+            context: graphics_context(window),
+            renderer: SoftwareRenderer::new(size.width, size.height, window.scale_factor() as f32),
+            size,
+            buffer: vec![],
+        }
+    }
+}
+
+impl Backend for SoftwareBackend {
+    fn renderer(&mut self) -> &mut dyn Renderer {
+        &mut self.renderer
+    }
+
+    fn resize(&mut self, size: PhysicalSize<u32>) {
+        self.size = size;
+    }
+
+    fn resume(&mut self, window: &Window) {
+        // The window's native handle may have changed while in the background.
+        self.context = graphics_context(window);
+    }
+
+    fn frame(
+        &mut self,
+        _width: f32,
+        _height: f32,
+        scale: f32,
+        draw: &mut dyn FnMut(&mut dyn Renderer),
+    ) {
+        let width = self.size.width.clamp(1, u16::MAX as u32);
+        let height = self.size.height.clamp(1, u16::MAX as u32);
+        self.renderer.begin(width, height, scale);
+        draw(&mut self.renderer);
+
+        // softbuffer wants 0RGB pixels. The background is opaque, so the
+        // premultiplied colors are already the final ones.
+        self.buffer.clear();
+        self.buffer.extend(
+            self.renderer
+                .pixmap()
+                .pixels()
+                .iter()
+                .map(|p| (p.red() as u32) << 16 | (p.green() as u32) << 8 | p.blue() as u32),
+        );
+        self.context
+            .set_buffer(&self.buffer, width as u16, height as u16);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arc_points() {
+        let pts = arc_points(LocalPoint::zero(), 10.0, 0.0, std::f32::consts::FRAC_PI_2);
+        assert!((pts[0] - LocalPoint::new(0.0, -10.0)).length() < 1e-4);
+        assert!((pts[pts.len() - 1] - LocalPoint::new(0.0, 10.0)).length() < 1e-4);
+        assert!(pts
+            .iter()
+            .all(|pt| (pt.to_vector().length() - 10.0).abs() < 1e-4));
+    }
+}
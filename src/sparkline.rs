@@ -0,0 +1,152 @@
+use crate::*;
+
+pub const SPARKLINE_SIZE: LocalSize = LocalSize::new(80.0, 20.0);
+
+/// Struct for `sparkline`.
+pub struct Sparkline<B> {
+    values: B,
+    size: LocalSize,
+    color: Color,
+    line_width: f32,
+    range: Option<(f32, f32)>,
+    last_point: bool,
+}
+
+impl<B> Sparkline<B> {
+    pub fn sparkline_size(self, size: impl Into<LocalSize>) -> Self {
+        Self {
+            size: size.into(),
+            ..self
+        }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    pub fn line_width(self, line_width: f32) -> Self {
+        Self { line_width, ..self }
+    }
+
+    /// Fixes the values at the bottom and top, instead of fitting the
+    /// line to the values.
+    pub fn range(self, range: std::ops::Range<f32>) -> Self {
+        Self {
+            range: Some((range.start, range.end)),
+            ..self
+        }
+    }
+
+    /// Marks the latest value with a dot. On by default.
+    pub fn last_point(self, last_point: bool) -> Self {
+        Self { last_point, ..self }
+    }
+}
+
+impl<B> View for Sparkline<B>
+where
+    B: Binding<Vec<f32>>,
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("sparkline({} values)", self.values.with(|v| v.len()));
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let paint = vger.color_paint(self.color);
+        let inset = self.line_width.max(if self.last_point { 2.0 } else { 0.0 });
+        let area = rect.inflate(-inset, -inset);
+
+        // Read the values in place, since they may be updated often.
+        self.values.with(|values| {
+            let (lo, hi) = self.range.unwrap_or_else(|| {
+                values
+                    .iter()
+                    .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(*v), hi.max(*v)))
+            });
+            let span = if hi > lo { hi - lo } else { 1.0 };
+            let n = values.len();
+            let point = |i: usize| -> LocalPoint {
+                let x = if n > 1 {
+                    i as f32 / (n - 1) as f32
+                } else {
+                    1.0
+                };
+                let y = ((values[i] - lo) / span).clamp(0.0, 1.0);
+                [
+                    area.min_x() + x * area.width(),
+                    area.min_y() + y * area.height(),
+                ]
+                .into()
+            };
+
+            for i in 1..n {
+                vger.stroke_segment(point(i - 1), point(i), self.line_width, paint);
+            }
+            if self.last_point && n > 0 {
+                vger.fill_circle(point(n - 1), self.line_width + 1.0, paint);
+            }
+        });
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), self.size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        self.size
+    }
+
+    fn hittest(
+        &self,
+        _id: ViewID,
+        _pt: LocalPoint,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        None
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Image));
+        Some(aid)
+    }
+}
+
+impl<B> private::Sealed for Sparkline<B> {}
+
+/// A small line graph of `values`, without axes, for dashboards and list
+/// rows. It's `SPARKLINE_SIZE` unless changed with `sparkline_size`.
+pub fn sparkline<B: Binding<Vec<f32>>>(values: B) -> Sparkline<B> {
+    Sparkline {
+        values,
+        size: SPARKLINE_SIZE,
+        color: AZURE_HIGHLIGHT,
+        line_width: 1.5,
+        range: None,
+        last_point: true,
+    }
+}
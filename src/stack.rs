@@ -1,11 +1,16 @@
 use crate::*;
+use std::collections::HashMap;
 
 /// Allows rui to iterate over a tuple of `Views`.
 pub trait ViewTuple {
     fn foreach_view<F: FnMut(&dyn View)>(&self, f: &mut F);
+
+    /// Like `foreach_view`, from the last view to the first.
+    fn foreach_view_rev<F: FnMut(&dyn View)>(&self, f: &mut F);
     fn len(&self) -> usize;
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum StackOrientation {
     Horizontal,
     Vertical,
@@ -15,6 +20,12 @@ pub enum StackOrientation {
 struct Stack<VT> {
     orientation: StackOrientation,
     children: VT,
+
+    /// How the children of a vertical stack line up across it.
+    halign: HAlignment,
+
+    /// How the children of a horizontal stack line up.
+    valign: VAlignment,
 }
 
 impl<VT: ViewTuple> View for Stack<VT> {
@@ -28,9 +39,13 @@ impl<VT: ViewTuple> View for Stack<VT> {
         println!("}}");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
-        let mut c = 0;
-        self.children.foreach_view(&mut |child| {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let (indices, levels) = self.z_levels(id, cx);
+        let mut process_child = |c: i32, child: &dyn View| {
+            // Once a child has handled the event, the rest don't see it.
+            if cx.event_consumed() {
+                return;
+            }
             let child_id = id.child(&c);
             let offset = cx
                 .layout
@@ -42,172 +57,476 @@ impl<VT: ViewTuple> View for Stack<VT> {
             local_event.position -= offset;
 
             (*child).process(&local_event, child_id, cx, vger);
-            c += 1;
+        };
+
+        // Views on top get the first look: those with the highest
+        // `z_index`, then, in a z-stack, the later ones.
+        for z in levels.into_iter().rev() {
+            if self.orientation == StackOrientation::Z {
+                let mut c = self.children.len() as i32;
+                self.children.foreach_view_rev(&mut |child| {
+                    c -= 1;
+                    if indices[c as usize] == z {
+                        process_child(c, child);
+                    }
+                })
+            } else {
+                let mut c = 0;
+                self.children.foreach_view(&mut |child| {
+                    if indices[c as usize] == z {
+                        process_child(c, child);
+                    }
+                    c += 1;
+                })
+            }
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        cx.with_env(self.orientation, |cx| self.draw_children(id, cx, vger))
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        cx.with_env(self.orientation, |cx| {
+            self.layout_children(id, constraints, cx, vger)
         })
     }
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        // The last hit, drawn on top, wins.
+        let (indices, levels) = self.z_levels(id, cx);
+        let mut hit = None;
+        for z in levels {
+            let mut c = 0;
+            self.children.foreach_view(&mut |child| {
+                let child_id = id.child(&c);
+                let offset = cx.layout.entry(child_id).or_default().offset;
+
+                if indices[c as usize] == z {
+                    if let Some(h) = child.hittest(child_id, pt - offset, cx, vger) {
+                        hit = Some(h)
+                    }
+                }
+
+                c += 1;
+            });
+        }
+        hit
+    }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
         let mut c = 0;
         self.children.foreach_view(&mut |child| {
-            let child_id = id.child(&c);
-            let layout_box = cx
-                .layout
-                .entry(child_id)
-                .or_insert(LayoutBox::default())
-                .clone();
-
-            vger.save();
+            child.commands(id.child(&c), cx, cmds);
+            c += 1;
+        });
+    }
 
-            vger.translate(layout_box.offset);
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let mut c = 0;
+        self.children.foreach_view(&mut |child| {
+            child.gc(id.child(&c), cx, map);
+            c += 1;
+        });
+    }
 
-            (*child).draw(child_id, cx, vger);
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let mut c = 0;
+        let mut node = accesskit::Node::new(id.access_id(), accesskit::Role::List);
+        self.children.foreach_view(&mut |child| {
+            if let Some(id) = child.access(id.child(&c), cx, nodes) {
+                node.children.push(id)
+            }
             c += 1;
+        });
+        nodes.push(node);
+        Some(id.access_id())
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        let sizes = cx.with_env(self.orientation, |cx| {
+            self.child_intrinsic_sizes(id, cx, vger)
+        });
+        if sizes.iter().all(Option::is_none) {
+            return None;
+        }
+        Some(self.total_size(&sizes))
+    }
+}
+
+impl<VT: ViewTuple> Stack<VT> {
+    pub fn new(orientation: StackOrientation, children: VT) -> Self {
+        Self {
+            orientation,
+            children,
+            halign: HAlignment::Center,
+            valign: VAlignment::Middle,
+        }
+    }
+
+    /// Lays out a child in up to `sz`. Guides left in `cx.guides` after
+    /// are the child's.
+    fn layout_child(
+        child: &dyn View,
+        child_id: ViewID,
+        sz: LocalSize,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        cx.guides.clear();
+        cx.z_indices.remove(&child_id);
+        child.layout(child_id, sz.into(), cx, vger)
+    }
+
+    fn draw_children(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let (indices, levels) = self.z_levels(id, cx);
+        for z in levels {
+            let mut c = 0;
+            self.children.foreach_view(&mut |child| {
+                if indices[c as usize] == z {
+                    Self::draw_child(child, id.child(&c), cx, vger);
+                }
+                c += 1;
+            })
+        }
+    }
+
+    fn draw_child(child: &dyn View, child_id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let layout_box = *cx.layout.entry(child_id).or_default();
+
+        vger.save();
+
+        vger.translate(layout_box.offset);
+
+        child.draw(child_id, cx, vger);
+
+        if DEBUG_LAYOUT || cx.inspector.enabled {
+            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            vger.stroke_rect(
+                layout_box.rect.min(),
+                layout_box.rect.max(),
+                0.0,
+                1.0,
+                paint,
+            );
+        }
+
+        vger.restore();
+    }
+
+    /// Returns each child's `z_index`, and the different indices from the
+    /// lowest up. Children with the same index keep their order.
+    fn z_levels(&self, id: ViewID, cx: &Context) -> (Vec<i32>, Vec<i32>) {
+        let indices: Vec<i32> = (0..self.children.len() as i32)
+            .map(|c| cx.z_indices.get(&id.child(&c)).copied().unwrap_or(0))
+            .collect();
+        let mut levels = indices.clone();
+        levels.sort_unstable();
+        levels.dedup();
+        (indices, levels)
+    }
 
-            if DEBUG_LAYOUT {
-                let paint = vger.color_paint(CONTROL_BACKGROUND);
-                vger.stroke_rect(
-                    layout_box.rect.min(),
-                    layout_box.rect.max(),
-                    0.0,
-                    1.0,
-                    paint,
-                );
+    /// Splits the length of the stack between the children. It's split
+    /// evenly unless they have different layout priorities. Then each
+    /// group of children, from the highest priority down, is offered what
+    /// remains less the least the lower priority children shrink to, and
+    /// any space left over is shared out.
+    fn distribute(
+        &self,
+        id: ViewID,
+        sz: LocalSize,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Vec<f32> {
+        let horizontal = self.orientation == StackOrientation::Horizontal;
+        let length = if horizontal { sz.width } else { sz.height };
+        let main = |size: LocalSize| if horizontal { size.width } else { size.height };
+        let propose = |length: f32| {
+            if horizontal {
+                LocalSize::new(length, sz.height)
+            } else {
+                LocalSize::new(sz.width, length)
             }
+        };
+
+        let mut priorities = cx.pool.floats();
+        self.children
+            .foreach_view(&mut |child| priorities.push(child.priority()));
+        let n = priorities.len();
+        let mut sizes = cx.pool.floats();
+        if priorities.iter().all(|p| *p == priorities[0]) {
+            sizes.resize(n, length / n as f32);
+            cx.pool.recycle_floats(priorities);
+            return sizes;
+        }
 
-            vger.restore();
-        })
+        let mut minimums = cx.pool.floats();
+        let mut c = 0;
+        self.children.foreach_view(&mut |child| {
+            minimums.push(main(child.layout(
+                id.child(&c),
+                propose(0.0).into(),
+                cx,
+                vger,
+            )));
+            c += 1;
+        });
+
+        // Stable, so children with the same priority stay in order.
+        let mut order = cx.pool.indices();
+        order.extend(0..n);
+        order.sort_by(|a, b| priorities[*b].total_cmp(&priorities[*a]));
+
+        sizes.resize(n, 0.0);
+        let mut remaining = length;
+        let mut start = 0;
+        while start < n {
+            let priority = priorities[order[start]];
+            let end = start
+                + order[start..]
+                    .iter()
+                    .take_while(|i| priorities[**i] == priority)
+                    .count();
+            let group = &order[start..end];
+            let reserved: f32 = order[end..].iter().map(|i| minimums[*i]).sum();
+            let offered = ((remaining - reserved) / group.len() as f32).max(0.0);
+
+            let mut c = 0;
+            self.children.foreach_view(&mut |child| {
+                let index = c as usize;
+                if group.contains(&index) {
+                    sizes[index] =
+                        main(child.layout(id.child(&c), propose(offered).into(), cx, vger));
+                    remaining -= sizes[index];
+                }
+                c += 1;
+            });
+            start = end;
+        }
+
+        let extra = remaining.max(0.0) / n as f32;
+        for size in &mut sizes {
+            *size += extra;
+        }
+        cx.pool.recycle_floats(priorities);
+        cx.pool.recycle_floats(minimums);
+        cx.pool.recycle_indices(order);
+        sizes
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        let n = self.children.len() as f32;
+    fn child_intrinsic_sizes(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Vec<Option<LocalSize>> {
+        let mut sizes = vec![];
+        let mut c = 0;
+        self.children.foreach_view(&mut |child| {
+            sizes.push(child.intrinsic_size(id.child(&c), cx, vger));
+            c += 1;
+        });
+        sizes
+    }
 
+    /// The size of the children laid out along the stack at their
+    /// intrinsic sizes, with zero for those without one.
+    fn total_size(&self, sizes: &[Option<LocalSize>]) -> LocalSize {
+        let sizes = sizes.iter().map(|size| size.unwrap_or(LocalSize::zero()));
+        let max = |a: f32, b: f32| a.max(b);
         match self.orientation {
+            StackOrientation::Horizontal => LocalSize::new(
+                sizes.clone().map(|size| size.width).sum(),
+                sizes.map(|size| size.height).fold(0.0, max),
+            ),
+            StackOrientation::Vertical => LocalSize::new(
+                sizes.clone().map(|size| size.width).fold(0.0, max),
+                sizes.map(|size| size.height).sum(),
+            ),
+            StackOrientation::Z => LocalSize::new(
+                sizes.clone().map(|size| size.width).fold(0.0, max),
+                sizes.map(|size| size.height).fold(0.0, max),
+            ),
+        }
+    }
+
+    fn layout_children(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        // Unbounded space, like in a scroll view, is replaced by what the
+        // children need at their intrinsic sizes.
+        let unbounded_width = !constraints.has_bounded_width();
+        let unbounded_height = !constraints.has_bounded_height();
+        let intrinsic = if unbounded_width || unbounded_height {
+            self.child_intrinsic_sizes(id, cx, vger)
+        } else {
+            vec![]
+        };
+        let total = self.total_size(&intrinsic);
+        let sz = constraints.constrain(LocalSize::new(
+            if unbounded_width {
+                total.width
+            } else {
+                constraints.max.width
+            },
+            if unbounded_height {
+                total.height
+            } else {
+                constraints.max.height
+            },
+        ));
+        let unbounded_length = |length: fn(LocalSize) -> f32| {
+            intrinsic
+                .iter()
+                .map(|size| size.map(length).unwrap_or(0.0))
+                .collect::<Vec<f32>>()
+        };
+
+        let alignment = match self.orientation {
+            StackOrientation::Horizontal => Alignment::Vertical(self.valign),
+            _ => Alignment::Horizontal(self.halign),
+        };
+        let line = ViewDimensions::from(sz).default_guide(alignment);
+        let mut guides = cx.pool.guides();
+
+        let size = match self.orientation {
             StackOrientation::Horizontal => {
-                let proposed_child_size = LocalSize::new(sz.width / n, sz.height);
+                let slots = if unbounded_width {
+                    unbounded_length(|size| size.width)
+                } else {
+                    self.distribute(id, sz, cx, vger)
+                };
 
                 let mut c = 0;
                 let mut x = 0.0;
+                let mut above: f32 = 0.0;
+                let mut below: f32 = 0.0;
                 self.children.foreach_view(&mut |child| {
                     let child_id = id.child(&c);
+                    let proposed_child_size = LocalSize::new(slots[c as usize], sz.height);
                     let child_rect = LocalRect::new([x, 0.0].into(), proposed_child_size);
-                    let child_size = child.layout(child_id, proposed_child_size, cx, vger);
-
-                    cx.layout.entry(child_id).or_default().offset = align_h(
-                        LocalRect::new(LocalPoint::origin(), child_size),
-                        child_rect,
-                        HAlignment::Center,
-                    );
+                    let child_size =
+                        Self::layout_child(child, child_id, proposed_child_size, cx, vger);
+                    let child_line = guide(cx, alignment, child_size);
+                    above = above.max(child_size.height - child_line);
+                    below = below.max(child_line);
+
+                    let layout_box = cx.layout.entry(child_id).or_default();
+                    layout_box.rect = LocalRect::new(LocalPoint::origin(), child_size);
+                    layout_box.offset = align_h(layout_box.rect, child_rect, HAlignment::Center);
+                    layout_box.offset.y = line - child_line;
+                    Self::collect_guides(&mut guides, layout_box.offset, cx);
 
                     x += proposed_child_size.width;
                     c += 1;
                 });
+                cx.pool.recycle_floats(slots);
+
+                // The stack has no baseline of its own, so the children
+                // lined up on theirs are centered in it.
+                if matches!(
+                    self.valign,
+                    VAlignment::FirstTextBaseline | VAlignment::LastTextBaseline
+                ) {
+                    let shift =
+                        LocalOffset::new(0.0, (sz.height - above - below) / 2.0 + below - line);
+                    for c in 0..self.children.len() as i32 {
+                        cx.layout.entry(id.child(&c)).or_default().offset += shift;
+                    }
+                    offset_guides(&mut guides, shift);
+                }
 
                 sz
             }
             StackOrientation::Vertical => {
-                let proposed_child_size = LocalSize::new(sz.width, sz.height / n);
+                let slots = if unbounded_height {
+                    unbounded_length(|size| size.height)
+                } else {
+                    self.distribute(id, sz, cx, vger)
+                };
 
                 let mut c = 0;
                 let mut y = sz.height;
                 self.children.foreach_view(&mut |child| {
                     let child_id = id.child(&c);
+                    let proposed_child_size = LocalSize::new(sz.width, slots[c as usize]);
                     let child_rect = LocalRect::new(
                         [0.0, y - proposed_child_size.height].into(),
                         proposed_child_size,
                     );
-                    let child_size = child.layout(child_id, proposed_child_size, cx, vger);
+                    let child_size =
+                        Self::layout_child(child, child_id, proposed_child_size, cx, vger);
+                    let child_line = guide(cx, alignment, child_size);
 
                     y -= proposed_child_size.height;
-                    cx.layout.entry(child_id).or_default().offset = align_v(
-                        LocalRect::new(LocalPoint::origin(), child_size),
-                        child_rect,
-                        VAlignment::Middle,
-                    );
+                    let layout_box = cx.layout.entry(child_id).or_default();
+                    layout_box.rect = LocalRect::new(LocalPoint::origin(), child_size);
+                    layout_box.offset = align_v(layout_box.rect, child_rect, VAlignment::Middle);
+                    layout_box.offset.x = line - child_line;
+                    Self::collect_guides(&mut guides, layout_box.offset, cx);
 
                     c += 1;
                 });
+                cx.pool.recycle_floats(slots);
 
                 sz
             }
             StackOrientation::Z => {
                 let mut c = 0;
                 self.children.foreach_view(&mut |child| {
-                    child.layout(id.child(&c), sz, cx, vger);
+                    Self::layout_child(child, id.child(&c), sz, cx, vger);
+                    Self::collect_guides(&mut guides, LocalOffset::zero(), cx);
                     c += 1;
                 });
                 sz
             }
-        }
-    }
+        };
 
-    fn hittest(
-        &self,
-        id: ViewID,
-        pt: LocalPoint,
-        cx: &mut Context,
-        vger: &mut VGER,
-    ) -> Option<ViewID> {
-        let mut c = 0;
-        let mut hit = None;
-        self.children.foreach_view(&mut |child| {
-            let child_id = id.child(&c);
-            let offset = cx
-                .layout
-                .entry(child_id)
-                .or_insert(LayoutBox::default())
-                .offset;
-
-            if let Some(h) = child.hittest(child_id, pt - offset, cx, vger) {
-                hit = Some(h)
-            }
-
-            c += 1;
-        });
-        hit
-    }
-
-    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
-        let mut c = 0;
-        self.children.foreach_view(&mut |child| {
-            child.commands(id.child(&c), cx, cmds);
-            c += 1;
-        });
+        let child_guides = std::mem::replace(&mut cx.guides, guides);
+        cx.pool.recycle_guides(child_guides);
+        size
     }
 
-    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
-        let mut c = 0;
-        self.children.foreach_view(&mut |child| {
-            child.gc(id.child(&c), cx, map);
-            c += 1;
-        });
-    }
-
-    fn access(
-        &self,
-        id: ViewID,
-        cx: &mut Context,
-        nodes: &mut Vec<accesskit::Node>,
-    ) -> Option<accesskit::NodeId> {
-        let mut c = 0;
-        let mut node = accesskit::Node::new(id.access_id(), accesskit::Role::List);
-        self.children.foreach_view(&mut |child| {
-            match child.access(id.child(&c), cx, nodes) {
-                Some(id) => node.children.push(id),
-                None => (),
-            };
-            c += 1;
-        });
-        nodes.push(node);
-        Some(id.access_id())
-    }
-}
-
-impl<VT: ViewTuple> Stack<VT> {
-    pub fn new(orientation: StackOrientation, children: VT) -> Self {
-        Self {
-            orientation,
-            children,
+    /// Passes on the guides a child reported, moved to where it is. The
+    /// first child to report a guide sets it for the stack, except for the
+    /// last text baseline, which the last child sets.
+    fn collect_guides(guides: &mut HashMap<Alignment, f32>, offset: LocalOffset, cx: &mut Context) {
+        let empty = cx.pool.guides();
+        let mut child_guides = std::mem::replace(&mut cx.guides, empty);
+        offset_guides(&mut child_guides, offset);
+        for (alignment, value) in child_guides.drain() {
+            if alignment == Alignment::Vertical(VAlignment::LastTextBaseline) {
+                guides.insert(alignment, value);
+            } else {
+                guides.entry(alignment).or_insert(value);
+            }
         }
+        cx.pool.recycle_guides(child_guides);
     }
 }
 
@@ -217,6 +536,9 @@ impl<A: View> ViewTuple for (A,) {
     fn foreach_view<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
         f(&self.0);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         1
     }
@@ -227,6 +549,10 @@ impl<A: View, B: View> ViewTuple for (A, B) {
         f(&self.0);
         f(&self.1);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.1);
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         2
     }
@@ -238,6 +564,11 @@ impl<A: View, B: View, C: View> ViewTuple for (A, B, C) {
         f(&self.1);
         f(&self.2);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.2);
+        f(&self.1);
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         3
     }
@@ -250,6 +581,12 @@ impl<A: View, B: View, C: View, D: View> ViewTuple for (A, B, C, D) {
         f(&self.2);
         f(&self.3);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.3);
+        f(&self.2);
+        f(&self.1);
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         4
     }
@@ -263,6 +600,13 @@ impl<A: View, B: View, C: View, D: View, E: View> ViewTuple for (A, B, C, D, E)
         f(&self.3);
         f(&self.4);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.4);
+        f(&self.3);
+        f(&self.2);
+        f(&self.1);
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         5
     }
@@ -277,6 +621,14 @@ impl<A: View, B: View, C: View, D: View, E: View, F: View> ViewTuple for (A, B,
         f(&self.4);
         f(&self.5);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.5);
+        f(&self.4);
+        f(&self.3);
+        f(&self.2);
+        f(&self.1);
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         6
     }
@@ -294,6 +646,15 @@ impl<A: View, B: View, C: View, D: View, E: View, F: View, G: View> ViewTuple
         f(&self.5);
         f(&self.6);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.6);
+        f(&self.5);
+        f(&self.4);
+        f(&self.3);
+        f(&self.2);
+        f(&self.1);
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         7
     }
@@ -312,11 +673,37 @@ impl<A: View, B: View, C: View, D: View, E: View, F: View, G: View, H: View> Vie
         f(&self.6);
         f(&self.7);
     }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        f(&self.7);
+        f(&self.6);
+        f(&self.5);
+        f(&self.4);
+        f(&self.3);
+        f(&self.2);
+        f(&self.1);
+        f(&self.0);
+    }
     fn len(&self) -> usize {
         8
     }
 }
 
+impl<V: View> ViewTuple for Vec<V> {
+    fn foreach_view<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        for child in self {
+            f(child);
+        }
+    }
+    fn foreach_view_rev<FN: FnMut(&dyn View)>(&self, f: &mut FN) {
+        for child in self.iter().rev() {
+            f(child);
+        }
+    }
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
 /// Horizontal stack of up to 8 Views in a tuple. Each item can be a different view type.
 pub fn hstack<VT: ViewTuple>(children: VT) -> impl View {
     Stack::new(StackOrientation::Horizontal, children)
@@ -327,7 +714,53 @@ pub fn vstack<VT: ViewTuple>(children: VT) -> impl View {
     Stack::new(StackOrientation::Vertical, children)
 }
 
+/// Horizontal stack whose children line up on `alignment`, or on the
+/// guides they set for it with `alignment_guide`.
+pub fn hstack_aligned<VT: ViewTuple>(alignment: VAlignment, children: VT) -> impl View {
+    let mut stack = Stack::new(StackOrientation::Horizontal, children);
+    stack.valign = alignment;
+    stack
+}
+
+/// Vertical stack whose children line up on `alignment`, or on the
+/// guides they set for it with `alignment_guide`.
+pub fn vstack_aligned<VT: ViewTuple>(alignment: HAlignment, children: VT) -> impl View {
+    let mut stack = Stack::new(StackOrientation::Vertical, children);
+    stack.halign = alignment;
+    stack
+}
+
 /// Stack of up to 8 overlaid Views in a tuple. Each item can be a different view type.
 pub fn zstack<VT: ViewTuple>(children: VT) -> impl View {
     Stack::new(StackOrientation::Z, children)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unbounded_stack() {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([100.0, 100.0].into());
+        let view = hstack((
+            rectangle().size([30.0, 10.0]).padding(5.0),
+            rectangle(),
+            rectangle().size([20.0, 30.0]),
+        ));
+        assert_eq!(
+            view.intrinsic_size(cx.root_id, &mut cx, &mut vger),
+            Some(LocalSize::new(60.0, 30.0))
+        );
+
+        let sz = view.layout(
+            cx.root_id,
+            [f32::INFINITY, 100.0].into(),
+            &mut cx,
+            &mut vger,
+        );
+        assert_eq!(sz, LocalSize::new(60.0, 100.0));
+        assert_eq!(cx.layout[&cx.root_id.child(&1)].rect.width(), 0.0);
+        assert_eq!(cx.layout[&cx.root_id.child(&2)].offset.x, 40.0);
+    }
+}
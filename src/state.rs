@@ -1,29 +1,399 @@
+use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use futures::Stream;
 use std::any::Any;
-use std::sync::{Arc, Mutex};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context as TaskContext, Poll};
 
 use crate::*;
 
+// With the `sync` feature, readers on different threads don't block each
+// other. An `RwLock` is only `Sync` when what it holds is, so then
+// `State<S>` is `Send + Sync` when `S: Send + Sync`, rather than just
+// `S: Send`.
+#[cfg(not(feature = "sync"))]
+type Lock<T> = Mutex<T>;
+#[cfg(feature = "sync")]
+type Lock<T> = std::sync::RwLock<T>;
+
 struct Holder<S> {
     value: S,
 
-    /// Has the state changed since the last redraw?
-    dirty: Arc<Mutex<Dirty>>,
+    /// Has the state changed since the last redraw? `None` for states made
+    /// outside the view tree, which redraw the running app instead.
+    dirty: Option<Arc<Mutex<Dirty>>>,
+
+    /// How many times the state has changed, for `record_reads`.
+    version: Arc<AtomicU64>,
+
+    /// Called with the new value when it changes. See `State::on_change`.
+    observers: Arc<Mutex<Observers<S>>>,
+}
+
+impl<S> Holder<S> {
+    /// Notes a change, and redraws the views.
+    fn redraw(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+        match &self.dirty {
+            Some(dirty) => {
+                dirty.lock().unwrap().dirty = true;
+                wake(dirty);
+            }
+            None => redraw_app(),
+        }
+    }
+}
+
+type Observer<S> = Arc<dyn Fn(&S) + Send + Sync>;
+
+struct Observers<S> {
+    next_id: u64,
+    observers: Vec<(u64, Observer<S>)>,
+}
+
+/// Stops calling a `State::on_change` callback when dropped.
+pub struct Subscription {
+    unsubscribe: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe()
+        }
+    }
+}
+
+/// See `State::watch`.
+struct Watch<S> {
+    receiver: UnboundedReceiver<S>,
+    _subscription: Subscription,
+}
+
+impl<S> Stream for Watch<S> {
+    type Item = S;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<S>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+/// A read of a state, noted by `record_reads`.
+#[derive(Clone)]
+pub(crate) struct StateRead {
+    version: Arc<AtomicU64>,
+    seen: u64,
+}
+
+impl StateRead {
+    /// Whether the state has changed since it was read.
+    pub(crate) fn changed(&self) -> bool {
+        self.version.load(Ordering::SeqCst) != self.seen
+    }
+}
+
+thread_local! {
+    /// The reads noted by each `record_reads` in progress, innermost last.
+    static READS: RefCell<Vec<Vec<StateRead>>> = const { RefCell::new(vec![]) };
+
+    /// The states being read or changed on this thread.
+    static ACCESSING: RefCell<Vec<usize>> = const { RefCell::new(vec![]) };
+
+    /// Changes made with `State::update` while their state was being
+    /// accessed, to make once no state is.
+    static QUEUED: RefCell<VecDeque<Box<dyn FnOnce()>>> = RefCell::new(VecDeque::new());
+}
+
+/// Marks a state as being accessed on this thread while alive.
+struct Access {
+    key: usize,
+}
+
+impl Access {
+    /// Panics if the state is already being accessed on this thread, which
+    /// would otherwise deadlock.
+    fn begin(key: usize) -> Self {
+        ACCESSING.with(|accessing| {
+            let mut accessing = accessing.borrow_mut();
+            if accessing.contains(&key) {
+                drop(accessing);
+                panic!(
+                    "state accessed while it's already being read or changed; \
+                     use `State::update` to change a state from within its own \
+                     `with` or `with_mut`"
+                );
+            }
+            accessing.push(key);
+        });
+        Self { key }
+    }
+}
+
+impl Drop for Access {
+    fn drop(&mut self) {
+        let done = ACCESSING.with(|accessing| {
+            let mut accessing = accessing.borrow_mut();
+            accessing.retain(|key| *key != self.key);
+            accessing.is_empty()
+        });
+        if done && !std::thread::panicking() {
+            // Changes made by the queued ones are queued again if they
+            // update a state they're changing.
+            while let Some(queued) = QUEUED.with(|queued| queued.borrow_mut().pop_front()) {
+                queued();
+            }
+        }
+    }
+}
+
+/// Calls `f`, returning the states it read on this thread.
+pub(crate) fn record_reads<T>(f: impl FnOnce() -> T) -> (T, Vec<StateRead>) {
+    READS.with(|reads| reads.borrow_mut().push(vec![]));
+    let t = f();
+    let mut reads = READS.with(|reads| reads.borrow_mut().pop().unwrap_or_default());
+    reads.sort_by_key(|read| Arc::as_ptr(&read.version));
+    reads.dedup_by_key(|read| Arc::as_ptr(&read.version));
+    (t, reads)
+}
+
+/// Notes `reads` for the `record_reads` in progress, as if they were made
+/// again.
+pub(crate) fn note_reads(reads: &[StateRead]) {
+    READS.with(|recording| {
+        for recorded in recording.borrow_mut().iter_mut() {
+            recorded.extend_from_slice(reads);
+        }
+    })
+}
+
+/// Notes a read of something which can't tell when it changes, so what
+/// the `record_reads` in progress made is always made again.
+pub(crate) fn note_untracked_read() {
+    READS.with(|recording| {
+        let mut recording = recording.borrow_mut();
+        if !recording.is_empty() {
+            let read = StateRead {
+                version: Arc::new(AtomicU64::new(1)),
+                seen: 0,
+            };
+            for recorded in recording.iter_mut() {
+                recorded.push(read.clone());
+            }
+        }
+    })
+}
+
+/// Notes a read of something counting its changes in `version`, for the
+/// `record_reads` in progress.
+pub(crate) fn note_read(version: &Arc<AtomicU64>) {
+    READS.with(|recording| {
+        let mut recording = recording.borrow_mut();
+        if !recording.is_empty() {
+            let read = StateRead {
+                version: version.clone(),
+                seen: version.load(Ordering::SeqCst),
+            };
+            for recorded in recording.iter_mut() {
+                recorded.push(read.clone());
+            }
+        }
+    })
 }
 
 /// Contains application state. Application state is created using `state`.
+///
+/// `State<S>` is `Send + Sync` whenever `S: Send`, so it can be moved into
+/// threads and `Send` futures.
+///
+/// The value is only reachable within `with` and `with_mut`, so no borrow
+/// of it outlives the access. Accessing a state again from within one of
+/// these on the same thread panics rather than deadlocking; use `update`
+/// to change it from there.
 #[derive(Clone)]
 pub struct State<S> {
-    value: Arc<Mutex<Holder<S>>>,
+    value: Arc<Lock<Holder<S>>>,
 }
 
 impl<S> State<S> {
     pub fn new(value: S, dirty: Arc<Mutex<Dirty>>) -> Self {
+        Self::with_dirty(value, Some(dirty))
+    }
+
+    fn with_dirty(value: S, dirty: Option<Arc<Mutex<Dirty>>>) -> Self {
         Self {
-            value: Arc::new(Mutex::new(Holder { value, dirty })),
+            value: Arc::new(Lock::new(Holder {
+                value,
+                dirty,
+                version: Arc::new(AtomicU64::new(0)),
+                observers: Arc::new(Mutex::new(Observers {
+                    next_id: 0,
+                    observers: vec![],
+                })),
+            })),
+        }
+    }
+
+    /// Calls `f` with the new value each time the state changes, until the
+    /// returned `Subscription` is dropped. For app logic outside the views,
+    /// like saving or syncing. `f` is called on the thread which made the
+    /// change.
+    pub fn on_change(&self, f: impl Fn(&S) + Send + Sync + 'static) -> Subscription
+    where
+        S: 'static,
+    {
+        let observers = self.read().observers.clone();
+        let id = {
+            let mut observers = observers.lock().unwrap();
+            let id = observers.next_id;
+            observers.next_id += 1;
+            observers.observers.push((id, Arc::new(f)));
+            id
+        };
+        let observers = Arc::downgrade(&observers);
+        Subscription {
+            unsubscribe: Some(Box::new(move || {
+                if let Some(observers) = observers.upgrade() {
+                    observers
+                        .lock()
+                        .unwrap()
+                        .observers
+                        .retain(|(i, _)| *i != id);
+                }
+            })),
+        }
+    }
+
+    /// The new values of the state, as it changes.
+    pub fn watch(&self) -> impl Stream<Item = S>
+    where
+        S: Clone + Send + 'static,
+    {
+        let (sender, receiver) = unbounded();
+        let subscription = self.on_change(move |value: &S| {
+            // The stream is dropped along with the subscription.
+            let _ = sender.unbounded_send(value.clone());
+        });
+        Watch {
+            receiver,
+            _subscription: subscription,
+        }
+    }
+
+    #[cfg(not(feature = "sync"))]
+    fn read(&self) -> std::sync::MutexGuard<'_, Holder<S>> {
+        self.value.lock().unwrap()
+    }
+
+    #[cfg(not(feature = "sync"))]
+    fn write(&self) -> std::sync::MutexGuard<'_, Holder<S>> {
+        self.value.lock().unwrap()
+    }
+
+    #[cfg(feature = "sync")]
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Holder<S>> {
+        self.value.read().unwrap()
+    }
+
+    #[cfg(feature = "sync")]
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Holder<S>> {
+        self.value.write().unwrap()
+    }
+
+    /// Sets the value.
+    pub fn set(&self, value: S)
+    where
+        S: Clone + 'static,
+    {
+        self.with_mut(move |s| *s = value)
+    }
+
+    /// A copy of the value.
+    pub fn get_cloned(&self) -> S
+    where
+        S: Clone + 'static,
+    {
+        self.with(S::clone)
+    }
+
+    /// Takes the value, leaving the default in its place.
+    pub fn take(&self) -> S
+    where
+        S: Clone + Default + 'static,
+    {
+        self.with_mut(std::mem::take)
+    }
+
+    /// Identifies the state for `Access`.
+    fn key(&self) -> usize {
+        Arc::as_ptr(&self.value) as *const () as usize
+    }
+
+    /// Changes the state with `f`. Unlike `with_mut`, it's fine to call
+    /// this while the state is being accessed on this thread, like from a
+    /// handler called within its `with`: the change is then made once
+    /// that's done.
+    pub fn update(&self, f: impl FnOnce(&mut S) + 'static)
+    where
+        S: Clone + 'static,
+    {
+        let key = self.key();
+        if ACCESSING.with(|accessing| accessing.borrow().contains(&key)) {
+            let state = self.clone();
+            QUEUED.with(|queued| {
+                queued
+                    .borrow_mut()
+                    .push_back(Box::new(move || state.with_mut(f)))
+            });
+        } else {
+            self.with_mut(f)
+        }
+    }
+
+    /// A handle which doesn't keep the state alive, for background work
+    /// which should stop mattering once the view is gone.
+    pub(crate) fn downgrade(&self) -> WeakState<S> {
+        WeakState {
+            value: Arc::downgrade(&self.value),
         }
     }
 }
 
+/// A state made outside the view tree, like app-wide data shared between
+/// views. Changing it redraws the running app.
+impl<S> From<S> for State<S> {
+    fn from(value: S) -> Self {
+        Self::with_dirty(value, None)
+    }
+}
+
+impl<S: Default> Default for State<S> {
+    fn default() -> Self {
+        S::default().into()
+    }
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for State<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let _access = Access::begin(self.key());
+        f.debug_tuple("State").field(&self.read().value).finish()
+    }
+}
+
+/// See `State::downgrade`.
+pub(crate) struct WeakState<S> {
+    value: Weak<Lock<Holder<S>>>,
+}
+
+impl<S> WeakState<S> {
+    pub(crate) fn upgrade(&self) -> Option<State<S>> {
+        self.value.upgrade().map(|value| State { value })
+    }
+}
+
 impl<S> AnyState for State<S>
 where
     S: 'static,
@@ -38,73 +408,141 @@ where
     S: Clone + 'static,
 {
     fn with<T, F: FnOnce(&S) -> T>(&self, f: F) -> T {
-        f(&self.value.lock().unwrap().value)
+        let _access = Access::begin(self.key());
+        let holder = self.read();
+        note_read(&holder.version);
+        f(&holder.value)
     }
     fn with_mut<T, F: FnOnce(&mut S) -> T>(&self, f: F) -> T {
-        let mut holder = self.value.lock().unwrap();
-        // Set dirty so the view tree will be redrawn.
-        holder.dirty.lock().unwrap().dirty = true;
-        let t = f(&mut holder.value);
-
-        // Wake up the event loop.
-        if let Some(proxy) = &holder.dirty.lock().unwrap().event_loop_proxy {
-            if let Err(err) = proxy.send_event(()) {
-                println!("error waking up event loop: {:?}", err);
+        let (t, observers, value) = {
+            let _access = Access::begin(self.key());
+            let mut holder = self.write();
+            let t = f(&mut holder.value);
+            holder.redraw();
+
+            let observers: Vec<_> = holder
+                .observers
+                .lock()
+                .unwrap()
+                .observers
+                .iter()
+                .map(|(_, f)| f.clone())
+                .collect();
+            let value = (!observers.is_empty()).then(|| holder.value.clone());
+            (t, observers, value)
+        };
+
+        // Called without the state accessed, so they can read it.
+        if let Some(value) = value {
+            for f in observers {
+                f(&value);
             }
         }
-
         t
     }
+    fn redraw(&self) {
+        self.read().redraw()
+    }
 }
 
+/// Numbers each `StateView`, so a retained body is only reused by the view
+/// which built it.
+static SERIALS: AtomicU64 = AtomicU64::new(0);
+
 struct StateView<S, F> {
     default: S,
     func: F,
+    serial: u64,
+}
+
+/// A body built by a `StateView`, and the states read while building it.
+struct Retained<V> {
+    serial: u64,
+    body: Rc<V>,
+    reads: Vec<StateRead>,
+}
+
+struct RetainedBody<V> {
+    inner: Rc<RefCell<Option<Retained<V>>>>,
+}
+
+impl<V> Clone for RetainedBody<V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<V> Default for RetainedBody<V> {
+    fn default() -> Self {
+        Self {
+            inner: Default::default(),
+        }
+    }
+}
+
+impl<S, V, F> StateView<S, F>
+where
+    V: View + 'static,
+    S: Clone + 'static,
+    F: Fn(State<S>) -> V,
+{
+    fn retained(&self, id: ViewID, cx: &mut Context) -> State<RetainedBody<V>> {
+        cx.get_state(id.child(&1), RetainedBody::default)
+    }
+
+    /// The view for the state. It's built once and kept, until one of the
+    /// states read while building it changes. The default is only cloned
+    /// the first time.
+    fn body(&self, id: ViewID, cx: &mut Context) -> Rc<V> {
+        let retained = self.retained(id, cx).get();
+        if let Some(kept) = &*retained.inner.borrow() {
+            if kept.serial == self.serial && !kept.reads.iter().any(|read| read.changed()) {
+                note_reads(&kept.reads);
+                return kept.body.clone();
+            }
+        }
+
+        let state = cx.get_state(id, || self.default.clone());
+        let (body, reads) = record_reads(|| (self.func)(state));
+        let body = Rc::new(body);
+        *retained.inner.borrow_mut() = Some(Retained {
+            serial: self.serial,
+            body: body.clone(),
+            reads,
+        });
+        body
+    }
 }
 
 impl<S, V, F> View for StateView<S, F>
 where
-    V: View,
+    V: View + 'static,
     S: Clone + 'static,
     F: Fn(State<S>) -> V,
 {
     fn print(&self, id: ViewID, cx: &mut Context) {
-        cx.with_state(self.default.clone(), id, |state: State<S>, cx| {
-            (self.func)(state.clone()).print(id.child(&0), cx);
-        });
+        self.body(id, cx).print(id.child(&0), cx);
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
-        cx.with_state_aux(
-            self.default.clone(),
-            id,
-            vger,
-            |state: State<S>, cx, vger| {
-                (self.func)(state.clone()).process(event, id.child(&0), cx, vger);
-            },
-        )
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(id, cx).process(event, id.child(&0), cx, vger)
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
-        cx.with_state_aux(
-            self.default.clone(),
-            id,
-            vger,
-            |state: State<S>, cx, vger| {
-                (self.func)(state.clone()).draw(id.child(&0), cx, vger);
-            },
-        );
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body(id, cx).draw(id.child(&0), cx, vger)
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        cx.with_state_aux(
-            self.default.clone(),
-            id,
-            vger,
-            |state: State<S>, cx, vger| {
-                (self.func)(state.clone()).layout(id.child(&0), sz, cx, vger)
-            },
-        )
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.body(id, cx)
+            .layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -112,29 +550,21 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
-        cx.with_state_aux(
-            self.default.clone(),
-            id,
-            vger,
-            |state: State<S>, cx, vger| {
-                (self.func)(state.clone()).hittest(id.child(&0), pt, cx, vger)
-            },
-        )
+        self.body(id, cx).hittest(id.child(&0), pt, cx, vger)
     }
 
     fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
-        cx.with_state_mut(self.default.clone(), id, &mut |state: State<S>, cx| {
-            (self.func)(state.clone()).commands(id.child(&0), cx, cmds);
-        });
+        self.body(id, cx).commands(id.child(&0), cx, cmds)
     }
 
     fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
-        cx.with_state_aux(self.default.clone(), id, map, |state: State<S>, cx, map| {
-            map.insert(id, Box::new(state.clone()));
-            (self.func)(state.clone()).gc(id.child(&0), cx, map);
-        });
+        let body = self.body(id, cx);
+        let state = cx.get_state(id, || self.default.clone());
+        map.insert(id, Box::new(state));
+        map.insert(id.child(&1), Box::new(self.retained(id, cx)));
+        body.gc(id.child(&0), cx, map)
     }
 
     fn access(
@@ -143,12 +573,16 @@ where
         cx: &mut Context,
         nodes: &mut Vec<accesskit::Node>,
     ) -> Option<accesskit::NodeId> {
-        cx.with_state_aux(
-            self.default.clone(),
-            id,
-            nodes,
-            |state: State<S>, cx, nodes| (self.func)(state.clone()).access(id.child(&0), cx, nodes),
-        )
+        self.body(id, cx).access(id.child(&0), cx, nodes)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.body(id, cx).intrinsic_size(id.child(&0), cx, vger)
     }
 }
 
@@ -161,6 +595,11 @@ impl<S, F> private::Sealed for StateView<S, F> {}
 /// `initial` is the initial value for your state.
 ///
 /// `f` callback which is passed a `State<S>`
+///
+/// The view `f` returns is kept, and only built again when a state read
+/// while building it changes, or when the view around it builds a new
+/// `state`. So `f` should only depend on its arguments and the states it
+/// reads.
 pub fn state<S: Clone + 'static, V: View + 'static, F: Fn(State<S>) -> V + 'static>(
     initial: S,
     f: F,
@@ -168,6 +607,7 @@ pub fn state<S: Clone + 'static, V: View + 'static, F: Fn(State<S>) -> V + 'stat
     StateView {
         default: initial,
         func: f,
+        serial: SERIALS.fetch_add(1, Ordering::Relaxed),
     }
 }
 
@@ -190,4 +630,141 @@ mod tests {
         s.set(42);
         assert_eq!(s2.get(), 42);
     }
+
+    #[test]
+    fn test_state_send_sync() {
+        fn is_send_sync<T: Send + Sync>(_: &T) {}
+        let d = Arc::new(Mutex::new(Dirty::new(None)));
+        let s = State::new(0, d);
+        is_send_sync(&s);
+
+        let s2 = s.clone();
+        std::thread::spawn(move || s2.set(42)).join().unwrap();
+        assert_eq!(s.get(), 42);
+    }
+
+    #[test]
+    fn test_update_while_accessed() {
+        let d = Arc::new(Mutex::new(Dirty::new(None)));
+        let s = State::new(0, d);
+        let s2 = s.clone();
+        s.with(|value| {
+            s2.update(|value| *value += 1);
+            assert_eq!(*value, 0);
+        });
+        assert_eq!(s.get(), 1);
+
+        let s3 = s.clone();
+        let nested = std::panic::catch_unwind(move || s.with_mut(|_| s3.get()));
+        assert!(nested.is_err());
+    }
+
+    #[test]
+    fn test_state_outside_views() {
+        let s: State<Vec<i32>> = vec![1].into();
+        s.update(|v| v.push(2));
+        assert_eq!(s.take(), vec![1, 2]);
+        assert!(s.get_cloned().is_empty());
+        assert_eq!(State::<i32>::default().get_cloned(), 0);
+        assert_eq!(format!("{:?}", State::from(3)), "State(3)");
+    }
+
+    #[test]
+    fn test_weak_state() {
+        let d = Arc::new(Mutex::new(Dirty::new(None)));
+        let s = State::new(0, d);
+        let weak = s.downgrade();
+        assert_eq!(weak.upgrade().map(|s| s.get()), Some(0));
+        drop(s);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_default_cloned_once() {
+        use std::sync::atomic::AtomicUsize;
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+        struct Counted;
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::SeqCst);
+                Counted
+            }
+        }
+
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = state(Counted, |_| EmptyView {});
+        for _ in 0..3 {
+            cx.layout_root(&ui, size, &mut vger);
+            ui.draw(cx.root_id, &mut cx, &mut vger);
+        }
+        assert_eq!(CLONES.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retained_body() {
+        use std::cell::Cell;
+
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let other = State::new(0, Arc::new(Mutex::new(Dirty::new(None))));
+        let builds = Rc::new(Cell::new(0));
+        let builds2 = builds.clone();
+        let ui = state(0, move |count: State<i32>| {
+            builds2.set(builds2.get() + 1);
+            rectangle().size([count.get() as f32, 10.0])
+        });
+        let frame = |cx: &mut Context, vger: &mut SvgRenderer| {
+            cx.layout_root(&ui, size, vger);
+            ui.draw(cx.root_id, cx, vger);
+        };
+
+        frame(&mut cx, &mut vger);
+        frame(&mut cx, &mut vger);
+        other.set(1);
+        frame(&mut cx, &mut vger);
+        assert_eq!(builds.get(), 1);
+
+        cx.get_state(cx.root_id, || 0).set(1);
+        frame(&mut cx, &mut vger);
+        assert_eq!(builds.get(), 2);
+    }
+
+    #[test]
+    fn test_on_change() {
+        use futures::executor::block_on;
+        use futures::StreamExt;
+
+        let d = Arc::new(Mutex::new(Dirty::new(None)));
+        let s = State::new(0, d);
+        let seen = Arc::new(Mutex::new(vec![]));
+        let seen2 = seen.clone();
+        let subscription = s.on_change(move |v| seen2.lock().unwrap().push(*v));
+        let mut values = s.watch();
+
+        s.set(1);
+        s.with_mut(|v| *v += 1);
+        drop(subscription);
+        s.set(3);
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+        assert_eq!(block_on(values.next()), Some(1));
+        assert_eq!(block_on(values.next()), Some(2));
+        assert_eq!(block_on(values.next()), Some(3));
+    }
+
+    #[test]
+    fn test_record_reads() {
+        let d = Arc::new(Mutex::new(Dirty::new(None)));
+        let (a, b) = (State::new(0, d.clone()), State::new(0, d));
+        let (sum, reads) = record_reads(|| a.get() + a.get());
+        assert_eq!(sum, 0);
+        assert_eq!(reads.len(), 1);
+        b.set(1);
+        assert!(!reads[0].changed());
+        a.set(1);
+        assert!(reads[0].changed());
+    }
 }
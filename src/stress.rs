@@ -0,0 +1,119 @@
+//! Synthetic view trees for measuring performance. These are what the
+//! benchmarks in `benches/` run, so you can reproduce their numbers.
+
+use crate::*;
+
+const LOREM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+
+/// A chain of `depth` nested stacks, each with padding.
+pub fn deep_nesting(depth: usize) -> AnyView {
+    if depth == 0 {
+        any_view(rectangle().color(AZURE_HIGHLIGHT))
+    } else {
+        any_view(hstack((circle(), deep_nesting(depth - 1))).padding(1.0))
+    }
+}
+
+/// A list of `rows` rows, each with a shape and a label.
+pub fn wide_list(rows: usize) -> impl View {
+    list((0..rows).collect(), |i| {
+        hstack((
+            circle().color(RED_HIGHLIGHT),
+            text(&format!("row {}", i)),
+            rectangle().corner_radius(2.0),
+        ))
+    })
+}
+
+/// A list of `rows` rows of text alone.
+pub fn text_list(rows: usize) -> impl View {
+    list((0..rows).collect(), |i| {
+        text(&format!("row {} of a long list of text", i))
+    })
+}
+
+/// A list of `paragraphs` rows of text.
+pub fn text_heavy(paragraphs: usize) -> impl View {
+    list((0..paragraphs).collect(), |i| {
+        vstack((text(&format!("paragraph {}", i)).font_size(24), text(LOREM)))
+    })
+}
+
+fn grid_cell(i: usize) -> impl View {
+    list((0..13).collect(), move |j| {
+        let colors = [
+            AZURE_HIGHLIGHT,
+            RED_HIGHLIGHT,
+            GREEN_HIGHLIGHT,
+            AZURE_HIGHLIGHT_DARK,
+        ];
+        rectangle()
+            .corner_radius(2.0)
+            .color(colors[(i + j) % colors.len()])
+    })
+}
+
+/// About 10k rectangles drawn with only four distinct paints, in `rows`
+/// rows of eight cells.
+pub fn rect_grid(rows: usize) -> impl View {
+    list((0..rows).collect(), |row| {
+        let row = *row;
+        hstack((
+            grid_cell(row),
+            grid_cell(row + 1),
+            grid_cell(row + 2),
+            grid_cell(row + 3),
+            grid_cell(row + 4),
+            grid_cell(row + 5),
+            grid_cell(row + 6),
+            grid_cell(row + 7),
+        ))
+    })
+}
+
+/// The renderer `headless` draws with: tiny-skia with the `software`
+/// feature, otherwise SVG. Neither needs a GPU.
+#[cfg(feature = "software")]
+pub type HeadlessRenderer = SoftwareRenderer;
+
+/// The renderer `headless` draws with: tiny-skia with the `software`
+/// feature, otherwise SVG. Neither needs a GPU.
+#[cfg(not(feature = "software"))]
+pub type HeadlessRenderer = SvgRenderer;
+
+/// Creates a context and renderer which aren't attached to a window, so
+/// they run in CI. Layout, drawing and event processing can run against
+/// them, but nothing is presented.
+pub fn headless(size: LocalSize) -> (Context, HeadlessRenderer) {
+    #[cfg(feature = "software")]
+    let vger = SoftwareRenderer::new(size.width as u32, size.height as u32, 1.0);
+    #[cfg(not(feature = "software"))]
+    let vger = SvgRenderer::new(size);
+    (Context::headless(), vger)
+}
+
+/// Clears what's been drawn with `headless`, for a new frame of `size`.
+pub fn begin_frame(vger: &mut HeadlessRenderer, size: LocalSize) {
+    #[cfg(feature = "software")]
+    vger.begin(size.width as u32, size.height as u32, 1.0);
+    #[cfg(not(feature = "software"))]
+    vger.begin(size);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headless() {
+        let size = LocalSize::new(200.0, 200.0);
+        let (mut cx, mut vger) = headless(size);
+        let view = wide_list(100);
+        for _ in 0..2 {
+            begin_frame(&mut vger, size);
+            cx.layout_root(&view, size, &mut vger);
+            view.draw(cx.root_id, &mut cx, &mut vger);
+        }
+        assert!(cx.layout[&cx.root_id.child(&99usize)].rect.height() > 0.0);
+    }
+}
@@ -0,0 +1,601 @@
+use crate::*;
+use std::fmt::Write;
+#[cfg(feature = "software")]
+use std::sync::Arc;
+
+/// Width of a character, in ems. The SVG stretches each line of text to
+/// the width it was laid out with, so this only needs to be roughly right.
+const SVG_CHAR_WIDTH: f32 = 0.55;
+const SVG_ASCENT: f32 = 0.8;
+const SVG_DESCENT: f32 = 0.2;
+const SVG_LINE_HEIGHT: f32 = 1.2;
+
+/// Splits `text` into lines no wider than `max_width`, breaking at spaces.
+fn wrap_lines(text: &str, size: u32, max_width: Option<f32>) -> Vec<String> {
+    let char_width = SVG_CHAR_WIDTH * size as f32;
+    let max_chars = max_width.map(|w| ((w / char_width) as usize).max(1));
+    let mut lines = vec![];
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let fits = match max_chars {
+                Some(max) => line.chars().count() + 1 + word.chars().count() <= max,
+                None => true,
+            };
+            if line.is_empty() {
+                line = word.into();
+            } else if fits {
+                line.push(' ');
+                line.push_str(word);
+            } else {
+                lines.push(std::mem::replace(&mut line, word.into()));
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn svg_color(color: Color) -> String {
+    let c = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", c(color.r), c(color.g), c(color.b))
+}
+
+#[cfg(feature = "software")]
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Renders into an SVG document, so views can be exported at full vector
+/// quality, for reports, diagrams and printing. Text is written as text,
+/// in the viewer's sans-serif font.
+pub struct SvgRenderer {
+    size: LocalSize,
+    defs: String,
+    body: String,
+    offset: LocalOffset,
+    zoom: f32,
+
+    /// Clip, opacity and layer groups currently open.
+    groups: usize,
+    saved: Vec<(LocalOffset, f32, usize)>,
+
+    /// The groups open when each layer began.
+    layers: Vec<usize>,
+
+    /// Path data for `fill`.
+    path: String,
+
+    /// For naming gradients and clip paths.
+    next_id: usize,
+}
+
+impl SvgRenderer {
+    /// Creates a renderer for a document of `size` points.
+    pub fn new(size: LocalSize) -> Self {
+        Self {
+            size,
+            defs: String::new(),
+            body: String::new(),
+            offset: LocalOffset::zero(),
+            zoom: 1.0,
+            groups: 0,
+            saved: vec![],
+            layers: vec![],
+            path: String::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Clears the document and resizes it, ready for a new frame.
+    pub fn begin(&mut self, size: LocalSize) {
+        *self = Self::new(size);
+    }
+
+    /// The SVG document drawn so far.
+    pub fn finish(&self) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+            w = self.size.width,
+            h = self.size.height
+        );
+        if !self.defs.is_empty() {
+            let _ = write!(svg, "<defs>\n{}</defs>\n", self.defs);
+        }
+        svg.push_str(&self.body);
+        svg.push_str(&"</g>\n".repeat(self.groups));
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Maps a point to the document's y-down coordinates.
+    fn point(&self, p: LocalPoint) -> (f32, f32) {
+        (
+            self.offset.x + p.x * self.zoom,
+            self.size.height - (self.offset.y + p.y * self.zoom),
+        )
+    }
+
+    /// The top left corner and size of `rect` in the document.
+    fn rect(&self, rect: LocalRect) -> (f32, f32, f32, f32) {
+        let (x, y) = self.point(LocalPoint::new(rect.min_x(), rect.max_y()));
+        (x, y, rect.width() * self.zoom, rect.height() * self.zoom)
+    }
+
+    fn id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}{}", prefix, self.next_id)
+    }
+
+    /// Attributes for filling or stroking with `paint`.
+    fn paint(&mut self, attr: &str, paint: Paint) -> String {
+        match paint {
+            Paint::Color(color) => format!(
+                "{}=\"{}\" {}-opacity=\"{}\"",
+                attr,
+                svg_color(color),
+                attr,
+                color.a
+            ),
+            Paint::Gradient {
+                start,
+                end,
+                inner_color,
+                outer_color,
+            } => {
+                let id = self.id("gradient");
+                let (x1, y1) = self.point(start);
+                let (x2, y2) = self.point(end);
+                let _ = writeln!(
+                    self.defs,
+                    "<linearGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">\
+                     <stop offset=\"0\" stop-color=\"{}\" stop-opacity=\"{}\"/>\
+                     <stop offset=\"1\" stop-color=\"{}\" stop-opacity=\"{}\"/></linearGradient>",
+                    id,
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    svg_color(inner_color),
+                    inner_color.a,
+                    svg_color(outer_color),
+                    outer_color.a
+                );
+                format!("{}=\"url(#{})\"", attr, id)
+            }
+        }
+    }
+
+    fn stroke(&mut self, width: f32, paint: Paint) -> String {
+        format!(
+            "fill=\"none\" stroke-width=\"{}\" stroke-linecap=\"round\" {}",
+            width * self.zoom,
+            self.paint("stroke", paint)
+        )
+    }
+
+    /// Starts a group clipped to `rect`, with corners rounded by `radius`.
+    fn open_clip(&mut self, rect: LocalRect, radius: f32) {
+        let id = self.id("clip");
+        let (x, y, w, h) = self.rect(rect);
+        let _ = writeln!(
+            self.defs,
+            "<clipPath id=\"{}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"/></clipPath>",
+            id,
+            x,
+            y,
+            w,
+            h,
+            radius * self.zoom
+        );
+        let _ = writeln!(self.body, "<g clip-path=\"url(#{})\">", id);
+        self.groups += 1;
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn save(&mut self) {
+        self.saved.push((self.offset, self.zoom, self.groups));
+    }
+
+    fn restore(&mut self) {
+        if let Some((offset, zoom, groups)) = self.saved.pop() {
+            self.offset = offset;
+            self.zoom = zoom;
+            while self.groups > groups {
+                self.body.push_str("</g>\n");
+                self.groups -= 1;
+            }
+        }
+    }
+
+    fn save_depth(&self) -> usize {
+        self.saved.len()
+    }
+
+    fn translate(&mut self, offset: LocalOffset) {
+        self.offset += offset * self.zoom;
+    }
+
+    fn scale(&mut self, scale: f32) {
+        self.zoom *= scale;
+    }
+
+    fn clip(&mut self, rect: LocalRect) {
+        self.open_clip(rect, 0.0);
+    }
+
+    fn to_world(&self, rect: LocalRect) -> Option<WorldRect> {
+        Some(
+            rect.scale(self.zoom, self.zoom)
+                .translate(self.offset)
+                .cast_unit(),
+        )
+    }
+
+    fn opacity(&mut self, opacity: f32) {
+        let _ = writeln!(self.body, "<g opacity=\"{}\">", opacity);
+        self.groups += 1;
+    }
+
+    fn begin_layer(&mut self, effects: LayerEffects) {
+        self.layers.push(self.groups);
+        let mut attrs = String::new();
+        if effects.blend_mode != BlendMode::Normal {
+            let mode = match effects.blend_mode {
+                BlendMode::Normal => "normal",
+                BlendMode::Multiply => "multiply",
+                BlendMode::Screen => "screen",
+                BlendMode::Darken => "darken",
+                BlendMode::Lighten => "lighten",
+                BlendMode::Plus => "plus-lighter",
+            };
+            let _ = write!(attrs, " style=\"mix-blend-mode:{}\"", mode);
+        }
+        if effects.blur > 0.0 || effects.saturation != 1.0 {
+            let id = self.id("filter");
+            let _ = write!(
+                self.defs,
+                "<filter id=\"{}\" x=\"-50%\" y=\"-50%\" width=\"200%\" height=\"200%\">",
+                id
+            );
+            if effects.blur > 0.0 {
+                let _ = write!(
+                    self.defs,
+                    "<feGaussianBlur stdDeviation=\"{}\"/>",
+                    effects.blur_sigma() * self.zoom
+                );
+            }
+            if effects.saturation != 1.0 {
+                let _ = write!(
+                    self.defs,
+                    "<feColorMatrix type=\"saturate\" values=\"{}\"/>",
+                    effects.saturation
+                );
+            }
+            self.defs.push_str("</filter>\n");
+            let _ = write!(attrs, " filter=\"url(#{})\"", id);
+        }
+        let _ = writeln!(self.body, "<g{}>", attrs);
+        self.groups += 1;
+    }
+
+    fn end_layer(&mut self) {
+        if let Some(groups) = self.layers.pop() {
+            while self.groups > groups {
+                self.body.push_str("</g>\n");
+                self.groups -= 1;
+            }
+        }
+    }
+
+    fn fill_rect(&mut self, rect: LocalRect, radius: f32, paint: Paint) {
+        let (x, y, w, h) = self.rect(rect);
+        let paint = self.paint("fill", paint);
+        let _ = writeln!(
+            self.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {}/>",
+            x,
+            y,
+            w,
+            h,
+            radius * self.zoom,
+            paint
+        );
+    }
+
+    fn stroke_rect(
+        &mut self,
+        min: LocalPoint,
+        max: LocalPoint,
+        radius: f32,
+        width: f32,
+        paint: Paint,
+    ) {
+        let (x, y, w, h) = self.rect(LocalRect::new(min, (max - min).to_size()));
+        let stroke = self.stroke(width, paint);
+        let _ = writeln!(
+            self.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {}/>",
+            x,
+            y,
+            w,
+            h,
+            radius * self.zoom,
+            stroke
+        );
+    }
+
+    fn fill_circle(&mut self, center: LocalPoint, radius: f32, paint: Paint) {
+        let (x, y) = self.point(center);
+        let paint = self.paint("fill", paint);
+        let _ = writeln!(
+            self.body,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+            x,
+            y,
+            radius * self.zoom,
+            paint
+        );
+    }
+
+    fn stroke_segment(&mut self, a: LocalPoint, b: LocalPoint, width: f32, paint: Paint) {
+        let ((x1, y1), (x2, y2)) = (self.point(a), self.point(b));
+        let stroke = self.stroke(width, paint);
+        let _ = writeln!(
+            self.body,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+            x1, y1, x2, y2, stroke
+        );
+    }
+
+    fn stroke_arc(
+        &mut self,
+        center: LocalPoint,
+        radius: f32,
+        width: f32,
+        rotation: f32,
+        aperture: f32,
+        paint: Paint,
+    ) {
+        let stroke = self.stroke(width, paint);
+        if aperture >= std::f32::consts::PI {
+            let (x, y) = self.point(center);
+            let _ = writeln!(
+                self.body,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+                x,
+                y,
+                radius * self.zoom,
+                stroke
+            );
+            return;
+        }
+
+        // Like vger, the arc spans `aperture` either side of `rotation`.
+        let at = |theta: f32| center + LocalOffset::new(theta.cos(), theta.sin()) * radius;
+        let (x1, y1) = self.point(at(rotation - aperture));
+        let (x2, y2) = self.point(at(rotation + aperture));
+        let large = if aperture > std::f32::consts::FRAC_PI_2 {
+            1
+        } else {
+            0
+        };
+        // Counterclockwise, which is SVG's negative sweep.
+        let _ = writeln!(
+            self.body,
+            "<path d=\"M {} {} A {r} {r} 0 {} 0 {} {}\" {}/>",
+            x1,
+            y1,
+            large,
+            x2,
+            y2,
+            stroke,
+            r = radius * self.zoom
+        );
+    }
+
+    fn stroke_bezier(
+        &mut self,
+        a: LocalPoint,
+        b: LocalPoint,
+        c: LocalPoint,
+        width: f32,
+        paint: Paint,
+    ) {
+        let ((ax, ay), (bx, by), (cx, cy)) = (self.point(a), self.point(b), self.point(c));
+        let stroke = self.stroke(width, paint);
+        let _ = writeln!(
+            self.body,
+            "<path d=\"M {} {} Q {} {} {} {}\" {}/>",
+            ax, ay, bx, by, cx, cy, stroke
+        );
+    }
+
+    fn move_to(&mut self, p: LocalPoint) {
+        let (x, y) = self.point(p);
+        let _ = write!(self.path, "M {} {} ", x, y);
+    }
+
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint) {
+        let ((bx, by), (cx, cy)) = (self.point(b), self.point(c));
+        let _ = write!(self.path, "Q {} {} {} {} ", bx, by, cx, cy);
+    }
+
+    fn fill(&mut self, paint: Paint) {
+        let path = std::mem::take(&mut self.path);
+        let paint = self.paint("fill", paint);
+        let _ = writeln!(self.body, "<path d=\"{}Z\" {}/>", path, paint);
+    }
+
+    #[cfg(feature = "software")]
+    fn draw_frame(&mut self, _key: ViewID, frame: &Arc<Frame>, rect: LocalRect, radius: f32) {
+        let png = match frame.to_png() {
+            Some(png) => png,
+            None => return,
+        };
+        if radius > 0.0 {
+            self.save();
+            self.open_clip(rect, radius);
+        }
+        let (x, y, w, h) = self.rect(rect);
+        let _ = writeln!(
+            self.body,
+            "<image x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" preserveAspectRatio=\"none\" href=\"data:image/png;base64,{}\"/>",
+            x,
+            y,
+            w,
+            h,
+            base64(&png)
+        );
+        if radius > 0.0 {
+            self.restore();
+        }
+    }
+
+    fn text(&mut self, text: &str, size: u32, color: Color, max_width: Option<f32>) {
+        let char_width = SVG_CHAR_WIDTH * size as f32;
+        let line_height = SVG_LINE_HEIGHT * size as f32;
+        let paint = self.paint("fill", Paint::Color(color));
+        for (row, line) in wrap_lines(text, size, max_width).iter().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let (x, y) = self.point(LocalPoint::new(0.0, -(row as f32) * line_height));
+            let width = line.chars().count() as f32 * char_width * self.zoom;
+            let _ = writeln!(
+                self.body,
+                "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" xml:space=\"preserve\" {}>{}</text>",
+                x,
+                y,
+                size as f32 * self.zoom,
+                width,
+                paint,
+                escape_xml(line)
+            );
+        }
+    }
+
+    fn text_bounds(&mut self, text: &str, size: u32, max_width: Option<f32>) -> LocalRect {
+        self.line_metrics(text, size, max_width)
+            .iter()
+            .fold(None, |bounds: Option<LocalRect>, line| {
+                Some(bounds.map_or(line.bounds, |b| b.union(&line.bounds)))
+            })
+            .unwrap_or_default()
+    }
+
+    fn glyph_positions(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LocalRect> {
+        let char_width = SVG_CHAR_WIDTH * size as f32;
+        let line_height = SVG_LINE_HEIGHT * size as f32;
+        let mut rects = vec![];
+        for (row, line) in wrap_lines(text, size, max_width).iter().enumerate() {
+            for col in 0..line.chars().count() {
+                rects.push(LocalRect::new(
+                    [
+                        col as f32 * char_width,
+                        -(row as f32) * line_height - SVG_DESCENT * size as f32,
+                    ]
+                    .into(),
+                    [char_width, (SVG_ASCENT + SVG_DESCENT) * size as f32].into(),
+                ));
+            }
+        }
+        rects
+    }
+
+    fn line_metrics(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<LineMetrics> {
+        let char_width = SVG_CHAR_WIDTH * size as f32;
+        let line_height = SVG_LINE_HEIGHT * size as f32;
+        let mut glyph_start = 0;
+        let mut metrics = vec![];
+        for (row, line) in wrap_lines(text, size, max_width).iter().enumerate() {
+            let n = line.chars().count();
+            metrics.push(LineMetrics {
+                glyph_start,
+                glyph_end: glyph_start + n,
+                bounds: LocalRect::new(
+                    [0.0, -(row as f32) * line_height - SVG_DESCENT * size as f32].into(),
+                    [
+                        n as f32 * char_width,
+                        (SVG_ASCENT + SVG_DESCENT) * size as f32,
+                    ]
+                    .into(),
+                ),
+            });
+            glyph_start += n;
+        }
+        metrics
+    }
+
+    fn baselines(&mut self, text: &str, size: u32, max_width: Option<f32>) -> Vec<f32> {
+        let line_height = SVG_LINE_HEIGHT * size as f32;
+        (0..wrap_lines(text, size, max_width).len())
+            .map(|row| -(row as f32) * line_height)
+            .collect()
+    }
+}
+
+/// Lays out and draws `view` at `size` points into an SVG document. As with
+/// `render_to_image`, the view gets fresh state.
+pub fn render_to_svg(view: &impl View, size: LocalSize) -> String {
+    let mut cx = Context::headless();
+    let mut renderer = SvgRenderer::new(size);
+    cx.layout_root(view, size, &mut renderer);
+    view.draw(cx.root_id, &mut cx, &mut renderer);
+    renderer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_lines() {
+        assert_eq!(wrap_lines("a b\nc", 10, None), vec!["a b", "c"]);
+        // Five and a half points a character, so three fit in 17.
+        assert_eq!(wrap_lines("ab cd e", 10, Some(17.0)), vec!["ab", "cd", "e"]);
+    }
+
+    #[test]
+    fn test_svg_renderer() {
+        let mut r = SvgRenderer::new([100.0, 50.0].into());
+        r.save();
+        r.clip(LocalRect::new([0.0, 0.0].into(), [10.0, 10.0].into()));
+        r.fill_rect(
+            LocalRect::new([0.0, 0.0].into(), [10.0, 10.0].into()),
+            0.0,
+            Paint::Color(Color::new(1.0, 0.0, 0.0, 1.0)),
+        );
+        r.restore();
+        let svg = r.finish();
+        // y flips, so a rect at the bottom is 40 from the top.
+        assert!(svg.contains("<rect x=\"0\" y=\"40\" width=\"10\" height=\"10\""));
+        assert!(svg.contains("fill=\"#ff0000\""));
+        assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+    }
+}
@@ -0,0 +1,433 @@
+//! Icons drawn from SVG, which usvg parses into paths.
+
+use crate::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use usvg::NodeExt;
+
+/// Where an SVG comes from: its contents, or a file to read.
+pub enum SvgSource {
+    Bytes(Vec<u8>),
+    Path(PathBuf),
+}
+
+impl From<&[u8]> for SvgSource {
+    fn from(bytes: &[u8]) -> Self {
+        SvgSource::Bytes(bytes.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for SvgSource {
+    fn from(bytes: &[u8; N]) -> Self {
+        SvgSource::Bytes(bytes.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for SvgSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        SvgSource::Bytes(bytes)
+    }
+}
+
+impl From<&Path> for SvgSource {
+    fn from(path: &Path) -> Self {
+        SvgSource::Path(path.into())
+    }
+}
+
+impl From<PathBuf> for SvgSource {
+    fn from(path: PathBuf) -> Self {
+        SvgSource::Path(path)
+    }
+}
+
+impl From<&str> for SvgSource {
+    fn from(path: &str) -> Self {
+        SvgSource::Path(path.into())
+    }
+}
+
+/// A closed or open run of cubic beziers, in SVG units. Lines are stored
+/// as cubics too.
+struct Contour {
+    start: LocalPoint,
+    cubics: Vec<[LocalPoint; 3]>,
+}
+
+struct SvgPath {
+    contours: Vec<Contour>,
+    fill: Option<Color>,
+    stroke: Option<(Color, f32)>,
+}
+
+/// A path flattened to quadratic beziers at a particular size, which is
+/// what renderers draw.
+struct QuadPath {
+    contours: Vec<(LocalPoint, Vec<[LocalPoint; 2]>)>,
+    fill: Option<Color>,
+    stroke: Option<(Color, f32)>,
+}
+
+/// Splits a cubic into `n` pieces and approximates each with a quadratic.
+/// Returns the control and end point of each quadratic.
+fn cubic_to_quads(p0: LocalPoint, c: [LocalPoint; 3], n: usize) -> Vec<[LocalPoint; 2]> {
+    let point = |t: f32| {
+        let mt = 1.0 - t;
+        let w = [mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t];
+        (p0.to_vector() * w[0]
+            + c[0].to_vector() * w[1]
+            + c[1].to_vector() * w[2]
+            + c[2].to_vector() * w[3])
+            .to_point()
+    };
+    let tangent = |t: f32| {
+        let mt = 1.0 - t;
+        (c[0] - p0) * (3.0 * mt * mt)
+            + (c[1] - c[0]) * (6.0 * mt * t)
+            + (c[2] - c[1]) * (3.0 * t * t)
+    };
+    (0..n)
+        .map(|i| {
+            let (t0, t1) = (i as f32 / n as f32, (i + 1) as f32 / n as f32);
+            let dt = t1 - t0;
+            let (a, b) = (point(t0), point(t1));
+            // Control points of the piece, as a cubic.
+            let c1 = a + tangent(t0) * (dt / 3.0);
+            let c2 = b - tangent(t1) * (dt / 3.0);
+            let ctrl =
+                ((c1.to_vector() + c2.to_vector()) * 3.0 - a.to_vector() - b.to_vector()) / 4.0;
+            [ctrl.to_point(), b]
+        })
+        .collect()
+}
+
+fn svg_color(paint: &usvg::Paint, opacity: f64) -> Color {
+    match paint {
+        usvg::Paint::Color(c) => Color::new(
+            c.red as f32 / 255.0,
+            c.green as f32 / 255.0,
+            c.blue as f32 / 255.0,
+            opacity as f32,
+        ),
+        // Gradients and patterns are drawn in a single color.
+        _ => Color::new(TEXT_COLOR.r, TEXT_COLOR.g, TEXT_COLOR.b, opacity as f32),
+    }
+}
+
+/// A parsed SVG, and its paths flattened at the sizes it's been drawn.
+struct SvgData {
+    view_box: LocalRect,
+    paths: Vec<SvgPath>,
+    cache: RefCell<HashMap<[u32; 2], Rc<Vec<QuadPath>>>>,
+}
+
+impl SvgData {
+    fn parse(data: &[u8]) -> Result<Self, usvg::Error> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default().to_ref())?;
+        let rect = tree.svg_node().view_box.rect;
+        let view_box = LocalRect::new(
+            [rect.x() as f32, rect.y() as f32].into(),
+            [rect.width() as f32, rect.height() as f32].into(),
+        );
+
+        let mut paths = vec![];
+        for node in tree.root().descendants() {
+            if let usvg::NodeKind::Path(ref path) = *node.borrow() {
+                let transform = node.abs_transform();
+                let pt = |x: f64, y: f64| {
+                    let (x, y) = transform.apply(x, y);
+                    LocalPoint::new(x as f32, y as f32)
+                };
+                let mut contours: Vec<Contour> = vec![];
+                let mut current = LocalPoint::zero();
+                for segment in path.data.iter() {
+                    match *segment {
+                        usvg::PathSegment::MoveTo { x, y } => {
+                            current = pt(x, y);
+                            contours.push(Contour {
+                                start: current,
+                                cubics: vec![],
+                            });
+                        }
+                        usvg::PathSegment::LineTo { x, y } => {
+                            let end = pt(x, y);
+                            if let Some(contour) = contours.last_mut() {
+                                contour.cubics.push([
+                                    current.lerp(end, 1.0 / 3.0),
+                                    current.lerp(end, 2.0 / 3.0),
+                                    end,
+                                ]);
+                            }
+                            current = end;
+                        }
+                        usvg::PathSegment::CurveTo {
+                            x1,
+                            y1,
+                            x2,
+                            y2,
+                            x,
+                            y,
+                        } => {
+                            let end = pt(x, y);
+                            if let Some(contour) = contours.last_mut() {
+                                contour.cubics.push([pt(x1, y1), pt(x2, y2), end]);
+                            }
+                            current = end;
+                        }
+                        usvg::PathSegment::ClosePath => {
+                            if let Some(contour) = contours.last_mut() {
+                                let start = contour.start;
+                                if current != start {
+                                    contour.cubics.push([
+                                        current.lerp(start, 1.0 / 3.0),
+                                        current.lerp(start, 2.0 / 3.0),
+                                        start,
+                                    ]);
+                                }
+                                current = start;
+                            }
+                        }
+                    }
+                }
+
+                paths.push(SvgPath {
+                    contours,
+                    fill: path
+                        .fill
+                        .as_ref()
+                        .map(|fill| svg_color(&fill.paint, fill.opacity.value())),
+                    stroke: path.stroke.as_ref().map(|stroke| {
+                        (
+                            svg_color(&stroke.paint, stroke.opacity.value()),
+                            stroke.width.value() as f32,
+                        )
+                    }),
+                });
+            }
+        }
+
+        Ok(Self {
+            view_box,
+            paths,
+            cache: RefCell::new(HashMap::new()),
+        })
+    }
+
+    fn empty() -> Self {
+        Self {
+            view_box: LocalRect::zero(),
+            paths: vec![],
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Flattens the paths to quadratics, fitting the view box into `size`
+    /// while keeping its aspect ratio. Curves are split finely enough to
+    /// look smooth on high-DPI displays.
+    fn tessellate(&self, size: LocalSize) -> Rc<Vec<QuadPath>> {
+        let key = [size.width.to_bits(), size.height.to_bits()];
+        if let Some(quads) = self.cache.borrow().get(&key) {
+            return quads.clone();
+        }
+
+        let vb = self.view_box;
+        let scale = if vb.is_empty() {
+            1.0
+        } else {
+            (size.width / vb.width()).min(size.height / vb.height())
+        };
+        let origin = LocalPoint::new(
+            (size.width - vb.width() * scale) / 2.0,
+            (size.height - vb.height() * scale) / 2.0,
+        );
+        // SVG is y-down, layout is y-up.
+        let map = |p: LocalPoint| {
+            LocalPoint::new(
+                origin.x + (p.x - vb.min_x()) * scale,
+                size.height - origin.y - (p.y - vb.min_y()) * scale,
+            )
+        };
+
+        let quads: Vec<QuadPath> = self
+            .paths
+            .iter()
+            .map(|path| QuadPath {
+                contours: path
+                    .contours
+                    .iter()
+                    .map(|contour| {
+                        let mut prev = map(contour.start);
+                        let mut quads = vec![];
+                        for cubic in &contour.cubics {
+                            let c = [map(cubic[0]), map(cubic[1]), map(cubic[2])];
+                            let length = (c[0] - prev).length()
+                                + (c[1] - c[0]).length()
+                                + (c[2] - c[1]).length();
+                            let n = ((length / 4.0).ceil() as usize).clamp(1, 16);
+                            quads.extend(cubic_to_quads(prev, c, n));
+                            prev = c[2];
+                        }
+                        (map(contour.start), quads)
+                    })
+                    .collect(),
+                fill: path.fill,
+                stroke: path.stroke.map(|(color, width)| (color, width * scale)),
+            })
+            .collect();
+
+        let quads = Rc::new(quads);
+        let mut cache = self.cache.borrow_mut();
+        // Icons are usually drawn at a handful of sizes. Don't let an
+        // animated size grow the cache without bound.
+        if cache.len() >= 16 {
+            cache.clear();
+        }
+        cache.insert(key, quads.clone());
+        quads
+    }
+}
+
+/// Struct for `icon_svg`.
+pub struct SvgIcon {
+    data: Rc<SvgData>,
+}
+
+impl View for SvgIcon {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("icon_svg");
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let tint = cx.get_env::<TintColor>();
+        let color = |c: Color| match tint {
+            Some(TintColor(tint)) => Color::new(tint.r, tint.g, tint.b, tint.a * c.a),
+            None => c,
+        };
+
+        vger.save();
+        vger.translate(rect.origin.to_vector());
+        for path in self.data.tessellate(rect.size).iter() {
+            if let Some(fill) = path.fill {
+                for (start, quads) in &path.contours {
+                    vger.move_to(*start);
+                    for [b, c] in quads {
+                        vger.quad_to(*b, *c);
+                    }
+                }
+                let paint = vger.color_paint(color(fill));
+                vger.fill(paint);
+            }
+            if let Some((stroke, width)) = path.stroke {
+                let paint = vger.color_paint(color(stroke));
+                for (start, quads) in &path.contours {
+                    let mut a = *start;
+                    for [b, c] in quads {
+                        vger.stroke_bezier(a, *b, *c, width, paint);
+                        a = *c;
+                    }
+                }
+            }
+        }
+        vger.restore();
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        _id: ViewID,
+        _cx: &mut Context,
+        _nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        None
+    }
+}
+
+impl private::Sealed for SvgIcon {}
+
+/// An icon drawn from an SVG, given either its contents or the path of
+/// a file. The SVG is fitted to the space available. Use the `tint`
+/// modifier to draw it in a single color.
+pub fn icon_svg(source: impl Into<SvgSource>) -> SvgIcon {
+    let data = match source.into() {
+        SvgSource::Bytes(bytes) => Ok(bytes),
+        SvgSource::Path(path) => {
+            std::fs::read(&path).map_err(|err| format!("error reading {:?}: {}", path, err))
+        }
+    };
+    let data = data.and_then(|bytes| {
+        SvgData::parse(&bytes).map_err(|err| format!("error parsing SVG: {}", err))
+    });
+    SvgIcon {
+        data: Rc::new(data.unwrap_or_else(|err| {
+            println!("{}", err);
+            SvgData::empty()
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cubic_to_quads() {
+        let p0 = LocalPoint::new(0.0, 0.0);
+        let c = [
+            LocalPoint::new(0.0, 10.0),
+            LocalPoint::new(10.0, 10.0),
+            LocalPoint::new(10.0, 0.0),
+        ];
+        let quads = cubic_to_quads(p0, c, 4);
+        assert_eq!(quads.len(), 4);
+        assert_eq!(quads[3][1], c[2]);
+
+        // Midpoint of the cubic is (5, 7.5).
+        assert!((quads[1][1] - LocalPoint::new(5.0, 7.5)).length() < 1e-4);
+    }
+}
@@ -0,0 +1,326 @@
+//! SwiftUI names for rui's views and modifiers, for porting SwiftUI code.
+//! Everything here maps onto the usual API:
+//!
+//! ```ignore
+//! use rui::swiftui::*;
+//! use rui::*;
+//!
+//! VStack! {
+//!     Text("Hello").font(TextStyle::Title),
+//!     Button("Go", || println!("go")).frame(Some(100.0), None),
+//! }
+//! ```
+//!
+//! Rust doesn't have argument labels, so arguments keep SwiftUI's order
+//! without them, and leading-dot enum cases are spelled out with their
+//! type.
+#![allow(non_snake_case)]
+
+use crate::*;
+
+/// `VStack! { a, b, c }` is `vstack((a, b, c))`.
+#[macro_export]
+macro_rules! VStack {
+    ($($child:expr),* $(,)?) => {
+        $crate::vstack(($($child,)*))
+    };
+}
+
+/// `HStack! { a, b, c }` is `hstack((a, b, c))`.
+#[macro_export]
+macro_rules! HStack {
+    ($($child:expr),* $(,)?) => {
+        $crate::hstack(($($child,)*))
+    };
+}
+
+/// `ZStack! { a, b, c }` is `zstack((a, b, c))`.
+#[macro_export]
+macro_rules! ZStack {
+    ($($child:expr),* $(,)?) => {
+        $crate::zstack(($($child,)*))
+    };
+}
+
+pub use crate::{HStack, VStack, ZStack};
+
+/// SwiftUI's text styles, for `SwiftText::font`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextStyle {
+    LargeTitle,
+    Title,
+    Title2,
+    Title3,
+    Headline,
+    Body,
+    Callout,
+    Subheadline,
+    Footnote,
+    Caption,
+    Caption2,
+}
+
+impl TextStyle {
+    /// The font size, scaled from SwiftUI's so `Body` is rui's default.
+    pub fn size(self) -> u32 {
+        match self {
+            TextStyle::LargeTitle => 36,
+            TextStyle::Title => 30,
+            TextStyle::Title2 => 24,
+            TextStyle::Title3 => 21,
+            TextStyle::Headline | TextStyle::Body => Text::DEFAULT_SIZE,
+            TextStyle::Callout => 17,
+            TextStyle::Subheadline => 16,
+            TextStyle::Footnote => 14,
+            TextStyle::Caption => 13,
+            TextStyle::Caption2 => 12,
+        }
+    }
+}
+
+/// `text`.
+pub fn Text(text: &str) -> SwiftText {
+    SwiftText(crate::text(text))
+}
+
+/// `button`, with a text label.
+pub fn Button<F: Fn() + 'static>(label: &str, action: F) -> crate::Button<impl View, F> {
+    button(text(label), action)
+}
+
+/// `toggle`.
+pub fn Toggle(is_on: impl Binding<bool>) -> impl View {
+    toggle(is_on)
+}
+
+/// `hslider`.
+pub fn Slider(value: impl Binding<f32>) -> impl View {
+    hslider(value)
+}
+
+/// Text with SwiftUI's text modifiers. Made by `Text`.
+pub struct SwiftText(crate::Text);
+
+impl SwiftText {
+    pub fn font(self, style: TextStyle) -> Self {
+        Self(self.0.font_size(style.size()))
+    }
+
+    pub fn foregroundColor(self, color: Color) -> Self {
+        Self(self.0.color(color))
+    }
+}
+
+impl View for SwiftText {
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.0.print(id, cx)
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.0.process(event, id, cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.0.draw(id, cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.0.layout(id, constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.0.hittest(id, pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.0.commands(id, cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.0.gc(id, cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.0.access(id, cx, nodes)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.0.intrinsic_size(id, cx, vger)
+    }
+}
+
+impl private::Sealed for SwiftText {}
+
+/// SwiftUI's modifiers which rui names differently.
+pub trait SwiftModifiers: View + Sized + 'static {
+    /// Fixes the width, the height or both, centering the view within.
+    /// `frame(Some(100.0), None)` is SwiftUI's `.frame(width: 100)`.
+    fn frame(self, width: Option<f32>, height: Option<f32>) -> Frame<Self> {
+        Frame {
+            child: self,
+            width,
+            height,
+        }
+    }
+
+    fn onTapGesture<F: Fn() + 'static>(self, action: F) -> Tap<Self, F> {
+        self.tap(action)
+    }
+}
+
+impl<V: View + 'static> SwiftModifiers for V {}
+
+/// Struct for `SwiftModifiers::frame`.
+pub struct Frame<V> {
+    child: V,
+    width: Option<f32>,
+    height: Option<f32>,
+}
+
+impl<V> Frame<V> {
+    /// Where the child is, within the frame.
+    fn child_offset(&self, id: ViewID, cx: &Context) -> LocalOffset {
+        cx.child_rect(id).origin.to_vector()
+    }
+}
+
+impl<V> View for Frame<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".frame({:?}, {:?})", self.width, self.height);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let mut local_event = event.clone();
+        local_event.position -= self.child_offset(id, cx);
+        self.child.process(&local_event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        vger.save();
+        vger.translate(self.child_offset(id, cx));
+        self.child.draw(id.child(&0), cx, vger);
+        vger.restore();
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let framed = constraints.tighten(self.width, self.height);
+        let child_size = self.child.layout(id.child(&0), framed.loosen(), cx, vger);
+        let size = framed.constrain(child_size);
+        let offset = ((size - child_size) / 2.0).to_vector();
+        offset_guides(&mut cx.guides, offset);
+
+        cx.note_child_rect(id, LocalRect::new(offset.to_point(), child_size));
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let offset = self.child_offset(id, cx);
+        self.child.hittest(id.child(&0), pt - offset, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => Some(LocalSize::new(width, height)),
+            _ => self
+                .child
+                .intrinsic_size(id.child(&0), cx, vger)
+                .map(|size| {
+                    LocalSize::new(
+                        self.width.unwrap_or(size.width),
+                        self.height.unwrap_or(size.height),
+                    )
+                }),
+        }
+    }
+}
+
+impl<V> private::Sealed for Frame<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame() {
+        let size = LocalSize::new(200.0, 200.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = VStack! {
+            Text("title").font(TextStyle::Title),
+            circle().frame(Some(50.0), Some(20.0)),
+        };
+        ui.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+
+        let framed = rectangle().size([10.0, 10.0]).frame(Some(30.0), None);
+        let framed_size = framed.layout(cx.root_id, size.into(), &mut cx, &mut vger);
+        assert_eq!(framed_size, LocalSize::new(30.0, 10.0));
+        assert_eq!(
+            framed.child_offset(cx.root_id, &cx),
+            LocalOffset::new(10.0, 0.0)
+        );
+    }
+}
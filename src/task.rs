@@ -0,0 +1,192 @@
+use crate::*;
+use futures::executor::block_on;
+use futures::future::{AbortHandle, Abortable};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::Poll;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How a task ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskEnd {
+    /// The future ran to completion.
+    Finished,
+    /// The task was aborted, because its view went away, the app shut down
+    /// or `TaskHandle::abort` was called.
+    Aborted,
+}
+
+/// A handle to a task spawned with `Context::spawn`. Dropping it doesn't
+/// abort the task; the view's task scope does that.
+#[derive(Clone)]
+pub struct TaskHandle {
+    abort: AbortHandle,
+    end: Arc<(Mutex<Option<TaskEnd>>, Condvar)>,
+}
+
+impl TaskHandle {
+    /// Stops the task the next time it waits.
+    pub fn abort(&self) {
+        self.abort.abort()
+    }
+
+    /// How the task ended, if it has.
+    pub fn end(&self) -> Option<TaskEnd> {
+        *self.end.0.lock().unwrap()
+    }
+
+    /// Waits for the task to end. Mostly for tests.
+    pub fn join(&self) -> TaskEnd {
+        let (end, ended) = &*self.end;
+        let mut end = end.lock().unwrap();
+        loop {
+            if let Some(end) = *end {
+                return end;
+            }
+            end = ended.wait(end).unwrap();
+        }
+    }
+}
+
+/// Future for `delay`.
+pub struct Delay {
+    due: Instant,
+    scheduled: bool,
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<()> {
+        if Instant::now() >= self.due {
+            return Poll::Ready(());
+        }
+        if !self.scheduled {
+            let waker = cx.waker().clone();
+            schedule_app(self.due, move || waker.wake());
+            self.scheduled = true;
+        }
+        Poll::Pending
+    }
+}
+
+/// Waits for `duration` in a task, without holding up its thread, so the
+/// task can be aborted while it waits. The event loop's timers wake it.
+pub fn delay(duration: Duration) -> Delay {
+    Delay {
+        due: Instant::now() + duration,
+        scheduled: false,
+    }
+}
+
+/// The tasks spawned for a view, which are aborted when it's dropped.
+#[derive(Default)]
+pub(crate) struct TaskScope {
+    tasks: Vec<TaskHandle>,
+}
+
+impl Drop for TaskScope {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl Context {
+    /// Runs `future` on another thread, for as long as the view `id` is
+    /// around. The task is aborted when the view goes away or the app shuts
+    /// down. The view must keep state at `id`, which is how we know it's
+    /// still there.
+    pub fn spawn<Fut>(&mut self, id: ViewID, future: Fut) -> TaskHandle
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (abort, registration) = AbortHandle::new_pair();
+        let handle = TaskHandle {
+            abort,
+            end: Default::default(),
+        };
+        let scope = self.tasks.entry(id).or_default();
+        scope.tasks.retain(|task| task.end().is_none());
+        scope.tasks.push(handle.clone());
+
+        let future = Abortable::new(future, registration);
+        let end = handle.end.clone();
+        thread::spawn(move || {
+            let result = match block_on(future) {
+                Ok(()) => TaskEnd::Finished,
+                Err(_) => TaskEnd::Aborted,
+            };
+            let (end, ended) = &*end;
+            *end.lock().unwrap() = Some(result);
+            ended.notify_all();
+        });
+        handle
+    }
+
+    /// The tasks spawned for the view `id` which haven't ended.
+    pub fn tasks(&self, id: ViewID) -> Vec<TaskHandle> {
+        self.tasks.get(&id).map_or(vec![], |scope| {
+            scope
+                .tasks
+                .iter()
+                .filter(|task| task.end().is_none())
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Aborts the tasks of views which have gone, after their states have
+    /// been collected.
+    pub(crate) fn retain_tasks(&mut self) {
+        let state_map = &self.state_map;
+        self.tasks.retain(|id, _| state_map.contains_key(id));
+    }
+
+    /// Aborts every task, when the app shuts down.
+    pub(crate) fn abort_tasks(&mut self) {
+        self.tasks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_scope() {
+        let mut cx = Context::headless();
+        let id = cx.root_id;
+        cx.get_state(id, || 0);
+
+        let done = cx.spawn(id, async {});
+        assert_eq!(done.join(), TaskEnd::Finished);
+
+        let pending = cx.spawn(id, futures::future::pending());
+        assert_eq!(cx.tasks(id).len(), 1);
+        cx.retain_tasks();
+        assert_eq!(pending.end(), None);
+
+        // The view's gone once its state has.
+        cx.state_map.clear();
+        cx.retain_tasks();
+        assert_eq!(pending.join(), TaskEnd::Aborted);
+        assert!(cx.tasks(id).is_empty());
+    }
+
+    #[test]
+    fn test_delay() {
+        let _timers = lock_app_timers();
+        let mut cx = Context::headless();
+        let id = cx.root_id;
+        cx.get_state(id, || 0);
+
+        let waiting = cx.spawn(id, delay(Duration::from_millis(10)));
+        thread::sleep(Duration::from_millis(20));
+        run_timers(Instant::now());
+        assert_eq!(waiting.join(), TaskEnd::Finished);
+    }
+}
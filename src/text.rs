@@ -1,9 +1,34 @@
 use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// How many layouts each `Text` keeps, for the widths it's laid out at
+/// each frame.
+const TEXT_LAYOUTS_KEPT: usize = 4;
+
+/// A `Text`'s string as shown at a width, and its measurements.
+struct TextLayout {
+    /// A hash of what the layout depends on: the string, font, size and
+    /// width.
+    key: u64,
+    shown: String,
+    bounds: LocalRect,
+    baselines: Vec<f32>,
+}
+
+/// A `Text`'s layouts, kept from frame to frame so text isn't shaped
+/// again until it changes.
+#[derive(Clone, Default)]
+struct TextLayouts {
+    layouts: Arc<Mutex<Vec<Arc<TextLayout>>>>,
+}
 
 /// Struct for `text`.
 pub struct Text {
     text: String,
     size: u32,
+    color: Color,
 }
 
 impl Text {
@@ -14,17 +39,44 @@ impl View for Text {
     fn print(&self, _id: ViewID, _cx: &mut Context) {
         println!("Text({:?})", self.text);
     }
-    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut VGER) {}
-    fn draw(&self, _id: ViewID, _cx: &mut Context, vger: &mut VGER) {
-        let origin = vger.text_bounds(self.text.as_str(), self.size, None).origin;
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {}
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let width = match cx.layout.get(&id) {
+            Some(layout_box) => layout_box.rect.width(),
+            None => f32::INFINITY,
+        };
+        let layout = self.text_layout(id, width, cx, vger);
+        let origin = layout.bounds.origin;
+        cx.note_rect(
+            id,
+            LocalRect::new(LocalPoint::zero(), layout.bounds.size),
+            vger,
+        );
 
         vger.save();
-        vger.translate([-origin.x, -origin.y]);
-        vger.text(self.text.as_str(), self.size, TEXT_COLOR, None);
+        vger.translate([-origin.x, -origin.y].into());
+        vger.text(layout.shown.as_str(), self.size, self.color, None);
         vger.restore();
     }
-    fn layout(&self, id: ViewID, _sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        let size = vger.text_bounds(self.text.as_str(), self.size, None).size;
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let layout = self.text_layout(id, constraints.max.width, cx, vger);
+        let (bounds, baselines) = (layout.bounds, &layout.baselines);
+        let size = bounds.size;
+
+        // The text is drawn moved up by its descent, so its baselines are
+        // that far up too.
+        if let (Some(first), Some(last)) = (baselines.first(), baselines.last()) {
+            let first_baseline = Alignment::Vertical(VAlignment::FirstTextBaseline);
+            let last_baseline = Alignment::Vertical(VAlignment::LastTextBaseline);
+            cx.guides.insert(first_baseline, first - bounds.origin.y);
+            cx.guides.insert(last_baseline, last - bounds.origin.y);
+        }
 
         cx.layout.insert(
             id,
@@ -40,34 +92,121 @@ impl View for Text {
         _id: ViewID,
         _pt: LocalPoint,
         _cx: &mut Context,
-        _vger: &mut VGER,
+        _vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         None
     }
 
     fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
 
-    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {
-        // do nothing
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = cx.get_state(id, TextLayouts::default);
+        map.insert(id, Box::new(state));
     }
 
     fn access(
         &self,
         id: ViewID,
-        _cx: &mut Context,
+        cx: &mut Context,
         nodes: &mut Vec<accesskit::Node>,
     ) -> Option<accesskit::NodeId> {
+        cx.note_label(id, &self.text);
         let aid = id.access_id();
         nodes.push(accesskit::Node::new(aid, accesskit::Role::LabelText));
         Some(aid)
     }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        Some(self.text_layout(id, f32::INFINITY, cx, vger).bounds.size)
+    }
 }
 
 impl Text {
+    /// The layout at `width`, kept from earlier frames if the text, font
+    /// and size haven't changed since.
+    fn text_layout(
+        &self,
+        id: ViewID,
+        width: f32,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Arc<TextLayout> {
+        let mut hasher = DefaultHasher::new();
+        self.text.hash(&mut hasher);
+        self.size.hash(&mut hasher);
+        cx.get_env::<fonts::Font>().hash(&mut hasher);
+        width.to_bits().hash(&mut hasher);
+        let key = hasher.finish();
+
+        let layouts = cx.get_state(id, TextLayouts::default).get();
+        let mut layouts = layouts.layouts.lock().unwrap();
+        if let Some(layout) = layouts.iter().find(|layout| layout.key == key) {
+            return layout.clone();
+        }
+
+        let shown = self.fit(width, vger);
+        let layout = Arc::new(TextLayout {
+            key,
+            bounds: vger.text_bounds(shown.as_str(), self.size, None),
+            baselines: vger.baselines(shown.as_str(), self.size, None),
+            shown,
+        });
+        if layouts.len() == TEXT_LAYOUTS_KEPT {
+            layouts.remove(0);
+        }
+        layouts.push(layout.clone());
+        layout
+    }
+
+    /// The text, cut short with an ellipsis if it's wider than `width`.
+    fn fit(&self, width: f32, vger: &mut dyn Renderer) -> String {
+        if vger
+            .text_bounds(self.text.as_str(), self.size, None)
+            .width()
+            <= width
+        {
+            return self.text.clone();
+        }
+        let chars: Vec<char> = self.text.chars().collect();
+        let truncated =
+            |n: usize| chars[..n].iter().collect::<String>().trim_end().to_owned() + "…";
+
+        // Finds the most characters which fit along with the ellipsis.
+        let (mut lo, mut hi) = (0, chars.len());
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if vger
+                .text_bounds(truncated(mid).as_str(), self.size, None)
+                .width()
+                <= width
+            {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        truncated(lo)
+    }
+
     pub fn font_size(self, size: u32) -> Self {
         Self {
             text: self.text,
             size,
+            color: self.color,
+        }
+    }
+
+    /// Sets the text color.
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            text: self.text,
+            size: self.size,
+            color,
         }
     }
 }
@@ -79,5 +218,36 @@ pub fn text(name: &str) -> Text {
     Text {
         text: String::from(name),
         size: Text::DEFAULT_SIZE,
+        color: TEXT_COLOR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_layout_cache() {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new([1000.0, 100.0].into());
+        let id = cx.root_id;
+
+        let line = text("a line of text which is cut short");
+        let full = line.layout(id, [1000.0, 100.0].into(), &mut cx, &mut vger);
+        let cut = line.layout(id, [60.0, 100.0].into(), &mut cx, &mut vger);
+        assert!(cut.width < full.width);
+        assert!(line
+            .text_layout(id, 60.0, &mut cx, &mut vger)
+            .shown
+            .ends_with('…'));
+
+        // Another string in the same place isn't given the old layout.
+        let short = text("short");
+        let size = short.layout(id, [1000.0, 100.0].into(), &mut cx, &mut vger);
+        assert!(size.width < full.width);
+        assert_eq!(
+            line.layout(id, [1000.0, 100.0].into(), &mut cx, &mut vger),
+            full
+        );
     }
 }
@@ -148,8 +148,8 @@ where
                 let text2 = text.clone();
                 let cursor = state.with(|s| s.cursor);
                 let state2 = state.clone();
-                canvas(move |rect, vger| {
-                    vger.translate([0.0, rect.height()]);
+                renderer_canvas(move |rect, vger| {
+                    vger.translate([0.0, rect.height()].into());
                     let font_size = 18;
                     let break_width = Some(rect.width());
 
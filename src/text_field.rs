@@ -0,0 +1,489 @@
+pub use crate::*;
+use std::sync::Arc;
+
+pub const TEXT_FIELD_HEIGHT: f32 = 28.0;
+const TEXT_FIELD_FONT_SIZE: u32 = 16;
+const TEXT_FIELD_PADDING: f32 = 6.0;
+
+type CharFilter = Arc<dyn Fn(char) -> bool>;
+type InputTransform = Arc<dyn Fn(&str) -> String>;
+
+/// Rules for what can be typed into a text field, applied to each edit
+/// before it reaches the binding.
+#[derive(Clone, Default)]
+pub(crate) struct InputFilter {
+    allow: Option<CharFilter>,
+    max_len: Option<usize>,
+    transform: Option<InputTransform>,
+}
+
+impl InputFilter {
+    /// What's left of `typed` after the rules, given the text already
+    /// has `len` characters.
+    fn apply(&self, typed: &str, len: usize) -> String {
+        let typed = match &self.transform {
+            Some(transform) => transform(typed),
+            None => typed.to_string(),
+        };
+        let room = self.max_len.map(|max| max.saturating_sub(len));
+        typed
+            .chars()
+            .filter(|c| self.allow.as_ref().map(|allow| allow(*c)).unwrap_or(true))
+            .take(room.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct TextFieldState {
+    /// Byte offset of the cursor.
+    pub(crate) cursor: usize,
+
+    /// Whether a secure field is showing its text.
+    revealed: bool,
+}
+
+impl TextFieldState {
+    pub(crate) fn new() -> Self {
+        Self {
+            cursor: 0,
+            revealed: false,
+        }
+    }
+
+    fn prev_boundary(&self, t: &str) -> usize {
+        t[..self.cursor]
+            .char_indices()
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self, t: &str) -> usize {
+        t[self.cursor..]
+            .chars()
+            .next()
+            .map(|c| self.cursor + c.len_utf8())
+            .unwrap_or(self.cursor)
+    }
+
+    fn insert(&mut self, s: &str, text: &impl Binding<String>, filter: &InputFilter) {
+        let s = filter.apply(s, text.with(|t| t.chars().count()));
+        if !s.is_empty() {
+            text.with_mut(|t| t.insert_str(self.cursor, &s));
+            self.cursor += s.len();
+        }
+    }
+
+    pub(crate) fn key(&mut self, k: &KeyPress, text: &impl Binding<String>) {
+        self.key_filtered(k, text, &InputFilter::default())
+    }
+
+    fn key_filtered(&mut self, k: &KeyPress, text: &impl Binding<String>, filter: &InputFilter) {
+        // The text may have been changed through the binding elsewhere.
+        self.cursor = self.cursor.min(text.with(|t| t.len()));
+
+        match k {
+            KeyPress::ArrowLeft => self.cursor = text.with(|t| self.prev_boundary(t)),
+            KeyPress::ArrowRight => self.cursor = text.with(|t| self.next_boundary(t)),
+            KeyPress::Backspace if self.cursor > 0 => {
+                let start = text.with(|t| self.prev_boundary(t));
+                text.with_mut(|t| t.replace_range(start..self.cursor, ""));
+                self.cursor = start;
+            }
+            KeyPress::Delete => {
+                let end = text.with(|t| self.next_boundary(t));
+                text.with_mut(|t| t.replace_range(self.cursor..end, ""));
+            }
+            KeyPress::Character(c) => self.insert(c, text, filter),
+            KeyPress::Space => self.insert(" ", text, filter),
+            KeyPress::Home => self.cursor = 0,
+            KeyPress::End => self.cursor = text.with(|t| t.len()),
+            _ => (),
+        }
+    }
+}
+
+/// Bullets standing in for each character of `text`, and where `cursor`
+/// is in them.
+fn masked(text: &str, cursor: usize) -> (String, usize) {
+    let bullet = '\u{2022}';
+    let before = text[..cursor.min(text.len())].chars().count();
+    (
+        bullet.to_string().repeat(text.chars().count()),
+        before * bullet.len_utf8(),
+    )
+}
+
+/// Draws a text field filling the width of `rect`, centered vertically.
+pub(crate) fn draw_text_field(
+    rect: LocalRect,
+    text: &str,
+    cursor: usize,
+    has_focus: bool,
+    size: ControlSize,
+    vger: &mut dyn Renderer,
+) {
+    let height = TEXT_FIELD_HEIGHT * size.scale();
+    let font_size = size.font_size(TEXT_FIELD_FONT_SIZE);
+    let field = LocalRect::new(
+        [0.0, (rect.height() - height) / 2.0].into(),
+        [rect.width(), height].into(),
+    );
+
+    let bg = vger.color_paint(CONTROL_BACKGROUND);
+    vger.fill_rect(field, BUTTON_CORNER_RADIUS, bg);
+
+    if has_focus {
+        let border = vger.color_paint(AZURE_HIGHLIGHT);
+        vger.stroke_rect(field.min(), field.max(), BUTTON_CORNER_RADIUS, 1.0, border);
+    }
+
+    let cursor = cursor.min(text.len());
+    let bounds = vger.text_bounds(text, font_size, None);
+    let cursor_x = vger.text_bounds(&text[..cursor], font_size, None).max_x();
+
+    vger.save();
+    vger.translate(
+        [
+            field.min_x() + TEXT_FIELD_PADDING * size.scale(),
+            field.center().y - font_size as f32 / 2.0,
+        ]
+        .into(),
+    );
+    vger.text(text, font_size, TEXT_COLOR, None);
+
+    if has_focus {
+        let cursor_paint = vger.color_paint(AZURE_HIGHLIGHT);
+        vger.fill_rect(
+            LocalRect::new(
+                [cursor_x.max(0.0), bounds.min_y().min(0.0)].into(),
+                [2.0, font_size as f32 + 2.0].into(),
+            ),
+            0.0,
+            cursor_paint,
+        );
+    }
+    vger.restore();
+}
+
+/// The button at the right end of a secure field which shows or hides
+/// the text. Fills the space it's offered, but only hits where it's drawn.
+struct RevealButton {
+    revealed: bool,
+}
+
+impl RevealButton {
+    fn geom(&self, id: ViewID, cx: &mut Context) -> (LocalPoint, f32) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        (
+            [rect.max_x() - TEXT_FIELD_HEIGHT / 2.0, rect.center().y].into(),
+            TEXT_FIELD_HEIGHT / 4.0,
+        )
+    }
+}
+
+impl View for RevealButton {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("RevealButton");
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        // An eye, crossed out while the text is hidden.
+        let (center, radius) = self.geom(id, cx);
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        let corner = LocalOffset::new(radius, 0.0);
+        let lid = LocalOffset::new(0.0, radius * 0.8);
+        vger.stroke_bezier(center - corner, center + lid, center + corner, 1.5, paint);
+        vger.stroke_bezier(center - corner, center - lid, center + corner, 1.5, paint);
+        vger.fill_circle(center, radius / 3.0, paint);
+        if !self.revealed {
+            let d = LocalOffset::new(radius, -radius);
+            vger.stroke_segment(center - d, center + d, 1.5, paint);
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = constraints.biggest();
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let (center, radius) = self.geom(id, cx);
+        if pt.distance_to(center) < radius * 1.5 {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {
+        // do nothing
+    }
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {
+        // do nothing
+    }
+
+    fn access(
+        &self,
+        _id: ViewID,
+        _cx: &mut Context,
+        _nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        None
+    }
+}
+
+impl private::Sealed for RevealButton {}
+
+/// Struct for `text_field`.
+#[derive(Clone)]
+pub struct TextField<B> {
+    text: B,
+    secure: bool,
+    filter: InputFilter,
+}
+
+impl<B> TextField<B>
+where
+    B: Binding<String>,
+{
+    /// Shows bullets in place of the text, for passwords. A button at the
+    /// right end shows the text while it's toggled on. What's typed isn't
+    /// recorded by RUI_RECORD, and `print` doesn't show it.
+    pub fn secure(self) -> Self {
+        Self {
+            secure: true,
+            ..self
+        }
+    }
+
+    /// Only lets characters for which `f` returns true be typed. Others
+    /// are dropped, including from pasted text.
+    pub fn allow_chars(mut self, f: impl Fn(char) -> bool + 'static) -> Self {
+        self.filter.allow = Some(Arc::new(f));
+        self
+    }
+
+    /// Stops the text growing beyond `max_len` characters.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.filter.max_len = Some(max_len);
+        self
+    }
+
+    /// Changes what's typed before it's inserted, for example with
+    /// `str::to_uppercase` or `str::trim`. Runs before `allow_chars` and
+    /// `max_len`.
+    pub fn transform(mut self, f: impl Fn(&str) -> String + 'static) -> Self {
+        self.filter.transform = Some(Arc::new(f));
+        self
+    }
+
+    fn body(&self) -> impl View {
+        let text = self.text.clone();
+        let secure = self.secure;
+        let filter = self.filter.clone();
+        read_env(move |size: ControlSize| {
+            let text = text.clone();
+            let filter = filter.clone();
+            focus(move |has_focus| {
+                let text = text.clone();
+                let filter = filter.clone();
+                state(TextFieldState::new(), move |state| {
+                    let text = text.clone();
+                    let text2 = text.clone();
+                    let filter = filter.clone();
+                    let (cursor, revealed) = state.with(|s| (s.cursor, s.revealed));
+                    let state2 = state.clone();
+                    let field = renderer_canvas(move |rect, vger| {
+                        let t = text.get();
+                        if secure && !revealed {
+                            let (bullets, cursor) = masked(&t, cursor);
+                            draw_text_field(rect, &bullets, cursor, has_focus, size, vger)
+                        } else {
+                            draw_text_field(rect, &t, cursor, has_focus, size, vger)
+                        }
+                    })
+                    .key(move |k| {
+                        if has_focus {
+                            state.with_mut(|s| s.key_filtered(&k, &text2, &filter))
+                        }
+                    });
+                    zstack((
+                        field,
+                        cond(
+                            secure,
+                            RevealButton { revealed }.tap(move || {
+                                state2.with_mut(|s| s.revealed = !s.revealed);
+                            }),
+                            EmptyView {},
+                        ),
+                    ))
+                })
+            })
+        })
+    }
+}
+
+impl<B> View for TextField<B>
+where
+    B: Binding<String>,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        if self.secure {
+            println!("text_field(secure)");
+        } else {
+            self.body().print(id, cx)
+        }
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body().process(event, id, cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.body().draw(id, cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        // The focus view inside has the same id.
+        if self.secure {
+            cx.secure_ids.insert(id);
+        } else {
+            cx.secure_ids.remove(&id);
+        }
+        self.body().layout(id, constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.body().hittest(id, pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.body().commands(id, cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.body().gc(id, cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.body().access(id, cx, nodes)
+    }
+}
+
+impl<B> private::Sealed for TextField<B> {}
+
+impl<B> FormInput for TextField<B>
+where
+    B: Binding<String>,
+{
+    type Value = String;
+
+    fn value(&self) -> String {
+        self.text.get()
+    }
+}
+
+/// Single line editable text.
+pub fn text_field(text: impl Binding<String>) -> TextField<impl Binding<String>> {
+    TextField {
+        text,
+        secure: false,
+        filter: InputFilter::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_text_field_editing() {
+        let dirty = Arc::new(Mutex::new(Dirty::new(None)));
+        let text = State::new(String::new(), dirty);
+        let mut s = TextFieldState::new();
+
+        s.key(&KeyPress::Character("hé"), &text);
+        s.key(&KeyPress::Character("y"), &text);
+        assert_eq!(text.get(), "héy");
+
+        s.key(&KeyPress::ArrowLeft, &text);
+        s.key(&KeyPress::Backspace, &text);
+        assert_eq!(text.get(), "hy");
+        assert_eq!(s.cursor, 1);
+
+        s.key(&KeyPress::Delete, &text);
+        s.key(&KeyPress::End, &text);
+        s.key(&KeyPress::Space, &text);
+        assert_eq!(text.get(), "h ");
+    }
+
+    #[test]
+    fn test_input_filter() {
+        let dirty = Arc::new(Mutex::new(Dirty::new(None)));
+        let text = State::new(String::new(), dirty);
+        let mut s = TextFieldState::new();
+        let filter = InputFilter {
+            allow: Some(Arc::new(|c: char| c.is_ascii_alphanumeric())),
+            max_len: Some(4),
+            transform: Some(Arc::new(|s: &str| s.to_uppercase())),
+        };
+
+        s.key_filtered(&KeyPress::Character("a-b"), &text, &filter);
+        s.key_filtered(&KeyPress::Space, &text, &filter);
+        assert_eq!(text.get(), "AB");
+        s.key_filtered(&KeyPress::Character("cde"), &text, &filter);
+        assert_eq!(text.get(), "ABCD");
+        assert_eq!(s.cursor, 4);
+    }
+
+    #[test]
+    fn test_masked() {
+        let (bullets, cursor) = masked("hé!", 3);
+        assert_eq!(bullets.chars().count(), 3);
+        assert_eq!(&bullets[..cursor], "\u{2022}\u{2022}");
+    }
+}
@@ -1,21 +1,27 @@
 use crate::*;
 
-/// Toggle switch.
+/// Toggle switch, sized by the `control_size` of the views around it.
 pub fn toggle(state: impl Binding<bool>) -> impl View {
-    let b = state.get();
-    zstack((
-        rectangle()
-            .color(if b {
-                AZURE_HIGHLIGHT_BACKGROUND
-            } else {
-                CONTROL_BACKGROUND
-            })
-            .corner_radius(10.0)
-            .size([40.0, 20.0])
-            .tap(move || state.with_mut(|b| *b = !*b)),
-        circle()
-            .color(if b { AZURE_HIGHLIGHT } else { MEDIUM_GRAY })
-            .size([10.0, 10.0])
-            .offset([if b { 25.0 } else { 5.0 }, 5.0]),
-    ))
+    read_env(move |size: ControlSize| {
+        let b = state.get();
+        let s = size.scale();
+        let state = state.clone();
+        zstack((
+            rectangle()
+                .color(if b {
+                    AZURE_HIGHLIGHT_BACKGROUND
+                } else {
+                    CONTROL_BACKGROUND
+                })
+                .corner_radius(10.0 * s)
+                .size([40.0 * s, 20.0 * s])
+                .tap(move || state.with_mut(|b| *b = !*b)),
+            circle()
+                .color(if b { AZURE_HIGHLIGHT } else { MEDIUM_GRAY })
+                .size([10.0 * s, 10.0 * s])
+                .offset([if b { 25.0 * s } else { 5.0 * s }, 5.0 * s]),
+        ))
+        .role(accesskit::Role::Switch)
+        .value(b)
+    })
 }
@@ -0,0 +1,509 @@
+use crate::*;
+
+pub const TOKEN_HEIGHT: f32 = 22.0;
+const TOKEN_PADDING: f32 = 8.0;
+const TOKEN_SPACING: f32 = 4.0;
+const TOKEN_REMOVE_SIZE: f32 = 14.0;
+const TOKEN_INPUT_MIN_WIDTH: f32 = 60.0;
+const TOKEN_SUGGESTION_ROW_HEIGHT: f32 = 22.0;
+const TOKEN_MAX_SUGGESTIONS: usize = 6;
+
+/// Splits `text` at commas and newlines. Returns the trimmed pieces
+/// before the last separator, which are done, and the text after it,
+/// which is still being typed.
+fn split_tokens(text: &str) -> (Vec<String>, String) {
+    let mut pieces: Vec<&str> = text.split([',', '\n']).collect();
+    let rest = pieces.pop().unwrap_or("").to_string();
+    let tokens = pieces
+        .into_iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect();
+    (tokens, rest)
+}
+
+/// Lays out pieces of `widths` left to right, wrapping to a new row when
+/// one doesn't fit in `width`. Returns the row and x of each.
+fn wrap(widths: &[f32], width: f32) -> Vec<(usize, f32)> {
+    let mut row = 0;
+    let mut x = TOKEN_SPACING;
+    widths
+        .iter()
+        .map(|w| {
+            if x > TOKEN_SPACING && x + w + TOKEN_SPACING > width {
+                row += 1;
+                x = TOKEN_SPACING;
+            }
+            let place = (row, x);
+            x += w + TOKEN_SPACING;
+            place
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct TokenFieldState {
+    /// Editing the text being typed, which is in its own state so it can
+    /// be edited through a binding.
+    field: TextFieldState,
+
+    /// The suggestion chosen with the arrow keys.
+    selected: Option<usize>,
+}
+
+/// Struct for `token_field`.
+pub struct TokenField<B> {
+    tokens: B,
+    suggestions: Vec<String>,
+}
+
+impl<B> TokenField<B>
+where
+    B: Binding<Vec<String>>,
+{
+    /// Offers tokens which contain the text being typed in a dropdown.
+    pub fn suggestions(self, suggestions: Vec<String>) -> Self {
+        Self {
+            suggestions,
+            ..self
+        }
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<TokenFieldState> {
+        cx.with_state(
+            TokenFieldState {
+                field: TextFieldState::new(),
+                selected: None,
+            },
+            id,
+            |state: State<TokenFieldState>, _cx| state,
+        )
+    }
+
+    fn input(&self, id: ViewID, cx: &mut Context) -> State<String> {
+        cx.with_state(String::new(), id.child(&"input"), |s: State<String>, _cx| s)
+    }
+
+    /// The suggestions matching `input` which aren't tokens already.
+    fn matches(&self, input: &str) -> Vec<String> {
+        let query = input.trim().to_lowercase();
+        if query.is_empty() {
+            return vec![];
+        }
+        let tokens = self.tokens.get();
+        self.suggestions
+            .iter()
+            .filter(|s| s.to_lowercase().contains(&query) && !tokens.contains(s))
+            .take(TOKEN_MAX_SUGGESTIONS)
+            .cloned()
+            .collect()
+    }
+
+    fn add(&self, token: &str) {
+        let token = token.trim();
+        if !token.is_empty() && !self.tokens.with(|t| t.iter().any(|t| t == token)) {
+            self.tokens.with_mut(|t| t.push(token.to_string()));
+        }
+    }
+
+    /// Where each token goes, then where the text being typed goes,
+    /// wrapped within `width`. The first row is at the top.
+    fn pieces(&self, input: &str, width: f32, vger: &mut dyn Renderer) -> Vec<(usize, f32, f32)> {
+        let mut widths: Vec<f32> = self.tokens.with(|tokens| {
+            tokens
+                .iter()
+                .map(|t| {
+                    vger.text_bounds(t, Text::DEFAULT_SIZE, None).width()
+                        + 2.0 * TOKEN_PADDING
+                        + TOKEN_REMOVE_SIZE
+                })
+                .collect()
+        });
+        let input_width = vger.text_bounds(input, Text::DEFAULT_SIZE, None).width();
+        widths.push((input_width + 2.0 * TOKEN_PADDING).max(TOKEN_INPUT_MIN_WIDTH));
+        wrap(&widths, width)
+            .into_iter()
+            .zip(widths)
+            .map(|((row, x), w)| (row, x, w))
+            .collect()
+    }
+
+    /// The rects of the pieces within `rect`.
+    fn rects(&self, input: &str, rect: LocalRect, vger: &mut dyn Renderer) -> Vec<LocalRect> {
+        self.pieces(input, rect.width(), vger)
+            .into_iter()
+            .map(|(row, x, w)| {
+                let y = rect.max_y() - (row + 1) as f32 * (TOKEN_HEIGHT + TOKEN_SPACING);
+                LocalRect::new([rect.min_x() + x, y].into(), [w, TOKEN_HEIGHT].into())
+            })
+            .collect()
+    }
+
+    fn remove_rect(token: LocalRect) -> LocalRect {
+        LocalRect::new(
+            [
+                token.max_x() - TOKEN_PADDING / 2.0 - TOKEN_REMOVE_SIZE,
+                token.center().y - TOKEN_REMOVE_SIZE / 2.0,
+            ]
+            .into(),
+            [TOKEN_REMOVE_SIZE, TOKEN_REMOVE_SIZE].into(),
+        )
+    }
+
+    /// The rows of the suggestion dropdown, below the field.
+    fn suggestion_rects(rect: LocalRect, count: usize) -> Vec<LocalRect> {
+        (0..count)
+            .map(|i| {
+                LocalRect::new(
+                    [
+                        rect.min_x(),
+                        rect.min_y() - (i + 1) as f32 * TOKEN_SUGGESTION_ROW_HEIGHT,
+                    ]
+                    .into(),
+                    [rect.width(), TOKEN_SUGGESTION_ROW_HEIGHT].into(),
+                )
+            })
+            .collect()
+    }
+
+    /// The suggestions showing, if any.
+    fn shown_matches(&self, id: ViewID, cx: &mut Context) -> Vec<String> {
+        if cx.focused_id == Some(id) {
+            let input = self.input(id, cx).get();
+            self.matches(&input)
+        } else {
+            vec![]
+        }
+    }
+
+    fn key(&self, key: &KeyPress, id: ViewID, cx: &mut Context) {
+        let state = self.state(id, cx);
+        let input = self.input(id, cx);
+        let matches = self.matches(&input.get());
+        let selected = state.with(|s| s.selected).filter(|i| *i < matches.len());
+
+        match key {
+            KeyPress::Enter => {
+                match selected {
+                    Some(i) => self.add(&matches[i]),
+                    None => self.add(&input.get()),
+                }
+                input.set(String::new());
+                state.with_mut(|s| {
+                    s.field.cursor = 0;
+                    s.selected = None;
+                });
+            }
+            KeyPress::Backspace if input.with(|t| t.is_empty()) => {
+                if !self.tokens.with(|t| t.is_empty()) {
+                    self.tokens.with_mut(|t| {
+                        t.pop();
+                    });
+                }
+            }
+            KeyPress::ArrowDown if !matches.is_empty() => {
+                let i = selected
+                    .map(|i| (i + 1).min(matches.len() - 1))
+                    .unwrap_or(0);
+                state.with_mut(|s| s.selected = Some(i));
+            }
+            KeyPress::ArrowUp if selected.is_some() => {
+                state.with_mut(|s| s.selected = selected.and_then(|i| i.checked_sub(1)));
+            }
+            KeyPress::Escape => {
+                cx.focused_id = None;
+                state.with_mut(|s| s.selected = None);
+            }
+            _ => {
+                state.with_mut(|s| {
+                    s.field.key(key, &input);
+                    s.selected = None;
+                });
+
+                // Typing or pasting separators finishes tokens.
+                let (tokens, rest) = split_tokens(&input.get());
+                if !tokens.is_empty() || input.with(|t| t.len() != rest.len()) {
+                    for token in &tokens {
+                        self.add(token);
+                    }
+                    let len = rest.len();
+                    input.set(rest);
+                    state.with_mut(|s| s.field.cursor = len);
+                }
+            }
+        }
+    }
+}
+
+fn draw_token_label(vger: &mut dyn Renderer, label: &str, x: f32, center_y: f32, color: Color) {
+    let bounds = vger.text_bounds(label, Text::DEFAULT_SIZE, None);
+    vger.save();
+    vger.translate(
+        [
+            x - bounds.origin.x,
+            center_y - bounds.height() / 2.0 - bounds.origin.y,
+        ]
+        .into(),
+    );
+    vger.text(label, Text::DEFAULT_SIZE, color, None);
+    vger.restore();
+}
+
+impl<B> View for TokenField<B>
+where
+    B: Binding<Vec<String>>,
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("token_field({:?})", self.tokens.get());
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        match &event.kind {
+            EventKind::TouchBegin { .. } => {
+                let matches = self.shown_matches(id, cx);
+                let rows = Self::suggestion_rects(rect, matches.len());
+                if let Some(i) = rows.iter().position(|r| r.contains(event.position)) {
+                    self.add(&matches[i]);
+                    self.input(id, cx).set(String::new());
+                    self.state(id, cx).with_mut(|s| {
+                        s.field.cursor = 0;
+                        s.selected = None;
+                    });
+                } else if rect.contains(event.position) {
+                    let input = self.input(id, cx).get();
+                    let rects = self.rects(&input, rect, vger);
+                    let removed = rects[..rects.len() - 1]
+                        .iter()
+                        .position(|r| Self::remove_rect(*r).contains(event.position));
+                    if let Some(i) = removed {
+                        self.tokens.with_mut(|t| {
+                            t.remove(i);
+                        });
+                    }
+                    cx.focused_id = Some(id);
+                    cx.dirty.lock().unwrap().dirty = true;
+                }
+            }
+            EventKind::TouchMove { id: 0 } => {
+                let rows = Self::suggestion_rects(rect, self.shown_matches(id, cx).len());
+                let input = self.input(id, cx).get();
+                let rects = self.rects(&input, rect, vger);
+                let over_remove = rects[..rects.len() - 1]
+                    .iter()
+                    .any(|r| Self::remove_rect(*r).contains(event.position));
+                if over_remove || rows.iter().any(|r| r.contains(event.position)) {
+                    cx.set_cursor(CursorIcon::Hand);
+                } else if rect.contains(event.position) {
+                    cx.set_cursor(CursorIcon::Text);
+                }
+            }
+            EventKind::Key(key, _) if cx.focused_id == Some(id) => self.key(key, id, cx),
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let has_focus = cx.focused_id == Some(id);
+        let input = self.input(id, cx).get();
+        let (cursor, selected) = self.state(id, cx).with(|s| (s.field.cursor, s.selected));
+
+        let paint = vger.color_paint(CONTROL_BACKGROUND);
+        vger.fill_rect(rect, BUTTON_CORNER_RADIUS, paint);
+        if has_focus {
+            let paint = vger.color_paint(AZURE_HIGHLIGHT);
+            vger.stroke_rect(rect.min(), rect.max(), BUTTON_CORNER_RADIUS, 1.0, paint);
+        }
+
+        let rects = self.rects(&input, rect, vger);
+        let tokens = self.tokens.get();
+        for (token, r) in tokens.iter().zip(&rects) {
+            let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+            vger.fill_rect(*r, TOKEN_HEIGHT / 2.0, paint);
+            draw_token_label(
+                vger,
+                token,
+                r.min_x() + TOKEN_PADDING,
+                r.center().y,
+                TEXT_COLOR,
+            );
+
+            let x = Self::remove_rect(*r).inner_rect(euclid::SideOffsets2D::new_all_same(4.0));
+            let paint = vger.color_paint(MEDIUM_GRAY);
+            vger.stroke_segment(x.min(), x.max(), 1.5, paint);
+            vger.stroke_segment(
+                [x.min_x(), x.max_y()].into(),
+                [x.max_x(), x.min_y()].into(),
+                1.5,
+                paint,
+            );
+        }
+
+        if let Some(r) = rects.last() {
+            let x = r.min_x() + TOKEN_PADDING;
+            draw_token_label(vger, &input, x, r.center().y, TEXT_COLOR);
+            if has_focus {
+                let cursor = cursor.min(input.len());
+                let cursor_x = if cursor > 0 {
+                    vger.text_bounds(&input[..cursor], Text::DEFAULT_SIZE, None)
+                        .max_x()
+                } else {
+                    0.0
+                };
+                let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                vger.fill_rect(
+                    LocalRect::new(
+                        [x + cursor_x, r.min_y() + 3.0].into(),
+                        [2.0, TOKEN_HEIGHT - 6.0].into(),
+                    ),
+                    0.0,
+                    paint,
+                );
+            }
+        }
+
+        let matches = self.shown_matches(id, cx);
+        let rows = Self::suggestion_rects(rect, matches.len());
+        if let (Some(first), Some(last)) = (rows.first(), rows.last()) {
+            let menu = first.union(last);
+            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            vger.fill_rect(menu, 4.0, paint);
+            let paint = vger.color_paint(GROOVES);
+            vger.stroke_rect(menu.min(), menu.max(), 4.0, 1.0, paint);
+        }
+        for (i, (suggestion, r)) in matches.iter().zip(&rows).enumerate() {
+            if selected == Some(i) {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
+                vger.fill_rect(*r, 4.0, paint);
+            }
+            draw_token_label(
+                vger,
+                suggestion,
+                r.min_x() + TOKEN_PADDING,
+                r.center().y,
+                TEXT_COLOR,
+            );
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let input = self.input(id, cx).get();
+        let sz = constraints.biggest();
+        let rows = self
+            .pieces(&input, sz.width, vger)
+            .last()
+            .map(|(row, _, _)| row + 1)
+            .unwrap_or(1);
+        let size = LocalSize::new(
+            sz.width,
+            rows as f32 * (TOKEN_HEIGHT + TOKEN_SPACING) + TOKEN_SPACING,
+        );
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let rows = Self::suggestion_rects(rect, self.shown_matches(id, cx).len());
+        if rect.contains(pt) || rows.iter().any(|r| r.contains(pt)) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            TokenFieldState {
+                field: TextFieldState::new(),
+                selected: None,
+            },
+            id,
+            map,
+            |state: State<TokenFieldState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+        let input = id.child(&"input");
+        cx.with_state_aux(
+            String::new(),
+            input,
+            map,
+            |state: State<String>, _cx, map| {
+                map.insert(input, Box::new(state));
+            },
+        );
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::TextField));
+        Some(aid)
+    }
+}
+
+impl<B> private::Sealed for TokenField<B> {}
+
+/// A field where typed text becomes tokens, like tags or recipients.
+/// Enter or a comma finishes a token, and pasted text is split at commas
+/// and newlines. Backspace in an empty field removes the last token, and
+/// clicking a token's cross removes it. The field wraps onto more rows as
+/// tokens are added.
+pub fn token_field<B: Binding<Vec<String>>>(tokens: B) -> TokenField<B> {
+    TokenField {
+        tokens,
+        suggestions: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_tokens() {
+        assert_eq!(split_tokens("abc"), (vec![], "abc".to_string()));
+        assert_eq!(
+            split_tokens("a, b,,\nc"),
+            (vec!["a".to_string(), "b".to_string()], "c".to_string())
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        let x = TOKEN_SPACING;
+        assert_eq!(
+            wrap(&[40.0, 40.0, 40.0], 100.0),
+            vec![(0, x), (0, 2.0 * x + 40.0), (1, x)]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::*;
+use std::path::PathBuf;
+use tao::event_loop::EventLoopWindowTarget;
+use tao::menu::{ContextMenu, MenuId, MenuItemAttributes};
+use tao::system_tray::{SystemTray, SystemTrayBuilder};
+
+/// An entry in the tray icon's menu.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrayItem {
+    /// Sends the command with this name, as if chosen from the menu bar.
+    /// Handle it with the `command` modifier.
+    Command(String),
+
+    /// Shows the window.
+    ShowWindow,
+
+    /// Hides the window, leaving the tray icon.
+    HideWindow,
+
+    /// Exits the app.
+    Quit,
+}
+
+impl TrayItem {
+    fn title(&self) -> &str {
+        match self {
+            TrayItem::Command(path) => path.rsplit(':').next().unwrap_or(path),
+            TrayItem::ShowWindow => "Show",
+            TrayItem::HideWindow => "Hide",
+            TrayItem::Quit => "Quit",
+        }
+    }
+}
+
+/// A tray icon waiting to be created by the event loop.
+#[derive(Clone, PartialEq)]
+pub(crate) struct TrayRequest {
+    icon: PathBuf,
+    items: Vec<TrayItem>,
+}
+
+/// The tray icon, with the menu items it dispatches.
+pub(crate) struct Tray {
+    _tray: SystemTray,
+    items: HashMap<MenuId, TrayItem>,
+}
+
+impl Tray {
+    pub(crate) fn new<T: 'static>(
+        request: &TrayRequest,
+        target: &EventLoopWindowTarget<T>,
+    ) -> Option<Self> {
+        let mut menu = ContextMenu::new();
+        let mut items = HashMap::new();
+        for item in &request.items {
+            let id = menu.add_item(MenuItemAttributes::new(item.title())).id();
+            items.insert(id, item.clone());
+        }
+
+        // On Linux tao wants a path to the icon, elsewhere its bytes.
+        #[cfg(target_os = "linux")]
+        let icon = request.icon.clone();
+        #[cfg(not(target_os = "linux"))]
+        let icon = match std::fs::read(&request.icon) {
+            Ok(icon) => icon,
+            Err(err) => {
+                println!("error reading tray icon {:?}: {:?}", request.icon, err);
+                return None;
+            }
+        };
+
+        match SystemTrayBuilder::new(icon, Some(menu)).build(target) {
+            Ok(tray) => Some(Self { _tray: tray, items }),
+            Err(err) => {
+                println!("error creating tray icon: {:?}", err);
+                None
+            }
+        }
+    }
+
+    /// Performs the action for a menu item, if it's from the tray. Returns
+    /// the command to send to the views, if any.
+    pub(crate) fn menu_event(&self, menu_id: MenuId, cx: &mut Context) -> Option<Option<String>> {
+        let item = self.items.get(&menu_id)?;
+        Some(match item {
+            TrayItem::Command(path) => Some(path.clone()),
+            TrayItem::ShowWindow => {
+                cx.window().set_visible(true);
+                None
+            }
+            TrayItem::HideWindow => {
+                cx.window().set_visible(false);
+                None
+            }
+            TrayItem::Quit => {
+                cx.close_requested = true;
+                None
+            }
+        })
+    }
+}
+
+/// Struct for the `tray` modifier.
+pub struct TrayView<V> {
+    child: V,
+    request: TrayRequest,
+}
+
+impl<V> TrayView<V>
+where
+    V: View,
+{
+    pub fn new(v: V, icon: impl Into<PathBuf>, items: Vec<TrayItem>) -> Self {
+        Self {
+            child: v,
+            request: TrayRequest {
+                icon: icon.into(),
+                items,
+            },
+        }
+    }
+}
+
+impl<V> View for TrayView<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".tray({:?})", self.request.items);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger);
+        if cx.tray.as_ref() != Some(&self.request) {
+            cx.tray = Some(self.request.clone());
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V> private::Sealed for TrayView<V> {}
@@ -0,0 +1,616 @@
+//! Draws into a grid of terminal character cells instead of a GPU surface.
+
+use crate::*;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event as TermEvent, KeyCode, KeyModifiers,
+        MouseEventKind,
+    },
+    execute, queue,
+    style::{Color as TermColor, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::Write;
+
+/// Width of a character cell in layout units. In a terminal, views are
+/// laid out on a grid of cells, so each character of text takes up one
+/// cell regardless of font size.
+pub const CELL_WIDTH: f32 = 8.0;
+
+/// Height of a character cell in layout units.
+pub const CELL_HEIGHT: f32 = 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cell {
+    ch: char,
+    fg: Color,
+    bg: Color,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            fg: TEXT_COLOR,
+            bg: Color::BLACK,
+        }
+    }
+}
+
+fn term_color(color: Color) -> TermColor {
+    TermColor::Rgb {
+        r: (color.r.clamp(0.0, 1.0) * 255.0) as u8,
+        g: (color.g.clamp(0.0, 1.0) * 255.0) as u8,
+        b: (color.b.clamp(0.0, 1.0) * 255.0) as u8,
+    }
+}
+
+fn blend(under: Color, over: Color) -> Color {
+    let a = over.a.clamp(0.0, 1.0);
+    Color::new(
+        under.r + (over.r - under.r) * a,
+        under.g + (over.g - under.g) * a,
+        under.b + (over.b - under.b) * a,
+        1.0,
+    )
+}
+
+/// Renders to a grid of character cells, which `present` writes to a
+/// terminal with crossterm. See `rui_tui`. Rectangles and circles fill cell backgrounds,
+/// lines are drawn with dots and text is drawn one character per cell.
+pub struct TuiRenderer {
+    cols: usize,
+    rows: usize,
+    cells: Vec<Cell>,
+
+    offset: LocalOffset,
+    clip: Option<LocalRect>,
+    saved: Vec<(LocalOffset, Option<LocalRect>)>,
+
+    /// Contours of the path being built for `fill`, flattened to points.
+    path: Vec<Vec<LocalPoint>>,
+}
+
+/// Points along a quadratic bezier, excluding the start.
+fn flatten_quad(a: LocalPoint, b: LocalPoint, c: LocalPoint) -> Vec<LocalPoint> {
+    let steps = (((b - a).length() + (c - b).length()) / (CELL_WIDTH / 2.0))
+        .ceil()
+        .max(1.0) as usize;
+    (1..=steps)
+        .map(|i| {
+            let t = i as f32 / steps as f32;
+            a.lerp(b, t).lerp(b.lerp(c, t), t)
+        })
+        .collect()
+}
+
+/// Even-odd test of whether `pt` is inside the contours.
+fn path_contains(path: &[Vec<LocalPoint>], pt: LocalPoint) -> bool {
+    let mut inside = false;
+    for contour in path {
+        for (i, a) in contour.iter().enumerate() {
+            let b = contour[(i + 1) % contour.len()];
+            if (a.y > pt.y) != (b.y > pt.y) && pt.x < a.x + (pt.y - a.y) / (b.y - a.y) * (b.x - a.x)
+            {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+impl TuiRenderer {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            cells: vec![Cell::default(); cols * rows],
+            offset: LocalOffset::zero(),
+            clip: None,
+            saved: vec![],
+            path: vec![],
+        }
+    }
+
+    /// The size of the grid in layout units.
+    pub fn size(&self) -> LocalSize {
+        [
+            self.cols as f32 * CELL_WIDTH,
+            self.rows as f32 * CELL_HEIGHT,
+        ]
+        .into()
+    }
+
+    /// Clears the grid and resizes it, ready for a new frame.
+    pub fn begin(&mut self, cols: usize, rows: usize) {
+        self.cols = cols;
+        self.rows = rows;
+        self.cells = vec![Cell::default(); cols * rows];
+        self.offset = LocalOffset::zero();
+        self.clip = None;
+        self.saved.clear();
+        self.path.clear();
+    }
+
+    /// The cell containing a point in the current coordinate space. Layout
+    /// is y-up, and terminal rows count down.
+    fn cell_at(&self, pt: LocalPoint) -> Option<usize> {
+        let pt = pt + self.offset;
+        if let Some(clip) = self.clip {
+            if !clip.contains(pt) {
+                return None;
+            }
+        }
+        let col = (pt.x / CELL_WIDTH).floor();
+        let row = self.rows as f32 - 1.0 - (pt.y / CELL_HEIGHT).floor();
+        if col < 0.0 || row < 0.0 || col >= self.cols as f32 || row >= self.rows as f32 {
+            return None;
+        }
+        Some(row as usize * self.cols + col as usize)
+    }
+
+    /// Calls `f` with the center of each cell overlapping `rect`.
+    fn for_cells(&mut self, rect: LocalRect, mut f: impl FnMut(&mut Self, LocalPoint)) {
+        let world = rect.translate(self.offset);
+        let x0 = (world.min_x() / CELL_WIDTH).floor() as i32;
+        let x1 = (world.max_x() / CELL_WIDTH).ceil() as i32;
+        let y0 = (world.min_y() / CELL_HEIGHT).floor() as i32;
+        let y1 = (world.max_y() / CELL_HEIGHT).ceil() as i32;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let center = LocalPoint::new(
+                    (x as f32 + 0.5) * CELL_WIDTH,
+                    (y as f32 + 0.5) * CELL_HEIGHT,
+                ) - self.offset;
+                f(self, center);
+            }
+        }
+    }
+
+    fn fill_cell(&mut self, pt: LocalPoint, color: Color) {
+        if let Some(i) = self.cell_at(pt) {
+            let cell = &mut self.cells[i];
+            cell.bg = blend(cell.bg, color);
+            cell.ch = ' ';
+        }
+    }
+
+    fn set_char(&mut self, pt: LocalPoint, ch: char, color: Color) {
+        if let Some(i) = self.cell_at(pt) {
+            self.cells[i].ch = ch;
+            self.cells[i].fg = color;
+        }
+    }
+
+    fn wrap(&self, text: &str, max_width: Option<f32>) -> Vec<String> {
+        let max_cols = max_width.map(|w| ((w / CELL_WIDTH) as usize).max(1));
+        let mut lines = vec![];
+        for line in text.split('\n') {
+            let chars: Vec<char> = line.chars().collect();
+            match max_cols {
+                Some(n) if chars.len() > n => {
+                    lines.extend(chars.chunks(n).map(|c| c.iter().collect::<String>()))
+                }
+                _ => lines.push(line.to_string()),
+            }
+        }
+        lines
+    }
+
+    /// The characters in a row of the grid, from the top.
+    pub fn row_text(&self, row: usize) -> String {
+        self.cells[row * self.cols..(row + 1) * self.cols]
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    fn segment(&mut self, a: LocalPoint, b: LocalPoint, color: Color) {
+        let steps = ((b - a).length() / (CELL_WIDTH / 2.0)).ceil().max(1.0) as usize;
+        for i in 0..=steps {
+            let pt = a.lerp(b, i as f32 / steps as f32);
+            self.set_char(pt, '·', color);
+        }
+    }
+
+    /// Writes the grid to a terminal.
+    pub fn present(&self, out: &mut impl Write) -> std::io::Result<()> {
+        let mut colors = None;
+        for row in 0..self.rows {
+            queue!(out, MoveTo(0, row as u16))?;
+            for cell in &self.cells[row * self.cols..(row + 1) * self.cols] {
+                if colors != Some((cell.fg, cell.bg)) {
+                    queue!(
+                        out,
+                        SetForegroundColor(term_color(cell.fg)),
+                        SetBackgroundColor(term_color(cell.bg))
+                    )?;
+                    colors = Some((cell.fg, cell.bg));
+                }
+                queue!(out, Print(cell.ch))?;
+            }
+        }
+        queue!(out, ResetColor)?;
+        out.flush()
+    }
+}
+
+/// Cells have a single color, so gradients are drawn with their first color.
+fn paint_color(paint: Paint) -> Color {
+    match paint {
+        Paint::Color(color) => color,
+        Paint::Gradient { inner_color, .. } => inner_color,
+    }
+}
+
+impl Renderer for TuiRenderer {
+    fn save(&mut self) {
+        self.saved.push((self.offset, self.clip));
+    }
+
+    fn restore(&mut self) {
+        if let Some((offset, clip)) = self.saved.pop() {
+            self.offset = offset;
+            self.clip = clip;
+        }
+    }
+
+    fn save_depth(&self) -> usize {
+        self.saved.len()
+    }
+
+    fn translate(&mut self, offset: LocalOffset) {
+        self.offset += offset;
+    }
+
+    fn scale(&mut self, _scale: f32) {
+        // Cells can't be scaled.
+    }
+
+    fn clip(&mut self, rect: LocalRect) {
+        let rect = rect.translate(self.offset);
+        self.clip = Some(match self.clip {
+            Some(clip) => clip.intersection(&rect).unwrap_or_default(),
+            None => rect,
+        });
+    }
+
+    fn fill_rect(&mut self, rect: LocalRect, _radius: f32, paint: Paint) {
+        let color = paint_color(paint);
+        self.for_cells(rect, |r, center| {
+            if rect.contains(center) {
+                r.fill_cell(center, color)
+            }
+        });
+    }
+
+    fn stroke_rect(
+        &mut self,
+        min: LocalPoint,
+        max: LocalPoint,
+        _radius: f32,
+        _width: f32,
+        paint: Paint,
+    ) {
+        let color = paint_color(paint);
+        let sides = [
+            (LocalPoint::new(min.x, max.y), LocalPoint::new(max.x, max.y)),
+            (LocalPoint::new(min.x, min.y), LocalPoint::new(max.x, min.y)),
+            (LocalPoint::new(min.x, min.y), LocalPoint::new(min.x, max.y)),
+            (LocalPoint::new(max.x, min.y), LocalPoint::new(max.x, max.y)),
+        ];
+        for (a, b) in sides {
+            self.segment(a, b, color);
+        }
+    }
+
+    fn fill_circle(&mut self, center: LocalPoint, radius: f32, paint: Paint) {
+        let color = paint_color(paint);
+        let bounds = LocalRect::new(
+            center - LocalOffset::new(radius, radius),
+            [2.0 * radius, 2.0 * radius].into(),
+        );
+        self.for_cells(bounds, |r, pt| {
+            if pt.distance_to(center) <= radius {
+                r.fill_cell(pt, color)
+            }
+        });
+    }
+
+    fn stroke_segment(&mut self, a: LocalPoint, b: LocalPoint, _width: f32, paint: Paint) {
+        self.segment(a, b, paint_color(paint));
+    }
+
+    fn stroke_arc(
+        &mut self,
+        center: LocalPoint,
+        radius: f32,
+        _width: f32,
+        rotation: f32,
+        aperture: f32,
+        paint: Paint,
+    ) {
+        let color = paint_color(paint);
+        let steps = ((2.0 * aperture * radius) / (CELL_WIDTH / 2.0))
+            .ceil()
+            .max(1.0) as usize;
+        for i in 0..=steps {
+            let theta = rotation - aperture + 2.0 * aperture * i as f32 / steps as f32;
+            let pt = center + LocalOffset::new(theta.cos(), theta.sin()) * radius;
+            self.set_char(pt, '·', color);
+        }
+    }
+
+    fn stroke_bezier(
+        &mut self,
+        a: LocalPoint,
+        b: LocalPoint,
+        c: LocalPoint,
+        _width: f32,
+        paint: Paint,
+    ) {
+        let color = paint_color(paint);
+        let mut prev = a;
+        for pt in flatten_quad(a, b, c) {
+            self.segment(prev, pt, color);
+            prev = pt;
+        }
+    }
+
+    fn move_to(&mut self, p: LocalPoint) {
+        self.path.push(vec![p]);
+    }
+
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint) {
+        if let Some(contour) = self.path.last_mut() {
+            let a = *contour.last().unwrap();
+            contour.extend(flatten_quad(a, b, c));
+        }
+    }
+
+    fn fill(&mut self, paint: Paint) {
+        let path = std::mem::take(&mut self.path);
+        let points = path.iter().flatten();
+        let bounds = LocalRect::from_points(points);
+        let color = paint_color(paint);
+        self.for_cells(bounds, |r, center| {
+            if path_contains(&path, center) {
+                r.fill_cell(center, color)
+            }
+        });
+    }
+
+    /// Draws text with its first line at the origin, one character per
+    /// cell whatever the font size.
+    fn text(&mut self, text: &str, _size: u32, color: Color, max_width: Option<f32>) {
+        for (row, line) in self.wrap(text, max_width).iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                let pt = LocalPoint::new(
+                    (col as f32 + 0.5) * CELL_WIDTH,
+                    (0.5 - row as f32) * CELL_HEIGHT,
+                );
+                self.set_char(pt, ch, color);
+            }
+        }
+    }
+
+    fn text_bounds(&mut self, text: &str, _size: u32, max_width: Option<f32>) -> LocalRect {
+        let lines = self.wrap(text, max_width);
+        let cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let rows = lines.len().max(1) as f32;
+        LocalRect::new(
+            [0.0, (1.0 - rows) * CELL_HEIGHT].into(),
+            [cols as f32 * CELL_WIDTH, rows * CELL_HEIGHT].into(),
+        )
+    }
+
+    fn glyph_positions(
+        &mut self,
+        text: &str,
+        _size: u32,
+        max_width: Option<f32>,
+    ) -> Vec<LocalRect> {
+        let mut rects = vec![];
+        for (row, line) in self.wrap(text, max_width).iter().enumerate() {
+            for col in 0..line.chars().count() {
+                rects.push(LocalRect::new(
+                    [col as f32 * CELL_WIDTH, -(row as f32) * CELL_HEIGHT].into(),
+                    [CELL_WIDTH, CELL_HEIGHT].into(),
+                ));
+            }
+        }
+        rects
+    }
+
+    fn line_metrics(&mut self, text: &str, _size: u32, max_width: Option<f32>) -> Vec<LineMetrics> {
+        let mut glyph_start = 0;
+        let mut metrics = vec![];
+        for (row, line) in self.wrap(text, max_width).iter().enumerate() {
+            let n = line.chars().count();
+            metrics.push(LineMetrics {
+                glyph_start,
+                glyph_end: glyph_start + n,
+                bounds: LocalRect::new(
+                    [0.0, -(row as f32) * CELL_HEIGHT].into(),
+                    [n as f32 * CELL_WIDTH, CELL_HEIGHT].into(),
+                ),
+            });
+            glyph_start += n;
+        }
+        metrics
+    }
+
+    fn baselines(&mut self, text: &str, _size: u32, max_width: Option<f32>) -> Vec<f32> {
+        (0..self.wrap(text, max_width).len())
+            .map(|row| -(row as f32) * CELL_HEIGHT)
+            .collect()
+    }
+}
+
+fn key_press(code: KeyCode) -> Option<KeyPress> {
+    Some(match code {
+        KeyCode::Char(' ') => KeyPress::Space,
+        // Keys live for the rest of the program, like the ones tao hands us.
+        KeyCode::Char(c) => KeyPress::Character(Box::leak(c.to_string().into_boxed_str())),
+        KeyCode::Enter => KeyPress::Enter,
+        KeyCode::Tab => KeyPress::Tab,
+        KeyCode::Backspace => KeyPress::Backspace,
+        KeyCode::Delete => KeyPress::Delete,
+        KeyCode::Esc => KeyPress::Escape,
+        KeyCode::Left => KeyPress::ArrowLeft,
+        KeyCode::Right => KeyPress::ArrowRight,
+        KeyCode::Up => KeyPress::ArrowUp,
+        KeyCode::Down => KeyPress::ArrowDown,
+        KeyCode::Home => KeyPress::Home,
+        KeyCode::End => KeyPress::End,
+        KeyCode::PageUp => KeyPress::PageUp,
+        KeyCode::PageDown => KeyPress::PageDown,
+        _ => return None,
+    })
+}
+
+fn modifiers(mods: KeyModifiers) -> ModifiersState {
+    let mut state = ModifiersState::default();
+    if mods.contains(KeyModifiers::SHIFT) {
+        state = state | ModifiersState::SHIFT;
+    }
+    if mods.contains(KeyModifiers::CONTROL) {
+        state = state | ModifiersState::CONTROL;
+    }
+    if mods.contains(KeyModifiers::ALT) {
+        state = state | ModifiersState::ALT;
+    }
+    state
+}
+
+/// Translates a terminal event. Mouse positions are at cell centers.
+fn translate_event(event: TermEvent, rows: u16) -> Option<Event> {
+    let (kind, position) = match event {
+        TermEvent::Key(key) => (
+            EventKind::Key(key_press(key.code)?, modifiers(key.modifiers)),
+            LocalPoint::zero(),
+        ),
+        TermEvent::Mouse(mouse) => {
+            let kind = match mouse.kind {
+                MouseEventKind::Down(_) => EventKind::TouchBegin { id: 0 },
+                MouseEventKind::Drag(_) | MouseEventKind::Moved => EventKind::TouchMove { id: 0 },
+                MouseEventKind::Up(_) => EventKind::TouchEnd { id: 0 },
+                MouseEventKind::ScrollDown => EventKind::Scroll {
+                    delta: [0.0, CELL_HEIGHT].into(),
+                },
+                MouseEventKind::ScrollUp => EventKind::Scroll {
+                    delta: [0.0, -CELL_HEIGHT].into(),
+                },
+            };
+            let position = LocalPoint::new(
+                (mouse.column as f32 + 0.5) * CELL_WIDTH,
+                (rows as f32 - mouse.row as f32 - 0.5) * CELL_HEIGHT,
+            );
+            (kind, position)
+        }
+        _ => return None,
+    };
+    Some(Event { kind, position })
+}
+
+fn run_tui(view: &impl View, out: &mut impl Write) -> std::io::Result<()> {
+    let mut cx = Context::headless();
+    set_app_dirty(cx.dirty.clone());
+    let (cols, rows) = terminal::size()?;
+    let mut renderer = TuiRenderer::new(cols as usize, rows as usize);
+    let mut redraw = true;
+
+    loop {
+        if redraw || cx.dirty.lock().unwrap().dirty {
+            let mut new_map = StateMap::new();
+            view.gc(cx.root_id, &mut cx, &mut new_map);
+            cx.state_map = new_map;
+            cx.retain_tasks();
+            cx.layout.collect();
+            cx.dirty.lock().unwrap().dirty = false;
+
+            let (cols, rows) = terminal::size()?;
+            renderer.begin(cols as usize, rows as usize);
+            let size = renderer.size();
+            cx.layout_root(view, size, &mut renderer);
+            view.draw(cx.root_id, &mut cx, &mut renderer);
+            renderer.present(out)?;
+            redraw = false;
+        }
+
+        if cx.close_requested {
+            return Ok(());
+        }
+
+        for payload in take_user_events() {
+            let event = Event {
+                kind: EventKind::User(payload),
+                position: LocalPoint::zero(),
+            };
+            cx.process_root(view, &event, &mut renderer);
+        }
+        run_timers(std::time::Instant::now());
+
+        // Poll so state set from other threads and timers gets drawn.
+        if event::poll(std::time::Duration::from_millis(50))? {
+            let term_event = event::read()?;
+            match term_event {
+                TermEvent::Key(key)
+                    if key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    return Ok(())
+                }
+                TermEvent::Resize(_, _) => redraw = true,
+                _ => {
+                    if let Some(event) = translate_event(term_event, renderer.rows as u16) {
+                        cx.process_root(view, &event, &mut renderer);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Runs your UI in the terminal, until Ctrl-C. Views are laid out on the
+/// grid of character cells, and the mouse works where the terminal
+/// supports it.
+pub fn rui_tui(view: impl View) {
+    let mut out = std::io::stdout();
+    let result = terminal::enable_raw_mode()
+        .and_then(|_| execute!(out, EnterAlternateScreen, EnableMouseCapture, Hide))
+        .and_then(|_| run_tui(&view, &mut out));
+
+    let _ = execute!(out, Show, DisableMouseCapture, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+
+    if let Err(err) = result {
+        println!("error running in the terminal: {:?}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tui_renderer() {
+        let mut r = TuiRenderer::new(10, 3);
+        r.save();
+        r.translate([CELL_WIDTH, CELL_HEIGHT].into());
+        r.text("hi", 12, TEXT_COLOR, None);
+        r.restore();
+        assert_eq!(r.row_text(1), " hi       ");
+
+        let bounds = r.text_bounds("abc\nde", 12, None);
+        assert_eq!(
+            bounds.size,
+            LocalSize::new(3.0 * CELL_WIDTH, 2.0 * CELL_HEIGHT)
+        );
+
+        let rect = LocalRect::new([0.0, 0.0].into(), r.size());
+        r.fill_rect(rect, 0.0, Paint::Color(Color::WHITE));
+        assert_eq!(r.row_text(1), "          ");
+    }
+}
@@ -3,11 +3,67 @@ use crate::*;
 #[derive(Clone, Debug)]
 pub enum EventKind {
     PressButton(String),
-    TouchBegin { id: usize },
-    TouchMove { id: usize },
-    TouchEnd { id: usize },
+    TouchBegin {
+        id: usize,
+    },
+    TouchMove {
+        id: usize,
+    },
+    TouchEnd {
+        id: usize,
+    },
+
+    /// The system took over the touch, for example for a system gesture.
+    /// Views should forget the touch without acting on it.
+    TouchCancel {
+        id: usize,
+    },
+
+    /// The mouse wheel or trackpad scrolled. `delta` is how far the content
+    /// should move, in y-up points, so scrolling down gives a positive y.
+    Scroll {
+        delta: LocalOffset,
+    },
     Command(String),
     Key(KeyPress, ModifiersState),
+    Window(WindowEventKind),
+
+    /// Sent from another thread with an `Injector`. See `on_user_event`.
+    User(UserEvent),
+
+    /// Published by a view. See `on_message`.
+    Message(Message),
+}
+
+/// Changes to the window, for `on_window_event`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WindowEventKind {
+    Focused,
+    Unfocused,
+    Minimized,
+    Restored,
+
+    /// The window moved. The position of its top left corner is in
+    /// physical pixels.
+    Moved {
+        x: i32,
+        y: i32,
+    },
+
+    /// The window's content changed size, in points. Not sent while the
+    /// window is minimized.
+    Resized {
+        width: f32,
+        height: f32,
+    },
+
+    /// The user asked to close the window. Use `on_close_requested`
+    /// to prevent it.
+    CloseRequested,
+
+    /// A display was connected, disconnected or changed, or the window
+    /// moved to another one.
+    DisplaysChanged(Monitors),
 }
 
 #[derive(Clone, Debug)]
@@ -22,13 +78,20 @@ pub trait View: private::Sealed {
     fn print(&self, id: ViewID, cx: &mut Context);
 
     /// Processes an event.
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER);
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer);
 
-    /// Draws the view using vger.
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER);
+    /// Draws the view to a `Renderer`, which is vger in a window.
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer);
 
-    /// Lays out subviews and return the size of the view.
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize;
+    /// Lays out subviews and return the size of the view, which should be
+    /// within `constraints`.
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize;
 
     /// Returns the topmost view which the point intersects.
     fn hittest(
@@ -36,7 +99,7 @@ pub trait View: private::Sealed {
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID>;
 
     /// Accumulates information about menu bar commands.
@@ -52,6 +115,37 @@ pub trait View: private::Sealed {
         cx: &mut Context,
         nodes: &mut Vec<accesskit::Node>,
     ) -> Option<accesskit::NodeId>;
+
+    /// How readily the view is given space in a stack. When a stack runs
+    /// out of room, views with lower priorities shrink first.
+    fn priority(&self) -> f32 {
+        0.0
+    }
+
+    /// The size the view would be whatever it was offered, like the
+    /// natural size of text. `None` for views which take whatever they're
+    /// given, like shapes. Stacks use this when they're offered unbounded
+    /// space.
+    fn intrinsic_size(
+        &self,
+        _id: ViewID,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        None
+    }
+
+    /// Lays out and draws the view at `size`, with fresh state, and
+    /// returns what's in it. For tests. See `Query`.
+    fn query(&self, size: LocalSize) -> Query
+    where
+        Self: Sized,
+    {
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        cx.layout_root(self, size, &mut vger);
+        cx.query(self, &mut vger)
+    }
 }
 
 pub struct EmptyView {}
@@ -60,14 +154,14 @@ impl View for EmptyView {
     fn print(&self, _id: ViewID, _cx: &mut Context) {
         println!("EmptyView");
     }
-    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut VGER) {}
-    fn draw(&self, _id: ViewID, _cx: &mut Context, _vger: &mut VGER) {}
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {}
+    fn draw(&self, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {}
     fn layout(
         &self,
         _id: ViewID,
-        _sz: LocalSize,
+        _constraints: Constraints,
         _cx: &mut Context,
-        _vger: &mut VGER,
+        _vger: &mut dyn Renderer,
     ) -> LocalSize {
         [0.0, 0.0].into()
     }
@@ -76,7 +170,7 @@ impl View for EmptyView {
         _id: ViewID,
         _pt: LocalPoint,
         _cx: &mut Context,
-        _vger: &mut VGER,
+        _vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         None
     }
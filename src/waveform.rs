@@ -0,0 +1,503 @@
+use crate::*;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver};
+
+pub const WAVEFORM_HEIGHT: f32 = 80.0;
+pub const LEVEL_METER_SIZE: LocalSize = LocalSize::new(120.0, 8.0);
+
+/// Buffers longer than this are bucketed on a worker thread, so the UI
+/// doesn't stall.
+const WAVEFORM_THREAD_SAMPLES: usize = 1 << 16;
+
+/// How far the mouse must move before a click becomes a selection.
+const WAVEFORM_DRAG_DISTANCE: f32 = 3.0;
+
+/// Level meter colors, above -12 dB and -3 dB.
+const LEVEL_WARNING: Color = Color::hex_const("#FFC400");
+const LEVEL_CLIPPING: Color = RED_HIGHLIGHT;
+
+/// The quietest level a level meter shows, in dB.
+const LEVEL_FLOOR_DB: f32 = -60.0;
+
+/// The lowest and highest sample in each of `buckets` equal slices of
+/// `samples`.
+fn peaks(samples: &[f32], buckets: usize) -> Peaks {
+    let n = samples.len();
+    (0..buckets)
+        .map(|i| {
+            let start = i * n / buckets;
+            let end = ((i + 1) * n / buckets).max(start + 1).min(n);
+            samples[start.min(n)..end]
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), s| (lo.min(*s), hi.max(*s)))
+        })
+        .filter(|(lo, hi)| lo <= hi)
+        .collect()
+}
+
+/// Identifies a sample buffer without comparing every sample: its address,
+/// length, and a hash of a few samples spread through it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SamplesKey {
+    ptr: usize,
+    len: usize,
+    hash: u32,
+}
+
+impl SamplesKey {
+    fn new(samples: &[f32]) -> Self {
+        let step = (samples.len() / 64).max(1);
+        let hash = samples
+            .iter()
+            .step_by(step)
+            .fold(0u32, |h, s| h.rotate_left(5) ^ s.to_bits());
+        Self {
+            ptr: samples.as_ptr() as usize,
+            len: samples.len(),
+            hash,
+        }
+    }
+}
+
+/// The lowest and highest sample in each bucket.
+type Peaks = Vec<(f32, f32)>;
+
+/// Peaks of the samples last drawn, for one bucket per point of width.
+#[derive(Default)]
+struct WaveformCache {
+    peaks: Option<(SamplesKey, usize, Peaks)>,
+
+    /// Peaks being computed on a worker thread.
+    pending: Option<(SamplesKey, usize, Receiver<Peaks>)>,
+}
+
+impl WaveformCache {
+    /// Brings the peaks up to date with `samples`, returning whether work
+    /// is still pending. Stale peaks are kept until it's done.
+    fn update(&mut self, samples: &[f32], buckets: usize) -> bool {
+        let key = SamplesKey::new(samples);
+
+        if let Some((k, b, receiver)) = &self.pending {
+            if let Ok(peaks) = receiver.try_recv() {
+                self.peaks = Some((*k, *b, peaks));
+                self.pending = None;
+            }
+        }
+
+        let current = |cached: Option<(SamplesKey, usize)>| cached == Some((key, buckets));
+        let done = current(self.peaks.as_ref().map(|(k, b, _)| (*k, *b)));
+        let started = current(self.pending.as_ref().map(|(k, b, _)| (*k, *b)));
+        if !done && !started {
+            if samples.len() <= WAVEFORM_THREAD_SAMPLES {
+                self.peaks = Some((key, buckets, peaks(samples, buckets)));
+                self.pending = None;
+            } else {
+                let samples = samples.to_vec();
+                let (sender, receiver) = channel();
+                std::thread::spawn(move || {
+                    let _ = sender.send(peaks(&samples, buckets));
+                });
+                self.pending = Some((key, buckets, receiver));
+            }
+        }
+
+        self.pending.is_some()
+    }
+}
+
+#[derive(Clone, Default)]
+struct WaveformState {
+    /// Shared, so peaks can be updated while drawing without marking the
+    /// view dirty.
+    cache: Rc<RefCell<WaveformCache>>,
+
+    /// Where a drag started and is now, in local coordinates.
+    drag: Option<(f32, f32)>,
+}
+
+/// Struct for `waveform`.
+pub struct Waveform<B> {
+    samples: B,
+    height: f32,
+    color: Color,
+    playhead: Option<usize>,
+    selection: Option<Range<usize>>,
+    on_seek: Option<Box<dyn Fn(usize)>>,
+    on_select: Option<Box<dyn Fn(Range<usize>)>>,
+}
+
+impl<B> Waveform<B> {
+    pub fn waveform_height(self, height: f32) -> Self {
+        Self { height, ..self }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Draws a line at the sample being played.
+    pub fn playhead(self, playhead: Option<usize>) -> Self {
+        Self { playhead, ..self }
+    }
+
+    /// Highlights a range of samples.
+    pub fn selection(self, selection: Option<Range<usize>>) -> Self {
+        Self { selection, ..self }
+    }
+
+    /// Calls `f` with the sample clicked on.
+    pub fn on_seek(self, f: impl Fn(usize) + 'static) -> Self {
+        Self {
+            on_seek: Some(Box::new(f)),
+            ..self
+        }
+    }
+
+    /// Calls `f` with the samples dragged across.
+    pub fn on_select(self, f: impl Fn(Range<usize>) + 'static) -> Self {
+        Self {
+            on_select: Some(Box::new(f)),
+            ..self
+        }
+    }
+
+    fn state(&self, id: ViewID, cx: &mut Context) -> State<WaveformState> {
+        cx.with_state(
+            WaveformState::default(),
+            id,
+            |state: State<WaveformState>, _cx| state,
+        )
+    }
+}
+
+impl<B> View for Waveform<B>
+where
+    B: Binding<Vec<f32>>,
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("waveform({} samples)", self.samples.with(|s| s.len()));
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, _vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let x = event.position.x.clamp(rect.min_x(), rect.max_x());
+        let state = self.state(id, cx);
+        let sample_at = |x: f32| {
+            let len = self.samples.with(|s| s.len());
+            ((x - rect.min_x()) / rect.width() * len as f32).round() as usize
+        };
+
+        match &event.kind {
+            EventKind::TouchBegin { id: touch } if rect.contains(event.position) => {
+                cx.touches[*touch] = id;
+                state.with_mut(|s| s.drag = Some((x, x)));
+            }
+            EventKind::TouchMove { id: touch } if cx.touches[*touch] == id => {
+                state.with_mut(|s| {
+                    if let Some(drag) = &mut s.drag {
+                        drag.1 = x;
+                    }
+                });
+            }
+            EventKind::TouchEnd { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                if let Some((start, end)) = state.with(|s| s.drag) {
+                    if (end - start).abs() < WAVEFORM_DRAG_DISTANCE {
+                        if let Some(f) = &self.on_seek {
+                            f(sample_at(start));
+                        }
+                    } else if let Some(f) = &self.on_select {
+                        f(sample_at(start.min(end))..sample_at(start.max(end)));
+                    }
+                }
+                state.with_mut(|s| s.drag = None);
+            }
+            EventKind::TouchCancel { id: touch } if cx.touches[*touch] == id => {
+                cx.touches[*touch] = ViewID::default();
+                state.with_mut(|s| s.drag = None);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let state = self.state(id, cx);
+        let cache = state.with(|s| s.cache.clone());
+        let buckets = rect.width().ceil().max(1.0) as usize;
+
+        let (len, pending) = self
+            .samples
+            .with(|samples| (samples.len(), cache.borrow_mut().update(samples, buckets)));
+        if pending {
+            if let Some(window) = &cx.window {
+                window.request_redraw();
+            }
+        }
+
+        let x_at = |sample: usize| rect.min_x() + sample as f32 / len.max(1) as f32 * rect.width();
+        let selection = match state.with(|s| s.drag) {
+            Some((start, end)) if (end - start).abs() >= WAVEFORM_DRAG_DISTANCE => {
+                Some((start.min(end), start.max(end)))
+            }
+            _ => self
+                .selection
+                .as_ref()
+                .map(|range| (x_at(range.start), x_at(range.end))),
+        };
+        if let Some((x0, x1)) = selection {
+            let paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
+            let selected =
+                LocalRect::new([x0, rect.min_y()].into(), [x1 - x0, rect.height()].into());
+            vger.fill_rect(selected, 0.0, paint);
+        }
+
+        // The outline of the peaks, as one path: maxima left to right, then
+        // minima back. Quads with their control points on the line are
+        // straight.
+        if let Some((_, _, peaks)) = &cache.borrow().peaks {
+            if !peaks.is_empty() {
+                let mid = rect.center().y;
+                let half = rect.height() / 2.0;
+                let step = rect.width() / peaks.len() as f32;
+                let point = |i: usize, v: f32, nudge: f32| -> LocalPoint {
+                    let y = mid + v.clamp(-1.0, 1.0) * half + nudge;
+                    [rect.min_x() + (i as f32 + 0.5) * step, y].into()
+                };
+                let mut last = point(0, peaks[0].1, 0.5);
+                vger.move_to(last);
+                let mut line_to = |p: LocalPoint, last: &mut LocalPoint| {
+                    vger.quad_to(last.lerp(p, 0.5), p);
+                    *last = p;
+                };
+                for (i, (_, hi)) in peaks.iter().enumerate().skip(1) {
+                    line_to(point(i, *hi, 0.5), &mut last);
+                }
+                for (i, (lo, _)) in peaks.iter().enumerate().rev() {
+                    line_to(point(i, *lo, -0.5), &mut last);
+                }
+                let paint = vger.color_paint(self.color);
+                vger.fill(paint);
+            }
+        }
+
+        if let Some(playhead) = self.playhead {
+            let x = x_at(playhead);
+            let paint = vger.color_paint(TEXT_COLOR);
+            vger.stroke_segment(
+                [x, rect.min_y()].into(),
+                [x, rect.max_y()].into(),
+                1.0,
+                paint,
+            );
+        }
+
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = LocalSize::new(constraints.biggest().width, self.height);
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        let rect = cx.layout.entry(id).or_default().rect;
+
+        if rect.contains(pt) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            WaveformState::default(),
+            id,
+            map,
+            |state: State<WaveformState>, _cx, map| {
+                map.insert(id, Box::new(state));
+            },
+        );
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Image));
+        Some(aid)
+    }
+}
+
+impl<B> private::Sealed for Waveform<B> {}
+
+/// Draws audio samples, from -1 to 1, as the outline of their peaks.
+/// Clicking calls `on_seek` and dragging calls `on_select`. Long buffers
+/// are summarized on a worker thread; the peaks are recomputed when the
+/// buffer moves, changes length or its samples change.
+pub fn waveform<B: Binding<Vec<f32>>>(samples: B) -> Waveform<B> {
+    Waveform {
+        samples,
+        height: WAVEFORM_HEIGHT,
+        color: AZURE_HIGHLIGHT,
+        playhead: None,
+        selection: None,
+        on_seek: None,
+        on_select: None,
+    }
+}
+
+/// Struct for `level_meter`.
+pub struct LevelMeter<B> {
+    level: B,
+    size: LocalSize,
+}
+
+impl<B> LevelMeter<B> {
+    pub fn level_meter_size(self, size: impl Into<LocalSize>) -> Self {
+        Self {
+            size: size.into(),
+            ..self
+        }
+    }
+}
+
+/// Where a level, from 0 to 1, goes along a meter, in dB.
+fn level_position(level: f32) -> f32 {
+    if level <= 0.0 {
+        return 0.0;
+    }
+    let db = 20.0 * level.log10();
+    (1.0 - db / LEVEL_FLOOR_DB).clamp(0.0, 1.0)
+}
+
+impl<B> View for LevelMeter<B>
+where
+    B: Binding<f32>,
+{
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("level_meter({})", self.level.get());
+    }
+
+    fn process(&self, _event: &Event, _id: ViewID, _cx: &mut Context, _vger: &mut dyn Renderer) {
+        // do nothing
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        let rect = cx.layout.entry(id).or_default().rect;
+        let paint = vger.color_paint(CONTROL_BACKGROUND);
+        vger.fill_rect(rect, 2.0, paint);
+
+        // Green, then yellow from -12 dB, then red from -3 dB.
+        let end = rect.min_x() + level_position(self.level.get()) * rect.width();
+        let mut x = rect.min_x();
+        for (to, color) in [
+            (-12.0, GREEN_HIGHLIGHT),
+            (-3.0, LEVEL_WARNING),
+            (0.0, LEVEL_CLIPPING),
+        ] {
+            let to = (rect.min_x() + (1.0 - to / LEVEL_FLOOR_DB) * rect.width()).min(end);
+            if to > x {
+                let paint = vger.color_paint(color);
+                let segment =
+                    LocalRect::new([x, rect.min_y()].into(), [to - x, rect.height()].into());
+                vger.fill_rect(segment, 0.0, paint);
+                x = to;
+            }
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        _constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), self.size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        self.size
+    }
+
+    fn hittest(
+        &self,
+        _id: ViewID,
+        _pt: LocalPoint,
+        _cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        None
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, _id: ViewID, _cx: &mut Context, _map: &mut StateMap) {}
+
+    fn access(
+        &self,
+        id: ViewID,
+        _cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = id.access_id();
+        nodes.push(accesskit::Node::new(aid, accesskit::Role::Meter));
+        Some(aid)
+    }
+}
+
+impl<B> private::Sealed for LevelMeter<B> {}
+
+/// A bar showing an audio level, from 0 to 1, on a dB scale from -60 dB.
+/// It's `LEVEL_METER_SIZE` unless changed with `level_meter_size`.
+pub fn level_meter<B: Binding<f32>>(level: B) -> LevelMeter<B> {
+    LevelMeter {
+        level,
+        size: LEVEL_METER_SIZE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peaks() {
+        let samples = [0.0, 1.0, -1.0, 0.5, 0.25, -0.25];
+        assert_eq!(peaks(&samples, 2), vec![(-1.0, 1.0), (-0.25, 0.5)]);
+        assert_eq!(peaks(&samples, 6).len(), 6);
+        assert_eq!(peaks(&samples[..1], 3), vec![(0.0, 0.0); 3]);
+        assert!(peaks(&[], 3).is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+//! Runs rui in a browser, drawing to a canvas with WebGPU.
+
+use crate::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlCanvasElement, KeyboardEvent, PointerEvent};
+
+/// The canvas rui draws into, if the page provides one. Otherwise a
+/// canvas filling the page is added.
+const CANVAS_ID: &str = "rui";
+
+struct WebApp<V> {
+    view: V,
+    cx: Context,
+    vger: VgerRenderer,
+    canvas: HtmlCanvasElement,
+    surface: wgpu::Surface,
+    device: Arc<wgpu::Device>,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+
+    /// Browser pointer ids of the touches in progress.
+    touch_slots: [Option<u64>; MAX_TOUCHES],
+    needs_redraw: bool,
+}
+
+impl<V: View> WebApp<V> {
+    fn scale(&self) -> f32 {
+        web_sys::window().unwrap().device_pixel_ratio() as f32
+    }
+
+    /// Converts a position in CSS pixels from the top left of the canvas.
+    fn position(&self, x: i32, y: i32) -> LocalPoint {
+        [x as f32, (self.canvas.client_height() - y) as f32].into()
+    }
+
+    fn process(&mut self, kind: EventKind, position: LocalPoint) {
+        let event = Event { kind, position };
+        self.cx.process_root(&self.view, &event, &mut self.vger);
+    }
+
+    fn pointer(&mut self, e: &PointerEvent, phase: &str) {
+        // The mouse is touch 0, like on the desktop.
+        let slot = if e.pointer_type() == "mouse" {
+            Some(0)
+        } else {
+            let begin = phase == "pointerdown";
+            let slot = touch_slot(&mut self.touch_slots, e.pointer_id() as u64, begin);
+            if phase == "pointerup" || phase == "pointercancel" {
+                if let Some(slot) = slot {
+                    self.touch_slots[slot] = None;
+                }
+            }
+            slot
+        };
+
+        if let Some(id) = slot {
+            let kind = match phase {
+                "pointerdown" => EventKind::TouchBegin { id },
+                "pointermove" => EventKind::TouchMove { id },
+                "pointerup" => EventKind::TouchEnd { id },
+                _ => EventKind::TouchCancel { id },
+            };
+            let position = self.position(e.offset_x(), e.offset_y());
+            self.process(kind, position);
+        }
+    }
+
+    fn key(&mut self, e: &KeyboardEvent) {
+        let mut mods = ModifiersState::default();
+        if e.shift_key() {
+            mods = mods | ModifiersState::SHIFT;
+        }
+        if e.ctrl_key() {
+            mods = mods | ModifiersState::CONTROL;
+        }
+        if e.alt_key() {
+            mods = mods | ModifiersState::ALT;
+        }
+        if e.meta_key() {
+            mods = mods | ModifiersState::SUPER;
+        }
+        self.process(
+            EventKind::Key(key_press(&e.key()), mods),
+            LocalPoint::zero(),
+        );
+    }
+
+    /// Resizes the surface to match the canvas, and redraws if anything changed.
+    fn frame(&mut self) {
+        let scale = self.scale();
+        let width = self.canvas.client_width().max(1) as f32;
+        let height = self.canvas.client_height().max(1) as f32;
+
+        let physical_width = (width * scale).round() as u32;
+        let physical_height = (height * scale).round() as u32;
+        if physical_width != self.config.width || physical_height != self.config.height {
+            self.canvas.set_width(physical_width);
+            self.canvas.set_height(physical_height);
+            self.config.width = physical_width;
+            self.config.height = physical_height;
+            self.surface.configure(&self.device, &self.config);
+            self.needs_redraw = true;
+        }
+
+        if self.cx.dirty.lock().unwrap().dirty {
+            // Clean up state.
+            let mut new_map = StateMap::new();
+            self.view.gc(self.cx.root_id, &mut self.cx, &mut new_map);
+            self.cx.state_map = new_map;
+            self.cx.retain_tasks();
+            self.cx.layout.collect();
+
+            self.cx.dirty.lock().unwrap().dirty = false;
+            self.needs_redraw = true;
+        }
+
+        if !self.needs_redraw {
+            return;
+        }
+        self.needs_redraw = false;
+
+        let frame = match self.surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(_) => {
+                self.surface.configure(&self.device, &self.config);
+                self.surface
+                    .get_current_texture()
+                    .expect("Failed to acquire next surface texture!")
+            }
+        };
+
+        let vger = &mut self.vger;
+        let view = &self.view;
+        vger.begin(width, height, scale);
+        self.cx.with_env(SafeAreaInsets::default(), |cx| {
+            // Solid shapes are drawn grouped by paint.
+            let vger = &mut BatchingRenderer::new(vger);
+            cx.layout_root(view, [width, height].into(), vger);
+            view.draw(cx.root_id, cx, vger);
+        });
+
+        let texture_view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let desc = wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: &texture_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        };
+
+        self.vger.encode(&self.device, &desc, &self.queue);
+        frame.present();
+    }
+}
+
+/// Translates a DOM key name. Named keys like "ArrowLeft" share their
+/// names with the keys rui records.
+fn key_press(key: &str) -> KeyPress {
+    if key == " " {
+        return KeyPress::Space;
+    }
+    for (name, named_key) in NAMED_KEYS {
+        if *name == key {
+            return named_key.clone();
+        }
+    }
+    if key.chars().count() == 1 {
+        // Keys live for the rest of the program, like the ones tao hands us.
+        KeyPress::Character(Box::leak(key.to_string().into_boxed_str()))
+    } else {
+        KeyPress::Unidentified
+    }
+}
+
+fn find_or_create_canvas() -> HtmlCanvasElement {
+    let document = web_sys::window().unwrap().document().unwrap();
+    if let Some(element) = document.get_element_by_id(CANVAS_ID) {
+        return element
+            .dyn_into()
+            .expect("element with id \"rui\" should be a canvas");
+    }
+
+    let canvas: HtmlCanvasElement = document
+        .create_element("canvas")
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    canvas.set_id(CANVAS_ID);
+    canvas
+        .style()
+        .set_css_text("display: block; width: 100vw; height: 100vh; touch-action: none");
+    document
+        .body()
+        .expect("document should have a body")
+        .append_child(&canvas)
+        .unwrap();
+    canvas
+}
+
+fn request_animation_frame(f: &Closure<dyn FnMut()>) {
+    web_sys::window()
+        .unwrap()
+        .request_animation_frame(f.as_ref().unchecked_ref())
+        .unwrap();
+}
+
+async fn run(view: impl View + 'static) {
+    console_error_panic_hook::set_once();
+
+    let canvas = find_or_create_canvas();
+
+    let instance = wgpu::Instance::new(wgpu::Backends::all());
+    let surface = unsafe { instance.create_surface_from_canvas(&canvas) };
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            force_fallback_adapter: false,
+            compatible_surface: Some(&surface),
+        })
+        .await
+        .expect("No suitable GPU adapters found!");
+
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                features: wgpu::Features::default(),
+                limits: wgpu::Limits::downlevel_webgl2_defaults(),
+            },
+            None,
+        )
+        .await
+        .expect("Unable to find a suitable GPU adapter!");
+
+    let config = wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format: surface.get_preferred_format(&adapter).unwrap(),
+        width: 1,
+        height: 1,
+        present_mode: wgpu::PresentMode::Fifo,
+    };
+    surface.configure(&device, &config);
+
+    let device = Arc::new(device);
+    let mut vger = VgerRenderer::new(VGER::new(&device, config.format));
+    vger.enable_layers(device.clone(), config.format);
+
+    let app = Rc::new(RefCell::new(WebApp {
+        view,
+        cx: Context::headless(),
+        vger,
+        canvas: canvas.clone(),
+        surface,
+        device,
+        queue,
+        config,
+        touch_slots: [None; MAX_TOUCHES],
+        needs_redraw: true,
+    }));
+
+    for &phase in &["pointerdown", "pointermove", "pointerup", "pointercancel"] {
+        let app = app.clone();
+        let closure = Closure::wrap(Box::new(move |e: PointerEvent| {
+            app.borrow_mut().pointer(&e, phase);
+        }) as Box<dyn FnMut(_)>);
+        canvas
+            .add_event_listener_with_callback(phase, closure.as_ref().unchecked_ref())
+            .unwrap();
+        closure.forget();
+    }
+
+    {
+        let app = app.clone();
+        let closure = Closure::wrap(Box::new(move |e: KeyboardEvent| {
+            app.borrow_mut().key(&e);
+        }) as Box<dyn FnMut(_)>);
+        web_sys::window()
+            .unwrap()
+            .add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())
+            .unwrap();
+        closure.forget();
+    }
+
+    // Check for changes once per display frame.
+    let f: Rc<RefCell<Option<Closure<dyn FnMut()>>>> = Rc::new(RefCell::new(None));
+    let g = f.clone();
+    *g.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        app.borrow_mut().frame();
+        request_animation_frame(f.borrow().as_ref().unwrap());
+    }) as Box<dyn FnMut()>));
+    request_animation_frame(g.borrow().as_ref().unwrap());
+}
+
+/// Call this function to run your UI. In the browser, rui draws into the
+/// canvas with id "rui", or adds a canvas filling the page.
+pub fn rui(view: impl View + 'static) {
+    wasm_bindgen_futures::spawn_local(run(view));
+}
@@ -0,0 +1,306 @@
+use crate::*;
+use std::marker::PhantomData;
+
+/// A control written outside rui, turned into a view with `widget`. `View`
+/// can't be implemented outside rui, so crates publishing their own
+/// controls implement this instead, and only rely on what's here.
+///
+/// Each frame a widget goes through:
+///
+/// 1. `layout`, to choose its size within the constraints of the view
+///    around it. Stacks may ask for `intrinsic_size` first, and layout can
+///    happen more than once a frame.
+/// 2. `event`, for each event since the last frame, with positions
+///    relative to the widget's bounds.
+/// 3. `draw`, within its bounds.
+///
+/// The widget's `State` is created from its default the first time the
+/// widget appears, kept from frame to frame, and dropped when the widget
+/// goes away. Changing it redraws the app.
+pub trait Widget: 'static {
+    /// Per-widget state, kept for you. Use `()` for none.
+    type State: Clone + Default + 'static;
+
+    /// The name printed for the widget when debugging.
+    fn name(&self) -> &str {
+        "widget"
+    }
+
+    /// Chooses the widget's size within `constraints`. Takes as much as
+    /// it can by default.
+    fn layout(&self, constraints: Constraints, _cx: &mut WidgetCx<Self::State>) -> LocalSize {
+        constraints.biggest()
+    }
+
+    /// The size the widget would be whatever it was offered, if it has
+    /// one, like the natural size of text.
+    fn intrinsic_size(&self, _cx: &mut WidgetCx<Self::State>) -> Option<LocalSize> {
+        None
+    }
+
+    /// Draws the widget within `cx.rect()`. Call `cx.request_frame()` to
+    /// be drawn again next frame, while animating.
+    fn draw(&self, cx: &mut WidgetCx<Self::State>, vger: &mut dyn Renderer);
+
+    /// Responds to an event. Call `cx.capture_touch` on a `TouchBegin` to
+    /// keep getting the touch's moves after it leaves the widget, and
+    /// `cx.consume_event` so views after this one don't see the event.
+    fn event(&self, _event: &Event, _cx: &mut WidgetCx<Self::State>) {}
+
+    /// Whether the widget takes touches at `pt`. Anywhere in its bounds by
+    /// default.
+    fn hittest(&self, pt: LocalPoint, cx: &mut WidgetCx<Self::State>) -> bool {
+        cx.rect().contains(pt)
+    }
+
+    /// What the widget is to accessibility tools, like a button or slider.
+    /// Widgets without a role are left out of the accessibility tree.
+    fn role(&self) -> Option<accesskit::Role> {
+        None
+    }
+
+    /// Adds to the widget's accessibility node, like its name or value.
+    fn access(&self, _node: &mut accesskit::Node, _cx: &mut WidgetCx<Self::State>) {}
+}
+
+/// What a `Widget` can see and do while it's laid out, drawn or sent
+/// events.
+pub struct WidgetCx<'a, S> {
+    cx: &'a mut Context,
+    id: ViewID,
+    phantom: PhantomData<S>,
+}
+
+impl<'a, S> WidgetCx<'a, S>
+where
+    S: Clone + Default + 'static,
+{
+    /// The widget's id, which stays the same while it's in the same place
+    /// in the view tree.
+    pub fn id(&self) -> ViewID {
+        self.id
+    }
+
+    /// The widget's bounds, from its last layout.
+    pub fn rect(&mut self) -> LocalRect {
+        self.cx.layout.entry(self.id).or_default().rect
+    }
+
+    /// The widget's state.
+    pub fn state(&mut self) -> State<S> {
+        self.cx.get_state(self.id, S::default)
+    }
+
+    /// Gets the environment value of type `E`, if a view around the widget
+    /// set one.
+    pub fn env<E: Clone + 'static>(&self) -> Option<E> {
+        self.cx.get_env::<E>()
+    }
+
+    /// Whether the widget responds to input. False inside a `disabled`
+    /// view.
+    pub fn is_enabled(&self) -> bool {
+        self.cx.is_enabled()
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.cx.focused_id == Some(self.id)
+    }
+
+    /// Takes the keyboard focus, so the widget gets key events.
+    pub fn focus(&mut self) {
+        self.cx.focused_id = Some(self.id);
+    }
+
+    /// Gives up the keyboard focus, if the widget has it.
+    pub fn unfocus(&mut self) {
+        if self.is_focused() {
+            self.cx.focused_id = None;
+        }
+    }
+
+    /// Sends the rest of `touch`'s events to the widget, until
+    /// `release_touch`.
+    pub fn capture_touch(&mut self, touch: usize) {
+        self.cx.touches[touch] = self.id;
+    }
+
+    pub fn release_touch(&mut self, touch: usize) {
+        if self.has_touch(touch) {
+            self.cx.touches[touch] = ViewID::default();
+        }
+    }
+
+    /// Whether the widget captured `touch`.
+    pub fn has_touch(&self, touch: usize) -> bool {
+        self.cx.touches[touch] == self.id
+    }
+
+    /// Stops the event being processed going to the views after this one.
+    pub fn consume_event(&mut self) {
+        self.cx.consume_event()
+    }
+
+    /// Draws another frame after this one, for animation.
+    pub fn request_frame(&self) {
+        if let Some(window) = &self.cx.window {
+            window.request_redraw();
+        }
+    }
+}
+
+/// Struct for `widget`.
+pub struct WidgetView<W> {
+    widget: W,
+}
+
+impl<W: Widget> WidgetView<W> {
+    fn cx<'a>(&self, id: ViewID, cx: &'a mut Context) -> WidgetCx<'a, W::State> {
+        WidgetCx {
+            cx,
+            id,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<W: Widget> View for WidgetView<W> {
+    fn print(&self, _id: ViewID, _cx: &mut Context) {
+        println!("{}", self.widget.name());
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, _vger: &mut dyn Renderer) {
+        self.widget.event(event, &mut self.cx(id, cx))
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.widget.draw(&mut self.cx(id, cx), vger);
+        let rect = cx.layout.entry(id).or_default().rect;
+        inspector_highlight(cx, id, rect, vger);
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        let size = self.widget.layout(constraints, &mut self.cx(id, cx));
+        cx.layout.insert(
+            id,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        if self.widget.hittest(pt, &mut self.cx(id, cx)) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, _id: ViewID, _cx: &mut Context, _cmds: &mut Vec<CommandInfo>) {}
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        let state = self.cx(id, cx).state();
+        map.insert(id, Box::new(state));
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        let role = self.widget.role()?;
+        let aid = id.access_id();
+        let mut node = accesskit::Node::new(aid, role);
+        self.widget.access(&mut node, &mut self.cx(id, cx));
+        nodes.push(node);
+        Some(aid)
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        _vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.widget.intrinsic_size(&mut self.cx(id, cx))
+    }
+}
+
+impl<W> private::Sealed for WidgetView<W> {}
+
+/// Makes a view from a `Widget`.
+pub fn widget<W: Widget>(widget: W) -> WidgetView<W> {
+    WidgetView { widget }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the taps on it.
+    struct Clicker;
+
+    impl Widget for Clicker {
+        type State = usize;
+
+        fn layout(&self, _constraints: Constraints, _cx: &mut WidgetCx<usize>) -> LocalSize {
+            [40.0, 20.0].into()
+        }
+
+        fn draw(&self, cx: &mut WidgetCx<usize>, vger: &mut dyn Renderer) {
+            let rect = cx.rect();
+            vger.fill_rect(rect, 4.0, Paint::Color(BUTTON_BACKGROUND_COLOR));
+        }
+
+        fn event(&self, event: &Event, cx: &mut WidgetCx<usize>) {
+            if let EventKind::TouchBegin { .. } = event.kind {
+                if cx.rect().contains(event.position) {
+                    cx.state().with_mut(|n| *n += 1);
+                    cx.consume_event();
+                }
+            }
+        }
+
+        fn role(&self) -> Option<accesskit::Role> {
+            Some(accesskit::Role::Button)
+        }
+    }
+
+    #[test]
+    fn test_widget() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let ui = widget(Clicker);
+        assert_eq!(cx.layout_root(&ui, size, &mut vger), [40.0, 20.0].into());
+
+        for position in [[10.0, 10.0], [60.0, 10.0]].iter() {
+            let event = Event {
+                kind: EventKind::TouchBegin { id: 0 },
+                position: (*position).into(),
+            };
+            cx.process_root(&ui, &event, &mut vger);
+        }
+        assert_eq!(cx.get_state(cx.root_id, || 0usize).get(), 1);
+
+        let mut nodes = vec![];
+        assert!(ui.access(cx.root_id, &mut cx, &mut nodes).is_some());
+        assert_eq!(nodes[0].role, accesskit::Role::Button);
+    }
+}
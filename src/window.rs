@@ -27,20 +27,28 @@ where
         println!(".window_title()");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.process(&event, id.child(&0), cx, vger);
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger);
         if cx.window_title != self.title {
             cx.window_title = self.title.clone();
-            cx.window.set_title(&self.title)
+            if let Some(window) = &cx.window {
+                window.set_title(&self.title)
+            }
         }
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.child.layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -48,7 +56,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -69,6 +77,10 @@ where
     ) -> Option<accesskit::NodeId> {
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
 }
 
 impl<V> private::Sealed for TitleView<V> {}
@@ -96,18 +108,25 @@ where
         println!(".fullscreen()");
     }
 
-    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.process(&event, id.child(&0), cx, vger);
     }
 
-    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut VGER) {
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
         self.child.draw(id.child(&0), cx, vger);
-        cx.window
-            .set_fullscreen(Some(tao::window::Fullscreen::Borderless(None)))
+        if let Some(window) = &cx.window {
+            window.set_fullscreen(Some(tao::window::Fullscreen::Borderless(None)))
+        }
     }
 
-    fn layout(&self, id: ViewID, sz: LocalSize, cx: &mut Context, vger: &mut VGER) -> LocalSize {
-        self.child.layout(id.child(&0), sz, cx, vger)
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
     }
 
     fn hittest(
@@ -115,7 +134,7 @@ where
         id: ViewID,
         pt: LocalPoint,
         cx: &mut Context,
-        vger: &mut VGER,
+        vger: &mut dyn Renderer,
     ) -> Option<ViewID> {
         self.child.hittest(id.child(&0), pt, cx, vger)
     }
@@ -136,6 +155,481 @@ where
     ) -> Option<accesskit::NodeId> {
         self.child.access(id.child(&0), cx, nodes)
     }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
 }
 
 impl<V> private::Sealed for FullscreenView<V> {}
+
+/// Struct for the `window_decorations` modifier.
+pub struct DecorationsView<V> {
+    child: V,
+    decorations: bool,
+}
+
+impl<V> DecorationsView<V>
+where
+    V: View,
+{
+    pub fn new(v: V, decorations: bool) -> Self {
+        Self {
+            child: v,
+            decorations,
+        }
+    }
+}
+
+impl<V> View for DecorationsView<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".window_decorations({:?})", self.decorations);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger);
+        if cx.window_decorations != self.decorations {
+            cx.window_decorations = self.decorations;
+            if let Some(window) = &cx.window {
+                window.set_decorations(self.decorations)
+            }
+        }
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V> private::Sealed for DecorationsView<V> {}
+
+/// Struct for the `window_drag_region` modifier.
+pub struct DragRegion<V> {
+    child: V,
+}
+
+impl<V> DragRegion<V>
+where
+    V: View,
+{
+    pub fn new(v: V) -> Self {
+        Self { child: v }
+    }
+}
+
+impl<V> View for DragRegion<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".window_drag_region()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger);
+
+        if let EventKind::TouchBegin { id: touch } = &event.kind {
+            // Controls in the region, like window buttons, get the touch.
+            let claimed = cx.touches[*touch] != ViewID::default();
+            if !claimed
+                && self
+                    .child
+                    .hittest(id.child(&0), event.position, cx, vger)
+                    .is_some()
+            {
+                cx.with_state(
+                    None,
+                    id,
+                    |last_click: State<Option<std::time::Instant>>, cx| {
+                        let now = std::time::Instant::now();
+                        let double_click = last_click
+                            .get()
+                            .map(|t| now - t < cx.double_click_interval)
+                            .unwrap_or(false);
+
+                        if let Some(window) = &cx.window {
+                            if double_click {
+                                window.set_maximized(!window.is_maximized());
+                            } else if let Err(err) = window.drag_window() {
+                                println!("error dragging window: {:?}", err);
+                            }
+                        }
+                        last_click.set(if double_click { None } else { Some(now) });
+                    },
+                );
+            }
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        cx.with_state_aux(
+            None,
+            id,
+            map,
+            |last_click: State<Option<std::time::Instant>>, _cx, map| {
+                map.insert(id, Box::new(last_click));
+            },
+        );
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V> private::Sealed for DragRegion<V> {}
+
+/// Actions for the `window_button` modifier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowButton {
+    Close,
+    Minimize,
+    Maximize,
+}
+
+/// Struct for the `window_button` modifier.
+pub struct WindowButtonView<V> {
+    child: V,
+    button: WindowButton,
+}
+
+impl<V> WindowButtonView<V>
+where
+    V: View,
+{
+    pub fn new(v: V, button: WindowButton) -> Self {
+        Self { child: v, button }
+    }
+}
+
+impl<V> View for WindowButtonView<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".window_button({:?})", self.button);
+    }
+
+    fn process(&self, event: &Event, vid: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        match &event.kind {
+            EventKind::TouchBegin { id }
+                if self.hittest(vid, event.position, cx, vger).is_some() =>
+            {
+                cx.touches[*id] = vid;
+            }
+            EventKind::TouchEnd { id } if cx.touches[*id] == vid => {
+                cx.touches[*id] = ViewID::default();
+                match self.button {
+                    WindowButton::Close => cx.close_requested = true,
+                    WindowButton::Minimize => {
+                        if let Some(window) = &cx.window {
+                            window.set_minimized(true)
+                        }
+                    }
+                    WindowButton::Maximize => {
+                        if let Some(window) = &cx.window {
+                            window.set_maximized(!window.is_maximized())
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V> private::Sealed for WindowButtonView<V> {}
+
+/// Struct for the `on_window_event` modifier.
+pub struct OnWindowEvent<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> OnWindowEvent<V, F>
+where
+    V: View,
+    F: Fn(&WindowEventKind) + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self { child: v, func: f }
+    }
+}
+
+impl<V, F> View for OnWindowEvent<V, F>
+where
+    V: View,
+    F: Fn(&WindowEventKind) + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".on_window_event()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::Window(kind) = &event.kind {
+            (self.func)(kind);
+        }
+        self.child.process(event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, F> private::Sealed for OnWindowEvent<V, F> {}
+
+/// Struct for the `on_close_requested` modifier.
+pub struct OnCloseRequested<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> OnCloseRequested<V, F>
+where
+    V: View,
+    F: Fn() -> bool + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self { child: v, func: f }
+    }
+}
+
+impl<V, F> View for OnCloseRequested<V, F>
+where
+    V: View,
+    F: Fn() -> bool + 'static,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        (self.child).print(id.child(&0), cx);
+        println!(".on_close_requested()");
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        if let EventKind::Window(WindowEventKind::CloseRequested) = &event.kind {
+            if !(self.func)() {
+                cx.close_vetoed = true;
+            }
+        }
+        self.child.process(event, id.child(&0), cx, vger);
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+}
+
+impl<V, F> private::Sealed for OnCloseRequested<V, F> {}
@@ -0,0 +1,117 @@
+use crate::*;
+
+pub const WIZARD_TITLE_SIZE: u32 = 14;
+
+/// A page of a `wizard`.
+pub struct WizardStep {
+    title: String,
+    view: AnyView,
+    valid: bool,
+}
+
+impl WizardStep {
+    /// Whether the user can go on to the next step. Steps are valid
+    /// unless set otherwise.
+    pub fn valid(self, valid: bool) -> Self {
+        Self { valid, ..self }
+    }
+}
+
+/// A step of a `wizard`, showing `view`.
+pub fn wizard_step(title: &str, view: impl View + 'static) -> WizardStep {
+    WizardStep {
+        title: title.into(),
+        view: any_view(view),
+        valid: true,
+    }
+}
+
+/// Leads the user through `steps` one at a time, with the index of the
+/// step showing in `current`. The titles of the steps along the top show
+/// progress. Next is disabled until the step is valid, and on the last
+/// step it becomes Finish, which calls `on_finish`. Steps keep their
+/// state when they're hidden, so going back shows them as they were.
+pub fn wizard<F: Fn() + 'static>(
+    steps: Vec<WizardStep>,
+    current: impl Binding<usize>,
+    on_finish: F,
+) -> impl View {
+    let n = steps.len();
+    let i = current.get().min(n.saturating_sub(1));
+    let valid = steps.get(i).map(|s| s.valid).unwrap_or(false);
+    let last = i + 1 >= n;
+
+    let mut titles = vec![];
+    let mut views = vec![];
+    for (j, step) in steps.into_iter().enumerate() {
+        let color = if j <= i { AZURE_HIGHLIGHT } else { MEDIUM_GRAY };
+        titles.push(
+            text(&format!("{}. {}", j + 1, step.title))
+                .font_size(WIZARD_TITLE_SIZE)
+                .color(color),
+        );
+        views.push(step.view);
+    }
+
+    let back = current.clone();
+    let next = current.clone();
+    vstack((
+        hstack(titles),
+        pages(views, current).swipe(false).indicator(false),
+        hstack((
+            button(text("Back"), move || back.set(i - 1)).disabled(i == 0),
+            button(text(if last { "Finish" } else { "Next" }), move || {
+                if last {
+                    on_finish()
+                } else {
+                    next.set(i + 1)
+                }
+            })
+            .disabled(!valid),
+        )),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesskit::Role;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+
+    /// Taps the button labelled `label` in a two step wizard, and returns
+    /// the step shown after, and whether the wizard finished.
+    fn tap(start: usize, valid: bool, label: &str) -> (usize, bool) {
+        let size = LocalSize::new(300.0, 200.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let current = State::new(start, Arc::new(Mutex::new(Dirty::new(None))));
+        let finished = Rc::new(Cell::new(false));
+        let f = finished.clone();
+        let ui = wizard(
+            vec![
+                wizard_step("One", text("first")).valid(valid),
+                wizard_step("Two", text("second")).valid(valid),
+            ],
+            current.clone(),
+            move || f.set(true),
+        );
+        cx.layout_root(&ui, size, &mut vger);
+        let query = cx.query(&ui, &mut vger);
+        let button = query.by_role(Role::Button).by_label(label);
+        let rect = button.nth(0).unwrap().rect.unwrap();
+        cx.tap_at(&ui, rect.center().cast_unit(), &mut vger);
+        (current.get(), finished.get())
+    }
+
+    #[test]
+    fn test_wizard_buttons() {
+        assert_eq!(tap(0, true, "Next"), (1, false));
+        assert_eq!(tap(0, false, "Next"), (0, false));
+        assert_eq!(tap(1, true, "Back"), (0, false));
+        assert_eq!(tap(0, true, "Back"), (0, false));
+        assert_eq!(tap(1, true, "Finish"), (1, true));
+        assert_eq!(tap(1, false, "Finish"), (1, false));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::*;
+
+/// Struct for the `z_index` modifier.
+pub struct ZIndex<V> {
+    child: V,
+    index: i32,
+}
+
+impl<V> ZIndex<V>
+where
+    V: View,
+{
+    pub fn new(child: V, index: i32) -> Self {
+        Self { child, index }
+    }
+}
+
+impl<V> View for ZIndex<V>
+where
+    V: View,
+{
+    fn print(&self, id: ViewID, cx: &mut Context) {
+        self.child.print(id.child(&0), cx);
+        println!(".z_index({})", self.index);
+    }
+
+    fn process(&self, event: &Event, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.process(event, id.child(&0), cx, vger)
+    }
+
+    fn draw(&self, id: ViewID, cx: &mut Context, vger: &mut dyn Renderer) {
+        self.child.draw(id.child(&0), cx, vger)
+    }
+
+    fn layout(
+        &self,
+        id: ViewID,
+        constraints: Constraints,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> LocalSize {
+        cx.z_indices.insert(id, self.index);
+        self.child.layout(id.child(&0), constraints, cx, vger)
+    }
+
+    fn hittest(
+        &self,
+        id: ViewID,
+        pt: LocalPoint,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<ViewID> {
+        self.child.hittest(id.child(&0), pt, cx, vger)
+    }
+
+    fn commands(&self, id: ViewID, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        self.child.commands(id.child(&0), cx, cmds)
+    }
+
+    fn gc(&self, id: ViewID, cx: &mut Context, map: &mut StateMap) {
+        self.child.gc(id.child(&0), cx, map)
+    }
+
+    fn access(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        nodes: &mut Vec<accesskit::Node>,
+    ) -> Option<accesskit::NodeId> {
+        self.child.access(id.child(&0), cx, nodes)
+    }
+
+    fn priority(&self) -> f32 {
+        self.child.priority()
+    }
+
+    fn intrinsic_size(
+        &self,
+        id: ViewID,
+        cx: &mut Context,
+        vger: &mut dyn Renderer,
+    ) -> Option<LocalSize> {
+        self.child.intrinsic_size(id.child(&0), cx, vger)
+    }
+}
+
+impl<V> private::Sealed for ZIndex<V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_z_index() {
+        let size = LocalSize::new(100.0, 100.0);
+        let mut cx = Context::headless();
+        let mut vger = SvgRenderer::new(size);
+        let tapped = Rc::new(Cell::new(""));
+        let tap = |name| {
+            let tapped = tapped.clone();
+            move || tapped.set(name)
+        };
+
+        // The first view is raised above the others.
+        let ui = zstack((
+            rectangle().tap(tap("raised")).z_index(1),
+            rectangle().tap(tap("middle")),
+            rectangle().tap(tap("top")),
+        ));
+        cx.layout_root(&ui, size, &mut vger);
+        let center = LocalPoint::new(50.0, 50.0);
+        assert_eq!(
+            ui.hittest(cx.root_id, center, &mut cx, &mut vger),
+            Some(cx.root_id.child(&0).child(&0).child(&0))
+        );
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        assert_eq!(tapped.get(), "raised");
+
+        // With the same index, the later view stays on top.
+        let ui = zstack((
+            rectangle().tap(tap("raised")).z_index(1),
+            rectangle().tap(tap("also raised")).z_index(1),
+            rectangle().tap(tap("top")),
+        ));
+        cx.layout_root(&ui, size, &mut vger);
+        cx.tap_at(&ui, [50.0, 50.0].into(), &mut vger);
+        assert_eq!(tapped.get(), "also raised");
+    }
+}